@@ -0,0 +1,350 @@
+//! Shared ownership and interior mutability primitives used by the computational graph.
+//!
+//! By default these are the single-threaded [`std::rc`] and [`std::cell`] types. With the `sync`
+//! feature enabled they are replaced by drop-in, thread-safe counterparts: [`Rc`] becomes
+//! [`std::sync::Arc`] and [`RefCell`] becomes a wrapper of [`std::sync::RwLock`], so that
+//! variables, and everything holding them, are `Send` and `Sync`.
+//!
+//! Just like [`std::cell::RefCell`], the thread-safe [`RefCell`] panics when a borrow conflicts
+//! with an outstanding one, whether the latter is held by the same thread or by another. Variables
+//! can thus be shared and read from several threads, but a node must not be evaluated by two
+//! threads at the same time.
+
+#[cfg(not(feature = "sync"))]
+pub use std::{
+    cell::{Cell, Ref, RefCell, RefMut},
+    rc::{Rc, Weak},
+};
+
+#[cfg(feature = "sync")]
+pub use self::sync::{Cell, Ref, RefCell, RefMut};
+
+#[cfg(feature = "sync")]
+pub use std::sync::{Arc as Rc, Weak};
+
+/// Types that can be stored in the computational graph.
+///
+/// This is implemented by every type and, with the `sync` feature enabled, requires `Send` and
+/// `Sync`.
+#[cfg(not(feature = "sync"))]
+pub trait MaybeSync {}
+
+#[cfg(not(feature = "sync"))]
+impl<T: ?Sized> MaybeSync for T {}
+
+/// Types that can be stored in the computational graph.
+///
+/// This is implemented by every type and, with the `sync` feature enabled, requires `Send` and
+/// `Sync`.
+#[cfg(feature = "sync")]
+pub trait MaybeSync: Send + Sync {}
+
+#[cfg(feature = "sync")]
+impl<T: ?Sized + Send + Sync> MaybeSync for T {}
+
+#[cfg(feature = "sync")]
+mod sync {
+    use std::{
+        cell::UnsafeCell,
+        fmt::{self, Debug, Display},
+        mem,
+        ops::{Deref, DerefMut},
+        ptr::NonNull,
+        sync::{PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard, TryLockError},
+    };
+
+    /// A thread-safe mutable memory location with dynamically checked borrow rules.
+    ///
+    /// Any number of shared borrows or a single mutable one can be active at the same time, a
+    /// borrow conflicting with the active ones panics.
+    #[derive(Default)]
+    pub struct RefCell<T: ?Sized> {
+        lock: RwLock<()>,
+        value: UnsafeCell<T>,
+    }
+
+    // The lock grants shared access to the value to readers only and exclusive access to a
+    // single writer.
+    unsafe impl<T: ?Sized + Send + Sync> Sync for RefCell<T> {}
+
+    impl<T> RefCell<T> {
+        /// Creates a new cell containing `value`.
+        pub const fn new(value: T) -> Self {
+            Self {
+                lock: RwLock::new(()),
+                value: UnsafeCell::new(value),
+            }
+        }
+
+        /// Consumes the cell, returning the wrapped value.
+        pub fn into_inner(self) -> T {
+            self.value.into_inner()
+        }
+
+        /// Replaces the wrapped value with `value`, returning the old one.
+        ///
+        /// # Panics
+        ///
+        /// If the value is currently borrowed.
+        pub fn replace(&self, value: T) -> T {
+            mem::replace(&mut *self.borrow_mut(), value)
+        }
+
+        /// Takes the wrapped value, leaving `Default::default()` in its place.
+        ///
+        /// # Panics
+        ///
+        /// If the value is currently borrowed.
+        pub fn take(&self) -> T
+        where
+            T: Default,
+        {
+            self.replace(T::default())
+        }
+    }
+
+    impl<T: ?Sized> RefCell<T> {
+        /// Immutably borrows the wrapped value.
+        ///
+        /// # Panics
+        ///
+        /// If the value is currently mutably borrowed.
+        pub fn borrow(&self) -> Ref<'_, T> {
+            let guard = match self.lock.try_read() {
+                Ok(guard) => guard,
+                // A panic while the value was borrowed is reported where it happened.
+                Err(TryLockError::Poisoned(error)) => error.into_inner(),
+                Err(TryLockError::WouldBlock) => panic!("already mutably borrowed"),
+            };
+
+            Ref {
+                value: unsafe { NonNull::new_unchecked(self.value.get()) },
+                _guard: guard,
+            }
+        }
+
+        /// Mutably borrows the wrapped value.
+        ///
+        /// # Panics
+        ///
+        /// If the value is currently borrowed.
+        pub fn borrow_mut(&self) -> RefMut<'_, T> {
+            let guard = match self.lock.try_write() {
+                Ok(guard) => guard,
+                Err(TryLockError::Poisoned(error)) => error.into_inner(),
+                Err(TryLockError::WouldBlock) => panic!("already borrowed"),
+            };
+
+            RefMut {
+                value: unsafe { NonNull::new_unchecked(self.value.get()) },
+                _guard: guard,
+            }
+        }
+
+        /// Returns a mutable reference to the wrapped value.
+        pub fn get_mut(&mut self) -> &mut T {
+            self.value.get_mut()
+        }
+    }
+
+    impl<T: Clone> Clone for RefCell<T> {
+        fn clone(&self) -> Self {
+            Self::new(self.borrow().clone())
+        }
+    }
+
+    impl<T: ?Sized + Debug> Debug for RefCell<T> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.debug_struct("RefCell")
+                .field("value", &&*self.borrow())
+                .finish()
+        }
+    }
+
+    /// A shared borrow of the value of a [`RefCell`].
+    pub struct Ref<'b, T: ?Sized> {
+        value: NonNull<T>,
+        _guard: RwLockReadGuard<'b, ()>,
+    }
+
+    impl<'b, T: ?Sized> Ref<'b, T> {
+        /// Makes a new `Ref` for a component of the borrowed data.
+        pub fn map<U: ?Sized, F>(orig: Ref<'b, T>, f: F) -> Ref<'b, U>
+        where
+            F: FnOnce(&T) -> &U,
+        {
+            Ref {
+                value: NonNull::from(f(unsafe { orig.value.as_ref() })),
+                _guard: orig._guard,
+            }
+        }
+    }
+
+    impl<T: ?Sized> Deref for Ref<'_, T> {
+        type Target = T;
+
+        fn deref(&self) -> &T {
+            unsafe { self.value.as_ref() }
+        }
+    }
+
+    impl<T: ?Sized + Debug> Debug for Ref<'_, T> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            Debug::fmt(&**self, f)
+        }
+    }
+
+    impl<T: ?Sized + Display> Display for Ref<'_, T> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            Display::fmt(&**self, f)
+        }
+    }
+
+    /// A mutable borrow of the value of a [`RefCell`].
+    pub struct RefMut<'b, T: ?Sized> {
+        value: NonNull<T>,
+        _guard: RwLockWriteGuard<'b, ()>,
+    }
+
+    impl<'b, T: ?Sized> RefMut<'b, T> {
+        /// Makes a new `RefMut` for a component of the borrowed data.
+        pub fn map<U: ?Sized, F>(mut orig: RefMut<'b, T>, f: F) -> RefMut<'b, U>
+        where
+            F: FnOnce(&mut T) -> &mut U,
+        {
+            RefMut {
+                value: NonNull::from(f(unsafe { orig.value.as_mut() })),
+                _guard: orig._guard,
+            }
+        }
+    }
+
+    impl<T: ?Sized> Deref for RefMut<'_, T> {
+        type Target = T;
+
+        fn deref(&self) -> &T {
+            unsafe { self.value.as_ref() }
+        }
+    }
+
+    impl<T: ?Sized> DerefMut for RefMut<'_, T> {
+        fn deref_mut(&mut self) -> &mut T {
+            unsafe { self.value.as_mut() }
+        }
+    }
+
+    impl<T: ?Sized + Debug> Debug for RefMut<'_, T> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            Debug::fmt(&**self, f)
+        }
+    }
+
+    impl<T: ?Sized + Display> Display for RefMut<'_, T> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            Display::fmt(&**self, f)
+        }
+    }
+
+    /// A thread-safe mutable memory location for `Copy` values.
+    ///
+    /// The value is never borrowed past a single access, so concurrent accesses wait for each
+    /// other instead of panicking.
+    #[derive(Default)]
+    pub struct Cell<T> {
+        value: RwLock<T>,
+    }
+
+    impl<T> Cell<T> {
+        /// Creates a new cell containing `value`.
+        pub const fn new(value: T) -> Self {
+            Self {
+                value: RwLock::new(value),
+            }
+        }
+
+        /// Sets the contained value.
+        pub fn set(&self, value: T) {
+            *self.value.write().unwrap_or_else(PoisonError::into_inner) = value;
+        }
+
+        /// Replaces the contained value with `value`, returning the old one.
+        pub fn replace(&self, value: T) -> T {
+            let mut guard = self.value.write().unwrap_or_else(PoisonError::into_inner);
+            mem::replace(&mut *guard, value)
+        }
+
+        /// Consumes the cell, returning the contained value.
+        pub fn into_inner(self) -> T {
+            self.value
+                .into_inner()
+                .unwrap_or_else(PoisonError::into_inner)
+        }
+    }
+
+    impl<T: Copy> Cell<T> {
+        /// Returns a copy of the contained value.
+        pub fn get(&self) -> T {
+            *self.value.read().unwrap_or_else(PoisonError::into_inner)
+        }
+    }
+
+    impl<T: Copy> Clone for Cell<T> {
+        fn clone(&self) -> Self {
+            Self::new(self.get())
+        }
+    }
+
+    impl<T: Copy + Debug> Debug for Cell<T> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.debug_struct("Cell").field("value", &self.get()).finish()
+        }
+    }
+
+    #[cfg(test)]
+    mod test {
+        use super::{Cell, RefCell};
+
+        #[test]
+        fn borrows() {
+            let cell = RefCell::new(vec![1, 2]);
+            {
+                let (first, second) = (cell.borrow(), cell.borrow());
+                assert_eq!(*first, *second);
+            }
+            cell.borrow_mut().push(3);
+            assert_eq!(*cell.borrow(), [1, 2, 3]);
+            assert_eq!(cell.replace(vec![4]), [1, 2, 3]);
+            assert_eq!(cell.into_inner(), [4]);
+
+            let cell = Cell::new(1);
+            cell.set(2);
+            assert_eq!(cell.replace(3), 2);
+            assert_eq!(cell.get(), 3);
+        }
+
+        #[test]
+        #[should_panic(expected = "already borrowed")]
+        fn conflicting_mutable_borrow() {
+            let cell = RefCell::new(0);
+            let _shared = cell.borrow();
+            let _mutable = cell.borrow_mut();
+        }
+
+        #[test]
+        #[should_panic(expected = "already mutably borrowed")]
+        fn conflicting_shared_borrow() {
+            let cell = RefCell::new(0);
+            let _mutable = cell.borrow_mut();
+            let _shared = cell.borrow();
+        }
+
+        #[test]
+        fn conflicting_borrow_across_threads() {
+            let cell = RefCell::new(0);
+            let _mutable = cell.borrow_mut();
+            std::thread::scope(|scope| {
+                assert!(scope.spawn(|| *cell.borrow()).join().is_err());
+            });
+        }
+    }
+}
@@ -0,0 +1,71 @@
+use std::fmt::{Debug, Display};
+
+/// Element type of variables.
+///
+/// Every variable holds elements implementing this trait. Only [`Float`](crate::Float) elements
+/// can be differentiated, integer and boolean variables are used for labels, indices and masks.
+pub trait Element:
+    Copy + PartialEq + PartialOrd + Default + Debug + Display + Send + Sync + 'static
+{
+    /// Returns `true` if the element is infinite or NaN. Integer and boolean elements are always
+    /// finite.
+    fn is_non_finite(self) -> bool {
+        false
+    }
+}
+
+/// Element type that can be used as an index or as a class label.
+pub trait AsIndex: Element {
+    /// Converts the element to an index, returning `None` if it does not represent one.
+    fn to_index(self) -> Option<usize>;
+}
+
+macro_rules! impl_element {
+    ($($type:ty),*) => {
+        $(impl Element for $type {})*
+    };
+}
+
+impl_element!(i64, usize, bool);
+
+impl Element for f32 {
+    fn is_non_finite(self) -> bool {
+        !self.is_finite()
+    }
+}
+
+impl Element for f64 {
+    fn is_non_finite(self) -> bool {
+        !self.is_finite()
+    }
+}
+
+impl AsIndex for i64 {
+    fn to_index(self) -> Option<usize> {
+        usize::try_from(self).ok()
+    }
+}
+
+impl AsIndex for usize {
+    fn to_index(self) -> Option<usize> {
+        Some(self)
+    }
+}
+
+macro_rules! impl_float_index {
+    ($($type:ty),*) => {
+        $(
+            impl AsIndex for $type {
+                fn to_index(self) -> Option<usize> {
+                    if self.fract() == 0. {
+                        num_traits::ToPrimitive::to_usize(&self)
+                    } else {
+                        None
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_float_index!(f32, f64);
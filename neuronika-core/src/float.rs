@@ -0,0 +1,50 @@
+use std::{
+    iter::Sum,
+    ops::{AddAssign, DivAssign, MulAssign, SubAssign},
+};
+
+use ndarray::{LinalgScalar, ScalarOperand};
+
+use crate::{AsIndex, Element};
+
+/// Floating point element type of variables.
+///
+/// This trait is implemented for [`f32`], [`f64`] and for the software half precision types
+/// [`f16`](crate::f16) and [`bf16`](crate::bf16).
+pub trait Float:
+    num_traits::Float
+    + LinalgScalar
+    + ScalarOperand
+    + AddAssign
+    + SubAssign
+    + MulAssign
+    + DivAssign
+    + Sum
+    + Element
+    + AsIndex
+{
+    /// Converts a `f64` to the nearest representable value.
+    fn from_f64(value: f64) -> Self;
+
+    /// Converts a `f32` to the nearest representable value.
+    fn from_f32(value: f32) -> Self {
+        Self::from_f64(value as f64)
+    }
+
+    /// Converts a `usize` to the nearest representable value.
+    fn from_usize(value: usize) -> Self {
+        Self::from_f64(value as f64)
+    }
+}
+
+impl Float for f32 {
+    fn from_f64(value: f64) -> Self {
+        value as f32
+    }
+}
+
+impl Float for f64 {
+    fn from_f64(value: f64) -> Self {
+        value
+    }
+}
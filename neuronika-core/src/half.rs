@@ -0,0 +1,505 @@
+//! Software half precision floating point types.
+//!
+//! Values are stored in 16 bits and every arithmetic operation is carried out in `f32`, rounding
+//! the result to the nearest representable value.
+
+use std::{
+    cmp::Ordering,
+    fmt::{self, Debug, Display},
+    iter::Sum,
+    num::FpCategory,
+    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign},
+};
+
+use ndarray::ScalarOperand;
+use num_traits::{Num, NumCast, One, Zero};
+
+use crate::{AsIndex, Element, Float};
+
+/// A 16-bit floating point type implementing the IEEE 754-2008 *binary16* format.
+#[allow(non_camel_case_types)]
+#[derive(Clone, Copy, Default)]
+pub struct f16(u16);
+
+/// A 16-bit floating point type implementing the *bfloat16* format, that is, the upper half of a
+/// `f32`.
+#[allow(non_camel_case_types)]
+#[derive(Clone, Copy, Default)]
+pub struct bf16(u16);
+
+impl f16 {
+    /// Converts a `f32` to the nearest `f16`, rounding ties to even.
+    pub fn from_f32(value: f32) -> Self {
+        let bits = value.to_bits();
+        let sign = (bits >> 16) & 0x8000;
+        let exp = (bits >> 23) & 0xff;
+        let man = bits & 0x7f_ffff;
+
+        // Infinities and NaNs, the latter keep being NaNs.
+        if exp == 0xff {
+            let nan_bit = if man == 0 { 0 } else { 0x0200 };
+            return Self((sign | 0x7c00 | nan_bit | (man >> 13)) as u16);
+        }
+
+        let half_exp = exp as i32 - 127 + 15;
+
+        // Overflow to infinity.
+        if half_exp >= 0x1f {
+            return Self((sign | 0x7c00) as u16);
+        }
+
+        // Subnormals and underflow to zero.
+        if half_exp <= 0 {
+            if 14 - half_exp > 24 {
+                return Self(sign as u16);
+            }
+
+            let man = man | 0x80_0000;
+            let shift = (14 - half_exp) as u32;
+            let mut half_man = man >> shift;
+            let round_bit = 1 << (shift - 1);
+            if (man & round_bit) != 0 && (man & (3 * round_bit - 1)) != 0 {
+                half_man += 1;
+            }
+
+            return Self((sign | half_man) as u16);
+        }
+
+        let half = sign | ((half_exp as u32) << 10) | (man >> 13);
+        let round_bit = 0x1000;
+        if (man & round_bit) != 0 && (man & (3 * round_bit - 1)) != 0 {
+            Self((half + 1) as u16)
+        } else {
+            Self(half as u16)
+        }
+    }
+
+    /// Converts `self` to `f32`. The conversion is exact.
+    pub fn to_f32(self) -> f32 {
+        let bits = self.0 as u32;
+
+        // Signed zeros.
+        if bits & 0x7fff == 0 {
+            return f32::from_bits(bits << 16);
+        }
+
+        let sign = (bits & 0x8000) << 16;
+        let exp = (bits & 0x7c00) >> 10;
+        let man = bits & 0x03ff;
+
+        // Infinities and NaNs.
+        if exp == 0x1f {
+            return if man == 0 {
+                f32::from_bits(sign | 0x7f80_0000)
+            } else {
+                f32::from_bits(sign | 0x7fc0_0000 | (man << 13))
+            };
+        }
+
+        // Subnormals are normalized.
+        if exp == 0 {
+            let shift = man.leading_zeros() - 22;
+            let exp = (127 - 15 - shift) << 23;
+            let man = (man << (14 + shift)) & 0x7f_ffff;
+
+            return f32::from_bits(sign | exp | man);
+        }
+
+        f32::from_bits(sign | ((exp + 127 - 15) << 23) | (man << 13))
+    }
+
+    /// Returns the raw bits of `self`.
+    pub fn to_bits(self) -> u16 {
+        self.0
+    }
+
+    /// Creates a value from its raw bits.
+    pub fn from_bits(bits: u16) -> Self {
+        Self(bits)
+    }
+
+    const EPSILON: f32 = 9.765_625e-4;
+    const MAX: f32 = 65504.;
+    const MIN_POSITIVE: f32 = 6.103_515_6e-5;
+}
+
+impl bf16 {
+    /// Converts a `f32` to the nearest `bf16`, rounding ties to even.
+    pub fn from_f32(value: f32) -> Self {
+        let bits = value.to_bits();
+
+        // NaNs must not be rounded to infinity.
+        if value.is_nan() {
+            return Self(((bits >> 16) | 0x0040) as u16);
+        }
+
+        let round = ((bits >> 16) & 1) + 0x7fff;
+        Self((bits.wrapping_add(round) >> 16) as u16)
+    }
+
+    /// Converts `self` to `f32`. The conversion is exact.
+    pub fn to_f32(self) -> f32 {
+        f32::from_bits((self.0 as u32) << 16)
+    }
+
+    /// Returns the raw bits of `self`.
+    pub fn to_bits(self) -> u16 {
+        self.0
+    }
+
+    /// Creates a value from its raw bits.
+    pub fn from_bits(bits: u16) -> Self {
+        Self(bits)
+    }
+
+    const EPSILON: f32 = 7.812_5e-3;
+    const MAX: f32 = 3.389_531_4e38;
+    const MIN_POSITIVE: f32 = f32::MIN_POSITIVE;
+}
+
+/// Implements the arithmetic and the numeric traits for a half precision type by computing in
+/// `f32`.
+macro_rules! impl_half {
+    ($half:ident) => {
+        impl PartialEq for $half {
+            fn eq(&self, other: &Self) -> bool {
+                self.to_f32() == other.to_f32()
+            }
+        }
+
+        impl PartialOrd for $half {
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                self.to_f32().partial_cmp(&other.to_f32())
+            }
+        }
+
+        impl Debug for $half {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                Debug::fmt(&self.to_f32(), f)
+            }
+        }
+
+        impl Display for $half {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                Display::fmt(&self.to_f32(), f)
+            }
+        }
+
+        impl From<$half> for f32 {
+            fn from(value: $half) -> Self {
+                value.to_f32()
+            }
+        }
+
+        impl From<$half> for f64 {
+            fn from(value: $half) -> Self {
+                value.to_f32() as f64
+            }
+        }
+
+        impl Neg for $half {
+            type Output = Self;
+
+            fn neg(self) -> Self {
+                Self(self.0 ^ 0x8000)
+            }
+        }
+
+        impl_half!(@binary $half, Add, add, AddAssign, add_assign, +);
+        impl_half!(@binary $half, Sub, sub, SubAssign, sub_assign, -);
+        impl_half!(@binary $half, Mul, mul, MulAssign, mul_assign, *);
+        impl_half!(@binary $half, Div, div, DivAssign, div_assign, /);
+        impl_half!(@binary $half, Rem, rem, RemAssign, rem_assign, %);
+
+        impl Sum for $half {
+            fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+                Self::from_f32(iter.map(Self::to_f32).sum())
+            }
+        }
+
+        impl<'a> Sum<&'a $half> for $half {
+            fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+                iter.copied().sum()
+            }
+        }
+
+        impl Zero for $half {
+            fn zero() -> Self {
+                Self(0)
+            }
+
+            fn is_zero(&self) -> bool {
+                self.0 & 0x7fff == 0
+            }
+        }
+
+        impl One for $half {
+            fn one() -> Self {
+                Self::from_f32(1.)
+            }
+        }
+
+        impl Num for $half {
+            type FromStrRadixErr = <f32 as Num>::FromStrRadixErr;
+
+            fn from_str_radix(src: &str, radix: u32) -> Result<Self, Self::FromStrRadixErr> {
+                f32::from_str_radix(src, radix).map(Self::from_f32)
+            }
+        }
+
+        impl num_traits::ToPrimitive for $half {
+            fn to_i64(&self) -> Option<i64> {
+                num_traits::ToPrimitive::to_i64(&$half::to_f32(*self))
+            }
+
+            fn to_u64(&self) -> Option<u64> {
+                num_traits::ToPrimitive::to_u64(&$half::to_f32(*self))
+            }
+
+            fn to_f32(&self) -> Option<f32> {
+                Some($half::to_f32(*self))
+            }
+
+            fn to_f64(&self) -> Option<f64> {
+                Some($half::to_f32(*self) as f64)
+            }
+        }
+
+        impl NumCast for $half {
+            fn from<N: num_traits::ToPrimitive>(n: N) -> Option<Self> {
+                n.to_f32().map(Self::from_f32)
+            }
+        }
+
+        impl ScalarOperand for $half {}
+
+        impl num_traits::Float for $half {
+            fn nan() -> Self {
+                Self::from_f32(f32::NAN)
+            }
+
+            fn infinity() -> Self {
+                Self::from_f32(f32::INFINITY)
+            }
+
+            fn neg_infinity() -> Self {
+                Self::from_f32(f32::NEG_INFINITY)
+            }
+
+            fn neg_zero() -> Self {
+                Self(0x8000)
+            }
+
+            fn min_value() -> Self {
+                Self::from_f32(-Self::MAX)
+            }
+
+            fn min_positive_value() -> Self {
+                Self::from_f32(Self::MIN_POSITIVE)
+            }
+
+            fn epsilon() -> Self {
+                Self::from_f32(Self::EPSILON)
+            }
+
+            fn max_value() -> Self {
+                Self::from_f32(Self::MAX)
+            }
+
+            fn is_nan(self) -> bool {
+                self.to_f32().is_nan()
+            }
+
+            fn is_infinite(self) -> bool {
+                self.to_f32().is_infinite()
+            }
+
+            fn is_finite(self) -> bool {
+                self.to_f32().is_finite()
+            }
+
+            fn is_normal(self) -> bool {
+                self.classify() == FpCategory::Normal
+            }
+
+            fn classify(self) -> FpCategory {
+                let value = self.to_f32();
+                if value.is_normal() && value.abs() < Self::MIN_POSITIVE {
+                    FpCategory::Subnormal
+                } else {
+                    value.classify()
+                }
+            }
+
+            fn mul_add(self, a: Self, b: Self) -> Self {
+                Self::from_f32(self.to_f32().mul_add(a.to_f32(), b.to_f32()))
+            }
+
+            fn powi(self, n: i32) -> Self {
+                Self::from_f32(self.to_f32().powi(n))
+            }
+
+            fn powf(self, n: Self) -> Self {
+                Self::from_f32(self.to_f32().powf(n.to_f32()))
+            }
+
+            fn log(self, base: Self) -> Self {
+                Self::from_f32(self.to_f32().log(base.to_f32()))
+            }
+
+            fn max(self, other: Self) -> Self {
+                Self::from_f32(self.to_f32().max(other.to_f32()))
+            }
+
+            fn min(self, other: Self) -> Self {
+                Self::from_f32(self.to_f32().min(other.to_f32()))
+            }
+
+            #[allow(deprecated)]
+            fn abs_sub(self, other: Self) -> Self {
+                Self::from_f32((self.to_f32() - other.to_f32()).max(0.))
+            }
+
+            fn hypot(self, other: Self) -> Self {
+                Self::from_f32(self.to_f32().hypot(other.to_f32()))
+            }
+
+            fn atan2(self, other: Self) -> Self {
+                Self::from_f32(self.to_f32().atan2(other.to_f32()))
+            }
+
+            fn sin_cos(self) -> (Self, Self) {
+                let (sin, cos) = self.to_f32().sin_cos();
+                (Self::from_f32(sin), Self::from_f32(cos))
+            }
+
+            fn is_sign_positive(self) -> bool {
+                self.0 & 0x8000 == 0
+            }
+
+            fn is_sign_negative(self) -> bool {
+                self.0 & 0x8000 != 0
+            }
+
+            fn integer_decode(self) -> (u64, i16, i8) {
+                num_traits::Float::integer_decode(self.to_f32())
+            }
+
+            impl_half!(@unary floor ceil round trunc fract abs signum recip sqrt exp exp2 ln log2
+                log10 cbrt sin cos tan asin acos atan exp_m1 ln_1p sinh cosh tanh asinh acosh
+                atanh);
+        }
+
+        impl Element for $half {
+            fn is_non_finite(self) -> bool {
+                !self.to_f32().is_finite()
+            }
+        }
+
+        impl AsIndex for $half {
+            fn to_index(self) -> Option<usize> {
+                <f32 as From<$half>>::from(self).to_index()
+            }
+        }
+
+        impl Float for $half {
+            fn from_f64(value: f64) -> Self {
+                Self::from_f32(value as f32)
+            }
+        }
+    };
+
+    (@binary $half:ident, $op:ident, $fn:ident, $op_assign:ident, $fn_assign:ident, $symbol:tt) => {
+        impl $op for $half {
+            type Output = Self;
+
+            fn $fn(self, rhs: Self) -> Self {
+                Self::from_f32(self.to_f32() $symbol rhs.to_f32())
+            }
+        }
+
+        impl $op_assign for $half {
+            fn $fn_assign(&mut self, rhs: Self) {
+                *self = *self $symbol rhs;
+            }
+        }
+    };
+
+    (@unary $($fn:ident)*) => {
+        $(
+            fn $fn(self) -> Self {
+                Self::from_f32(self.to_f32().$fn())
+            }
+        )*
+    };
+}
+
+impl_half!(f16);
+impl_half!(bf16);
+
+#[cfg(test)]
+mod test {
+    use super::{bf16, f16};
+
+    #[test]
+    fn f16_round_trip() {
+        for value in [
+            0.,
+            -0.,
+            1.,
+            -2.5,
+            0.1,
+            65504.,
+            6.103_515_6e-5,
+            5.960_464_5e-8,
+        ] {
+            let half = f16::from_f32(value);
+            assert!((half.to_f32() - value).abs() <= value.abs() * 1e-3);
+        }
+
+        assert_eq!(f16::from_f32(1.).to_bits(), 0x3c00);
+        assert_eq!(f16::from_f32(-2.).to_bits(), 0xc000);
+        assert_eq!(f16::from_f32(65504.).to_bits(), 0x7bff);
+        assert_eq!(f16::from_f32(5.960_464_5e-8).to_bits(), 0x0001);
+    }
+
+    #[test]
+    fn f16_special() {
+        assert!(f16::from_f32(1e6).to_f32().is_infinite());
+        assert!(f16::from_f32(f32::NAN).to_f32().is_nan());
+        assert_eq!(f16::from_f32(1e-10).to_f32(), 0.);
+    }
+
+    #[test]
+    fn f16_rounding() {
+        // 1 + 2^-11 is halfway between 1 and the next f16, ties go to even.
+        assert_eq!(f16::from_f32(1. + 2f32.powi(-11)).to_bits(), 0x3c00);
+        // 1 + 3 * 2^-11 is halfway between two f16, the upper one is even.
+        assert_eq!(f16::from_f32(1. + 3. * 2f32.powi(-11)).to_bits(), 0x3c02);
+    }
+
+    #[test]
+    fn bf16_round_trip() {
+        assert_eq!(bf16::from_f32(1.).to_bits(), 0x3f80);
+        assert_eq!(bf16::from_f32(-2.).to_f32(), -2.);
+        assert_eq!(bf16::from_f32(1. + 2f32.powi(-8)).to_f32(), 1.);
+        assert_eq!(
+            bf16::from_f32(1. + 3. * 2f32.powi(-8)).to_f32(),
+            1. + 2f32.powi(-6)
+        );
+        assert!(bf16::from_f32(f32::NAN).to_f32().is_nan());
+    }
+
+    #[test]
+    fn arithmetic() {
+        let a = f16::from_f32(1.5);
+        let b = f16::from_f32(0.25);
+        assert_eq!((a + b).to_f32(), 1.75);
+        assert_eq!((a * b).to_f32(), 0.375);
+        assert_eq!((-a).to_f32(), -1.5);
+        assert!(a > b);
+
+        let a = bf16::from_f32(3.);
+        assert_eq!(num_traits::Float::sqrt(a * a).to_f32(), 3.);
+    }
+}
@@ -1,5 +1,21 @@
+pub mod cell;
+
+mod element;
+mod float;
+mod half;
+mod random;
+
 use ndarray::{Dimension, IntoDimension};
 
+use crate::cell::{Cell, Rc};
+
+pub use crate::{
+    element::{AsIndex, Element},
+    float::Float,
+    half::{bf16, f16},
+    random::{manual_seed, with_generator, Generator},
+};
+
 /// Matrix-matrix multiplication.
 pub trait MatMatMul<Rhs> {
     /// The type of the matrix-matrix multiplication's result. See the
@@ -87,6 +103,74 @@ pub trait Stack<Rhs> {
     fn stack(self, other: Rhs, axis: usize) -> Self::Output;
 }
 
+/// Element-wise selection.
+pub trait Where<Mask, Rhs> {
+    /// The type of the selection's result. See the [*differentiability arithmetic*] for more
+    /// details.
+    ///
+    /// [*differentiability arithmetic*]: index.html#differentiability-arithmetic
+    type Output;
+
+    /// Takes the elements of `self` where `mask` is `true` and those of `other` elsewhere.
+    fn where_(self, mask: Mask, other: Rhs) -> Self::Output;
+}
+
+/// Solution of linear systems.
+pub trait Solve<Rhs> {
+    /// The type of the solution. See the [*differentiability arithmetic*] for more details.
+    ///
+    /// [*differentiability arithmetic*]: index.html#differentiability-arithmetic
+    type Output;
+
+    /// Solves the linear systems having `self` as coefficients and `other` as right hand sides.
+    fn solve(self, other: Rhs) -> Self::Output;
+}
+
+/// Scaled dot-product attention.
+pub trait Attention<Key, Value, Mask> {
+    /// The type of the attention's result. See the [*differentiability arithmetic*] for more
+    /// details.
+    ///
+    /// [*differentiability arithmetic*]: index.html#differentiability-arithmetic
+    type Output;
+
+    /// Computes the scaled dot-product attention of the queries `self` over `key` and `value`.
+    ///
+    /// The attention weights *softmax(QKᵀ / √E)* of every sample and head are computed and
+    /// multiplied by the values in a single operation, without storing them. The queries must be
+    /// of shape *(N, H, L, E)*, the keys of shape *(N, H, S, E)* and the values of shape
+    /// *(N, H, S, Ev)*, the result is of shape *(N, H, L, Ev)*. Queries that cannot attend to any
+    /// key because of masking result in zeros.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - keys.
+    ///
+    /// * `value` - values.
+    ///
+    /// * `mask` - optional mask, broadcastable to *(N, H, L, S)*.
+    ///
+    /// * `dropout` - probability of zeroing each attention weight.
+    ///
+    /// * `status` - dropout status, no dropout is applied when it is off.
+    ///
+    /// * `causal` - whether to prevent each query from attending to the keys past its position.
+    ///
+    /// # Panics
+    ///
+    /// If the shapes of the queries, keys and values don't match, if `mask` cannot be broadcast
+    /// to the shape of the attention weights or if `dropout` is not a probability.
+    fn scaled_dot_product_attention(
+        self,
+        key: Key,
+        value: Value,
+        mask: Option<Mask>,
+        dropout: f64,
+        status: Rc<Cell<bool>>,
+        causal: bool,
+    ) -> Self::Output;
+}
+
 /// Convolution.
 pub trait Convolution<Rhs, D>
 where
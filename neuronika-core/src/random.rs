@@ -0,0 +1,104 @@
+use std::cell::RefCell;
+
+use rand::{rngs::StdRng, RngCore, SeedableRng};
+
+std::thread_local! {
+    static GENERATOR: RefCell<Generator> = RefCell::new(Generator::from_entropy());
+}
+
+/// A pseudo-random number generator.
+///
+/// Each thread owns one, from which every random component of the library draws: the random
+/// constructors, the initialization functions, the dropout and the shuffling of the datasets. It
+/// can be seeded with [`manual_seed()`] and accessed with [`with_generator()`].
+///
+/// Generators are cheap to clone, so that the state of the one of a thread can be saved and
+/// restored.
+///
+/// # Examples
+///
+/// ```
+/// # use neuronika_core as neuronika;
+/// use rand::Rng;
+///
+/// neuronika::manual_seed(42);
+/// let state = neuronika::with_generator(|generator| generator.clone());
+/// let first: f32 = neuronika::with_generator(|generator| generator.gen());
+///
+/// neuronika::with_generator(|generator| *generator = state);
+/// let second: f32 = neuronika::with_generator(|generator| generator.gen());
+/// assert_eq!(first, second);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Generator {
+    rng: StdRng,
+}
+
+impl Generator {
+    /// Creates a new generator from `seed`.
+    pub fn new(seed: u64) -> Self {
+        Self {
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    /// Creates a new generator seeded with entropy provided by the operating system.
+    pub fn from_entropy() -> Self {
+        Self {
+            rng: StdRng::from_entropy(),
+        }
+    }
+}
+
+impl RngCore for Generator {
+    fn next_u32(&mut self) -> u32 {
+        self.rng.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.rng.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.rng.fill_bytes(dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.rng.try_fill_bytes(dest)
+    }
+}
+
+/// Seeds the generator of the current thread with `seed`.
+///
+/// Everything drawing random numbers on the current thread afterwards, such as parameters
+/// initialization, dropout and dataset shuffling, is thus reproducible. Generators of other
+/// threads are not affected.
+///
+/// # Examples
+///
+/// ```
+/// # use neuronika_core as neuronika;
+/// use rand::Rng;
+///
+/// neuronika::manual_seed(0);
+/// let first: u64 = neuronika::with_generator(|generator| generator.gen());
+///
+/// neuronika::manual_seed(0);
+/// let second: u64 = neuronika::with_generator(|generator| generator.gen());
+/// assert_eq!(first, second);
+/// ```
+pub fn manual_seed(seed: u64) {
+    GENERATOR.with(|generator| *generator.borrow_mut() = Generator::new(seed));
+}
+
+/// Calls `f` with the generator of the current thread.
+///
+/// # Panics
+///
+/// If called from within `f`.
+pub fn with_generator<F, R>(f: F) -> R
+where
+    F: FnOnce(&mut Generator) -> R,
+{
+    GENERATOR.with(|generator| f(&mut generator.borrow_mut()))
+}
@@ -8,6 +8,8 @@ use ndarray::{
     iter::AxisChunksIter, Array, ArrayView, Axis, Dimension, IntoDimension, Ix, RemoveAxis, Zip,
 };
 
+use neuronika_core::with_generator;
+
 use rand::{rngs::StdRng, Rng, SeedableRng};
 
 use serde::de::DeserializeOwned;
@@ -114,8 +116,11 @@ impl<D: RemoveAxis> Dataset<D> {
     }
 
     /// Randomly shuffles the dataset.
+    ///
+    /// The permutation is drawn from the generator of the current thread, hence it can be made
+    /// reproducible with [`manual_seed()`](neuronika_core::manual_seed()).
     pub fn shuffle(&mut self) -> &mut Self {
-        self.shuffle_with_seed(rand::thread_rng().gen())
+        self.shuffle_with_seed(with_generator(|generator| generator.gen()))
     }
 
     /// Randomly shuffles the dataset.
@@ -610,8 +615,11 @@ impl<D1: RemoveAxis, D2: RemoveAxis> LabeledDataset<D1, D2> {
     }
 
     /// Randomly shuffles the labeled dataset.
+    ///
+    /// The permutation is drawn from the generator of the current thread, hence it can be made
+    /// reproducible with [`manual_seed()`](neuronika_core::manual_seed()).
     pub fn shuffle(&mut self) -> &mut Self {
-        self.shuffle_with_seed(rand::thread_rng().gen())
+        self.shuffle_with_seed(with_generator(|generator| generator.gen()))
     }
 
     /// Randomly shuffles the labeled dataset.
@@ -179,6 +179,21 @@ mod dataset {
         );
     }
 
+    #[test]
+    fn shuffle_manual_seed() {
+        let shuffled = || {
+            let mut dataset = DataLoader::default()
+                .without_headers()
+                .from_reader(DATASET.as_bytes(), 10);
+            neuronika_core::manual_seed(7);
+            dataset.shuffle();
+
+            dataset.records().to_owned()
+        };
+
+        assert_eq!(shuffled(), shuffled());
+    }
+
     #[test]
     fn drop_last() {
         let dataset = DataLoader::default()
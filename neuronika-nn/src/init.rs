@@ -1,9 +1,9 @@
-use rand::thread_rng;
-
 use rand_distr::{Distribution, Normal, Uniform};
 
 use ndarray::{Dimension, Ix2};
 
+use neuronika_core::{with_generator, Float};
+
 use neuronika_variable::VarDiff;
 
 /// Returns the recommended gain value for the given non-linearity function.
@@ -42,9 +42,10 @@ pub fn calculate_gain(non_linearity: &str) -> f32 {
 ///
 /// `param` - differentiable variable for which the *fan in* and the *fan out* must be
 /// calculated.
-pub fn calculate_fan_in_fan_out<D>(param: &VarDiff<D>) -> (f32, f32)
+pub fn calculate_fan_in_fan_out<D, T>(param: &VarDiff<D, T>) -> (f32, f32)
 where
     D: Dimension,
+    T: Float,
 {
     let data = param.data();
     let shape = data.shape();
@@ -71,7 +72,7 @@ where
 /// * `param` - differentiable variable to initialize.
 ///
 /// * `value` - value to fill the variable with.
-pub fn constant<D: Dimension>(param: &VarDiff<D>, value: f32) {
+pub fn constant<D: Dimension, T: Float>(param: &VarDiff<D, T>, value: T) {
     param.data_mut().map_inplace(|el| *el = value);
 }
 
@@ -80,8 +81,8 @@ pub fn constant<D: Dimension>(param: &VarDiff<D>, value: f32) {
 /// # Arguments
 ///
 /// `param` - differentiable variable to initialize.
-pub fn zeros<D: Dimension>(param: &VarDiff<D>) {
-    param.data_mut().map_inplace(|el| *el = 0.);
+pub fn zeros<D: Dimension, T: Float>(param: &VarDiff<D, T>) {
+    param.data_mut().map_inplace(|el| *el = T::zero());
 }
 
 /// Fills the differentiable leaf variable with ones.
@@ -89,8 +90,8 @@ pub fn zeros<D: Dimension>(param: &VarDiff<D>) {
 /// # Arguments
 ///
 /// `param` - differentiable variable to initialize.
-pub fn ones<D: Dimension>(param: &VarDiff<D>) {
-    param.data_mut().map_inplace(|el| *el = 1.0);
+pub fn ones<D: Dimension, T: Float>(param: &VarDiff<D, T>) {
+    param.data_mut().map_inplace(|el| *el = T::one());
 }
 
 /// Fills the matrix differentiable leaf variable with the identity matrix.
@@ -101,12 +102,12 @@ pub fn ones<D: Dimension>(param: &VarDiff<D>) {
 /// # Arguments
 ///
 /// `param` - differentiable variable to initialize.
-pub fn eye(param: &VarDiff<Ix2>) {
+pub fn eye<T: Float>(param: &VarDiff<Ix2, T>) {
     for ((x, y), el) in param.data_mut().indexed_iter_mut() {
         if x == y {
-            *el = 1.
+            *el = T::one()
         } else {
-            *el = 0.
+            *el = T::zero()
         }
     }
 }
@@ -128,7 +129,7 @@ pub fn eye(param: &VarDiff<Ix2>) {
 /// If the differentiable variable is not {3, 4, 5}-dimensional and the number of output
 /// channels is not divisible by `groups`. The number of output channels is equal to the length
 /// of the first axis of `param`'s data.
-pub fn dirac<D: Dimension>(param: &VarDiff<D>, groups: usize) {
+pub fn dirac<D: Dimension, T: Float>(param: &VarDiff<D, T>, groups: usize) {
     let mut data = param.data_mut();
     let shape = data.shape().to_vec();
     let no_dim = shape.len();
@@ -155,7 +156,7 @@ pub fn dirac<D: Dimension>(param: &VarDiff<D>, groups: usize) {
                 .skip(2)
                 .zip(shape.iter().skip(2))
                 .for_each(|(el, sh)| *el = sh / 2);
-            data[index] = 1.
+            data[index] = T::one()
         }
     }
 }
@@ -174,12 +175,13 @@ pub fn dirac<D: Dimension>(param: &VarDiff<D>, groups: usize) {
 /// # Panics
 ///
 /// If `low` >= `high`.
-pub fn uniform<D: Dimension>(param: &VarDiff<D>, low: f32, high: f32) {
-    let unif_dstr = Uniform::new(low, high);
-    let mut t_rng = thread_rng();
-    param
-        .data_mut()
-        .map_inplace(|el| *el = unif_dstr.sample(&mut t_rng));
+pub fn uniform<D: Dimension, T: Float>(param: &VarDiff<D, T>, low: T, high: T) {
+    let unif_dstr = Uniform::new(low.to_f64().unwrap(), high.to_f64().unwrap());
+    with_generator(|generator| {
+        param
+            .data_mut()
+            .map_inplace(|el| *el = T::from_f64(unif_dstr.sample(generator)))
+    });
 }
 
 /// Fills the differentiable leaf variable with elements drawn from the normal distribution
@@ -192,12 +194,13 @@ pub fn uniform<D: Dimension>(param: &VarDiff<D>, low: f32, high: f32) {
 /// * `mean` - mean of the normal distribution.
 ///
 /// * `std` - standard deviation of the normal distribution.
-pub fn normal<D: Dimension>(param: &VarDiff<D>, mean: f32, std: f32) {
-    let norm_dstr = Normal::new(mean, std).unwrap();
-    let mut t_rng = thread_rng();
-    param
-        .data_mut()
-        .map_inplace(|el| *el = norm_dstr.sample(&mut t_rng));
+pub fn normal<D: Dimension, T: Float>(param: &VarDiff<D, T>, mean: T, std: T) {
+    let norm_dstr = Normal::new(mean.to_f64().unwrap(), std.to_f64().unwrap()).unwrap();
+    with_generator(|generator| {
+        param
+            .data_mut()
+            .map_inplace(|el| *el = T::from_f64(norm_dstr.sample(generator)))
+    });
 }
 
 /// Fills the differentiable leaf variable with values according to the method described in
@@ -210,15 +213,16 @@ pub fn normal<D: Dimension>(param: &VarDiff<D>, mean: f32, std: f32) {
 /// * `param` - differentiable variable to initialize.
 ///
 /// * `gain` - optional scaling factor. See also [`calculate_gain`](function@calculate_gain).
-pub fn xavier_uniform<D: Dimension>(param: &VarDiff<D>, gain: f32) {
+pub fn xavier_uniform<D: Dimension, T: Float>(param: &VarDiff<D, T>, gain: f32) {
     let (fan_in, fan_out) = calculate_fan_in_fan_out(param);
     let std = gain * (2. / ((fan_in + fan_out) as f32)).sqrt();
     let a = 3.0_f32.sqrt() * std;
     let unif_distr = Uniform::new(-a, a);
-    let mut t_rng = thread_rng();
-    param
-        .data_mut()
-        .map_inplace(|el| *el = unif_distr.sample(&mut t_rng));
+    with_generator(|generator| {
+        param
+            .data_mut()
+            .map_inplace(|el| *el = T::from_f64(unif_distr.sample(generator) as f64))
+    });
 }
 
 /// Fills the differentiable leaf variable with values according to the method described in
@@ -233,12 +237,13 @@ pub fn xavier_uniform<D: Dimension>(param: &VarDiff<D>, gain: f32) {
 /// * `param` - differentiable variable to initialize.
 ///
 /// * `gain` - optional scaling factor. See also [`calculate_gain`](function@calculate_gain).
-pub fn xavier_normal<D: Dimension>(param: &VarDiff<D>, gain: f32) {
+pub fn xavier_normal<D: Dimension, T: Float>(param: &VarDiff<D, T>, gain: f32) {
     let (fan_in, fan_out) = calculate_fan_in_fan_out(param);
     let std = gain * (2. / ((fan_in + fan_out) as f32)).sqrt();
     let norm_distr = Normal::new(0., std).unwrap();
-    let mut t_rng = thread_rng();
-    param
-        .data_mut()
-        .map_inplace(|el| *el = norm_distr.sample(&mut t_rng));
+    with_generator(|generator| {
+        param
+            .data_mut()
+            .map_inplace(|el| *el = T::from_f64(norm_distr.sample(generator) as f64))
+    });
 }
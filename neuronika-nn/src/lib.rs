@@ -361,6 +361,40 @@
 // //!
 // //! * [`nn::LSTMCell`](struct@LSTMCell) - A long short term memory cell.
 // //!
+// //! * [`nn::RNNCell`](struct@RNNCell) - An Elman recurrent neural network cell.
+// //!
+// //! * [`nn::GRU`](struct@GRU) - A multi-layer gated recurrent unit network.
+// //!
+// //! * [`nn::LSTM`](struct@LSTM) - A multi-layer long short term memory network.
+// //!
+// //! * [`nn::RNN`](struct@RNN) - A multi-layer Elman recurrent neural network.
+// //!
+// //! ## Normalization Layers
+// //!
+// //! * [`nn::LayerNorm`](struct@LayerNorm) - Applies layer normalization over the last dimension
+// //! of the input.
+// //!
+// //! ## Transformer Layers
+// //!
+// //! * [`nn::MultiheadAttention`](struct@MultiheadAttention) - Allows the model to jointly attend
+// //! to information from different representation subspaces.
+// //!
+// //! * [`nn::TransformerEncoderLayer`](struct@TransformerEncoderLayer) - Made up of self-attention
+// //! and a feedforward network.
+// //!
+// //! * [`nn::TransformerDecoderLayer`](struct@TransformerDecoderLayer) - Made up of self-attention,
+// //! attention over the output of the encoder and a feedforward network.
+// //!
+// //! * [`nn::TransformerEncoder`](struct@TransformerEncoder) - A stack of encoder layers.
+// //!
+// //! * [`nn::TransformerDecoder`](struct@TransformerDecoder) - A stack of decoder layers.
+// //!
+// //! * [`nn::SinusoidalPositionalEncoding`](struct@SinusoidalPositionalEncoding) - Adds fixed
+// //! sinusoidal positional encodings to a sequence.
+// //!
+// //! * [`nn::LearnedPositionalEncoding`](struct@LearnedPositionalEncoding) - Adds learned
+// //! positional encodings to a sequence.
+// //!
 // //! ## Convolution Layers
 // //!
 // //! * [`nn::Conv1d`](struct@Conv1d) - Applies a temporal convolution over an input signal composed
@@ -381,19 +415,36 @@
 // //! * [`nn::GroupedConv3d`](struct@GroupedConv3d) - Applies a grouped volumetric convolution over an
 // //! input signal composed of several input planes.
 // //!
+// //! ## Vision Layers
+// //!
+// //! * [`nn::Upsample`](struct@Upsample) - Upsamples a multi-channel input signal of one, two or
+// //! three spatial dimensions.
+// //!
+// //! * [`nn::PixelShuffle`](struct@PixelShuffle) - Rearranges channels into spatial blocks.
+// //!
+// //! * [`nn::PixelUnshuffle`](struct@PixelUnshuffle) - Rearranges spatial blocks into channels.
+// //!
 // //! ## Dropout Layers
 // //!
 // //! * [`nn::Dropout`](struct@Dropout) - During training, randomly zeroes some of the elements of
 // //! the input variable with probability *p* using samples from a Bernoulli distribution.
 
-use ndarray::{Ix1, Ix2, Ix3, Ix4, Ix5};
+use std::ops::{Mul, Sub};
 
-use neuronika_core::{Convolution, MatMatMulT};
+use ndarray::{s, Array2, Axis, Dimension, Ix1, Ix2, Ix3, Ix4, Ix5, RemoveAxis};
 
-use neuronika_variable::{PaddingMode, VarDiff};
+use neuronika_core::{
+    cell::{Cell, Rc},
+    Attention, Convolution, MatMatMulT,
+};
+
+use neuronika_variable::{AttentionMask, Interpolation, PaddingMode, Resize, Var, VarDiff};
 
 pub mod init;
 
+#[cfg(feature = "sync")]
+mod parallel;
+
 #[cfg(feature = "serialize")]
 use serde::{Deserialize, Serialize};
 
@@ -502,24 +553,23 @@ impl LSTMCell {
     ///
     /// * `state` - a tuple of tensors, both of shape *(batch, hidden_size)*, containing the
     /// initial hidden state for each element in the batch and the initial cell's state for
-    /// each element in the batch.
+    /// each element in the batch. Each of them may be either differentiable or not.
     ///
     /// * `input` - a variable containing the input features of shape *(batch, input_size)*.
     ///
     /// The **output** is a tuple of tensors made of the next hidden state for each element in
     /// the batch, of shape *(batch, hidden_size)* and the next cell's state for each element in
     /// the batch, of shape *(batch, hidden_size)*.
-    pub fn forward<I>(
-        &self,
-        state: (VarDiff<Ix2>, VarDiff<Ix2>),
-        input: I,
-    ) -> (VarDiff<Ix2>, VarDiff<Ix2>)
+    pub fn forward<C, H, I>(&self, state: (C, H), input: I) -> (VarDiff<Ix2>, VarDiff<Ix2>)
     where
+        C: Mul<VarDiff<Ix2>, Output = VarDiff<Ix2>>,
+        H: MatMatMulT<VarDiff<Ix2>>,
+        H::Output: Into<VarDiff<Ix2>>,
         I: MatMatMulT<VarDiff<Ix2>>,
         I::Output: Into<VarDiff<Ix2>>,
     {
         let (cell_state, hidden) = state;
-        let gates = hidden.mm_t(self.weight_hh.clone())
+        let gates = hidden.mm_t(self.weight_hh.clone()).into()
             + self.bias_hh.clone()
             + input.mm_t(self.weight_ih.clone()).into()
             + self.bias_ih.clone();
@@ -534,7 +584,7 @@ impl LSTMCell {
             chunked_gates[2].clone().sigmoid(),
             chunked_gates[3].clone().sigmoid(),
         );
-        let new_cell_state = forget_gate * cell_state + (input_gate * cell_state_gate);
+        let new_cell_state = cell_state * forget_gate + (input_gate * cell_state_gate);
         let new_hidden = output_gate * new_cell_state.clone().tanh();
 
         (new_cell_state, new_hidden)
@@ -592,22 +642,24 @@ impl GRUCell {
 
     /// Computes a single GRU step.
     ///
-    /// * `hidden` - a variable of shape *(batch, hidden_size)*, containing the initial hidden state
-    /// for each element in the batch.
+    /// * `hidden` - a variable of shape *(batch, hidden_size)*, either differentiable or not,
+    /// containing the initial hidden state for each element in the batch.
     ///
     /// * `input` - a variable containing the input features of shape *(batch, input_size)*.
     ///
     /// The output is a variable made of the next hidden state for each element in
     /// the batch, of shape *(batch, hidden_size)*.
-    pub fn forward<I>(&self, hidden: VarDiff<Ix2>, input: I) -> VarDiff<Ix2>
+    pub fn forward<H, I>(&self, hidden: H, input: I) -> VarDiff<Ix2>
     where
+        H: Clone + MatMatMulT<VarDiff<Ix2>> + Sub<VarDiff<Ix2>, Output = VarDiff<Ix2>>,
+        <H as MatMatMulT<VarDiff<Ix2>>>::Output: Into<VarDiff<Ix2>>,
         I: MatMatMulT<VarDiff<Ix2>>,
         I::Output: Into<VarDiff<Ix2>>,
     {
         let (igates, hgates) = {
             (
                 input.mm_t(self.weight_ih.clone()).into() + self.bias_ih.clone(),
-                hidden.clone().mm_t(self.weight_hh.clone()) + self.bias_hh.clone(),
+                hidden.clone().mm_t(self.weight_hh.clone()).into() + self.bias_hh.clone(),
             )
         };
         let gate_shape = {
@@ -625,6 +677,606 @@ impl GRUCell {
     }
 }
 
+/// An **Elman recurrent neural network (RNN)** cell with *tanh* non-linearity.
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[allow(clippy::upper_case_acronyms)]
+pub struct RNNCell {
+    pub weight_ih: VarDiff<Ix2>,
+    pub weight_hh: VarDiff<Ix2>,
+    pub bias_ih: VarDiff<Ix1>,
+    pub bias_hh: VarDiff<Ix1>,
+}
+
+impl RNNCell {
+    /// Creates a new RNNCell.
+    ///
+    /// # Arguments
+    ///
+    /// * `input_size` - number of expected features in the input.
+    ///
+    /// * `hidden_size` - number of features in the hidden state.
+    ///
+    /// All the weight and biases are initialized from *U(-k, k)* where
+    /// `k = (1. / hidden_size as f32).sqrt()`.
+    pub fn new(input_size: usize, hidden_size: usize) -> Self {
+        let weight_ih = neuronika_variable::zeros((hidden_size, input_size)).requires_grad();
+        let weight_hh = neuronika_variable::zeros((hidden_size, hidden_size)).requires_grad();
+        let bias_ih = neuronika_variable::zeros(hidden_size).requires_grad();
+        let bias_hh = neuronika_variable::zeros(hidden_size).requires_grad();
+
+        let k = 1. / (hidden_size as f32).sqrt();
+        init::uniform(&weight_ih, -k, k);
+        init::uniform(&weight_hh, -k, k);
+        init::uniform(&bias_ih, -k, k);
+        init::uniform(&bias_hh, -k, k);
+
+        Self {
+            weight_ih,
+            weight_hh,
+            bias_ih,
+            bias_hh,
+        }
+    }
+
+    /// Computes a single RNN step.
+    ///
+    /// * `hidden` - a variable of shape *(batch, hidden_size)*, either differentiable or not,
+    ///   containing the initial hidden state for each element in the batch.
+    ///
+    /// * `input` - a variable containing the input features of shape *(batch, input_size)*.
+    ///
+    /// The output is a variable made of the next hidden state for each element in
+    /// the batch, of shape *(batch, hidden_size)*.
+    pub fn forward<H, I>(&self, hidden: H, input: I) -> VarDiff<Ix2>
+    where
+        H: MatMatMulT<VarDiff<Ix2>>,
+        H::Output: Into<VarDiff<Ix2>>,
+        I: MatMatMulT<VarDiff<Ix2>>,
+        I::Output: Into<VarDiff<Ix2>>,
+    {
+        (input.mm_t(self.weight_ih.clone()).into()
+            + self.bias_ih.clone()
+            + hidden.mm_t(self.weight_hh.clone()).into()
+            + self.bias_hh.clone())
+        .tanh()
+    }
+}
+
+/// A recurrent cell that can be unrolled over a sequence.
+trait Recurrent {
+    type State: Clone;
+
+    /// Returns the number of features in the hidden state.
+    fn hidden_size(&self) -> usize;
+
+    /// Computes the state following `state` given `input`.
+    fn step(&self, state: Self::State, input: VarDiff<Ix2>) -> Self::State;
+
+    /// Computes the state following the zero state given `input`. The zero state is constant, so
+    /// that it doesn't become a parameter of the graph.
+    fn zero_step(&self, input: VarDiff<Ix2>) -> Self::State;
+
+    /// Returns the output of the cell in `state`.
+    fn output(state: &Self::State) -> VarDiff<Ix2>;
+}
+
+impl Recurrent for RNNCell {
+    type State = VarDiff<Ix2>;
+
+    fn hidden_size(&self) -> usize {
+        self.weight_hh.data().ncols()
+    }
+
+    fn step(&self, state: Self::State, input: VarDiff<Ix2>) -> Self::State {
+        self.forward(state, input)
+    }
+
+    fn zero_step(&self, input: VarDiff<Ix2>) -> Self::State {
+        let shape = (input.data().nrows(), self.hidden_size());
+        self.forward(neuronika_variable::zeros(shape), input)
+    }
+
+    fn output(state: &Self::State) -> VarDiff<Ix2> {
+        state.clone()
+    }
+}
+
+impl Recurrent for LSTMCell {
+    type State = (VarDiff<Ix2>, VarDiff<Ix2>);
+
+    fn hidden_size(&self) -> usize {
+        self.weight_hh.data().ncols()
+    }
+
+    fn step(&self, state: Self::State, input: VarDiff<Ix2>) -> Self::State {
+        self.forward(state, input)
+    }
+
+    fn zero_step(&self, input: VarDiff<Ix2>) -> Self::State {
+        let shape = (input.data().nrows(), self.hidden_size());
+        let state = (
+            neuronika_variable::zeros(shape),
+            neuronika_variable::zeros(shape),
+        );
+        self.forward(state, input)
+    }
+
+    fn output(state: &Self::State) -> VarDiff<Ix2> {
+        state.1.clone()
+    }
+}
+
+impl Recurrent for GRUCell {
+    type State = VarDiff<Ix2>;
+
+    fn hidden_size(&self) -> usize {
+        self.weight_hh.data().ncols()
+    }
+
+    fn step(&self, state: Self::State, input: VarDiff<Ix2>) -> Self::State {
+        self.forward(state, input)
+    }
+
+    fn zero_step(&self, input: VarDiff<Ix2>) -> Self::State {
+        let shape = (input.data().nrows(), self.hidden_size());
+        self.forward(neuronika_variable::zeros(shape), input)
+    }
+
+    fn output(state: &Self::State) -> VarDiff<Ix2> {
+        state.clone()
+    }
+}
+
+/// Creates the cells of a recurrent layer, ordered by layer and then by direction.
+fn recurrent_cells<C>(
+    input_size: usize,
+    hidden_size: usize,
+    num_layers: usize,
+    bidirectional: bool,
+    cell: fn(usize, usize) -> C,
+) -> Vec<C> {
+    let directions = 1 + bidirectional as usize;
+    (0..num_layers * directions)
+        .map(|i| {
+            let input_size = if i < directions {
+                input_size
+            } else {
+                hidden_size * directions
+            };
+            cell(input_size, hidden_size)
+        })
+        .collect()
+}
+
+/// Splits `sequence` along its first dimension.
+fn unstack(sequence: VarDiff<Ix3>) -> Vec<VarDiff<Ix2>> {
+    let (_, rows, cols) = sequence.data().dim();
+    sequence
+        .chunks((1, rows, cols))
+        .into_iter()
+        .map(|step| step.reshape((rows, cols)))
+        .collect()
+}
+
+/// Splits an initial state of a recurrent layer by layer and direction.
+fn initial_states(state: VarDiff<Ix3>, shape: (usize, usize, usize)) -> Vec<VarDiff<Ix2>> {
+    assert_eq!(
+        state.data().dim(),
+        shape,
+        "error: initial state of shape {:?} doesn't match the expected one.",
+        state.data().shape()
+    );
+    unstack(state)
+}
+
+/// Unrolls a stack of optionally bidirectional recurrent cells over `input`, starting from
+/// `states` or from the zero states if not given, and returns the output sequence and the final
+/// states.
+fn unroll<C>(
+    cells: &[C],
+    bidirectional: bool,
+    batch_first: bool,
+    (dropout, status): (f64, &Rc<Cell<bool>>),
+    input: VarDiff<Ix3>,
+    states: Option<Vec<C::State>>,
+) -> (VarDiff<Ix3>, Vec<C::State>)
+where
+    C: Recurrent,
+{
+    let directions = 1 + bidirectional as usize;
+    let input = if batch_first {
+        input.permute((1, 0, 2))
+    } else {
+        input
+    };
+    assert!(
+        input.data().len_of(Axis(0)) > 0,
+        "error: cannot unroll a recurrent layer over an empty sequence."
+    );
+
+    let mut steps = unstack(input);
+    let mut states = states.map(Vec::into_iter);
+    let mut final_states = Vec::with_capacity(cells.len());
+    for (layer, layer_cells) in cells.chunks(directions).enumerate() {
+        // Dropout is applied to the outputs of every layer but the last one.
+        if layer > 0 && dropout > 0. {
+            steps = steps
+                .into_iter()
+                .map(|step| step.dropout(dropout, status.clone()))
+                .collect();
+        }
+
+        let mut outputs = layer_cells.iter().enumerate().map(|(direction, cell)| {
+            let mut sequence = steps.to_vec();
+            if direction == 1 {
+                sequence.reverse();
+            }
+
+            let mut sequence = sequence.into_iter();
+            let first = sequence.next().unwrap();
+            let mut state = match states.as_mut() {
+                Some(states) => cell.step(states.next().unwrap(), first),
+                None => cell.zero_step(first),
+            };
+            let mut outputs = vec![C::output(&state)];
+            for step in sequence {
+                state = cell.step(state, step);
+                outputs.push(C::output(&state));
+            }
+            if direction == 1 {
+                outputs.reverse();
+            }
+            final_states.push(state);
+
+            outputs
+        });
+        let forward = outputs.next().unwrap();
+        steps = match outputs.next() {
+            Some(backward) => forward
+                .into_iter()
+                .zip(backward)
+                .map(|(forward, backward)| forward.cat(&[backward], 1))
+                .collect(),
+            None => forward,
+        };
+    }
+
+    let output = steps[0].clone().stack(&steps[1..], 0);
+    let output = if batch_first {
+        output.permute((1, 0, 2))
+    } else {
+        output
+    };
+
+    (output, final_states)
+}
+
+/// Returns the shape of the states of a recurrent layer made of `cells` given `input`.
+fn state_shape<C>(cells: &[C], batch_first: bool, input: &VarDiff<Ix3>) -> (usize, usize, usize)
+where
+    C: Recurrent,
+{
+    let (rows, cols, _) = input.data().dim();
+    let batch_size = if batch_first { rows } else { cols };
+
+    (cells.len(), batch_size, cells[0].hidden_size())
+}
+
+/// Stacks the hidden states of the layers and directions of a recurrent layer.
+fn stack_states(states: Vec<VarDiff<Ix2>>) -> VarDiff<Ix3> {
+    states[0].clone().stack(&states[1..], 0)
+}
+
+/// A multi-layer **Elman recurrent neural network (RNN)** with *tanh* non-linearity, applied to
+/// a whole sequence.
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[allow(clippy::upper_case_acronyms)]
+pub struct RNN {
+    pub cells: Vec<RNNCell>,
+    pub bidirectional: bool,
+    pub batch_first: bool,
+    pub dropout: f64,
+    #[cfg_attr(feature = "serialize", serde(skip, default = "training"))]
+    status: Rc<Cell<bool>>,
+}
+
+impl RNN {
+    /// Creates a new RNN.
+    ///
+    /// # Arguments
+    ///
+    /// * `input_size` - number of expected features in the input.
+    ///
+    /// * `hidden_size` - number of features in the hidden state.
+    ///
+    /// * `num_layers` - number of stacked recurrent layers.
+    ///
+    /// * `bidirectional` - whether each layer also processes the sequence backwards.
+    ///
+    /// * `batch_first` - whether the batch comes before the time steps in the input and output.
+    ///
+    /// * `dropout` - dropout probability of the outputs of every layer but the last one.
+    ///
+    /// The cells are ordered by layer and then by direction, and are initialized as in
+    /// [`RNNCell::new()`].
+    pub fn new(
+        input_size: usize,
+        hidden_size: usize,
+        num_layers: usize,
+        bidirectional: bool,
+        batch_first: bool,
+        dropout: f64,
+    ) -> Self {
+        Self {
+            cells: recurrent_cells(
+                input_size,
+                hidden_size,
+                num_layers,
+                bidirectional,
+                RNNCell::new,
+            ),
+            bidirectional,
+            batch_first,
+            dropout,
+            status: training(),
+        }
+    }
+
+    /// Switches the layer in training mode, enabling dropout.
+    pub fn train(&self) {
+        self.status.set(true);
+    }
+
+    /// Switches the layer in inference mode, disabling dropout.
+    pub fn eval(&self) {
+        self.status.set(false);
+    }
+
+    /// Applies the RNN to a sequence.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - a variable of shape *(T, N, input_size)*, or *(N, T, input_size)* if
+    ///   `batch_first` is set.
+    ///
+    /// * `hidden` - optional initial hidden state of shape
+    ///   *(num_layers × num_directions, N, hidden_size)*, zero if not given.
+    ///
+    /// The **output** is a tuple made of the hidden states of the last layer for every time step,
+    /// of shape *(T, N, num_directions × hidden_size)* or *(N, T, num_directions × hidden_size)*,
+    /// and the final hidden state, of the same shape of `hidden`.
+    ///
+    /// # Panics
+    ///
+    /// If the sequence is empty or if the initial hidden state doesn't have the expected shape.
+    pub fn forward(
+        &self,
+        input: VarDiff<Ix3>,
+        hidden: Option<VarDiff<Ix3>>,
+    ) -> (VarDiff<Ix3>, VarDiff<Ix3>) {
+        let shape = state_shape(&self.cells, self.batch_first, &input);
+        let (output, hidden) = unroll(
+            &self.cells,
+            self.bidirectional,
+            self.batch_first,
+            (self.dropout, &self.status),
+            input,
+            hidden.map(|hidden| initial_states(hidden, shape)),
+        );
+
+        (output, stack_states(hidden))
+    }
+}
+
+/// A multi-layer **long short-term memory (LSTM)** network, applied to a whole sequence.
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[allow(clippy::upper_case_acronyms)]
+pub struct LSTM {
+    pub cells: Vec<LSTMCell>,
+    pub bidirectional: bool,
+    pub batch_first: bool,
+    pub dropout: f64,
+    #[cfg_attr(feature = "serialize", serde(skip, default = "training"))]
+    status: Rc<Cell<bool>>,
+}
+
+impl LSTM {
+    /// Creates a new LSTM.
+    ///
+    /// # Arguments
+    ///
+    /// * `input_size` - number of expected features in the input.
+    ///
+    /// * `hidden_size` - number of features in the hidden state.
+    ///
+    /// * `num_layers` - number of stacked recurrent layers.
+    ///
+    /// * `bidirectional` - whether each layer also processes the sequence backwards.
+    ///
+    /// * `batch_first` - whether the batch comes before the time steps in the input and output.
+    ///
+    /// * `dropout` - dropout probability of the outputs of every layer but the last one.
+    ///
+    /// The cells are ordered by layer and then by direction, and are initialized as in
+    /// [`LSTMCell::new()`].
+    pub fn new(
+        input_size: usize,
+        hidden_size: usize,
+        num_layers: usize,
+        bidirectional: bool,
+        batch_first: bool,
+        dropout: f64,
+    ) -> Self {
+        Self {
+            cells: recurrent_cells(
+                input_size,
+                hidden_size,
+                num_layers,
+                bidirectional,
+                LSTMCell::new,
+            ),
+            bidirectional,
+            batch_first,
+            dropout,
+            status: training(),
+        }
+    }
+
+    /// Switches the layer in training mode, enabling dropout.
+    pub fn train(&self) {
+        self.status.set(true);
+    }
+
+    /// Switches the layer in inference mode, disabling dropout.
+    pub fn eval(&self) {
+        self.status.set(false);
+    }
+
+    /// Applies the LSTM to a sequence.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - a variable of shape *(T, N, input_size)*, or *(N, T, input_size)* if
+    ///   `batch_first` is set.
+    ///
+    /// * `state` - optional tuple made of the initial cell's state and the initial hidden state,
+    ///   both of shape *(num_layers × num_directions, N, hidden_size)*, zero if not given.
+    ///
+    /// The **output** is a tuple made of the hidden states of the last layer for every time step,
+    /// of shape *(T, N, num_directions × hidden_size)* or *(N, T, num_directions × hidden_size)*,
+    /// and of the final cell's state and hidden state, in the same layout of `state`.
+    ///
+    /// # Panics
+    ///
+    /// If the sequence is empty or if the initial states don't have the expected shape.
+    #[allow(clippy::type_complexity)]
+    pub fn forward(
+        &self,
+        input: VarDiff<Ix3>,
+        state: Option<(VarDiff<Ix3>, VarDiff<Ix3>)>,
+    ) -> (VarDiff<Ix3>, (VarDiff<Ix3>, VarDiff<Ix3>)) {
+        let shape = state_shape(&self.cells, self.batch_first, &input);
+        let states = state.map(|(cell_state, hidden)| {
+            initial_states(cell_state, shape)
+                .into_iter()
+                .zip(initial_states(hidden, shape))
+                .collect()
+        });
+
+        let (output, states) = unroll(
+            &self.cells,
+            self.bidirectional,
+            self.batch_first,
+            (self.dropout, &self.status),
+            input,
+            states,
+        );
+        let (cell_state, hidden) = states.into_iter().unzip();
+
+        (output, (stack_states(cell_state), stack_states(hidden)))
+    }
+}
+
+/// A multi-layer **gated recurrent unit (GRU)** network, applied to a whole sequence.
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[allow(clippy::upper_case_acronyms)]
+pub struct GRU {
+    pub cells: Vec<GRUCell>,
+    pub bidirectional: bool,
+    pub batch_first: bool,
+    pub dropout: f64,
+    #[cfg_attr(feature = "serialize", serde(skip, default = "training"))]
+    status: Rc<Cell<bool>>,
+}
+
+impl GRU {
+    /// Creates a new GRU.
+    ///
+    /// # Arguments
+    ///
+    /// * `input_size` - number of expected features in the input.
+    ///
+    /// * `hidden_size` - number of features in the hidden state.
+    ///
+    /// * `num_layers` - number of stacked recurrent layers.
+    ///
+    /// * `bidirectional` - whether each layer also processes the sequence backwards.
+    ///
+    /// * `batch_first` - whether the batch comes before the time steps in the input and output.
+    ///
+    /// * `dropout` - dropout probability of the outputs of every layer but the last one.
+    ///
+    /// The cells are ordered by layer and then by direction, and are initialized as in
+    /// [`GRUCell::new()`].
+    pub fn new(
+        input_size: usize,
+        hidden_size: usize,
+        num_layers: usize,
+        bidirectional: bool,
+        batch_first: bool,
+        dropout: f64,
+    ) -> Self {
+        Self {
+            cells: recurrent_cells(
+                input_size,
+                hidden_size,
+                num_layers,
+                bidirectional,
+                GRUCell::new,
+            ),
+            bidirectional,
+            batch_first,
+            dropout,
+            status: training(),
+        }
+    }
+
+    /// Switches the layer in training mode, enabling dropout.
+    pub fn train(&self) {
+        self.status.set(true);
+    }
+
+    /// Switches the layer in inference mode, disabling dropout.
+    pub fn eval(&self) {
+        self.status.set(false);
+    }
+
+    /// Applies the GRU to a sequence.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - a variable of shape *(T, N, input_size)*, or *(N, T, input_size)* if
+    ///   `batch_first` is set.
+    ///
+    /// * `hidden` - optional initial hidden state of shape
+    ///   *(num_layers × num_directions, N, hidden_size)*, zero if not given.
+    ///
+    /// The **output** is a tuple made of the hidden states of the last layer for every time step,
+    /// of shape *(T, N, num_directions × hidden_size)* or *(N, T, num_directions × hidden_size)*,
+    /// and the final hidden state, of the same shape of `hidden`.
+    ///
+    /// # Panics
+    ///
+    /// If the sequence is empty or if the initial hidden state doesn't have the expected shape.
+    pub fn forward(
+        &self,
+        input: VarDiff<Ix3>,
+        hidden: Option<VarDiff<Ix3>>,
+    ) -> (VarDiff<Ix3>, VarDiff<Ix3>) {
+        let shape = state_shape(&self.cells, self.batch_first, &input);
+        let (output, hidden) = unroll(
+            &self.cells,
+            self.bidirectional,
+            self.batch_first,
+            (self.dropout, &self.status),
+            input,
+            hidden.map(|hidden| initial_states(hidden, shape)),
+        );
+
+        (output, stack_states(hidden))
+    }
+}
+
 /// Applies a temporal convolution over an input signal composed of several input planes.
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 pub struct Conv1d<T>
@@ -914,3 +1566,934 @@ where
         todo!()
     }
 }
+
+/// **Upsamples** a multi-channel input signal of one, two or three spatial dimensions.
+///
+/// See [`VarDiff::interpolate()`] for the details of the interpolation algorithms.
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct Upsample<S>
+where
+    S: Dimension,
+{
+    pub size: Resize<S>,
+    pub mode: Interpolation,
+    pub align_corners: bool,
+}
+
+impl<S> Upsample<S>
+where
+    S: Dimension,
+{
+    /// Creates a new Upsample.
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - spatial size of the output or multiplier of that of the input.
+    ///
+    /// * `mode` - interpolation algorithm, it can be: [`Interpolation::Nearest`],
+    ///   [`Interpolation::Linear`], [`Interpolation::Bilinear`], [`Interpolation::Bicubic`] or
+    ///   [`Interpolation::Trilinear`].
+    ///
+    /// * `align_corners` - whether to align the centers of the corner elements of the input and
+    ///   of the output rather than their outer edges.
+    pub fn new(size: Resize<S>, mode: Interpolation, align_corners: bool) -> Self {
+        Self {
+            size,
+            mode,
+            align_corners,
+        }
+    }
+
+    /// Upsamples the input.
+    ///
+    /// # Arguments
+    ///
+    /// `input` - signal to upsample, of shape *(N, C, ...)* with as many spatial dimensions as
+    /// `S`.
+    pub fn forward<D>(&self, input: VarDiff<D>) -> VarDiff<D>
+    where
+        D: 'static + Dimension,
+        D::Smaller: RemoveAxis + Dimension<Smaller = S>,
+    {
+        input.interpolate(self.size.clone(), self.mode, self.align_corners)
+    }
+}
+
+/// Rearranges a variable of shape *(N, C × r², H, W)* into one of shape *(N, C, H × r, W × r)*,
+/// where *r* is the upscale factor.
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct PixelShuffle {
+    pub upscale_factor: usize,
+}
+
+impl PixelShuffle {
+    /// Creates a new PixelShuffle.
+    ///
+    /// # Arguments
+    ///
+    /// `upscale_factor` - factor to increase the spatial size by.
+    pub fn new(upscale_factor: usize) -> Self {
+        Self { upscale_factor }
+    }
+
+    /// Shuffles the input.
+    ///
+    /// # Arguments
+    ///
+    /// `input` - a variable of shape *(N, C × r², H, W)*, the output's shape will be
+    /// *(N, C, H × r, W × r)*.
+    pub fn forward(&self, input: VarDiff<Ix4>) -> VarDiff<Ix4> {
+        input.pixel_shuffle(self.upscale_factor)
+    }
+}
+
+/// Rearranges a variable of shape *(N, C, H × r, W × r)* into one of shape *(N, C × r², H, W)*,
+/// where *r* is the downscale factor. This is the inverse of [`PixelShuffle`].
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct PixelUnshuffle {
+    pub downscale_factor: usize,
+}
+
+impl PixelUnshuffle {
+    /// Creates a new PixelUnshuffle.
+    ///
+    /// # Arguments
+    ///
+    /// `downscale_factor` - factor to decrease the spatial size by.
+    pub fn new(downscale_factor: usize) -> Self {
+        Self { downscale_factor }
+    }
+
+    /// Unshuffles the input.
+    ///
+    /// # Arguments
+    ///
+    /// `input` - a variable of shape *(N, C, H × r, W × r)*, the output's shape will be
+    /// *(N, C × r², H, W)*.
+    pub fn forward(&self, input: VarDiff<Ix4>) -> VarDiff<Ix4> {
+        input.pixel_unshuffle(self.downscale_factor)
+    }
+}
+
+/// Returns the status of a freshly created layer, which starts in training mode.
+fn training() -> Rc<Cell<bool>> {
+    Rc::new(Cell::new(true))
+}
+
+/// Applies `linear` to the last dimension of `input`.
+fn linear_nd<D>(linear: &Linear, input: VarDiff<D>) -> VarDiff<D>
+where
+    D: 'static + Dimension,
+{
+    let mut shape = input.data().raw_dim();
+    let last = shape.ndim() - 1;
+    let features = shape[last];
+    shape[last] = linear.weight.data().nrows();
+
+    linear
+        .forward(input.reshape((shape.size() / shape[last], features)))
+        .reshape(shape)
+}
+
+/// Applies **layer normalization** over the last dimension of the input.
+///
+/// ```text
+/// ʏ = (x - E[x]) / √(Var[x] + ε) * γ + β
+/// ```
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct LayerNorm {
+    pub weight: VarDiff<Ix1>,
+    pub bias: VarDiff<Ix1>,
+    pub eps: f32,
+}
+
+impl LayerNorm {
+    /// Creates a new LayerNorm.
+    ///
+    /// # Arguments
+    ///
+    /// `normalized_shape` - size of the last dimension of the input.
+    ///
+    /// The learnable weight γ is initialized with ones and the learnable bias β with zeros, ε is
+    /// `1e-5`.
+    pub fn new(normalized_shape: usize) -> Self {
+        Self {
+            weight: neuronika_variable::ones(normalized_shape).requires_grad(),
+            bias: neuronika_variable::zeros(normalized_shape).requires_grad(),
+            eps: 1e-5,
+        }
+    }
+
+    /// Normalizes the input.
+    ///
+    /// # Arguments
+    ///
+    /// `input` - a variable of shape *(\*, normalized_shape)*, the output has the same shape.
+    pub fn forward<D>(&self, input: VarDiff<D>) -> VarDiff<D>
+    where
+        D: 'static + Dimension,
+    {
+        let shape = input.data().raw_dim();
+        let features = shape[shape.ndim() - 1];
+        let input = input.reshape((shape.size() / features, features));
+
+        // Means along the last dimension are computed as products by a column of averaging
+        // weights.
+        let averaging = neuronika_variable::full((features, 1), 1. / features as f32);
+        let centered = input.clone() - input.mm(averaging.clone());
+        let variance = centered.clone().pow(2).mm(averaging);
+        let normalized = centered / (variance + self.eps).sqrt();
+
+        (normalized * self.weight.clone() + self.bias.clone()).reshape(shape)
+    }
+}
+
+/// Input projections of the queries, keys and values of a [`MultiheadAttention`].
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub enum InProjection {
+    /// A single weight of shape *(3 × embed_dim, embed_dim)* packing those of the queries, keys
+    /// and values, in this order.
+    Packed(VarDiff<Ix2>),
+    /// Distinct weights of shapes *(embed_dim, embed_dim)*, *(embed_dim, kdim)* and
+    /// *(embed_dim, vdim)*, allowing keys and values of different sizes.
+    Separate(Box<SeparateInProjection>),
+}
+
+/// Distinct input projections of the queries, keys and values of a [`MultiheadAttention`].
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct SeparateInProjection {
+    pub query_weight: VarDiff<Ix2>,
+    pub key_weight: VarDiff<Ix2>,
+    pub value_weight: VarDiff<Ix2>,
+}
+
+/// Allows the model to jointly attend to information from different representation subspaces,
+/// as described in the paper [Attention Is All You Need](https://arxiv.org/abs/1706.03762).
+///
+/// ```text
+/// MultiHead(Q, K, V) = Concat(head₁, …, headₕ)Wᴼ, where headᵢ = Attention(QWᵢᵠ, KWᵢᴷ, VWᵢⱽ)
+/// ```
+///
+/// The attention of every head is computed by [`Attention::scaled_dot_product_attention()`].
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct MultiheadAttention {
+    pub num_heads: usize,
+    pub dropout: f64,
+    pub in_proj: InProjection,
+    pub in_proj_bias: Option<VarDiff<Ix1>>,
+    pub out_proj_weight: VarDiff<Ix2>,
+    pub out_proj_bias: Option<VarDiff<Ix1>>,
+    #[cfg_attr(feature = "serialize", serde(skip, default = "training"))]
+    status: Rc<Cell<bool>>,
+}
+
+impl MultiheadAttention {
+    /// Creates a new MultiheadAttention whose input projections are packed in a single weight.
+    ///
+    /// # Arguments
+    ///
+    /// * `embed_dim` - total dimension of the model, split across the heads.
+    ///
+    /// * `num_heads` - number of parallel attention heads.
+    ///
+    /// * `dropout` - dropout probability of the attention weights.
+    ///
+    /// * `bias` - whether to add a learnable bias to the input and output projections.
+    ///
+    /// The projection weights are initialized with the Xavier uniform initialization, the biases
+    /// with zeros.
+    ///
+    /// # Panics
+    ///
+    /// If `embed_dim` is not divisible by `num_heads`.
+    pub fn new(embed_dim: usize, num_heads: usize, dropout: f64, bias: bool) -> Self {
+        let weight = neuronika_variable::zeros((3 * embed_dim, embed_dim)).requires_grad();
+        init::xavier_uniform(&weight, 1.);
+
+        Self::with_in_proj(
+            embed_dim,
+            num_heads,
+            dropout,
+            InProjection::Packed(weight),
+            bias,
+        )
+    }
+
+    /// Creates a new MultiheadAttention with distinct projections for the queries, the keys and
+    /// the values.
+    ///
+    /// # Arguments
+    ///
+    /// * `embed_dim` - total dimension of the model, split across the heads.
+    ///
+    /// * `num_heads` - number of parallel attention heads.
+    ///
+    /// * `kdim` - number of features of the keys.
+    ///
+    /// * `vdim` - number of features of the values.
+    ///
+    /// * `dropout` - dropout probability of the attention weights.
+    ///
+    /// * `bias` - whether to add a learnable bias to the input and output projections.
+    ///
+    /// The projection weights are initialized with the Xavier uniform initialization, the biases
+    /// with zeros.
+    ///
+    /// # Panics
+    ///
+    /// If `embed_dim` is not divisible by `num_heads`.
+    pub fn separate(
+        embed_dim: usize,
+        num_heads: usize,
+        kdim: usize,
+        vdim: usize,
+        dropout: f64,
+        bias: bool,
+    ) -> Self {
+        let [query_weight, key_weight, value_weight] = [embed_dim, kdim, vdim].map(|features| {
+            let weight = neuronika_variable::zeros((embed_dim, features)).requires_grad();
+            init::xavier_uniform(&weight, 1.);
+            weight
+        });
+
+        Self::with_in_proj(
+            embed_dim,
+            num_heads,
+            dropout,
+            InProjection::Separate(Box::new(SeparateInProjection {
+                query_weight,
+                key_weight,
+                value_weight,
+            })),
+            bias,
+        )
+    }
+
+    fn with_in_proj(
+        embed_dim: usize,
+        num_heads: usize,
+        dropout: f64,
+        in_proj: InProjection,
+        bias: bool,
+    ) -> Self {
+        assert!(
+            num_heads > 0 && embed_dim.is_multiple_of(num_heads),
+            "error: embed_dim {} is not divisible by num_heads {}.",
+            embed_dim,
+            num_heads
+        );
+
+        let out_proj_weight = neuronika_variable::zeros((embed_dim, embed_dim)).requires_grad();
+        let k = (1. / (embed_dim as f32)).sqrt();
+        init::uniform(&out_proj_weight, -k, k);
+        let in_proj_bias = bias.then(|| neuronika_variable::zeros(3 * embed_dim).requires_grad());
+        let out_proj_bias = bias.then(|| neuronika_variable::zeros(embed_dim).requires_grad());
+
+        Self {
+            num_heads,
+            dropout,
+            in_proj,
+            in_proj_bias,
+            out_proj_weight,
+            out_proj_bias,
+            status: training(),
+        }
+    }
+
+    /// Switches the layer in training mode, enabling dropout.
+    pub fn train(&self) {
+        self.status.set(true);
+    }
+
+    /// Switches the layer in inference mode, disabling dropout.
+    pub fn eval(&self) {
+        self.status.set(false);
+    }
+
+    /// Computes the multi-head attention of `query` over `key` and `value`.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - a variable of shape *(N, L, embed_dim)*.
+    ///
+    /// * `key` - a variable of shape *(N, S, kdim)*.
+    ///
+    /// * `value` - a variable of shape *(N, S, vdim)*.
+    ///
+    /// * `key_padding_mask` - optional mask of shape *(N, S)*, the keys set to `true` are padding
+    ///   and are ignored.
+    ///
+    /// * `attn_mask` - optional mask broadcastable to *(N, num_heads, L, S)*.
+    ///
+    /// * `causal` - whether to prevent each query from attending to the keys past its position.
+    ///
+    /// The output's shape is *(N, L, embed_dim)*. Queries that cannot attend to any key result in
+    /// the output bias.
+    pub fn forward(
+        &self,
+        query: VarDiff<Ix3>,
+        key: VarDiff<Ix3>,
+        value: VarDiff<Ix3>,
+        key_padding_mask: Option<Var<Ix2, bool>>,
+        attn_mask: Option<AttentionMask<f32>>,
+        causal: bool,
+    ) -> VarDiff<Ix3> {
+        let (batch_size, length, embed_dim) = query.data().dim();
+        let head_dim = embed_dim / self.num_heads;
+
+        let weights = match &self.in_proj {
+            InProjection::Packed(weight) => weight.clone().chunks((embed_dim, embed_dim)),
+            InProjection::Separate(projection) => vec![
+                projection.query_weight.clone(),
+                projection.key_weight.clone(),
+                projection.value_weight.clone(),
+            ],
+        };
+        let mut biases = match self.in_proj_bias.clone() {
+            Some(bias) => bias.chunks(embed_dim).into_iter().map(Some).collect(),
+            None => vec![None; 3],
+        };
+        // Splits the projections of the inputs across the heads.
+        let mut projections = [query, key, value]
+            .into_iter()
+            .zip(weights)
+            .zip(biases.drain(..))
+            .map(|((input, weight), bias)| {
+                let (batch_size, length, features) = input.data().dim();
+                let mut projection = input.reshape((batch_size * length, features)).mm_t(weight);
+                if let Some(bias) = bias {
+                    projection = projection + bias;
+                }
+
+                projection
+                    .reshape((batch_size, length, self.num_heads, head_dim))
+                    .permute((0, 2, 1, 3))
+            })
+            .collect::<Vec<_>>();
+        let (value, key, query) = (
+            projections.pop().unwrap(),
+            projections.pop().unwrap(),
+            projections.pop().unwrap(),
+        );
+
+        let mask = match key_padding_mask {
+            Some(key_padding_mask) => {
+                let key_length = key_padding_mask.data().ncols();
+                let padding = neuronika_variable::zeros((batch_size, 1, 1, key_length))
+                    .masked_fill(
+                        key_padding_mask.reshape((batch_size, 1, 1, key_length)),
+                        f32::NEG_INFINITY,
+                    );
+                let mask = match attn_mask {
+                    Some(AttentionMask::Boolean(mask)) => {
+                        let shape = mask.data().raw_dim();
+                        neuronika_variable::full(shape, f32::NEG_INFINITY).masked_fill(mask, 0.)
+                            + padding
+                    }
+                    Some(AttentionMask::Additive(mask)) => mask + padding,
+                    None => padding.into_dyn(),
+                };
+
+                Some(AttentionMask::Additive(mask))
+            }
+            None => attn_mask,
+        };
+
+        let mut output = query
+            .scaled_dot_product_attention(
+                key,
+                value,
+                mask,
+                self.dropout,
+                self.status.clone(),
+                causal,
+            )
+            .permute((0, 2, 1, 3))
+            .reshape((batch_size * length, embed_dim))
+            .mm_t(self.out_proj_weight.clone());
+        if let Some(bias) = &self.out_proj_bias {
+            output = output + bias.clone();
+        }
+
+        output.reshape((batch_size, length, embed_dim))
+    }
+}
+
+/// A **Transformer encoder layer**, made up of self-attention and a feedforward network, as
+/// described in the paper [Attention Is All You Need](https://arxiv.org/abs/1706.03762).
+///
+/// With post-normalization, the default, each block is followed by a layer normalization of the
+/// residual sum, with pre-normalization the input of each block is normalized instead.
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct TransformerEncoderLayer {
+    pub self_attn: MultiheadAttention,
+    pub linear1: Linear,
+    pub linear2: Linear,
+    pub norm1: LayerNorm,
+    pub norm2: LayerNorm,
+    pub dropout: f64,
+    pub norm_first: bool,
+    #[cfg_attr(feature = "serialize", serde(skip, default = "training"))]
+    status: Rc<Cell<bool>>,
+}
+
+impl TransformerEncoderLayer {
+    /// Creates a new TransformerEncoderLayer.
+    ///
+    /// # Arguments
+    ///
+    /// * `d_model` - number of expected features in the input.
+    ///
+    /// * `nhead` - number of heads of the self-attention.
+    ///
+    /// * `dim_feedforward` - dimension of the feedforward network.
+    ///
+    /// * `dropout` - dropout probability.
+    ///
+    /// * `norm_first` - whether to normalize the inputs of the blocks rather than their outputs.
+    pub fn new(
+        d_model: usize,
+        nhead: usize,
+        dim_feedforward: usize,
+        dropout: f64,
+        norm_first: bool,
+    ) -> Self {
+        Self {
+            self_attn: MultiheadAttention::new(d_model, nhead, dropout, true),
+            linear1: Linear::new(d_model, dim_feedforward),
+            linear2: Linear::new(dim_feedforward, d_model),
+            norm1: LayerNorm::new(d_model),
+            norm2: LayerNorm::new(d_model),
+            dropout,
+            norm_first,
+            status: training(),
+        }
+    }
+
+    /// Switches the layer in training mode, enabling dropout.
+    pub fn train(&self) {
+        self.status.set(true);
+        self.self_attn.train();
+    }
+
+    /// Switches the layer in inference mode, disabling dropout.
+    pub fn eval(&self) {
+        self.status.set(false);
+        self.self_attn.eval();
+    }
+
+    /// Passes the input through the encoder layer.
+    ///
+    /// # Arguments
+    ///
+    /// * `src` - a variable of shape *(N, S, d_model)*, the output has the same shape.
+    ///
+    /// * `src_mask` - optional mask of the self-attention.
+    ///
+    /// * `src_key_padding_mask` - optional mask of shape *(N, S)*, the positions set to `true`
+    ///   are padding and are ignored.
+    pub fn forward(
+        &self,
+        src: VarDiff<Ix3>,
+        src_mask: Option<AttentionMask<f32>>,
+        src_key_padding_mask: Option<Var<Ix2, bool>>,
+    ) -> VarDiff<Ix3> {
+        let attention = |input: VarDiff<Ix3>| {
+            self.self_attn
+                .forward(
+                    input.clone(),
+                    input.clone(),
+                    input,
+                    src_key_padding_mask,
+                    src_mask,
+                    false,
+                )
+                .dropout(self.dropout, self.status.clone())
+        };
+
+        if self.norm_first {
+            let x = src.clone() + attention(self.norm1.forward(src));
+            x.clone() + self.feedforward(self.norm2.forward(x))
+        } else {
+            let x = self.norm1.forward(src.clone() + attention(src));
+            self.norm2.forward(x.clone() + self.feedforward(x))
+        }
+    }
+
+    fn feedforward(&self, input: VarDiff<Ix3>) -> VarDiff<Ix3> {
+        let hidden = linear_nd(&self.linear1, input)
+            .relu()
+            .dropout(self.dropout, self.status.clone());
+
+        linear_nd(&self.linear2, hidden).dropout(self.dropout, self.status.clone())
+    }
+}
+
+/// A **Transformer decoder layer**, made up of self-attention, attention over the output of the
+/// encoder and a feedforward network, as described in the paper
+/// [Attention Is All You Need](https://arxiv.org/abs/1706.03762).
+///
+/// With post-normalization, the default, each block is followed by a layer normalization of the
+/// residual sum, with pre-normalization the input of each block is normalized instead.
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct TransformerDecoderLayer {
+    pub self_attn: MultiheadAttention,
+    pub multihead_attn: MultiheadAttention,
+    pub linear1: Linear,
+    pub linear2: Linear,
+    pub norm1: LayerNorm,
+    pub norm2: LayerNorm,
+    pub norm3: LayerNorm,
+    pub dropout: f64,
+    pub norm_first: bool,
+    #[cfg_attr(feature = "serialize", serde(skip, default = "training"))]
+    status: Rc<Cell<bool>>,
+}
+
+impl TransformerDecoderLayer {
+    /// Creates a new TransformerDecoderLayer.
+    ///
+    /// # Arguments
+    ///
+    /// * `d_model` - number of expected features in the input.
+    ///
+    /// * `nhead` - number of heads of the attentions.
+    ///
+    /// * `dim_feedforward` - dimension of the feedforward network.
+    ///
+    /// * `dropout` - dropout probability.
+    ///
+    /// * `norm_first` - whether to normalize the inputs of the blocks rather than their outputs.
+    pub fn new(
+        d_model: usize,
+        nhead: usize,
+        dim_feedforward: usize,
+        dropout: f64,
+        norm_first: bool,
+    ) -> Self {
+        Self {
+            self_attn: MultiheadAttention::new(d_model, nhead, dropout, true),
+            multihead_attn: MultiheadAttention::new(d_model, nhead, dropout, true),
+            linear1: Linear::new(d_model, dim_feedforward),
+            linear2: Linear::new(dim_feedforward, d_model),
+            norm1: LayerNorm::new(d_model),
+            norm2: LayerNorm::new(d_model),
+            norm3: LayerNorm::new(d_model),
+            dropout,
+            norm_first,
+            status: training(),
+        }
+    }
+
+    /// Switches the layer in training mode, enabling dropout.
+    pub fn train(&self) {
+        self.status.set(true);
+        self.self_attn.train();
+        self.multihead_attn.train();
+    }
+
+    /// Switches the layer in inference mode, disabling dropout.
+    pub fn eval(&self) {
+        self.status.set(false);
+        self.self_attn.eval();
+        self.multihead_attn.eval();
+    }
+
+    /// Passes the inputs through the decoder layer.
+    ///
+    /// # Arguments
+    ///
+    /// * `tgt` - a variable of shape *(N, T, d_model)*, the output has the same shape.
+    ///
+    /// * `memory` - output of the encoder, of shape *(N, S, d_model)*.
+    ///
+    /// * `tgt_mask` - optional mask of the self-attention, see [`causal_mask()`] to prevent the
+    ///   positions from attending to the following ones.
+    ///
+    /// * `memory_mask` - optional mask of the attention over `memory`.
+    ///
+    /// * `tgt_key_padding_mask` - optional mask of shape *(N, T)*, the positions set to `true`
+    ///   are padding and are ignored.
+    ///
+    /// * `memory_key_padding_mask` - optional mask of shape *(N, S)*, the positions set to `true`
+    ///   are padding and are ignored.
+    pub fn forward(
+        &self,
+        tgt: VarDiff<Ix3>,
+        memory: VarDiff<Ix3>,
+        tgt_mask: Option<AttentionMask<f32>>,
+        memory_mask: Option<AttentionMask<f32>>,
+        tgt_key_padding_mask: Option<Var<Ix2, bool>>,
+        memory_key_padding_mask: Option<Var<Ix2, bool>>,
+    ) -> VarDiff<Ix3> {
+        let self_attention = |input: VarDiff<Ix3>| {
+            self.self_attn
+                .forward(
+                    input.clone(),
+                    input.clone(),
+                    input,
+                    tgt_key_padding_mask,
+                    tgt_mask,
+                    false,
+                )
+                .dropout(self.dropout, self.status.clone())
+        };
+        let attention = |input: VarDiff<Ix3>| {
+            self.multihead_attn
+                .forward(
+                    input,
+                    memory.clone(),
+                    memory,
+                    memory_key_padding_mask,
+                    memory_mask,
+                    false,
+                )
+                .dropout(self.dropout, self.status.clone())
+        };
+
+        if self.norm_first {
+            let x = tgt.clone() + self_attention(self.norm1.forward(tgt));
+            let x = x.clone() + attention(self.norm2.forward(x));
+            x.clone() + self.feedforward(self.norm3.forward(x))
+        } else {
+            let x = self.norm1.forward(tgt.clone() + self_attention(tgt));
+            let x = self.norm2.forward(x.clone() + attention(x));
+            self.norm3.forward(x.clone() + self.feedforward(x))
+        }
+    }
+
+    fn feedforward(&self, input: VarDiff<Ix3>) -> VarDiff<Ix3> {
+        let hidden = linear_nd(&self.linear1, input)
+            .relu()
+            .dropout(self.dropout, self.status.clone());
+
+        linear_nd(&self.linear2, hidden).dropout(self.dropout, self.status.clone())
+    }
+}
+
+/// Returns a boolean mask of shape *(length, length)* allowing each position to attend only to
+/// itself and to the ones preceding it.
+pub fn causal_mask(length: usize) -> AttentionMask<f32> {
+    AttentionMask::boolean(neuronika_variable::from_ndarray(Array2::from_shape_fn(
+        (length, length),
+        |(i, j)| j <= i,
+    )))
+}
+
+/// A stack of [`TransformerEncoderLayer`], optionally followed by a layer normalization.
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct TransformerEncoder {
+    pub layers: Vec<TransformerEncoderLayer>,
+    pub norm: Option<LayerNorm>,
+}
+
+impl TransformerEncoder {
+    /// Creates a new TransformerEncoder.
+    ///
+    /// # Arguments
+    ///
+    /// * `layers` - encoder layers, applied in order.
+    ///
+    /// * `norm` - optional final layer normalization, usually needed by pre-normalized layers.
+    pub fn new(layers: Vec<TransformerEncoderLayer>, norm: Option<LayerNorm>) -> Self {
+        Self { layers, norm }
+    }
+
+    /// Switches the encoder in training mode, enabling dropout.
+    pub fn train(&self) {
+        self.layers.iter().for_each(TransformerEncoderLayer::train);
+    }
+
+    /// Switches the encoder in inference mode, disabling dropout.
+    pub fn eval(&self) {
+        self.layers.iter().for_each(TransformerEncoderLayer::eval);
+    }
+
+    /// Passes the input through the encoder layers in turn.
+    ///
+    /// See [`TransformerEncoderLayer::forward()`] for the arguments.
+    pub fn forward(
+        &self,
+        src: VarDiff<Ix3>,
+        mask: Option<AttentionMask<f32>>,
+        src_key_padding_mask: Option<Var<Ix2, bool>>,
+    ) -> VarDiff<Ix3> {
+        let output = self.layers.iter().fold(src, |output, layer| {
+            layer.forward(output, mask.clone(), src_key_padding_mask.clone())
+        });
+
+        match &self.norm {
+            Some(norm) => norm.forward(output),
+            None => output,
+        }
+    }
+}
+
+/// A stack of [`TransformerDecoderLayer`], optionally followed by a layer normalization.
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct TransformerDecoder {
+    pub layers: Vec<TransformerDecoderLayer>,
+    pub norm: Option<LayerNorm>,
+}
+
+impl TransformerDecoder {
+    /// Creates a new TransformerDecoder.
+    ///
+    /// # Arguments
+    ///
+    /// * `layers` - decoder layers, applied in order.
+    ///
+    /// * `norm` - optional final layer normalization, usually needed by pre-normalized layers.
+    pub fn new(layers: Vec<TransformerDecoderLayer>, norm: Option<LayerNorm>) -> Self {
+        Self { layers, norm }
+    }
+
+    /// Switches the decoder in training mode, enabling dropout.
+    pub fn train(&self) {
+        self.layers.iter().for_each(TransformerDecoderLayer::train);
+    }
+
+    /// Switches the decoder in inference mode, disabling dropout.
+    pub fn eval(&self) {
+        self.layers.iter().for_each(TransformerDecoderLayer::eval);
+    }
+
+    /// Passes the inputs through the decoder layers in turn.
+    ///
+    /// See [`TransformerDecoderLayer::forward()`] for the arguments.
+    pub fn forward(
+        &self,
+        tgt: VarDiff<Ix3>,
+        memory: VarDiff<Ix3>,
+        tgt_mask: Option<AttentionMask<f32>>,
+        memory_mask: Option<AttentionMask<f32>>,
+        tgt_key_padding_mask: Option<Var<Ix2, bool>>,
+        memory_key_padding_mask: Option<Var<Ix2, bool>>,
+    ) -> VarDiff<Ix3> {
+        let output = self.layers.iter().fold(tgt, |output, layer| {
+            layer.forward(
+                output,
+                memory.clone(),
+                tgt_mask.clone(),
+                memory_mask.clone(),
+                tgt_key_padding_mask.clone(),
+                memory_key_padding_mask.clone(),
+            )
+        });
+
+        match &self.norm {
+            Some(norm) => norm.forward(output),
+            None => output,
+        }
+    }
+}
+
+/// Adds **sinusoidal positional encodings** to a sequence, as described in the paper
+/// [Attention Is All You Need](https://arxiv.org/abs/1706.03762).
+///
+/// ```text
+/// PE(pos, 2i) = sin(pos / 10000^(2i / d_model))
+/// PE(pos, 2i + 1) = cos(pos / 10000^(2i / d_model))
+/// ```
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct SinusoidalPositionalEncoding {
+    pub encoding: Array2<f32>,
+}
+
+impl SinusoidalPositionalEncoding {
+    /// Creates a new SinusoidalPositionalEncoding.
+    ///
+    /// # Arguments
+    ///
+    /// * `d_model` - number of features of the sequences.
+    ///
+    /// * `max_len` - maximum length of the sequences.
+    pub fn new(d_model: usize, max_len: usize) -> Self {
+        let encoding = Array2::from_shape_fn((max_len, d_model), |(position, feature)| {
+            let exponent = (feature - feature % 2) as f32 / d_model as f32;
+            let angle = position as f32 / 10000_f32.powf(exponent);
+            if feature % 2 == 0 {
+                angle.sin()
+            } else {
+                angle.cos()
+            }
+        });
+
+        Self { encoding }
+    }
+
+    /// Adds the encodings to the input.
+    ///
+    /// # Arguments
+    ///
+    /// `input` - a variable of shape *(N, L, d_model)*, the output has the same shape.
+    ///
+    /// # Panics
+    ///
+    /// If the sequences are longer than `max_len`.
+    pub fn forward(&self, input: VarDiff<Ix3>) -> VarDiff<Ix3> {
+        let length = input.data().dim().1;
+        assert!(
+            length <= self.encoding.nrows(),
+            "error: sequences of length {} exceed the maximum length {}.",
+            length,
+            self.encoding.nrows()
+        );
+
+        input + neuronika_variable::from_ndarray(self.encoding.slice(s![..length, ..]).to_owned())
+    }
+}
+
+/// Adds **learned positional encodings** to a sequence.
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct LearnedPositionalEncoding {
+    pub weight: VarDiff<Ix2>,
+}
+
+impl LearnedPositionalEncoding {
+    /// Creates a new LearnedPositionalEncoding.
+    ///
+    /// # Arguments
+    ///
+    /// * `d_model` - number of features of the sequences.
+    ///
+    /// * `max_len` - maximum length of the sequences.
+    ///
+    /// The learnable weight of shape `(max_len, d_model)` is initialized from *N(0, 0.02²)*.
+    pub fn new(d_model: usize, max_len: usize) -> Self {
+        let weight = neuronika_variable::zeros((max_len, d_model)).requires_grad();
+        init::normal(&weight, 0., 0.02);
+
+        Self { weight }
+    }
+
+    /// Adds the encodings to the input.
+    ///
+    /// # Arguments
+    ///
+    /// `input` - a variable of shape *(N, L, d_model)*, the output has the same shape.
+    ///
+    /// # Panics
+    ///
+    /// If the sequences are longer than `max_len`.
+    pub fn forward(&self, input: VarDiff<Ix3>) -> VarDiff<Ix3> {
+        let length = input.data().dim().1;
+        let (max_len, d_model) = self.weight.data().dim();
+        assert!(
+            length <= max_len,
+            "error: sequences of length {} exceed the maximum length {}.",
+            length,
+            max_len
+        );
+
+        let positions = Array2::from_shape_fn((length, d_model), |(position, _)| position);
+        input
+            + self
+                .weight
+                .clone()
+                .gather(0, neuronika_variable::from_ndarray(positions))
+    }
+}
+
+#[cfg(test)]
+mod test;
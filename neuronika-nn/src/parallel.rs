@@ -0,0 +1,85 @@
+use ndarray::{Dimension, Ix3, Ix4, Ix5};
+
+use neuronika_variable::{PaddingMode, Parameter, Replicate, VarDiff};
+
+use crate::{Conv1d, Conv2d, Conv3d, GRUCell, LSTMCell, Linear, RNNCell};
+
+/// Returns a new differentiable leaf holding a copy of the data of `parameter`.
+fn replicate<D: 'static + Dimension>(parameter: &VarDiff<D>) -> VarDiff<D> {
+    neuronika_variable::from_ndarray(parameter.data().clone()).requires_grad()
+}
+
+impl Replicate for Linear {
+    type Elem = f32;
+
+    fn replicate(&self) -> Self {
+        Self {
+            weight: replicate(&self.weight),
+            bias: replicate(&self.bias),
+        }
+    }
+
+    fn parameters(&self) -> Vec<Parameter> {
+        vec![self.weight.clone().into(), self.bias.clone().into()]
+    }
+}
+
+macro_rules! impl_replicate_recurrent {
+    ($($cell:ident),*) => {
+        $(
+            impl Replicate for $cell {
+                type Elem = f32;
+
+                fn replicate(&self) -> Self {
+                    Self {
+                        weight_ih: replicate(&self.weight_ih),
+                        weight_hh: replicate(&self.weight_hh),
+                        bias_ih: replicate(&self.bias_ih),
+                        bias_hh: replicate(&self.bias_hh),
+                    }
+                }
+
+                fn parameters(&self) -> Vec<Parameter> {
+                    vec![
+                        self.weight_ih.clone().into(),
+                        self.weight_hh.clone().into(),
+                        self.bias_ih.clone().into(),
+                        self.bias_hh.clone().into(),
+                    ]
+                }
+            }
+        )*
+    };
+}
+
+impl_replicate_recurrent!(LSTMCell, GRUCell, RNNCell);
+
+macro_rules! impl_replicate_conv {
+    ($($conv:ident, $dim:ty);*) => {
+        $(
+            impl<T> Replicate for $conv<T>
+            where
+                T: PaddingMode<$dim>,
+            {
+                type Elem = f32;
+
+                fn replicate(&self) -> Self {
+                    Self {
+                        padding: self.padding,
+                        padding_mode: self.padding_mode,
+                        stride: self.stride,
+                        dilation: self.dilation,
+                        weight: replicate(&self.weight),
+                        bias: replicate(&self.bias),
+                    }
+                }
+
+                fn parameters(&self) -> Vec<Parameter> {
+                    vec![self.weight.clone().into(), self.bias.clone().into()]
+                }
+            }
+        )*
+    };
+}
+
+impl_replicate_conv!(Conv1d, Ix3; Conv2d, Ix4; Conv3d, Ix5);
@@ -0,0 +1,693 @@
+use ndarray::{array, s, Array, Array2, Array3, Dimension, Ix2, Ix3};
+
+use neuronika_core::{
+    cell::{Cell, Rc},
+    Attention,
+};
+use neuronika_variable::{Interpolation, Parameter, Resize, VarDiff};
+
+use crate::{
+    causal_mask, InProjection, LayerNorm, LearnedPositionalEncoding, MultiheadAttention,
+    PixelShuffle, PixelUnshuffle, SinusoidalPositionalEncoding, TransformerDecoder,
+    TransformerDecoderLayer, TransformerEncoder, TransformerEncoderLayer, Upsample, GRU, LSTM, RNN,
+};
+
+/// Returns a differentiable sequence of shape `shape` filled with deterministic values.
+fn sequence(shape: (usize, usize, usize), step: f32) -> VarDiff<Ix3> {
+    let (batch_size, length, features) = shape;
+    let data =
+        Array::from_iter((0..batch_size * length * features).map(|i| (i as f32 * step).sin()))
+            .into_shape(shape)
+            .unwrap();
+
+    neuronika_variable::from_ndarray(data).requires_grad()
+}
+
+/// Asserts that the two arrays have the same shape and are element-wise close.
+fn assert_close<D: Dimension>(result: &Array<f32, D>, expected: &Array<f32, D>) {
+    assert_eq!(result.shape(), expected.shape());
+    assert!(
+        result
+            .iter()
+            .zip(expected)
+            .all(|(result, expected)| (result - expected).abs() <= 1e-5),
+        "result: {} | expected: {}",
+        result,
+        expected
+    );
+}
+
+#[test]
+fn multihead_attention() {
+    let attention = MultiheadAttention::new(4, 2, 0., true);
+    let (query, key) = (sequence((2, 3, 4), 0.3), sequence((2, 5, 4), 0.7));
+    let output = attention.forward(query, key.clone(), key, None, None, false);
+    output.forward();
+    assert_eq!(output.data().dim(), (2, 3, 4));
+
+    let attention = MultiheadAttention::separate(4, 2, 3, 6, 0., false);
+    let (query, key, value) = (
+        sequence((2, 3, 4), 0.3),
+        sequence((2, 5, 3), 0.7),
+        sequence((2, 5, 6), 1.1),
+    );
+    let output = attention.forward(query, key, value, None, None, false);
+    output.forward();
+    assert_eq!(output.data().dim(), (2, 3, 4));
+}
+
+#[test]
+fn multihead_attention_single_head() {
+    let attention = MultiheadAttention::new(4, 1, 0., false);
+    let (query, key, value) = (
+        sequence((2, 3, 4), 0.3),
+        sequence((2, 5, 4), 0.7),
+        sequence((2, 5, 4), 1.1),
+    );
+    let output = attention.forward(query.clone(), key.clone(), value.clone(), None, None, false);
+    output.forward();
+
+    let weights = match &attention.in_proj {
+        InProjection::Packed(weight) => weight.clone().chunks((4, 4)),
+        InProjection::Separate(_) => unreachable!(),
+    };
+    let project = |input: VarDiff<Ix3>, weight: &VarDiff<ndarray::Ix2>| {
+        let (batch_size, length, features) = input.data().dim();
+        input
+            .reshape((batch_size * length, features))
+            .mm_t(weight.clone())
+            .reshape((batch_size, 1, length, features))
+    };
+    let expected = project(query, &weights[0])
+        .scaled_dot_product_attention(
+            project(key, &weights[1]),
+            project(value, &weights[2]),
+            None,
+            0.,
+            Rc::new(Cell::new(false)),
+            false,
+        )
+        .reshape((6, 4))
+        .mm_t(attention.out_proj_weight.clone())
+        .reshape((2, 3, 4));
+    expected.forward();
+
+    assert_close(&output.data(), &expected.data());
+}
+
+#[test]
+fn multihead_attention_masks() {
+    let attention = MultiheadAttention::new(4, 2, 0., true);
+
+    // Padding keys is the same as leaving them out.
+    let (query, key) = (sequence((1, 3, 4), 0.3), sequence((1, 4, 4), 0.7));
+    let padding = neuronika_variable::from_ndarray(array![[false, false, true, true]]);
+    let output = attention.forward(
+        query.clone(),
+        key.clone(),
+        key.clone(),
+        Some(padding),
+        None,
+        false,
+    );
+    let truncated = key.chunks((1, 2, 4)).remove(0);
+    let expected = attention.forward(query, truncated.clone(), truncated, None, None, false);
+    output.forward();
+    expected.forward();
+    assert_close(&output.data(), &expected.data());
+
+    // Each position attends only to itself and to the preceding ones.
+    let input = sequence((2, 4, 4), 0.9);
+    let output = attention.forward(
+        input.clone(),
+        input.clone(),
+        input.clone(),
+        None,
+        None,
+        true,
+    );
+    let masked = attention.forward(
+        input.clone(),
+        input.clone(),
+        input.clone(),
+        None,
+        Some(causal_mask(4)),
+        false,
+    );
+    let prefix = input.chunks((2, 2, 4)).remove(0);
+    let expected = attention.forward(prefix.clone(), prefix.clone(), prefix, None, None, true);
+    output.forward();
+    masked.forward();
+    expected.forward();
+    assert_close(&output.data(), &masked.data());
+    assert_close(
+        &output.data().slice(s![.., ..2, ..]).to_owned(),
+        &expected.data(),
+    );
+}
+
+#[test]
+fn multihead_attention_gradcheck() {
+    neuronika_core::manual_seed(0);
+    let attention = MultiheadAttention::new(4, 2, 0., true);
+    let (query, key) = (sequence((2, 3, 4), 0.3), sequence((2, 3, 4), 0.7));
+    let weight = match &attention.in_proj {
+        InProjection::Packed(weight) => weight.clone(),
+        InProjection::Separate(_) => unreachable!(),
+    };
+    let inputs: [Parameter; 5] = [
+        weight.into(),
+        attention.in_proj_bias.clone().unwrap().into(),
+        attention.out_proj_weight.clone().into(),
+        query.clone().into(),
+        key.clone().into(),
+    ];
+
+    let f = || attention.forward(query.clone(), key.clone(), key.clone(), None, None, true);
+    assert_eq!(
+        neuronika_variable::gradcheck(f, &inputs, 1e-3, 1e-3, 1e-2),
+        Ok(())
+    );
+}
+
+#[test]
+fn transformer() {
+    for norm_first in [false, true] {
+        let encoder = TransformerEncoder::new(
+            (0..2)
+                .map(|_| TransformerEncoderLayer::new(4, 2, 8, 0.1, norm_first))
+                .collect(),
+            norm_first.then(|| LayerNorm::new(4)),
+        );
+        let decoder = TransformerDecoder::new(
+            (0..2)
+                .map(|_| TransformerDecoderLayer::new(4, 2, 8, 0.1, norm_first))
+                .collect(),
+            norm_first.then(|| LayerNorm::new(4)),
+        );
+        encoder.eval();
+        decoder.eval();
+
+        let memory = encoder.forward(sequence((2, 5, 4), 0.3), None, None);
+        memory.forward();
+        assert_eq!(memory.data().dim(), (2, 5, 4));
+
+        // The causal mask keeps the first outputs from depending on the following targets.
+        let target = sequence((2, 3, 4), 0.7);
+        let output = decoder.forward(
+            target.clone(),
+            memory.clone(),
+            Some(causal_mask(3)),
+            None,
+            None,
+            None,
+        );
+        let prefix = target.chunks((2, 1, 4)).remove(0);
+        let expected = decoder.forward(prefix, memory, Some(causal_mask(1)), None, None, None);
+        output.forward();
+        expected.forward();
+        assert_eq!(output.data().dim(), (2, 3, 4));
+        assert_close(
+            &output.data().slice(s![.., ..1, ..]).to_owned(),
+            &expected.data(),
+        );
+    }
+}
+
+#[test]
+fn transformer_encoder_layer_gradcheck() {
+    // The layer is seeded, as the finite differences are unreliable in single precision close to
+    // the kinks of the feedforward network.
+    neuronika_core::manual_seed(0);
+    for norm_first in [false, true] {
+        let layer = TransformerEncoderLayer::new(4, 2, 6, 0., norm_first);
+        let input = sequence((2, 3, 4), 0.3);
+        let inputs: [Parameter; 4] = [
+            layer.linear1.weight.clone().into(),
+            layer.linear2.weight.clone().into(),
+            layer.norm1.weight.clone().into(),
+            input.clone().into(),
+        ];
+
+        let f = || layer.forward(input.clone(), Some(causal_mask(3)), None);
+        assert_eq!(
+            neuronika_variable::gradcheck(f, &inputs, 1e-3, 1e-3, 1e-2),
+            Ok(())
+        );
+    }
+}
+
+#[test]
+fn positional_encodings() {
+    let input = sequence((2, 3, 4), 0.3);
+
+    let encoding = SinusoidalPositionalEncoding::new(4, 10);
+    let output = encoding.forward(input.clone());
+    output.forward();
+    let expected = Array2::from_shape_fn((3, 4), |(position, feature)| {
+        let angle = position as f32 / [1., 1., 100., 100.][feature];
+        if feature % 2 == 0 {
+            angle.sin()
+        } else {
+            angle.cos()
+        }
+    });
+    assert_close(&output.data(), &(&*input.data() + &expected));
+
+    let encoding = LearnedPositionalEncoding::new(4, 10);
+    let output = encoding.forward(input.clone());
+    output.forward();
+    output.backward(1.);
+    let expected = &*input.data() + &encoding.weight.data().slice(s![..3, ..]);
+    assert_close(&output.data(), &expected);
+    let mut gradient = Array2::zeros((10, 4));
+    gradient.slice_mut(s![..3, ..]).fill(2.);
+    assert_close(&encoding.weight.grad(), &gradient);
+}
+
+#[test]
+#[should_panic(expected = "error: sequences of length 3 exceed the maximum length 2.")]
+fn positional_encoding_too_long() {
+    let _ = SinusoidalPositionalEncoding::new(4, 2).forward(sequence((2, 3, 4), 0.3));
+}
+
+/// Output sequence and final states of a recurrent layer.
+type Unrolled = (VarDiff<Ix3>, Vec<VarDiff<Ix3>>);
+
+/// Data of the output sequence and of the final states of a recurrent layer, followed by the
+/// gradients of its weights and of its input.
+type Evaluated = (Array3<f32>, Vec<Array3<f32>>, Vec<Array2<f32>>, Array3<f32>);
+
+/// Unrolls by hand `cells`, ordered by layer and then by direction, over `input` of shape
+/// *(T, N, F)* starting from zero states.
+fn unroll_by_hand<C, S: Clone>(
+    cells: &[C],
+    input: VarDiff<Ix3>,
+    zero: impl Fn() -> S,
+    step: impl Fn(&C, S, VarDiff<Ix2>) -> S,
+    output: impl Fn(&S) -> VarDiff<Ix2>,
+) -> (VarDiff<Ix3>, Vec<S>) {
+    let (_, batch_size, features) = input.data().dim();
+    let mut steps: Vec<_> = input
+        .chunks((1, batch_size, features))
+        .into_iter()
+        .map(|step| step.reshape((batch_size, features)))
+        .collect();
+
+    let mut states = Vec::new();
+    for layer in cells.chunks(2) {
+        let mut state = zero();
+        let mut forward = Vec::new();
+        for input in &steps {
+            state = step(&layer[0], state, input.clone());
+            forward.push(output(&state));
+        }
+        states.push(state);
+
+        let mut state = zero();
+        let mut backward = Vec::new();
+        for input in steps.iter().rev() {
+            state = step(&layer[1], state, input.clone());
+            backward.push(output(&state));
+        }
+        states.push(state);
+        backward.reverse();
+
+        steps = forward
+            .into_iter()
+            .zip(backward)
+            .map(|(forward, backward)| forward.cat(&[backward], 1))
+            .collect();
+    }
+
+    (steps[0].clone().stack(&steps[1..], 0), states)
+}
+
+/// Asserts that `layer` and `by_hand` compute the same outputs and the same gradients with
+/// respect to `weights` and to the input.
+fn assert_same_unrolling(
+    weights: &[VarDiff<Ix2>],
+    layer: impl FnOnce(VarDiff<Ix3>) -> Unrolled,
+    by_hand: impl FnOnce(VarDiff<Ix3>) -> Unrolled,
+) {
+    fn run(weights: &[VarDiff<Ix2>], unroll: impl FnOnce(VarDiff<Ix3>) -> Unrolled) -> Evaluated {
+        let input = sequence((3, 2, 3), 0.3);
+        let (output, states) = unroll(input.clone());
+        let loss = states.iter().fold(output.clone().sum(), |loss, state| {
+            loss + state.clone().sum()
+        });
+        loss.forward();
+        loss.backward(1.);
+
+        let gradients = weights
+            .iter()
+            .map(|weight| {
+                let gradient = weight.grad().clone();
+                weight.zero_grad();
+                gradient
+            })
+            .collect();
+        let states = states.iter().map(|state| state.data().clone()).collect();
+        let (output, input) = (output.data().clone(), input.grad().clone());
+
+        (output, states, gradients, input)
+    }
+
+    let (output, states, gradients, input) = run(weights, layer);
+    let expected = run(weights, by_hand);
+    assert_close(&output, &expected.0);
+    states
+        .iter()
+        .zip(&expected.1)
+        .for_each(|(state, expected)| assert_close(state, expected));
+    gradients
+        .iter()
+        .zip(&expected.2)
+        .for_each(|(gradient, expected)| assert_close(gradient, expected));
+    assert_close(&input, &expected.3);
+}
+
+/// Stacks the final states of the cells unrolled by hand.
+fn stack_by_hand(states: Vec<VarDiff<Ix2>>) -> VarDiff<Ix3> {
+    states[0].clone().stack(&states[1..], 0)
+}
+
+#[test]
+fn rnn() {
+    let rnn = RNN::new(3, 4, 2, true, false, 0.);
+    let weights: Vec<_> = rnn
+        .cells
+        .iter()
+        .flat_map(|cell| [cell.weight_ih.clone(), cell.weight_hh.clone()])
+        .collect();
+
+    assert_same_unrolling(
+        &weights,
+        |input| {
+            let (output, hidden) = rnn.forward(input, None);
+            (output, vec![hidden])
+        },
+        |input| {
+            let zero = || neuronika_variable::zeros((2, 4)).requires_grad();
+            let step = |cell: &crate::RNNCell, hidden, input| cell.forward(hidden, input);
+            let (output, hidden) = unroll_by_hand(&rnn.cells, input, zero, step, Clone::clone);
+            (output, vec![stack_by_hand(hidden)])
+        },
+    );
+}
+
+#[test]
+fn lstm() {
+    let lstm = LSTM::new(3, 4, 2, true, false, 0.);
+    let weights: Vec<_> = lstm
+        .cells
+        .iter()
+        .flat_map(|cell| [cell.weight_ih.clone(), cell.weight_hh.clone()])
+        .collect();
+
+    assert_same_unrolling(
+        &weights,
+        |input| {
+            let (output, (cell_state, hidden)) = lstm.forward(input, None);
+            (output, vec![cell_state, hidden])
+        },
+        |input| {
+            let zero = || {
+                (
+                    neuronika_variable::zeros((2, 4)).requires_grad(),
+                    neuronika_variable::zeros((2, 4)).requires_grad(),
+                )
+            };
+            let step = |cell: &crate::LSTMCell, state, input| cell.forward(state, input);
+            let output = |state: &(VarDiff<Ix2>, VarDiff<Ix2>)| state.1.clone();
+            let (output, states) = unroll_by_hand(&lstm.cells, input, zero, step, output);
+            let (cell_state, hidden) = states.into_iter().unzip();
+            (
+                output,
+                vec![stack_by_hand(cell_state), stack_by_hand(hidden)],
+            )
+        },
+    );
+}
+
+#[test]
+fn gru() {
+    let gru = GRU::new(3, 4, 2, true, false, 0.);
+    let weights: Vec<_> = gru
+        .cells
+        .iter()
+        .flat_map(|cell| [cell.weight_ih.clone(), cell.weight_hh.clone()])
+        .collect();
+
+    assert_same_unrolling(
+        &weights,
+        |input| {
+            let (output, hidden) = gru.forward(input, None);
+            (output, vec![hidden])
+        },
+        |input| {
+            let zero = || neuronika_variable::zeros((2, 4)).requires_grad();
+            let step = |cell: &crate::GRUCell, hidden, input| cell.forward(hidden, input);
+            let (output, hidden) = unroll_by_hand(&gru.cells, input, zero, step, Clone::clone);
+            (output, vec![stack_by_hand(hidden)])
+        },
+    );
+}
+
+#[test]
+fn recurrent_layout() {
+    let mut gru = GRU::new(3, 4, 3, false, false, 0.5);
+    gru.eval();
+
+    let input = sequence((5, 2, 3), 0.3);
+    let hidden = sequence((3, 2, 4), 0.7);
+    let (output, final_hidden) = gru.forward(input.clone(), Some(hidden.clone()));
+    output.forward();
+    final_hidden.forward();
+    assert_eq!(output.data().dim(), (5, 2, 4));
+    assert_eq!(final_hidden.data().dim(), (3, 2, 4));
+    assert_close(
+        &output.data().slice(s![4, .., ..]).to_owned(),
+        &final_hidden.data().slice(s![2, .., ..]).to_owned(),
+    );
+
+    gru.batch_first = true;
+    let (batch_first, _) = gru.forward(input.permute((1, 0, 2)), Some(hidden));
+    batch_first.forward();
+    assert_close(
+        &batch_first.data(),
+        &output.data().view().permuted_axes((1, 0, 2)).to_owned(),
+    );
+}
+
+#[test]
+fn recurrent_zero_states() {
+    // The default zero states are constant, so the only parameters are the weights, the biases
+    // and the input.
+    let input = sequence((3, 2, 3), 0.3);
+
+    let rnn = RNN::new(3, 4, 2, true, false, 0.);
+    let (output, hidden) = rnn.forward(input.clone(), None);
+    assert_eq!((output + hidden.sum()).parameters().len(), 4 * 4 + 1);
+
+    let lstm = LSTM::new(3, 4, 2, true, false, 0.);
+    let (output, (cell_state, hidden)) = lstm.forward(input.clone(), None);
+    let loss = output.sum() + cell_state.sum() + hidden.sum();
+    assert_eq!(loss.parameters().len(), 4 * 4 + 1);
+
+    let gru = GRU::new(3, 4, 2, true, false, 0.);
+    let (output, hidden) = gru.forward(input, None);
+    assert_eq!((output + hidden.sum()).parameters().len(), 4 * 4 + 1);
+}
+
+#[test]
+fn lstm_gradcheck() {
+    neuronika_core::manual_seed(0);
+    let lstm = LSTM::new(2, 3, 2, true, true, 0.);
+    let input = sequence((2, 3, 2), 0.3);
+    let cell = &lstm.cells[2];
+    let inputs: [Parameter; 4] = [
+        cell.weight_ih.clone().into(),
+        cell.weight_hh.clone().into(),
+        cell.bias_ih.clone().into(),
+        input.clone().into(),
+    ];
+
+    let f = || {
+        let (output, (cell_state, hidden)) = lstm.forward(input.clone(), None);
+        output.sum() + cell_state.sum() + hidden.sum()
+    };
+    assert_eq!(
+        neuronika_variable::gradcheck(f, &inputs, 1e-2, 1e-3, 1e-2),
+        Ok(())
+    );
+}
+
+#[test]
+#[should_panic(expected = "error: cannot unroll a recurrent layer over an empty sequence.")]
+fn recurrent_empty_sequence() {
+    let rnn = RNN::new(3, 4, 1, false, false, 0.);
+    let _ = rnn.forward(neuronika_variable::zeros((0, 2, 3)).requires_grad(), None);
+}
+
+#[test]
+fn init_manual_seed() {
+    use crate::init::{normal, uniform, xavier_normal, xavier_uniform};
+
+    let initialized = || {
+        let inits: [fn(&VarDiff<Ix2>); 4] = [
+            |param| uniform(param, -1., 1.),
+            |param| normal(param, 0., 1.),
+            |param| xavier_uniform(param, 1.),
+            |param| xavier_normal(param, 1.),
+        ];
+        inits.map(|init| {
+            let param = neuronika_variable::zeros((3, 4)).requires_grad();
+            init(&param);
+            let data = param.data().to_owned();
+            data
+        })
+    };
+
+    neuronika_core::manual_seed(7);
+    let first = initialized();
+    neuronika_core::manual_seed(7);
+    let second = initialized();
+    let third = initialized();
+
+    assert_eq!(first, second);
+    assert_ne!(first, third);
+}
+
+#[test]
+fn upsample() {
+    let image = |size, data| Array::from_shape_vec((1, 1, size, size), data).unwrap();
+    let input = neuronika_variable::from_ndarray(image(2, vec![1., 2., 3., 4.])).requires_grad();
+
+    let upsample = Upsample::new(Resize::ScaleFactor(2.), Interpolation::Nearest, false);
+    let output = upsample.forward(input.clone());
+    output.forward();
+    let expected = image(
+        4,
+        vec![
+            1., 1., 2., 2., 1., 1., 2., 2., 3., 3., 4., 4., 3., 3., 4., 4.,
+        ],
+    );
+    assert_eq!(*output.data(), expected);
+
+    let upsample = Upsample::new(Resize::Size(Ix2(3, 3)), Interpolation::Bilinear, true);
+    let output = upsample.forward(input);
+    output.forward();
+    let expected = image(3, vec![1., 1.5, 2., 2., 2.5, 3., 3., 3.5, 4.]);
+    assert_close(&output.data(), &expected);
+}
+
+#[test]
+fn pixel_shuffle() {
+    let data = Array::from_shape_fn((1, 4, 1, 2), |(_, channel, _, column)| {
+        (channel * 2 + column) as f32
+    });
+    let input = neuronika_variable::from_ndarray(data).requires_grad();
+
+    // The channel i × r + j moves to the row offset i and the column offset j.
+    let output = PixelShuffle::new(2).forward(input.clone());
+    output.forward();
+    let expected = Array::from_shape_fn((1, 1, 2, 4), |(_, _, row, column)| {
+        ((row * 2 + column % 2) * 2 + column / 2) as f32
+    });
+    assert_eq!(*output.data(), expected);
+
+    let restored = PixelUnshuffle::new(2).forward(output);
+    restored.forward();
+    assert_eq!(*restored.data(), *input.data());
+}
+
+/// Asserts that `replica` is a distinct leaf holding a copy of the data of `original`.
+#[cfg(feature = "sync")]
+fn assert_replicated<D: Dimension>(original: &VarDiff<D>, replica: &VarDiff<D>) {
+    assert_eq!(*replica.data(), *original.data());
+    replica.data_mut().map_inplace(|value| *value += 1.);
+    assert_ne!(*replica.data(), *original.data());
+}
+
+#[cfg(feature = "sync")]
+#[test]
+fn replicate() {
+    use crate::{Conv1d, Conv2d, GRUCell, LSTMCell, Linear, RNNCell};
+    use neuronika_variable::{Reflective, Replicate, Zero};
+
+    let linear = Linear::new(3, 2);
+    let replica = linear.replicate();
+    assert_eq!(replica.parameters().len(), 2);
+    assert_replicated(&linear.weight, &replica.weight);
+    assert_replicated(&linear.bias, &replica.bias);
+
+    macro_rules! assert_cells_replicated {
+        ($($cell:ident),*) => {
+            $(
+                let cell = $cell::new(3, 2);
+                let replica = cell.replicate();
+                assert_eq!(replica.parameters().len(), 4);
+                assert_replicated(&cell.weight_ih, &replica.weight_ih);
+                assert_replicated(&cell.weight_hh, &replica.weight_hh);
+                assert_replicated(&cell.bias_ih, &replica.bias_ih);
+                assert_replicated(&cell.bias_hh, &replica.bias_hh);
+            )*
+        };
+    }
+    assert_cells_replicated!(LSTMCell, GRUCell, RNNCell);
+
+    let conv = Conv1d::new(2, 3, 2, 1, Zero, 2, 1);
+    let replica = conv.replicate();
+    assert_eq!(replica.parameters().len(), 2);
+    assert_eq!((replica.padding, replica.stride), (1, 2));
+    assert_replicated(&conv.weight, &replica.weight);
+    assert_replicated(&conv.bias, &replica.bias);
+
+    let conv = Conv2d::new(2, 3, (2, 2), (1, 0), Reflective, (1, 1), (2, 1));
+    let replica = conv.replicate();
+    assert_eq!(replica.parameters().len(), 2);
+    assert_eq!((replica.padding, replica.dilation), ((1, 0), (2, 1)));
+    assert_replicated(&conv.weight, &replica.weight);
+    assert_replicated(&conv.bias, &replica.bias);
+}
+
+#[cfg(feature = "sync")]
+#[test]
+fn data_parallel() {
+    use crate::Linear;
+    use neuronika_variable::{DataParallel, Reduction};
+
+    let linear = Linear::new(3, 2);
+    let records = Array::from_shape_fn((7, 3), |(i, j)| ((i * 3 + j) as f32 * 0.3).sin());
+    let labels = Array::from_shape_fn((7, 2), |(i, j)| ((i + j) % 2) as f32);
+
+    // Single-threaded reference.
+    let loss = linear
+        .forward(neuronika_variable::from_ndarray(records.clone()))
+        .mse(
+            neuronika_variable::from_ndarray(labels.clone()),
+            Reduction::Mean,
+        );
+    loss.forward();
+    loss.backward(1.);
+    let expected = (
+        loss.item(),
+        linear.weight.grad().clone(),
+        linear.bias.grad().clone(),
+    );
+    linear.weight.zero_grad();
+    linear.bias.zero_grad();
+
+    // The replicas' gradients are all-reduced into the ones of the wrapped model.
+    let parallel = DataParallel::new(linear, 3);
+    let loss =
+        parallel.forward_backward(records.view(), labels.view(), |linear, records, labels| {
+            linear.forward(records).mse(labels, Reduction::Mean)
+        });
+
+    let linear = parallel.module();
+    assert!((loss - expected.0).abs() <= 1e-5);
+    assert_close(&linear.weight.grad(), &expected.1);
+    assert_close(&linear.bias.grad(), &expected.2);
+}
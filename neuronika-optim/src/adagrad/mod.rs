@@ -1,7 +1,10 @@
-use std::{cell::Cell, rc::Rc};
-
 use ndarray::{Array, Dimension, Zip};
 
+use neuronika_core::{
+    cell::{Cell, Rc},
+    Float,
+};
+
 use neuronika_variable::VarDiff;
 
 use super::{IntoParam, Optimize, Optimizer, OptimizerStatus, Penalty};
@@ -94,21 +97,23 @@ where
 }
 
 /// A parameter used by the Adagrad optimizer.
-pub struct AdagradParam<D, T>
+pub struct AdagradParam<D, T, A>
 where
     D: Dimension,
     T: Penalty,
+    A: Float,
 {
-    variable: VarDiff<D>,
+    variable: VarDiff<D, A>,
     step: usize,
-    grad_sq: Array<f32, D>,
+    grad_sq: Array<A, D>,
     status: Rc<Adagrad<T>>,
 }
 
-impl<D, T> Optimize for AdagradParam<D, T>
+impl<D, T, A> Optimize for AdagradParam<D, T, A>
 where
     D: Dimension,
     T: Penalty,
+    A: Float,
 {
     fn optimize(&mut self) {
         self.step += 1;
@@ -118,23 +123,24 @@ where
         let eps = self.status.get_eps();
         let penalty = self.status.penalty;
 
-        let clr = lr / (1.0 + (self.step - 1) as f32 * lr_decay);
+        let clr = A::from_f32(lr / (1.0 + (self.step - 1) as f32 * lr_decay));
+        let eps = A::from_f32(eps);
 
         let mut data = self.variable.data_mut();
         let mut grad = self.variable.grad_mut();
 
         Zip::from(&mut *grad)
             .and(&*data)
-            .for_each(|grad_el, data_el| *grad_el += penalty.penalize(data_el));
+            .for_each(|grad_el, &data_el| *grad_el += penalty.penalize(&data_el));
 
         Zip::from(&mut self.grad_sq)
             .and(&*grad)
-            .for_each(|grad_sq_el, grad_el| *grad_sq_el += grad_el * grad_el);
+            .for_each(|grad_sq_el, &grad_el| *grad_sq_el += grad_el * grad_el);
 
         Zip::from(&mut *data)
             .and(&*grad)
             .and(&self.grad_sq)
-            .for_each(|data_el, grad_el, grad_sq_el| {
+            .for_each(|data_el, &grad_el, &grad_sq_el| {
                 *data_el -= grad_el / (grad_sq_el.sqrt() + eps) * clr
             });
     }
@@ -144,12 +150,13 @@ where
     }
 }
 
-impl<D, T> IntoParam<Adagrad<T>> for VarDiff<D>
+impl<D, T, A> IntoParam<Adagrad<T>> for VarDiff<D, A>
 where
     D: 'static + Dimension,
     T: 'static + Penalty,
+    A: Float,
 {
-    type Param = AdagradParam<D, T>;
+    type Param = AdagradParam<D, T, A>;
 
     fn into_param(self, status: Rc<Adagrad<T>>) -> Self::Param {
         let variable = self;
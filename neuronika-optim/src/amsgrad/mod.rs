@@ -1,7 +1,10 @@
-use std::{cell::Cell, rc::Rc};
-
 use ndarray::{Array, Dimension, Zip};
 
+use neuronika_core::{
+    cell::{Cell, Rc},
+    Float,
+};
+
 use neuronika_variable::VarDiff;
 
 use super::{IntoParam, Optimize, Optimizer, OptimizerStatus, Penalty};
@@ -113,25 +116,27 @@ where
 
 /// A parameter used by the AMSGrad optimizer.
 #[allow(clippy::upper_case_acronyms)]
-pub struct AMSGradParam<D, T>
+pub struct AMSGradParam<D, T, A>
 where
     D: Dimension,
     T: Penalty,
+    A: Float,
 {
-    variable: VarDiff<D>,
+    variable: VarDiff<D, A>,
     step: usize,
-    exp_avg: Array<f32, D>,
-    exp_avg_sq: Array<f32, D>,
-    max_exp_avg_sq: Array<f32, D>,
+    exp_avg: Array<A, D>,
+    exp_avg_sq: Array<A, D>,
+    max_exp_avg_sq: Array<A, D>,
     status: Rc<AMSGrad<T>>,
 }
 
-impl<D, T> IntoParam<AMSGrad<T>> for VarDiff<D>
+impl<D, T, A> IntoParam<AMSGrad<T>> for VarDiff<D, A>
 where
     D: 'static + Dimension,
     T: 'static + Penalty,
+    A: Float,
 {
-    type Param = AMSGradParam<D, T>;
+    type Param = AMSGradParam<D, T, A>;
 
     fn into_param(self, status: Rc<AMSGrad<T>>) -> Self::Param {
         let variable = self;
@@ -151,10 +156,11 @@ where
     }
 }
 
-impl<D, T> Optimize for AMSGradParam<D, T>
+impl<D, T, A> Optimize for AMSGradParam<D, T, A>
 where
     D: Dimension,
     T: Penalty,
+    A: Float,
 {
     fn optimize(&mut self) {
         self.step += 1;
@@ -168,38 +174,42 @@ where
         let bias_correction1 = 1.0 - beta1.powi(self.step as i32);
         let bias_correction2 = 1.0 - beta2.powi(self.step as i32);
 
+        let (beta1, beta2, eps) = (A::from_f32(beta1), A::from_f32(beta2), A::from_f32(eps));
+        let step_size = A::from_f32(lr / bias_correction1);
+        let bias_correction2 = A::from_f32(bias_correction2);
+
         let mut data = self.variable.data_mut();
         let mut grad = self.variable.grad_mut();
 
         Zip::from(&mut *grad)
             .and(&*data)
-            .for_each(|grad_el, data_el| *grad_el += penalty.penalize(data_el));
+            .for_each(|grad_el, &data_el| *grad_el += penalty.penalize(&data_el));
 
         Zip::from(&mut self.exp_avg)
             .and(&*grad)
-            .for_each(|exp_avg_el, grad_el| {
-                *exp_avg_el = *exp_avg_el * beta1 + grad_el * (1.0 - beta1)
+            .for_each(|exp_avg_el, &grad_el| {
+                *exp_avg_el = *exp_avg_el * beta1 + grad_el * (A::one() - beta1)
             });
 
         Zip::from(&mut self.exp_avg_sq)
             .and(&*grad)
-            .for_each(|exp_avg_sq_el, grad_el| {
-                *exp_avg_sq_el = *exp_avg_sq_el * beta2 + grad_el * grad_el * (1.0 - beta2)
+            .for_each(|exp_avg_sq_el, &grad_el| {
+                *exp_avg_sq_el = *exp_avg_sq_el * beta2 + grad_el * grad_el * (A::one() - beta2)
             });
 
         Zip::from(&mut self.max_exp_avg_sq)
             .and(&self.exp_avg_sq)
-            .for_each(|max_exp_avg_sq_el, exp_avg_sq_el| {
-                *max_exp_avg_sq_el = max_exp_avg_sq_el.max(*exp_avg_sq_el)
+            .for_each(|max_exp_avg_sq_el, &exp_avg_sq_el| {
+                *max_exp_avg_sq_el = max_exp_avg_sq_el.max(exp_avg_sq_el)
             });
 
         Zip::from(&mut *data)
             .and(&self.exp_avg)
             .and(&self.max_exp_avg_sq)
-            .for_each(|data_el, exp_avg_el, max_exp_avg_sq_el| {
+            .for_each(|data_el, &exp_avg_el, &max_exp_avg_sq_el| {
                 *data_el -= exp_avg_el
                     / ((max_exp_avg_sq_el.sqrt() / bias_correction2.sqrt()) + eps)
-                    * (lr / bias_correction1)
+                    * step_size
             });
     }
 
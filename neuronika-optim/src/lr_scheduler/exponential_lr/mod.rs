@@ -2,7 +2,7 @@ use crate::{Optimizer, OptimizerStatus};
 
 use super::{prepare_step, LRScheduler};
 
-use std::cell::Cell;
+use neuronika_core::cell::Cell;
 
 /// Decays the learning rate by `gamma` every epoch.
 ///
@@ -2,7 +2,7 @@ use crate::{Optimizer, OptimizerStatus};
 
 use super::{prepare_step, LRScheduler};
 
-use std::cell::Cell;
+use neuronika_core::cell::Cell;
 
 /// Sets the learning rate to the initial lr times a given function.
 ///
@@ -70,9 +70,9 @@ pub trait LRScheduler {
 ///
 /// Sets `last_lr` as `current_lr` and increases `current_epoch`.
 fn prepare_step(
-    last_lr: &std::cell::Cell<f32>,
-    current_lr: &std::cell::Cell<f32>,
-    current_epoch: &std::cell::Cell<usize>,
+    last_lr: &neuronika_core::cell::Cell<f32>,
+    current_lr: &neuronika_core::cell::Cell<f32>,
+    current_epoch: &neuronika_core::cell::Cell<usize>,
 ) {
     // Set current learning rate as last learning rate.
     last_lr.set(current_lr.get());
@@ -1,4 +1,4 @@
-use std::cell::{Cell, RefCell};
+use neuronika_core::cell::{Cell, RefCell};
 
 use crate::{Optimizer, OptimizerStatus};
 
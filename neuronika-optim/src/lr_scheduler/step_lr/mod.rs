@@ -1,4 +1,4 @@
-use std::cell::Cell;
+use neuronika_core::cell::Cell;
 
 use crate::{Optimizer, OptimizerStatus};
 
@@ -1,7 +1,7 @@
-use std::{cell::RefCell, rc::Rc};
+use neuronika_core::cell::{MaybeSync, Rc, RefCell};
 
 /// Parameter optimization logic trait.
-pub trait Optimize {
+pub trait Optimize: MaybeSync {
     /// Specifies the learning rule for the parameter.
     fn optimize(&mut self);
 
@@ -1,7 +1,9 @@
+use neuronika_core::Float;
+
 /// Penalty trait, defines the penalty regularization's logic.
 pub trait Penalty: Copy + Send + Sync {
     /// Applies the penalty to an element of the gradient.
-    fn penalize(&self, w: &f32) -> f32;
+    fn penalize<A: Float>(&self, w: &A) -> A;
 }
 
 /// L2 penalty, also known as *weight decay* or *Tichonov regularization*.
@@ -61,19 +63,19 @@ impl ElasticNet {
 }
 
 impl Penalty for L2 {
-    fn penalize(&self, w: &f32) -> f32 {
-        2. * self.lambda * w
+    fn penalize<A: Float>(&self, w: &A) -> A {
+        A::from_f32(2. * self.lambda) * *w
     }
 }
 
 impl Penalty for L1 {
-    fn penalize(&self, w: &f32) -> f32 {
-        self.lambda * w.signum()
+    fn penalize<A: Float>(&self, w: &A) -> A {
+        A::from_f32(self.lambda) * w.signum()
     }
 }
 
 impl Penalty for ElasticNet {
-    fn penalize(&self, w: &f32) -> f32 {
-        self.lambda_l1 * w.signum() + 2. * self.lambda_l2 * w
+    fn penalize<A: Float>(&self, w: &A) -> A {
+        A::from_f32(self.lambda_l1) * w.signum() + A::from_f32(2. * self.lambda_l2) * *w
     }
 }
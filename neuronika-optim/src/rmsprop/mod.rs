@@ -1,4 +1,7 @@
-use std::{cell::Cell, rc::Rc};
+use neuronika_core::{
+    cell::{Cell, Rc},
+    Float,
+};
 
 use neuronika_variable::VarDiff;
 
@@ -150,24 +153,26 @@ where
 
 /// A parameter used by the *RMSProp* optimizer.
 #[allow(clippy::upper_case_acronyms)]
-pub struct RMSPropParam<D, T>
+pub struct RMSPropParam<D, T, A>
 where
     D: Dimension,
     T: Penalty,
+    A: Float,
 {
-    variable: VarDiff<D>,
-    square_avg: Array<f32, D>,
-    buffer: Option<Array<f32, D>>,
-    grad_avg: Option<Array<f32, D>>,
+    variable: VarDiff<D, A>,
+    square_avg: Array<A, D>,
+    buffer: Option<Array<A, D>>,
+    grad_avg: Option<Array<A, D>>,
     status: Rc<RMSProp<T>>,
 }
 
-impl<D, T> IntoParam<RMSProp<T>> for VarDiff<D>
+impl<D, T, A> IntoParam<RMSProp<T>> for VarDiff<D, A>
 where
     D: 'static + Dimension,
     T: 'static + Penalty,
+    A: Float,
 {
-    type Param = RMSPropParam<D, T>;
+    type Param = RMSPropParam<D, T, A>;
 
     fn into_param(self, status: Rc<RMSProp<T>>) -> Self::Param {
         let variable = self;
@@ -185,28 +190,29 @@ where
     }
 }
 
-impl<D, T> Optimize for RMSPropParam<D, T>
+impl<D, T, A> Optimize for RMSPropParam<D, T, A>
 where
     D: 'static + Dimension,
     T: 'static + Penalty,
+    A: Float,
 {
     fn optimize(&mut self) {
-        let lr = self.status.get_lr();
-        let alpha = self.status.get_alpha().unwrap_or(0.0);
+        let lr = A::from_f32(self.status.get_lr());
+        let alpha = A::from_f32(self.status.get_alpha().unwrap_or(0.0));
         let penalty = self.status.penalty;
-        let eps = self.status.get_eps();
+        let eps = A::from_f32(self.status.get_eps());
 
         let mut data = self.variable.data_mut();
         let mut grad = self.variable.grad_mut();
 
         Zip::from(&mut *grad)
             .and(&*data)
-            .for_each(|grad_el, data_el| *grad_el += penalty.penalize(data_el));
+            .for_each(|grad_el, &data_el| *grad_el += penalty.penalize(&data_el));
 
         Zip::from(&mut self.square_avg)
             .and(&*grad)
-            .for_each(|square_avg_el, grad_el| {
-                *square_avg_el = *square_avg_el * alpha + grad_el * grad_el * (1.0 - alpha)
+            .for_each(|square_avg_el, &grad_el| {
+                *square_avg_el = *square_avg_el * alpha + grad_el * grad_el * (A::one() - alpha)
             });
 
         match (
@@ -216,6 +222,7 @@ where
                 .filter(|momentum| *momentum > f32::EPSILON),
         ) {
             (true, Some(momentum)) => {
+                let momentum = A::from_f32(momentum);
                 if self.grad_avg.is_none() {
                     self.grad_avg = Some(Array::zeros(grad.raw_dim()));
                 }
@@ -225,15 +232,15 @@ where
 
                 Zip::from(self.grad_avg.as_mut().unwrap())
                     .and(&*grad)
-                    .for_each(|grad_avg_el, grad_el| {
-                        *grad_avg_el = *grad_avg_el * alpha + grad_el * (1.0 - alpha)
+                    .for_each(|grad_avg_el, &grad_el| {
+                        *grad_avg_el = *grad_avg_el * alpha + grad_el * (A::one() - alpha)
                     });
 
                 Zip::from(self.buffer.as_mut().unwrap())
                     .and(&*grad)
                     .and(&self.square_avg)
                     .and(self.grad_avg.as_ref().unwrap())
-                    .for_each(|buffer_el, grad_el, square_avg_el, grad_avg_el| {
+                    .for_each(|buffer_el, &grad_el, &square_avg_el, &grad_avg_el| {
                         *buffer_el = *buffer_el * momentum
                             + grad_el
                                 / ((square_avg_el + (-grad_avg_el * grad_avg_el)).sqrt() + eps)
@@ -241,9 +248,10 @@ where
 
                 Zip::from(&mut *data)
                     .and(self.buffer.as_ref().unwrap())
-                    .for_each(|data_el, buffer_el| *data_el -= buffer_el * lr);
+                    .for_each(|data_el, &buffer_el| *data_el -= buffer_el * lr);
             }
             (false, Some(momentum)) => {
+                let momentum = A::from_f32(momentum);
                 if self.buffer.is_none() {
                     self.buffer = Some(Array::zeros(grad.raw_dim()));
                 }
@@ -252,13 +260,13 @@ where
                 Zip::from(self.buffer.as_mut().unwrap())
                     .and(&*grad)
                     .and(&mut self.square_avg)
-                    .for_each(|buffer_el, grad_el, square_avg_el| {
+                    .for_each(|buffer_el, &grad_el, &mut square_avg_el| {
                         *buffer_el = *buffer_el * momentum + grad_el / (square_avg_el.sqrt() + eps)
                     });
 
                 Zip::from(&mut *data)
                     .and(self.buffer.as_ref().unwrap())
-                    .for_each(|data_el, buffer_el| *data_el -= buffer_el * lr);
+                    .for_each(|data_el, &buffer_el| *data_el -= buffer_el * lr);
             }
             (true, None) => {
                 if self.grad_avg.is_none() {
@@ -268,15 +276,15 @@ where
 
                 Zip::from(self.grad_avg.as_mut().unwrap())
                     .and(&*grad)
-                    .for_each(|grad_avg_el, grad_el| {
-                        *grad_avg_el = *grad_avg_el * alpha + grad_el * (1.0 - alpha)
+                    .for_each(|grad_avg_el, &grad_el| {
+                        *grad_avg_el = *grad_avg_el * alpha + grad_el * (A::one() - alpha)
                     });
 
                 Zip::from(&mut *data)
                     .and(&*grad)
                     .and(&self.square_avg)
                     .and(self.grad_avg.as_ref().unwrap())
-                    .for_each(|data_el, grad_el, square_avg_el, grad_avg_el| {
+                    .for_each(|data_el, &grad_el, &square_avg_el, &grad_avg_el| {
                         *data_el -= grad_el
                             / ((square_avg_el + (-grad_avg_el * grad_avg_el)).sqrt() + eps)
                             * lr
@@ -289,7 +297,7 @@ where
                 Zip::from(&mut *data)
                     .and(&*grad)
                     .and(&self.square_avg)
-                    .for_each(|data_el, grad_el, square_avg_el| {
+                    .for_each(|data_el, &grad_el, &square_avg_el| {
                         *data_el -= grad_el / (square_avg_el.sqrt() + eps) * lr
                     });
             }
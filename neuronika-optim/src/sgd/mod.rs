@@ -1,7 +1,10 @@
-use std::{cell::Cell, rc::Rc};
-
 use ndarray::{Array, Dimension, Zip};
 
+use neuronika_core::{
+    cell::{Cell, Rc},
+    Float,
+};
+
 use neuronika_variable::VarDiff;
 
 use super::{IntoParam, Optimize, Optimizer, OptimizerStatus, Penalty};
@@ -151,22 +154,24 @@ where
 
 /// A parameter used by the SDG optimizer.
 #[allow(clippy::upper_case_acronyms)]
-pub struct SGDParam<D, T>
+pub struct SGDParam<D, T, A>
 where
     D: Dimension,
     T: Penalty,
+    A: Float,
 {
-    variable: VarDiff<D>,
-    buffer: Option<Array<f32, D>>,
+    variable: VarDiff<D, A>,
+    buffer: Option<Array<A, D>>,
     status: Rc<StochasticGD<T>>,
 }
 
-impl<D, T> IntoParam<StochasticGD<T>> for VarDiff<D>
+impl<D, T, A> IntoParam<StochasticGD<T>> for VarDiff<D, A>
 where
     D: 'static + Dimension,
     T: 'static + Penalty,
+    A: Float,
 {
-    type Param = SGDParam<D, T>;
+    type Param = SGDParam<D, T, A>;
 
     fn into_param(self, status: Rc<StochasticGD<T>>) -> Self::Param {
         let variable = self;
@@ -183,13 +188,14 @@ where
     }
 }
 
-impl<D, T> Optimize for SGDParam<D, T>
+impl<D, T, A> Optimize for SGDParam<D, T, A>
 where
     D: Dimension,
     T: Penalty,
+    A: Float,
 {
     fn optimize(&mut self) {
-        let lr = self.status.get_lr();
+        let lr = A::from_f32(self.status.get_lr());
         let penalty = self.status.penalty;
 
         let mut data = self.variable.data_mut();
@@ -197,34 +203,35 @@ where
 
         Zip::from(&mut *grad)
             .and(&*data)
-            .for_each(|grad_el, data_el| *grad_el += penalty.penalize(data_el));
+            .for_each(|grad_el, &data_el| *grad_el += penalty.penalize(&data_el));
 
         match self.status.get_momentum().filter(|val| *val > f32::EPSILON) {
             None => {
                 self.buffer = None;
                 Zip::from(&mut *data)
                     .and(&*grad)
-                    .for_each(|data_el, grad_el| *data_el -= grad_el * lr);
+                    .for_each(|data_el, &grad_el| *data_el -= grad_el * lr);
             }
             Some(momentum) => {
-                let dampening = self.status.get_dampening().unwrap_or(0.0);
+                let momentum = A::from_f32(momentum);
+                let dampening = A::from_f32(self.status.get_dampening().unwrap_or(0.0));
                 if self.buffer.is_none() {
                     self.buffer = Some(Array::zeros(grad.raw_dim()));
                 }
 
                 Zip::from(self.buffer.as_mut().unwrap())
                     .and(&*grad)
-                    .for_each(|buffer_el, grad_el| {
-                        *buffer_el = *buffer_el * momentum + *grad_el * (1.0 - dampening)
+                    .for_each(|buffer_el, &grad_el| {
+                        *buffer_el = *buffer_el * momentum + grad_el * (A::one() - dampening)
                     });
 
                 let zip = Zip::from(&mut *data).and(self.buffer.as_ref().unwrap());
                 if self.status.get_nesterov() {
-                    zip.and(&*grad).for_each(|data_el, buffer_el, grad_el| {
-                        *data_el -= (grad_el + *buffer_el * momentum) * lr
+                    zip.and(&*grad).for_each(|data_el, &buffer_el, &grad_el| {
+                        *data_el -= (grad_el + buffer_el * momentum) * lr
                     });
                 } else {
-                    zip.for_each(|data_el, buffer_el| *data_el -= *buffer_el * lr);
+                    zip.for_each(|data_el, &buffer_el| *data_el -= buffer_el * lr);
                 }
             }
         }
@@ -132,3 +132,27 @@ fn step_with_nesterov_momentum() {
 
     assert!(loss.item() < first_value);
 }
+
+#[test]
+fn step_double_precision() {
+    let x = neuronika_variable::from_ndarray(ndarray::Array::from_elem((3, 3), 0.5_f64));
+    let x = x.requires_grad();
+    let y = neuronika_variable::from_ndarray(ndarray::Array::from_elem((3, 3), 0.25_f64));
+
+    let loss = (x.clone().mm(y) - 1.).pow(2).sum();
+    loss.forward();
+
+    let first_value = loss.item();
+    let optim = StochasticGD::new(1e-2, L2::new(1e-3), 0.7, 0.3, true);
+    optim.register(x);
+
+    for _ in 0..EPOCHS {
+        loss.forward();
+        loss.backward(1.0);
+
+        optim.step();
+        optim.zero_grad();
+    }
+
+    assert!(loss.item() < first_value);
+}
@@ -0,0 +1,30 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use neuronika_variable as neuronika;
+
+fn elementwise_chain(c: &mut Criterion) {
+    let mut group = c.benchmark_group("elementwise_chain");
+
+    for fused in [false, true] {
+        let x = neuronika::rand((1000, 1000)).requires_grad();
+        let w = neuronika::rand((1000, 1000));
+        let b = neuronika::rand((1000, 1000));
+        let y = ((x * w + b).sigmoid() * 2. - 1.).tanh();
+        if fused {
+            y.fuse();
+        }
+        y.forward();
+
+        let id = if fused { "fused" } else { "unfused" };
+        group.bench_function(BenchmarkId::new("forward", id), |bencher| {
+            bencher.iter(|| y.forward())
+        });
+        group.bench_function(BenchmarkId::new("backward", id), |bencher| {
+            bencher.iter(|| y.backward(1.))
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, elementwise_chain);
+criterion_main!(benches);
@@ -0,0 +1,144 @@
+use std::collections::HashMap;
+
+use crate::{
+    autograd::Forward,
+    cell::Rc,
+    gradient::NodeGradient,
+    parameter::Leaf,
+    var::{ForwardOp, VarHistory},
+    vardiff::DiffHistory,
+};
+
+std::thread_local! {
+    static ANOMALY_DETECTION: std::cell::Cell<bool> = const { std::cell::Cell::new(false) };
+}
+
+/// Enables or disables the anomaly detection mode on the current thread.
+///
+/// While enabled, the result of each operation of a forward pass and the gradients written by
+/// each operation of a backward pass are checked for infinite and NaN values. The first
+/// operation producing any causes a panic, reporting its name, the shape of its result and its
+/// position in the tape. The element-wise operations are not fused in this mode.
+///
+/// Anomaly detection slows down the computations noticeably and is meant for debugging.
+///
+/// # Examples
+///
+/// ```should_panic
+/// # use neuronika_variable as neuronika;
+/// neuronika::set_anomaly_detection(true);
+///
+/// let x = neuronika::from_ndarray(ndarray::array![1., 0., 2.]).requires_grad();
+/// let y = (x.ln() * 2.).sum();
+///
+/// // Panics as the logarithm of 0 is -inf.
+/// y.forward();
+/// ```
+pub fn set_anomaly_detection(enabled: bool) {
+    ANOMALY_DETECTION.with(|detection| detection.set(enabled));
+}
+
+/// Returns `true` if the anomaly detection mode is enabled on the current thread.
+///
+/// See [`set_anomaly_detection()`] for more details.
+pub fn is_anomaly_detection_enabled() -> bool {
+    ANOMALY_DETECTION.with(|detection| detection.get())
+}
+
+/// Panics if the data of the op at `position` in the forward tape holds infinite or NaN values.
+pub(crate) fn check_forward(position: usize, (op, _, data): &ForwardOp) {
+    if !data.is_finite() {
+        anomaly("forward", op.as_ref(), position);
+    }
+}
+
+/// A gradient written by the back-propagation of an op.
+enum Written {
+    Node(Rc<dyn NodeGradient>),
+    Leaf(Rc<dyn Leaf>),
+}
+
+impl Written {
+    fn is_finite(&self) -> bool {
+        match self {
+            Self::Node(gradient) => gradient.is_finite(),
+            Self::Leaf(gradient) => gradient.is_finite(),
+        }
+    }
+}
+
+/// A forward op together with the gradients written by its back-propagation.
+type Step = (Rc<dyn Forward>, Vec<Written>);
+
+/// Checks of the gradients written by the ops of a differentiable tape.
+pub(crate) struct BackwardChecks {
+    /// For each op of the differentiable tape, the corresponding forward op and the gradients of
+    /// its operands, if it's known.
+    steps: Vec<Option<Step>>,
+}
+
+impl BackwardChecks {
+    /// Collects the checks of a differentiable tape.
+    ///
+    /// # Arguments
+    ///
+    /// * `forward` - forward tape.
+    ///
+    /// * `backward` - differentiable tape.
+    pub(crate) fn new(forward: &VarHistory, backward: &DiffHistory) -> Self {
+        let ops: HashMap<usize, &Rc<dyn Forward>> = forward
+            .iter()
+            .map(|(_, (op, _, _))| (op.data().id, op))
+            .collect();
+        let nodes: HashMap<usize, &Rc<dyn NodeGradient>> = backward
+            .iter()
+            .map(|(id, (_, gradient))| (id, gradient))
+            .collect();
+        let leaves: HashMap<usize, &Rc<dyn Leaf>> = backward.iter_leaves().collect();
+
+        let steps = backward
+            .iter()
+            .map(|(id, _)| {
+                let op = (*ops.get(&id)?).clone();
+                let written = op
+                    .operands()
+                    .into_iter()
+                    .filter_map(|operand| match nodes.get(&operand.id) {
+                        Some(&gradient) => Some(Written::Node(gradient.clone())),
+                        None => leaves
+                            .get(&operand.id)
+                            .map(|&gradient| Written::Leaf(gradient.clone())),
+                    })
+                    .collect();
+
+                Some((op, written))
+            })
+            .collect();
+
+        Self { steps }
+    }
+
+    /// Panics if the back-propagation of the op at `position` in the differentiable tape wrote
+    /// infinite or NaN values.
+    pub(crate) fn check(&self, position: usize) {
+        let (op, written) = match &self.steps[position] {
+            Some(step) => step,
+            None => return,
+        };
+
+        if !written.iter().all(Written::is_finite) {
+            anomaly("backward", op.as_ref(), position);
+        }
+    }
+}
+
+fn anomaly(pass: &str, op: &dyn Forward, position: usize) -> ! {
+    panic!(
+        "error: the {} pass of {} with shape {:?}, at position {} of the tape, produced infinite \
+         or NaN values.",
+        pass,
+        op.name(),
+        op.data().shape,
+        position
+    )
+}
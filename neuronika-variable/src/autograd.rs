@@ -1,14 +1,34 @@
+use crate::{
+    cell::MaybeSync,
+    fusion::{ElementwiseBackward, ElementwiseForward},
+    graph::Buffer,
+};
+
 /// Forward-propagation behavior.
 ///
 /// This trait is implemented by all the internal forward components of `Var` and `VarDiff`.
 ///
 /// The main method it provides is the `.forward()` method that is used to propagate computations
 /// from the leaf variables to the graph's root.
-pub(crate) trait Forward {
+pub(crate) trait Forward: MaybeSync {
     /// Propagates the computations forwards.
     ///
     /// It also defines the logic for the computation of the node.
     fn forward(&self);
+
+    /// Returns the name of the operation.
+    fn name(&self) -> &'static str;
+
+    /// Returns the buffers the operation reads from.
+    fn operands(&self) -> Vec<Buffer>;
+
+    /// Returns the buffer the operation writes to.
+    fn data(&self) -> Buffer;
+
+    /// Returns the operation as an element-wise one, if it is, so that it can be fused.
+    fn elementwise(&self) -> Option<&dyn ElementwiseForward> {
+        None
+    }
 }
 
 /// Back-propagation behavior.
@@ -17,9 +37,47 @@ pub(crate) trait Forward {
 ///
 /// The main method it provides is the `.backward()` method that is used to back-propagate gradients
 /// from the root variables to the graph's leaves.
-pub(crate) trait Backward {
+pub(crate) trait Backward: MaybeSync {
     /// Propagates the computations backwards.
     ///
     /// It also defines the logic for the back-propagation of the node.
     fn backward(&self);
+
+    /// Returns the operation as an element-wise one, if it is, so that it can be fused.
+    fn elementwise(&self) -> Option<&dyn ElementwiseBackward> {
+        None
+    }
+
+    /// Returns the data read by the back-propagation, the buffers it doesn't read can be
+    /// de-allocated by a memory plan as soon as the forward pass is done with them.
+    ///
+    /// Defaults to [`Reads::All`].
+    fn reads(&self) -> Reads {
+        Reads::All
+    }
+}
+
+/// The data read by the back-propagation of an op.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) enum Reads {
+    /// Neither the data of the node nor the one of its operands.
+    Nothing,
+    /// The data of the node only.
+    Data,
+    /// The data of the operands of the node only.
+    Operands,
+    /// Both the data of the node and the one of its operands.
+    All,
+}
+
+impl Reads {
+    /// Returns `true` if the data of the node is read.
+    pub(crate) fn data(self) -> bool {
+        matches!(self, Self::Data | Self::All)
+    }
+
+    /// Returns `true` if the data of the operands of the node is read.
+    pub(crate) fn operands(self) -> bool {
+        matches!(self, Self::Operands | Self::All)
+    }
 }
@@ -5,6 +5,7 @@ use cudnn::{BinaryOp, BinaryOpTensorDescriptor, NanPropagation, TensorDescriptor
 use crate::{
     autograd::Forward,
     cuda::cuarray::CuArray,
+    graph::Buffer,
     utils::{Broadcast, Shared},
 };
 
@@ -79,4 +80,31 @@ where
             )
             .unwrap()
     }
+
+    fn name(&self) -> &'static str {
+        "BinaryOperation"
+    }
+
+    fn operands(&self) -> Vec<Buffer> {
+        vec![
+            Buffer::with_shape(
+                &self.left_data,
+                self.left_data.borrow().dimension().slice(),
+                std::mem::size_of::<f32>(),
+            ),
+            Buffer::with_shape(
+                &self.right_data,
+                self.right_data.borrow().dimension().slice(),
+                std::mem::size_of::<f32>(),
+            ),
+        ]
+    }
+
+    fn data(&self) -> Buffer {
+        Buffer::with_shape(
+            &self.data,
+            self.data.borrow().dimension().slice(),
+            std::mem::size_of::<f32>(),
+        )
+    }
 }
@@ -0,0 +1,640 @@
+use std::{
+    any::Any,
+    collections::{HashMap, HashSet},
+    ops::Range,
+};
+
+use ndarray::{Array, Dimension};
+
+use crate::{
+    autograd::Backward,
+    cell::{Rc, Ref, RefCell, RefMut},
+    gradient::NodeGradient,
+    utils::Shared,
+    var::ForwardOp,
+    Element, Float,
+};
+
+/// An op of a differentiable tape, together with the gradient of its node.
+type Op = (Rc<dyn Backward>, Rc<dyn NodeGradient>);
+
+/// Computes a tile of the result of an element-wise operation from the tiles of its operands.
+type ForwardFunction<T> = Box<dyn Fn(&[&[T]], &mut [T])>;
+
+/// Accumulates into a tile of the gradient of an operand the contributions of a tile of the
+/// gradient of an element-wise operation, given the tiles of the data it reads.
+type BackwardFunction<T> = Box<dyn Fn(&[T], &[&[T]], &mut [T])>;
+
+/// Number of elements a fused group of ops computes at a time, small enough for the tiles of all
+/// the intermediate results to stay in the L1 cache.
+const TILE_LEN: usize = 256;
+
+/// An element-wise forward computation that can be fused with the neighbouring ones.
+pub(crate) trait ElementwiseForward {
+    /// Returns the number of elements of the result if it can be computed a tile of elements at a
+    /// time, see [`fusible_len`].
+    fn fusible_len(&self) -> Option<usize>;
+
+    /// Returns the [`ForwardKernel`] of the computation.
+    fn kernel(&self) -> Box<dyn Any>;
+}
+
+/// An element-wise backward computation that can be fused with the neighbouring ones.
+pub(crate) trait ElementwiseBackward {
+    /// Returns the number of elements of the gradient if it can be back-propagated a tile of
+    /// elements at a time, see [`fusible_len`].
+    fn fusible_len(&self) -> Option<usize>;
+
+    /// Returns the [`BackwardKernel`] of the computation.
+    fn kernel(&self) -> Box<dyn Any>;
+}
+
+/// An array in standard layout whose elements are read or written by a fused group of ops.
+pub(crate) trait Elements<T> {
+    /// Returns the address of the array, which identifies it.
+    fn id(&self) -> usize;
+
+    /// Returns the elements of the array.
+    fn elements(&self) -> Ref<'_, [T]>;
+
+    /// Returns the elements of the array, mutably.
+    fn elements_mut(&self) -> RefMut<'_, [T]>;
+}
+
+impl<T, D> Elements<T> for RefCell<Array<T, D>>
+where
+    D: Dimension,
+{
+    fn id(&self) -> usize {
+        self as *const Self as *const () as usize
+    }
+
+    fn elements(&self) -> Ref<'_, [T]> {
+        Ref::map(self.borrow(), |array| array.as_slice().unwrap())
+    }
+
+    fn elements_mut(&self) -> RefMut<'_, [T]> {
+        RefMut::map(self.borrow_mut(), |array| array.as_slice_mut().unwrap())
+    }
+}
+
+/// The computation of the result of an element-wise operation, in which each element is computed
+/// from the elements of the operands at the same position.
+pub(crate) struct ForwardKernel<T> {
+    operands: Vec<Rc<dyn Elements<T>>>,
+    data: Rc<dyn Elements<T>>,
+    function: ForwardFunction<T>,
+}
+
+impl<T> ForwardKernel<T>
+where
+    T: Element,
+{
+    /// Creates the kernel of an operation with a single operand.
+    pub(crate) fn unary<D, E>(
+        operand: &Shared<Array<T, E>>,
+        data: &Shared<Array<T, D>>,
+        function: impl Fn(T) -> T + 'static,
+    ) -> Self
+    where
+        D: 'static + Dimension,
+        E: 'static + Dimension,
+    {
+        Self {
+            operands: vec![operand.clone()],
+            data: data.clone(),
+            function: Box::new(move |operands, tile| {
+                tile.iter_mut()
+                    .zip(operands[0])
+                    .for_each(|(v, &o)| *v = function(o))
+            }),
+        }
+    }
+
+    /// Creates the kernel of an operation with two operands.
+    pub(crate) fn binary<D, E, F>(
+        left: &Shared<Array<T, D>>,
+        right: &Shared<Array<T, E>>,
+        data: &Shared<Array<T, F>>,
+        function: impl Fn(T, T) -> T + 'static,
+    ) -> Self
+    where
+        D: 'static + Dimension,
+        E: 'static + Dimension,
+        F: 'static + Dimension,
+    {
+        Self {
+            operands: vec![left.clone(), right.clone()],
+            data: data.clone(),
+            function: Box::new(move |operands, tile| {
+                tile.iter_mut()
+                    .zip(operands[0])
+                    .zip(operands[1])
+                    .for_each(|((v, &l), &r)| *v = function(l, r))
+            }),
+        }
+    }
+}
+
+/// A contribution of a [`BackwardKernel`] to the gradient of an operand.
+struct Derivative<T> {
+    operand_gradient: Rc<dyn Elements<T>>,
+    data: Vec<Rc<dyn Elements<T>>>,
+    function: BackwardFunction<T>,
+}
+
+/// The back-propagation of the gradient of an element-wise operation, in which each element of
+/// the gradients of the operands receives a contribution computed from the elements of the
+/// gradient and of the data at the same position.
+pub(crate) struct BackwardKernel<T> {
+    gradient: Rc<dyn Elements<T>>,
+    derivatives: Vec<Derivative<T>>,
+}
+
+impl<T> BackwardKernel<T>
+where
+    T: Float,
+{
+    /// Creates the kernel back-propagating `gradient`.
+    pub(crate) fn new(gradient: Rc<dyn Elements<T>>) -> Self {
+        Self {
+            gradient,
+            derivatives: Vec::new(),
+        }
+    }
+
+    /// Returns the kernel of `op`, whose elements are of type `T`.
+    pub(crate) fn of(op: &dyn ElementwiseBackward) -> Self {
+        *op.kernel().downcast().unwrap()
+    }
+
+    /// Accumulates `derivative` of the elements of the gradient into `operand_gradient`.
+    pub(crate) fn accumulate(
+        mut self,
+        operand_gradient: Rc<dyn Elements<T>>,
+        derivative: impl Fn(T) -> T + 'static,
+    ) -> Self {
+        self.derivatives.push(Derivative {
+            operand_gradient,
+            data: Vec::new(),
+            function: Box::new(move |gradient, _, tile| {
+                tile.iter_mut()
+                    .zip(gradient)
+                    .for_each(|(op_grad_el, &grad_el)| *op_grad_el += derivative(grad_el))
+            }),
+        });
+
+        self
+    }
+
+    /// Accumulates `derivative` of the elements of the gradient and of `data` into
+    /// `operand_gradient`.
+    pub(crate) fn accumulate_with<D>(
+        mut self,
+        operand_gradient: Rc<dyn Elements<T>>,
+        data: &Shared<Array<T, D>>,
+        derivative: impl Fn(T, T) -> T + 'static,
+    ) -> Self
+    where
+        D: 'static + Dimension,
+    {
+        self.derivatives.push(Derivative {
+            operand_gradient,
+            data: vec![data.clone()],
+            function: Box::new(move |gradient, data, tile| {
+                tile.iter_mut().zip(gradient).zip(data[0]).for_each(
+                    |((op_grad_el, &grad_el), &data_el)| {
+                        *op_grad_el += derivative(grad_el, data_el)
+                    },
+                )
+            }),
+        });
+
+        self
+    }
+
+    /// Accumulates `derivative` of the elements of the gradient, of `left` and of `right` into
+    /// `operand_gradient`.
+    pub(crate) fn accumulate_with_both<D, E>(
+        mut self,
+        operand_gradient: Rc<dyn Elements<T>>,
+        left: &Shared<Array<T, D>>,
+        right: &Shared<Array<T, E>>,
+        derivative: impl Fn(T, T, T) -> T + 'static,
+    ) -> Self
+    where
+        D: 'static + Dimension,
+        E: 'static + Dimension,
+    {
+        self.derivatives.push(Derivative {
+            operand_gradient,
+            data: vec![left.clone(), right.clone()],
+            function: Box::new(move |gradient, data, tile| {
+                tile.iter_mut()
+                    .zip(gradient)
+                    .zip(data[0])
+                    .zip(data[1])
+                    .for_each(|(((op_grad_el, &grad_el), &l), &r)| {
+                        *op_grad_el += derivative(grad_el, l, r)
+                    })
+            }),
+        });
+
+        self
+    }
+
+    /// Joins the kernel with `other`, that back-propagates the same gradient.
+    pub(crate) fn and(mut self, other: Self) -> Self {
+        self.derivatives.extend(other.derivatives);
+        self
+    }
+}
+
+/// Returns the number of elements of `array` if it is in standard layout.
+pub(crate) fn standard_len<T, D>(array: &Array<T, D>) -> Option<usize>
+where
+    D: Dimension,
+{
+    if array.is_standard_layout() {
+        Some(array.len())
+    } else {
+        None
+    }
+}
+
+/// Returns `len` if the arrays written to have `len` elements and the ones read from have either
+/// `len` elements or a single one, that is broadcast. All of them must be in standard layout.
+///
+/// # Arguments
+///
+/// * `len` - number of elements of the computation.
+///
+/// * `written` - number of elements of the arrays written to, see [`standard_len`].
+///
+/// * `read` - number of elements of the arrays read from, see [`standard_len`].
+pub(crate) fn fusible_len(
+    len: Option<usize>,
+    written: &[Option<usize>],
+    read: &[Option<usize>],
+) -> Option<usize> {
+    let len = len?;
+
+    let fits = written.iter().all(|&other| other == Some(len))
+        && read
+            .iter()
+            .all(|&other| other == Some(len) || other == Some(1));
+
+    if fits {
+        Some(len)
+    } else {
+        None
+    }
+}
+
+/// Runs the ops of a forward tape, fusing the consecutive element-wise ones.
+///
+/// The kernels of a fused group are composed in a single pass over the elements, a tile at a
+/// time, in which the tiles of the intermediate results are handed over to the kernels reading
+/// them through a scratch space. Each element is computed exactly as by the unfused op.
+///
+/// `read` holds the ids of the data read by the backward pass, see [`observable`].
+pub(crate) fn forward<T>(tape: &[ForwardOp], read: &HashSet<usize>)
+where
+    T: Element,
+{
+    let mut consumers: HashMap<usize, Vec<usize>> = HashMap::new();
+    for (position, (op, _, _)) in tape.iter().enumerate() {
+        for operand in op.operands() {
+            consumers.entry(operand.id).or_default().push(position);
+        }
+    }
+
+    let mut start = 0;
+    while start < tape.len() {
+        let (end, len) = group(tape, start, |(op, _, _)| op.elementwise()?.fusible_len());
+        let ops = &tape[start..end];
+
+        if ops.iter().all(|(_, computed, _)| computed.get()) {
+            start = end;
+            continue;
+        }
+
+        let kernels = len.and_then(|_| {
+            ops.iter()
+                .map(|(op, _, _)| op.elementwise().unwrap().kernel().downcast().ok())
+                .collect::<Option<Vec<Box<ForwardKernel<T>>>>>()
+        });
+        match (len, kernels) {
+            (Some(len), Some(kernels)) => {
+                let stored = observable(tape, start..end, &consumers, read);
+                run_forward(&kernels, len, &stored)
+            }
+            _ => ops.iter().for_each(|(op, _, _)| op.forward()),
+        }
+        ops.iter().for_each(|(_, computed, _)| computed.set(true));
+
+        start = end;
+    }
+}
+
+/// Returns, for each op of the fused group `tape[group]`, whether its result is observable
+/// outside of the group, thus must be stored.
+///
+/// A result is observable if a variable of its node is alive, if it's read by the backward pass,
+/// as listed in `read`, or if it's read by an op of the tape that is not in the group, as listed in
+/// `consumers`, which maps the id of each data to the positions of the ops reading it.
+fn observable(
+    tape: &[ForwardOp],
+    group: Range<usize>,
+    consumers: &HashMap<usize, Vec<usize>>,
+    read: &HashSet<usize>,
+) -> Vec<bool> {
+    tape[group.clone()]
+        .iter()
+        .map(|(op, _, data)| {
+            let id = op.data().id;
+            let outside = consumers
+                .get(&id)
+                .into_iter()
+                .flatten()
+                .any(|position| !group.contains(position));
+
+            data.is_held() || read.contains(&id) || outside
+        })
+        .collect()
+}
+
+/// Runs the ops of a backward tape in reverse, fusing the consecutive element-wise ones.
+///
+/// The kernels of a fused group are composed in a single pass over the elements, a tile at a
+/// time, so that the tile of each gradient is still in cache when it is back-propagated any
+/// further. Every gradient is written, as they can be inspected once the pass is over.
+///
+/// The hooks of a node must run once its gradient is fully accumulated, so only the first node of
+/// a fused group, in back-propagation order, may have some.
+pub(crate) fn backward<T>(tape: &[Op])
+where
+    T: Float,
+{
+    let reversed: Vec<_> = tape.iter().rev().collect();
+
+    let mut start = 0;
+    while start < reversed.len() {
+        let (mut end, mut len) = group(&reversed, start, |(op, _)| op.elementwise()?.fusible_len());
+        if let Some(hooked) = reversed[start + 1..end]
+            .iter()
+            .position(|(_, grad)| grad.has_hooks())
+        {
+            end = start + 1 + hooked;
+            len = len.filter(|_| end - start > 1);
+        }
+
+        let ops = &reversed[start..end];
+        ops[0].1.run_hooks();
+
+        let kernels = len.and_then(|_| {
+            ops.iter()
+                .map(|(op, _)| op.elementwise().unwrap().kernel().downcast().ok())
+                .collect::<Option<Vec<Box<BackwardKernel<T>>>>>()
+        });
+        match (len, kernels) {
+            (Some(len), Some(kernels)) => run_backward(&kernels, len),
+            _ => ops.iter().for_each(|(op, _)| op.backward()),
+        }
+
+        start = end;
+    }
+}
+
+/// Where a kernel finds a tile of one of its arguments.
+#[derive(Clone, Copy)]
+enum Argument {
+    /// The tile computed in the slot of the scratch space.
+    Slot(usize),
+    /// The tile of the array read.
+    Read(usize),
+}
+
+/// The arrays read by a fused group of ops, each borrowed once.
+struct Reads<'a, T> {
+    ids: Vec<usize>,
+    arrays: Vec<Ref<'a, [T]>>,
+    /// The tiles of the arrays with a single element, that is broadcast.
+    broadcast: Vec<Option<Vec<T>>>,
+}
+
+impl<'a, T> Reads<'a, T>
+where
+    T: Element,
+{
+    fn new() -> Self {
+        Self {
+            ids: Vec::new(),
+            arrays: Vec::new(),
+            broadcast: Vec::new(),
+        }
+    }
+
+    /// Returns the argument reading `array`.
+    fn argument(&mut self, array: &'a Rc<dyn Elements<T>>) -> Argument {
+        let id = array.id();
+        let position = self.ids.iter().position(|&other| other == id);
+
+        Argument::Read(position.unwrap_or_else(|| {
+            let elements = array.elements();
+            self.broadcast
+                .push((elements.len() == 1).then(|| vec![elements[0]; TILE_LEN]));
+            self.ids.push(id);
+            self.arrays.push(elements);
+
+            self.ids.len() - 1
+        }))
+    }
+
+    /// Returns the tile of `argument` for the elements in `range`.
+    fn tile<'b>(&'b self, argument: Argument, scratch: &'b [T], range: Range<usize>) -> &'b [T] {
+        match argument {
+            Argument::Slot(slot) => &scratch[slot * TILE_LEN..][..range.len()],
+            Argument::Read(position) => match &self.broadcast[position] {
+                Some(tile) => &tile[..range.len()],
+                None => &self.arrays[position][range],
+            },
+        }
+    }
+}
+
+/// Splits `0..len` in tiles.
+fn tiles(len: usize) -> impl Iterator<Item = Range<usize>> {
+    (0..len)
+        .step_by(TILE_LEN)
+        .map(move |start| start..len.min(start + TILE_LEN))
+}
+
+/// Computes the results of a fused group of forward kernels, in tape order.
+///
+/// Only the results marked in `stored` are written, the other ones can't be read by anyone
+/// outside of the group.
+fn run_forward<T>(kernels: &[Box<ForwardKernel<T>>], len: usize, stored: &[bool])
+where
+    T: Element,
+{
+    let ids: Vec<usize> = kernels.iter().map(|kernel| kernel.data.id()).collect();
+
+    let mut reads = Reads::new();
+    let arguments: Vec<Vec<Argument>> = kernels
+        .iter()
+        .map(|kernel| {
+            kernel
+                .operands
+                .iter()
+                .map(
+                    |operand| match ids.iter().position(|&id| id == operand.id()) {
+                        Some(slot) => Argument::Slot(slot),
+                        None => reads.argument(operand),
+                    },
+                )
+                .collect()
+        })
+        .collect();
+
+    let mut writes: Vec<(usize, RefMut<[T]>)> = kernels
+        .iter()
+        .enumerate()
+        .filter(|&(slot, _)| stored[slot])
+        .map(|(slot, kernel)| (slot, kernel.data.elements_mut()))
+        .collect();
+
+    let mut scratch = vec![T::default(); kernels.len() * TILE_LEN];
+    for range in tiles(len) {
+        for (slot, (kernel, arguments)) in kernels.iter().zip(&arguments).enumerate() {
+            let (computed, rest) = scratch.split_at_mut(slot * TILE_LEN);
+
+            // Kernels have at most two operands.
+            let mut operands: [&[T]; 2] = [&[], &[]];
+            operands
+                .iter_mut()
+                .zip(arguments)
+                .for_each(|(operand, &argument)| {
+                    *operand = reads.tile(argument, computed, range.clone())
+                });
+            (kernel.function)(&operands[..arguments.len()], &mut rest[..range.len()]);
+        }
+
+        writes.iter_mut().for_each(|(slot, data)| {
+            data[range.clone()].copy_from_slice(&scratch[*slot * TILE_LEN..][..range.len()])
+        });
+    }
+}
+
+/// Back-propagates the gradients of a fused group of backward kernels, in back-propagation order.
+///
+/// The gradients are accumulated into in place, as they already hold the contributions of the ops
+/// outside of the group.
+fn run_backward<T>(kernels: &[Box<BackwardKernel<T>>], len: usize)
+where
+    T: Float,
+{
+    let mut slots = Vec::new();
+    let mut reads = Reads::new();
+    let derivatives: Vec<Vec<(usize, Vec<Argument>)>> = kernels
+        .iter()
+        .map(|kernel| {
+            kernel
+                .derivatives
+                .iter()
+                .map(|derivative| {
+                    let data = derivative
+                        .data
+                        .iter()
+                        .map(|data| reads.argument(data))
+                        .collect();
+
+                    (slot(&mut slots, &derivative.operand_gradient), data)
+                })
+                .collect()
+        })
+        .collect();
+
+    // The gradients not accumulated into are fully accumulated before the group starts.
+    let gradients: Vec<Argument> = kernels
+        .iter()
+        .map(|kernel| {
+            let id = kernel.gradient.id();
+            match slots.iter().position(|other| other.id() == id) {
+                Some(slot) => Argument::Slot(slot),
+                None => reads.argument(&kernel.gradient),
+            }
+        })
+        .collect();
+
+    let mut accumulated: Vec<RefMut<[T]>> = slots.iter().map(|slot| slot.elements_mut()).collect();
+    for range in tiles(len) {
+        for ((kernel, derivatives), &argument) in kernels.iter().zip(&derivatives).zip(&gradients) {
+            for (derivative, &(slot, ref arguments)) in kernel.derivatives.iter().zip(derivatives) {
+                // Derivatives read at most two arrays.
+                let mut data: [&[T]; 2] = [&[], &[]];
+                data.iter_mut()
+                    .zip(arguments)
+                    .for_each(|(data, &argument)| *data = reads.tile(argument, &[], range.clone()));
+
+                // A node is never its own operand, so its gradient is never accumulated into.
+                let (gradient, operand_gradient) = match argument {
+                    Argument::Slot(gradient) => {
+                        let (gradient, operand_gradient) = pair(&mut accumulated, gradient, slot);
+                        (&gradient[range.clone()], operand_gradient)
+                    }
+                    Argument::Read(_) => (
+                        reads.tile(argument, &[], range.clone()),
+                        &mut *accumulated[slot],
+                    ),
+                };
+                (derivative.function)(
+                    gradient,
+                    &data[..arguments.len()],
+                    &mut operand_gradient[range.clone()],
+                );
+            }
+        }
+    }
+}
+
+/// Returns the array `read` and the distinct mutable array `write`.
+fn pair<'a, T>(arrays: &'a mut [RefMut<[T]>], read: usize, write: usize) -> (&'a [T], &'a mut [T]) {
+    if read < write {
+        let (head, tail) = arrays.split_at_mut(write);
+        (&head[read], &mut tail[0])
+    } else {
+        let (head, tail) = arrays.split_at_mut(read);
+        (&tail[0], &mut head[write])
+    }
+}
+
+/// Returns the position of `array` in `slots`, adding it if missing.
+fn slot<'a, T>(slots: &mut Vec<&'a Rc<dyn Elements<T>>>, array: &'a Rc<dyn Elements<T>>) -> usize {
+    slots
+        .iter()
+        .position(|other| other.id() == array.id())
+        .unwrap_or_else(|| {
+            slots.push(array);
+            slots.len() - 1
+        })
+}
+
+/// Returns the end of the group of ops starting at `start` and the number of elements of its
+/// ops, if it has more than one.
+fn group<O, F>(ops: &[O], start: usize, fusible_len: F) -> (usize, Option<usize>)
+where
+    F: Fn(&O) -> Option<usize>,
+{
+    let len = match fusible_len(&ops[start]) {
+        Some(len) => len,
+        None => return (start + 1, None),
+    };
+
+    let end = ops[start + 1..]
+        .iter()
+        .position(|op| fusible_len(op) != Some(len))
+        .map_or(ops.len(), |position| start + 1 + position);
+
+    (end, Some(len).filter(|_| end - start > 1))
+}
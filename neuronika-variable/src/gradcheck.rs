@@ -0,0 +1,360 @@
+use std::{
+    cmp::Ordering,
+    fmt::{self, Display},
+};
+
+use ndarray::Dimension;
+
+use crate::{Float, Parameter, VarDiff};
+
+/// An element of the gradient of an input whose value doesn't match its numerical estimate.
+///
+/// It is returned by [`gradcheck()`] and [`gradgradcheck()`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct Mismatch<T = f32> {
+    input: usize,
+    index: Vec<usize>,
+    analytical: T,
+    numerical: T,
+}
+
+impl<T> Mismatch<T>
+where
+    T: Float,
+{
+    /// Returns the position of the input in the slice passed to the check.
+    pub fn input(&self) -> usize {
+        self.input
+    }
+
+    /// Returns the index of the element in the input.
+    pub fn index(&self) -> &[usize] {
+        &self.index
+    }
+
+    /// Returns the value computed through back-propagation.
+    pub fn analytical(&self) -> T {
+        self.analytical
+    }
+
+    /// Returns the value estimated through finite differences.
+    pub fn numerical(&self) -> T {
+        self.numerical
+    }
+}
+
+impl<T> Display for Mismatch<T>
+where
+    T: Float,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "input {} at {:?}: analytical {}, numerical {}",
+            self.input, self.index, self.analytical, self.numerical
+        )
+    }
+}
+
+/// Checks the gradients computed through back-propagation against the ones estimated through
+/// central finite differences.
+///
+/// The output of `f` is differentiated with respect to each element of each input. If the output
+/// is not a scalar, the gradient of the sum of its elements is considered. Each element of the
+/// gradients must satisfy `|analytical - numerical| <= atol + rtol * |numerical|`.
+///
+/// `f` is called only once to build the computational graph, which is then evaluated as many
+/// times as needed. The data of the inputs is restored when the check terminates, while their
+/// gradients are overwritten.
+///
+/// # Arguments
+///
+/// * `f` - function building the output from the inputs.
+///
+/// * `inputs` - differentiable leaves the output depends on.
+///
+/// * `eps` - perturbation of the finite differences.
+///
+/// * `atol` - absolute tolerance.
+///
+/// * `rtol` - relative tolerance.
+///
+/// Returns, for each input with at least a mismatching element, the one that mismatches the most.
+///
+/// # Examples
+///
+/// ```
+/// # use neuronika_variable as neuronika;
+/// use ndarray::Array;
+/// use neuronika::Parameter;
+///
+/// let w = neuronika::from_ndarray(Array::from_shape_fn((3, 3), |(i, j)| (i + j) as f64 / 6.));
+/// let w = w.requires_grad();
+/// let b = neuronika::from_ndarray(Array::from_elem(3, 0.1)).requires_grad();
+/// let x = neuronika::from_ndarray(Array::from_shape_fn((4, 3), |(i, j)| (i * j) as f64 / 4.));
+///
+/// let inputs: [Parameter<f64>; 2] = [w.clone().into(), b.clone().into()];
+/// let result = neuronika::gradcheck(|| (x.mm(w) + b).tanh(), &inputs, 1e-6, 1e-5, 1e-3);
+/// assert!(result.is_ok());
+/// ```
+pub fn gradcheck<F, D, T>(
+    f: F,
+    inputs: &[Parameter<T>],
+    eps: T,
+    atol: T,
+    rtol: T,
+) -> Result<(), Vec<Mismatch<T>>>
+where
+    F: FnOnce() -> VarDiff<D, T>,
+    D: Dimension,
+    T: Float,
+{
+    let output = f();
+    let value = || {
+        output.forward();
+        output.data().sum()
+    };
+
+    let two = T::from_f64(2.);
+    let mut mismatches = Vec::new();
+    let gradients = gradients(&output, inputs);
+    for (position, (input, analytical)) in inputs.iter().zip(gradients).enumerate() {
+        let numerical = (0..analytical.len()).map(|index| {
+            let upper = shifted(&[(input, index, eps)], value);
+            let lower = shifted(&[(input, index, -eps)], value);
+
+            (upper - lower) / (two * eps)
+        });
+
+        let pairs = analytical.into_iter().zip(numerical).enumerate();
+        mismatches.extend(worst(position, input, pairs, atol, rtol));
+    }
+    output.forward();
+
+    if mismatches.is_empty() {
+        Ok(())
+    } else {
+        Err(mismatches)
+    }
+}
+
+/// Checks the derivatives of the gradients computed through back-propagation against the
+/// second-order derivatives estimated through central finite differences.
+///
+/// As the gradients computed by [`.backward()`](VarDiff::backward()) are not themselves
+/// differentiable, no second-order graph is needed: their derivatives are estimated by central
+/// finite differences of the gradients back-propagated by [`gradcheck()`]. These are then checked against the second-order central finite differences of the
+/// output of `f`, using the same criterion of [`gradcheck()`]. This ensures that the
+/// back-propagated gradients are also correct in a neighbourhood of the data of the inputs.
+///
+/// The number of evaluations grows with the square of the number of elements of the inputs,
+/// which should thus be kept small. Double precision is recommended.
+///
+/// # Arguments
+///
+/// * `f` - function building the output from the inputs.
+///
+/// * `inputs` - differentiable leaves the output depends on.
+///
+/// * `eps` - perturbation of the finite differences.
+///
+/// * `atol` - absolute tolerance.
+///
+/// * `rtol` - relative tolerance.
+///
+/// Returns, for each input with at least a mismatching element of its gradient, the one whose
+/// derivatives mismatch the most.
+///
+/// # Examples
+///
+/// ```
+/// # use neuronika_variable as neuronika;
+/// use neuronika::Parameter;
+///
+/// let x = neuronika::from_ndarray(ndarray::array![-1., 0., 1.]).requires_grad();
+///
+/// let inputs: [Parameter<f64>; 1] = [x.clone().into()];
+/// let result = neuronika::gradgradcheck(|| x.sigmoid().pow(2), &inputs, 1e-4, 1e-4, 1e-3);
+/// assert!(result.is_ok());
+/// ```
+pub fn gradgradcheck<F, D, T>(
+    f: F,
+    inputs: &[Parameter<T>],
+    eps: T,
+    atol: T,
+    rtol: T,
+) -> Result<(), Vec<Mismatch<T>>>
+where
+    F: FnOnce() -> VarDiff<D, T>,
+    D: Dimension,
+    T: Float,
+{
+    let output = f();
+    let value = || {
+        output.forward();
+        output.data().sum()
+    };
+
+    // Positions of the elements of the inputs.
+    let elements: Vec<(&Parameter<T>, usize)> = inputs
+        .iter()
+        .flat_map(|input| (0..input.len()).map(move |index| (input, index)))
+        .collect();
+
+    // Derivatives of the gradients, one column per element.
+    let two = T::from_f64(2.);
+    let analytical: Vec<Vec<T>> = elements
+        .iter()
+        .map(|&(input, index)| {
+            let upper = shifted(&[(input, index, eps)], || flat_gradients(&output, inputs));
+            let lower = shifted(&[(input, index, -eps)], || flat_gradients(&output, inputs));
+
+            upper
+                .into_iter()
+                .zip(lower)
+                .map(|(upper, lower)| (upper - lower) / (two * eps))
+                .collect()
+        })
+        .collect();
+
+    let mut mismatches = Vec::new();
+    let mut offset = 0;
+    for (position, input) in inputs.iter().enumerate() {
+        let pairs = (offset..offset + input.len()).flat_map(|row| {
+            let (row_input, row_index) = elements[row];
+            let analytical = &analytical;
+            elements
+                .iter()
+                .enumerate()
+                .map(move |(column, &(input, index))| {
+                    let evaluate = |first, second| {
+                        shifted(
+                            &[(row_input, row_index, first), (input, index, second)],
+                            value,
+                        )
+                    };
+                    let numerical =
+                        (evaluate(eps, eps) - evaluate(eps, -eps) - evaluate(-eps, eps)
+                            + evaluate(-eps, -eps))
+                            / (two * two * eps * eps);
+
+                    (row - offset, (analytical[column][row], numerical))
+                })
+        });
+        mismatches.extend(worst(position, input, pairs, atol, rtol));
+
+        offset += input.len();
+    }
+    output.forward();
+
+    if mismatches.is_empty() {
+        Ok(())
+    } else {
+        Err(mismatches)
+    }
+}
+
+/// Back-propagates through `output` and returns the gradients of `inputs`.
+fn gradients<D, T>(output: &VarDiff<D, T>, inputs: &[Parameter<T>]) -> Vec<Vec<T>>
+where
+    D: Dimension,
+    T: Float,
+{
+    output.forward();
+    inputs.iter().for_each(Parameter::zero_grad);
+    output.backward(T::one());
+
+    inputs
+        .iter()
+        .map(|input| input.grad().iter().copied().collect())
+        .collect()
+}
+
+/// Back-propagates through `output` and returns the gradients of `inputs`, concatenated.
+fn flat_gradients<D, T>(output: &VarDiff<D, T>, inputs: &[Parameter<T>]) -> Vec<T>
+where
+    D: Dimension,
+    T: Float,
+{
+    gradients(output, inputs).into_iter().flatten().collect()
+}
+
+/// Evaluates `f` after shifting some elements of the inputs, restoring them afterwards. The same
+/// element can be shifted more than once.
+///
+/// # Arguments
+///
+/// * `shifts` - inputs, logical positions of their elements and values to add to them.
+///
+/// * `f` - function to evaluate.
+fn shifted<T, R>(shifts: &[(&Parameter<T>, usize, T)], f: impl FnOnce() -> R) -> R
+where
+    T: Float,
+{
+    let previous: Vec<T> = shifts
+        .iter()
+        .map(|&(input, index, delta)| input.shift(index, delta))
+        .collect();
+    let result = f();
+    shifts
+        .iter()
+        .zip(previous)
+        .rev()
+        .for_each(|(&(input, index, _), previous)| {
+            input.replace(index, previous);
+        });
+
+    result
+}
+
+/// Returns the pair of analytical and numerical values that mismatches the most, if any.
+///
+/// # Arguments
+///
+/// * `position` - position of the input.
+///
+/// * `input` - input the values refer to.
+///
+/// * `pairs` - logical positions of the elements together with their analytical and numerical values.
+///
+/// * `atol` - absolute tolerance.
+///
+/// * `rtol` - relative tolerance.
+fn worst<T>(
+    position: usize,
+    input: &Parameter<T>,
+    pairs: impl Iterator<Item = (usize, (T, T))>,
+    atol: T,
+    rtol: T,
+) -> Option<Mismatch<T>>
+where
+    T: Float,
+{
+    let (index, (analytical, numerical), _) = pairs
+        .map(|(index, (analytical, numerical))| {
+            let excess = (analytical - numerical).abs() - (atol + rtol * numerical.abs());
+
+            // Non-finite values always mismatch.
+            let excess = if excess.is_nan() {
+                T::infinity()
+            } else {
+                excess
+            };
+
+            (index, (analytical, numerical), excess)
+        })
+        .filter(|&(_, _, excess)| excess > T::zero())
+        .max_by(|(_, _, lhs), (_, _, rhs)| lhs.partial_cmp(rhs).unwrap_or(Ordering::Equal))?;
+
+    Some(Mismatch {
+        input: position,
+        index: ndarray::indices(input.shape())
+            .into_iter()
+            .nth(index)
+            .unwrap()
+            .slice()
+            .to_vec(),
+        analytical,
+        numerical,
+    })
+}
@@ -1,69 +1,195 @@
-use std::{
-    cell::{Ref, RefCell, RefMut},
-    rc::Rc,
-};
+use ndarray::{Array, Dimension, ShapeBuilder, Zip};
 
-use ndarray::{Array, Dimension, ShapeBuilder};
+use crate::{
+    cell::{Cell, MaybeSync, Rc, Ref, RefCell, RefMut},
+    fusion::Elements,
+    hook::{Hook, Hooks, RemoveHook},
+    memory::BufferPool,
+    Float,
+};
 
-pub(crate) trait NoGrad {
+pub(crate) trait NoGrad: MaybeSync {
     fn no_grad(&self);
 
     fn with_grad(&self);
 }
 
+/// Operations performed by the tape on the gradient of a node during back-propagation.
+pub(crate) trait NodeGradient: NoGrad {
+    /// Zeroes the gradient so that it only accumulates the contributions of the current pass.
+    fn zero(&self);
+
+    /// Runs the hooks on the fully accumulated gradient, before it is propagated any further.
+    fn run_hooks(&self);
+
+    /// Returns `true` if there are hooks to run on the gradient.
+    fn has_hooks(&self) -> bool;
+
+    /// Returns the number of bytes currently allocated for the gradient.
+    fn bytes(&self) -> usize;
+
+    /// Returns the sizes in bytes of the buffers the gradient needs once allocated.
+    fn sizes(&self) -> Vec<usize>;
+
+    /// Allocates the gradient, zeroed, recycling the buffers in `pool` when possible.
+    fn acquire(&self, pool: &mut BufferPool);
+
+    /// De-allocates the gradient, putting its buffers in `pool`.
+    fn release(&self, pool: &mut BufferPool);
+
+    /// Hands the gradient over to a memory plan, de-allocating it and putting its buffers in
+    /// `pool`.
+    fn hand_over(&self, pool: &mut BufferPool);
+
+    /// Returns `true` if the gradient has been handed over to a memory plan.
+    fn is_planned(&self) -> bool;
+
+    /// Returns `false` if the gradient holds infinite or NaN values.
+    fn is_finite(&self) -> bool;
+}
+
+/// Operations performed by the tape on the gradient of a differentiable leaf during
+/// back-propagation.
+pub(crate) trait LeafGradient: MaybeSync {
+    /// Called before the backward pass starts accumulating into the gradient.
+    fn begin_accumulation(&self);
+
+    /// Called once the backward pass is over, runs the hooks.
+    fn end_accumulation(&self);
+
+    /// Returns the number of bytes currently allocated for the gradient.
+    fn bytes(&self) -> usize;
+
+    /// Returns `false` if the gradient holds infinite or NaN values.
+    fn is_finite(&self) -> bool;
+}
+
 pub(crate) struct Gradient<T, D>
 where
     D: Dimension,
 {
     shape: D,
     array: RefCell<Option<T>>,
+    hooks: RefCell<Hooks<T>>,
+    stash: RefCell<Option<T>>,
+    planned: Cell<bool>,
 }
 
 impl<T, D> Gradient<T, D>
 where
     D: Dimension,
 {
+    fn new(shape: D, array: Option<T>) -> Self {
+        Self {
+            shape,
+            array: RefCell::new(array),
+            hooks: RefCell::default(),
+            stash: RefCell::new(None),
+            planned: Cell::new(false),
+        }
+    }
+
     pub(crate) fn borrow(&self) -> Ref<T> {
+        let planned = self.planned.get();
         Ref::map(self.array.borrow(), |option| {
-            option.as_ref().expect("Trying to get a de-allocated gradient. Switch on the gradients first by using `.with_grad()`")
+            option.as_ref().unwrap_or_else(|| de_allocated(planned))
         })
     }
 
     pub(crate) fn borrow_mut(&self) -> RefMut<T> {
+        let planned = self.planned.get();
         RefMut::map(self.array.borrow_mut(), |option| {
-            option.as_mut().expect("Trying to get a de-allocated gradient. Switch on the gradients first by using `.with_grad()`")
+            option.as_mut().unwrap_or_else(|| de_allocated(planned))
         })
     }
 
     pub(crate) fn shape(&self) -> D {
         self.shape.clone()
     }
+
+    /// Registers a hook that runs on the gradient once it is fully accumulated and returns its id.
+    pub(crate) fn register_hook(&self, hook: Hook<T>) -> usize {
+        let mut hooks = self.hooks.borrow_mut();
+        let id = hooks.next_id();
+        hooks.pre.push((id, hook));
+
+        id
+    }
+
+    /// Registers a hook that runs on the gradient of a leaf after the backward pass has
+    /// accumulated into it and returns its id.
+    pub(crate) fn register_post_accumulate_hook(&self, hook: Hook<T>) -> usize {
+        let mut hooks = self.hooks.borrow_mut();
+        let id = hooks.next_id();
+        hooks.post.push((id, hook));
+
+        id
+    }
 }
 
-impl<D> Gradient<Array<f32, D>, D>
+impl<D, T> Gradient<Array<T, D>, D>
 where
     D: Dimension,
+    T: Float,
 {
     pub(crate) fn ndarray_zeros<Sh: ShapeBuilder<Dim = D>>(shape: Sh) -> Self {
         let array = Array::zeros(shape);
 
-        Self {
-            shape: array.raw_dim(),
-            array: RefCell::new(Some(array)),
-        }
+        Self::new(array.raw_dim(), Some(array))
+    }
+
+    pub(crate) fn from_ndarray(array: Array<T, D>) -> Self {
+        Self::new(array.raw_dim(), Some(array))
+    }
+
+    fn allocated_bytes(&self) -> usize {
+        allocated_bytes(&self.array)
+    }
+
+    fn size(&self) -> usize {
+        self.shape.size() * std::mem::size_of::<T>()
+    }
+
+    fn is_finite(&self) -> bool {
+        self.array
+            .borrow()
+            .iter()
+            .flatten()
+            .all(|el| !el.is_non_finite())
+    }
+}
+
+impl<D, T> Elements<T> for Gradient<Array<T, D>, D>
+where
+    D: Dimension,
+{
+    fn id(&self) -> usize {
+        self as *const Self as *const () as usize
     }
 
-    pub(crate) fn from_ndarray(array: Array<f32, D>) -> Self {
-        let shape = array.raw_dim();
-        let array = RefCell::new(Some(array));
+    fn elements(&self) -> Ref<'_, [T]> {
+        Ref::map(self.borrow(), |array| array.as_slice().unwrap())
+    }
+
+    fn elements_mut(&self) -> RefMut<'_, [T]> {
+        RefMut::map(self.borrow_mut(), |array| array.as_slice_mut().unwrap())
+    }
+}
 
-        Self { shape, array }
+impl<T, D> RemoveHook for Gradient<T, D>
+where
+    T: MaybeSync,
+    D: Dimension,
+{
+    fn remove_hook(&self, id: usize) {
+        self.hooks.borrow_mut().remove(id);
     }
 }
 
-impl<D> NoGrad for Gradient<Array<f32, D>, D>
+impl<D, T> NoGrad for Gradient<Array<T, D>, D>
 where
     D: Dimension,
+    T: Float,
 {
     fn no_grad(&self) {
         *self.array.borrow_mut() = None;
@@ -78,6 +204,108 @@ where
     }
 }
 
+impl<D, T> NodeGradient for Gradient<Array<T, D>, D>
+where
+    D: Dimension,
+    T: Float,
+{
+    fn zero(&self) {
+        if let Some(array) = &mut *self.array.borrow_mut() {
+            array.fill(T::zero());
+        }
+    }
+
+    fn run_hooks(&self) {
+        let mut hooks = self.hooks.borrow_mut();
+
+        if hooks.pre.is_empty() {
+            return;
+        }
+
+        let mut array = self.borrow_mut();
+        hooks.pre.iter_mut().for_each(|(_, hook)| hook(&mut array));
+    }
+
+    fn has_hooks(&self) -> bool {
+        !self.hooks.borrow().pre.is_empty()
+    }
+
+    fn bytes(&self) -> usize {
+        self.allocated_bytes()
+    }
+
+    fn sizes(&self) -> Vec<usize> {
+        vec![self.size()]
+    }
+
+    fn acquire(&self, pool: &mut BufferPool) {
+        acquire(&self.array, self.shape.clone(), pool);
+    }
+
+    fn release(&self, pool: &mut BufferPool) {
+        release(&self.array, pool);
+    }
+
+    fn hand_over(&self, pool: &mut BufferPool) {
+        self.planned.set(true);
+        self.release(pool);
+    }
+
+    fn is_planned(&self) -> bool {
+        self.planned.get()
+    }
+
+    fn is_finite(&self) -> bool {
+        Gradient::is_finite(self)
+    }
+}
+
+impl<D, T> LeafGradient for Gradient<Array<T, D>, D>
+where
+    D: Dimension,
+    T: Float,
+{
+    fn begin_accumulation(&self) {
+        // The gradient of a leaf is never reset, so in order to hand the hooks only the
+        // contribution of the current pass its previous value is stashed.
+        if !self.hooks.borrow().pre.is_empty() {
+            *self.stash.borrow_mut() = Some(self.borrow().clone());
+        }
+    }
+
+    fn end_accumulation(&self) {
+        let mut hooks = self.hooks.borrow_mut();
+
+        if hooks.pre.is_empty() && hooks.post.is_empty() {
+            return;
+        }
+
+        let mut array = self.borrow_mut();
+        if let Some(stash) = self.stash.borrow_mut().take() {
+            let mut incoming = &*array - &stash;
+            hooks
+                .pre
+                .iter_mut()
+                .for_each(|(_, hook)| hook(&mut incoming));
+
+            Zip::from(&mut *array)
+                .and(&stash)
+                .and(&incoming)
+                .for_each(|array_el, &stash_el, &incoming_el| *array_el = stash_el + incoming_el);
+        }
+
+        hooks.post.iter_mut().for_each(|(_, hook)| hook(&mut array));
+    }
+
+    fn bytes(&self) -> usize {
+        self.allocated_bytes()
+    }
+
+    fn is_finite(&self) -> bool {
+        Gradient::is_finite(self)
+    }
+}
+
 pub(crate) struct BufferedGradient<T, D>
 where
     D: Dimension,
@@ -112,20 +340,40 @@ where
     }
 }
 
-impl<D> BufferedGradient<Array<f32, D>, D>
+impl<D, T> BufferedGradient<Array<T, D>, D>
 where
     D: Dimension,
+    T: Float,
 {
-    pub(crate) fn from_ndarray(gradient: Rc<Gradient<Array<f32, D>, D>>) -> Self {
+    pub(crate) fn from_ndarray(gradient: Rc<Gradient<Array<T, D>, D>>) -> Self {
         let buffer = RefCell::new(Some(Array::zeros(gradient.shape())));
 
         Self { gradient, buffer }
     }
 }
 
-impl<D> NoGrad for BufferedGradient<Array<f32, D>, D>
+// The buffer is only used by the unfused backward pass.
+impl<D, T> Elements<T> for BufferedGradient<Array<T, D>, D>
 where
     D: Dimension,
+{
+    fn id(&self) -> usize {
+        self.gradient.id()
+    }
+
+    fn elements(&self) -> Ref<'_, [T]> {
+        self.gradient.elements()
+    }
+
+    fn elements_mut(&self) -> RefMut<'_, [T]> {
+        self.gradient.elements_mut()
+    }
+}
+
+impl<D, T> NoGrad for BufferedGradient<Array<T, D>, D>
+where
+    D: Dimension,
+    T: Float,
 {
     fn no_grad(&self) {
         self.gradient.no_grad();
@@ -141,3 +389,98 @@ where
         }
     }
 }
+
+impl<D, T> NodeGradient for BufferedGradient<Array<T, D>, D>
+where
+    D: Dimension,
+    T: Float,
+{
+    fn zero(&self) {
+        self.gradient.zero();
+    }
+
+    fn run_hooks(&self) {
+        self.gradient.run_hooks();
+    }
+
+    fn has_hooks(&self) -> bool {
+        self.gradient.has_hooks()
+    }
+
+    fn bytes(&self) -> usize {
+        self.gradient.allocated_bytes() + allocated_bytes(&self.buffer)
+    }
+
+    fn sizes(&self) -> Vec<usize> {
+        vec![self.gradient.size(), self.gradient.size()]
+    }
+
+    fn acquire(&self, pool: &mut BufferPool) {
+        self.gradient.acquire(pool);
+        acquire(&self.buffer, self.shape(), pool);
+    }
+
+    fn release(&self, pool: &mut BufferPool) {
+        self.gradient.release(pool);
+        release(&self.buffer, pool);
+    }
+
+    fn hand_over(&self, pool: &mut BufferPool) {
+        self.gradient.hand_over(pool);
+        release(&self.buffer, pool);
+    }
+
+    fn is_planned(&self) -> bool {
+        self.gradient.is_planned()
+    }
+
+    fn is_finite(&self) -> bool {
+        Gradient::is_finite(&self.gradient)
+    }
+}
+
+/// Panics on the access to a de-allocated gradient, `planned` tells whether it's been handed over
+/// to a memory plan.
+fn de_allocated(planned: bool) -> ! {
+    if planned {
+        panic!("error: the gradient of an intermediate node is de-allocated by the memory plan of its graph, see `.plan_memory()`.")
+    }
+
+    panic!("Trying to get a de-allocated gradient. Switch on the gradients first by using `.with_grad()`")
+}
+
+/// Returns the number of bytes allocated for an optional array.
+fn allocated_bytes<D, T>(array: &RefCell<Option<Array<T, D>>>) -> usize
+where
+    D: Dimension,
+    T: Float,
+{
+    array
+        .borrow()
+        .as_ref()
+        .map_or(0, |array| array.len() * std::mem::size_of::<T>())
+}
+
+/// Allocates an optional array of zeros of the given shape from `pool`, if it's not allocated.
+fn acquire<D, T>(array: &RefCell<Option<Array<T, D>>>, shape: D, pool: &mut BufferPool)
+where
+    D: Dimension,
+    T: Float,
+{
+    let mut option = array.borrow_mut();
+
+    if option.is_none() {
+        *option = Some(pool.zeros(shape));
+    }
+}
+
+/// De-allocates an optional array, putting its buffer in `pool`.
+fn release<D, T>(array: &RefCell<Option<Array<T, D>>>, pool: &mut BufferPool)
+where
+    D: Dimension,
+    T: Float,
+{
+    if let Some(array) = array.borrow_mut().take() {
+        pool.put(array);
+    }
+}
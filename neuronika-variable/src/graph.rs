@@ -0,0 +1,224 @@
+use std::{collections::HashMap, fmt::Write};
+
+use ndarray::{Array, Dimension};
+
+use crate::{autograd::Forward, cell::Rc, utils::Shared, Element};
+
+/// Description of a buffer of the computational graph.
+#[derive(Clone)]
+pub(crate) struct Buffer {
+    pub(crate) id: usize,
+    pub(crate) shape: Vec<usize>,
+    pub(crate) element_size: usize,
+}
+
+impl Buffer {
+    /// Describes the buffer `data`.
+    pub(crate) fn new<D, T>(data: &Shared<Array<T, D>>) -> Self
+    where
+        D: Dimension,
+        T: Element,
+    {
+        Self::with_shape(data, data.borrow().shape(), std::mem::size_of::<T>())
+    }
+
+    /// Describes the buffer `data`, whose shape is `shape` and whose elements take
+    /// `element_size` bytes each.
+    pub(crate) fn with_shape<C>(data: &Shared<C>, shape: &[usize], element_size: usize) -> Self {
+        Self {
+            id: Rc::as_ptr(data) as *const () as usize,
+            shape: shape.to_vec(),
+            element_size,
+        }
+    }
+}
+
+/// A node of the computational graph of a variable.
+///
+/// This is a read-only snapshot taken when the graph is requested with
+/// [`Var::graph()`](crate::Var::graph()) or [`VarDiff::graph()`](crate::VarDiff::graph()).
+#[derive(Clone, Debug)]
+pub struct GraphNode {
+    op: Option<&'static str>,
+    shape: Vec<usize>,
+    parents: Vec<usize>,
+    element_size: usize,
+    grad_bytes: Option<usize>,
+}
+
+impl GraphNode {
+    /// Returns the name of the operation that computes the node, or `None` if the node is a leaf.
+    pub fn op(&self) -> Option<&'static str> {
+        self.op
+    }
+
+    /// Returns `true` if the node is a leaf.
+    pub fn is_leaf(&self) -> bool {
+        self.op.is_none()
+    }
+
+    /// Returns the shape of the result of the node.
+    pub fn shape(&self) -> &[usize] {
+        &self.shape
+    }
+
+    /// Returns the positions of the operands of the node in [`.nodes()`](Graph::nodes()).
+    pub fn parents(&self) -> &[usize] {
+        &self.parents
+    }
+
+    /// Returns `true` if the gradient of the node is computed during the backward pass.
+    pub fn requires_grad(&self) -> bool {
+        self.grad_bytes.is_some()
+    }
+
+    /// Returns the number of bytes used by the data of the node.
+    pub fn data_bytes(&self) -> usize {
+        self.shape.iter().product::<usize>() * self.element_size
+    }
+
+    /// Returns the number of bytes used by the gradient of the node, including any auxiliary
+    /// buffer employed by its backward pass. This is `0` for nodes that do not require the
+    /// gradient or whose gradient has been de-allocated with `.no_grad()`.
+    pub fn grad_bytes(&self) -> usize {
+        self.grad_bytes.unwrap_or(0)
+    }
+}
+
+/// A read-only snapshot of the computational graph of a variable.
+///
+/// The nodes are sorted in topological order, so that each node comes after its operands and the
+/// variable the graph was requested on is the last one.
+///
+/// # Examples
+///
+/// ```
+/// # use neuronika_variable as neuronika;
+/// let w = neuronika::ones((2, 3)).requires_grad();
+/// let b = neuronika::zeros(2).requires_grad();
+/// let x = neuronika::ones((4, 3));
+/// let y = (x.mm_t(w) + b).relu();
+///
+/// let graph = y.graph();
+/// for node in graph.nodes() {
+///     println!("{:?} {:?} <- {:?}", node.op(), node.shape(), node.parents());
+/// }
+///
+/// assert_eq!(graph.differentiable_leaves().count(), 2);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Graph {
+    nodes: Vec<GraphNode>,
+}
+
+impl Graph {
+    /// Builds the graph from the ops of a forward tape.
+    ///
+    /// # Arguments
+    ///
+    /// * `ops` - forward tape, in execution order.
+    ///
+    /// * `gradients` - sizes in bytes of the gradients, indexed by the ids of their buffers.
+    pub(crate) fn new<'a, I>(ops: I, gradients: &HashMap<usize, usize>) -> Self
+    where
+        I: IntoIterator<Item = &'a Rc<dyn Forward>>,
+    {
+        let mut nodes = Vec::new();
+        let mut positions = HashMap::new();
+
+        for op in ops {
+            let parents = op
+                .operands()
+                .into_iter()
+                .map(|operand| {
+                    // Operands that are not computed by any op are leaves.
+                    *positions.entry(operand.id).or_insert_with(|| {
+                        nodes.push(GraphNode {
+                            op: None,
+                            grad_bytes: gradients.get(&operand.id).copied(),
+                            shape: operand.shape,
+                            element_size: operand.element_size,
+                            parents: Vec::new(),
+                        });
+
+                        nodes.len() - 1
+                    })
+                })
+                .collect();
+
+            let data = op.data();
+            positions.insert(data.id, nodes.len());
+            nodes.push(GraphNode {
+                op: Some(op.name()),
+                grad_bytes: gradients.get(&data.id).copied(),
+                shape: data.shape,
+                element_size: data.element_size,
+                parents,
+            });
+        }
+
+        Self { nodes }
+    }
+
+    /// Builds the graph of a variable with no history.
+    pub(crate) fn leaf(data: Buffer, gradients: &HashMap<usize, usize>) -> Self {
+        Self {
+            nodes: vec![GraphNode {
+                op: None,
+                grad_bytes: gradients.get(&data.id).copied(),
+                shape: data.shape,
+                element_size: data.element_size,
+                parents: Vec::new(),
+            }],
+        }
+    }
+
+    /// Returns the nodes of the graph in topological order.
+    pub fn nodes(&self) -> &[GraphNode] {
+        &self.nodes
+    }
+
+    /// Returns the nodes of the differentiable leaves of the graph. Handles to the leaves
+    /// themselves are returned by [`VarDiff::parameters()`](crate::VarDiff::parameters()).
+    pub fn differentiable_leaves(&self) -> impl Iterator<Item = &GraphNode> {
+        self.nodes
+            .iter()
+            .filter(|node| node.is_leaf() && node.requires_grad())
+    }
+
+    /// Renders the graph in the Graphviz DOT language.
+    pub(crate) fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph {\n    rankdir=LR;\n");
+
+        for (position, node) in self.nodes.iter().enumerate() {
+            let attributes = match (node.op, node.requires_grad()) {
+                (Some(op), _) => format!("label=\"{}\\n{:?}\", shape=box", op, node.shape),
+                (None, false) => format!("label=\"Leaf\\n{:?}\", shape=ellipse", node.shape),
+                (None, true) => format!(
+                    "label=\"Parameter\\n{:?}\", shape=ellipse, style=filled, fillcolor=lightblue",
+                    node.shape
+                ),
+            };
+            writeln!(dot, "    {} [{}];", position, attributes).unwrap();
+        }
+
+        for (position, node) in self.nodes.iter().enumerate() {
+            for &parent in &node.parents {
+                // The gradient flows along an edge only if both ends are differentiable.
+                if node.requires_grad() && self.nodes[parent].requires_grad() {
+                    writeln!(
+                        dot,
+                        "    {} -> {} [color=red, penwidth=2];",
+                        parent, position
+                    )
+                } else {
+                    writeln!(dot, "    {} -> {};", parent, position)
+                }
+                .unwrap();
+            }
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+}
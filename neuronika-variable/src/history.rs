@@ -1,25 +1,32 @@
 use std::{
-    cell::{Ref, RefCell, RefMut},
     collections::BTreeMap,
+    sync::atomic::{AtomicUsize, Ordering},
 };
 
-/// Id of an operation in the tape. The first component is the address of the struct and the second
-/// is the size of the history at insertion. The former is unique, the latter enforces order.
+use crate::cell::{Ref, RefCell, RefMut};
+
+/// Number of operations ever appended to a tape, used to break ties between ids.
+static CREATED: AtomicUsize = AtomicUsize::new(0);
+
+/// Id of an operation in the tape. The first component is the address of the struct, the second
+/// is the size of the history at insertion and the third is the creation sequence number of the
+/// id. The first is unique, the second enforces order and the third breaks ties between ops
+/// recorded in different tapes.
 #[derive(Copy, Clone, Eq)]
-struct HistoryId((usize, usize));
+struct HistoryId((usize, usize, usize));
 
 impl HistoryId {
     /// Creates a new id from a pointer value and the instantaneous order of the op in the local
     /// tape.
     fn new(ptr: usize, order: usize) -> Self {
-        Self((ptr, order))
+        Self((ptr, order, CREATED.fetch_add(1, Ordering::Relaxed)))
     }
 }
 
 impl PartialEq for HistoryId {
     fn eq(&self, other: &Self) -> bool {
-        let Self((lhs_ptr, _)) = self;
-        let Self((rhs_ptr, _)) = other;
+        let Self((lhs_ptr, ..)) = self;
+        let Self((rhs_ptr, ..)) = other;
 
         lhs_ptr == rhs_ptr
     }
@@ -27,20 +34,17 @@ impl PartialEq for HistoryId {
 
 impl Ord for HistoryId {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        let Self((lhs_ptr, lhs_order)) = self;
-        let Self((rhs_ptr, rhs_order)) = other;
+        let Self((lhs_ptr, lhs_order, lhs_seq)) = self;
+        let Self((rhs_ptr, rhs_order, rhs_seq)) = other;
 
         // Equal only if the pointers are the same.
         if lhs_ptr == rhs_ptr {
             return std::cmp::Ordering::Equal;
         }
 
-        // But same ordering does not imply equality.
-        if lhs_order == rhs_order {
-            return std::cmp::Ordering::Less;
-        }
-
-        lhs_order.cmp(rhs_order)
+        // But same ordering does not imply equality, ties are broken by creation so that the
+        // ordering stays total and merged tapes don't end up with duplicates.
+        (lhs_order, lhs_seq).cmp(&(rhs_order, rhs_seq))
     }
 }
 
@@ -50,18 +54,22 @@ impl PartialOrd for HistoryId {
     }
 }
 
+/// The tape of the computations a variable depends on, together with the leaves it depends on.
 #[derive(Clone)]
-pub(crate) struct History<T>
+pub(crate) struct History<T, L = ()>
 where
     T: Clone,
+    L: Clone,
 {
     path: BTreeMap<HistoryId, T>,
+    leaves: BTreeMap<usize, L>,
     buffer: RefCell<Vec<T>>,
 }
 
-impl<T> History<T>
+impl<T, L> History<T, L>
 where
     T: Clone,
+    L: Clone,
 {
     /// Performs the merge between this history and another one.
     ///
@@ -70,6 +78,7 @@ where
     /// `other` - other history.
     pub(crate) fn merge(&mut self, mut other: Self) {
         self.path.append(&mut other.path);
+        self.leaves.append(&mut other.leaves);
     }
 
     /// Appends a new computation to the history.
@@ -86,6 +95,34 @@ where
         self.buffer.borrow_mut().truncate(0);
     }
 
+    /// Registers a leaf in the history.
+    ///
+    /// # Arguments
+    ///
+    /// * `ptr` - address of the leaf.
+    ///
+    /// * `leaf` - leaf to register.
+    pub(crate) fn insert_leaf(&mut self, ptr: usize, leaf: L) {
+        self.leaves.insert(ptr, leaf);
+    }
+
+    /// Returns the leaves the history depends on.
+    pub(crate) fn leaves(&self) -> impl Iterator<Item = &L> {
+        self.leaves.values()
+    }
+
+    /// Returns the computations in the history together with the addresses of their nodes.
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (usize, &T)> {
+        self.path
+            .iter()
+            .map(|(&HistoryId((ptr, ..)), op)| (ptr, op))
+    }
+
+    /// Returns the leaves the history depends on together with their addresses.
+    pub(crate) fn iter_leaves(&self) -> impl Iterator<Item = (usize, &L)> {
+        self.leaves.iter().map(|(&ptr, leaf)| (ptr, leaf))
+    }
+
     /// Returns the length of the history.
     pub(crate) fn len(&self) -> usize {
         self.path.len()
@@ -112,14 +149,20 @@ where
     }
 }
 
-impl<T> Default for History<T>
+impl<T, L> Default for History<T, L>
 where
     T: Clone,
+    L: Clone,
 {
     fn default() -> Self {
         let path = BTreeMap::new();
+        let leaves = BTreeMap::new();
         let buffer = RefCell::new(Vec::new());
 
-        Self { path, buffer }
+        Self {
+            path,
+            leaves,
+            buffer,
+        }
     }
 }
@@ -0,0 +1,70 @@
+use crate::cell::{Cell, MaybeSync, Weak};
+
+/// A function that inspects or modifies a gradient during the backward pass.
+#[cfg(not(feature = "sync"))]
+pub(crate) type Hook<T> = Box<dyn FnMut(&mut T)>;
+
+/// A function that inspects or modifies a gradient during the backward pass.
+#[cfg(feature = "sync")]
+pub(crate) type Hook<T> = Box<dyn FnMut(&mut T) + Send + Sync>;
+
+/// The hooks registered on a gradient.
+pub(crate) struct Hooks<T> {
+    next_id: Cell<usize>,
+    pub(crate) pre: Vec<(usize, Hook<T>)>,
+    pub(crate) post: Vec<(usize, Hook<T>)>,
+}
+
+impl<T> Hooks<T> {
+    /// Returns a fresh id for a hook.
+    pub(crate) fn next_id(&self) -> usize {
+        let id = self.next_id.get();
+        self.next_id.set(id + 1);
+
+        id
+    }
+
+    /// Removes the hook with the given id, if present.
+    pub(crate) fn remove(&mut self, id: usize) {
+        self.pre.retain(|(hook_id, _)| *hook_id != id);
+        self.post.retain(|(hook_id, _)| *hook_id != id);
+    }
+}
+
+impl<T> Default for Hooks<T> {
+    fn default() -> Self {
+        Self {
+            next_id: Cell::new(0),
+            pre: Vec::new(),
+            post: Vec::new(),
+        }
+    }
+}
+
+/// Something that owns hooks that can be removed by id.
+pub(crate) trait RemoveHook: MaybeSync {
+    fn remove_hook(&self, id: usize);
+}
+
+/// A handle to a hook registered on a differentiable variable.
+///
+/// The hook stays registered until [`.remove()`](HookHandle::remove()) is called. Dropping the
+/// handle does **not** remove the hook.
+pub struct HookHandle {
+    id: usize,
+    owner: Weak<dyn RemoveHook>,
+}
+
+impl HookHandle {
+    pub(crate) fn new(id: usize, owner: Weak<dyn RemoveHook>) -> Self {
+        Self { id, owner }
+    }
+
+    /// Removes the hook from the variable it was registered on. If the variable has already been
+    /// dropped this does nothing.
+    pub fn remove(self) {
+        if let Some(owner) = self.owner.upgrade() {
+            owner.remove_hook(self.id);
+        }
+    }
+}
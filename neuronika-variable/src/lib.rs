@@ -1,26 +1,52 @@
+mod anomaly;
 mod autograd;
+mod fusion;
+mod gradcheck;
 mod gradient;
+mod graph;
 mod history;
+mod hook;
+mod memory;
 mod node;
+mod parameter;
+mod sparse;
 mod utils;
 mod var;
 mod vardiff;
 
+#[cfg(feature = "sync")]
+mod parallel;
+
 #[cfg(feature = "serialize")]
 mod serde;
 
 use ndarray::{Array, Array2, Dimension, Ix1, Ix2, ShapeBuilder};
 
-use ndarray_rand::{rand_distr::Uniform, RandomExt};
+use ndarray_rand::{
+    rand_distr::{Bernoulli, Distribution, StandardNormal, Uniform},
+    RandomExt,
+};
 
 use neuronika_core::*;
 
 pub use crate::{
-    node::{Constant, PaddingMode, Reflective, Replicative, Zero},
+    anomaly::{is_anomaly_detection_enabled, set_anomaly_detection},
+    gradcheck::{gradcheck, gradgradcheck, Mismatch},
+    graph::{Graph, GraphNode},
+    hook::HookHandle,
+    memory::MemoryReport,
+    node::{
+        AttentionMask, Constant, Interpolation, PaddingMode, Reflective, Replicative, Resize, Zero,
+    },
+    parameter::Parameter,
+    sparse::{SparseLayout, SparseVar},
     var::Var,
     vardiff::VarDiff,
 };
 
+#[cfg(feature = "sync")]
+pub use crate::parallel::{DataParallel, Replicate};
+
 #[cfg(feature = "cuda")]
 pub mod cuda;
 
@@ -48,9 +74,10 @@ pub enum Reduction {
 ///
 /// assert_eq!(*t.data(), a);
 /// ```
-pub fn from_ndarray<D>(array: Array<f32, D>) -> Var<D>
+pub fn from_ndarray<D, T>(array: Array<T, D>) -> Var<D, T>
 where
     D: Dimension,
+    T: Element,
 {
     Var::leaf(array)
 }
@@ -126,6 +153,8 @@ where
 
 /// Creates a variable with values sampled from a uniform distribution on the interval *[0,1)*.
 ///
+/// The values are drawn from the generator of the current thread, see [`manual_seed()`].
+///
 /// # Examples
 ///
 /// ```
@@ -139,7 +168,91 @@ where
     D: Dimension,
     Sh: ShapeBuilder<Dim = D>,
 {
-    Var::leaf(Array::random(shape, Uniform::new(0., 1.)))
+    random(shape, Uniform::new(0., 1.))
+}
+
+/// Creates a variable with values sampled from a standard normal distribution.
+///
+/// The values are drawn from the generator of the current thread, see [`manual_seed()`].
+///
+/// # Examples
+///
+/// ```
+/// # use neuronika_variable as neuronika;
+///
+/// let x = neuronika::randn([4, 5, 6]);
+/// assert_eq!(x.data().shape(), &[4, 5, 6]);
+/// ```
+pub fn randn<D, Sh>(shape: Sh) -> Var<D>
+where
+    D: Dimension,
+    Sh: ShapeBuilder<Dim = D>,
+{
+    random(shape, StandardNormal)
+}
+
+/// Creates a variable with integers sampled uniformly from the interval *[low, high)*.
+///
+/// The values are drawn from the generator of the current thread, see [`manual_seed()`].
+///
+/// # Panics
+///
+/// If `low` >= `high`.
+///
+/// # Examples
+///
+/// ```
+/// # use neuronika_variable as neuronika;
+///
+/// let x = neuronika::randint(0, 10, [4, 5]);
+/// assert!(x.data().iter().all(|&el| (0..10).contains(&el)));
+/// ```
+pub fn randint<D, Sh>(low: i64, high: i64, shape: Sh) -> Var<D, i64>
+where
+    D: Dimension,
+    Sh: ShapeBuilder<Dim = D>,
+{
+    random(shape, Uniform::new(low, high))
+}
+
+/// Creates a variable with values sampled from a Bernoulli distribution, that are equal to 1 with
+/// probability *p* and to 0 otherwise.
+///
+/// The values are drawn from the generator of the current thread, see [`manual_seed()`].
+///
+/// # Panics
+///
+/// If `p` is not in the interval *[0,1]*.
+///
+/// # Examples
+///
+/// ```
+/// # use neuronika_variable as neuronika;
+///
+/// let x = neuronika::bernoulli([4, 5], 0.5);
+/// assert!(x.data().iter().all(|&el| el == 0. || el == 1.));
+/// ```
+pub fn bernoulli<D, Sh>(shape: Sh, p: f64) -> Var<D>
+where
+    D: Dimension,
+    Sh: ShapeBuilder<Dim = D>,
+{
+    let distribution = Bernoulli::new(p)
+        .unwrap_or_else(|_| panic!("error: invalid probability {} for bernoulli.", p));
+
+    random(shape, distribution.map(|el| if el { 1. } else { 0. }))
+}
+
+/// Creates a variable with values sampled from `distribution` using the generator of the current
+/// thread.
+fn random<D, Sh, T, Ds>(shape: Sh, distribution: Ds) -> Var<D, T>
+where
+    D: Dimension,
+    Sh: ShapeBuilder<Dim = D>,
+    T: Element,
+    Ds: Distribution<T>,
+{
+    with_generator(|generator| Var::leaf(Array::random_using(shape, distribution, generator)))
 }
 
 /// Creates a variable with an identity matrix of size *n*.
@@ -285,6 +398,45 @@ where
     Stack::stack(lhs, rhs, axis)
 }
 
+/// Takes the elements of `lhs` where `mask` is `true` and those of `rhs` elsewhere.
+///
+/// The gradient of the result flows back to `lhs` where `mask` is `true` and to `rhs` elsewhere.
+///
+/// # Arguments
+///
+/// * `mask` - boolean mask, must be broadcastable to the shape of the variables.
+///
+/// * `lhs` - variable.
+///
+/// * `rhs` - other variable.
+///
+/// # Panics
+///
+/// If the variables have mismatching shapes or if `mask` cannot be broadcast to their shape.
+///
+/// # Examples
+///
+/// ```
+/// # use neuronika_variable as neuronika;
+/// use ndarray;
+///
+/// let x = neuronika::from_ndarray(ndarray::array![-1., 2., -3.]).requires_grad();
+/// let mask = x.clone().gt(neuronika::zeros(1));
+///
+/// let y = neuronika::where_(mask, x.clone(), neuronika::zeros(3));
+/// y.forward();
+/// y.backward(1.);
+///
+/// assert_eq!(*y.data(), ndarray::array![0., 2., 0.]);
+/// assert_eq!(*x.grad(), ndarray::array![0., 1., 0.]);
+/// ```
+pub fn where_<Mask, Lhs, Rhs>(mask: Mask, lhs: Lhs, rhs: Rhs) -> <Lhs as Where<Mask, Rhs>>::Output
+where
+    Lhs: Where<Mask, Rhs>,
+{
+    Where::where_(lhs, mask, rhs)
+}
+
 #[cfg(test)]
 mod tests {
     #[test]
@@ -355,6 +507,94 @@ mod tests {
         assert_eq!(t.data().shape(), &[4, 5, 6]);
     }
 
+    #[test]
+    fn random_constructors() {
+        use super::{bernoulli, randint, randn};
+
+        assert_eq!(randn((4, 5)).data().shape(), &[4, 5]);
+
+        let t = randint(-2, 3, [4, 5, 6]);
+        assert_eq!(t.data().shape(), &[4, 5, 6]);
+        assert!(t.data().iter().all(|&el| (-2..3).contains(&el)));
+
+        assert!(bernoulli(10, 0.).data().iter().all(|&el| el == 0.));
+        assert!(bernoulli(10, 1.).data().iter().all(|&el| el == 1.));
+    }
+
+    #[test]
+    fn manual_seed() {
+        use super::{manual_seed, rand, randn};
+        use crate::cell::{Cell, Rc};
+
+        let sample = || {
+            let x = randn((3, 4)).requires_grad();
+            let y = (x.clone() + rand(4)).dropout(0.5, Rc::new(Cell::new(true)));
+            y.forward();
+
+            let sample = (x.data().clone(), y.data().clone());
+            sample
+        };
+
+        manual_seed(42);
+        let first = sample();
+        manual_seed(42);
+        let second = sample();
+        let third = sample();
+
+        assert_eq!(first, second);
+        assert_ne!(first, third);
+    }
+
+    #[test]
+    fn manual_seed_constructors() {
+        use super::{bernoulli, manual_seed, rand, randint, randn};
+
+        let sample = || {
+            let sample = (
+                rand((3, 4)).data().clone(),
+                randn((3, 4)).data().clone(),
+                randint(-5, 5, (3, 4)).data().clone(),
+                bernoulli((3, 4), 0.3).data().clone(),
+            );
+            sample
+        };
+
+        manual_seed(7);
+        let first = sample();
+        manual_seed(7);
+        let second = sample();
+        let third = sample();
+
+        assert_eq!(first, second);
+        assert_ne!(first, third);
+    }
+
+    #[test]
+    fn manual_seed_dropout() {
+        use super::{manual_seed, ones};
+        use crate::cell::{Cell, Rc};
+
+        let masks = || {
+            let status = Rc::new(Cell::new(true));
+            let x = ones((4, 5)).dropout(0.5, status.clone());
+            let y = ones((4, 5)).requires_grad().dropout(0.5, status);
+            x.forward();
+            y.forward();
+
+            let masks = (x.data().clone(), y.data().clone());
+            masks
+        };
+
+        manual_seed(7);
+        let first = masks();
+        manual_seed(7);
+        let second = masks();
+        let third = masks();
+
+        assert_eq!(first, second);
+        assert_ne!(first, third);
+    }
+
     #[test]
     fn eye_test() {
         use super::{eye, Array2};
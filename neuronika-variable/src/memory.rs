@@ -0,0 +1,524 @@
+use std::{
+    any::{Any, TypeId},
+    collections::{HashMap, HashSet},
+};
+
+use ndarray::{Array, Dimension};
+
+use crate::{
+    autograd::Backward,
+    cell::{Cell, MaybeSync, Rc, RefCell, Weak},
+    gradient::NodeGradient,
+    utils::Shared,
+    var::ForwardOp,
+    Element, Float,
+};
+
+/// An op of a differentiable tape, together with the gradient of its node.
+type Op = (Rc<dyn Backward>, Rc<dyn NodeGradient>);
+
+/// Peak memory used by the computational graph of a differentiable variable during a forward and
+/// backward pass, before and after planning it with
+/// [`.plan_memory()`](crate::VarDiff::plan_memory()).
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct MemoryReport {
+    before: usize,
+    after: usize,
+}
+
+impl MemoryReport {
+    pub(crate) fn new(before: usize, after: usize) -> Self {
+        Self { before, after }
+    }
+
+    /// Returns the peak number of bytes used before planning.
+    pub fn before(&self) -> usize {
+        self.before
+    }
+
+    /// Returns the peak number of bytes used after planning.
+    pub fn after(&self) -> usize {
+        self.after
+    }
+}
+
+/// Pool of de-allocated buffers, that are recycled by the ones allocated later on.
+#[derive(Default)]
+pub(crate) struct BufferPool {
+    buffers: Vec<Box<dyn Any + Send + Sync>>,
+}
+
+impl BufferPool {
+    /// Returns an array of zeros of the given shape, recycling the smallest fitting buffer of the
+    /// pool if there's any.
+    pub(crate) fn zeros<D, T>(&mut self, shape: D) -> Array<T, D>
+    where
+        D: Dimension,
+        T: Element,
+    {
+        let len = shape.size();
+        let position = self
+            .buffers
+            .iter()
+            .enumerate()
+            .filter_map(|(position, buffer)| {
+                buffer
+                    .downcast_ref::<Vec<T>>()
+                    .filter(|buffer| buffer.capacity() >= len)
+                    .map(|buffer| (position, buffer.capacity()))
+            })
+            .min_by_key(|&(_, capacity)| capacity)
+            .map(|(position, _)| position);
+
+        let mut buffer = match position {
+            Some(position) => *self.buffers.swap_remove(position).downcast().unwrap(),
+            None => Vec::with_capacity(len),
+        };
+        buffer.clear();
+        buffer.resize(len, T::default());
+
+        Array::from_shape_vec(shape, buffer).unwrap()
+    }
+
+    /// Puts the buffer of `array` in the pool.
+    pub(crate) fn put<D, T>(&mut self, array: Array<T, D>)
+    where
+        D: Dimension,
+        T: Element,
+    {
+        self.buffers.push(Box::new(array.into_raw_vec()));
+    }
+}
+
+/// The data of a node of a forward tape, whose buffer can be handed over to a memory plan.
+pub(crate) trait NodeData: MaybeSync {
+    /// Returns the number of bytes of the data.
+    fn bytes(&self) -> usize;
+
+    /// Returns the type of the elements and the number of bytes of the buffer, or `None` if the
+    /// data can't be de-allocated, as it's zero-dimensional or empty.
+    fn buffer(&self) -> Option<(TypeId, usize)>;
+
+    /// Allocates the data, zeroed, recycling the buffers in `pool` when possible.
+    fn acquire(&self, pool: &mut BufferPool);
+
+    /// De-allocates the data, putting its buffer in `pool`.
+    fn release(&self, pool: &mut BufferPool);
+
+    /// Hands the data over to a memory plan, de-allocating it and putting its buffer in `pool`.
+    fn hand_over(&self, pool: &mut BufferPool);
+
+    /// Returns `true` if the data has been handed over to a memory plan.
+    fn is_planned(&self) -> bool;
+
+    /// Returns `true` if a variable of the node is alive, through which the data can be read.
+    fn is_held(&self) -> bool;
+
+    /// Returns `false` if the data holds infinite or NaN values.
+    fn is_finite(&self) -> bool;
+}
+
+/// The data of a node, as seen by the memory plans.
+///
+/// Once de-allocated, the array is left empty until it's allocated again.
+pub(crate) struct DataBuffer<D, T>
+where
+    D: Dimension,
+{
+    array: Shared<Array<T, D>>,
+    shape: D,
+    planned: Cell<bool>,
+    handle: Weak<()>,
+}
+
+impl<D, T> DataBuffer<D, T>
+where
+    D: Dimension,
+{
+    /// Describes the data `array` of a node, whose variables share `handle`.
+    pub(crate) fn new(array: Shared<Array<T, D>>, handle: &Rc<()>) -> Self {
+        let shape = array.borrow().raw_dim();
+
+        Self {
+            array,
+            shape,
+            planned: Cell::new(false),
+            handle: Rc::downgrade(handle),
+        }
+    }
+
+    fn is_allocated(&self) -> bool {
+        self.array.borrow().raw_dim() == self.shape
+    }
+}
+
+impl<D, T> NodeData for DataBuffer<D, T>
+where
+    D: Dimension,
+    T: Element,
+{
+    fn bytes(&self) -> usize {
+        self.shape.size() * std::mem::size_of::<T>()
+    }
+
+    fn buffer(&self) -> Option<(TypeId, usize)> {
+        (self.shape.ndim() > 0 && self.shape.size() > 0).then(|| (TypeId::of::<T>(), self.bytes()))
+    }
+
+    fn acquire(&self, pool: &mut BufferPool) {
+        if !self.is_allocated() {
+            *self.array.borrow_mut() = pool.zeros(self.shape.clone());
+        }
+    }
+
+    fn release(&self, pool: &mut BufferPool) {
+        if self.buffer().is_some() && self.is_allocated() {
+            let empty = Array::from_shape_vec(D::zeros(self.shape.ndim()), Vec::new()).unwrap();
+            pool.put(std::mem::replace(&mut *self.array.borrow_mut(), empty));
+        }
+    }
+
+    fn hand_over(&self, pool: &mut BufferPool) {
+        self.planned.set(true);
+        self.release(pool);
+    }
+
+    fn is_planned(&self) -> bool {
+        self.planned.get()
+    }
+
+    fn is_held(&self) -> bool {
+        self.handle.strong_count() > 0
+    }
+
+    fn is_finite(&self) -> bool {
+        self.array.borrow().iter().all(|el| !el.is_non_finite())
+    }
+}
+
+/// Schedule of the data and gradient buffers of the nodes of a graph during the forward and
+/// backward passes.
+///
+/// The data of a node that no back-propagation reads is needed from its computation to the one of
+/// its last consumer. It's thus allocated right before the former and de-allocated right after the
+/// latter, the data read by the backward pass is instead never de-allocated.
+///
+/// The gradient of a node is needed from the back-propagation of the first of its consumers,
+/// which is the last one in the tape, to its own one. It's thus allocated right before the
+/// former and de-allocated right after the latter. The gradient of the root of the tape is never
+/// de-allocated.
+///
+/// Both kinds of buffers are recycled through the same [`BufferPool`], which is carried over from
+/// the forward pass to the backward one, so that buffers whose lifetimes don't overlap share the
+/// same memory.
+pub(crate) struct MemoryPlan {
+    /// Whether the data of each op of the forward tape is allocated right before its computation.
+    planned_data: Vec<bool>,
+    /// Positions in the forward tape of the data to de-allocate after the computation of each op.
+    data_releases: Vec<Vec<usize>>,
+    /// Positions in the tape of the gradients to allocate before the back-propagation of each op.
+    allocations: Vec<Vec<usize>>,
+    /// Whether the gradient of each op is de-allocated after its back-propagation.
+    releases: Vec<bool>,
+    /// Buffers de-allocated by the forward pass, recycled by the backward one.
+    pool: RefCell<BufferPool>,
+}
+
+impl MemoryPlan {
+    /// Plans the memory of a graph.
+    ///
+    /// # Arguments
+    ///
+    /// * `forward` - forward tape of the graph.
+    ///
+    /// * `ids` - ids of the data buffers of the nodes in the differentiable tape.
+    ///
+    /// * `backward` - differentiable tape of the graph.
+    ///
+    /// * `root` - id of the data buffer of the root.
+    ///
+    /// Returns `None` if the operands of a node of the differentiable tape are unknown, as the
+    /// gradients its back-propagation writes to can't be determined.
+    pub(crate) fn new(
+        forward: &[ForwardOp],
+        ids: &[usize],
+        backward: &[Op],
+        root: usize,
+    ) -> Option<Self> {
+        let data: Vec<(usize, Vec<usize>)> = forward
+            .iter()
+            .map(|(op, _, _)| {
+                let operands = op.operands().into_iter().map(|operand| operand.id);
+                (op.data().id, operands.collect())
+            })
+            .collect();
+        let operands: HashMap<usize, &[usize]> = data
+            .iter()
+            .map(|(id, operands)| (*id, &operands[..]))
+            .collect();
+
+        // The data read by the backward pass, as well as the one of the root, is kept.
+        let mut kept = read_by_backward(forward.iter(), ids.iter().copied().zip(backward));
+        kept.insert(root);
+
+        let positions: HashMap<usize, usize> = data
+            .iter()
+            .enumerate()
+            .map(|(position, &(id, _))| (id, position))
+            .collect();
+        let mut last_consumers: Vec<usize> = (0..data.len()).collect();
+        for (consumer, (_, operands)) in data.iter().enumerate() {
+            for operand in operands {
+                if let Some(&position) = positions.get(operand) {
+                    last_consumers[position] = last_consumers[position].max(consumer);
+                }
+            }
+        }
+
+        let mut planned_data = vec![false; data.len()];
+        let mut data_releases = vec![Vec::new(); data.len()];
+        for (position, ((id, _), (_, _, buffer))) in data.iter().zip(forward).enumerate() {
+            if !kept.contains(id) && buffer.buffer().is_some() {
+                planned_data[position] = true;
+                data_releases[last_consumers[position]].push(position);
+            }
+        }
+
+        let positions: HashMap<usize, usize> = ids
+            .iter()
+            .enumerate()
+            .map(|(position, &id)| (id, position))
+            .collect();
+
+        // The back-propagation runs in reverse, so the first writer is the last consumer.
+        let mut first_writers: Vec<usize> = (0..ids.len()).collect();
+        for (consumer, id) in ids.iter().enumerate() {
+            for operand in operands.get(id)?.iter() {
+                if let Some(&position) = positions.get(operand) {
+                    first_writers[position] = first_writers[position].max(consumer);
+                }
+            }
+        }
+
+        let mut allocations = vec![Vec::new(); ids.len()];
+        let mut releases = vec![false; ids.len()];
+        for (position, _) in ids.iter().enumerate().filter(|&(_, &id)| id != root) {
+            allocations[first_writers[position]].push(position);
+            releases[position] = true;
+        }
+
+        Some(Self {
+            planned_data,
+            data_releases,
+            allocations,
+            releases,
+            pool: RefCell::default(),
+        })
+    }
+
+    /// Hands the data and the gradients that are not needed outside of the passes over to the
+    /// plan, de-allocating them.
+    pub(crate) fn hand_over(&self, forward: &[ForwardOp], backward: &[Op]) {
+        let mut pool = BufferPool::default();
+        forward
+            .iter()
+            .zip(&self.planned_data)
+            .filter(|(_, &planned)| planned)
+            .for_each(|((_, _, data), _)| data.hand_over(&mut pool));
+        backward
+            .iter()
+            .zip(&self.releases)
+            .filter(|(_, &release)| release)
+            .for_each(|((_, grad), _)| grad.hand_over(&mut pool));
+    }
+
+    /// Takes the buffers left over by the forward pass.
+    pub(crate) fn take_pool(&self) -> BufferPool {
+        std::mem::take(&mut *self.pool.borrow_mut())
+    }
+
+    /// Keeps the buffers left over by the forward pass for the backward one.
+    pub(crate) fn keep_pool(&self, pool: BufferPool) {
+        *self.pool.borrow_mut() = pool;
+    }
+
+    /// Allocates the data of the op at `position` in the forward tape, if it's planned.
+    pub(crate) fn allocate_data(
+        &self,
+        position: usize,
+        forward: &[ForwardOp],
+        pool: &mut BufferPool,
+    ) {
+        if self.planned_data[position] {
+            forward[position].2.acquire(pool);
+        }
+    }
+
+    /// De-allocates the data no longer needed after the computation of the op at `position` in
+    /// the forward tape.
+    pub(crate) fn release_data(
+        &self,
+        position: usize,
+        forward: &[ForwardOp],
+        pool: &mut BufferPool,
+    ) {
+        self.data_releases[position]
+            .iter()
+            .for_each(|&position| forward[position].2.release(pool));
+    }
+
+    /// Zeroes the gradients that are not allocated by the plan.
+    pub(crate) fn zero(&self, tape: &[Op]) {
+        tape.iter()
+            .zip(&self.releases)
+            .filter(|(_, &release)| !release)
+            .for_each(|((_, grad), _)| grad.zero());
+    }
+
+    /// Allocates the gradients needed by the back-propagation of the op at `position`.
+    pub(crate) fn allocate(&self, position: usize, tape: &[Op], pool: &mut BufferPool) {
+        self.allocations[position]
+            .iter()
+            .for_each(|&position| tape[position].1.acquire(pool));
+    }
+
+    /// De-allocates the gradient of the op at `position`, if it's no longer needed.
+    pub(crate) fn release(&self, position: usize, tape: &[Op], pool: &mut BufferPool) {
+        if self.releases[position] {
+            tape[position].1.release(pool);
+        }
+    }
+
+    /// Computes the peak number of bytes used by the data of the forward tape and by the
+    /// gradients of the differentiable one during a planned forward and backward pass.
+    ///
+    /// The pool is simulated so that the buffers it holds are accounted for.
+    pub(crate) fn peak<T>(&self, forward: &[ForwardOp], backward: &[Op]) -> usize
+    where
+        T: Float,
+    {
+        let sizes: Vec<Vec<usize>> = backward.iter().map(|(_, grad)| grad.sizes()).collect();
+
+        let data: usize = forward
+            .iter()
+            .zip(&self.planned_data)
+            .filter(|(_, &planned)| !planned)
+            .map(|((_, _, data), _)| data.bytes())
+            .sum();
+        let gradients: usize = sizes
+            .iter()
+            .zip(&self.releases)
+            .filter(|(_, &release)| !release)
+            .flat_map(|(sizes, _)| sizes)
+            .sum();
+        let mut current = data + gradients;
+        let mut peak = current;
+
+        // Element types and capacities of the buffers held by each data, gradient and by the pool.
+        let mut pool = Vec::new();
+        let mut held_data = vec![None; forward.len()];
+        for (position, (_, _, data)) in forward.iter().enumerate() {
+            if self.planned_data[position] {
+                let (element, size) = data.buffer().unwrap();
+                held_data[position] = Some(recycle(&mut pool, element, size, &mut current));
+                peak = peak.max(current);
+            }
+
+            for &released in &self.data_releases[position] {
+                pool.extend(held_data[released].take());
+            }
+        }
+
+        let mut held = vec![Vec::new(); backward.len()];
+        for position in (0..backward.len()).rev() {
+            for &allocated in &self.allocations[position] {
+                for &size in &sizes[allocated] {
+                    let buffer = recycle(&mut pool, TypeId::of::<T>(), size, &mut current);
+                    held[allocated].push(buffer);
+                }
+            }
+            peak = peak.max(current);
+
+            if self.releases[position] {
+                pool.append(&mut held[position]);
+            }
+        }
+
+        peak
+    }
+}
+
+/// Takes the smallest buffer of `element`s in the simulated `pool` that fits `size` bytes, or
+/// allocates a new one adding its size to `current`. Returns the element type and the capacity of
+/// the buffer.
+fn recycle(
+    pool: &mut Vec<(TypeId, usize)>,
+    element: TypeId,
+    size: usize,
+    current: &mut usize,
+) -> (TypeId, usize) {
+    let recycled = pool
+        .iter()
+        .enumerate()
+        .filter(|(_, &(other, capacity))| other == element && capacity >= size)
+        .min_by_key(|(_, &(_, capacity))| capacity)
+        .map(|(index, _)| index);
+
+    match recycled {
+        Some(index) => pool.swap_remove(index),
+        None => {
+            *current += size;
+            (element, size)
+        }
+    }
+}
+
+/// Panics if the gradient of a node of `tape` has been handed over to the memory plan of another
+/// graph, as the plan de-allocates it outside of the backward pass of that graph.
+pub(crate) fn assert_unplanned(tape: &[Op]) {
+    assert!(
+        tape.iter().all(|(_, grad)| !grad.is_planned()),
+        "error: the memory of a node of the graph has been planned by another variable."
+    );
+}
+
+/// Panics if the data of a node of `tape` has been handed over to the memory plan of another
+/// graph, as the plan de-allocates it outside of the forward pass of that graph.
+pub(crate) fn assert_data_unplanned(tape: &[ForwardOp]) {
+    assert!(
+        tape.iter().all(|(_, _, data)| !data.is_planned()),
+        "error: the memory of a node of the graph has been planned by another variable."
+    );
+}
+
+/// Returns the ids of the data read by the back-propagation of the ops of a differentiable tape.
+///
+/// # Arguments
+///
+/// * `forward` - ops of the forward tape.
+///
+/// * `backward` - ops of the differentiable tape, with the ids of the data of their nodes.
+pub(crate) fn read_by_backward<'a>(
+    forward: impl Iterator<Item = &'a ForwardOp>,
+    backward: impl Iterator<Item = (usize, &'a Op)>,
+) -> HashSet<usize> {
+    let operands: HashMap<usize, Vec<usize>> = forward
+        .map(|(op, _, _)| {
+            let operands = op.operands().into_iter().map(|operand| operand.id);
+            (op.data().id, operands.collect())
+        })
+        .collect();
+
+    let mut read = HashSet::new();
+    for (id, (op, _)) in backward {
+        let reads = op.reads();
+        if reads.data() {
+            read.insert(id);
+        }
+        if reads.operands() {
+            read.extend(operands.get(&id).into_iter().flatten());
+        }
+    }
+
+    read
+}
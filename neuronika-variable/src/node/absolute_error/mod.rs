@@ -1,32 +1,34 @@
-use std::rc::Rc;
-
 use ndarray::{arr0, Array, Dimension, Ix0, Zip};
 
 use crate::{
     autograd::{Backward, Forward},
+    cell::Rc,
     gradient::Gradient,
+    graph::Buffer,
     utils::Shared,
-    Reduction,
+    Float, Reduction,
 };
 
-pub struct AbsoluteError<D>
+pub struct AbsoluteError<D, T>
 where
     D: Dimension,
+    T: Float,
 {
-    input_data: Shared<Array<f32, D>>,
-    target_data: Shared<Array<f32, D>>,
-    data: Shared<Array<f32, Ix0>>,
+    input_data: Shared<Array<T, D>>,
+    target_data: Shared<Array<T, D>>,
+    data: Shared<Array<T, Ix0>>,
     reduction: Reduction,
 }
 
-impl<D> AbsoluteError<D>
+impl<D, T> AbsoluteError<D, T>
 where
     D: Dimension,
+    T: Float,
 {
     pub(crate) fn new(
-        input_data: Shared<Array<f32, D>>,
-        target_data: Shared<Array<f32, D>>,
-        data: Shared<Array<f32, Ix0>>,
+        input_data: Shared<Array<T, D>>,
+        target_data: Shared<Array<T, D>>,
+        data: Shared<Array<T, Ix0>>,
         reduction: Reduction,
     ) -> Self {
         Self {
@@ -38,45 +40,65 @@ where
     }
 }
 
-impl<D> Forward for AbsoluteError<D>
+impl<D, T> Forward for AbsoluteError<D, T>
 where
     D: Dimension,
+    T: Float,
 {
     fn forward(&self) {
         let input_data = self.input_data.borrow();
         *self.data.borrow_mut() = {
             let total_loss = Zip::from(&*input_data)
                 .and(&*self.target_data.borrow())
-                .fold(0., |loss, &input, &target| loss + (input - target).abs());
+                .fold(T::zero(), |loss, &input, &target| {
+                    loss + (input - target).abs()
+                });
 
             match self.reduction {
-                Reduction::Mean => arr0(total_loss / input_data.len() as f32),
+                Reduction::Mean => arr0(total_loss / T::from_usize(input_data.len())),
                 Reduction::Sum => arr0(total_loss),
             }
         };
     }
+
+    fn name(&self) -> &'static str {
+        "AbsoluteError"
+    }
+
+    fn operands(&self) -> Vec<Buffer> {
+        vec![
+            Buffer::new(&self.input_data),
+            Buffer::new(&self.target_data),
+        ]
+    }
+
+    fn data(&self) -> Buffer {
+        Buffer::new(&self.data)
+    }
 }
 
-pub struct AbsoluteErrorBackward<D>
+pub struct AbsoluteErrorBackward<D, T>
 where
     D: Dimension,
+    T: Float,
 {
-    input_data: Shared<Array<f32, D>>,
-    target_data: Shared<Array<f32, D>>,
-    input_gradient: Rc<Gradient<Array<f32, D>, D>>,
-    gradient: Rc<Gradient<Array<f32, Ix0>, Ix0>>,
+    input_data: Shared<Array<T, D>>,
+    target_data: Shared<Array<T, D>>,
+    input_gradient: Rc<Gradient<Array<T, D>, D>>,
+    gradient: Rc<Gradient<Array<T, Ix0>, Ix0>>,
     reduction: Reduction,
 }
 
-impl<D> AbsoluteErrorBackward<D>
+impl<D, T> AbsoluteErrorBackward<D, T>
 where
     D: Dimension,
+    T: Float,
 {
     pub(crate) fn new(
-        input_data: Shared<Array<f32, D>>,
-        target_data: Shared<Array<f32, D>>,
-        input_gradient: Rc<Gradient<Array<f32, D>, D>>,
-        gradient: Rc<Gradient<Array<f32, Ix0>, Ix0>>,
+        input_data: Shared<Array<T, D>>,
+        target_data: Shared<Array<T, D>>,
+        input_gradient: Rc<Gradient<Array<T, D>, D>>,
+        gradient: Rc<Gradient<Array<T, Ix0>, Ix0>>,
         reduction: Reduction,
     ) -> Self {
         Self {
@@ -89,9 +111,10 @@ where
     }
 }
 
-impl<D> Backward for AbsoluteErrorBackward<D>
+impl<D, T> Backward for AbsoluteErrorBackward<D, T>
 where
     D: Dimension,
+    T: Float,
 {
     fn backward(&self) {
         let mut input_gradient = self.input_gradient.borrow_mut();
@@ -106,16 +129,20 @@ where
 
         match self.reduction {
             Reduction::Mean => {
-                let n = input_data.len() as f32;
+                let n = T::from_usize(input_data.len());
                 zip.for_each(|op_grad, &grad, &input, &target| {
                     let diff = input - target;
-                    *op_grad += ((diff != 0.) as u8 as f32) * (diff.signum() * grad / n);
+                    if diff != T::zero() {
+                        *op_grad += diff.signum() * grad / n;
+                    }
                 });
             }
             Reduction::Sum => {
                 zip.for_each(|op_grad, &grad, &input, &target| {
                     let diff = input - target;
-                    *op_grad += ((diff != 0.) as u8 as f32) * (diff.signum() * grad)
+                    if diff != T::zero() {
+                        *op_grad += diff.signum() * grad
+                    }
                 });
             }
         }
@@ -1,32 +1,41 @@
-use std::rc::Rc;
+use std::any::Any;
 
 use ndarray::{Array, DimMax, Dimension, Zip};
 
 use crate::{
-    autograd::{Backward, Forward},
+    autograd::{Backward, Forward, Reads},
+    cell::Rc,
+    fusion::{
+        fusible_len, standard_len, BackwardKernel, ElementwiseBackward, ElementwiseForward,
+        ForwardKernel,
+    },
     gradient::Gradient,
+    graph::Buffer,
     utils::{accumulate, Broadcast, Shared},
+    Float,
 };
 
-pub(crate) struct Addition<D, E>
+pub(crate) struct Addition<D, E, T>
 where
     D: Dimension + DimMax<E>,
     E: Dimension,
+    T: Float,
 {
-    left_data: Shared<Array<f32, D>>,
-    right_data: Shared<Array<f32, E>>,
-    data: Shared<Array<f32, Broadcast<D, E>>>,
+    left_data: Shared<Array<T, D>>,
+    right_data: Shared<Array<T, E>>,
+    data: Shared<Array<T, Broadcast<D, E>>>,
 }
 
-impl<D, E> Addition<D, E>
+impl<D, E, T> Addition<D, E, T>
 where
     D: Dimension + DimMax<E>,
     E: Dimension,
+    T: Float,
 {
     pub(crate) fn new(
-        left_data: Shared<Array<f32, D>>,
-        right_data: Shared<Array<f32, E>>,
-        data: Shared<Array<f32, Broadcast<D, E>>>,
+        left_data: Shared<Array<T, D>>,
+        right_data: Shared<Array<T, E>>,
+        data: Shared<Array<T, Broadcast<D, E>>>,
     ) -> Self {
         Self {
             left_data,
@@ -36,10 +45,11 @@ where
     }
 }
 
-impl<D, E> Forward for Addition<D, E>
+impl<D, E, T> Forward for Addition<D, E, T>
 where
-    D: Dimension + DimMax<E>,
-    E: Dimension,
+    D: 'static + Dimension + DimMax<E>,
+    E: 'static + Dimension,
+    T: Float,
 {
     fn forward(&self) {
         Zip::from(&mut *self.data.borrow_mut())
@@ -47,24 +57,69 @@ where
             .and_broadcast(&*self.right_data.borrow())
             .for_each(|v, &l, &r| *v = l + r);
     }
+
+    fn name(&self) -> &'static str {
+        "Addition"
+    }
+
+    fn operands(&self) -> Vec<Buffer> {
+        vec![Buffer::new(&self.left_data), Buffer::new(&self.right_data)]
+    }
+
+    fn data(&self) -> Buffer {
+        Buffer::new(&self.data)
+    }
+
+    fn elementwise(&self) -> Option<&dyn ElementwiseForward> {
+        Some(self)
+    }
 }
-pub(crate) struct AdditionBackwardLeft<D, E>
+
+impl<D, E, T> ElementwiseForward for Addition<D, E, T>
+where
+    D: 'static + Dimension + DimMax<E>,
+    E: 'static + Dimension,
+    T: Float,
+{
+    fn fusible_len(&self) -> Option<usize> {
+        fusible_len(
+            standard_len(&self.data.borrow()),
+            &[],
+            &[
+                standard_len(&self.left_data.borrow()),
+                standard_len(&self.right_data.borrow()),
+            ],
+        )
+    }
+
+    fn kernel(&self) -> Box<dyn Any> {
+        Box::new(ForwardKernel::binary(
+            &self.left_data,
+            &self.right_data,
+            &self.data,
+            |l, r| l + r,
+        ))
+    }
+}
+pub(crate) struct AdditionBackwardLeft<D, E, T>
 where
     D: Dimension + DimMax<E>,
     E: Dimension,
+    T: Float,
 {
-    operand_gradient: Rc<Gradient<Array<f32, D>, D>>,
-    gradient: Rc<Gradient<Array<f32, Broadcast<D, E>>, Broadcast<D, E>>>,
+    operand_gradient: Rc<Gradient<Array<T, D>, D>>,
+    gradient: Rc<Gradient<Array<T, Broadcast<D, E>>, Broadcast<D, E>>>,
 }
 
-impl<D, E> AdditionBackwardLeft<D, E>
+impl<D, E, T> AdditionBackwardLeft<D, E, T>
 where
     D: Dimension + DimMax<E>,
     E: Dimension,
+    T: Float,
 {
     pub(crate) fn new(
-        operand_gradient: Rc<Gradient<Array<f32, D>, D>>,
-        gradient: Rc<Gradient<Array<f32, Broadcast<D, E>>, Broadcast<D, E>>>,
+        operand_gradient: Rc<Gradient<Array<T, D>, D>>,
+        gradient: Rc<Gradient<Array<T, Broadcast<D, E>>, Broadcast<D, E>>>,
     ) -> Self {
         debug_assert!(operand_gradient
             .borrow()
@@ -78,10 +133,11 @@ where
     }
 }
 
-impl<D, E> Backward for AdditionBackwardLeft<D, E>
+impl<D, E, T> Backward for AdditionBackwardLeft<D, E, T>
 where
-    D: Dimension + DimMax<E>,
-    E: Dimension,
+    D: 'static + Dimension + DimMax<E>,
+    E: 'static + Dimension,
+    T: Float,
 {
     fn backward(&self) {
         accumulate(
@@ -89,25 +145,57 @@ where
             &self.gradient.borrow(),
         );
     }
+
+    fn elementwise(&self) -> Option<&dyn ElementwiseBackward> {
+        Some(self)
+    }
+
+    fn reads(&self) -> Reads {
+        Reads::Nothing
+    }
+}
+
+impl<D, E, T> ElementwiseBackward for AdditionBackwardLeft<D, E, T>
+where
+    D: 'static + Dimension + DimMax<E>,
+    E: 'static + Dimension,
+    T: Float,
+{
+    fn fusible_len(&self) -> Option<usize> {
+        fusible_len(
+            standard_len(&self.gradient.borrow()),
+            &[standard_len(&self.operand_gradient.borrow())],
+            &[],
+        )
+    }
+
+    fn kernel(&self) -> Box<dyn Any> {
+        Box::new(
+            BackwardKernel::new(self.gradient.clone())
+                .accumulate(self.operand_gradient.clone(), |g| g),
+        )
+    }
 }
 
-pub(crate) struct AdditionBackwardRight<D, E>
+pub(crate) struct AdditionBackwardRight<D, E, T>
 where
     D: Dimension + DimMax<E>,
     E: Dimension,
+    T: Float,
 {
-    operand_gradient: Rc<Gradient<Array<f32, E>, E>>,
-    gradient: Rc<Gradient<Array<f32, Broadcast<D, E>>, Broadcast<D, E>>>,
+    operand_gradient: Rc<Gradient<Array<T, E>, E>>,
+    gradient: Rc<Gradient<Array<T, Broadcast<D, E>>, Broadcast<D, E>>>,
 }
 
-impl<D, E> AdditionBackwardRight<D, E>
+impl<D, E, T> AdditionBackwardRight<D, E, T>
 where
     D: Dimension + DimMax<E>,
     E: Dimension,
+    T: Float,
 {
     pub(crate) fn new(
-        operand_gradient: Rc<Gradient<Array<f32, E>, E>>,
-        gradient: Rc<Gradient<Array<f32, Broadcast<D, E>>, Broadcast<D, E>>>,
+        operand_gradient: Rc<Gradient<Array<T, E>, E>>,
+        gradient: Rc<Gradient<Array<T, Broadcast<D, E>>, Broadcast<D, E>>>,
     ) -> Self {
         debug_assert!(operand_gradient
             .borrow()
@@ -121,10 +209,11 @@ where
     }
 }
 
-impl<D, E> Backward for AdditionBackwardRight<D, E>
+impl<D, E, T> Backward for AdditionBackwardRight<D, E, T>
 where
-    D: Dimension + DimMax<E>,
-    E: Dimension,
+    D: 'static + Dimension + DimMax<E>,
+    E: 'static + Dimension,
+    T: Float,
 {
     fn backward(&self) {
         accumulate(
@@ -132,39 +221,101 @@ where
             &self.gradient.borrow(),
         );
     }
+
+    fn elementwise(&self) -> Option<&dyn ElementwiseBackward> {
+        Some(self)
+    }
+
+    fn reads(&self) -> Reads {
+        Reads::Nothing
+    }
 }
 
-pub(crate) struct AdditionBackward<D, E>
+impl<D, E, T> ElementwiseBackward for AdditionBackwardRight<D, E, T>
+where
+    D: 'static + Dimension + DimMax<E>,
+    E: 'static + Dimension,
+    T: Float,
+{
+    fn fusible_len(&self) -> Option<usize> {
+        fusible_len(
+            standard_len(&self.gradient.borrow()),
+            &[standard_len(&self.operand_gradient.borrow())],
+            &[],
+        )
+    }
+
+    fn kernel(&self) -> Box<dyn Any> {
+        Box::new(
+            BackwardKernel::new(self.gradient.clone())
+                .accumulate(self.operand_gradient.clone(), |g| g),
+        )
+    }
+}
+
+pub(crate) struct AdditionBackward<D, E, T>
 where
     D: Dimension + DimMax<E>,
     E: Dimension,
+    T: Float,
 {
-    left: AdditionBackwardLeft<D, E>,
-    right: AdditionBackwardRight<D, E>,
+    left: AdditionBackwardLeft<D, E, T>,
+    right: AdditionBackwardRight<D, E, T>,
 }
 
-impl<D, E> AdditionBackward<D, E>
+impl<D, E, T> AdditionBackward<D, E, T>
 where
     D: Dimension + DimMax<E>,
     E: Dimension,
+    T: Float,
 {
     pub(crate) fn new(
-        left: AdditionBackwardLeft<D, E>,
-        right: AdditionBackwardRight<D, E>,
+        left: AdditionBackwardLeft<D, E, T>,
+        right: AdditionBackwardRight<D, E, T>,
     ) -> Self {
         Self { left, right }
     }
 }
 
-impl<D, E> Backward for AdditionBackward<D, E>
+impl<D, E, T> Backward for AdditionBackward<D, E, T>
 where
-    D: Dimension + DimMax<E>,
-    E: Dimension,
+    D: 'static + Dimension + DimMax<E>,
+    E: 'static + Dimension,
+    T: Float,
 {
     fn backward(&self) {
         self.left.backward();
         self.right.backward();
     }
+
+    fn elementwise(&self) -> Option<&dyn ElementwiseBackward> {
+        Some(self)
+    }
+
+    fn reads(&self) -> Reads {
+        Reads::Nothing
+    }
+}
+
+impl<D, E, T> ElementwiseBackward for AdditionBackward<D, E, T>
+where
+    D: 'static + Dimension + DimMax<E>,
+    E: 'static + Dimension,
+    T: Float,
+{
+    fn fusible_len(&self) -> Option<usize> {
+        let len = self.left.fusible_len()?;
+
+        if self.right.fusible_len()? == len {
+            Some(len)
+        } else {
+            None
+        }
+    }
+
+    fn kernel(&self) -> Box<dyn Any> {
+        Box::new(BackwardKernel::<T>::of(&self.left).and(BackwardKernel::of(&self.right)))
+    }
 }
 
 #[cfg(test)]
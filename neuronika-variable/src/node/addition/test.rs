@@ -1,7 +1,8 @@
-use std::{error::Error, rc::Rc};
+use std::error::Error;
 
 use ndarray::Array;
 
+use crate::cell::Rc;
 use crate::utils::{are_similar, new_shared};
 
 mod forward {
@@ -79,7 +80,7 @@ mod backward {
     fn left_creation() -> Result<(), Box<dyn Error>> {
         let left = Array::zeros((3, 3));
         let grad = Array::ones((3, 3));
-        let op = AdditionBackwardLeft::<Ix2, Ix2>::new(
+        let op = AdditionBackwardLeft::<Ix2, Ix2, f32>::new(
             Rc::new(Gradient::from_ndarray(left.clone())),
             Rc::new(Gradient::from_ndarray(grad.clone())),
         );
@@ -92,7 +93,7 @@ mod backward {
     fn left_base_case() -> Result<(), Box<dyn Error>> {
         let left = Array::zeros((3, 3));
         let grad = Array::ones((3, 3));
-        let op = AdditionBackwardLeft::<Ix2, Ix2>::new(
+        let op = AdditionBackwardLeft::<Ix2, Ix2, f32>::new(
             Rc::new(Gradient::from_ndarray(left.clone())),
             Rc::new(Gradient::from_ndarray(grad.clone())),
         );
@@ -109,7 +110,7 @@ mod backward {
     #[test]
     fn left_reduction() -> Result<(), Box<dyn Error>> {
         let grad = Array::ones((3, 3));
-        let op = AdditionBackwardLeft::<Ix1, Ix2>::new(
+        let op = AdditionBackwardLeft::<Ix1, Ix2, f32>::new(
             Rc::new(Gradient::ndarray_zeros(3)),
             Rc::new(Gradient::from_ndarray(grad.clone())),
         );
@@ -127,7 +128,7 @@ mod backward {
     fn right_creation() -> Result<(), Box<dyn Error>> {
         let right = Array::zeros((3, 3));
         let grad = Array::ones((3, 3));
-        let op = AdditionBackwardRight::<Ix2, Ix2>::new(
+        let op = AdditionBackwardRight::<Ix2, Ix2, f32>::new(
             Rc::new(Gradient::from_ndarray(right.clone())),
             Rc::new(Gradient::from_ndarray(grad.clone())),
         );
@@ -140,7 +141,7 @@ mod backward {
     fn right_base_case() -> Result<(), Box<dyn Error>> {
         let right = Array::zeros((3, 3));
         let grad = Array::ones((3, 3));
-        let op = AdditionBackwardRight::<Ix2, Ix2>::new(
+        let op = AdditionBackwardRight::<Ix2, Ix2, f32>::new(
             Rc::new(Gradient::from_ndarray(right.clone())),
             Rc::new(Gradient::from_ndarray(grad.clone())),
         );
@@ -157,7 +158,7 @@ mod backward {
     #[test]
     fn right_reduction() -> Result<(), Box<dyn Error>> {
         let grad = Array::ones((3, 3));
-        let op = AdditionBackwardRight::<Ix2, Ix1>::new(
+        let op = AdditionBackwardRight::<Ix2, Ix1, f32>::new(
             Rc::new(Gradient::ndarray_zeros(3)),
             Rc::new(Gradient::from_ndarray(grad.clone())),
         );
@@ -178,11 +179,11 @@ mod backward {
         let grad = Array::ones((3, 3));
         let shared_grad = Rc::new(Gradient::from_ndarray(grad.clone()));
         let op = AdditionBackward::new(
-            AdditionBackwardLeft::<Ix2, Ix2>::new(
+            AdditionBackwardLeft::<Ix2, Ix2, f32>::new(
                 Rc::new(Gradient::from_ndarray(left.clone())),
                 shared_grad.clone(),
             ),
-            AdditionBackwardRight::<Ix2, Ix2>::new(
+            AdditionBackwardRight::<Ix2, Ix2, f32>::new(
                 Rc::new(Gradient::from_ndarray(right.clone())),
                 shared_grad,
             ),
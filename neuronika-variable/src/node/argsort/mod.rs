@@ -0,0 +1,90 @@
+use std::{cmp::Ordering, ops::Range};
+
+use ndarray::{Array, Axis, Dimension, Zip};
+
+use crate::{autograd::Forward, graph::Buffer, utils::Shared, Float};
+
+/// Compares two elements placing NaNs after every other value.
+fn compare<T>(lhs: T, rhs: T) -> Ordering
+where
+    T: Float,
+{
+    lhs.partial_cmp(&rhs)
+        .unwrap_or_else(|| lhs.is_nan().cmp(&rhs.is_nan()))
+}
+
+/// Computes the positions of the elements of the lanes of a variable along an axis, once sorted,
+/// keeping only those whose rank falls in a range.
+pub(crate) struct ArgSort<D, T>
+where
+    D: Dimension,
+    T: Float,
+{
+    operand_data: Shared<Array<T, D>>,
+    data: Shared<Array<usize, D>>,
+    axis: Axis,
+    ranks: Range<usize>,
+    descending: bool,
+}
+
+impl<D, T> ArgSort<D, T>
+where
+    D: Dimension,
+    T: Float,
+{
+    pub(crate) fn new(
+        operand_data: Shared<Array<T, D>>,
+        data: Shared<Array<usize, D>>,
+        axis: usize,
+        ranks: Range<usize>,
+        descending: bool,
+    ) -> Self {
+        Self {
+            operand_data,
+            data,
+            axis: Axis(axis),
+            ranks,
+            descending,
+        }
+    }
+}
+
+impl<D, T> Forward for ArgSort<D, T>
+where
+    D: Dimension,
+    T: Float,
+{
+    fn forward(&self) {
+        Zip::from(self.data.borrow_mut().lanes_mut(self.axis))
+            .and(self.operand_data.borrow().lanes(self.axis))
+            .for_each(|mut data_lane, operand_lane| {
+                let mut positions = (0..operand_lane.len()).collect::<Vec<_>>();
+                // The sort is stable, so that equal elements keep their relative order.
+                if self.descending {
+                    positions.sort_by(|&i, &j| compare(operand_lane[j], operand_lane[i]));
+                } else {
+                    positions.sort_by(|&i, &j| compare(operand_lane[i], operand_lane[j]));
+                }
+
+                data_lane
+                    .iter_mut()
+                    .zip(&positions[self.ranks.clone()])
+                    .for_each(|(data_el, &position)| *data_el = position);
+            });
+    }
+
+    fn name(&self) -> &'static str {
+        "ArgSort"
+    }
+
+    fn operands(&self) -> Vec<Buffer> {
+        vec![Buffer::new(&self.operand_data)]
+    }
+
+    fn data(&self) -> Buffer {
+        Buffer::new(&self.data)
+    }
+}
+
+#[cfg(test)]
+mod test;
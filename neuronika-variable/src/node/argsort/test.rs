@@ -0,0 +1,134 @@
+use std::error::Error;
+
+use ndarray::{array, Array, Array2};
+
+use crate::cell::Rc;
+use crate::utils::{are_similar, new_shared};
+
+mod forward {
+    use ndarray::array;
+
+    #[test]
+    fn sort_topk_kthvalue() {
+        let x = crate::from_ndarray(array![[3., 1., 2., 1.], [0., 5., 5., -1.]]);
+        let (sorted, sorted_index) = x.clone().sort(1, false);
+        let (descending, descending_index) = x.clone().sort(1, true);
+        let (top, top_index) = x.clone().topk(2, 1, true);
+        let (bottom, bottom_index) = x.clone().topk(2, 0, false);
+        let (kth, kth_index) = x.clone().kthvalue(2, 1);
+        for y in [&sorted, &descending, &top, &bottom, &kth] {
+            y.forward();
+        }
+        // Ties keep the order in which they appear.
+        assert_eq!(*sorted.data(), array![[1., 1., 2., 3.], [-1., 0., 5., 5.]]);
+        assert_eq!(*sorted_index.data(), array![[1, 3, 2, 0], [3, 0, 1, 2]]);
+        assert_eq!(
+            *descending.data(),
+            array![[3., 2., 1., 1.], [5., 5., 0., -1.]]
+        );
+        assert_eq!(*descending_index.data(), array![[0, 2, 1, 3], [1, 2, 0, 3]]);
+        assert_eq!(*top.data(), array![[3., 2.], [5., 5.]]);
+        assert_eq!(*top_index.data(), array![[0, 2], [1, 2]]);
+        assert_eq!(*bottom.data(), array![[0., 1., 2., -1.], [3., 5., 5., 1.]]);
+        assert_eq!(*bottom_index.data(), array![[1, 0, 0, 1], [0, 1, 1, 0]]);
+        assert_eq!(*kth.data(), array![[1.], [0.]]);
+        assert_eq!(*kth_index.data(), array![[3], [0]]);
+
+        // The indices select the matching elements of other variables.
+        let other = crate::from_ndarray(array![[10., 11., 12., 13.], [20., 21., 22., 23.]]);
+        let gathered = other.gather(1, top_index);
+        gathered.forward();
+        assert_eq!(*gathered.data(), array![[10., 12.], [21., 22.]]);
+    }
+
+    #[test]
+    #[should_panic(expected = "error: cannot select 5 elements out of 4 along axis 1.")]
+    fn topk_too_many() {
+        let _ = crate::zeros((2, 4)).topk(5, 1, true);
+    }
+}
+
+mod backward {
+    use super::super::{ArgSort, Forward};
+    use super::*;
+    use crate::{autograd::Backward, gradient::Gradient, node::GatherBackward};
+
+    /// Back-propagates `gradient` through the elements of `operand` selected by the ranks `ranks`
+    /// along `axis`, as the values returned together with the indices do.
+    fn selected(
+        operand: Array2<f32>,
+        gradient: Array2<f32>,
+        axis: usize,
+        ranks: std::ops::Range<usize>,
+        descending: bool,
+    ) -> Rc<Gradient<Array2<f32>, ndarray::Ix2>> {
+        let index = new_shared(Array::zeros(gradient.raw_dim()));
+        let op = ArgSort::new(
+            new_shared(operand.clone()),
+            index.clone(),
+            axis,
+            ranks,
+            descending,
+        );
+        op.forward();
+
+        let operand_gradient = Rc::new(Gradient::ndarray_zeros(operand.raw_dim()));
+        let op = GatherBackward::new(
+            operand_gradient.clone(),
+            index,
+            Rc::new(Gradient::from_ndarray(gradient)),
+            axis,
+        );
+        op.backward();
+
+        operand_gradient
+    }
+
+    #[test]
+    fn sort() -> Result<(), Box<dyn Error>> {
+        let gradient = selected(
+            array![[3., 1., 2., 1.], [0., 5., 5., -1.]],
+            array![[1., 2., 3., 4.], [5., 6., 7., 8.]],
+            1,
+            0..4,
+            true,
+        );
+
+        are_similar(
+            gradient.borrow(),
+            &array![[1., 3., 2., 4.], [7., 5., 6., 8.]],
+        )
+    }
+
+    #[test]
+    fn topk() -> Result<(), Box<dyn Error>> {
+        let gradient = selected(
+            array![[3., 1., 2., 1.], [0., 5., 5., -1.]],
+            array![[1., 2.], [3., 4.]],
+            1,
+            0..2,
+            true,
+        );
+
+        are_similar(
+            gradient.borrow(),
+            &array![[1., 0., 2., 0.], [0., 3., 4., 0.]],
+        )
+    }
+
+    #[test]
+    fn kthvalue() -> Result<(), Box<dyn Error>> {
+        let gradient = selected(
+            array![[3., 1., 2., 1.], [0., 5., 5., -1.]],
+            array![[1., 2., 3., 4.]],
+            0,
+            1..2,
+            false,
+        );
+
+        are_similar(
+            gradient.borrow(),
+            &array![[1., 0., 0., 4.], [0., 2., 3., 0.]],
+        )
+    }
+}
@@ -0,0 +1,475 @@
+use ndarray::{s, Array, Array2, ArrayD, ArrayView2, Axis, Dimension, Ix2, Ix3, Ix4, IxDyn, Zip};
+
+use rand_distr::{Bernoulli, Distribution};
+
+use crate::{
+    autograd::{Backward, Forward, Reads},
+    cell::{Cell, Rc},
+    gradient::Gradient,
+    graph::Buffer,
+    utils::Shared,
+    var::Var,
+    with_generator, Float, Generator,
+};
+
+/// Data and gradient of an operand of the attention, operands that are not differentiable have
+/// no gradient.
+type Operand<T> = (
+    Shared<Array<T, Ix4>>,
+    Option<Rc<Gradient<Array<T, Ix4>, Ix4>>>,
+);
+
+/// State of the generator the dropout masks of the last forward pass were sampled from, `None`
+/// when dropout was not applied.
+pub(crate) type DropoutState = Shared<Option<Generator>>;
+
+/// Dropout distribution, probability of keeping a weight, status and generator state.
+type Dropout = (Bernoulli, f64, Rc<Cell<bool>>, DropoutState);
+
+/// Mask restricting the positions attended to by scaled dot-product attention.
+///
+/// Masks must be broadcastable to the shape of the attention weights, that is
+/// *(N, H, L, S)*, where *N* is the batch size, *H* the number of heads, *L* the length of the
+/// queries and *S* the length of the keys.
+#[derive(Clone)]
+pub enum AttentionMask<T>
+where
+    T: Float,
+{
+    /// Only the positions set to `true` take part in the attention.
+    Boolean(Var<IxDyn, bool>),
+    /// Values added to the attention scores before the softmax.
+    Additive(Var<IxDyn, T>),
+}
+
+impl<T> AttentionMask<T>
+where
+    T: Float,
+{
+    /// Creates a boolean mask, only the positions set to `true` take part in the attention.
+    pub fn boolean<D>(mask: Var<D, bool>) -> Self
+    where
+        D: 'static + Dimension,
+    {
+        Self::Boolean(mask.into_dyn())
+    }
+
+    /// Creates an additive mask, its values are added to the attention scores before the softmax.
+    pub fn additive<D>(mask: Var<D, T>) -> Self
+    where
+        D: 'static + Dimension,
+    {
+        Self::Additive(mask.into_dyn())
+    }
+
+    pub(crate) fn data(&self) -> MaskData<T> {
+        match self {
+            Self::Boolean(mask) => MaskData::Boolean(mask.data.clone()),
+            Self::Additive(mask) => MaskData::Additive(mask.data.clone()),
+        }
+    }
+}
+
+/// Data of an attention mask.
+#[derive(Clone)]
+pub(crate) enum MaskData<T>
+where
+    T: Float,
+{
+    Boolean(Shared<ArrayD<bool>>),
+    Additive(Shared<ArrayD<T>>),
+}
+
+impl<T> MaskData<T>
+where
+    T: Float,
+{
+    /// Masks the scores of the `head`-th head of the `batch`-th sample.
+    fn apply(&self, scores: &mut Array2<T>, batch: usize, head: usize, shape: Ix4) {
+        match self {
+            Self::Boolean(mask) => {
+                let mask = mask.borrow();
+                let mask = mask.broadcast(shape).unwrap();
+                Zip::from(scores)
+                    .and(mask.slice(s![batch, head, .., ..]))
+                    .for_each(|score, &keep| {
+                        if !keep {
+                            *score = T::neg_infinity()
+                        }
+                    });
+            }
+            Self::Additive(mask) => {
+                let mask = mask.borrow();
+                let mask = mask.broadcast(shape).unwrap();
+                *scores += &mask.slice(s![batch, head, .., ..]);
+            }
+        }
+    }
+
+    fn buffer(&self) -> Buffer {
+        match self {
+            Self::Boolean(mask) => Buffer::new(mask),
+            Self::Additive(mask) => Buffer::new(mask),
+        }
+    }
+}
+
+/// Computes the scaled and masked attention scores of a single head.
+#[allow(clippy::too_many_arguments)]
+fn scores<T>(
+    query: ArrayView2<T>,
+    key: ArrayView2<T>,
+    scale: T,
+    mask: Option<&MaskData<T>>,
+    causal: bool,
+    batch: usize,
+    head: usize,
+    shape: Ix4,
+) -> Array2<T>
+where
+    T: Float,
+{
+    let mut scores = query.dot(&key.t()) * scale;
+    if let Some(mask) = mask {
+        mask.apply(&mut scores, batch, head, shape);
+    }
+    if causal {
+        scores
+            .indexed_iter_mut()
+            .filter(|((i, j), _)| j > i)
+            .for_each(|(_, score)| *score = T::neg_infinity());
+    }
+
+    scores
+}
+
+pub(crate) struct ScaledDotProductAttention<T>
+where
+    T: Float,
+{
+    query_data: Shared<Array<T, Ix4>>,
+    key_data: Shared<Array<T, Ix4>>,
+    value_data: Shared<Array<T, Ix4>>,
+    mask: Option<MaskData<T>>,
+    causal: bool,
+    dropout: Option<Dropout>,
+    logsumexp: Shared<Array<T, Ix3>>,
+    data: Shared<Array<T, Ix4>>,
+}
+
+impl<T> ScaledDotProductAttention<T>
+where
+    T: Float,
+{
+    #[allow(clippy::too_many_arguments, clippy::type_complexity)]
+    pub(crate) fn new(
+        query_data: Shared<Array<T, Ix4>>,
+        key_data: Shared<Array<T, Ix4>>,
+        value_data: Shared<Array<T, Ix4>>,
+        mask: Option<MaskData<T>>,
+        causal: bool,
+        dropout: Option<(f64, Rc<Cell<bool>>, DropoutState)>,
+        logsumexp: Shared<Array<T, Ix3>>,
+        data: Shared<Array<T, Ix4>>,
+    ) -> Self {
+        let dropout = dropout
+            .map(|(p, status, state)| (Bernoulli::new(1. - p).unwrap(), 1. - p, status, state));
+
+        Self {
+            query_data,
+            key_data,
+            value_data,
+            mask,
+            causal,
+            dropout,
+            logsumexp,
+            data,
+        }
+    }
+}
+
+/// Samples the dropout mask of the attention weights of a single head, already rescaled by the
+/// probability of keeping them.
+fn sample_mask<T>(generator: &mut Generator, distr: &Bernoulli, keep: f64, shape: Ix2) -> Array2<T>
+where
+    T: Float,
+{
+    let scale = if keep > 0. {
+        T::from_f64(1. / keep)
+    } else {
+        T::zero()
+    };
+
+    Array2::from_shape_simple_fn(shape, || {
+        if distr.sample(generator) {
+            scale
+        } else {
+            T::zero()
+        }
+    })
+}
+
+impl<T> Forward for ScaledDotProductAttention<T>
+where
+    T: Float,
+{
+    fn forward(&self) {
+        // A copy of the generator is stored before sampling, so that the backward pass can
+        // regenerate the masks instead of keeping them.
+        let mut generator = self.dropout.as_ref().and_then(|(_, _, status, state)| {
+            let generator = status
+                .get()
+                .then(|| with_generator(|generator| generator.clone()));
+            *state.borrow_mut() = generator.clone();
+            generator
+        });
+
+        let (query, key, value) = (
+            self.query_data.borrow(),
+            self.key_data.borrow(),
+            self.value_data.borrow(),
+        );
+        let mut logsumexp = self.logsumexp.borrow_mut();
+        let mut data = self.data.borrow_mut();
+
+        let (batch_size, heads, length, features) = query.dim();
+        let shape = Ix4(batch_size, heads, length, key.len_of(Axis(2)));
+        let scale = T::from_f64(1. / (features as f64).sqrt());
+
+        for batch in 0..batch_size {
+            for head in 0..heads {
+                let mut weights = scores(
+                    query.slice(s![batch, head, .., ..]),
+                    key.slice(s![batch, head, .., ..]),
+                    scale,
+                    self.mask.as_ref(),
+                    self.causal,
+                    batch,
+                    head,
+                    shape,
+                );
+
+                // Numerically stable softmax, rows that are entirely masked are set to zero.
+                let mut logsumexp = logsumexp.slice_mut(s![batch, head, ..]);
+                Zip::from(weights.rows_mut())
+                    .and(&mut logsumexp)
+                    .for_each(|mut row, lse| {
+                        let max = row.fold(T::neg_infinity(), |max, &el| max.max(el));
+                        if max == T::neg_infinity() {
+                            row.fill(T::zero());
+                            *lse = T::neg_infinity();
+                            return;
+                        }
+                        row.mapv_inplace(|el| (el - max).exp());
+                        let sum = row.sum();
+                        row.mapv_inplace(|el| el / sum);
+                        *lse = max + sum.ln();
+                    });
+                if let (Some((distr, keep, ..)), Some(generator)) = (&self.dropout, &mut generator)
+                {
+                    weights *= &sample_mask(generator, distr, *keep, weights.raw_dim());
+                }
+
+                data.slice_mut(s![batch, head, .., ..])
+                    .assign(&weights.dot(&value.slice(s![batch, head, .., ..])));
+            }
+        }
+
+        if let Some(generator) = generator {
+            with_generator(|current| *current = generator);
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        "ScaledDotProductAttention"
+    }
+
+    fn operands(&self) -> Vec<Buffer> {
+        let mut operands = vec![
+            Buffer::new(&self.query_data),
+            Buffer::new(&self.key_data),
+            Buffer::new(&self.value_data),
+        ];
+        if let Some(mask) = &self.mask {
+            operands.push(mask.buffer());
+        }
+
+        operands
+    }
+
+    fn data(&self) -> Buffer {
+        Buffer::new(&self.data)
+    }
+}
+
+pub(crate) struct ScaledDotProductAttentionBackward<T>
+where
+    T: Float,
+{
+    query_data: Shared<Array<T, Ix4>>,
+    query_gradient: Option<Rc<Gradient<Array<T, Ix4>, Ix4>>>,
+    key_data: Shared<Array<T, Ix4>>,
+    key_gradient: Option<Rc<Gradient<Array<T, Ix4>, Ix4>>>,
+    value_data: Shared<Array<T, Ix4>>,
+    value_gradient: Option<Rc<Gradient<Array<T, Ix4>, Ix4>>>,
+    mask: Option<MaskData<T>>,
+    causal: bool,
+    dropout: Option<(Bernoulli, f64, DropoutState)>,
+    logsumexp: Shared<Array<T, Ix3>>,
+    gradient: Rc<Gradient<Array<T, Ix4>, Ix4>>,
+}
+
+impl<T> ScaledDotProductAttentionBackward<T>
+where
+    T: Float,
+{
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn new(
+        query: Operand<T>,
+        key: Operand<T>,
+        value: Operand<T>,
+        mask: Option<MaskData<T>>,
+        causal: bool,
+        dropout: Option<(f64, DropoutState)>,
+        logsumexp: Shared<Array<T, Ix3>>,
+        gradient: Rc<Gradient<Array<T, Ix4>, Ix4>>,
+    ) -> Self {
+        let (query_data, query_gradient) = query;
+        let (key_data, key_gradient) = key;
+        let (value_data, value_gradient) = value;
+        let dropout = dropout.map(|(p, state)| (Bernoulli::new(1. - p).unwrap(), 1. - p, state));
+
+        Self {
+            query_data,
+            query_gradient,
+            key_data,
+            key_gradient,
+            value_data,
+            value_gradient,
+            mask,
+            causal,
+            dropout,
+            logsumexp,
+            gradient,
+        }
+    }
+}
+
+impl<T> Backward for ScaledDotProductAttentionBackward<T>
+where
+    T: Float,
+{
+    fn backward(&self) {
+        let (query, key, value) = (
+            self.query_data.borrow(),
+            self.key_data.borrow(),
+            self.value_data.borrow(),
+        );
+        let mut generator = self
+            .dropout
+            .as_ref()
+            .and_then(|(_, _, state)| state.borrow().clone());
+        let logsumexp = self.logsumexp.borrow();
+        let gradient = self.gradient.borrow();
+        let mut query_gradient = self
+            .query_gradient
+            .as_ref()
+            .map(|gradient| gradient.borrow_mut());
+        let mut key_gradient = self
+            .key_gradient
+            .as_ref()
+            .map(|gradient| gradient.borrow_mut());
+        let mut value_gradient = self
+            .value_gradient
+            .as_ref()
+            .map(|gradient| gradient.borrow_mut());
+
+        let (batch_size, heads, length, features) = query.dim();
+        let shape = Ix4(batch_size, heads, length, key.len_of(Axis(2)));
+        let scale = T::from_f64(1. / (features as f64).sqrt());
+
+        for batch in 0..batch_size {
+            for head in 0..heads {
+                let (query, key, value, gradient) = (
+                    query.slice(s![batch, head, .., ..]),
+                    key.slice(s![batch, head, .., ..]),
+                    value.slice(s![batch, head, .., ..]),
+                    gradient.slice(s![batch, head, .., ..]),
+                );
+
+                // The softmax is recomputed from the scores and the stored normalizers.
+                let mut weights = scores(
+                    query,
+                    key,
+                    scale,
+                    self.mask.as_ref(),
+                    self.causal,
+                    batch,
+                    head,
+                    shape,
+                );
+                Zip::from(weights.rows_mut())
+                    .and(logsumexp.slice(s![batch, head, ..]))
+                    .for_each(|mut row, &lse| {
+                        if lse == T::neg_infinity() {
+                            row.fill(T::zero());
+                        } else {
+                            row.mapv_inplace(|el| (el - lse).exp());
+                        }
+                    });
+
+                // The masks are sampled again in the same order as in the forward pass.
+                let mask = self.dropout.as_ref().zip(generator.as_mut()).map(
+                    |((distr, keep, _), generator)| {
+                        sample_mask::<T>(generator, distr, *keep, weights.raw_dim())
+                    },
+                );
+                if let Some(value_gradient) = &mut value_gradient {
+                    let increment = match &mask {
+                        Some(mask) => (&weights * mask).t().dot(&gradient),
+                        None => weights.t().dot(&gradient),
+                    };
+                    value_gradient
+                        .slice_mut(s![batch, head, .., ..])
+                        .scaled_add(T::one(), &increment);
+                }
+                if query_gradient.is_none() && key_gradient.is_none() {
+                    continue;
+                }
+
+                let mut weights_gradient = gradient.dot(&value.t());
+                if let Some(mask) = &mask {
+                    weights_gradient *= mask;
+                }
+
+                // Gradient of the softmax.
+                Zip::from(weights_gradient.rows_mut())
+                    .and(weights.rows())
+                    .for_each(|mut gradient_row, weights_row| {
+                        let dot = gradient_row.dot(&weights_row);
+                        Zip::from(&mut gradient_row).and(&weights_row).for_each(
+                            |gradient_el, &weight| *gradient_el = weight * (*gradient_el - dot),
+                        );
+                    });
+
+                if let Some(query_gradient) = &mut query_gradient {
+                    query_gradient
+                        .slice_mut(s![batch, head, .., ..])
+                        .scaled_add(scale, &weights_gradient.dot(&key));
+                }
+                if let Some(key_gradient) = &mut key_gradient {
+                    key_gradient
+                        .slice_mut(s![batch, head, .., ..])
+                        .scaled_add(scale, &weights_gradient.t().dot(&query));
+                }
+            }
+        }
+    }
+
+    fn reads(&self) -> Reads {
+        Reads::Operands
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,250 @@
+use crate::utils::weights;
+use ndarray::Array4;
+
+/// Returns the queries, keys and values shared by the tests.
+fn inputs() -> [Array4<f64>; 3] {
+    [
+        weights(&[2, 3, 4, 5], 0.7),
+        weights(&[2, 3, 6, 5], 1.3),
+        weights(&[2, 3, 6, 2], 0.4),
+    ]
+    .map(|weights| weights.data().to_owned().into_dimensionality().unwrap())
+}
+
+mod forward {
+    use super::inputs;
+    use crate::{
+        cell::{Cell, Rc},
+        Attention, AttentionMask,
+    };
+    use ndarray::{array, s, Array, Array4};
+
+    // Unfused attention, with masked scores set to -inf.
+    fn reference(
+        query: &Array4<f64>,
+        key: &Array4<f64>,
+        value: &Array4<f64>,
+        keep: &dyn Fn(usize, usize) -> bool,
+    ) -> Array4<f64> {
+        let (batch_size, heads, length, features) = query.dim();
+        let mut result = Array4::zeros((batch_size, heads, length, value.len_of(ndarray::Axis(3))));
+        for batch in 0..batch_size {
+            for head in 0..heads {
+                let mut scores = query
+                    .slice(s![batch, head, .., ..])
+                    .dot(&key.slice(s![batch, head, .., ..]).t())
+                    / (features as f64).sqrt();
+                scores.indexed_iter_mut().for_each(|((i, j), score)| {
+                    if !keep(i, j) {
+                        *score = f64::NEG_INFINITY
+                    }
+                });
+                scores.rows_mut().into_iter().for_each(|mut row| {
+                    let max = row.fold(f64::NEG_INFINITY, |max, &el| max.max(el));
+                    row.mapv_inplace(|el| (el - max).exp());
+                    let sum = row.sum();
+                    row /= sum;
+                });
+                result
+                    .slice_mut(s![batch, head, .., ..])
+                    .assign(&scores.dot(&value.slice(s![batch, head, .., ..])));
+            }
+        }
+        result
+    }
+
+    #[test]
+    fn masks() {
+        let [query, key, value] = inputs();
+        let status = Rc::new(Cell::new(true));
+        let attend = |mask: Option<AttentionMask<f64>>, causal| {
+            let y = crate::from_ndarray(query.clone()).scaled_dot_product_attention(
+                crate::from_ndarray(key.clone()),
+                crate::from_ndarray(value.clone()),
+                mask,
+                0.,
+                status.clone(),
+                causal,
+            );
+            y.forward();
+            let data = y.data().to_owned();
+            data
+        };
+
+        let expected = reference(&query, &key, &value, &|_, _| true);
+        assert!(attend(None, false).abs_diff_eq(&expected, 1e-12));
+
+        let expected = reference(&query, &key, &value, &|i, j| j <= i);
+        assert!(attend(None, true).abs_diff_eq(&expected, 1e-12));
+
+        let keep = Array::from_shape_fn((4, 6), |(i, j)| (i + j) % 3 != 0);
+        let expected = reference(&query, &key, &value, &|i, j| keep[[i, j]]);
+        let mask = AttentionMask::boolean(crate::from_ndarray(keep.clone()));
+        assert!(attend(Some(mask), false).abs_diff_eq(&expected, 1e-12));
+        let additive = keep.mapv(|keep| if keep { 0. } else { f64::NEG_INFINITY });
+        let mask = AttentionMask::additive(crate::from_ndarray(additive));
+        assert!(attend(Some(mask), false).abs_diff_eq(&expected, 1e-12));
+
+        // Queries that cannot attend to any key result in zeros.
+        let mask = AttentionMask::boolean(crate::from_ndarray(array![
+            false, false, true, true, true, true
+        ]));
+        let result = attend(Some(mask), true);
+        assert!(result.slice(s![.., .., ..2, ..]).iter().all(|&el| el == 0.));
+        assert!(result
+            .slice(s![.., .., 2.., ..])
+            .iter()
+            .all(|el| el.is_finite()));
+    }
+
+    #[test]
+    fn dropout_status() {
+        let [query, key, value] = inputs();
+        let status = Rc::new(Cell::new(false));
+        let attend = |dropout| {
+            let y = crate::from_ndarray(query.clone()).scaled_dot_product_attention(
+                crate::from_ndarray(key.clone()),
+                crate::from_ndarray(value.clone()),
+                None,
+                dropout,
+                status.clone(),
+                false,
+            );
+            y.forward();
+            let data = y.data().to_owned();
+            data
+        };
+
+        // Dropout is not applied when the status is off.
+        let expected = reference(&query, &key, &value, &|_, _| true);
+        assert!(attend(0.5).abs_diff_eq(&expected, 1e-12));
+        status.set(true);
+        assert!(attend(1.).iter().all(|&el| el == 0.));
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "error: cannot attend with queries of shape [2, 3, 4, 5], keys of shape [2, 3, 6, 4] and values of shape [2, 3, 6, 2]."
+    )]
+    fn wrong_shapes() {
+        let _ = crate::zeros((2, 3, 4, 5)).scaled_dot_product_attention(
+            crate::zeros((2, 3, 6, 4)),
+            crate::zeros((2, 3, 6, 2)),
+            None,
+            0.,
+            Rc::new(Cell::new(true)),
+            false,
+        );
+    }
+}
+
+mod backward {
+    use super::inputs;
+    use crate::{
+        cell::{Cell, Rc},
+        utils::weights,
+        Attention, AttentionMask,
+    };
+    use rand::RngCore;
+
+    #[test]
+    fn masked() {
+        let [query, key, value] = inputs().map(|x| crate::from_ndarray(x).requires_grad());
+        let inputs: [crate::Parameter<f64>; 3] = [
+            query.clone().into(),
+            key.clone().into(),
+            value.clone().into(),
+        ];
+        let status = Rc::new(Cell::new(true));
+        let f = || {
+            query.clone().scaled_dot_product_attention(
+                key.clone(),
+                value.clone(),
+                Some(AttentionMask::additive(weights(&[4, 6], 0.9))),
+                0.,
+                status.clone(),
+                true,
+            ) * weights(&[2, 3, 4, 2], 2.1)
+        };
+        assert_eq!(crate::gradcheck(f, &inputs, 1e-6, 1e-7, 1e-5), Ok(()));
+    }
+
+    #[test]
+    fn constant_key_value() {
+        // Attending over a constant memory only accumulates the gradient of the queries.
+        let [query, key, value] = inputs();
+        let query = crate::from_ndarray(query).requires_grad();
+        let (key, value) = (crate::from_ndarray(key), crate::from_ndarray(value));
+        let inputs: [crate::Parameter<f64>; 1] = [query.clone().into()];
+        let status = Rc::new(Cell::new(true));
+        let f = || {
+            query.clone().scaled_dot_product_attention(
+                key.clone(),
+                value.clone(),
+                Some(AttentionMask::additive(weights(&[4, 6], 0.9))),
+                0.,
+                status.clone(),
+                true,
+            ) * weights(&[2, 3, 4, 2], 2.1)
+        };
+        assert_eq!(crate::gradcheck(f, &inputs, 1e-6, 1e-7, 1e-5), Ok(()));
+
+        // The gradient of the queries matches the one with differentiable keys and values.
+        let y = f().sum();
+        y.forward();
+        query.zero_grad();
+        y.backward(1.);
+        let expected = query.grad().to_owned();
+        query.zero_grad();
+        let y = (query.clone().scaled_dot_product_attention(
+            key.requires_grad(),
+            value.requires_grad(),
+            Some(AttentionMask::additive(weights(&[4, 6], 0.9))),
+            0.,
+            status,
+            true,
+        ) * weights(&[2, 3, 4, 2], 2.1))
+        .sum();
+        y.forward();
+        y.backward(1.);
+        assert!(query.grad().abs_diff_eq(&expected, 1e-12));
+    }
+
+    #[test]
+    fn constant_query() {
+        let [query, key, value] = inputs();
+        let query = crate::from_ndarray(query);
+        let (key, value) = (
+            crate::from_ndarray(key).requires_grad(),
+            crate::from_ndarray(value).requires_grad(),
+        );
+        let inputs: [crate::Parameter<f64>; 2] = [key.clone().into(), value.clone().into()];
+        let status = Rc::new(Cell::new(true));
+        let f = || {
+            query.clone().scaled_dot_product_attention(
+                key.clone(),
+                value.clone(),
+                None,
+                0.,
+                status.clone(),
+                false,
+            ) * weights(&[2, 3, 4, 2], 2.1)
+        };
+        assert_eq!(crate::gradcheck(f, &inputs, 1e-6, 1e-7, 1e-5), Ok(()));
+    }
+
+    #[test]
+    fn dropout() {
+        // With dropout, the backward pass regenerates the masks of the forward pass, even if the
+        // generator has been used in between.
+        let [query, key, value] = inputs().map(|x| crate::from_ndarray(x).requires_grad());
+        let status = Rc::new(Cell::new(true));
+        let y = query.scaled_dot_product_attention(key, value.clone(), None, 0.5, status, false);
+        y.forward();
+        crate::with_generator(|generator| generator.next_u64());
+        y.backward(1.);
+        // The output is linear in the values, so that its sum is recovered from their gradient.
+        let expected = y.data().sum();
+        assert!((expected - (&*value.grad() * &*value.data()).sum()).abs() < 1e-12);
+    }
+}
@@ -1,32 +1,34 @@
-use std::rc::Rc;
-
 use ndarray::{arr0, Array, Dimension, Ix0, Zip};
 
 use crate::{
-    autograd::{Backward, Forward},
+    autograd::{Backward, Forward, Reads},
+    cell::Rc,
     gradient::Gradient,
+    graph::Buffer,
     utils::Shared,
-    Reduction,
+    Float, Reduction,
 };
 
-pub(crate) struct BinaryCrossEntropy<D>
+pub(crate) struct BinaryCrossEntropy<D, T>
 where
     D: Dimension,
+    T: Float,
 {
-    input_data: Shared<Array<f32, D>>,
-    target_data: Shared<Array<f32, D>>,
-    data: Shared<Array<f32, Ix0>>,
+    input_data: Shared<Array<T, D>>,
+    target_data: Shared<Array<T, D>>,
+    data: Shared<Array<T, Ix0>>,
     reduction: Reduction,
 }
 
-impl<D> BinaryCrossEntropy<D>
+impl<D, T> BinaryCrossEntropy<D, T>
 where
     D: Dimension,
+    T: Float,
 {
     pub(crate) fn new(
-        input_data: Shared<Array<f32, D>>,
-        target_data: Shared<Array<f32, D>>,
-        data: Shared<Array<f32, Ix0>>,
+        input_data: Shared<Array<T, D>>,
+        target_data: Shared<Array<T, D>>,
+        data: Shared<Array<T, Ix0>>,
         reduction: Reduction,
     ) -> Self {
         Self {
@@ -38,49 +40,68 @@ where
     }
 }
 
-impl<D> Forward for BinaryCrossEntropy<D>
+impl<D, T> Forward for BinaryCrossEntropy<D, T>
 where
     D: Dimension,
+    T: Float,
 {
     fn forward(&self) {
-        const LOG_MIN: f32 = 100.;
+        let log_min = T::from_f64(100.);
 
         let input_data = self.input_data.borrow();
         *self.data.borrow_mut() = {
             let total_loss = Zip::from(&*input_data)
                 .and(&*self.target_data.borrow())
-                .fold(0., |loss, &input, &target| {
-                    loss - target * input.ln().clamp(-LOG_MIN, f32::MAX)
-                        + (target - 1.) * (1. - input).ln().clamp(-LOG_MIN, f32::MAX)
+                .fold(T::zero(), |loss, &input, &target| {
+                    loss - target * input.ln().clamp(-log_min, T::max_value())
+                        + (target - T::one())
+                            * (T::one() - input).ln().clamp(-log_min, T::max_value())
                 });
             match self.reduction {
-                Reduction::Mean => arr0(total_loss / input_data.len() as f32),
+                Reduction::Mean => arr0(total_loss / T::from_usize(input_data.len())),
                 Reduction::Sum => arr0(total_loss),
             }
         };
     }
+
+    fn name(&self) -> &'static str {
+        "BinaryCrossEntropy"
+    }
+
+    fn operands(&self) -> Vec<Buffer> {
+        vec![
+            Buffer::new(&self.input_data),
+            Buffer::new(&self.target_data),
+        ]
+    }
+
+    fn data(&self) -> Buffer {
+        Buffer::new(&self.data)
+    }
 }
 
-pub(crate) struct BinaryCrossEntropyBackward<D>
+pub(crate) struct BinaryCrossEntropyBackward<D, T>
 where
     D: Dimension,
+    T: Float,
 {
-    input_data: Shared<Array<f32, D>>,
-    target_data: Shared<Array<f32, D>>,
-    input_gradient: Rc<Gradient<Array<f32, D>, D>>,
-    gradient: Rc<Gradient<Array<f32, Ix0>, Ix0>>,
+    input_data: Shared<Array<T, D>>,
+    target_data: Shared<Array<T, D>>,
+    input_gradient: Rc<Gradient<Array<T, D>, D>>,
+    gradient: Rc<Gradient<Array<T, Ix0>, Ix0>>,
     reduction: Reduction,
 }
 
-impl<D> BinaryCrossEntropyBackward<D>
+impl<D, T> BinaryCrossEntropyBackward<D, T>
 where
     D: Dimension,
+    T: Float,
 {
     pub(crate) fn new(
-        input_data: Shared<Array<f32, D>>,
-        target_data: Shared<Array<f32, D>>,
-        input_gradient: Rc<Gradient<Array<f32, D>, D>>,
-        gradient: Rc<Gradient<Array<f32, Ix0>, Ix0>>,
+        input_data: Shared<Array<T, D>>,
+        target_data: Shared<Array<T, D>>,
+        input_gradient: Rc<Gradient<Array<T, D>, D>>,
+        gradient: Rc<Gradient<Array<T, Ix0>, Ix0>>,
         reduction: Reduction,
     ) -> Self {
         Self {
@@ -93,9 +114,10 @@ where
     }
 }
 
-impl<D> Backward for BinaryCrossEntropyBackward<D>
+impl<D, T> Backward for BinaryCrossEntropyBackward<D, T>
 where
     D: Dimension,
+    T: Float,
 {
     fn backward(&self) {
         let mut input_gradient = self.input_gradient.borrow_mut();
@@ -110,19 +132,25 @@ where
 
         match self.reduction {
             Reduction::Mean => {
-                let n = input_data.len() as f32;
+                let n = T::from_usize(input_data.len());
                 zip.for_each(|op_grad, &grad, &input, &target| {
-                    *op_grad +=
-                        (input - target) / ((1. - input) * input).max(f32::EPSILON) * grad / n;
+                    *op_grad += (input - target) / ((T::one() - input) * input).max(T::epsilon())
+                        * grad
+                        / n;
                 });
             }
             Reduction::Sum => {
                 zip.for_each(|op_grad, &grad, &input, &target| {
-                    *op_grad += (input - target) / ((1. - input) * input).max(f32::EPSILON) * grad;
+                    *op_grad +=
+                        (input - target) / ((T::one() - input) * input).max(T::epsilon()) * grad;
                 });
             }
         }
     }
+
+    fn reads(&self) -> Reads {
+        Reads::Operands
+    }
 }
 
 #[cfg(test)]
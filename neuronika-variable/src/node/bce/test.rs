@@ -2,6 +2,7 @@ use std::error::Error;
 
 use ndarray::{arr0, Array};
 
+use crate::cell::Rc;
 use crate::utils::{are_similar, new_shared};
 
 #[cfg(test)]
@@ -55,7 +56,6 @@ mod forward {
 
 #[cfg(test)]
 mod backward {
-    use std::rc::Rc;
 
     use super::super::{Backward, BinaryCrossEntropyBackward, Gradient, Reduction};
     use super::*;
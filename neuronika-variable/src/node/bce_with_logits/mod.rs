@@ -1,33 +1,35 @@
-use std::rc::Rc;
-
 use ndarray::{arr0, Array, Dimension, Ix0, Zip};
 
 use crate::{
-    autograd::{Backward, Forward},
+    autograd::{Backward, Forward, Reads},
+    cell::Rc,
     gradient::Gradient,
+    graph::Buffer,
     utils::Shared,
-    Reduction,
+    Float, Reduction,
 };
 
 #[allow(clippy::upper_case_acronyms)]
-pub(crate) struct BCEWithLogits<D>
+pub(crate) struct BCEWithLogits<D, T>
 where
     D: Dimension,
+    T: Float,
 {
-    input_data: Shared<Array<f32, D>>,
-    target_data: Shared<Array<f32, D>>,
-    data: Shared<Array<f32, Ix0>>,
+    input_data: Shared<Array<T, D>>,
+    target_data: Shared<Array<T, D>>,
+    data: Shared<Array<T, Ix0>>,
     reduction: Reduction,
 }
 
-impl<D> BCEWithLogits<D>
+impl<D, T> BCEWithLogits<D, T>
 where
     D: Dimension,
+    T: Float,
 {
     pub(crate) fn new(
-        input_data: Shared<Array<f32, D>>,
-        target_data: Shared<Array<f32, D>>,
-        data: Shared<Array<f32, Ix0>>,
+        input_data: Shared<Array<T, D>>,
+        target_data: Shared<Array<T, D>>,
+        data: Shared<Array<T, Ix0>>,
         reduction: Reduction,
     ) -> Self {
         Self {
@@ -39,54 +41,72 @@ where
     }
 }
 
-impl<D> Forward for BCEWithLogits<D>
+impl<D, T> Forward for BCEWithLogits<D, T>
 where
     D: Dimension,
+    T: Float,
 {
     fn forward(&self) {
         let input_data = self.input_data.borrow();
         let target_data = self.target_data.borrow();
 
         *self.data.borrow_mut() = {
-            let total_loss =
-                Zip::from(&*input_data)
-                    .and(&*target_data)
-                    .fold(0., |loss, &input, &target| {
-                        let max = (-input).max(0.);
-                        loss + (1. - target) * input
-                            + max
-                            + ((-max).exp() + (-input - max).exp()).ln()
-                    });
+            let total_loss = Zip::from(&*input_data).and(&*target_data).fold(
+                T::zero(),
+                |loss, &input, &target| {
+                    let max = (-input).max(T::zero());
+                    loss + (T::one() - target) * input
+                        + max
+                        + ((-max).exp() + (-input - max).exp()).ln()
+                },
+            );
 
             match self.reduction {
-                Reduction::Mean => arr0(total_loss / input_data.len() as f32),
+                Reduction::Mean => arr0(total_loss / T::from_usize(input_data.len())),
                 Reduction::Sum => arr0(total_loss),
             }
         };
     }
+
+    fn name(&self) -> &'static str {
+        "BCEWithLogits"
+    }
+
+    fn operands(&self) -> Vec<Buffer> {
+        vec![
+            Buffer::new(&self.input_data),
+            Buffer::new(&self.target_data),
+        ]
+    }
+
+    fn data(&self) -> Buffer {
+        Buffer::new(&self.data)
+    }
 }
 
 #[allow(clippy::upper_case_acronyms)]
-pub(crate) struct BCEWithLogitsBackward<D>
+pub(crate) struct BCEWithLogitsBackward<D, T>
 where
     D: Dimension,
+    T: Float,
 {
-    input_data: Shared<Array<f32, D>>,
-    input_gradient: Rc<Gradient<Array<f32, D>, D>>,
-    target_data: Shared<Array<f32, D>>,
-    gradient: Rc<Gradient<Array<f32, Ix0>, Ix0>>,
+    input_data: Shared<Array<T, D>>,
+    input_gradient: Rc<Gradient<Array<T, D>, D>>,
+    target_data: Shared<Array<T, D>>,
+    gradient: Rc<Gradient<Array<T, Ix0>, Ix0>>,
     reduction: Reduction,
 }
 
-impl<D> BCEWithLogitsBackward<D>
+impl<D, T> BCEWithLogitsBackward<D, T>
 where
     D: Dimension,
+    T: Float,
 {
     pub(crate) fn new(
-        input_data: Shared<Array<f32, D>>,
-        input_gradient: Rc<Gradient<Array<f32, D>, D>>,
-        target_data: Shared<Array<f32, D>>,
-        gradient: Rc<Gradient<Array<f32, Ix0>, Ix0>>,
+        input_data: Shared<Array<T, D>>,
+        input_gradient: Rc<Gradient<Array<T, D>, D>>,
+        target_data: Shared<Array<T, D>>,
+        gradient: Rc<Gradient<Array<T, Ix0>, Ix0>>,
         reduction: Reduction,
     ) -> Self {
         Self {
@@ -99,9 +119,10 @@ where
     }
 }
 
-impl<D> Backward for BCEWithLogitsBackward<D>
+impl<D, T> Backward for BCEWithLogitsBackward<D, T>
 where
     D: Dimension,
+    T: Float,
 {
     fn backward(&self) {
         let mut input_gradient = self.input_gradient.borrow_mut();
@@ -115,20 +136,24 @@ where
 
         match self.reduction {
             Reduction::Mean => {
-                let n = input_data.len() as f32;
+                let n = T::from_usize(input_data.len());
                 zip.for_each(|op_grad, &grad, &input, &target| {
-                    let input_sigmoid = 1. / (1. + (-input).exp());
+                    let input_sigmoid = T::one() / (T::one() + (-input).exp());
                     *op_grad += (input_sigmoid - target) * grad / n
                 });
             }
             Reduction::Sum => {
                 zip.for_each(|op_grad, &grad, &input, &target| {
-                    let input_sigmoid = 1. / (1. + (-input).exp());
+                    let input_sigmoid = T::one() / (T::one() + (-input).exp());
                     *op_grad += (input_sigmoid - target) * grad
                 });
             }
         }
     }
+
+    fn reads(&self) -> Reads {
+        Reads::Operands
+    }
 }
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
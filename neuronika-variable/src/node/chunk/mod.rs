@@ -1,30 +1,33 @@
-use std::rc::Rc;
-
 use ndarray::{Array, Dimension};
 
 use crate::{
-    autograd::{Backward, Forward},
+    autograd::{Backward, Forward, Reads},
+    cell::Rc,
     gradient::Gradient,
+    graph::Buffer,
     utils::Shared,
+    Float,
 };
 
-pub(crate) struct Chunk<D>
+pub(crate) struct Chunk<D, T>
 where
     D: Dimension,
+    T: Float,
 {
-    operand_data: Shared<Array<f32, D>>,
+    operand_data: Shared<Array<T, D>>,
     chunk_no: usize,
     shape: D,
-    data: Shared<Array<f32, D>>,
+    data: Shared<Array<T, D>>,
 }
 
-impl<D> Chunk<D>
+impl<D, T> Chunk<D, T>
 where
     D: Dimension,
+    T: Float,
 {
     pub(crate) fn new(
-        operand_data: Shared<Array<f32, D>>,
-        data: Shared<Array<f32, D>>,
+        operand_data: Shared<Array<T, D>>,
+        data: Shared<Array<T, D>>,
         chunk_no: usize,
     ) -> Self {
         debug_assert!(data
@@ -45,9 +48,10 @@ where
     }
 }
 
-impl<D> Forward for Chunk<D>
+impl<D, T> Forward for Chunk<D, T>
 where
     D: Dimension,
+    T: Float,
 {
     fn forward(&self) {
         let operand_data = self.operand_data.borrow();
@@ -61,24 +65,38 @@ where
 
         self.data.borrow_mut().assign(&operand_data_chunk);
     }
+
+    fn name(&self) -> &'static str {
+        "Chunk"
+    }
+
+    fn operands(&self) -> Vec<Buffer> {
+        vec![Buffer::new(&self.operand_data)]
+    }
+
+    fn data(&self) -> Buffer {
+        Buffer::new(&self.data)
+    }
 }
 
-pub(crate) struct ChunkBackward<D>
+pub(crate) struct ChunkBackward<D, T>
 where
     D: Dimension,
+    T: Float,
 {
-    operand_gradient: Rc<Gradient<Array<f32, D>, D>>,
-    gradient: Rc<Gradient<Array<f32, D>, D>>,
+    operand_gradient: Rc<Gradient<Array<T, D>, D>>,
+    gradient: Rc<Gradient<Array<T, D>, D>>,
     chunk_no: usize,
 }
 
-impl<D> ChunkBackward<D>
+impl<D, T> ChunkBackward<D, T>
 where
     D: Dimension,
+    T: Float,
 {
     pub(crate) fn new(
-        operand_gradient: Rc<Gradient<Array<f32, D>, D>>,
-        gradient: Rc<Gradient<Array<f32, D>, D>>,
+        operand_gradient: Rc<Gradient<Array<T, D>, D>>,
+        gradient: Rc<Gradient<Array<T, D>, D>>,
         chunk_no: usize,
     ) -> Self {
         debug_assert!(gradient
@@ -96,9 +114,10 @@ where
     }
 }
 
-impl<D> Backward for ChunkBackward<D>
+impl<D, T> Backward for ChunkBackward<D, T>
 where
     D: Dimension,
+    T: Float,
 {
     fn backward(&self) {
         let mut operand_gradient = self.operand_gradient.borrow_mut();
@@ -112,6 +131,10 @@ where
 
         operand_gradient_chunk += &*self.gradient.borrow();
     }
+
+    fn reads(&self) -> Reads {
+        Reads::Nothing
+    }
 }
 
 #[cfg(test)]
@@ -2,6 +2,7 @@ use std::error::Error;
 
 use ndarray::Array;
 
+use crate::cell::Rc;
 use crate::utils::{are_similar, new_shared};
 
 mod forward {
@@ -48,7 +49,6 @@ mod forward {
 
 #[cfg(test)]
 mod backward {
-    use std::rc::Rc;
 
     use super::super::{Backward, ChunkBackward, Gradient};
     use super::*;
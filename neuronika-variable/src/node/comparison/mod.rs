@@ -0,0 +1,97 @@
+use ndarray::{Array, DimMax, Dimension, Zip};
+
+use crate::{
+    autograd::Forward,
+    graph::Buffer,
+    utils::{Broadcast, Shared},
+    Element,
+};
+
+/// The comparison carried out by a [`Comparison`] node.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum ComparisonKind {
+    Greater,
+    GreaterEqual,
+    Less,
+    LessEqual,
+    Equal,
+    NotEqual,
+}
+
+pub(crate) struct Comparison<D, E, T>
+where
+    D: Dimension + DimMax<E>,
+    E: Dimension,
+    T: Element,
+{
+    left_data: Shared<Array<T, D>>,
+    right_data: Shared<Array<T, E>>,
+    data: Shared<Array<bool, Broadcast<D, E>>>,
+    kind: ComparisonKind,
+}
+
+impl<D, E, T> Comparison<D, E, T>
+where
+    D: Dimension + DimMax<E>,
+    E: Dimension,
+    T: Element,
+{
+    pub(crate) fn new(
+        left_data: Shared<Array<T, D>>,
+        right_data: Shared<Array<T, E>>,
+        data: Shared<Array<bool, Broadcast<D, E>>>,
+        kind: ComparisonKind,
+    ) -> Self {
+        Self {
+            left_data,
+            right_data,
+            data,
+            kind,
+        }
+    }
+}
+
+impl<D, E, T> Forward for Comparison<D, E, T>
+where
+    D: Dimension + DimMax<E>,
+    E: Dimension,
+    T: Element,
+{
+    fn forward(&self) {
+        let compare: fn(&T, &T) -> bool = match self.kind {
+            ComparisonKind::Greater => PartialOrd::gt,
+            ComparisonKind::GreaterEqual => PartialOrd::ge,
+            ComparisonKind::Less => PartialOrd::lt,
+            ComparisonKind::LessEqual => PartialOrd::le,
+            ComparisonKind::Equal => PartialEq::eq,
+            ComparisonKind::NotEqual => PartialEq::ne,
+        };
+
+        Zip::from(&mut *self.data.borrow_mut())
+            .and_broadcast(&*self.left_data.borrow())
+            .and_broadcast(&*self.right_data.borrow())
+            .for_each(|v, l, r| *v = compare(l, r));
+    }
+
+    fn name(&self) -> &'static str {
+        match self.kind {
+            ComparisonKind::Greater => "Greater",
+            ComparisonKind::GreaterEqual => "GreaterEqual",
+            ComparisonKind::Less => "Less",
+            ComparisonKind::LessEqual => "LessEqual",
+            ComparisonKind::Equal => "Equal",
+            ComparisonKind::NotEqual => "NotEqual",
+        }
+    }
+
+    fn operands(&self) -> Vec<Buffer> {
+        vec![Buffer::new(&self.left_data), Buffer::new(&self.right_data)]
+    }
+
+    fn data(&self) -> Buffer {
+        Buffer::new(&self.data)
+    }
+}
+
+#[cfg(test)]
+mod test;
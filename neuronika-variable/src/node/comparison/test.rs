@@ -0,0 +1,61 @@
+use ndarray::{array, Array};
+
+use super::{Comparison, ComparisonKind, Forward};
+use crate::utils::new_shared;
+
+#[test]
+fn creation() {
+    let op = Comparison::new(
+        new_shared(array![1., 2., 3.]),
+        new_shared(array![2.]),
+        new_shared(Array::from_elem(3, false)),
+        ComparisonKind::Greater,
+    );
+
+    assert_eq!(*op.left_data.borrow(), array![1., 2., 3.]);
+    assert_eq!(*op.right_data.borrow(), array![2.]);
+    assert_eq!(*op.data.borrow(), Array::from_elem(3, false));
+    assert_eq!(op.kind, ComparisonKind::Greater);
+}
+
+#[test]
+fn base_case() {
+    let left = new_shared(array![1., 2., 3.]);
+    let right = new_shared(array![2., 2., 2.]);
+    let cases = [
+        (ComparisonKind::Greater, array![false, false, true]),
+        (ComparisonKind::GreaterEqual, array![false, true, true]),
+        (ComparisonKind::Less, array![true, false, false]),
+        (ComparisonKind::LessEqual, array![true, true, false]),
+        (ComparisonKind::Equal, array![false, true, false]),
+        (ComparisonKind::NotEqual, array![true, false, true]),
+    ];
+
+    for (kind, expected) in cases {
+        let op = Comparison::new(
+            left.clone(),
+            right.clone(),
+            new_shared(Array::from_elem(3, false)),
+            kind,
+        );
+
+        op.forward();
+        assert_eq!(*op.data.borrow(), expected);
+    }
+}
+
+#[test]
+fn broadcast() {
+    let op = Comparison::new(
+        new_shared(array![[1_i64], [2], [3]]),
+        new_shared(array![1_i64, 2, 3]),
+        new_shared(Array::from_elem((3, 3), false)),
+        ComparisonKind::Equal,
+    );
+
+    op.forward();
+    assert_eq!(
+        *op.data.borrow(),
+        Array::from_shape_fn((3, 3), |(i, j)| i == j)
+    );
+}
@@ -1,31 +1,34 @@
-use std::rc::Rc;
-
 use ndarray::{Array, Axis, Dimension, RemoveAxis, Zip};
 
 use crate::{
-    autograd::{Backward, Forward},
+    autograd::{Backward, Forward, Reads},
+    cell::Rc,
     gradient::Gradient,
+    graph::Buffer,
     utils::Shared,
+    Float,
 };
 
-pub(crate) struct Concatenate<D>
+pub(crate) struct Concatenate<D, T>
 where
     D: Dimension + RemoveAxis,
+    T: Float,
 {
-    left: Shared<Array<f32, D>>,
-    right: Shared<Array<f32, D>>,
-    data: Shared<Array<f32, D>>,
+    left: Shared<Array<T, D>>,
+    right: Shared<Array<T, D>>,
+    data: Shared<Array<T, D>>,
     axis: Axis,
 }
 
-impl<D> Concatenate<D>
+impl<D, T> Concatenate<D, T>
 where
     D: Dimension + RemoveAxis,
+    T: Float,
 {
     pub(crate) fn new(
-        left: Shared<Array<f32, D>>,
-        right: Shared<Array<f32, D>>,
-        data: Shared<Array<f32, D>>,
+        left: Shared<Array<T, D>>,
+        right: Shared<Array<T, D>>,
+        data: Shared<Array<T, D>>,
         axis: usize,
     ) -> Self {
         Self {
@@ -37,9 +40,10 @@ where
     }
 }
 
-impl<D> Forward for Concatenate<D>
+impl<D, T> Forward for Concatenate<D, T>
 where
     D: Dimension + RemoveAxis,
+    T: Float,
 {
     fn forward(&self) {
         let lhs_data = self.left.borrow();
@@ -56,24 +60,38 @@ where
             .and(&*self.right.borrow())
             .for_each(|fused_el, &single_el| *fused_el = single_el);
     }
+
+    fn name(&self) -> &'static str {
+        "Concatenate"
+    }
+
+    fn operands(&self) -> Vec<Buffer> {
+        vec![Buffer::new(&self.left), Buffer::new(&self.right)]
+    }
+
+    fn data(&self) -> Buffer {
+        Buffer::new(&self.data)
+    }
 }
 
-pub(crate) struct ConcatenateBackwardLeft<D>
+pub(crate) struct ConcatenateBackwardLeft<D, T>
 where
     D: Dimension + RemoveAxis,
+    T: Float,
 {
-    operand_gradient: Rc<Gradient<Array<f32, D>, D>>,
-    gradient: Rc<Gradient<Array<f32, D>, D>>,
+    operand_gradient: Rc<Gradient<Array<T, D>, D>>,
+    gradient: Rc<Gradient<Array<T, D>, D>>,
     axis: Axis,
 }
 
-impl<D> ConcatenateBackwardLeft<D>
+impl<D, T> ConcatenateBackwardLeft<D, T>
 where
     D: Dimension + RemoveAxis,
+    T: Float,
 {
     pub(crate) fn new(
-        operand_gradient: Rc<Gradient<Array<f32, D>, D>>,
-        gradient: Rc<Gradient<Array<f32, D>, D>>,
+        operand_gradient: Rc<Gradient<Array<T, D>, D>>,
+        gradient: Rc<Gradient<Array<T, D>, D>>,
         axis: usize,
     ) -> Self {
         Self {
@@ -84,9 +102,10 @@ where
     }
 }
 
-impl<D> Backward for ConcatenateBackwardLeft<D>
+impl<D, T> Backward for ConcatenateBackwardLeft<D, T>
 where
     D: Dimension + RemoveAxis,
+    T: Float,
 {
     fn backward(&self) {
         let mut operand_gradient = self.operand_gradient.borrow_mut();
@@ -97,25 +116,31 @@ where
 
         *operand_gradient += &operand_gradient_slice;
     }
+
+    fn reads(&self) -> Reads {
+        Reads::Nothing
+    }
 }
 
-pub(crate) struct ConcatenateBackwardRight<D>
+pub(crate) struct ConcatenateBackwardRight<D, T>
 where
     D: Dimension,
+    T: Float,
 {
-    operand_gradient: Rc<Gradient<Array<f32, D>, D>>,
-    gradient: Rc<Gradient<Array<f32, D>, D>>,
+    operand_gradient: Rc<Gradient<Array<T, D>, D>>,
+    gradient: Rc<Gradient<Array<T, D>, D>>,
     axis: Axis,
     offset: usize,
 }
 
-impl<D> ConcatenateBackwardRight<D>
+impl<D, T> ConcatenateBackwardRight<D, T>
 where
     D: Dimension + RemoveAxis,
+    T: Float,
 {
     pub(crate) fn new(
-        operand_gradient: Rc<Gradient<Array<f32, D>, D>>,
-        gradient: Rc<Gradient<Array<f32, D>, D>>,
+        operand_gradient: Rc<Gradient<Array<T, D>, D>>,
+        gradient: Rc<Gradient<Array<T, D>, D>>,
         axis: usize,
         offset: usize,
     ) -> Self {
@@ -128,9 +153,10 @@ where
     }
 }
 
-impl<D> Backward for ConcatenateBackwardRight<D>
+impl<D, T> Backward for ConcatenateBackwardRight<D, T>
 where
     D: Dimension + RemoveAxis,
+    T: Float,
 {
     fn backward(&self) {
         let gradient = self.gradient.borrow();
@@ -138,36 +164,47 @@ where
 
         *self.operand_gradient.borrow_mut() += &operand_gradient_slice;
     }
+
+    fn reads(&self) -> Reads {
+        Reads::Nothing
+    }
 }
 
-pub(crate) struct ConcatenateBackward<D>
+pub(crate) struct ConcatenateBackward<D, T>
 where
     D: Dimension + RemoveAxis,
+    T: Float,
 {
-    left: ConcatenateBackwardLeft<D>,
-    right: ConcatenateBackwardRight<D>,
+    left: ConcatenateBackwardLeft<D, T>,
+    right: ConcatenateBackwardRight<D, T>,
 }
 
-impl<D> ConcatenateBackward<D>
+impl<D, T> ConcatenateBackward<D, T>
 where
     D: Dimension + RemoveAxis,
+    T: Float,
 {
     pub(crate) fn new(
-        left: ConcatenateBackwardLeft<D>,
-        right: ConcatenateBackwardRight<D>,
+        left: ConcatenateBackwardLeft<D, T>,
+        right: ConcatenateBackwardRight<D, T>,
     ) -> Self {
         Self { left, right }
     }
 }
 
-impl<D> Backward for ConcatenateBackward<D>
+impl<D, T> Backward for ConcatenateBackward<D, T>
 where
     D: Dimension + RemoveAxis,
+    T: Float,
 {
     fn backward(&self) {
         self.left.backward();
         self.right.backward();
     }
+
+    fn reads(&self) -> Reads {
+        Reads::Nothing
+    }
 }
 
 #[cfg(test)]
@@ -2,6 +2,7 @@ use std::error::Error;
 
 use ndarray::{Array, Axis};
 
+use crate::cell::Rc;
 use crate::utils::{are_similar, new_shared};
 
 mod forward {
@@ -61,7 +62,6 @@ mod forward {
 
 #[cfg(test)]
 mod backward {
-    use std::rc::Rc;
 
     use super::super::{
         Backward, ConcatenateBackward, ConcatenateBackwardLeft, ConcatenateBackwardRight, Gradient,
@@ -1,5 +1,3 @@
-use std::rc::Rc;
-
 use ndarray::{
     iter::{AxisChunksIter, AxisChunksIterMut},
     linalg::general_mat_mul,
@@ -9,32 +7,36 @@ use ndarray::{
 use rayon::iter::{IndexedParallelIterator, IntoParallelIterator, ParallelIterator};
 
 use crate::{
-    autograd::{Backward, Forward},
+    autograd::{Backward, Forward, Reads},
+    cell::Rc,
     gradient::Gradient,
+    graph::Buffer,
     utils::{as_windows, as_windows_mut, columns_shape, Shared},
+    Float,
 };
 
 /// Iterators needed for the **backward pass** of a grouped convolution.
-type GroupedBackwardArgs<'a, D> = (
-    AxisChunksIterMut<'a, f32, D>,
-    AxisChunksIter<'a, f32, D>,
-    AxisChunksIter<'a, f32, D>,
+type GroupedBackwardArgs<'a, D, T> = (
+    AxisChunksIterMut<'a, T, D>,
+    AxisChunksIter<'a, T, D>,
+    AxisChunksIter<'a, T, D>,
 );
 
 /// Partitions the **flattened input**, the **flattened kernel** and the **output map**
 /// so that they can be used in a grouped convolution.
-fn group_inputs<'a, D>(
-    input: &'a Array<f32, D>,
-    kernel: &'a Array<f32, D>,
-    output: &'a mut Array<f32, D>,
+fn group_inputs<'a, D, T>(
+    input: &'a Array<T, D>,
+    kernel: &'a Array<T, D>,
+    output: &'a mut Array<T, D>,
     groups: usize,
 ) -> (
-    AxisChunksIter<'a, f32, D>,
-    AxisChunksIter<'a, f32, D>,
-    AxisChunksIterMut<'a, f32, D>,
+    AxisChunksIter<'a, T, D>,
+    AxisChunksIter<'a, T, D>,
+    AxisChunksIterMut<'a, T, D>,
 )
 where
     D: Dimension,
+    T: Float,
 {
     // Splits the input map along the channels.
     let input_groups = input.axis_chunks_iter(Axis(1), input.len_of(Axis(1)) / groups);
@@ -60,9 +62,9 @@ where
 /// Assigns to the **n**-dimensional feature map's gradient `dest` the **2**-dimensional
 /// array `columns`. This method encapsulates the functionalities of **col2sig**, **col2im** and
 /// **col2vol**.
-fn assign_from_cols<D: Dimension, S: DataMut<Elem = f32>, T: Data<Elem = f32>>(
+fn assign_from_cols<D: Dimension, S: DataMut<Elem = T>, V: Data<Elem = T>, T: Float>(
     dest: &mut ArrayBase<S, D>,
-    columns: ArrayBase<T, Ix3>,
+    columns: ArrayBase<V, Ix3>,
     kernel_shape: &[usize],
     stride: &[usize],
     dilation: &[usize],
@@ -84,13 +86,14 @@ fn assign_from_cols<D: Dimension, S: DataMut<Elem = f32>, T: Data<Elem = f32>>(
 
 fn convolution<
     D: Dimension + RemoveAxis,
-    S: Data<Elem = f32>,
-    U: Data<Elem = f32>,
-    T: DataMut<Elem = f32>,
+    S: Data<Elem = T>,
+    U: Data<Elem = T>,
+    V: DataMut<Elem = T>,
+    T: Float,
 >(
     input: &ArrayBase<S, D>,
     kernel: &ArrayBase<U, D>,
-    output: &mut ArrayBase<T, D>,
+    output: &mut ArrayBase<V, D>,
     stride: &[usize],
     dilation: &[usize],
 ) {
@@ -113,24 +116,25 @@ fn convolution<
             let flat_shape = flat_shape(output_sample.raw_dim());
             let mut flattened_sample_out_view_mut = output_sample.into_shape(flat_shape).unwrap();
             general_mat_mul(
-                1.,
+                T::one(),
                 &flattened_kernel,
                 &input_sample_columns.t(),
-                0.,
+                T::zero(),
                 &mut flattened_sample_out_view_mut,
             );
         });
 }
 
-fn grouped_convolution<D>(
-    input: &Array<f32, D>,
-    kernel: &Array<f32, D>,
-    output: &mut Array<f32, D>,
+fn grouped_convolution<D, T>(
+    input: &Array<T, D>,
+    kernel: &Array<T, D>,
+    output: &mut Array<T, D>,
     stride: &[usize],
     dilation: &[usize],
     groups: usize,
 ) where
     D: Dimension + RemoveAxis,
+    T: Float,
 {
     let (input_groups, kernel_groups, output_buffer_groups) =
         group_inputs(input, kernel, output, groups);
@@ -145,12 +149,13 @@ fn grouped_convolution<D>(
 
 fn convolution_backward_input<
     D: Dimension + RemoveAxis,
-    S: DataMut<Elem = f32>,
-    T: Data<Elem = f32>,
+    S: DataMut<Elem = T>,
+    V: Data<Elem = T>,
+    T: Float,
 >(
     input_grad: &mut ArrayBase<S, D>,
-    grad: &ArrayBase<T, D>,
-    kernel: &ArrayBase<T, D>,
+    grad: &ArrayBase<V, D>,
+    kernel: &ArrayBase<V, D>,
     stride: &[usize],
     dilation: &[usize],
 ) {
@@ -167,7 +172,7 @@ fn convolution_backward_input<
     buffer_shape[0] = grad_shape[0];
     buffer_shape[1] = flattened_kernel.shape()[1];
     buffer_shape[2] = grad_shape.iter().skip(2).product();
-    let mut buffer = Array::<f32, Ix3>::zeros(buffer_shape);
+    let mut buffer = Array::<T, Ix3>::zeros(buffer_shape);
 
     Zip::from(grad.axis_iter(Axis(0)))
         .and(buffer.axis_iter_mut(Axis(0)))
@@ -177,10 +182,10 @@ fn convolution_backward_input<
                 .into_shape(gradient_sample_flat_shape)
                 .unwrap();
             general_mat_mul(
-                1.,
+                T::one(),
                 &flattened_kernel.t(),
                 &flattened_sample_in,
-                0.,
+                T::zero(),
                 &mut buffer_sample,
             );
         });
@@ -190,12 +195,13 @@ fn convolution_backward_input<
 
 fn convolution_backward_kernel<
     D: Dimension + RemoveAxis,
-    S: DataMut<Elem = f32>,
-    T: Data<Elem = f32>,
+    S: DataMut<Elem = T>,
+    V: Data<Elem = T>,
+    T: Float,
 >(
     kernel_grad: &mut ArrayBase<S, D>,
-    grad: &ArrayBase<T, D>,
-    input: &ArrayBase<T, D>,
+    grad: &ArrayBase<V, D>,
+    input: &ArrayBase<V, D>,
     stride: &[usize],
     dilation: &[usize],
 ) {
@@ -214,10 +220,10 @@ fn convolution_backward_kernel<
             let grad_view_numel = grad_view.shape().iter().product::<usize>();
 
             general_mat_mul(
-                1.0,
+                T::one(),
                 &grad_view.to_shape((1, grad_view_numel)).unwrap(),
                 &input_matrix,
-                1.0,
+                T::one(),
                 &mut kernel_grad_view_mut
                     .into_shape((1, kernel_grad_numel))
                     .unwrap(),
@@ -225,12 +231,12 @@ fn convolution_backward_kernel<
         });
 }
 
-fn group_gradients_input<'a, D: Dimension, S: DataMut<Elem = f32>, U: Data<Elem = f32>>(
+fn group_gradients_input<'a, D: Dimension, S: DataMut<Elem = T>, U: Data<Elem = T>, T: Float>(
     input_grad: &'a mut ArrayBase<S, D>,
     grad: &'a ArrayBase<U, D>,
     kernel: &'a ArrayBase<U, D>,
     groups: usize,
-) -> GroupedBackwardArgs<'a, D> {
+) -> GroupedBackwardArgs<'a, D, T> {
     let input_grad_groups =
         input_grad.axis_chunks_iter_mut(Axis(1), input_grad.len_of(Axis(1)) / groups);
     let grad_groups = grad.axis_chunks_iter(Axis(1), grad.len_of(Axis(1)) / groups);
@@ -239,12 +245,12 @@ fn group_gradients_input<'a, D: Dimension, S: DataMut<Elem = f32>, U: Data<Elem
     (input_grad_groups, grad_groups, kernel_groups)
 }
 
-fn group_gradients_kernel<'a, D: Dimension, S: DataMut<Elem = f32>, U: Data<Elem = f32>>(
+fn group_gradients_kernel<'a, D: Dimension, S: DataMut<Elem = T>, U: Data<Elem = T>, T: Float>(
     kernel_grad: &'a mut ArrayBase<S, D>,
     grad: &'a ArrayBase<U, D>,
     input: &'a ArrayBase<U, D>,
     groups: usize,
-) -> GroupedBackwardArgs<'a, D> {
+) -> GroupedBackwardArgs<'a, D, T> {
     let kernel_grad_groups =
         kernel_grad.axis_chunks_iter_mut(Axis(0), kernel_grad.len_of(Axis(0)) / groups);
     let grad_groups = grad.axis_chunks_iter(Axis(1), grad.len_of(Axis(1)) / groups);
@@ -253,10 +259,10 @@ fn group_gradients_kernel<'a, D: Dimension, S: DataMut<Elem = f32>, U: Data<Elem
     (kernel_grad_groups, grad_groups, input_groups)
 }
 
-pub(super) fn grouped_convolution_backward_input<D: Dimension + RemoveAxis>(
-    input_grad: &mut Array<f32, D>,
-    grad: &Array<f32, D>,
-    kernel: &Array<f32, D>,
+pub(super) fn grouped_convolution_backward_input<D: Dimension + RemoveAxis, T: Float>(
+    input_grad: &mut Array<T, D>,
+    grad: &Array<T, D>,
+    kernel: &Array<T, D>,
     stride: &[usize],
     dilation: &[usize],
     groups: usize,
@@ -273,10 +279,10 @@ pub(super) fn grouped_convolution_backward_input<D: Dimension + RemoveAxis>(
         });
 }
 
-fn grouped_convolution_backward_kernel<D: Dimension + RemoveAxis>(
-    kernel_grad: &mut Array<f32, D>,
-    grad: &Array<f32, D>,
-    input: &Array<f32, D>,
+fn grouped_convolution_backward_kernel<D: Dimension + RemoveAxis, T: Float>(
+    kernel_grad: &mut Array<T, D>,
+    grad: &Array<T, D>,
+    input: &Array<T, D>,
     stride: &[usize],
     dilation: &[usize],
     groups: usize,
@@ -293,29 +299,31 @@ fn grouped_convolution_backward_kernel<D: Dimension + RemoveAxis>(
         });
 }
 
-pub(crate) struct Convolution<D>
+pub(crate) struct Convolution<D, T>
 where
     D: Dimension + RemoveAxis,
+    T: Float,
 {
-    input_data: Shared<Array<f32, D>>,
-    kernel_data: Shared<Array<f32, D>>,
+    input_data: Shared<Array<T, D>>,
+    kernel_data: Shared<Array<T, D>>,
     stride: <D::Smaller as Dimension>::Smaller,
     dilation: <D::Smaller as Dimension>::Smaller,
     groups: usize,
-    data: Shared<Array<f32, D>>,
+    data: Shared<Array<T, D>>,
 }
 
-impl<D> Convolution<D>
+impl<D, T> Convolution<D, T>
 where
     D: Dimension + RemoveAxis,
+    T: Float,
 {
     pub(crate) fn new(
-        input_data: Shared<Array<f32, D>>,
-        kernel_data: Shared<Array<f32, D>>,
+        input_data: Shared<Array<T, D>>,
+        kernel_data: Shared<Array<T, D>>,
         stride: <D::Smaller as Dimension>::Smaller,
         dilation: <D::Smaller as Dimension>::Smaller,
         groups: usize,
-        data: Shared<Array<f32, D>>,
+        data: Shared<Array<T, D>>,
     ) -> Self {
         Self {
             input_data,
@@ -328,9 +336,10 @@ where
     }
 }
 
-impl<D> Forward for Convolution<D>
+impl<D, T> Forward for Convolution<D, T>
 where
     D: Dimension + RemoveAxis,
+    T: Float,
 {
     fn forward(&self) {
         if self.groups < 2 {
@@ -352,23 +361,40 @@ where
             )
         }
     }
+
+    fn name(&self) -> &'static str {
+        "Convolution"
+    }
+
+    fn operands(&self) -> Vec<Buffer> {
+        vec![
+            Buffer::new(&self.input_data),
+            Buffer::new(&self.kernel_data),
+        ]
+    }
+
+    fn data(&self) -> Buffer {
+        Buffer::new(&self.data)
+    }
 }
 
-pub(crate) struct ConvolutionBackward<D>
+pub(crate) struct ConvolutionBackward<D, T>
 where
     D: Dimension + RemoveAxis,
+    T: Float,
 {
-    backward_input: ConvolutionBackwardInput<D>,
-    backward_kernel: ConvolutionBackwardKernel<D>,
+    backward_input: ConvolutionBackwardInput<D, T>,
+    backward_kernel: ConvolutionBackwardKernel<D, T>,
 }
 
-impl<D> ConvolutionBackward<D>
+impl<D, T> ConvolutionBackward<D, T>
 where
     D: Dimension + RemoveAxis,
+    T: Float,
 {
     pub(crate) fn new(
-        backward_input: ConvolutionBackwardInput<D>,
-        backward_kernel: ConvolutionBackwardKernel<D>,
+        backward_input: ConvolutionBackwardInput<D, T>,
+        backward_kernel: ConvolutionBackwardKernel<D, T>,
     ) -> Self {
         Self {
             backward_input,
@@ -377,36 +403,43 @@ where
     }
 }
 
-impl<D> Backward for ConvolutionBackward<D>
+impl<D, T> Backward for ConvolutionBackward<D, T>
 where
     D: Dimension + RemoveAxis,
+    T: Float,
 {
     fn backward(&self) {
         self.backward_input.backward();
         self.backward_kernel.backward();
     }
+
+    fn reads(&self) -> Reads {
+        Reads::Operands
+    }
 }
 
-pub(crate) struct ConvolutionBackwardInput<D>
+pub(crate) struct ConvolutionBackwardInput<D, T>
 where
     D: Dimension + RemoveAxis,
+    T: Float,
 {
-    kernel_data: Shared<Array<f32, D>>,
-    input_gradient: Rc<Gradient<Array<f32, D>, D>>,
-    gradient: Rc<Gradient<Array<f32, D>, D>>,
+    kernel_data: Shared<Array<T, D>>,
+    input_gradient: Rc<Gradient<Array<T, D>, D>>,
+    gradient: Rc<Gradient<Array<T, D>, D>>,
     stride: <D::Smaller as Dimension>::Smaller,
     dilation: <D::Smaller as Dimension>::Smaller,
     groups: usize,
 }
 
-impl<D> ConvolutionBackwardInput<D>
+impl<D, T> ConvolutionBackwardInput<D, T>
 where
     D: Dimension + RemoveAxis,
+    T: Float,
 {
     pub(crate) fn new(
-        kernel_data: Shared<Array<f32, D>>,
-        input_gradient: Rc<Gradient<Array<f32, D>, D>>,
-        gradient: Rc<Gradient<Array<f32, D>, D>>,
+        kernel_data: Shared<Array<T, D>>,
+        input_gradient: Rc<Gradient<Array<T, D>, D>>,
+        gradient: Rc<Gradient<Array<T, D>, D>>,
         stride: <D::Smaller as Dimension>::Smaller,
         dilation: <D::Smaller as Dimension>::Smaller,
         groups: usize,
@@ -422,9 +455,10 @@ where
     }
 }
 
-impl<D> Backward for ConvolutionBackwardInput<D>
+impl<D, T> Backward for ConvolutionBackwardInput<D, T>
 where
     D: Dimension + RemoveAxis,
+    T: Float,
 {
     fn backward(&self) {
         if self.groups < 2 {
@@ -446,28 +480,34 @@ where
             )
         }
     }
+
+    fn reads(&self) -> Reads {
+        Reads::Operands
+    }
 }
 
-pub(crate) struct ConvolutionBackwardKernel<D>
+pub(crate) struct ConvolutionBackwardKernel<D, T>
 where
     D: Dimension + RemoveAxis,
+    T: Float,
 {
-    input_data: Shared<Array<f32, D>>,
-    kernel_gradient: Rc<Gradient<Array<f32, D>, D>>,
-    gradient: Rc<Gradient<Array<f32, D>, D>>,
+    input_data: Shared<Array<T, D>>,
+    kernel_gradient: Rc<Gradient<Array<T, D>, D>>,
+    gradient: Rc<Gradient<Array<T, D>, D>>,
     stride: <D::Smaller as Dimension>::Smaller,
     dilation: <D::Smaller as Dimension>::Smaller,
     groups: usize,
 }
 
-impl<D> ConvolutionBackwardKernel<D>
+impl<D, T> ConvolutionBackwardKernel<D, T>
 where
     D: Dimension + RemoveAxis,
+    T: Float,
 {
     pub(crate) fn new(
-        input_data: Shared<Array<f32, D>>,
-        kernel_gradient: Rc<Gradient<Array<f32, D>, D>>,
-        gradient: Rc<Gradient<Array<f32, D>, D>>,
+        input_data: Shared<Array<T, D>>,
+        kernel_gradient: Rc<Gradient<Array<T, D>, D>>,
+        gradient: Rc<Gradient<Array<T, D>, D>>,
         stride: <D::Smaller as Dimension>::Smaller,
         dilation: <D::Smaller as Dimension>::Smaller,
         groups: usize,
@@ -483,9 +523,10 @@ where
     }
 }
 
-impl<D> Backward for ConvolutionBackwardKernel<D>
+impl<D, T> Backward for ConvolutionBackwardKernel<D, T>
 where
     D: Dimension + RemoveAxis,
+    T: Float,
 {
     fn backward(&self) {
         if self.groups < 2 {
@@ -507,6 +548,10 @@ where
             )
         }
     }
+
+    fn reads(&self) -> Reads {
+        Reads::Operands
+    }
 }
 
 #[cfg(test)]
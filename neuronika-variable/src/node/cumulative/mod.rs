@@ -0,0 +1,253 @@
+use ndarray::{Array, ArrayView1, ArrayViewMut1, Axis, Dimension, Zip};
+
+use crate::{
+    autograd::{Backward, Forward},
+    cell::Rc,
+    gradient::Gradient,
+    graph::Buffer,
+    utils::Shared,
+    Float,
+};
+
+/// Operation accumulated along an axis.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Scan {
+    Sum,
+    Prod,
+    Max,
+    LogSumExp,
+}
+
+impl Scan {
+    /// Scans `lane` into `result`.
+    fn forward<T>(self, lane: ArrayView1<T>, mut result: ArrayViewMut1<T>)
+    where
+        T: Float,
+    {
+        let mut acc = match self {
+            Self::Sum => T::zero(),
+            Self::Prod => T::one(),
+            Self::Max | Self::LogSumExp => T::neg_infinity(),
+        };
+
+        for (&el, result_el) in lane.iter().zip(result.iter_mut()) {
+            acc = match self {
+                Self::Sum => acc + el,
+                Self::Prod => acc * el,
+                Self::Max => acc.max(el),
+                Self::LogSumExp => log_add_exp(acc, el),
+            };
+            *result_el = acc;
+        }
+    }
+
+    /// Accumulates into `operand_gradient` the gradient of the scan `data` of `lane`.
+    fn backward<T>(
+        self,
+        lane: ArrayView1<T>,
+        data: ArrayView1<T>,
+        gradient: ArrayView1<T>,
+        mut operand_gradient: ArrayViewMut1<T>,
+    ) where
+        T: Float,
+    {
+        let n = lane.len();
+
+        match self {
+            Self::Sum => {
+                let mut acc = T::zero();
+                for i in (0..n).rev() {
+                    acc += gradient[i];
+                    operand_gradient[i] += acc;
+                }
+            }
+            Self::Prod => {
+                // Past the first zero all the products vanish, so do their derivatives with
+                // respect to the elements following it.
+                let zero = lane.iter().position(|&el| el == T::zero()).unwrap_or(n);
+
+                let mut acc = T::zero();
+                for i in (0..n).rev() {
+                    acc += gradient[i] * data[i];
+                    if i < zero {
+                        operand_gradient[i] += acc / lane[i];
+                    }
+                }
+
+                if zero < n {
+                    let mut product = if zero > 0 { data[zero - 1] } else { T::one() };
+                    let mut acc = T::zero();
+                    for i in zero..n {
+                        if i > zero {
+                            product *= lane[i];
+                        }
+                        acc += gradient[i] * product;
+                    }
+                    operand_gradient[zero] += acc;
+                }
+            }
+            Self::Max => {
+                let mut position = 0;
+                for i in 0..n {
+                    if lane[i] >= lane[position] {
+                        position = i;
+                    }
+                    operand_gradient[position] += gradient[i];
+                }
+            }
+            Self::LogSumExp => {
+                // Positive and negative gradients are accumulated separately in log-space.
+                let (mut positive, mut negative) = (T::neg_infinity(), T::neg_infinity());
+                for i in (0..n).rev() {
+                    let log = gradient[i].abs().ln() - data[i];
+                    if gradient[i] > T::zero() {
+                        positive = log_add_exp(positive, log);
+                    } else if gradient[i] < T::zero() {
+                        negative = log_add_exp(negative, log);
+                    }
+                    operand_gradient[i] += (lane[i] + positive).exp() - (lane[i] + negative).exp();
+                }
+            }
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Sum => "Cumsum",
+            Self::Prod => "Cumprod",
+            Self::Max => "Cummax",
+            Self::LogSumExp => "Logcumsumexp",
+        }
+    }
+}
+
+/// Computes `ln(exp(x) + exp(y))` avoiding overflows.
+fn log_add_exp<T>(x: T, y: T) -> T
+where
+    T: Float,
+{
+    let max = x.max(y);
+    if max == T::neg_infinity() {
+        return max;
+    }
+
+    max + ((x - max).exp() + (y - max).exp()).ln()
+}
+
+pub(crate) struct Cumulative<D, T>
+where
+    D: Dimension,
+    T: Float,
+{
+    operand_data: Shared<Array<T, D>>,
+    data: Shared<Array<T, D>>,
+    axis: Axis,
+    scan: Scan,
+}
+
+impl<D, T> Cumulative<D, T>
+where
+    D: Dimension,
+    T: Float,
+{
+    pub(crate) fn new(
+        operand_data: Shared<Array<T, D>>,
+        data: Shared<Array<T, D>>,
+        axis: usize,
+        scan: Scan,
+    ) -> Self {
+        Self {
+            operand_data,
+            data,
+            axis: Axis(axis),
+            scan,
+        }
+    }
+}
+
+impl<D, T> Forward for Cumulative<D, T>
+where
+    D: Dimension,
+    T: Float,
+{
+    fn forward(&self) {
+        Zip::from(self.data.borrow_mut().lanes_mut(self.axis))
+            .and(self.operand_data.borrow().lanes(self.axis))
+            .for_each(|data_lane, operand_lane| self.scan.forward(operand_lane, data_lane));
+    }
+
+    fn name(&self) -> &'static str {
+        self.scan.name()
+    }
+
+    fn operands(&self) -> Vec<Buffer> {
+        vec![Buffer::new(&self.operand_data)]
+    }
+
+    fn data(&self) -> Buffer {
+        Buffer::new(&self.data)
+    }
+}
+
+pub(crate) struct CumulativeBackward<D, T>
+where
+    D: Dimension,
+    T: Float,
+{
+    operand_data: Shared<Array<T, D>>,
+    operand_gradient: Rc<Gradient<Array<T, D>, D>>,
+    data: Shared<Array<T, D>>,
+    gradient: Rc<Gradient<Array<T, D>, D>>,
+    axis: Axis,
+    scan: Scan,
+}
+
+impl<D, T> CumulativeBackward<D, T>
+where
+    D: Dimension,
+    T: Float,
+{
+    pub(crate) fn new(
+        operand_data: Shared<Array<T, D>>,
+        operand_gradient: Rc<Gradient<Array<T, D>, D>>,
+        data: Shared<Array<T, D>>,
+        gradient: Rc<Gradient<Array<T, D>, D>>,
+        axis: usize,
+        scan: Scan,
+    ) -> Self {
+        Self {
+            operand_data,
+            operand_gradient,
+            data,
+            gradient,
+            axis: Axis(axis),
+            scan,
+        }
+    }
+}
+
+impl<D, T> Backward for CumulativeBackward<D, T>
+where
+    D: Dimension,
+    T: Float,
+{
+    fn backward(&self) {
+        Zip::from(self.operand_gradient.borrow_mut().lanes_mut(self.axis))
+            .and(self.operand_data.borrow().lanes(self.axis))
+            .and(self.data.borrow().lanes(self.axis))
+            .and(self.gradient.borrow().lanes(self.axis))
+            .for_each(
+                |operand_gradient_lane, operand_lane, data_lane, gradient_lane| {
+                    self.scan.backward(
+                        operand_lane,
+                        data_lane,
+                        gradient_lane,
+                        operand_gradient_lane,
+                    )
+                },
+            );
+    }
+}
+
+#[cfg(test)]
+mod test;
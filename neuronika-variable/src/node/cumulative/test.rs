@@ -0,0 +1,148 @@
+use std::error::Error;
+
+use ndarray::{array, Array2};
+
+use crate::cell::Rc;
+use crate::utils::{are_similar, new_shared};
+
+mod forward {
+    use ndarray::{array, Axis};
+
+    #[test]
+    fn scans() {
+        let x = crate::from_ndarray(array![[1., -2., 3.], [0.5, 4., -1.]]);
+        let (sum, prod) = (x.clone().cumsum(1), x.clone().cumprod(0));
+        let (max, lse) = (x.clone().cummax(1), x.clone().logcumsumexp(1));
+        for y in [&sum, &prod, &max, &lse] {
+            y.forward();
+        }
+        assert_eq!(*sum.data(), array![[1., -1., 2.], [0.5, 4.5, 3.5]]);
+        assert_eq!(*prod.data(), array![[1., -2., 3.], [0.5, -8., -3.]]);
+        assert_eq!(*max.data(), array![[1., 1., 3.], [0.5, 4., 4.]]);
+        let mut expected = x.data().mapv(f32::exp);
+        expected.accumulate_axis_inplace(Axis(1), |&previous, el| *el += previous);
+        expected.mapv_inplace(f32::ln);
+        assert!(lse.data().abs_diff_eq(&expected, 1e-6));
+    }
+
+    #[test]
+    fn logcumsumexp_large() {
+        let y = crate::from_ndarray(array![1000_f32, 1000., -1000.]).logcumsumexp(0);
+        y.forward();
+        assert!(y
+            .data()
+            .abs_diff_eq(&array![1000., 1000. + 2_f32.ln(), 1000. + 2_f32.ln()], 1e-3));
+    }
+}
+
+mod backward {
+    use super::super::{Backward, CumulativeBackward, Scan};
+    use super::*;
+    use crate::gradient::Gradient;
+
+    /// Builds the backward op of the scan `data` of `operand` along `axis`.
+    fn new_op(
+        operand: Array2<f32>,
+        data: Array2<f32>,
+        gradient: Array2<f32>,
+        axis: usize,
+        scan: Scan,
+    ) -> CumulativeBackward<ndarray::Ix2, f32> {
+        CumulativeBackward::new(
+            new_shared(operand.clone()),
+            Rc::new(Gradient::ndarray_zeros(operand.raw_dim())),
+            new_shared(data),
+            Rc::new(Gradient::from_ndarray(gradient)),
+            axis,
+            scan,
+        )
+    }
+
+    #[test]
+    fn cumsum() -> Result<(), Box<dyn Error>> {
+        let op = new_op(
+            array![[1., -2., 3.]],
+            array![[1., -1., 2.]],
+            array![[1., 2., 3.]],
+            1,
+            Scan::Sum,
+        );
+
+        op.backward();
+        are_similar(op.operand_gradient.borrow(), &array![[6., 5., 3.]])?;
+
+        op.backward();
+        are_similar(op.operand_gradient.borrow(), &array![[12., 10., 6.]])
+    }
+
+    #[test]
+    fn cumprod() -> Result<(), Box<dyn Error>> {
+        let op = new_op(
+            array![[1.], [-2.], [3.]],
+            array![[1.], [-2.], [-6.]],
+            array![[1.], [2.], [3.]],
+            0,
+            Scan::Prod,
+        );
+
+        op.backward();
+        are_similar(op.operand_gradient.borrow(), &array![[-21.], [11.], [-6.]])
+    }
+
+    #[test]
+    fn cumprod_zeros() -> Result<(), Box<dyn Error>> {
+        // Past the first zero the products vanish, so does the gradient of the later elements.
+        let op = new_op(
+            array![[0., 2., 0., 3.]],
+            array![[0., 0., 0., 0.]],
+            array![[1., 1., 1., 1.]],
+            1,
+            Scan::Prod,
+        );
+
+        op.backward();
+        are_similar(op.operand_gradient.borrow(), &array![[3., 0., 0., 0.]])
+    }
+
+    #[test]
+    fn cummax() -> Result<(), Box<dyn Error>> {
+        let op = new_op(
+            array![[1., -2., 3.]],
+            array![[1., 1., 3.]],
+            array![[1., 2., 3.]],
+            1,
+            Scan::Max,
+        );
+
+        op.backward();
+        are_similar(op.operand_gradient.borrow(), &array![[3., 0., 3.]])
+    }
+
+    #[test]
+    fn logcumsumexp() -> Result<(), Box<dyn Error>> {
+        let op = new_op(
+            array![[0., 0., 0.]],
+            array![[0., 2_f32.ln(), 3_f32.ln()]],
+            array![[1., 2., 3.]],
+            1,
+            Scan::LogSumExp,
+        );
+
+        op.backward();
+        are_similar(op.operand_gradient.borrow(), &array![[3., 2., 1.]])
+    }
+
+    #[test]
+    fn logcumsumexp_negative_gradient() -> Result<(), Box<dyn Error>> {
+        let op = new_op(
+            array![[0., 0., 0.]],
+            array![[0., 2_f32.ln(), 3_f32.ln()]],
+            array![[1., -2., 3.]],
+            1,
+            Scan::LogSumExp,
+        );
+
+        op.backward();
+        are_similar(op.operand_gradient.borrow(), &array![[1., 0., 1.]])
+    }
+}
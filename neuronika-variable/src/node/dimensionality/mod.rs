@@ -0,0 +1,108 @@
+use ndarray::{Array, Dimension};
+
+use crate::{
+    autograd::{Backward, Forward, Reads},
+    cell::Rc,
+    gradient::Gradient,
+    graph::Buffer,
+    utils::Shared,
+    Element, Float,
+};
+
+pub(crate) struct IntoDimensionality<D, E, T>
+where
+    D: Dimension,
+    E: Dimension,
+    T: Element,
+{
+    operand_data: Shared<Array<T, D>>,
+    data: Shared<Array<T, E>>,
+}
+
+impl<D, E, T> IntoDimensionality<D, E, T>
+where
+    D: Dimension,
+    E: Dimension,
+    T: Element,
+{
+    pub(crate) fn new(operand_data: Shared<Array<T, D>>, data: Shared<Array<T, E>>) -> Self {
+        Self { operand_data, data }
+    }
+}
+
+impl<D, E, T> Forward for IntoDimensionality<D, E, T>
+where
+    D: Dimension,
+    E: Dimension,
+    T: Element,
+{
+    fn forward(&self) {
+        let mut data = self.data.borrow_mut();
+        let operand_data = self.operand_data.borrow();
+
+        data.view_mut()
+            .into_dyn()
+            .assign(&operand_data.view().into_dyn());
+    }
+
+    fn name(&self) -> &'static str {
+        "IntoDimensionality"
+    }
+
+    fn operands(&self) -> Vec<Buffer> {
+        vec![Buffer::new(&self.operand_data)]
+    }
+
+    fn data(&self) -> Buffer {
+        Buffer::new(&self.data)
+    }
+}
+
+pub(crate) struct IntoDimensionalityBackward<D, E, T>
+where
+    D: Dimension,
+    E: Dimension,
+    T: Float,
+{
+    operand_gradient: Rc<Gradient<Array<T, D>, D>>,
+    gradient: Rc<Gradient<Array<T, E>, E>>,
+}
+
+impl<D, E, T> IntoDimensionalityBackward<D, E, T>
+where
+    D: Dimension,
+    E: Dimension,
+    T: Float,
+{
+    pub(crate) fn new(
+        operand_gradient: Rc<Gradient<Array<T, D>, D>>,
+        gradient: Rc<Gradient<Array<T, E>, E>>,
+    ) -> Self {
+        Self {
+            operand_gradient,
+            gradient,
+        }
+    }
+}
+
+impl<D, E, T> Backward for IntoDimensionalityBackward<D, E, T>
+where
+    D: Dimension,
+    E: Dimension,
+    T: Float,
+{
+    fn backward(&self) {
+        let mut operand_gradient = self.operand_gradient.borrow_mut();
+        let gradient = self.gradient.borrow();
+
+        let mut operand_gradient = operand_gradient.view_mut().into_dyn();
+        operand_gradient += &gradient.view().into_dyn();
+    }
+
+    fn reads(&self) -> Reads {
+        Reads::Nothing
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,67 @@
+mod forward {
+    #[test]
+    #[should_panic(
+        expected = "error: variable of shape [2, 3] cannot be converted into one of 3 dimensions."
+    )]
+    fn mismatch() {
+        crate::ones(ndarray::IxDyn(&[2, 3])).into_dimensionality::<ndarray::Ix3>();
+    }
+}
+
+mod backward {
+    use crate::Convolution;
+    use ndarray::{Array, Ix3, IxDyn};
+
+    #[test]
+    fn matches_static() {
+        let x = Array::from_shape_fn((2, 2, 5), |(i, j, k)| (i + 2 * j + k) as f32 / 7. - 0.5);
+        let w = Array::from_shape_fn((3, 2, 2), |(i, j, k)| {
+            ((i + j + 2 * k) % 3) as f32 / 3. - 0.3
+        });
+        let target = Array::from_shape_fn((2, 12), |(i, j)| (i + j) % 3);
+
+        let (x, x_dyn) = (
+            crate::from_ndarray(x.clone()).requires_grad(),
+            crate::from_ndarray(x).requires_grad(),
+        );
+        let (w, w_dyn) = (
+            crate::from_ndarray(w.clone()).requires_grad(),
+            crate::from_ndarray(w.into_dyn()).requires_grad(),
+        );
+        let (target, target_dyn) = (
+            crate::from_ndarray(target.clone()),
+            crate::from_ndarray(target.into_dyn()),
+        );
+
+        let y = {
+            let input = x.clone().pad(1, crate::Reflective);
+            let h = Convolution::convolution(w.clone(), input, 1, 1, 1);
+            let h = h.clone().cat(&[h], 2);
+            let stacked = h.clone().unsqueeze(0).stack(&[h.clone().unsqueeze(0)], 1);
+
+            h.log_softmax(1).nll(target, crate::Reduction::Mean) + stacked.tanh().mean()
+        };
+        let y_dyn = {
+            let input = x_dyn.clone().into_dyn().pad(&[1][..], crate::Reflective);
+            let h = Convolution::convolution(w_dyn.clone(), input, &[1][..], &[1][..], 1);
+            let h = h.clone().cat(&[h], 2);
+            let stacked = h.clone().unsqueeze(0).stack(&[h.clone().unsqueeze(0)], 1);
+
+            let h = h.into_dimensionality::<Ix3>();
+            h.log_softmax(1)
+                .nll(target_dyn.into_dimensionality(), crate::Reduction::Mean)
+                + stacked.tanh().mean()
+        };
+
+        y.forward();
+        y.backward(1.);
+        y_dyn.forward();
+        y_dyn.backward(1.);
+
+        assert_eq!(y.item(), y_dyn.item());
+        assert_eq!(*x.grad(), *x_dyn.grad());
+        assert_eq!(w.grad().view().into_dyn(), *w_dyn.grad());
+        assert_ne!(*x.grad(), Array::zeros((2, 2, 5)));
+        assert_eq!(w_dyn.grad().raw_dim(), IxDyn(&[3, 2, 2]));
+    }
+}
@@ -1,32 +1,41 @@
-use std::rc::Rc;
+use std::any::Any;
 
 use ndarray::{Array, DimMax, Dimension, Zip};
 
 use crate::{
-    autograd::{Backward, Forward},
+    autograd::{Backward, Forward, Reads},
+    cell::Rc,
+    fusion::{
+        fusible_len, standard_len, BackwardKernel, ElementwiseBackward, ElementwiseForward,
+        ForwardKernel,
+    },
     gradient::{BufferedGradient, Gradient},
+    graph::Buffer,
     utils::{accumulate, Broadcast, Shared},
+    Float,
 };
 
-pub(crate) struct Division<D, E>
+pub(crate) struct Division<D, E, T>
 where
     D: Dimension + DimMax<E>,
     E: Dimension,
+    T: Float,
 {
-    left_data: Shared<Array<f32, D>>,
-    right_data: Shared<Array<f32, E>>,
-    data: Shared<Array<f32, Broadcast<D, E>>>,
+    left_data: Shared<Array<T, D>>,
+    right_data: Shared<Array<T, E>>,
+    data: Shared<Array<T, Broadcast<D, E>>>,
 }
 
-impl<D, E> Division<D, E>
+impl<D, E, T> Division<D, E, T>
 where
     D: Dimension + DimMax<E>,
     E: Dimension,
+    T: Float,
 {
     pub(crate) fn new(
-        left_data: Shared<Array<f32, D>>,
-        right_data: Shared<Array<f32, E>>,
-        data: Shared<Array<f32, Broadcast<D, E>>>,
+        left_data: Shared<Array<T, D>>,
+        right_data: Shared<Array<T, E>>,
+        data: Shared<Array<T, Broadcast<D, E>>>,
     ) -> Self {
         Self {
             left_data,
@@ -36,10 +45,11 @@ where
     }
 }
 
-impl<D, E> Forward for Division<D, E>
+impl<D, E, T> Forward for Division<D, E, T>
 where
-    D: Dimension + DimMax<E>,
-    E: Dimension,
+    D: 'static + Dimension + DimMax<E>,
+    E: 'static + Dimension,
+    T: Float,
 {
     fn forward(&self) {
         Zip::from(&mut *self.data.borrow_mut())
@@ -47,27 +57,72 @@ where
             .and_broadcast(&*self.right_data.borrow())
             .for_each(|v, &l, &r| *v = l / r);
     }
+
+    fn name(&self) -> &'static str {
+        "Division"
+    }
+
+    fn operands(&self) -> Vec<Buffer> {
+        vec![Buffer::new(&self.left_data), Buffer::new(&self.right_data)]
+    }
+
+    fn data(&self) -> Buffer {
+        Buffer::new(&self.data)
+    }
+
+    fn elementwise(&self) -> Option<&dyn ElementwiseForward> {
+        Some(self)
+    }
 }
 
-pub(crate) struct DivisionBackwardLeft<D, E>
+impl<D, E, T> ElementwiseForward for Division<D, E, T>
+where
+    D: 'static + Dimension + DimMax<E>,
+    E: 'static + Dimension,
+    T: Float,
+{
+    fn fusible_len(&self) -> Option<usize> {
+        fusible_len(
+            standard_len(&self.data.borrow()),
+            &[],
+            &[
+                standard_len(&self.left_data.borrow()),
+                standard_len(&self.right_data.borrow()),
+            ],
+        )
+    }
+
+    fn kernel(&self) -> Box<dyn Any> {
+        Box::new(ForwardKernel::binary(
+            &self.left_data,
+            &self.right_data,
+            &self.data,
+            |l, r| l / r,
+        ))
+    }
+}
+
+pub(crate) struct DivisionBackwardLeft<D, E, T>
 where
     D: Dimension + DimMax<E>,
     E: Dimension,
+    T: Float,
 {
-    right_data: Shared<Array<f32, E>>,
-    left_gradient: Rc<Gradient<Array<f32, D>, D>>,
-    gradient: Rc<BufferedGradient<Array<f32, Broadcast<D, E>>, Broadcast<D, E>>>,
+    right_data: Shared<Array<T, E>>,
+    left_gradient: Rc<Gradient<Array<T, D>, D>>,
+    gradient: Rc<BufferedGradient<Array<T, Broadcast<D, E>>, Broadcast<D, E>>>,
 }
 
-impl<D, E> DivisionBackwardLeft<D, E>
+impl<D, E, T> DivisionBackwardLeft<D, E, T>
 where
     D: Dimension + DimMax<E>,
     E: Dimension,
+    T: Float,
 {
     pub(crate) fn new(
-        right_data: Shared<Array<f32, E>>,
-        left_gradient: Rc<Gradient<Array<f32, D>, D>>,
-        gradient: Rc<BufferedGradient<Array<f32, Broadcast<D, E>>, Broadcast<D, E>>>,
+        right_data: Shared<Array<T, E>>,
+        left_gradient: Rc<Gradient<Array<T, D>, D>>,
+        gradient: Rc<BufferedGradient<Array<T, Broadcast<D, E>>, Broadcast<D, E>>>,
     ) -> Self {
         debug_assert!(left_gradient
             .borrow()
@@ -82,10 +137,11 @@ where
     }
 }
 
-impl<D, E> Backward for DivisionBackwardLeft<D, E>
+impl<D, E, T> Backward for DivisionBackwardLeft<D, E, T>
 where
-    D: Dimension + DimMax<E>,
-    E: Dimension,
+    D: 'static + Dimension + DimMax<E>,
+    E: 'static + Dimension,
+    T: Float,
 {
     fn backward(&self) {
         let mut buffer = self.gradient.buffer_mut();
@@ -96,29 +152,62 @@ where
 
         accumulate(&mut self.left_gradient.borrow_mut(), &buffer);
     }
+
+    fn elementwise(&self) -> Option<&dyn ElementwiseBackward> {
+        Some(self)
+    }
+
+    fn reads(&self) -> Reads {
+        Reads::Operands
+    }
+}
+
+impl<D, E, T> ElementwiseBackward for DivisionBackwardLeft<D, E, T>
+where
+    D: 'static + Dimension + DimMax<E>,
+    E: 'static + Dimension,
+    T: Float,
+{
+    fn fusible_len(&self) -> Option<usize> {
+        fusible_len(
+            standard_len(&self.gradient.borrow()),
+            &[standard_len(&self.left_gradient.borrow())],
+            &[standard_len(&self.right_data.borrow())],
+        )
+    }
+
+    fn kernel(&self) -> Box<dyn Any> {
+        Box::new(BackwardKernel::new(self.gradient.clone()).accumulate_with(
+            self.left_gradient.clone(),
+            &self.right_data,
+            |g, r| g / r,
+        ))
+    }
 }
 
-pub(crate) struct DivisionBackwardRight<D, E>
+pub(crate) struct DivisionBackwardRight<D, E, T>
 where
     D: Dimension + DimMax<E>,
     E: Dimension,
+    T: Float,
 {
-    left_data: Shared<Array<f32, D>>,
-    right_data: Shared<Array<f32, E>>,
-    right_gradient: Rc<Gradient<Array<f32, E>, E>>,
-    gradient: Rc<BufferedGradient<Array<f32, Broadcast<D, E>>, Broadcast<D, E>>>,
+    left_data: Shared<Array<T, D>>,
+    right_data: Shared<Array<T, E>>,
+    right_gradient: Rc<Gradient<Array<T, E>, E>>,
+    gradient: Rc<BufferedGradient<Array<T, Broadcast<D, E>>, Broadcast<D, E>>>,
 }
 
-impl<D, E> DivisionBackwardRight<D, E>
+impl<D, E, T> DivisionBackwardRight<D, E, T>
 where
     D: Dimension + DimMax<E>,
     E: Dimension,
+    T: Float,
 {
     pub(crate) fn new(
-        left_data: Shared<Array<f32, D>>,
-        right_data: Shared<Array<f32, E>>,
-        right_gradient: Rc<Gradient<Array<f32, E>, E>>,
-        gradient: Rc<BufferedGradient<Array<f32, Broadcast<D, E>>, Broadcast<D, E>>>,
+        left_data: Shared<Array<T, D>>,
+        right_data: Shared<Array<T, E>>,
+        right_gradient: Rc<Gradient<Array<T, E>, E>>,
+        gradient: Rc<BufferedGradient<Array<T, Broadcast<D, E>>, Broadcast<D, E>>>,
     ) -> Self {
         debug_assert_eq!(right_data.borrow().shape(), right_gradient.shape().slice());
 
@@ -131,10 +220,11 @@ where
     }
 }
 
-impl<D, E> Backward for DivisionBackwardRight<D, E>
+impl<D, E, T> Backward for DivisionBackwardRight<D, E, T>
 where
-    D: Dimension + DimMax<E>,
-    E: Dimension,
+    D: 'static + Dimension + DimMax<E>,
+    E: 'static + Dimension,
+    T: Float,
 {
     fn backward(&self) {
         let mut buffer = self.gradient.buffer_mut();
@@ -146,39 +236,108 @@ where
 
         accumulate(&mut self.right_gradient.borrow_mut(), &buffer);
     }
+
+    fn elementwise(&self) -> Option<&dyn ElementwiseBackward> {
+        Some(self)
+    }
+
+    fn reads(&self) -> Reads {
+        Reads::Operands
+    }
 }
 
-pub(crate) struct DivisionBackward<D, E>
+impl<D, E, T> ElementwiseBackward for DivisionBackwardRight<D, E, T>
+where
+    D: 'static + Dimension + DimMax<E>,
+    E: 'static + Dimension,
+    T: Float,
+{
+    fn fusible_len(&self) -> Option<usize> {
+        fusible_len(
+            standard_len(&self.gradient.borrow()),
+            &[standard_len(&self.right_gradient.borrow())],
+            &[
+                standard_len(&self.left_data.borrow()),
+                standard_len(&self.right_data.borrow()),
+            ],
+        )
+    }
+
+    fn kernel(&self) -> Box<dyn Any> {
+        Box::new(
+            BackwardKernel::new(self.gradient.clone()).accumulate_with_both(
+                self.right_gradient.clone(),
+                &self.left_data,
+                &self.right_data,
+                |g, l, r| -g * l / r.powi(2),
+            ),
+        )
+    }
+}
+
+pub(crate) struct DivisionBackward<D, E, T>
 where
     D: Dimension + DimMax<E>,
     E: Dimension,
+    T: Float,
 {
-    left: DivisionBackwardLeft<D, E>,
-    right: DivisionBackwardRight<D, E>,
+    left: DivisionBackwardLeft<D, E, T>,
+    right: DivisionBackwardRight<D, E, T>,
 }
 
-impl<D, E> DivisionBackward<D, E>
+impl<D, E, T> DivisionBackward<D, E, T>
 where
     D: Dimension + DimMax<E>,
     E: Dimension,
+    T: Float,
 {
     pub(crate) fn new(
-        left: DivisionBackwardLeft<D, E>,
-        right: DivisionBackwardRight<D, E>,
+        left: DivisionBackwardLeft<D, E, T>,
+        right: DivisionBackwardRight<D, E, T>,
     ) -> Self {
         Self { left, right }
     }
 }
 
-impl<D, E> Backward for DivisionBackward<D, E>
+impl<D, E, T> Backward for DivisionBackward<D, E, T>
 where
-    D: Dimension + DimMax<E>,
-    E: Dimension,
+    D: 'static + Dimension + DimMax<E>,
+    E: 'static + Dimension,
+    T: Float,
 {
     fn backward(&self) {
         self.left.backward();
         self.right.backward();
     }
+
+    fn elementwise(&self) -> Option<&dyn ElementwiseBackward> {
+        Some(self)
+    }
+
+    fn reads(&self) -> Reads {
+        Reads::Operands
+    }
+}
+
+impl<D, E, T> ElementwiseBackward for DivisionBackward<D, E, T>
+where
+    D: 'static + Dimension + DimMax<E>,
+    E: 'static + Dimension,
+    T: Float,
+{
+    fn fusible_len(&self) -> Option<usize> {
+        let len = self.left.fusible_len()?;
+
+        if self.right.fusible_len()? == len {
+            Some(len)
+        } else {
+            None
+        }
+    }
+
+    fn kernel(&self) -> Box<dyn Any> {
+        Box::new(BackwardKernel::<T>::of(&self.left).and(BackwardKernel::of(&self.right)))
+    }
 }
 
 #[cfg(test)]
@@ -1,7 +1,8 @@
-use std::{error::Error, rc::Rc};
+use std::error::Error;
 
 use ndarray::Array;
 
+use crate::cell::Rc;
 use crate::utils::{are_similar, new_shared};
 
 mod forward {
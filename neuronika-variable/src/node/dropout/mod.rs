@@ -1,38 +1,39 @@
-use std::{cell::Cell, rc::Rc};
-
 use ndarray::{Array, Dimension, Zip};
 
-use rand::thread_rng;
-
 use rand_distr::{Bernoulli, Distribution};
 
 use crate::{
-    autograd::{Backward, Forward},
+    autograd::{Backward, Forward, Reads},
+    cell::{Cell, Rc},
     gradient::Gradient,
+    graph::Buffer,
     utils::Shared,
+    with_generator, Float,
 };
 
-pub(crate) struct Dropout<D>
+pub(crate) struct Dropout<D, T>
 where
     D: Dimension,
+    T: Float,
 {
-    operand_data: Shared<Array<f32, D>>,
-    data: Shared<Array<f32, D>>,
-    noise: Shared<Array<f32, D>>,
+    operand_data: Shared<Array<T, D>>,
+    data: Shared<Array<T, D>>,
+    noise: Shared<Array<T, D>>,
     distr: Bernoulli,
     p: f64,
     status: Rc<Cell<bool>>,
 }
 
-impl<D> Dropout<D>
+impl<D, T> Dropout<D, T>
 where
     D: Dimension,
+    T: Float,
 {
     pub(crate) fn new(
-        operand_data: Shared<Array<f32, D>>,
-        data: Shared<Array<f32, D>>,
+        operand_data: Shared<Array<T, D>>,
+        data: Shared<Array<T, D>>,
         p: f64,
-        noise: Shared<Array<f32, D>>,
+        noise: Shared<Array<T, D>>,
         status: Rc<Cell<bool>>,
     ) -> Self {
         if !(0. ..=1.).contains(&p) {
@@ -50,9 +51,10 @@ where
     }
 }
 
-impl<D> Forward for Dropout<D>
+impl<D, T> Forward for Dropout<D, T>
 where
     D: Dimension,
+    T: Float,
 {
     fn forward(&self) {
         if !self.status.get() || self.p == 0.0 {
@@ -61,43 +63,64 @@ where
         }
 
         if (1. - self.p) == 0.0 {
-            Zip::from(&mut *self.data.borrow_mut()).for_each(|data_el| *data_el = 0.);
+            Zip::from(&mut *self.data.borrow_mut()).for_each(|data_el| *data_el = T::zero());
             return;
         }
 
         let mut noise = self.noise.borrow_mut();
-        Zip::from(&mut *noise)
-            .for_each(|noise_el| *noise_el = self.distr.sample(&mut thread_rng()) as i32 as f32);
+        with_generator(|generator| {
+            Zip::from(&mut *noise).for_each(|noise_el| {
+                *noise_el = if self.distr.sample(generator) {
+                    T::one()
+                } else {
+                    T::zero()
+                }
+            })
+        });
         // Remember: keep these zips separate
         Zip::from(&mut *self.data.borrow_mut())
             .and(&*self.operand_data.borrow())
             .and(&*noise)
             .for_each(|data_el, &operand_data_el, &noise_el| {
-                *data_el = (operand_data_el * noise_el) / (1. - self.p as f32)
+                *data_el = (operand_data_el * noise_el) / T::from_f64(1. - self.p)
             });
     }
+
+    fn name(&self) -> &'static str {
+        "Dropout"
+    }
+
+    fn operands(&self) -> Vec<Buffer> {
+        vec![Buffer::new(&self.operand_data)]
+    }
+
+    fn data(&self) -> Buffer {
+        Buffer::new(&self.data)
+    }
 }
 
-pub(crate) struct DropoutBackward<D>
+pub(crate) struct DropoutBackward<D, T>
 where
     D: Dimension,
+    T: Float,
 {
-    operand_gradient: Rc<Gradient<Array<f32, D>, D>>,
-    gradient: Rc<Gradient<Array<f32, D>, D>>,
-    noise: Shared<Array<f32, D>>,
+    operand_gradient: Rc<Gradient<Array<T, D>, D>>,
+    gradient: Rc<Gradient<Array<T, D>, D>>,
+    noise: Shared<Array<T, D>>,
     p: f64,
     status: Rc<Cell<bool>>,
 }
 
-impl<D> DropoutBackward<D>
+impl<D, T> DropoutBackward<D, T>
 where
     D: Dimension,
+    T: Float,
 {
     pub(crate) fn new(
-        operand_gradient: Rc<Gradient<Array<f32, D>, D>>,
-        gradient: Rc<Gradient<Array<f32, D>, D>>,
+        operand_gradient: Rc<Gradient<Array<T, D>, D>>,
+        gradient: Rc<Gradient<Array<T, D>, D>>,
         p: f64,
-        noise: Shared<Array<f32, D>>,
+        noise: Shared<Array<T, D>>,
         status: Rc<Cell<bool>>,
     ) -> Self {
         Self {
@@ -110,9 +133,10 @@ where
     }
 }
 
-impl<D> Backward for DropoutBackward<D>
+impl<D, T> Backward for DropoutBackward<D, T>
 where
     D: Dimension,
+    T: Float,
 {
     fn backward(&self) {
         if !self.status.get() || self.p == 0.0 {
@@ -125,6 +149,10 @@ where
             .and(&*self.noise.borrow())
             .for_each(|op_grad_el, &grad_el, &noise_el| *op_grad_el += grad_el * noise_el);
     }
+
+    fn reads(&self) -> Reads {
+        Reads::Nothing
+    }
 }
 
 #[cfg(test)]
@@ -1,7 +1,8 @@
-use std::{cell::Cell, error::Error, rc::Rc};
+use std::error::Error;
 
 use ndarray::Array;
 
+use crate::cell::{Cell, Rc};
 use crate::utils::{are_similar, new_shared};
 
 mod forward {
@@ -32,7 +33,7 @@ mod forward {
     #[test]
     #[should_panic]
     fn too_low_probability() {
-        let _ = Dropout::new(
+        let _ = Dropout::<_, f32>::new(
             new_shared(Array::zeros((3, 3))),
             new_shared(Array::zeros((3, 3))),
             -0.5,
@@ -44,7 +45,7 @@ mod forward {
     #[test]
     #[should_panic]
     fn too_high_probability() {
-        let _ = Dropout::new(
+        let _ = Dropout::<_, f32>::new(
             new_shared(Array::zeros((3, 3))),
             new_shared(Array::zeros((3, 3))),
             1.5,
@@ -1,58 +1,106 @@
-use std::rc::Rc;
+use std::any::Any;
 
 use ndarray::{Array, Dimension, Zip};
 
 use crate::{
-    autograd::{Backward, Forward},
+    autograd::{Backward, Forward, Reads},
+    cell::Rc,
+    fusion::{
+        fusible_len, standard_len, BackwardKernel, ElementwiseBackward, ElementwiseForward,
+        ForwardKernel,
+    },
     gradient::Gradient,
+    graph::Buffer,
     utils::Shared,
+    Float,
 };
 
-pub(crate) struct Exp<D>
+pub(crate) struct Exp<D, T>
 where
     D: Dimension,
+    T: Float,
 {
-    operand_data: Shared<Array<f32, D>>,
-    data: Shared<Array<f32, D>>,
+    operand_data: Shared<Array<T, D>>,
+    data: Shared<Array<T, D>>,
 }
 
-impl<D> Exp<D>
+impl<D, T> Exp<D, T>
 where
     D: Dimension,
+    T: Float,
 {
-    pub(crate) fn new(operand_data: Shared<Array<f32, D>>, data: Shared<Array<f32, D>>) -> Self {
+    pub(crate) fn new(operand_data: Shared<Array<T, D>>, data: Shared<Array<T, D>>) -> Self {
         Self { operand_data, data }
     }
 }
 
-impl<D> Forward for Exp<D>
+impl<D, T> Forward for Exp<D, T>
 where
-    D: Dimension,
+    D: 'static + Dimension,
+    T: Float,
 {
     fn forward(&self) {
         Zip::from(&mut *self.data.borrow_mut())
             .and(&*self.operand_data.borrow())
             .for_each(|v, &o| *v = o.exp());
     }
+
+    fn name(&self) -> &'static str {
+        "Exp"
+    }
+
+    fn operands(&self) -> Vec<Buffer> {
+        vec![Buffer::new(&self.operand_data)]
+    }
+
+    fn data(&self) -> Buffer {
+        Buffer::new(&self.data)
+    }
+
+    fn elementwise(&self) -> Option<&dyn ElementwiseForward> {
+        Some(self)
+    }
+}
+
+impl<D, T> ElementwiseForward for Exp<D, T>
+where
+    D: 'static + Dimension,
+    T: Float,
+{
+    fn fusible_len(&self) -> Option<usize> {
+        fusible_len(
+            standard_len(&self.data.borrow()),
+            &[],
+            &[standard_len(&self.operand_data.borrow())],
+        )
+    }
+
+    fn kernel(&self) -> Box<dyn Any> {
+        Box::new(ForwardKernel::unary(&self.operand_data, &self.data, |o| {
+            o.exp()
+        }))
+    }
 }
 
-pub(crate) struct ExpBackward<D>
+pub(crate) struct ExpBackward<D, T>
 where
     D: Dimension,
+    T: Float,
 {
-    operand_gradient: Rc<Gradient<Array<f32, D>, D>>,
-    data: Shared<Array<f32, D>>,
-    gradient: Rc<Gradient<Array<f32, D>, D>>,
+    operand_gradient: Rc<Gradient<Array<T, D>, D>>,
+    data: Shared<Array<T, D>>,
+    gradient: Rc<Gradient<Array<T, D>, D>>,
 }
 
-impl<D> ExpBackward<D>
+impl<D, T> ExpBackward<D, T>
 where
     D: Dimension,
+    T: Float,
 {
     pub(crate) fn new(
-        operand_gradient: Rc<Gradient<Array<f32, D>, D>>,
-        data: Shared<Array<f32, D>>,
-        gradient: Rc<Gradient<Array<f32, D>, D>>,
+        operand_gradient: Rc<Gradient<Array<T, D>, D>>,
+        data: Shared<Array<T, D>>,
+        gradient: Rc<Gradient<Array<T, D>, D>>,
     ) -> Self {
         Self {
             operand_gradient,
@@ -62,9 +110,10 @@ where
     }
 }
 
-impl<D> Backward for ExpBackward<D>
+impl<D, T> Backward for ExpBackward<D, T>
 where
-    D: Dimension,
+    D: 'static + Dimension,
+    T: Float,
 {
     fn backward(&self) {
         Zip::from(&mut *self.operand_gradient.borrow_mut())
@@ -72,6 +121,36 @@ where
             .and(&*self.data.borrow())
             .for_each(|op_grad_el, &grad_el, &data_el| *op_grad_el += grad_el * data_el);
     }
+
+    fn elementwise(&self) -> Option<&dyn ElementwiseBackward> {
+        Some(self)
+    }
+
+    fn reads(&self) -> Reads {
+        Reads::Data
+    }
+}
+
+impl<D, T> ElementwiseBackward for ExpBackward<D, T>
+where
+    D: 'static + Dimension,
+    T: Float,
+{
+    fn fusible_len(&self) -> Option<usize> {
+        fusible_len(
+            standard_len(&self.gradient.borrow()),
+            &[standard_len(&self.operand_gradient.borrow())],
+            &[standard_len(&self.data.borrow())],
+        )
+    }
+
+    fn kernel(&self) -> Box<dyn Any> {
+        Box::new(BackwardKernel::new(self.gradient.clone()).accumulate_with(
+            self.operand_gradient.clone(),
+            &self.data,
+            |g, v| g * v,
+        ))
+    }
 }
 
 #[cfg(test)]
@@ -2,6 +2,7 @@ use std::error::Error;
 
 use ndarray::Array;
 
+use crate::cell::Rc;
 use crate::utils::{are_similar, new_shared};
 
 #[cfg(test)]
@@ -36,7 +37,6 @@ mod forward {
 
 #[cfg(test)]
 mod backward {
-    use std::rc::Rc;
 
     use super::super::{Backward, ExpBackward, Gradient};
     use super::*;
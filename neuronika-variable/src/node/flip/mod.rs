@@ -0,0 +1,118 @@
+use ndarray::{Array, ArrayView, Axis, Dimension};
+
+use crate::{
+    autograd::{Backward, Forward, Reads},
+    cell::Rc,
+    gradient::Gradient,
+    graph::Buffer,
+    utils::Shared,
+    Float,
+};
+
+/// Reverses the order of the elements of `view` along `axes`.
+fn flipped<'a, D, T>(mut view: ArrayView<'a, T, D>, axes: &[Axis]) -> ArrayView<'a, T, D>
+where
+    D: Dimension,
+{
+    axes.iter().for_each(|&axis| view.invert_axis(axis));
+    view
+}
+
+pub(crate) struct Flip<D, T>
+where
+    D: Dimension,
+    T: Float,
+{
+    operand_data: Shared<Array<T, D>>,
+    data: Shared<Array<T, D>>,
+    axes: Vec<Axis>,
+}
+
+impl<D, T> Flip<D, T>
+where
+    D: Dimension,
+    T: Float,
+{
+    pub(crate) fn new(
+        operand_data: Shared<Array<T, D>>,
+        data: Shared<Array<T, D>>,
+        axes: &[usize],
+    ) -> Self {
+        Self {
+            operand_data,
+            data,
+            axes: axes.iter().copied().map(Axis).collect(),
+        }
+    }
+}
+
+impl<D, T> Forward for Flip<D, T>
+where
+    D: Dimension,
+    T: Float,
+{
+    fn forward(&self) {
+        let operand_data = self.operand_data.borrow();
+        self.data
+            .borrow_mut()
+            .assign(&flipped(operand_data.view(), &self.axes));
+    }
+
+    fn name(&self) -> &'static str {
+        "Flip"
+    }
+
+    fn operands(&self) -> Vec<Buffer> {
+        vec![Buffer::new(&self.operand_data)]
+    }
+
+    fn data(&self) -> Buffer {
+        Buffer::new(&self.data)
+    }
+}
+
+pub(crate) struct FlipBackward<D, T>
+where
+    D: Dimension,
+    T: Float,
+{
+    operand_gradient: Rc<Gradient<Array<T, D>, D>>,
+    gradient: Rc<Gradient<Array<T, D>, D>>,
+    axes: Vec<Axis>,
+}
+
+impl<D, T> FlipBackward<D, T>
+where
+    D: Dimension,
+    T: Float,
+{
+    pub(crate) fn new(
+        operand_gradient: Rc<Gradient<Array<T, D>, D>>,
+        gradient: Rc<Gradient<Array<T, D>, D>>,
+        axes: &[usize],
+    ) -> Self {
+        Self {
+            operand_gradient,
+            gradient,
+            axes: axes.iter().copied().map(Axis).collect(),
+        }
+    }
+}
+
+impl<D, T> Backward for FlipBackward<D, T>
+where
+    D: Dimension,
+    T: Float,
+{
+    fn backward(&self) {
+        let gradient = self.gradient.borrow();
+        *self.operand_gradient.borrow_mut() += &flipped(gradient.view(), &self.axes);
+    }
+
+    fn reads(&self) -> Reads {
+        Reads::Nothing
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,61 @@
+use std::error::Error;
+
+use ndarray::array;
+
+use crate::cell::Rc;
+use crate::utils::are_similar;
+
+mod forward {
+    use ndarray::array;
+
+    #[test]
+    fn flip() {
+        let y = crate::from_ndarray(array![[[1., 2., 3.], [4., 5., 6.]]]).flip(&[1, 2]);
+        y.forward();
+        assert_eq!(*y.data(), array![[[6., 5., 4.], [3., 2., 1.]]]);
+    }
+}
+
+mod backward {
+    use ndarray::Ix3;
+
+    use super::super::{Backward, FlipBackward};
+    use super::*;
+    use crate::gradient::Gradient;
+
+    #[test]
+    fn base_case() -> Result<(), Box<dyn Error>> {
+        let op = FlipBackward::<Ix3, f32>::new(
+            Rc::new(Gradient::ndarray_zeros((1, 2, 3))),
+            Rc::new(Gradient::from_ndarray(array![[[1., 2., 3.], [4., 5., 6.]]])),
+            &[1, 2],
+        );
+
+        op.backward();
+        are_similar(
+            op.operand_gradient.borrow(),
+            &array![[[6., 5., 4.], [3., 2., 1.]]],
+        )?;
+
+        op.backward();
+        are_similar(
+            op.operand_gradient.borrow(),
+            &array![[[12., 10., 8.], [6., 4., 2.]]],
+        )
+    }
+
+    #[test]
+    fn single_axis() -> Result<(), Box<dyn Error>> {
+        let op = FlipBackward::<Ix3, f32>::new(
+            Rc::new(Gradient::ndarray_zeros((1, 2, 3))),
+            Rc::new(Gradient::from_ndarray(array![[[1., 2., 3.], [4., 5., 6.]]])),
+            &[1],
+        );
+
+        op.backward();
+        are_similar(
+            op.operand_gradient.borrow(),
+            &array![[[4., 5., 6.], [1., 2., 3.]]],
+        )
+    }
+}
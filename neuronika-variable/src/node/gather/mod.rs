@@ -0,0 +1,150 @@
+use ndarray::{Array, Dimension, IntoDimension};
+
+use crate::{
+    autograd::{Backward, Forward, Reads},
+    cell::Rc,
+    gradient::Gradient,
+    graph::Buffer,
+    utils::Shared,
+    AsIndex, Float,
+};
+
+/// Returns the position of the operand's element selected by the index found at `position`.
+fn gathered_position<D, L>(position: D::Pattern, index: L, axis: usize) -> D
+where
+    D: Dimension,
+    L: AsIndex,
+{
+    let mut position = position.into_dimension();
+    position[axis] = index
+        .to_index()
+        .unwrap_or_else(|| panic!("error: {} is not a valid index.", index));
+
+    position
+}
+
+pub(crate) struct Gather<D, T, L>
+where
+    D: Dimension,
+    T: Float,
+    L: AsIndex,
+{
+    operand_data: Shared<Array<T, D>>,
+    index_data: Shared<Array<L, D>>,
+    data: Shared<Array<T, D>>,
+    axis: usize,
+}
+
+impl<D, T, L> Gather<D, T, L>
+where
+    D: Dimension,
+    T: Float,
+    L: AsIndex,
+{
+    pub(crate) fn new(
+        operand_data: Shared<Array<T, D>>,
+        index_data: Shared<Array<L, D>>,
+        data: Shared<Array<T, D>>,
+        axis: usize,
+    ) -> Self {
+        Self {
+            operand_data,
+            index_data,
+            data,
+            axis,
+        }
+    }
+}
+
+impl<D, T, L> Forward for Gather<D, T, L>
+where
+    D: Dimension,
+    T: Float,
+    L: AsIndex,
+{
+    fn forward(&self) {
+        let operand_data = self.operand_data.borrow();
+
+        self.data
+            .borrow_mut()
+            .indexed_iter_mut()
+            .zip(self.index_data.borrow().iter())
+            .for_each(|((position, v), &index)| {
+                *v = operand_data[gathered_position::<D, L>(position, index, self.axis)]
+            });
+    }
+
+    fn name(&self) -> &'static str {
+        "Gather"
+    }
+
+    fn operands(&self) -> Vec<Buffer> {
+        vec![
+            Buffer::new(&self.operand_data),
+            Buffer::new(&self.index_data),
+        ]
+    }
+
+    fn data(&self) -> Buffer {
+        Buffer::new(&self.data)
+    }
+}
+
+pub(crate) struct GatherBackward<D, T, L>
+where
+    D: Dimension,
+    T: Float,
+    L: AsIndex,
+{
+    operand_gradient: Rc<Gradient<Array<T, D>, D>>,
+    index_data: Shared<Array<L, D>>,
+    gradient: Rc<Gradient<Array<T, D>, D>>,
+    axis: usize,
+}
+
+impl<D, T, L> GatherBackward<D, T, L>
+where
+    D: Dimension,
+    T: Float,
+    L: AsIndex,
+{
+    pub(crate) fn new(
+        operand_gradient: Rc<Gradient<Array<T, D>, D>>,
+        index_data: Shared<Array<L, D>>,
+        gradient: Rc<Gradient<Array<T, D>, D>>,
+        axis: usize,
+    ) -> Self {
+        Self {
+            operand_gradient,
+            index_data,
+            gradient,
+            axis,
+        }
+    }
+}
+
+impl<D, T, L> Backward for GatherBackward<D, T, L>
+where
+    D: Dimension,
+    T: Float,
+    L: AsIndex,
+{
+    fn backward(&self) {
+        let mut operand_gradient = self.operand_gradient.borrow_mut();
+
+        self.gradient
+            .borrow()
+            .indexed_iter()
+            .zip(self.index_data.borrow().iter())
+            .for_each(|((position, &grad_el), &index)| {
+                operand_gradient[gathered_position::<D, L>(position, index, self.axis)] += grad_el
+            });
+    }
+
+    fn reads(&self) -> Reads {
+        Reads::Operands
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,77 @@
+use std::error::Error;
+
+use ndarray::{array, Array};
+
+use crate::cell::Rc;
+use crate::utils::{are_similar, new_shared};
+
+mod forward {
+    use super::super::{Forward, Gather};
+    use super::*;
+
+    #[test]
+    fn rows() -> Result<(), Box<dyn Error>> {
+        let op = Gather::new(
+            new_shared(array![[1., 2., 3.], [4., 5., 6.]]),
+            new_shared(array![[1_usize, 0, 1]]),
+            new_shared(Array::zeros((1, 3))),
+            0,
+        );
+
+        op.forward();
+        are_similar(op.data.borrow(), &array![[4., 2., 6.]])
+    }
+
+    #[test]
+    fn columns() -> Result<(), Box<dyn Error>> {
+        let op = Gather::new(
+            new_shared(array![[1., 2., 3.], [4., 5., 6.]]),
+            new_shared(array![[2_i64, 2], [0, 1]]),
+            new_shared(Array::zeros((2, 2))),
+            1,
+        );
+
+        op.forward();
+        are_similar(op.data.borrow(), &array![[3., 3.], [4., 5.]])
+    }
+
+    #[test]
+    #[should_panic]
+    fn negative_index() {
+        let op = Gather::new(
+            new_shared(array![1., 2., 3.]),
+            new_shared(array![-1_i64]),
+            new_shared(Array::zeros(1)),
+            0,
+        );
+
+        op.forward();
+    }
+}
+
+mod backward {
+    use super::super::{Backward, GatherBackward, Gradient};
+    use super::*;
+
+    #[test]
+    fn backward() -> Result<(), Box<dyn Error>> {
+        let op = GatherBackward::new(
+            Rc::new(Gradient::ndarray_zeros((2, 3))),
+            new_shared(array![[2_i64, 2], [0, 1]]),
+            Rc::new(Gradient::from_ndarray(array![[1., 2.], [3., 4.]])),
+            1,
+        );
+
+        op.backward();
+        are_similar(
+            op.operand_gradient.borrow(),
+            &array![[0., 0., 3.], [3., 4., 0.]],
+        )?;
+
+        op.backward();
+        are_similar(
+            op.operand_gradient.borrow(),
+            &array![[0., 0., 6.], [6., 8., 0.]],
+        )
+    }
+}
@@ -0,0 +1,360 @@
+use ndarray::{Array, ArrayD, ArrayViewD, ArrayViewMutD, Axis, Dimension, Zip};
+
+use crate::{
+    autograd::{Backward, Forward, Reads},
+    cell::Rc,
+    gradient::Gradient,
+    graph::Buffer,
+    utils::Shared,
+    Float,
+};
+
+/// Parameter of the cubic convolution kernel, the same used by PyTorch.
+const CUBIC_COEFFICIENT: f64 = -0.75;
+
+/// Interpolation algorithm used to resize a variable.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum Interpolation {
+    /// Nearest neighbour interpolation, over any number of spatial dimensions.
+    Nearest,
+    /// Linear interpolation, over one spatial dimension.
+    Linear,
+    /// Bilinear interpolation, over two spatial dimensions.
+    Bilinear,
+    /// Trilinear interpolation, over three spatial dimensions.
+    Trilinear,
+    /// Bicubic interpolation, over two spatial dimensions.
+    Bicubic,
+}
+
+impl Interpolation {
+    /// Returns whether the interpolation can be applied over `spatial` spatial dimensions.
+    fn supports(self, spatial: usize) -> bool {
+        match self {
+            Self::Nearest => (1..=3).contains(&spatial),
+            Self::Linear => spatial == 1,
+            Self::Bilinear | Self::Bicubic => spatial == 2,
+            Self::Trilinear => spatial == 3,
+        }
+    }
+
+    /// Returns, for each position along a resized axis of length `output`, the positions along the
+    /// original axis of length `input` it is interpolated from, together with their weights.
+    fn taps<T>(self, input: usize, output: usize, align_corners: bool) -> Taps<T>
+    where
+        T: Float,
+    {
+        let last = input - 1;
+        let source = |position: usize| {
+            let position = position as f64;
+            if align_corners {
+                if output > 1 {
+                    position * last as f64 / (output - 1) as f64
+                } else {
+                    0.
+                }
+            } else {
+                (position + 0.5) * input as f64 / output as f64 - 0.5
+            }
+        };
+
+        (0..output)
+            .map(|position| match self {
+                Self::Nearest => {
+                    let nearest = position * input / output;
+                    vec![(nearest.min(last), T::one())]
+                }
+                Self::Linear | Self::Bilinear | Self::Trilinear => {
+                    let source = source(position).max(0.);
+                    let left = (source.floor() as usize).min(last);
+                    let right = (left + 1).min(last);
+                    let lambda = source - left as f64;
+                    vec![
+                        (left, T::from_f64(1. - lambda)),
+                        (right, T::from_f64(lambda)),
+                    ]
+                }
+                Self::Bicubic => {
+                    let source = source(position);
+                    let floor = source.floor();
+                    let t = source - floor;
+                    let weights = [
+                        cubic_far(t + 1.),
+                        cubic_near(t),
+                        cubic_near(1. - t),
+                        cubic_far(2. - t),
+                    ];
+                    // Taps outside of the axis are replaced by the nearest border.
+                    weights
+                        .iter()
+                        .enumerate()
+                        .map(|(offset, &weight)| {
+                            let tap =
+                                (floor as isize + offset as isize - 1).clamp(0, last as isize);
+                            (tap as usize, T::from_f64(weight))
+                        })
+                        .collect()
+                }
+            })
+            .collect()
+    }
+}
+
+/// Cubic convolution kernel for distances up to one.
+fn cubic_near(x: f64) -> f64 {
+    let a = CUBIC_COEFFICIENT;
+    ((a + 2.) * x - (a + 3.)) * x * x + 1.
+}
+
+/// Cubic convolution kernel for distances between one and two.
+fn cubic_far(x: f64) -> f64 {
+    let a = CUBIC_COEFFICIENT;
+    ((a * x - 5. * a) * x + 8. * a) * x - 4. * a
+}
+
+/// Size of a resized variable.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum Resize<D> {
+    /// Exact spatial size of the result.
+    Size(D),
+    /// Multiplier of the spatial size, the resulting lengths are rounded down.
+    ScaleFactor(f64),
+}
+
+/// Positions and weights every element of a resized axis is interpolated from.
+pub(crate) type Taps<T> = Vec<Vec<(usize, T)>>;
+
+/// Returns the shape of a variable of shape `shape` once resized to `size`, together with the
+/// taps of each of its spatial axes.
+///
+/// # Panics
+///
+/// If `mode` cannot be applied to a variable of shape `shape`, if `size` doesn't match its
+/// number of spatial dimensions or if any of the resulting lengths is zero.
+pub(crate) fn resampling<D, E, T>(
+    shape: &D,
+    size: &Resize<E>,
+    mode: Interpolation,
+    align_corners: bool,
+) -> (D, Vec<Taps<T>>)
+where
+    D: Dimension,
+    E: Dimension,
+    T: Float,
+{
+    let spatial = shape.ndim().saturating_sub(2);
+    assert!(
+        shape.ndim() > 2 && mode.supports(spatial),
+        "error: {:?} interpolation cannot be applied to a variable of shape {:?}.",
+        mode,
+        shape.slice()
+    );
+
+    let mut resized = shape.clone();
+    match size {
+        Resize::Size(size) => {
+            assert_eq!(
+                size.ndim(),
+                spatial,
+                "error: size {:?} given for a variable of shape {:?}.",
+                size.slice(),
+                shape.slice()
+            );
+            resized.slice_mut()[2..].copy_from_slice(size.slice());
+        }
+        Resize::ScaleFactor(factor) => {
+            assert!(
+                *factor > 0.,
+                "error: scale factor {} is not positive.",
+                factor
+            );
+            resized.slice_mut()[2..]
+                .iter_mut()
+                .for_each(|len| *len = (*len as f64 * factor).floor() as usize);
+        }
+    }
+    assert!(
+        shape.slice()[2..]
+            .iter()
+            .zip(&resized.slice()[2..])
+            .all(|(&input, &output)| input > 0 && output > 0),
+        "error: variable of shape {:?} cannot be resized to shape {:?}.",
+        shape.slice(),
+        resized.slice()
+    );
+
+    let taps = shape.slice()[2..]
+        .iter()
+        .zip(&resized.slice()[2..])
+        .map(|(&input, &output)| mode.taps(input, output, align_corners))
+        .collect();
+
+    (resized, taps)
+}
+
+/// Interpolates the lanes of `input` along `axis` into those of `output`.
+fn resample<T>(input: ArrayViewD<T>, mut output: ArrayViewMutD<T>, taps: &Taps<T>, axis: Axis)
+where
+    T: Float,
+{
+    Zip::from(output.lanes_mut(axis))
+        .and(input.lanes(axis))
+        .for_each(|mut output_lane, input_lane| {
+            output_lane
+                .iter_mut()
+                .zip(taps)
+                .for_each(|(output_el, taps)| {
+                    *output_el = taps
+                        .iter()
+                        .map(|&(position, weight)| input_lane[position] * weight)
+                        .sum();
+                })
+        });
+}
+
+/// Accumulates into the lanes of `input` along `axis` the gradient of the interpolation of them,
+/// given that of the result `output`.
+fn resample_backward<T>(
+    mut input: ArrayViewMutD<T>,
+    output: ArrayViewD<T>,
+    taps: &Taps<T>,
+    axis: Axis,
+) where
+    T: Float,
+{
+    Zip::from(input.lanes_mut(axis))
+        .and(output.lanes(axis))
+        .for_each(|mut input_lane, output_lane| {
+            output_lane.iter().zip(taps).for_each(|(&output_el, taps)| {
+                taps.iter()
+                    .for_each(|&(position, weight)| input_lane[position] += output_el * weight)
+            })
+        });
+}
+
+pub(crate) struct Interpolate<D, T>
+where
+    D: Dimension,
+    T: Float,
+{
+    operand_data: Shared<Array<T, D>>,
+    data: Shared<Array<T, D>>,
+    taps: Vec<Taps<T>>,
+}
+
+impl<D, T> Interpolate<D, T>
+where
+    D: Dimension,
+    T: Float,
+{
+    pub(crate) fn new(
+        operand_data: Shared<Array<T, D>>,
+        data: Shared<Array<T, D>>,
+        taps: Vec<Taps<T>>,
+    ) -> Self {
+        Self {
+            operand_data,
+            data,
+            taps,
+        }
+    }
+}
+
+impl<D, T> Forward for Interpolate<D, T>
+where
+    D: Dimension,
+    T: Float,
+{
+    fn forward(&self) {
+        let operand_data = self.operand_data.borrow();
+
+        // The spatial axes are resized one at a time.
+        let mut resized: ArrayD<T> = operand_data.view().into_dyn().to_owned();
+        for (axis, taps) in self
+            .taps
+            .iter()
+            .enumerate()
+            .map(|(i, taps)| (Axis(i + 2), taps))
+        {
+            let mut shape = resized.raw_dim();
+            shape[axis.index()] = taps.len();
+            let mut next = Array::zeros(shape);
+            resample(resized.view(), next.view_mut(), taps, axis);
+            resized = next;
+        }
+
+        self.data.borrow_mut().assign(&resized);
+    }
+
+    fn name(&self) -> &'static str {
+        "Interpolate"
+    }
+
+    fn operands(&self) -> Vec<Buffer> {
+        vec![Buffer::new(&self.operand_data)]
+    }
+
+    fn data(&self) -> Buffer {
+        Buffer::new(&self.data)
+    }
+}
+
+pub(crate) struct InterpolateBackward<D, T>
+where
+    D: Dimension,
+    T: Float,
+{
+    operand_gradient: Rc<Gradient<Array<T, D>, D>>,
+    gradient: Rc<Gradient<Array<T, D>, D>>,
+    taps: Vec<Taps<T>>,
+}
+
+impl<D, T> InterpolateBackward<D, T>
+where
+    D: Dimension,
+    T: Float,
+{
+    pub(crate) fn new(
+        operand_gradient: Rc<Gradient<Array<T, D>, D>>,
+        gradient: Rc<Gradient<Array<T, D>, D>>,
+        taps: Vec<Taps<T>>,
+    ) -> Self {
+        Self {
+            operand_gradient,
+            gradient,
+            taps,
+        }
+    }
+}
+
+impl<D, T> Backward for InterpolateBackward<D, T>
+where
+    D: Dimension,
+    T: Float,
+{
+    fn backward(&self) {
+        let operand_shape = self.operand_gradient.shape();
+
+        // Walks the spatial axes back in reverse order.
+        let mut gradient: ArrayD<T> = self.gradient.borrow().view().into_dyn().to_owned();
+        for (axis, taps) in self.taps.iter().enumerate().rev() {
+            let axis = Axis(axis + 2);
+            let mut shape = gradient.raw_dim();
+            shape[axis.index()] = operand_shape[axis.index()];
+            let mut previous = Array::zeros(shape);
+            resample_backward(previous.view_mut(), gradient.view(), taps, axis);
+            gradient = previous;
+        }
+
+        *self.operand_gradient.borrow_mut() += &gradient;
+    }
+
+    fn reads(&self) -> Reads {
+        Reads::Nothing
+    }
+}
+
+#[cfg(test)]
+mod test;
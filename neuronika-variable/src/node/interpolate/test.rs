@@ -0,0 +1,286 @@
+use std::error::Error;
+
+use ndarray::{array, Array};
+
+use crate::cell::Rc;
+use crate::utils::are_similar;
+
+mod forward {
+    use crate::{Interpolation, Resize};
+    use ndarray::{array, Ix1, Ix2, Ix3};
+
+    #[test]
+    fn linear() {
+        // Reference values match those of PyTorch.
+        let x = crate::from_ndarray(array![[[1., 2., 3., 4.]]]);
+        let cases = [
+            (
+                Interpolation::Nearest,
+                false,
+                array![1., 1., 2., 2., 3., 3., 4., 4.],
+            ),
+            (
+                Interpolation::Linear,
+                false,
+                array![1., 1.25, 1.75, 2.25, 2.75, 3.25, 3.75, 4.],
+            ),
+            (
+                Interpolation::Linear,
+                true,
+                array![1., 1.428571, 1.857143, 2.285714, 2.714286, 3.142857, 3.571429, 4.],
+            ),
+        ];
+        for (mode, align_corners, expected) in cases {
+            let y = x
+                .clone()
+                .interpolate(Resize::ScaleFactor(2.), mode, align_corners);
+            y.forward();
+            assert!(y
+                .data()
+                .abs_diff_eq(&expected.into_shape((1, 1, 8)).unwrap(), 1e-6));
+        }
+        let x = crate::from_ndarray(array![[[1., 2., 3., 4., 5.]]]);
+        for (align_corners, expected) in [
+            (false, array![1.333333, 3., 4.666667]),
+            (true, array![1., 3., 5.]),
+        ] {
+            let y =
+                x.clone()
+                    .interpolate(Resize::Size(Ix1(3)), Interpolation::Linear, align_corners);
+            y.forward();
+            assert!(y
+                .data()
+                .abs_diff_eq(&expected.into_shape((1, 1, 3)).unwrap(), 1e-6));
+        }
+    }
+
+    #[test]
+    fn bilinear_bicubic() {
+        // Reference values match those of PyTorch.
+        let x = crate::from_ndarray(array![[1., 2.], [3., 4.]].into_shape((1, 1, 2, 2)).unwrap());
+        let cases = [
+            (
+                Interpolation::Bilinear,
+                false,
+                array![
+                    [1., 1.25, 1.75, 2.],
+                    [1.5, 1.75, 2.25, 2.5],
+                    [2.5, 2.75, 3.25, 3.5],
+                    [3., 3.25, 3.75, 4.]
+                ],
+            ),
+            (
+                Interpolation::Bilinear,
+                true,
+                array![
+                    [1., 1.333333, 1.666667, 2.],
+                    [1.666667, 2., 2.333333, 2.666667],
+                    [2.333333, 2.666667, 3., 3.333333],
+                    [3., 3.333333, 3.666667, 4.]
+                ],
+            ),
+            (
+                Interpolation::Bicubic,
+                false,
+                array![
+                    [0.683594, 1.015625, 1.5625, 1.894531],
+                    [1.347656, 1.679688, 2.226562, 2.558594],
+                    [2.441406, 2.773438, 3.320312, 3.652344],
+                    [3.105469, 3.4375, 3.984375, 4.316406]
+                ],
+            ),
+            (
+                Interpolation::Bicubic,
+                true,
+                array![
+                    [1., 1.314815, 1.685185, 2.],
+                    [1.62963, 1.944444, 2.314815, 2.62963],
+                    [2.37037, 2.685185, 3.055556, 3.37037],
+                    [3., 3.314815, 3.685185, 4.]
+                ],
+            ),
+        ];
+        for (mode, align_corners, expected) in cases {
+            let y = x
+                .clone()
+                .interpolate(Resize::Size(Ix2(4, 4)), mode, align_corners);
+            y.forward();
+            assert!(y
+                .data()
+                .abs_diff_eq(&expected.into_shape((1, 1, 4, 4)).unwrap(), 1e-6));
+        }
+        let y = x
+            .clone()
+            .interpolate(Resize::Size(Ix2(3, 5)), Interpolation::Nearest, false);
+        y.forward();
+        assert_eq!(
+            *y.data(),
+            array![
+                [1., 1., 1., 2., 2.],
+                [1., 1., 1., 2., 2.],
+                [3., 3., 3., 4., 4.]
+            ]
+            .into_shape((1, 1, 3, 5))
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn trilinear() {
+        // Trilinear interpolation with aligned corners is exact on linear functions.
+        let x = crate::from_ndarray(
+            ndarray::Array::from_iter((0..8).map(|i| i as f64))
+                .into_shape((1, 1, 2, 2, 2))
+                .unwrap(),
+        );
+        let y = x
+            .clone()
+            .interpolate(Resize::Size(Ix3(3, 3, 3)), Interpolation::Trilinear, true);
+        y.forward();
+        let expected = ndarray::Array::from_shape_fn((1, 1, 3, 3, 3), |(_, _, k, j, i)| {
+            2. * k as f64 + j as f64 + 0.5 * i as f64
+        });
+        assert!(y.data().abs_diff_eq(&expected, 1e-12));
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "error: Bilinear interpolation cannot be applied to a variable of shape [1, 1, 4]."
+    )]
+    fn wrong_mode() {
+        let _ = crate::zeros((1, 1, 4)).interpolate(
+            Resize::ScaleFactor(2.),
+            Interpolation::Bilinear,
+            false,
+        );
+    }
+}
+
+mod backward {
+    use ndarray::{Ix1, Ix2, Ix3, Ix4, Ix5};
+
+    use super::super::{resampling, Backward, InterpolateBackward};
+    use super::*;
+    use crate::{gradient::Gradient, Interpolation, Resize};
+
+    #[test]
+    fn nearest() -> Result<(), Box<dyn Error>> {
+        let (_, taps) = resampling(
+            &Ix3(1, 1, 4),
+            &Resize::Size(Ix1(8)),
+            Interpolation::Nearest,
+            false,
+        );
+        let op = InterpolateBackward::<Ix3, f32>::new(
+            Rc::new(Gradient::ndarray_zeros((1, 1, 4))),
+            Rc::new(Gradient::from_ndarray(array![[[
+                1., 2., 3., 4., 5., 6., 7., 8.
+            ]]])),
+            taps,
+        );
+
+        op.backward();
+        are_similar(op.operand_gradient.borrow(), &array![[[3., 7., 11., 15.]]])?;
+
+        op.backward();
+        are_similar(op.operand_gradient.borrow(), &array![[[6., 14., 22., 30.]]])
+    }
+
+    #[test]
+    fn linear() -> Result<(), Box<dyn Error>> {
+        // The elements of the result are taken at 0, 0.25, 0.75, ..., 3.25 along the operand,
+        // the ones outside of it are clamped to the border.
+        let (_, taps) = resampling(
+            &Ix3(1, 1, 4),
+            &Resize::Size(Ix1(8)),
+            Interpolation::Linear,
+            false,
+        );
+        let op = InterpolateBackward::<Ix3, f32>::new(
+            Rc::new(Gradient::ndarray_zeros((1, 1, 4))),
+            Rc::new(Gradient::from_ndarray(array![[[
+                1., 2., 3., 4., 5., 6., 7., 8.
+            ]]])),
+            taps,
+        );
+
+        op.backward();
+        are_similar(
+            op.operand_gradient.borrow(),
+            &array![[[3.25, 7., 11., 14.75]]],
+        )
+    }
+
+    #[test]
+    fn bilinear() -> Result<(), Box<dyn Error>> {
+        // With aligned corners, the middle row and column are averages of their neighbours.
+        let (_, taps) = resampling(
+            &Ix4(1, 1, 2, 2),
+            &Resize::Size(Ix2(3, 3)),
+            Interpolation::Bilinear,
+            true,
+        );
+        let op = InterpolateBackward::<Ix4, f32>::new(
+            Rc::new(Gradient::ndarray_zeros((1, 1, 2, 2))),
+            Rc::new(Gradient::from_ndarray(
+                Array::linspace(1., 9., 9).into_shape((1, 1, 3, 3))?,
+            )),
+            taps,
+        );
+
+        op.backward();
+        are_similar(
+            op.operand_gradient.borrow(),
+            &array![[5.25, 8.25], [14.25, 17.25]].into_shape((1, 1, 2, 2))?,
+        )
+    }
+
+    #[test]
+    fn bicubic() -> Result<(), Box<dyn Error>> {
+        // The middle element of the result lies halfway between the second and the third element
+        // of the operand, the cubic kernel weighs the four around it -0.09375, 0.59375, 0.59375
+        // and -0.09375.
+        let (_, taps) = resampling(
+            &Ix4(1, 1, 1, 4),
+            &Resize::Size(Ix2(1, 3)),
+            Interpolation::Bicubic,
+            true,
+        );
+        let op = InterpolateBackward::<Ix4, f32>::new(
+            Rc::new(Gradient::ndarray_zeros((1, 1, 1, 4))),
+            Rc::new(Gradient::from_ndarray(
+                array![1., 2., 3.].into_shape((1, 1, 1, 3))?,
+            )),
+            taps,
+        );
+
+        op.backward();
+        are_similar(
+            op.operand_gradient.borrow(),
+            &array![0.8125, 1.1875, 1.1875, 2.8125].into_shape((1, 1, 1, 4))?,
+        )
+    }
+
+    #[test]
+    fn trilinear() -> Result<(), Box<dyn Error>> {
+        // Along each axis, the three elements of the result weigh the first element of the operand
+        // 1, 0.5 and 0, the second one 0, 0.5 and 1.
+        let (_, taps) = resampling(
+            &Ix5(1, 1, 2, 2, 2),
+            &Resize::Size(Ix3(3, 3, 3)),
+            Interpolation::Trilinear,
+            false,
+        );
+        let op = InterpolateBackward::<Ix5, f32>::new(
+            Rc::new(Gradient::ndarray_zeros((1, 1, 2, 2, 2))),
+            Rc::new(Gradient::from_ndarray(Array::ones((1, 1, 3, 3, 3)))),
+            taps,
+        );
+
+        op.backward();
+        are_similar(
+            op.operand_gradient.borrow(),
+            &Array::from_elem((1, 1, 2, 2, 2), 3.375),
+        )
+    }
+}
@@ -1,33 +1,35 @@
-use std::rc::Rc;
-
 use ndarray::{arr0, Array, Axis, Dimension, Ix0, Zip};
 
 use crate::{
-    autograd::{Backward, Forward},
+    autograd::{Backward, Forward, Reads},
+    cell::Rc,
     gradient::Gradient,
+    graph::Buffer,
     utils::Shared,
-    Reduction,
+    Float, Reduction,
 };
 
 #[allow(clippy::upper_case_acronyms)]
-pub(crate) struct KLDiv<D>
+pub(crate) struct KLDiv<D, T>
 where
     D: Dimension,
+    T: Float,
 {
-    input_data: Shared<Array<f32, D>>,
-    target_data: Shared<Array<f32, D>>,
-    data: Shared<Array<f32, Ix0>>,
+    input_data: Shared<Array<T, D>>,
+    target_data: Shared<Array<T, D>>,
+    data: Shared<Array<T, Ix0>>,
     reduction: Reduction,
 }
 
-impl<D> KLDiv<D>
+impl<D, T> KLDiv<D, T>
 where
     D: Dimension,
+    T: Float,
 {
     pub(crate) fn new(
-        input_data: Shared<Array<f32, D>>,
-        target_data: Shared<Array<f32, D>>,
-        data: Shared<Array<f32, Ix0>>,
+        input_data: Shared<Array<T, D>>,
+        target_data: Shared<Array<T, D>>,
+        data: Shared<Array<T, Ix0>>,
         reduction: Reduction,
     ) -> Self {
         Self {
@@ -39,47 +41,69 @@ where
     }
 }
 
-impl<D> Forward for KLDiv<D>
+impl<D, T> Forward for KLDiv<D, T>
 where
     D: Dimension,
+    T: Float,
 {
     fn forward(&self) {
         let (input_data, target_data) = (self.input_data.borrow(), self.target_data.borrow());
         *self.data.borrow_mut() = {
-            let total_loss =
-                Zip::from(&*input_data)
-                    .and(&*target_data)
-                    .fold(0., |loss, &log, &target| {
-                        loss + target * (target.ln() - log) * (target > 0.) as u8 as f32
-                    });
+            let total_loss = Zip::from(&*input_data).and(&*target_data).fold(
+                T::zero(),
+                |loss, &log, &target| {
+                    if target > T::zero() {
+                        loss + target * (target.ln() - log)
+                    } else {
+                        loss
+                    }
+                },
+            );
 
             match self.reduction {
-                Reduction::Mean => arr0(total_loss / input_data.len_of(Axis(0)) as f32),
+                Reduction::Mean => arr0(total_loss / T::from_usize(input_data.len_of(Axis(0)))),
                 Reduction::Sum => arr0(total_loss),
             }
         };
     }
+
+    fn name(&self) -> &'static str {
+        "KLDiv"
+    }
+
+    fn operands(&self) -> Vec<Buffer> {
+        vec![
+            Buffer::new(&self.input_data),
+            Buffer::new(&self.target_data),
+        ]
+    }
+
+    fn data(&self) -> Buffer {
+        Buffer::new(&self.data)
+    }
 }
 
 #[allow(clippy::upper_case_acronyms)]
-pub(crate) struct KLDivBackward<D>
+pub(crate) struct KLDivBackward<D, T>
 where
     D: Dimension,
+    T: Float,
 {
-    input_gradient: Rc<Gradient<Array<f32, D>, D>>,
-    target_data: Shared<Array<f32, D>>,
-    gradient: Rc<Gradient<Array<f32, Ix0>, Ix0>>,
+    input_gradient: Rc<Gradient<Array<T, D>, D>>,
+    target_data: Shared<Array<T, D>>,
+    gradient: Rc<Gradient<Array<T, Ix0>, Ix0>>,
     reduction: Reduction,
 }
 
-impl<D> KLDivBackward<D>
+impl<D, T> KLDivBackward<D, T>
 where
     D: Dimension,
+    T: Float,
 {
     pub(crate) fn new(
-        input_gradient: Rc<Gradient<Array<f32, D>, D>>,
-        target_data: Shared<Array<f32, D>>,
-        gradient: Rc<Gradient<Array<f32, Ix0>, Ix0>>,
+        input_gradient: Rc<Gradient<Array<T, D>, D>>,
+        target_data: Shared<Array<T, D>>,
+        gradient: Rc<Gradient<Array<T, Ix0>, Ix0>>,
         reduction: Reduction,
     ) -> Self {
         Self {
@@ -91,9 +115,10 @@ where
     }
 }
 
-impl<D> Backward for KLDivBackward<D>
+impl<D, T> Backward for KLDivBackward<D, T>
 where
     D: Dimension,
+    T: Float,
 {
     fn backward(&self) {
         let mut input_gradient = self.input_gradient.borrow_mut();
@@ -106,7 +131,7 @@ where
 
         match self.reduction {
             Reduction::Mean => {
-                let n = target_data.len_of(Axis(0)) as f32;
+                let n = T::from_usize(target_data.len_of(Axis(0)));
                 zip.for_each(|op_grad, &grad, &target| *op_grad += -target * grad / n);
             }
             Reduction::Sum => {
@@ -114,6 +139,10 @@ where
             }
         }
     }
+
+    fn reads(&self) -> Reads {
+        Reads::Operands
+    }
 }
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
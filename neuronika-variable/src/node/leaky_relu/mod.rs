@@ -1,62 +1,118 @@
-use std::rc::Rc;
+use std::any::Any;
 
 use ndarray::{Array, Dimension, Zip};
 
 use crate::{
-    autograd::{Backward, Forward},
+    autograd::{Backward, Forward, Reads},
+    cell::Rc,
+    fusion::{
+        fusible_len, standard_len, BackwardKernel, ElementwiseBackward, ElementwiseForward,
+        ForwardKernel,
+    },
     gradient::Gradient,
+    graph::Buffer,
     utils::Shared,
+    Float,
 };
 
 #[allow(clippy::upper_case_acronyms)]
-pub(crate) struct LeakyReLU<D>
+pub(crate) struct LeakyReLU<D, T>
 where
     D: Dimension,
+    T: Float,
 {
-    operand_data: Shared<Array<f32, D>>,
-    data: Shared<Array<f32, D>>,
+    operand_data: Shared<Array<T, D>>,
+    data: Shared<Array<T, D>>,
 }
 
-impl<D> LeakyReLU<D>
+impl<D, T> LeakyReLU<D, T>
 where
     D: Dimension,
+    T: Float,
 {
-    pub(crate) fn new(operand_data: Shared<Array<f32, D>>, data: Shared<Array<f32, D>>) -> Self {
+    pub(crate) fn new(operand_data: Shared<Array<T, D>>, data: Shared<Array<T, D>>) -> Self {
         Self { operand_data, data }
     }
 }
 
-impl<D> Forward for LeakyReLU<D>
+impl<D, T> Forward for LeakyReLU<D, T>
 where
-    D: Dimension,
+    D: 'static + Dimension,
+    T: Float,
 {
     fn forward(&self) {
         Zip::from(&mut *self.data.borrow_mut())
             .and(&*self.operand_data.borrow())
             .for_each(|v, &o| {
-                *v = ((o > 0.) as u8 as f32) * o + ((o <= 0.) as u8 as f32) * (0.01 * o)
+                *v = if o > T::zero() {
+                    o
+                } else {
+                    T::from_f64(0.01) * o
+                }
             });
     }
+
+    fn name(&self) -> &'static str {
+        "LeakyReLU"
+    }
+
+    fn operands(&self) -> Vec<Buffer> {
+        vec![Buffer::new(&self.operand_data)]
+    }
+
+    fn data(&self) -> Buffer {
+        Buffer::new(&self.data)
+    }
+
+    fn elementwise(&self) -> Option<&dyn ElementwiseForward> {
+        Some(self)
+    }
+}
+
+impl<D, T> ElementwiseForward for LeakyReLU<D, T>
+where
+    D: 'static + Dimension,
+    T: Float,
+{
+    fn fusible_len(&self) -> Option<usize> {
+        fusible_len(
+            standard_len(&self.data.borrow()),
+            &[],
+            &[standard_len(&self.operand_data.borrow())],
+        )
+    }
+
+    fn kernel(&self) -> Box<dyn Any> {
+        Box::new(ForwardKernel::unary(&self.operand_data, &self.data, |o| {
+            if o > T::zero() {
+                o
+            } else {
+                T::from_f64(0.01) * o
+            }
+        }))
+    }
 }
 
 #[allow(clippy::upper_case_acronyms)]
-pub(crate) struct LeakyReLUBackward<D>
+pub(crate) struct LeakyReLUBackward<D, T>
 where
     D: Dimension,
+    T: Float,
 {
-    operand_gradient: Rc<Gradient<Array<f32, D>, D>>,
-    operand_data: Shared<Array<f32, D>>,
-    gradient: Rc<Gradient<Array<f32, D>, D>>,
+    operand_gradient: Rc<Gradient<Array<T, D>, D>>,
+    operand_data: Shared<Array<T, D>>,
+    gradient: Rc<Gradient<Array<T, D>, D>>,
 }
 
-impl<D> LeakyReLUBackward<D>
+impl<D, T> LeakyReLUBackward<D, T>
 where
     D: Dimension,
+    T: Float,
 {
     pub(crate) fn new(
-        operand_gradient: Rc<Gradient<Array<f32, D>, D>>,
-        operand_data: Shared<Array<f32, D>>,
-        gradient: Rc<Gradient<Array<f32, D>, D>>,
+        operand_gradient: Rc<Gradient<Array<T, D>, D>>,
+        operand_data: Shared<Array<T, D>>,
+        gradient: Rc<Gradient<Array<T, D>, D>>,
     ) -> Self {
         Self {
             operand_gradient,
@@ -66,19 +122,59 @@ where
     }
 }
 
-impl<D> Backward for LeakyReLUBackward<D>
+impl<D, T> Backward for LeakyReLUBackward<D, T>
 where
-    D: Dimension,
+    D: 'static + Dimension,
+    T: Float,
 {
     fn backward(&self) {
         Zip::from(&mut *self.operand_gradient.borrow_mut())
             .and(&*self.gradient.borrow())
             .and(&*self.operand_data.borrow())
             .for_each(|op_grad_el, &grad_el, &op_data_el| {
-                *op_grad_el += ((op_data_el > 0.) as u8 as f32) * grad_el
-                    + ((op_data_el <= 0.) as u8 as f32) * 0.01;
+                *op_grad_el += if op_data_el > T::zero() {
+                    grad_el
+                } else {
+                    T::from_f64(0.01)
+                };
             });
     }
+
+    fn elementwise(&self) -> Option<&dyn ElementwiseBackward> {
+        Some(self)
+    }
+
+    fn reads(&self) -> Reads {
+        Reads::Operands
+    }
+}
+
+impl<D, T> ElementwiseBackward for LeakyReLUBackward<D, T>
+where
+    D: 'static + Dimension,
+    T: Float,
+{
+    fn fusible_len(&self) -> Option<usize> {
+        fusible_len(
+            standard_len(&self.gradient.borrow()),
+            &[standard_len(&self.operand_gradient.borrow())],
+            &[standard_len(&self.operand_data.borrow())],
+        )
+    }
+
+    fn kernel(&self) -> Box<dyn Any> {
+        Box::new(BackwardKernel::new(self.gradient.clone()).accumulate_with(
+            self.operand_gradient.clone(),
+            &self.operand_data,
+            |g, o| {
+                if o > T::zero() {
+                    g
+                } else {
+                    T::from_f64(0.01)
+                }
+            },
+        ))
+    }
 }
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
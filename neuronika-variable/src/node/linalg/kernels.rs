@@ -0,0 +1,537 @@
+use std::cmp::Ordering;
+
+use ndarray::{concatenate, s, Array1, Array2, ArrayView1, ArrayView2, ArrayViewMut2, Axis};
+
+use crate::Float;
+
+/// Maximum number of sweeps of the Jacobi methods.
+const MAX_SWEEPS: usize = 64;
+
+/// LU factorization with partial pivoting of a square matrix, such that `P · A = L · U`.
+pub(super) struct Lu<T> {
+    lower: Array2<T>,
+    upper: Array2<T>,
+    permutation: Vec<usize>,
+    sign: T,
+}
+
+impl<T> Lu<T>
+where
+    T: Float,
+{
+    pub(super) fn new(matrix: ArrayView2<T>) -> Self {
+        let n = matrix.nrows();
+        let mut factors = matrix.to_owned();
+        let mut permutation = (0..n).collect::<Vec<_>>();
+        let mut sign = T::one();
+
+        for k in 0..n {
+            let pivot = (k..n)
+                .max_by(|&i, &j| {
+                    factors[[i, k]]
+                        .abs()
+                        .partial_cmp(&factors[[j, k]].abs())
+                        .unwrap_or(Ordering::Equal)
+                })
+                .unwrap();
+            if pivot != k {
+                for j in 0..n {
+                    factors.swap([k, j], [pivot, j]);
+                }
+                permutation.swap(k, pivot);
+                sign = -sign;
+            }
+
+            let diagonal = factors[[k, k]];
+            if diagonal == T::zero() {
+                continue;
+            }
+            let row = factors.slice(s![k, k + 1..]).to_owned();
+            for i in k + 1..n {
+                let factor = factors[[i, k]] / diagonal;
+                factors[[i, k]] = factor;
+                factors.slice_mut(s![i, k + 1..]).scaled_add(-factor, &row);
+            }
+        }
+
+        let lower = Array2::from_shape_fn((n, n), |(i, j)| match i.cmp(&j) {
+            Ordering::Greater => factors[[i, j]],
+            Ordering::Equal => T::one(),
+            Ordering::Less => T::zero(),
+        });
+        let upper = Array2::from_shape_fn(
+            (n, n),
+            |(i, j)| {
+                if i <= j {
+                    factors[[i, j]]
+                } else {
+                    T::zero()
+                }
+            },
+        );
+
+        Self {
+            lower,
+            upper,
+            permutation,
+            sign,
+        }
+    }
+
+    /// Returns the determinant of the factorized matrix.
+    pub(super) fn det(&self) -> T {
+        self.upper
+            .diag()
+            .iter()
+            .fold(self.sign, |det, &diagonal| det * diagonal)
+    }
+
+    /// Returns the sign and the natural logarithm of the absolute value of the determinant of the
+    /// factorized matrix.
+    pub(super) fn slogdet(&self) -> (T, T) {
+        self.upper
+            .diag()
+            .iter()
+            .fold((self.sign, T::zero()), |(sign, logabsdet), &diagonal| {
+                let sign = if diagonal == T::zero() {
+                    T::zero()
+                } else {
+                    sign * diagonal.signum()
+                };
+
+                (sign, logabsdet + diagonal.abs().ln())
+            })
+    }
+
+    /// Solves `A · X = B`.
+    pub(super) fn solve(&self, rhs: ArrayView2<T>) -> Array2<T> {
+        let permuted = Array2::from_shape_fn(rhs.raw_dim(), |(i, j)| rhs[[self.permutation[i], j]]);
+        let solution = solve_triangular(self.lower.view(), permuted.view(), true);
+
+        solve_triangular(self.upper.view(), solution.view(), false)
+    }
+
+    /// Solves `Aᵀ · X = B`.
+    pub(super) fn solve_transposed(&self, rhs: ArrayView2<T>) -> Array2<T> {
+        let solution = solve_triangular(self.upper.t(), rhs, true);
+        let solution = solve_triangular(self.lower.t(), solution.view(), false);
+
+        let mut result = Array2::zeros(rhs.raw_dim());
+        for (row, &position) in solution.outer_iter().zip(&self.permutation) {
+            result.row_mut(position).assign(&row);
+        }
+        result
+    }
+}
+
+/// Solves `T · X = B` where `T` is either lower or upper triangular.
+pub(super) fn solve_triangular<T>(
+    triangular: ArrayView2<T>,
+    rhs: ArrayView2<T>,
+    lower: bool,
+) -> Array2<T>
+where
+    T: Float,
+{
+    let n = triangular.nrows();
+    let order = if lower {
+        (0..n).collect::<Vec<_>>()
+    } else {
+        (0..n).rev().collect()
+    };
+
+    let mut solution = rhs.to_owned();
+    for (position, &i) in order.iter().enumerate() {
+        for &k in &order[..position] {
+            let coefficient = triangular[[i, k]];
+            if coefficient != T::zero() {
+                let row = solution.row(k).to_owned();
+                solution.row_mut(i).scaled_add(-coefficient, &row);
+            }
+        }
+        let diagonal = triangular[[i, i]];
+        solution.row_mut(i).mapv_inplace(|el| el / diagonal);
+    }
+    solution
+}
+
+/// Returns the lower triangular Cholesky factor of the symmetric part of `matrix`.
+pub(super) fn cholesky<T>(matrix: ArrayView2<T>) -> Array2<T>
+where
+    T: Float,
+{
+    let n = matrix.nrows();
+    let two = T::from_f64(2.);
+
+    let mut lower = Array2::<T>::zeros((n, n));
+    for j in 0..n {
+        let row = lower.slice(s![j, ..j]).to_owned();
+        let diagonal = (matrix[[j, j]] - row.dot(&row)).sqrt();
+        lower[[j, j]] = diagonal;
+
+        for i in j + 1..n {
+            let dot = lower.slice(s![i, ..j]).dot(&row);
+            lower[[i, j]] = ((matrix[[i, j]] + matrix[[j, i]]) / two - dot) / diagonal;
+        }
+    }
+    lower
+}
+
+/// Returns the reduced QR decomposition of `matrix`, with the diagonal of `R` non-negative.
+pub(super) fn qr<T>(matrix: ArrayView2<T>) -> (Array2<T>, Array2<T>)
+where
+    T: Float,
+{
+    let (m, n) = matrix.dim();
+    let k = m.min(n);
+    let two = T::from_f64(2.);
+
+    let mut q = Array2::<T>::eye(m);
+    let mut r = matrix.to_owned();
+    for j in 0..k {
+        let mut v = r.slice(s![j.., j]).to_owned();
+        let norm = v.dot(&v).sqrt();
+        if norm == T::zero() {
+            continue;
+        }
+        let shift = if v[0] > T::zero() { norm } else { -norm };
+        v[0] += shift;
+        let scale = two / v.dot(&v);
+
+        let mut block = r.slice_mut(s![j.., j..]);
+        let w = v.dot(&block) * scale;
+        for (mut row, &el) in block.outer_iter_mut().zip(&v) {
+            row.scaled_add(-el, &w);
+        }
+
+        let mut block = q.slice_mut(s![.., j..]);
+        let w = block.dot(&v) * scale;
+        for (mut column, &el) in block.axis_iter_mut(Axis(1)).zip(&v) {
+            column.scaled_add(-el, &w);
+        }
+    }
+
+    let mut q = q.slice_move(s![.., ..k]);
+    let mut r = r.slice_move(s![..k, ..]);
+    for i in 0..k {
+        r.slice_mut(s![i, ..i]).fill(T::zero());
+        if r[[i, i]] < T::zero() {
+            r.row_mut(i).mapv_inplace(|el| -el);
+            q.column_mut(i).mapv_inplace(|el| -el);
+        }
+    }
+    (q, r)
+}
+
+/// Returns the eigenvalues, in ascending order, and the eigenvectors, as columns, of the symmetric
+/// part of `matrix`.
+pub(super) fn eigh<T>(matrix: ArrayView2<T>) -> (Array1<T>, Array2<T>)
+where
+    T: Float,
+{
+    let n = matrix.nrows();
+    let two = T::from_f64(2.);
+
+    let mut a = (&matrix + &matrix.t()) / two;
+    let mut vectors = Array2::<T>::eye(n);
+    let norm = a.iter().fold(T::zero(), |acc, &el| acc + el * el);
+    for _ in 0..MAX_SWEEPS {
+        let off = a
+            .indexed_iter()
+            .filter(|((i, j), _)| i != j)
+            .fold(T::zero(), |acc, (_, &el)| acc + el * el);
+        if off <= T::epsilon() * T::epsilon() * norm {
+            break;
+        }
+
+        for p in 0..n {
+            for q in p + 1..n {
+                let apq = a[[p, q]];
+                if apq == T::zero() {
+                    continue;
+                }
+                let (c, s) = rotation((a[[q, q]] - a[[p, p]]) / (two * apq));
+
+                rotate(a.view_mut(), p, q, c, s);
+                rotate(a.view_mut().reversed_axes(), p, q, c, s);
+                rotate(vectors.view_mut(), p, q, c, s);
+            }
+        }
+    }
+
+    let values = a.diag().to_owned();
+    let mut order = (0..n).collect::<Vec<_>>();
+    order.sort_by(|&i, &j| values[i].partial_cmp(&values[j]).unwrap_or(Ordering::Equal));
+
+    (
+        order.iter().map(|&i| values[i]).collect(),
+        vectors.select(Axis(1), &order),
+    )
+}
+
+/// Returns the reduced singular value decomposition `U · diag(S) · Vᵀ` of `matrix`, with the
+/// singular values in descending order.
+pub(super) fn svd<T>(matrix: ArrayView2<T>) -> (Array2<T>, Array1<T>, Array2<T>)
+where
+    T: Float,
+{
+    let (m, n) = matrix.dim();
+    if m < n {
+        let (u, s, vt) = svd(matrix.t());
+        return (vt.reversed_axes(), s, u.reversed_axes());
+    }
+    let two = T::from_f64(2.);
+
+    let mut u = matrix.to_owned();
+    let mut v = Array2::<T>::eye(n);
+    for _ in 0..MAX_SWEEPS {
+        let mut rotated = false;
+        for p in 0..n {
+            for q in p + 1..n {
+                let alpha = u.column(p).dot(&u.column(p));
+                let beta = u.column(q).dot(&u.column(q));
+                let gamma = u.column(p).dot(&u.column(q));
+                if gamma.abs() <= T::epsilon() * (alpha * beta).sqrt() {
+                    continue;
+                }
+                rotated = true;
+                let (c, s) = rotation((beta - alpha) / (two * gamma));
+
+                rotate(u.view_mut(), p, q, c, s);
+                rotate(v.view_mut(), p, q, c, s);
+            }
+        }
+        if !rotated {
+            break;
+        }
+    }
+
+    let values = u
+        .axis_iter(Axis(1))
+        .map(|column| column.dot(&column).sqrt())
+        .collect::<Array1<T>>();
+    let mut order = (0..n).collect::<Vec<_>>();
+    order.sort_by(|&i, &j| values[j].partial_cmp(&values[i]).unwrap_or(Ordering::Equal));
+
+    let mut u = u.select(Axis(1), &order);
+    let values = order.iter().map(|&i| values[i]).collect::<Array1<T>>();
+    for (mut column, &value) in u.axis_iter_mut(Axis(1)).zip(&values) {
+        if value > T::zero() {
+            column.mapv_inplace(|el| el / value);
+        }
+    }
+    let rank = values.iter().filter(|&&value| value > T::zero()).count();
+    complete_basis(u.view_mut(), rank);
+
+    (u, values, v.select(Axis(1), &order).reversed_axes())
+}
+
+/// Replaces the columns of `columns` following the first `rank` ones, which are orthonormal, so
+/// that all of them are.
+///
+/// Each new column is the standard basis vector with the largest component orthogonal to the
+/// previous columns, orthogonalized against them through Gram-Schmidt.
+fn complete_basis<T>(mut columns: ArrayViewMut2<T>, rank: usize)
+where
+    T: Float,
+{
+    let m = columns.nrows();
+
+    for j in rank..columns.ncols() {
+        let completed = columns.slice(s![.., ..j]);
+        let column = (0..m)
+            .map(|i| {
+                let mut column = Array1::zeros(m);
+                column[i] = T::one();
+                for previous in completed.axis_iter(Axis(1)) {
+                    column.scaled_add(-previous[i], &previous);
+                }
+                column
+            })
+            .max_by(|x, y| x.dot(x).partial_cmp(&y.dot(y)).unwrap_or(Ordering::Equal))
+            .unwrap();
+
+        let norm = column.dot(&column).sqrt();
+        columns.column_mut(j).assign(&(column / norm));
+    }
+}
+
+/// Returns the cosine and the sine of the Jacobi rotation annihilating an off-diagonal element,
+/// given the cotangent of twice its angle.
+fn rotation<T>(cot: T) -> (T, T)
+where
+    T: Float,
+{
+    let t = cot.signum() / (cot.abs() + (cot * cot + T::one()).sqrt());
+    let c = T::one() / (t * t + T::one()).sqrt();
+
+    (c, c * t)
+}
+
+/// Applies a Jacobi rotation to the columns `p` and `q` of `matrix`.
+fn rotate<T>(mut matrix: ArrayViewMut2<T>, p: usize, q: usize, c: T, s: T)
+where
+    T: Float,
+{
+    for mut row in matrix.outer_iter_mut() {
+        let (x, y) = (row[p], row[q]);
+        row[p] = c * x - s * y;
+        row[q] = s * x + c * y;
+    }
+}
+
+/// Returns the gradient of the inverse `inverse` of a matrix.
+pub(super) fn inverse_backward<T>(inverse: ArrayView2<T>, gradient: ArrayView2<T>) -> Array2<T>
+where
+    T: Float,
+{
+    -inverse.t().dot(&gradient).dot(&inverse.t())
+}
+
+/// Returns the gradient of the Cholesky factor `lower` of a matrix.
+pub(super) fn cholesky_backward<T>(lower: ArrayView2<T>, gradient: ArrayView2<T>) -> Array2<T>
+where
+    T: Float,
+{
+    let half = T::from_f64(0.5);
+
+    let mut phi = lower.t().dot(&gradient);
+    for ((i, j), el) in phi.indexed_iter_mut() {
+        match i.cmp(&j) {
+            Ordering::Less => *el = T::zero(),
+            Ordering::Equal => *el *= half,
+            Ordering::Greater => {}
+        }
+    }
+
+    let left = solve_triangular(lower.t(), phi.view(), false);
+    let s = solve_triangular(lower.t(), left.t(), false).reversed_axes();
+
+    (&s + &s.t()) * half
+}
+
+/// Returns the gradient of the reduced QR decomposition `q`, `r` of a matrix.
+pub(super) fn qr_backward<T>(
+    q: ArrayView2<T>,
+    r: ArrayView2<T>,
+    q_gradient: Option<ArrayView2<T>>,
+    r_gradient: Option<ArrayView2<T>>,
+) -> Array2<T>
+where
+    T: Float,
+{
+    let (m, n) = (q.nrows(), r.ncols());
+
+    if m < n {
+        // The matrix is split as [X | Y], where X = Q · U is square and Y = Q · V.
+        let (u, v) = (r.slice(s![.., ..m]), r.slice(s![.., m..]));
+
+        let mut q_gradient = q_gradient.map_or_else(|| Array2::zeros((m, m)), |g| g.to_owned());
+        let mut y_result = Array2::zeros((m, n - m));
+        if let Some(v_gradient) = r_gradient.map(|gradient| gradient.slice_move(s![.., m..])) {
+            q_gradient += &q.dot(&v).dot(&v_gradient.t());
+            y_result = q.dot(&v_gradient);
+        }
+        let u_gradient = r_gradient.map(|gradient| gradient.slice_move(s![.., ..m]));
+        let x_result = qr_backward(q, u, Some(q_gradient.view()), u_gradient);
+
+        return concatenate(Axis(1), &[x_result.view(), y_result.view()]).unwrap();
+    }
+
+    let mut inner = Array2::zeros((n, n));
+    if let Some(r_gradient) = r_gradient {
+        inner += &r.dot(&r_gradient.t());
+    }
+    if let Some(q_gradient) = q_gradient {
+        inner -= &q_gradient.t().dot(&q);
+    }
+    let copyltu = Array2::from_shape_fn((n, n), |(i, j)| inner[[i.max(j), i.min(j)]]);
+
+    let mut result = q.dot(&copyltu);
+    if let Some(q_gradient) = q_gradient {
+        result += &q_gradient;
+    }
+    solve_triangular(r, result.t(), false).reversed_axes()
+}
+
+/// Returns the gradient of the eigendecomposition `values`, `vectors` of a symmetric matrix.
+pub(super) fn eigh_backward<T>(
+    values: ArrayView1<T>,
+    vectors: ArrayView2<T>,
+    values_gradient: Option<ArrayView1<T>>,
+    vectors_gradient: Option<ArrayView2<T>>,
+) -> Array2<T>
+where
+    T: Float,
+{
+    let n = values.len();
+    let half = T::from_f64(0.5);
+
+    let mut inner = Array2::zeros((n, n));
+    if let Some(vectors_gradient) = vectors_gradient {
+        inner = vectors.t().dot(&vectors_gradient);
+        for ((i, j), el) in inner.indexed_iter_mut() {
+            *el = if i == j {
+                T::zero()
+            } else {
+                *el / (values[j] - values[i])
+            };
+        }
+    }
+    if let Some(values_gradient) = values_gradient {
+        inner
+            .diag_mut()
+            .zip_mut_with(&values_gradient, |el, &g| *el += g);
+    }
+
+    let result = vectors.dot(&inner).dot(&vectors.t());
+    (&result + &result.t()) * half
+}
+
+/// Returns the gradient of the reduced singular value decomposition `u`, `s`, `vt` of a matrix.
+pub(super) fn svd_backward<T>(
+    u: ArrayView2<T>,
+    s: ArrayView1<T>,
+    vt: ArrayView2<T>,
+    u_gradient: Option<ArrayView2<T>>,
+    s_gradient: Option<ArrayView1<T>>,
+    vt_gradient: Option<ArrayView2<T>>,
+) -> Array2<T>
+where
+    T: Float,
+{
+    let (m, n, k) = (u.nrows(), vt.ncols(), s.len());
+    let skew = |x: Array2<T>| &x - &x.t();
+
+    let mut inner = Array2::zeros((k, k));
+    if let Some(u_gradient) = u_gradient {
+        inner += &(skew(u.t().dot(&u_gradient)) * s.view().insert_axis(Axis(0)));
+    }
+    if let Some(vt_gradient) = vt_gradient {
+        inner += &(skew(vt.dot(&vt_gradient.t())) * s.view().insert_axis(Axis(1)));
+    }
+    for ((i, j), el) in inner.indexed_iter_mut() {
+        if i != j {
+            *el /= s[j] * s[j] - s[i] * s[i];
+        }
+    }
+    if let Some(s_gradient) = s_gradient {
+        inner
+            .diag_mut()
+            .zip_mut_with(&s_gradient, |el, &g| *el += g);
+    }
+
+    match (u_gradient, vt_gradient) {
+        (Some(u_gradient), _) if m > k => {
+            let scaled = &u_gradient / &s.view().insert_axis(Axis(0));
+            let result = u.dot(&inner) + &scaled - u.dot(&u.t().dot(&scaled));
+            result.dot(&vt)
+        }
+        (_, Some(vt_gradient)) if n > k => {
+            let scaled = &vt_gradient / &s.view().insert_axis(Axis(1));
+            let result = inner.dot(&vt) + &scaled - scaled.dot(&vt.t()).dot(&vt);
+            u.dot(&result)
+        }
+        _ => u.dot(&inner).dot(&vt),
+    }
+}
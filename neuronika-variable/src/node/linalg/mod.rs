@@ -0,0 +1,655 @@
+mod kernels;
+
+use std::ops::Range;
+
+use ndarray::{
+    s, Array, Array1, Array2, ArrayView1, ArrayView2, Axis, CowArray, Dimension, Ix2, Ix3, Order,
+};
+
+use crate::{
+    autograd::{Backward, Forward, Reads},
+    cell::Rc,
+    gradient::Gradient,
+    graph::Buffer,
+    utils::{matrices_shape, Shared},
+    Float,
+};
+
+use kernels::Lu;
+
+/// Linear algebra function computed by a [`Linalg`] op over a batch of matrices.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Decomposition {
+    Inverse,
+    Determinant,
+    Slogdet,
+    Cholesky,
+    Qr,
+    Eigh,
+    Svd,
+}
+
+impl Decomposition {
+    /// Returns the number of rows and columns of each output for a matrix of the given size.
+    /// Scalars and vectors are treated as matrices with a single row.
+    fn shapes(self, rows: usize, columns: usize) -> Vec<(usize, usize)> {
+        let k = rows.min(columns);
+
+        match self {
+            Self::Inverse | Self::Cholesky => vec![(rows, columns)],
+            Self::Determinant => vec![(1, 1)],
+            Self::Slogdet => vec![(1, 1), (1, 1)],
+            Self::Qr => vec![(rows, k), (k, columns)],
+            Self::Eigh => vec![(1, columns), (rows, columns)],
+            Self::Svd => vec![(rows, k), (1, k), (k, columns)],
+        }
+    }
+
+    /// Returns the positions of each output among the packed outputs of a matrix of the given
+    /// size.
+    pub(crate) fn ranges(self, rows: usize, columns: usize) -> Vec<Range<usize>> {
+        let mut offset = 0;
+
+        self.shapes(rows, columns)
+            .into_iter()
+            .map(|(rows, columns)| {
+                offset += rows * columns;
+                offset - rows * columns..offset
+            })
+            .collect()
+    }
+
+    /// Computes the outputs for `matrix`.
+    fn evaluate<T>(self, matrix: ArrayView2<T>) -> Vec<Array2<T>>
+    where
+        T: Float,
+    {
+        let scalar = |value| Array2::from_elem((1, 1), value);
+        let row = |vector: Array1<T>| vector.insert_axis(Axis(0));
+
+        match self {
+            Self::Inverse => vec![Lu::new(matrix).solve(Array2::eye(matrix.nrows()).view())],
+            Self::Determinant => vec![scalar(Lu::new(matrix).det())],
+            Self::Slogdet => {
+                let (sign, logabsdet) = Lu::new(matrix).slogdet();
+                vec![scalar(sign), scalar(logabsdet)]
+            }
+            Self::Cholesky => vec![kernels::cholesky(matrix)],
+            Self::Qr => {
+                let (q, r) = kernels::qr(matrix);
+                vec![q, r]
+            }
+            Self::Eigh => {
+                let (values, vectors) = kernels::eigh(matrix);
+                vec![row(values), vectors]
+            }
+            Self::Svd => {
+                let (u, s, vt) = kernels::svd(matrix);
+                vec![u, row(s), vt]
+            }
+        }
+    }
+
+    /// Returns `true` if the gradient is computed from the matrix rather than from the outputs.
+    fn reads_matrix(self) -> bool {
+        matches!(self, Self::Determinant | Self::Slogdet)
+    }
+
+    /// Computes the gradient with respect to `matrix` given its outputs and their gradients.
+    ///
+    /// The gradients of the outputs that don't contribute to the result are `None`, and so is
+    /// `matrix` if it isn't [read](Decomposition::reads_matrix()).
+    fn gradient<T>(
+        self,
+        matrix: Option<ArrayView2<T>>,
+        outputs: &[ArrayView2<T>],
+        gradients: &[Option<ArrayView2<T>>],
+    ) -> Option<Array2<T>>
+    where
+        T: Float,
+    {
+        let transposed_inverse = |matrix: ArrayView2<T>| {
+            let lu = Lu::new(matrix);
+            let inverse = lu.solve_transposed(Array2::eye(matrix.nrows()).view());
+            (lu, inverse)
+        };
+
+        let gradient = match self {
+            Self::Inverse => kernels::inverse_backward(outputs[0], gradients[0]?),
+            Self::Determinant => {
+                let (lu, inverse) = transposed_inverse(matrix.unwrap());
+                inverse * (gradients[0]?[[0, 0]] * lu.det())
+            }
+            // The sign is piecewise constant, so only the logarithm contributes.
+            Self::Slogdet => transposed_inverse(matrix.unwrap()).1 * gradients[1]?[[0, 0]],
+            Self::Cholesky => kernels::cholesky_backward(outputs[0], gradients[0]?),
+            Self::Qr => kernels::qr_backward(outputs[0], outputs[1], gradients[0], gradients[1]),
+            Self::Eigh => kernels::eigh_backward(
+                outputs[0].row(0),
+                outputs[1],
+                gradients[0].map(|gradient| gradient.index_axis_move(Axis(0), 0)),
+                gradients[1],
+            ),
+            Self::Svd => kernels::svd_backward(
+                outputs[0],
+                outputs[1].row(0),
+                outputs[2],
+                gradients[0],
+                gradients[1].map(|gradient| gradient.index_axis_move(Axis(0), 0)),
+                gradients[2],
+            ),
+        };
+
+        Some(gradient)
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Inverse => "Inverse",
+            Self::Determinant => "Determinant",
+            Self::Slogdet => "Slogdet",
+            Self::Cholesky => "Cholesky",
+            Self::Qr => "Qr",
+            Self::Eigh => "Eigh",
+            Self::Svd => "Svd",
+        }
+    }
+}
+
+/// Views `array` as a batch of matrices, collapsing all the axes but the last two.
+fn matrices<T, D>(array: &Array<T, D>) -> CowArray<'_, T, Ix3>
+where
+    T: Float,
+    D: Dimension,
+{
+    let (batch, matrix) = array.shape().split_at(array.ndim() - 2);
+    let shape = (batch.iter().product(), matrix[0], matrix[1]);
+
+    array.to_shape((shape, Order::RowMajor)).unwrap()
+}
+
+/// Splits the packed outputs `packed` of a matrix into matrices of the given shapes.
+fn unpack<'a, T>(packed: ArrayView1<'a, T>, shapes: &[(usize, usize)]) -> Vec<ArrayView2<'a, T>> {
+    let mut rest = packed;
+
+    shapes
+        .iter()
+        .map(|&(rows, columns)| {
+            let (output, tail) = rest.split_at(Axis(0), rows * columns);
+            rest = tail;
+            output.into_shape((rows, columns)).unwrap()
+        })
+        .collect()
+}
+
+/// Computes a [`Decomposition`] of each matrix of a batch.
+///
+/// All the outputs of a matrix are computed at once and packed in a row of the data, in the order
+/// given by [`Decomposition::ranges()`]. Each of them is then copied out by an [`Unpack`] op.
+pub(crate) struct Linalg<D, T>
+where
+    D: Dimension,
+    T: Float,
+{
+    operand_data: Shared<Array<T, D>>,
+    data: Shared<Array2<T>>,
+    decomposition: Decomposition,
+}
+
+impl<D, T> Linalg<D, T>
+where
+    D: Dimension,
+    T: Float,
+{
+    pub(crate) fn new(
+        operand_data: Shared<Array<T, D>>,
+        data: Shared<Array2<T>>,
+        decomposition: Decomposition,
+    ) -> Self {
+        Self {
+            operand_data,
+            data,
+            decomposition,
+        }
+    }
+}
+
+impl<D, T> Forward for Linalg<D, T>
+where
+    D: Dimension,
+    T: Float,
+{
+    fn forward(&self) {
+        let operand_data = self.operand_data.borrow();
+        let mut data = self.data.borrow_mut();
+
+        matrices(&operand_data)
+            .outer_iter()
+            .zip(data.outer_iter_mut())
+            .for_each(|(matrix, mut packed)| {
+                let outputs = self.decomposition.evaluate(matrix);
+                packed
+                    .iter_mut()
+                    .zip(outputs.iter().flatten())
+                    .for_each(|(el, &value)| *el = value);
+            });
+    }
+
+    fn name(&self) -> &'static str {
+        self.decomposition.name()
+    }
+
+    fn operands(&self) -> Vec<Buffer> {
+        vec![Buffer::new(&self.operand_data)]
+    }
+
+    fn data(&self) -> Buffer {
+        Buffer::new(&self.data)
+    }
+}
+
+pub(crate) struct LinalgBackward<D, T>
+where
+    D: Dimension,
+    T: Float,
+{
+    operand_data: Shared<Array<T, D>>,
+    operand_gradient: Rc<Gradient<Array<T, D>, D>>,
+    data: Shared<Array2<T>>,
+    gradient: Rc<Gradient<Array2<T>, Ix2>>,
+    decomposition: Decomposition,
+}
+
+impl<D, T> LinalgBackward<D, T>
+where
+    D: Dimension,
+    T: Float,
+{
+    pub(crate) fn new(
+        operand_data: Shared<Array<T, D>>,
+        operand_gradient: Rc<Gradient<Array<T, D>, D>>,
+        data: Shared<Array2<T>>,
+        gradient: Rc<Gradient<Array2<T>, Ix2>>,
+        decomposition: Decomposition,
+    ) -> Self {
+        Self {
+            operand_data,
+            operand_gradient,
+            data,
+            gradient,
+            decomposition,
+        }
+    }
+}
+
+impl<D, T> Backward for LinalgBackward<D, T>
+where
+    D: Dimension,
+    T: Float,
+{
+    fn backward(&self) {
+        let (rows, columns) = matrices_shape(&self.operand_gradient.shape(), false);
+        let shapes = self.decomposition.shapes(rows, columns);
+
+        let operand_data = self
+            .decomposition
+            .reads_matrix()
+            .then(|| self.operand_data.borrow());
+        let matrices = operand_data.as_ref().map(|data| matrices(data));
+        let (data, gradient) = (self.data.borrow(), self.gradient.borrow());
+
+        let values = data
+            .outer_iter()
+            .zip(gradient.outer_iter())
+            .enumerate()
+            .flat_map(|(position, (packed, packed_gradient))| {
+                // Outputs with a zero gradient, such as the unused ones, are skipped. This avoids
+                // the singularities of the terms they would contribute.
+                let gradients = unpack(packed_gradient, &shapes)
+                    .into_iter()
+                    .map(|gradient| {
+                        gradient
+                            .iter()
+                            .any(|&el| el != T::zero())
+                            .then_some(gradient)
+                    })
+                    .collect::<Vec<_>>();
+                let matrix = matrices
+                    .as_ref()
+                    .map(|matrices| matrices.index_axis(Axis(0), position));
+
+                self.decomposition
+                    .gradient(matrix, &unpack(packed, &shapes), &gradients)
+                    .unwrap_or_else(|| Array2::zeros((rows, columns)))
+            })
+            .collect::<Vec<_>>();
+
+        self.operand_gradient
+            .borrow_mut()
+            .iter_mut()
+            .zip(values)
+            .for_each(|(el, value)| *el += value);
+    }
+
+    fn reads(&self) -> Reads {
+        if self.decomposition.reads_matrix() {
+            Reads::All
+        } else {
+            Reads::Data
+        }
+    }
+}
+
+/// Copies an output of a [`Linalg`] op out of its packed outputs.
+pub(crate) struct Unpack<E, T>
+where
+    E: Dimension,
+    T: Float,
+{
+    packed_data: Shared<Array2<T>>,
+    data: Shared<Array<T, E>>,
+    range: Range<usize>,
+}
+
+impl<E, T> Unpack<E, T>
+where
+    E: Dimension,
+    T: Float,
+{
+    pub(crate) fn new(
+        packed_data: Shared<Array2<T>>,
+        data: Shared<Array<T, E>>,
+        range: Range<usize>,
+    ) -> Self {
+        Self {
+            packed_data,
+            data,
+            range,
+        }
+    }
+}
+
+impl<E, T> Forward for Unpack<E, T>
+where
+    E: Dimension,
+    T: Float,
+{
+    fn forward(&self) {
+        let packed_data = self.packed_data.borrow();
+
+        self.data
+            .borrow_mut()
+            .iter_mut()
+            .zip(packed_data.slice(s![.., self.range.clone()]))
+            .for_each(|(el, &value)| *el = value);
+    }
+
+    fn name(&self) -> &'static str {
+        "Unpack"
+    }
+
+    fn operands(&self) -> Vec<Buffer> {
+        vec![Buffer::new(&self.packed_data)]
+    }
+
+    fn data(&self) -> Buffer {
+        Buffer::new(&self.data)
+    }
+}
+
+pub(crate) struct UnpackBackward<E, T>
+where
+    E: Dimension,
+    T: Float,
+{
+    packed_gradient: Rc<Gradient<Array2<T>, Ix2>>,
+    gradient: Rc<Gradient<Array<T, E>, E>>,
+    range: Range<usize>,
+}
+
+impl<E, T> UnpackBackward<E, T>
+where
+    E: Dimension,
+    T: Float,
+{
+    pub(crate) fn new(
+        packed_gradient: Rc<Gradient<Array2<T>, Ix2>>,
+        gradient: Rc<Gradient<Array<T, E>, E>>,
+        range: Range<usize>,
+    ) -> Self {
+        Self {
+            packed_gradient,
+            gradient,
+            range,
+        }
+    }
+}
+
+impl<E, T> Backward for UnpackBackward<E, T>
+where
+    E: Dimension,
+    T: Float,
+{
+    fn backward(&self) {
+        let gradient = self.gradient.borrow();
+
+        self.packed_gradient
+            .borrow_mut()
+            .slice_mut(s![.., self.range.clone()])
+            .iter_mut()
+            .zip(gradient.iter())
+            .for_each(|(el, &value)| *el += value);
+    }
+
+    fn reads(&self) -> Reads {
+        Reads::Nothing
+    }
+}
+
+pub(crate) struct Solve<D, T>
+where
+    D: Dimension,
+    T: Float,
+{
+    left_data: Shared<Array<T, D>>,
+    right_data: Shared<Array<T, D>>,
+    data: Shared<Array<T, D>>,
+}
+
+impl<D, T> Solve<D, T>
+where
+    D: Dimension,
+    T: Float,
+{
+    pub(crate) fn new(
+        left_data: Shared<Array<T, D>>,
+        right_data: Shared<Array<T, D>>,
+        data: Shared<Array<T, D>>,
+    ) -> Self {
+        Self {
+            left_data,
+            right_data,
+            data,
+        }
+    }
+}
+
+impl<D, T> Forward for Solve<D, T>
+where
+    D: Dimension,
+    T: Float,
+{
+    fn forward(&self) {
+        let (left_data, right_data) = (self.left_data.borrow(), self.right_data.borrow());
+        let values = matrices(&left_data)
+            .outer_iter()
+            .zip(matrices(&right_data).outer_iter())
+            .flat_map(|(left, right)| Lu::new(left).solve(right))
+            .collect::<Vec<_>>();
+
+        self.data
+            .borrow_mut()
+            .iter_mut()
+            .zip(values)
+            .for_each(|(el, value)| *el = value);
+    }
+
+    fn name(&self) -> &'static str {
+        "Solve"
+    }
+
+    fn operands(&self) -> Vec<Buffer> {
+        vec![Buffer::new(&self.left_data), Buffer::new(&self.right_data)]
+    }
+
+    fn data(&self) -> Buffer {
+        Buffer::new(&self.data)
+    }
+}
+
+/// Solves the transposed systems of `left` against `gradient`, matrix by matrix.
+fn solve_transposed<T, D>(left: &Array<T, D>, gradient: &Array<T, D>) -> Vec<Array2<T>>
+where
+    T: Float,
+    D: Dimension,
+{
+    matrices(left)
+        .outer_iter()
+        .zip(matrices(gradient).outer_iter())
+        .map(|(left, gradient)| Lu::new(left).solve_transposed(gradient))
+        .collect()
+}
+
+pub(crate) struct SolveBackwardLeft<D, T>
+where
+    D: Dimension,
+    T: Float,
+{
+    left_data: Shared<Array<T, D>>,
+    data: Shared<Array<T, D>>,
+    left_gradient: Rc<Gradient<Array<T, D>, D>>,
+    gradient: Rc<Gradient<Array<T, D>, D>>,
+}
+
+impl<D, T> SolveBackwardLeft<D, T>
+where
+    D: Dimension,
+    T: Float,
+{
+    pub(crate) fn new(
+        left_data: Shared<Array<T, D>>,
+        data: Shared<Array<T, D>>,
+        left_gradient: Rc<Gradient<Array<T, D>, D>>,
+        gradient: Rc<Gradient<Array<T, D>, D>>,
+    ) -> Self {
+        Self {
+            left_data,
+            data,
+            left_gradient,
+            gradient,
+        }
+    }
+}
+
+impl<D, T> Backward for SolveBackwardLeft<D, T>
+where
+    D: Dimension,
+    T: Float,
+{
+    fn backward(&self) {
+        let data = self.data.borrow();
+        let right_gradients = solve_transposed(&self.left_data.borrow(), &self.gradient.borrow());
+        let values = right_gradients
+            .iter()
+            .zip(matrices(&data).outer_iter())
+            .flat_map(|(right_gradient, solution)| right_gradient.dot(&solution.t()))
+            .collect::<Vec<_>>();
+
+        self.left_gradient
+            .borrow_mut()
+            .iter_mut()
+            .zip(values)
+            .for_each(|(el, value)| *el -= value);
+    }
+}
+
+pub(crate) struct SolveBackwardRight<D, T>
+where
+    D: Dimension,
+    T: Float,
+{
+    left_data: Shared<Array<T, D>>,
+    right_gradient: Rc<Gradient<Array<T, D>, D>>,
+    gradient: Rc<Gradient<Array<T, D>, D>>,
+}
+
+impl<D, T> SolveBackwardRight<D, T>
+where
+    D: Dimension,
+    T: Float,
+{
+    pub(crate) fn new(
+        left_data: Shared<Array<T, D>>,
+        right_gradient: Rc<Gradient<Array<T, D>, D>>,
+        gradient: Rc<Gradient<Array<T, D>, D>>,
+    ) -> Self {
+        Self {
+            left_data,
+            right_gradient,
+            gradient,
+        }
+    }
+}
+
+impl<D, T> Backward for SolveBackwardRight<D, T>
+where
+    D: Dimension,
+    T: Float,
+{
+    fn backward(&self) {
+        let right_gradients = solve_transposed(&self.left_data.borrow(), &self.gradient.borrow());
+
+        self.right_gradient
+            .borrow_mut()
+            .iter_mut()
+            .zip(right_gradients.into_iter().flatten())
+            .for_each(|(el, value)| *el += value);
+    }
+
+    fn reads(&self) -> Reads {
+        Reads::Operands
+    }
+}
+
+pub(crate) struct SolveBackward<D, T>
+where
+    D: Dimension,
+    T: Float,
+{
+    left: SolveBackwardLeft<D, T>,
+    right: SolveBackwardRight<D, T>,
+}
+
+impl<D, T> SolveBackward<D, T>
+where
+    D: Dimension,
+    T: Float,
+{
+    pub(crate) fn new(left: SolveBackwardLeft<D, T>, right: SolveBackwardRight<D, T>) -> Self {
+        Self { left, right }
+    }
+}
+
+impl<D, T> Backward for SolveBackward<D, T>
+where
+    D: Dimension,
+    T: Float,
+{
+    fn backward(&self) {
+        self.left.backward();
+        self.right.backward();
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,255 @@
+use ndarray::{Array, Array2, Array3};
+
+/// Returns a batch of two well conditioned 3×3 matrices.
+fn matrices() -> Array3<f64> {
+    Array::from_shape_fn((2, 3, 3), |(b, i, j)| {
+        ((b + 2 * i + 3 * j) % 5) as f64 / 4. - 0.3 + if i == j { 1.5 } else { 0. }
+    })
+}
+
+/// Returns a batch of two symmetric positive definite 3×3 matrices.
+fn symmetric() -> Array3<f64> {
+    let a = matrices();
+    Array::from_shape_fn((2, 3, 3), |(b, i, j)| {
+        (0..3).map(|k| a[[b, i, k]] * a[[b, j, k]]).sum::<f64>()
+    })
+}
+
+/// Returns a 4×3 matrix.
+fn tall() -> Array2<f64> {
+    Array::from_shape_fn((4, 3), |(i, j)| ((i * 3 + j * 7) % 5) as f64 / 3. - 0.6)
+}
+
+/// Returns the right-hand sides of the systems of [`matrices()`].
+fn rhs() -> Array3<f64> {
+    Array::from_shape_fn((2, 3, 2), |(b, i, j)| (b + i * j) as f64 / 3. - 0.2)
+}
+
+mod forward {
+    use super::*;
+    use crate::Solve;
+    use ndarray::{array, Axis};
+
+    fn close(x: &Array2<f64>, y: &Array2<f64>) -> bool {
+        x.abs_diff_eq(y, 1e-10)
+    }
+
+    #[test]
+    fn square() {
+        let (a, spd, rhs) = (matrices(), symmetric(), rhs());
+
+        let (inverse, det) = (
+            crate::from_ndarray(a.clone()).inv(),
+            crate::from_ndarray(a.clone()).det(),
+        );
+        let (sign, logabsdet) = crate::from_ndarray(a.clone()).slogdet();
+        let solution = crate::from_ndarray(a.clone()).solve(crate::from_ndarray(rhs.clone()));
+        let lower = crate::from_ndarray(spd.clone()).cholesky();
+        let (values, vectors) = crate::from_ndarray(spd.clone()).eigh();
+        for var in [&inverse, &solution, &lower, &vectors] {
+            var.forward();
+        }
+        for var in [&det, &sign, &logabsdet] {
+            var.forward();
+        }
+        values.forward();
+        for b in 0..2 {
+            let (a, spd) = (a.index_axis(Axis(0), b), spd.index_axis(Axis(0), b));
+            let (inverse, lower) = (inverse.data(), lower.data());
+            let (inverse, lower) = (inverse.index_axis(Axis(0), b), lower.index_axis(Axis(0), b));
+            let vectors = vectors.data().index_axis(Axis(0), b).to_owned();
+            let values = values.data().index_axis(Axis(0), b).to_owned();
+
+            assert!(close(&a.dot(&inverse), &Array2::eye(3)));
+            assert!(close(
+                &a.dot(&solution.data().index_axis(Axis(0), b)),
+                &rhs.index_axis(Axis(0), b).to_owned()
+            ));
+            assert!((sign.data()[b] * logabsdet.data()[b].exp() - det.data()[b]).abs() < 1e-10);
+            assert!(close(&lower.dot(&lower.t()), &spd.to_owned()));
+            assert_eq!(lower[[0, 1]], 0.);
+            assert!(close(
+                &vectors.dot(&Array2::from_diag(&values)).dot(&vectors.t()),
+                &spd.to_owned()
+            ));
+            assert!(values[0] <= values[1] && values[1] <= values[2]);
+        }
+
+        let diagonal = crate::from_ndarray(array![[2_f64, 1.], [0., -3.]]).det();
+        diagonal.forward();
+        assert!((diagonal.item() + 6.).abs() < 1e-12);
+    }
+
+    #[test]
+    fn rectangular() {
+        let tall = tall();
+
+        for matrix in [tall.clone(), tall.reversed_axes()] {
+            let (q, r) = crate::from_ndarray(matrix.clone()).qr();
+            let (u, singular, vt) = crate::from_ndarray(matrix.clone()).svd();
+            for var in [&q, &r, &u, &vt] {
+                var.forward();
+            }
+            singular.forward();
+            let (q, r, u, vt) = (q.data(), r.data(), u.data(), vt.data());
+
+            assert_eq!(q.ncols(), 3);
+            assert!(close(&q.dot(&*r), &matrix));
+            assert!(close(&q.t().dot(&*q), &Array2::eye(3)));
+            assert!(r.diag().iter().all(|&el| el >= 0.));
+            assert_eq!(r[[2, 1]], 0.);
+            assert!(close(
+                &u.dot(&Array2::from_diag(&*singular.data())).dot(&*vt),
+                &matrix
+            ));
+            assert!(close(&vt.dot(&vt.t()), &Array2::eye(3)));
+            assert!(singular.data()[0] >= singular.data()[1]);
+        }
+    }
+
+    #[test]
+    fn single_decomposition() {
+        let (u, s, vt) = crate::from_ndarray(tall()).svd();
+        let y = u.sum() + s.sum() + vt.sum();
+
+        let decompositions = y
+            .graph()
+            .nodes()
+            .iter()
+            .filter(|node| node.op() == Some("Svd"))
+            .count();
+        assert_eq!(decompositions, 1);
+    }
+
+    #[test]
+    fn rank_deficient() {
+        let matrix: Array2<f64> = array![[1., 2., 0.], [2., 4., 0.], [3., 6., 0.], [-1., -2., 0.]];
+
+        for matrix in [matrix.clone(), matrix.reversed_axes()] {
+            let (u, singular, vt) = crate::from_ndarray(matrix.clone()).svd();
+            for var in [&u, &vt] {
+                var.forward();
+            }
+            singular.forward();
+            let (u, vt) = (u.data(), vt.data());
+
+            assert!(singular.data().iter().skip(1).all(|&el| el.abs() < 1e-10));
+            assert!(close(
+                &u.dot(&Array2::from_diag(&*singular.data())).dot(&*vt),
+                &matrix
+            ));
+            assert!(close(&u.t().dot(&*u), &Array2::eye(3)));
+            assert!(close(&vt.dot(&vt.t()), &Array2::eye(3)));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "error: variable of shape [2, 3] is not a batch of square matrices.")]
+    fn not_square() {
+        crate::ones((2, 3)).inv();
+    }
+}
+
+mod backward {
+    use super::*;
+    use crate::{utils::weights, Solve, VarDiff};
+    use ndarray::{ArrayD, Ix0, IxDyn};
+
+    /// Checks the gradient of `f` at `data` against finite differences.
+    fn check<F>(f: F, data: ArrayD<f64>)
+    where
+        F: Fn(VarDiff<IxDyn, f64>) -> VarDiff<Ix0, f64>,
+    {
+        let x = crate::from_ndarray(data).requires_grad();
+        let inputs: [crate::Parameter<f64>; 1] = [x.clone().into()];
+        assert_eq!(
+            crate::gradcheck(|| f(x.clone()), &inputs, 1e-6, 1e-7, 1e-5),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn square() {
+        let (a, spd, rhs) = (matrices().into_dyn(), symmetric().into_dyn(), rhs());
+
+        check(|x| (x.inv() * weights(&[2, 3, 3], 0.7)).sum(), a.clone());
+        check(|x| (x.det() * weights(&[2], 0.7)).sum(), a.clone());
+        check(|x| (x.slogdet().1 * weights(&[2], 0.7)).sum(), a.clone());
+        check(
+            |x| {
+                let rhs = crate::from_ndarray(rhs.clone().into_dyn());
+                (x.solve(rhs) * weights(&[2, 3, 2], 0.7)).sum()
+            },
+            a.clone(),
+        );
+        check(
+            |x| {
+                let coefficients = crate::from_ndarray(a.clone());
+                (coefficients.solve(x) * weights(&[2, 3, 2], 0.7)).sum()
+            },
+            rhs.clone().into_dyn(),
+        );
+        check(
+            |x| (x.cholesky() * weights(&[2, 3, 3], 0.7)).sum(),
+            spd.clone(),
+        );
+        check(|x| (x.eigh().0 * weights(&[2, 3], 0.7)).sum(), spd.clone());
+        check(
+            |x| {
+                let vectors = x.eigh().1;
+                (vectors.clone() * vectors * weights(&[2, 3, 3], 0.7)).sum()
+            },
+            spd,
+        );
+    }
+
+    #[test]
+    fn rectangular() {
+        let tall = tall();
+
+        for matrix in [tall.clone().into_dyn(), tall.reversed_axes().into_dyn()] {
+            let (rows, columns) = (matrix.shape()[0], matrix.shape()[1]);
+            let k = rows.min(columns);
+
+            check(
+                |x| (x.qr().0 * weights(&[rows, k], 0.7)).sum(),
+                matrix.clone(),
+            );
+            check(
+                |x| (x.qr().1 * weights(&[k, columns], 0.7)).sum(),
+                matrix.clone(),
+            );
+            check(|x| (x.svd().1 * weights(&[k], 0.7)).sum(), matrix.clone());
+            check(
+                |x| {
+                    let u = x.svd().0;
+                    (u.clone() * u * weights(&[rows, k], 0.7)).sum()
+                },
+                matrix.clone(),
+            );
+            check(
+                |x| {
+                    let vt = x.svd().2;
+                    (vt.clone() * vt * weights(&[k, columns], 0.7)).sum()
+                },
+                matrix.clone(),
+            );
+            check(
+                |x| {
+                    let (u, s, vt) = x.svd();
+                    (u.clone() * u * weights(&[rows, k], 0.7)).sum()
+                        + (s * weights(&[k], 0.3)).sum()
+                        + (vt.clone() * vt * weights(&[k, columns], 0.5)).sum()
+                },
+                matrix.clone(),
+            );
+            check(
+                |x| {
+                    let (q, r) = x.qr();
+                    (q * weights(&[rows, k], 0.7)).sum() + (r * weights(&[k, columns], 0.3)).sum()
+                },
+                matrix,
+            );
+        }
+    }
+}
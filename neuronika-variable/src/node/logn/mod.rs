@@ -1,58 +1,106 @@
-use std::rc::Rc;
+use std::any::Any;
 
 use ndarray::{Array, Dimension, Zip};
 
 use crate::{
-    autograd::{Backward, Forward},
+    autograd::{Backward, Forward, Reads},
+    cell::Rc,
+    fusion::{
+        fusible_len, standard_len, BackwardKernel, ElementwiseBackward, ElementwiseForward,
+        ForwardKernel,
+    },
     gradient::Gradient,
+    graph::Buffer,
     utils::Shared,
+    Float,
 };
 
-pub(crate) struct Logn<D>
+pub(crate) struct Logn<D, T>
 where
     D: Dimension,
+    T: Float,
 {
-    operand_data: Shared<Array<f32, D>>,
-    data: Shared<Array<f32, D>>,
+    operand_data: Shared<Array<T, D>>,
+    data: Shared<Array<T, D>>,
 }
 
-impl<D> Logn<D>
+impl<D, T> Logn<D, T>
 where
     D: Dimension,
+    T: Float,
 {
-    pub(crate) fn new(operand_data: Shared<Array<f32, D>>, data: Shared<Array<f32, D>>) -> Self {
+    pub(crate) fn new(operand_data: Shared<Array<T, D>>, data: Shared<Array<T, D>>) -> Self {
         Self { operand_data, data }
     }
 }
 
-impl<D> Forward for Logn<D>
+impl<D, T> Forward for Logn<D, T>
 where
-    D: Dimension,
+    D: 'static + Dimension,
+    T: Float,
 {
     fn forward(&self) {
         Zip::from(&mut *self.data.borrow_mut())
             .and(&*self.operand_data.borrow())
             .for_each(|v, &o| *v = o.ln());
     }
+
+    fn name(&self) -> &'static str {
+        "Logn"
+    }
+
+    fn operands(&self) -> Vec<Buffer> {
+        vec![Buffer::new(&self.operand_data)]
+    }
+
+    fn data(&self) -> Buffer {
+        Buffer::new(&self.data)
+    }
+
+    fn elementwise(&self) -> Option<&dyn ElementwiseForward> {
+        Some(self)
+    }
+}
+
+impl<D, T> ElementwiseForward for Logn<D, T>
+where
+    D: 'static + Dimension,
+    T: Float,
+{
+    fn fusible_len(&self) -> Option<usize> {
+        fusible_len(
+            standard_len(&self.data.borrow()),
+            &[],
+            &[standard_len(&self.operand_data.borrow())],
+        )
+    }
+
+    fn kernel(&self) -> Box<dyn Any> {
+        Box::new(ForwardKernel::unary(&self.operand_data, &self.data, |o| {
+            o.ln()
+        }))
+    }
 }
 
-pub(crate) struct LognBackward<D>
+pub(crate) struct LognBackward<D, T>
 where
     D: Dimension,
+    T: Float,
 {
-    operand_gradient: Rc<Gradient<Array<f32, D>, D>>,
-    operand_data: Shared<Array<f32, D>>,
-    gradient: Rc<Gradient<Array<f32, D>, D>>,
+    operand_gradient: Rc<Gradient<Array<T, D>, D>>,
+    operand_data: Shared<Array<T, D>>,
+    gradient: Rc<Gradient<Array<T, D>, D>>,
 }
 
-impl<D> LognBackward<D>
+impl<D, T> LognBackward<D, T>
 where
     D: Dimension,
+    T: Float,
 {
     pub(crate) fn new(
-        operand_gradient: Rc<Gradient<Array<f32, D>, D>>,
-        operand_data: Shared<Array<f32, D>>,
-        gradient: Rc<Gradient<Array<f32, D>, D>>,
+        operand_gradient: Rc<Gradient<Array<T, D>, D>>,
+        operand_data: Shared<Array<T, D>>,
+        gradient: Rc<Gradient<Array<T, D>, D>>,
     ) -> Self {
         Self {
             operand_gradient,
@@ -62,9 +110,10 @@ where
     }
 }
 
-impl<D> Backward for LognBackward<D>
+impl<D, T> Backward for LognBackward<D, T>
 where
-    D: Dimension,
+    D: 'static + Dimension,
+    T: Float,
 {
     fn backward(&self) {
         Zip::from(&mut *self.operand_gradient.borrow_mut())
@@ -72,6 +121,36 @@ where
             .and(&*self.operand_data.borrow())
             .for_each(|op_grad_el, &grad_el, &op_data_el| *op_grad_el += grad_el / op_data_el);
     }
+
+    fn elementwise(&self) -> Option<&dyn ElementwiseBackward> {
+        Some(self)
+    }
+
+    fn reads(&self) -> Reads {
+        Reads::Operands
+    }
+}
+
+impl<D, T> ElementwiseBackward for LognBackward<D, T>
+where
+    D: 'static + Dimension,
+    T: Float,
+{
+    fn fusible_len(&self) -> Option<usize> {
+        fusible_len(
+            standard_len(&self.gradient.borrow()),
+            &[standard_len(&self.operand_gradient.borrow())],
+            &[standard_len(&self.operand_data.borrow())],
+        )
+    }
+
+    fn kernel(&self) -> Box<dyn Any> {
+        Box::new(BackwardKernel::new(self.gradient.clone()).accumulate_with(
+            self.operand_gradient.clone(),
+            &self.operand_data,
+            |g, o| g / o,
+        ))
+    }
 }
 
 #[cfg(test)]
@@ -1,29 +1,32 @@
-use std::rc::Rc;
-
 use ndarray::{Array, Axis, Dimension, Zip};
 
 use crate::{
-    autograd::{Backward, Forward},
+    autograd::{Backward, Forward, Reads},
+    cell::Rc,
     gradient::Gradient,
+    graph::Buffer,
     utils::Shared,
+    Float,
 };
 
-pub(crate) struct LogSoftmax<D>
+pub(crate) struct LogSoftmax<D, T>
 where
     D: Dimension,
+    T: Float,
 {
-    operand_data: Shared<Array<f32, D>>,
-    data: Shared<Array<f32, D>>,
+    operand_data: Shared<Array<T, D>>,
+    data: Shared<Array<T, D>>,
     axis: Axis,
 }
 
-impl<D> LogSoftmax<D>
+impl<D, T> LogSoftmax<D, T>
 where
     D: Dimension,
+    T: Float,
 {
     pub(crate) fn new(
-        operand_data: Shared<Array<f32, D>>,
-        data: Shared<Array<f32, D>>,
+        operand_data: Shared<Array<T, D>>,
+        data: Shared<Array<T, D>>,
         axis: usize,
     ) -> Self {
         Self {
@@ -34,15 +37,16 @@ where
     }
 }
 
-impl<D> Forward for LogSoftmax<D>
+impl<D, T> Forward for LogSoftmax<D, T>
 where
     D: Dimension,
+    T: Float,
 {
     fn forward(&self) {
         Zip::from(self.data.borrow_mut().lanes_mut(self.axis))
             .and(self.operand_data.borrow().lanes(self.axis))
             .for_each(|lane_v, lane_o| {
-                let max = lane_o.fold(f32::MIN, |x, &y| x.max(y));
+                let max = lane_o.fold(T::min_value(), |x, &y| x.max(y));
                 let exp = &lane_o.map(|&el| (el - max).exp());
                 let log_sum_exp = exp.sum().ln();
                 Zip::from(lane_v)
@@ -50,26 +54,40 @@ where
                     .for_each(|lane_v_el, &lane_o_el| *lane_v_el = lane_o_el - log_sum_exp - max);
             });
     }
+
+    fn name(&self) -> &'static str {
+        "LogSoftmax"
+    }
+
+    fn operands(&self) -> Vec<Buffer> {
+        vec![Buffer::new(&self.operand_data)]
+    }
+
+    fn data(&self) -> Buffer {
+        Buffer::new(&self.data)
+    }
 }
 
-pub(crate) struct LogSoftmaxBackward<D>
+pub(crate) struct LogSoftmaxBackward<D, T>
 where
     D: Dimension,
+    T: Float,
 {
-    operand_gradient: Rc<Gradient<Array<f32, D>, D>>,
-    data: Shared<Array<f32, D>>,
-    gradient: Rc<Gradient<Array<f32, D>, D>>,
+    operand_gradient: Rc<Gradient<Array<T, D>, D>>,
+    data: Shared<Array<T, D>>,
+    gradient: Rc<Gradient<Array<T, D>, D>>,
     axis: Axis,
 }
 
-impl<D> LogSoftmaxBackward<D>
+impl<D, T> LogSoftmaxBackward<D, T>
 where
     D: Dimension,
+    T: Float,
 {
     pub(crate) fn new(
-        operand_gradient: Rc<Gradient<Array<f32, D>, D>>,
-        data: Shared<Array<f32, D>>,
-        gradient: Rc<Gradient<Array<f32, D>, D>>,
+        operand_gradient: Rc<Gradient<Array<T, D>, D>>,
+        data: Shared<Array<T, D>>,
+        gradient: Rc<Gradient<Array<T, D>, D>>,
         axis: usize,
     ) -> Self {
         Self {
@@ -81,9 +99,10 @@ where
     }
 }
 
-impl<D> Backward for LogSoftmaxBackward<D>
+impl<D, T> Backward for LogSoftmaxBackward<D, T>
 where
     D: Dimension,
+    T: Float,
 {
     fn backward(&self) {
         Zip::from(self.operand_gradient.borrow_mut().lanes_mut(self.axis))
@@ -99,6 +118,10 @@ where
                     })
             });
     }
+
+    fn reads(&self) -> Reads {
+        Reads::Data
+    }
 }
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
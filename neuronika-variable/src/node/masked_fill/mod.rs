@@ -0,0 +1,127 @@
+use ndarray::{Array, Dimension, Zip};
+
+use crate::{
+    autograd::{Backward, Forward, Reads},
+    cell::Rc,
+    gradient::Gradient,
+    graph::Buffer,
+    utils::Shared,
+    Float,
+};
+
+pub(crate) struct MaskedFill<D, M, T>
+where
+    D: Dimension,
+    M: Dimension,
+    T: Float,
+{
+    operand_data: Shared<Array<T, D>>,
+    mask_data: Shared<Array<bool, M>>,
+    value: T,
+    data: Shared<Array<T, D>>,
+}
+
+impl<D, M, T> MaskedFill<D, M, T>
+where
+    D: Dimension,
+    M: Dimension,
+    T: Float,
+{
+    pub(crate) fn new(
+        operand_data: Shared<Array<T, D>>,
+        mask_data: Shared<Array<bool, M>>,
+        value: T,
+        data: Shared<Array<T, D>>,
+    ) -> Self {
+        Self {
+            operand_data,
+            mask_data,
+            value,
+            data,
+        }
+    }
+}
+
+impl<D, M, T> Forward for MaskedFill<D, M, T>
+where
+    D: Dimension,
+    M: Dimension,
+    T: Float,
+{
+    fn forward(&self) {
+        Zip::from(&mut *self.data.borrow_mut())
+            .and(&*self.operand_data.borrow())
+            .and_broadcast(&*self.mask_data.borrow())
+            .for_each(|v, &o, &mask| *v = if mask { self.value } else { o });
+    }
+
+    fn name(&self) -> &'static str {
+        "MaskedFill"
+    }
+
+    fn operands(&self) -> Vec<Buffer> {
+        vec![
+            Buffer::new(&self.operand_data),
+            Buffer::new(&self.mask_data),
+        ]
+    }
+
+    fn data(&self) -> Buffer {
+        Buffer::new(&self.data)
+    }
+}
+
+pub(crate) struct MaskedFillBackward<D, M, T>
+where
+    D: Dimension,
+    M: Dimension,
+    T: Float,
+{
+    operand_gradient: Rc<Gradient<Array<T, D>, D>>,
+    mask_data: Shared<Array<bool, M>>,
+    gradient: Rc<Gradient<Array<T, D>, D>>,
+}
+
+impl<D, M, T> MaskedFillBackward<D, M, T>
+where
+    D: Dimension,
+    M: Dimension,
+    T: Float,
+{
+    pub(crate) fn new(
+        operand_gradient: Rc<Gradient<Array<T, D>, D>>,
+        mask_data: Shared<Array<bool, M>>,
+        gradient: Rc<Gradient<Array<T, D>, D>>,
+    ) -> Self {
+        Self {
+            operand_gradient,
+            mask_data,
+            gradient,
+        }
+    }
+}
+
+impl<D, M, T> Backward for MaskedFillBackward<D, M, T>
+where
+    D: Dimension,
+    M: Dimension,
+    T: Float,
+{
+    fn backward(&self) {
+        Zip::from(&mut *self.operand_gradient.borrow_mut())
+            .and(&*self.gradient.borrow())
+            .and_broadcast(&*self.mask_data.borrow())
+            .for_each(|op_grad_el, &grad_el, &mask| {
+                if !mask {
+                    *op_grad_el += grad_el
+                }
+            });
+    }
+
+    fn reads(&self) -> Reads {
+        Reads::Operands
+    }
+}
+
+#[cfg(test)]
+mod test;
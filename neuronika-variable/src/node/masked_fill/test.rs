@@ -0,0 +1,57 @@
+use std::error::Error;
+
+use ndarray::{array, Array};
+
+use crate::cell::Rc;
+use crate::utils::{are_similar, new_shared};
+
+mod forward {
+    use super::super::{Forward, MaskedFill};
+    use super::*;
+
+    #[test]
+    fn base_case() -> Result<(), Box<dyn Error>> {
+        let op = MaskedFill::new(
+            new_shared(array![[1., 2.], [3., 4.]]),
+            new_shared(array![[true, false], [false, true]]),
+            0.,
+            new_shared(Array::zeros((2, 2))),
+        );
+
+        op.forward();
+        are_similar(op.data.borrow(), &array![[0., 2.], [3., 0.]])
+    }
+
+    #[test]
+    fn mask_broadcast() -> Result<(), Box<dyn Error>> {
+        let op = MaskedFill::new(
+            new_shared(array![[1., 2.], [3., 4.]]),
+            new_shared(array![[false], [true]]),
+            -1.,
+            new_shared(Array::zeros((2, 2))),
+        );
+
+        op.forward();
+        are_similar(op.data.borrow(), &array![[1., 2.], [-1., -1.]])
+    }
+}
+
+mod backward {
+    use super::super::{Backward, Gradient, MaskedFillBackward};
+    use super::*;
+
+    #[test]
+    fn backward() -> Result<(), Box<dyn Error>> {
+        let op = MaskedFillBackward::new(
+            Rc::new(Gradient::ndarray_zeros((2, 2))),
+            new_shared(array![[true, false], [false, true]]),
+            Rc::new(Gradient::from_ndarray(Array::ones((2, 2)))),
+        );
+
+        op.backward();
+        are_similar(op.operand_gradient.borrow(), &array![[0., 1.], [1., 0.]])?;
+
+        op.backward();
+        are_similar(op.operand_gradient.borrow(), &array![[0., 2.], [2., 0.]])
+    }
+}
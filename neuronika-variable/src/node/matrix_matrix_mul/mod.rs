@@ -1,24 +1,31 @@
-use std::rc::Rc;
-
 use ndarray::{linalg::general_mat_mul, Array2, Ix2};
 
 use crate::{
-    autograd::{Backward, Forward},
+    autograd::{Backward, Forward, Reads},
+    cell::Rc,
     gradient::Gradient,
+    graph::Buffer,
     utils::Shared,
+    Float,
 };
 
-pub(crate) struct MatrixMatrixMul {
-    left_data: Shared<Array2<f32>>,
-    right_data: Shared<Array2<f32>>,
-    data: Shared<Array2<f32>>,
+pub(crate) struct MatrixMatrixMul<T>
+where
+    T: Float,
+{
+    left_data: Shared<Array2<T>>,
+    right_data: Shared<Array2<T>>,
+    data: Shared<Array2<T>>,
 }
 
-impl MatrixMatrixMul {
+impl<T> MatrixMatrixMul<T>
+where
+    T: Float,
+{
     pub(crate) fn new(
-        left_data: Shared<Array2<f32>>,
-        right_data: Shared<Array2<f32>>,
-        data: Shared<Array2<f32>>,
+        left_data: Shared<Array2<T>>,
+        right_data: Shared<Array2<T>>,
+        data: Shared<Array2<T>>,
     ) -> Self {
         Self {
             left_data,
@@ -28,29 +35,50 @@ impl MatrixMatrixMul {
     }
 }
 
-impl Forward for MatrixMatrixMul {
+impl<T> Forward for MatrixMatrixMul<T>
+where
+    T: Float,
+{
     fn forward(&self) {
         general_mat_mul(
-            1.,
+            T::one(),
             &*self.left_data.borrow(),
             &*self.right_data.borrow(),
-            0.,
+            T::zero(),
             &mut *self.data.borrow_mut(),
         );
     }
+
+    fn name(&self) -> &'static str {
+        "MatrixMatrixMul"
+    }
+
+    fn operands(&self) -> Vec<Buffer> {
+        vec![Buffer::new(&self.left_data), Buffer::new(&self.right_data)]
+    }
+
+    fn data(&self) -> Buffer {
+        Buffer::new(&self.data)
+    }
 }
 
-pub(crate) struct MatrixMatrixMulBackwardLeft {
-    right_data: Shared<Array2<f32>>,
-    left_gradient: Rc<Gradient<Array2<f32>, Ix2>>,
-    gradient: Rc<Gradient<Array2<f32>, Ix2>>,
+pub(crate) struct MatrixMatrixMulBackwardLeft<T>
+where
+    T: Float,
+{
+    right_data: Shared<Array2<T>>,
+    left_gradient: Rc<Gradient<Array2<T>, Ix2>>,
+    gradient: Rc<Gradient<Array2<T>, Ix2>>,
 }
 
-impl MatrixMatrixMulBackwardLeft {
+impl<T> MatrixMatrixMulBackwardLeft<T>
+where
+    T: Float,
+{
     pub(crate) fn new(
-        right_data: Shared<Array2<f32>>,
-        left_gradient: Rc<Gradient<Array2<f32>, Ix2>>,
-        gradient: Rc<Gradient<Array2<f32>, Ix2>>,
+        right_data: Shared<Array2<T>>,
+        left_gradient: Rc<Gradient<Array2<T>, Ix2>>,
+        gradient: Rc<Gradient<Array2<T>, Ix2>>,
     ) -> Self {
         Self {
             right_data,
@@ -60,29 +88,42 @@ impl MatrixMatrixMulBackwardLeft {
     }
 }
 
-impl Backward for MatrixMatrixMulBackwardLeft {
+impl<T> Backward for MatrixMatrixMulBackwardLeft<T>
+where
+    T: Float,
+{
     fn backward(&self) {
         general_mat_mul(
-            1.,
+            T::one(),
             &*self.gradient.borrow(),
             &self.right_data.borrow().t(),
-            1.,
+            T::one(),
             &mut *self.left_gradient.borrow_mut(),
         );
     }
+
+    fn reads(&self) -> Reads {
+        Reads::Operands
+    }
 }
 
-pub(crate) struct MatrixMatrixMulBackwardRight {
-    left_data: Shared<Array2<f32>>,
-    right_gradient: Rc<Gradient<Array2<f32>, Ix2>>,
-    gradient: Rc<Gradient<Array2<f32>, Ix2>>,
+pub(crate) struct MatrixMatrixMulBackwardRight<T>
+where
+    T: Float,
+{
+    left_data: Shared<Array2<T>>,
+    right_gradient: Rc<Gradient<Array2<T>, Ix2>>,
+    gradient: Rc<Gradient<Array2<T>, Ix2>>,
 }
 
-impl MatrixMatrixMulBackwardRight {
+impl<T> MatrixMatrixMulBackwardRight<T>
+where
+    T: Float,
+{
     pub(crate) fn new(
-        left_data: Shared<Array2<f32>>,
-        right_gradient: Rc<Gradient<Array2<f32>, Ix2>>,
-        gradient: Rc<Gradient<Array2<f32>, Ix2>>,
+        left_data: Shared<Array2<T>>,
+        right_gradient: Rc<Gradient<Array2<T>, Ix2>>,
+        gradient: Rc<Gradient<Array2<T>, Ix2>>,
     ) -> Self {
         Self {
             left_data,
@@ -92,37 +133,57 @@ impl MatrixMatrixMulBackwardRight {
     }
 }
 
-impl Backward for MatrixMatrixMulBackwardRight {
+impl<T> Backward for MatrixMatrixMulBackwardRight<T>
+where
+    T: Float,
+{
     fn backward(&self) {
         general_mat_mul(
-            1.,
+            T::one(),
             &self.left_data.borrow().t(),
             &*self.gradient.borrow(),
-            1.,
+            T::one(),
             &mut *self.right_gradient.borrow_mut(),
         )
     }
+
+    fn reads(&self) -> Reads {
+        Reads::Operands
+    }
 }
 
-pub(crate) struct MatrixMatrixMulBackward {
-    left: MatrixMatrixMulBackwardLeft,
-    right: MatrixMatrixMulBackwardRight,
+pub(crate) struct MatrixMatrixMulBackward<T>
+where
+    T: Float,
+{
+    left: MatrixMatrixMulBackwardLeft<T>,
+    right: MatrixMatrixMulBackwardRight<T>,
 }
 
-impl MatrixMatrixMulBackward {
+impl<T> MatrixMatrixMulBackward<T>
+where
+    T: Float,
+{
     pub(crate) fn new(
-        left: MatrixMatrixMulBackwardLeft,
-        right: MatrixMatrixMulBackwardRight,
+        left: MatrixMatrixMulBackwardLeft<T>,
+        right: MatrixMatrixMulBackwardRight<T>,
     ) -> Self {
         Self { left, right }
     }
 }
 
-impl Backward for MatrixMatrixMulBackward {
+impl<T> Backward for MatrixMatrixMulBackward<T>
+where
+    T: Float,
+{
     fn backward(&self) {
         self.left.backward();
         self.right.backward();
     }
+
+    fn reads(&self) -> Reads {
+        Reads::Operands
+    }
 }
 
 #[cfg(test)]
@@ -1,24 +1,31 @@
-use std::rc::Rc;
-
 use ndarray::{linalg::general_mat_mul, Array2, Ix2};
 
 use crate::{
-    autograd::{Backward, Forward},
+    autograd::{Backward, Forward, Reads},
+    cell::Rc,
     gradient::Gradient,
+    graph::Buffer,
     utils::Shared,
+    Float,
 };
 
-pub(crate) struct MatrixMatrixMulT {
-    left_data: Shared<Array2<f32>>,
-    right_data: Shared<Array2<f32>>,
-    data: Shared<Array2<f32>>,
+pub(crate) struct MatrixMatrixMulT<T>
+where
+    T: Float,
+{
+    left_data: Shared<Array2<T>>,
+    right_data: Shared<Array2<T>>,
+    data: Shared<Array2<T>>,
 }
 
-impl MatrixMatrixMulT {
+impl<T> MatrixMatrixMulT<T>
+where
+    T: Float,
+{
     pub(crate) fn new(
-        left_data: Shared<Array2<f32>>,
-        right_data: Shared<Array2<f32>>,
-        data: Shared<Array2<f32>>,
+        left_data: Shared<Array2<T>>,
+        right_data: Shared<Array2<T>>,
+        data: Shared<Array2<T>>,
     ) -> Self {
         Self {
             left_data,
@@ -28,29 +35,50 @@ impl MatrixMatrixMulT {
     }
 }
 
-impl Forward for MatrixMatrixMulT {
+impl<T> Forward for MatrixMatrixMulT<T>
+where
+    T: Float,
+{
     fn forward(&self) {
         general_mat_mul(
-            1.,
+            T::one(),
             &*self.left_data.borrow(),
             &self.right_data.borrow().t(),
-            0.,
+            T::zero(),
             &mut *self.data.borrow_mut(),
         );
     }
+
+    fn name(&self) -> &'static str {
+        "MatrixMatrixMulT"
+    }
+
+    fn operands(&self) -> Vec<Buffer> {
+        vec![Buffer::new(&self.left_data), Buffer::new(&self.right_data)]
+    }
+
+    fn data(&self) -> Buffer {
+        Buffer::new(&self.data)
+    }
 }
 
-pub(crate) struct MatrixMatrixMulTBackwardLeft {
-    left_gradient: Rc<Gradient<Array2<f32>, Ix2>>,
-    right_data: Shared<Array2<f32>>,
-    gradient: Rc<Gradient<Array2<f32>, Ix2>>,
+pub(crate) struct MatrixMatrixMulTBackwardLeft<T>
+where
+    T: Float,
+{
+    left_gradient: Rc<Gradient<Array2<T>, Ix2>>,
+    right_data: Shared<Array2<T>>,
+    gradient: Rc<Gradient<Array2<T>, Ix2>>,
 }
 
-impl MatrixMatrixMulTBackwardLeft {
+impl<T> MatrixMatrixMulTBackwardLeft<T>
+where
+    T: Float,
+{
     pub(crate) fn new(
-        left_gradient: Rc<Gradient<Array2<f32>, Ix2>>,
-        right_data: Shared<Array2<f32>>,
-        gradient: Rc<Gradient<Array2<f32>, Ix2>>,
+        left_gradient: Rc<Gradient<Array2<T>, Ix2>>,
+        right_data: Shared<Array2<T>>,
+        gradient: Rc<Gradient<Array2<T>, Ix2>>,
     ) -> Self {
         Self {
             left_gradient,
@@ -60,29 +88,42 @@ impl MatrixMatrixMulTBackwardLeft {
     }
 }
 
-impl Backward for MatrixMatrixMulTBackwardLeft {
+impl<T> Backward for MatrixMatrixMulTBackwardLeft<T>
+where
+    T: Float,
+{
     fn backward(&self) {
         general_mat_mul(
-            1.,
+            T::one(),
             &*self.gradient.borrow(),
             &self.right_data.borrow(),
-            1.,
+            T::one(),
             &mut *self.left_gradient.borrow_mut(),
         );
     }
+
+    fn reads(&self) -> Reads {
+        Reads::Operands
+    }
 }
 
-pub(crate) struct MatrixMatrixMulTBackwardRight {
-    left_data: Shared<Array2<f32>>,
-    right_gradient: Rc<Gradient<Array2<f32>, Ix2>>,
-    gradient: Rc<Gradient<Array2<f32>, Ix2>>,
+pub(crate) struct MatrixMatrixMulTBackwardRight<T>
+where
+    T: Float,
+{
+    left_data: Shared<Array2<T>>,
+    right_gradient: Rc<Gradient<Array2<T>, Ix2>>,
+    gradient: Rc<Gradient<Array2<T>, Ix2>>,
 }
 
-impl MatrixMatrixMulTBackwardRight {
+impl<T> MatrixMatrixMulTBackwardRight<T>
+where
+    T: Float,
+{
     pub(crate) fn new(
-        left_data: Shared<Array2<f32>>,
-        right_gradient: Rc<Gradient<Array2<f32>, Ix2>>,
-        gradient: Rc<Gradient<Array2<f32>, Ix2>>,
+        left_data: Shared<Array2<T>>,
+        right_gradient: Rc<Gradient<Array2<T>, Ix2>>,
+        gradient: Rc<Gradient<Array2<T>, Ix2>>,
     ) -> Self {
         Self {
             left_data,
@@ -92,37 +133,57 @@ impl MatrixMatrixMulTBackwardRight {
     }
 }
 
-impl Backward for MatrixMatrixMulTBackwardRight {
+impl<T> Backward for MatrixMatrixMulTBackwardRight<T>
+where
+    T: Float,
+{
     fn backward(&self) {
         general_mat_mul(
-            1.,
+            T::one(),
             &self.gradient.borrow().t(),
             &self.left_data.borrow(),
-            1.,
+            T::one(),
             &mut *self.right_gradient.borrow_mut(),
         )
     }
+
+    fn reads(&self) -> Reads {
+        Reads::Operands
+    }
 }
 
-pub(crate) struct MatrixMatrixMulTBackward {
-    left: MatrixMatrixMulTBackwardLeft,
-    right: MatrixMatrixMulTBackwardRight,
+pub(crate) struct MatrixMatrixMulTBackward<T>
+where
+    T: Float,
+{
+    left: MatrixMatrixMulTBackwardLeft<T>,
+    right: MatrixMatrixMulTBackwardRight<T>,
 }
 
-impl MatrixMatrixMulTBackward {
+impl<T> MatrixMatrixMulTBackward<T>
+where
+    T: Float,
+{
     pub(crate) fn new(
-        left: MatrixMatrixMulTBackwardLeft,
-        right: MatrixMatrixMulTBackwardRight,
+        left: MatrixMatrixMulTBackwardLeft<T>,
+        right: MatrixMatrixMulTBackwardRight<T>,
     ) -> Self {
         Self { left, right }
     }
 }
 
-impl Backward for MatrixMatrixMulTBackward {
+impl<T> Backward for MatrixMatrixMulTBackward<T>
+where
+    T: Float,
+{
     fn backward(&self) {
         self.left.backward();
         self.right.backward();
     }
+
+    fn reads(&self) -> Reads {
+        Reads::Operands
+    }
 }
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
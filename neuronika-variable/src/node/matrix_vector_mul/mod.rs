@@ -1,24 +1,31 @@
-use std::rc::Rc;
-
 use ndarray::{linalg::general_mat_vec_mul, s, Array1, Array2, Ix1, Ix2, NewAxis, Zip};
 
 use crate::{
-    autograd::{Backward, Forward},
+    autograd::{Backward, Forward, Reads},
+    cell::Rc,
     gradient::Gradient,
+    graph::Buffer,
     utils::Shared,
+    Float,
 };
 
-pub(crate) struct MatrixVectorMul {
-    left_data: Shared<Array2<f32>>,
-    right_data: Shared<Array1<f32>>,
-    data: Shared<Array1<f32>>,
+pub(crate) struct MatrixVectorMul<T>
+where
+    T: Float,
+{
+    left_data: Shared<Array2<T>>,
+    right_data: Shared<Array1<T>>,
+    data: Shared<Array1<T>>,
 }
 
-impl MatrixVectorMul {
+impl<T> MatrixVectorMul<T>
+where
+    T: Float,
+{
     pub(crate) fn new(
-        left_data: Shared<Array2<f32>>,
-        right_data: Shared<Array1<f32>>,
-        data: Shared<Array1<f32>>,
+        left_data: Shared<Array2<T>>,
+        right_data: Shared<Array1<T>>,
+        data: Shared<Array1<T>>,
     ) -> Self {
         Self {
             left_data,
@@ -28,29 +35,50 @@ impl MatrixVectorMul {
     }
 }
 
-impl Forward for MatrixVectorMul {
+impl<T> Forward for MatrixVectorMul<T>
+where
+    T: Float,
+{
     fn forward(&self) {
         general_mat_vec_mul(
-            1.,
+            T::one(),
             &*self.left_data.borrow(),
             &*self.right_data.borrow(),
-            0.,
+            T::zero(),
             &mut *self.data.borrow_mut(),
         );
     }
+
+    fn name(&self) -> &'static str {
+        "MatrixVectorMul"
+    }
+
+    fn operands(&self) -> Vec<Buffer> {
+        vec![Buffer::new(&self.left_data), Buffer::new(&self.right_data)]
+    }
+
+    fn data(&self) -> Buffer {
+        Buffer::new(&self.data)
+    }
 }
 
-pub(crate) struct MatrixVectorMulBackwardLeft {
-    left_gradient: Rc<Gradient<Array2<f32>, Ix2>>,
-    right_data: Shared<Array1<f32>>,
-    gradient: Rc<Gradient<Array1<f32>, Ix1>>,
+pub(crate) struct MatrixVectorMulBackwardLeft<T>
+where
+    T: Float,
+{
+    left_gradient: Rc<Gradient<Array2<T>, Ix2>>,
+    right_data: Shared<Array1<T>>,
+    gradient: Rc<Gradient<Array1<T>, Ix1>>,
 }
 
-impl MatrixVectorMulBackwardLeft {
+impl<T> MatrixVectorMulBackwardLeft<T>
+where
+    T: Float,
+{
     pub(crate) fn new(
-        left_gradient: Rc<Gradient<Array2<f32>, Ix2>>,
-        right_data: Shared<Array1<f32>>,
-        gradient: Rc<Gradient<Array1<f32>, Ix1>>,
+        left_gradient: Rc<Gradient<Array2<T>, Ix2>>,
+        right_data: Shared<Array1<T>>,
+        gradient: Rc<Gradient<Array1<T>, Ix1>>,
     ) -> Self {
         Self {
             left_gradient,
@@ -60,26 +88,39 @@ impl MatrixVectorMulBackwardLeft {
     }
 }
 
-impl Backward for MatrixVectorMulBackwardLeft {
+impl<T> Backward for MatrixVectorMulBackwardLeft<T>
+where
+    T: Float,
+{
     fn backward(&self) {
         Zip::from(&mut *self.left_gradient.borrow_mut())
             .and_broadcast(&self.gradient.borrow().slice(s![.., NewAxis]))
             .and_broadcast(&*self.right_data.borrow())
             .for_each(|d, &f, &s| *d += f * s);
     }
+
+    fn reads(&self) -> Reads {
+        Reads::Operands
+    }
 }
 
-pub(crate) struct MatrixVectorMulBackwardRight {
-    left_data: Shared<Array2<f32>>,
-    right_gradient: Rc<Gradient<Array1<f32>, Ix1>>,
-    gradient: Rc<Gradient<Array1<f32>, Ix1>>,
+pub(crate) struct MatrixVectorMulBackwardRight<T>
+where
+    T: Float,
+{
+    left_data: Shared<Array2<T>>,
+    right_gradient: Rc<Gradient<Array1<T>, Ix1>>,
+    gradient: Rc<Gradient<Array1<T>, Ix1>>,
 }
 
-impl MatrixVectorMulBackwardRight {
+impl<T> MatrixVectorMulBackwardRight<T>
+where
+    T: Float,
+{
     pub(crate) fn new(
-        left_data: Shared<Array2<f32>>,
-        right_gradient: Rc<Gradient<Array1<f32>, Ix1>>,
-        gradient: Rc<Gradient<Array1<f32>, Ix1>>,
+        left_data: Shared<Array2<T>>,
+        right_gradient: Rc<Gradient<Array1<T>, Ix1>>,
+        gradient: Rc<Gradient<Array1<T>, Ix1>>,
     ) -> Self {
         Self {
             left_data,
@@ -89,37 +130,57 @@ impl MatrixVectorMulBackwardRight {
     }
 }
 
-impl Backward for MatrixVectorMulBackwardRight {
+impl<T> Backward for MatrixVectorMulBackwardRight<T>
+where
+    T: Float,
+{
     fn backward(&self) {
         general_mat_vec_mul(
-            1.,
+            T::one(),
             &self.left_data.borrow().t(),
             &*self.gradient.borrow(),
-            1.,
+            T::one(),
             &mut *self.right_gradient.borrow_mut(),
         );
     }
+
+    fn reads(&self) -> Reads {
+        Reads::Operands
+    }
 }
 
-pub(crate) struct MatrixVectorMulBackward {
-    left: MatrixVectorMulBackwardLeft,
-    right: MatrixVectorMulBackwardRight,
+pub(crate) struct MatrixVectorMulBackward<T>
+where
+    T: Float,
+{
+    left: MatrixVectorMulBackwardLeft<T>,
+    right: MatrixVectorMulBackwardRight<T>,
 }
 
-impl MatrixVectorMulBackward {
+impl<T> MatrixVectorMulBackward<T>
+where
+    T: Float,
+{
     pub(crate) fn new(
-        left: MatrixVectorMulBackwardLeft,
-        right: MatrixVectorMulBackwardRight,
+        left: MatrixVectorMulBackwardLeft<T>,
+        right: MatrixVectorMulBackwardRight<T>,
     ) -> Self {
         Self { left, right }
     }
 }
 
-impl Backward for MatrixVectorMulBackward {
+impl<T> Backward for MatrixVectorMulBackward<T>
+where
+    T: Float,
+{
     fn backward(&self) {
         self.left.backward();
         self.right.backward();
     }
+
+    fn reads(&self) -> Reads {
+        Reads::Operands
+    }
 }
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
@@ -1,54 +1,73 @@
-use std::rc::Rc;
-
 use ndarray::{arr0, Array, Dimension, Ix0, Zip};
 
 use crate::{
-    autograd::{Backward, Forward},
+    autograd::{Backward, Forward, Reads},
+    cell::Rc,
     gradient::Gradient,
+    graph::Buffer,
     utils::Shared,
+    Float,
 };
 
-pub(crate) struct Mean<D>
+pub(crate) struct Mean<D, T>
 where
     D: Dimension,
+    T: Float,
 {
-    operand_data: Shared<Array<f32, D>>,
-    data: Shared<Array<f32, Ix0>>,
+    operand_data: Shared<Array<T, D>>,
+    data: Shared<Array<T, Ix0>>,
 }
 
-impl<D> Mean<D>
+impl<D, T> Mean<D, T>
 where
     D: Dimension,
+    T: Float,
 {
-    pub(crate) fn new(operand_data: Shared<Array<f32, D>>, data: Shared<Array<f32, Ix0>>) -> Self {
+    pub(crate) fn new(operand_data: Shared<Array<T, D>>, data: Shared<Array<T, Ix0>>) -> Self {
         Self { operand_data, data }
     }
 }
 
-impl<D> Forward for Mean<D>
+impl<D, T> Forward for Mean<D, T>
 where
     D: Dimension,
+    T: Float,
 {
     fn forward(&self) {
-        *self.data.borrow_mut() = arr0(self.operand_data.borrow().mean().unwrap());
+        let operand_data = self.operand_data.borrow();
+        *self.data.borrow_mut() = arr0(operand_data.sum() / T::from_usize(operand_data.len()));
+    }
+
+    fn name(&self) -> &'static str {
+        "Mean"
+    }
+
+    fn operands(&self) -> Vec<Buffer> {
+        vec![Buffer::new(&self.operand_data)]
+    }
+
+    fn data(&self) -> Buffer {
+        Buffer::new(&self.data)
     }
 }
 
-pub(crate) struct MeanBackward<D>
+pub(crate) struct MeanBackward<D, T>
 where
     D: Dimension,
+    T: Float,
 {
-    operand_gradient: Rc<Gradient<Array<f32, D>, D>>,
-    gradient: Rc<Gradient<Array<f32, Ix0>, Ix0>>,
+    operand_gradient: Rc<Gradient<Array<T, D>, D>>,
+    gradient: Rc<Gradient<Array<T, Ix0>, Ix0>>,
 }
 
-impl<D> MeanBackward<D>
+impl<D, T> MeanBackward<D, T>
 where
     D: Dimension,
+    T: Float,
 {
     pub(crate) fn new(
-        operand_gradient: Rc<Gradient<Array<f32, D>, D>>,
-        gradient: Rc<Gradient<Array<f32, Ix0>, Ix0>>,
+        operand_gradient: Rc<Gradient<Array<T, D>, D>>,
+        gradient: Rc<Gradient<Array<T, Ix0>, Ix0>>,
     ) -> Self {
         Self {
             operand_gradient,
@@ -57,18 +76,23 @@ where
     }
 }
 
-impl<D> Backward for MeanBackward<D>
+impl<D, T> Backward for MeanBackward<D, T>
 where
     D: Dimension,
+    T: Float,
 {
     fn backward(&self) {
         let mut operand_gradient = self.operand_gradient.borrow_mut();
-        let den = operand_gradient.len() as f32;
+        let den = T::from_usize(operand_gradient.len());
 
         Zip::from(&mut *operand_gradient)
             .and_broadcast(&*self.gradient.borrow())
             .for_each(|op_grad_el, &grad_el| *op_grad_el += grad_el / den);
     }
+
+    fn reads(&self) -> Reads {
+        Reads::Nothing
+    }
 }
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
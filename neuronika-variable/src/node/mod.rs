@@ -1,17 +1,27 @@
 mod absolute_error;
 mod addition;
+mod argsort;
+mod attention;
 mod bce;
 mod bce_with_logits;
 mod chunk;
+mod comparison;
 mod concatenate;
 mod convolution;
+mod cumulative;
+mod dimensionality;
 mod division;
 mod dropout;
 mod exp;
+mod flip;
+mod gather;
+mod interpolate;
 mod kldiv;
 mod leaky_relu;
+mod linalg;
 mod logn;
 mod logsoftmax;
+mod masked_fill;
 mod matrix_matrix_mul;
 mod matrix_matrix_mul_t;
 mod matrix_vector_mul;
@@ -22,11 +32,17 @@ mod multiplication;
 mod negation;
 mod nll;
 mod pad;
+mod permute;
+mod pixel_shuffle;
 mod power;
 mod relu;
+mod repeat;
+mod reshape;
+mod roll;
 mod sigmoid;
 mod softmax;
 mod softplus;
+mod sparse_matrix_mul;
 mod sqrt;
 mod squared_error;
 mod stack;
@@ -37,21 +53,32 @@ mod transpose;
 mod unsqueeze;
 mod vector_matrix_mul;
 mod vector_vector_mul;
+mod where_;
 
 pub(crate) use absolute_error::*;
 pub(crate) use addition::*;
+pub(crate) use argsort::*;
+pub(crate) use attention::*;
 pub(crate) use bce::*;
 pub(crate) use bce_with_logits::*;
 pub(crate) use chunk::*;
+pub(crate) use comparison::*;
 pub(crate) use concatenate::*;
 pub(crate) use convolution::*;
+pub(crate) use cumulative::*;
+pub(crate) use dimensionality::*;
 pub(crate) use division::*;
 pub(crate) use dropout::*;
 pub(crate) use exp::*;
+pub(crate) use flip::*;
+pub(crate) use gather::*;
+pub(crate) use interpolate::*;
 pub(crate) use kldiv::*;
 pub(crate) use leaky_relu::*;
+pub(crate) use linalg::*;
 pub(crate) use logn::*;
 pub(crate) use logsoftmax::*;
+pub(crate) use masked_fill::*;
 pub(crate) use matrix_matrix_mul::*;
 pub(crate) use matrix_matrix_mul_t::*;
 pub(crate) use matrix_vector_mul::*;
@@ -62,11 +89,17 @@ pub(crate) use multiplication::*;
 pub(crate) use negation::*;
 pub(crate) use nll::*;
 pub(crate) use pad::*;
+pub(crate) use permute::*;
+pub(crate) use pixel_shuffle::*;
 pub(crate) use power::*;
 pub(crate) use relu::*;
+pub(crate) use repeat::*;
+pub(crate) use reshape::*;
+pub(crate) use roll::*;
 pub(crate) use sigmoid::*;
 pub(crate) use softmax::*;
 pub(crate) use softplus::*;
+pub(crate) use sparse_matrix_mul::*;
 pub(crate) use sqrt::*;
 pub(crate) use squared_error::*;
 pub(crate) use stack::*;
@@ -77,5 +110,8 @@ pub(crate) use transpose::*;
 pub(crate) use unsqueeze::*;
 pub(crate) use vector_matrix_mul::*;
 pub(crate) use vector_vector_mul::*;
+pub(crate) use where_::*;
 
+pub use attention::AttentionMask;
+pub use interpolate::{Interpolation, Resize};
 pub use pad::{Constant, PaddingMode, Reflective, Replicative, Zero};
@@ -1,29 +1,32 @@
-use std::rc::Rc;
-
 use ndarray::{Array, Axis, Dimension, Slice};
 
 use crate::{
-    autograd::{Backward, Forward},
+    autograd::{Backward, Forward, Reads},
+    cell::Rc,
     gradient::Gradient,
+    graph::Buffer,
     utils::Shared,
+    Float,
 };
 
-pub(crate) struct MultiConcatenate<D>
+pub(crate) struct MultiConcatenate<D, T>
 where
     D: Dimension,
+    T: Float,
 {
-    operands_data: Vec<Shared<Array<f32, D>>>,
-    data: Shared<Array<f32, D>>,
+    operands_data: Vec<Shared<Array<T, D>>>,
+    data: Shared<Array<T, D>>,
     axis: Axis,
 }
 
-impl<D> MultiConcatenate<D>
+impl<D, T> MultiConcatenate<D, T>
 where
     D: Dimension,
+    T: Float,
 {
     pub(crate) fn new(
-        operands_data: Vec<Shared<Array<f32, D>>>,
-        data: Shared<Array<f32, D>>,
+        operands_data: Vec<Shared<Array<T, D>>>,
+        data: Shared<Array<T, D>>,
         axis: usize,
     ) -> Self {
         Self {
@@ -34,9 +37,10 @@ where
     }
 }
 
-impl<D> Forward for MultiConcatenate<D>
+impl<D, T> Forward for MultiConcatenate<D, T>
 where
     D: Dimension,
+    T: Float,
 {
     fn forward(&self) {
         let (mut offset, mut data) = (0, self.data.borrow_mut());
@@ -50,24 +54,38 @@ where
             offset += axis_len;
         });
     }
+
+    fn name(&self) -> &'static str {
+        "MultiConcatenate"
+    }
+
+    fn operands(&self) -> Vec<Buffer> {
+        self.operands_data.iter().map(Buffer::new).collect()
+    }
+
+    fn data(&self) -> Buffer {
+        Buffer::new(&self.data)
+    }
 }
 
-pub(crate) struct MultiConcatenateBackward<D>
+pub(crate) struct MultiConcatenateBackward<D, T>
 where
     D: Dimension,
+    T: Float,
 {
-    operands_gradients: Vec<Rc<Gradient<Array<f32, D>, D>>>,
-    gradient: Rc<Gradient<Array<f32, D>, D>>,
+    operands_gradients: Vec<Rc<Gradient<Array<T, D>, D>>>,
+    gradient: Rc<Gradient<Array<T, D>, D>>,
     axis: Axis,
 }
 
-impl<D> MultiConcatenateBackward<D>
+impl<D, T> MultiConcatenateBackward<D, T>
 where
     D: Dimension,
+    T: Float,
 {
     pub(crate) fn new(
-        operands_gradients: Vec<Rc<Gradient<Array<f32, D>, D>>>,
-        gradient: Rc<Gradient<Array<f32, D>, D>>,
+        operands_gradients: Vec<Rc<Gradient<Array<T, D>, D>>>,
+        gradient: Rc<Gradient<Array<T, D>, D>>,
         axis: usize,
     ) -> Self {
         Self {
@@ -78,9 +96,10 @@ where
     }
 }
 
-impl<D> Backward for MultiConcatenateBackward<D>
+impl<D, T> Backward for MultiConcatenateBackward<D, T>
 where
     D: Dimension,
+    T: Float,
 {
     fn backward(&self) {
         let gradient = self.gradient.borrow();
@@ -96,6 +115,10 @@ where
                 offset += axis_len;
             });
     }
+
+    fn reads(&self) -> Reads {
+        Reads::Nothing
+    }
 }
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
@@ -1,29 +1,32 @@
-use std::rc::Rc;
-
 use ndarray::{Array, Axis, Dimension, RemoveAxis};
 
 use crate::{
-    autograd::{Backward, Forward},
+    autograd::{Backward, Forward, Reads},
+    cell::Rc,
     gradient::Gradient,
+    graph::Buffer,
     utils::Shared,
+    Float,
 };
 
-pub(crate) struct MultiStack<D>
+pub(crate) struct MultiStack<D, T>
 where
     D: Dimension + RemoveAxis,
+    T: Float,
 {
-    operands_data: Vec<Shared<Array<f32, D>>>,
-    data: Shared<Array<f32, D::Larger>>,
+    operands_data: Vec<Shared<Array<T, D>>>,
+    data: Shared<Array<T, D::Larger>>,
     axis: Axis,
 }
 
-impl<D> MultiStack<D>
+impl<D, T> MultiStack<D, T>
 where
     D: Dimension + RemoveAxis,
+    T: Float,
 {
     pub(crate) fn new(
-        operands_data: Vec<Shared<Array<f32, D>>>,
-        data: Shared<Array<f32, D::Larger>>,
+        operands_data: Vec<Shared<Array<T, D>>>,
+        data: Shared<Array<T, D::Larger>>,
         axis: usize,
     ) -> Self {
         Self {
@@ -34,9 +37,10 @@ where
     }
 }
 
-impl<D> Forward for MultiStack<D>
+impl<D, T> Forward for MultiStack<D, T>
 where
     D: Dimension + RemoveAxis,
+    T: Float,
 {
     fn forward(&self) {
         let mut data = self.data.borrow_mut();
@@ -48,24 +52,38 @@ where
                 axis_data.assign(&operand_data)
             });
     }
+
+    fn name(&self) -> &'static str {
+        "MultiStack"
+    }
+
+    fn operands(&self) -> Vec<Buffer> {
+        self.operands_data.iter().map(Buffer::new).collect()
+    }
+
+    fn data(&self) -> Buffer {
+        Buffer::new(&self.data)
+    }
 }
 
-pub(crate) struct MultiStackBackward<D>
+pub(crate) struct MultiStackBackward<D, T>
 where
     D: Dimension + RemoveAxis,
+    T: Float,
 {
-    operands_gradients: Vec<Rc<Gradient<Array<f32, D>, D>>>,
-    gradient: Rc<Gradient<Array<f32, D::Larger>, D::Larger>>,
+    operands_gradients: Vec<Rc<Gradient<Array<T, D>, D>>>,
+    gradient: Rc<Gradient<Array<T, D::Larger>, D::Larger>>,
     axis: Axis,
 }
 
-impl<D> MultiStackBackward<D>
+impl<D, T> MultiStackBackward<D, T>
 where
     D: Dimension + RemoveAxis,
+    T: Float,
 {
     pub(crate) fn new(
-        operands_gradients: Vec<Rc<Gradient<Array<f32, D>, D>>>,
-        gradient: Rc<Gradient<Array<f32, D::Larger>, D::Larger>>,
+        operands_gradients: Vec<Rc<Gradient<Array<T, D>, D>>>,
+        gradient: Rc<Gradient<Array<T, D::Larger>, D::Larger>>,
         axis: usize,
     ) -> Self {
         Self {
@@ -76,9 +94,10 @@ where
     }
 }
 
-impl<D> Backward for MultiStackBackward<D>
+impl<D, T> Backward for MultiStackBackward<D, T>
 where
     D: Dimension + RemoveAxis,
+    T: Float,
 {
     fn backward(&self) {
         self.operands_gradients
@@ -89,6 +108,10 @@ where
                 *operand_gradient += &grad_view;
             });
     }
+
+    fn reads(&self) -> Reads {
+        Reads::Nothing
+    }
 }
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
@@ -1,32 +1,41 @@
-use std::rc::Rc;
+use std::any::Any;
 
 use ndarray::{Array, DimMax, Dimension, Zip};
 
 use crate::{
-    autograd::{Backward, Forward},
+    autograd::{Backward, Forward, Reads},
+    cell::Rc,
+    fusion::{
+        fusible_len, standard_len, BackwardKernel, ElementwiseBackward, ElementwiseForward,
+        ForwardKernel,
+    },
     gradient::{BufferedGradient, Gradient},
+    graph::Buffer,
     utils::{accumulate, Broadcast, Shared},
+    Float,
 };
 
-pub(crate) struct Multiplication<D, E>
+pub(crate) struct Multiplication<D, E, T>
 where
     D: Dimension + DimMax<E>,
     E: Dimension,
+    T: Float,
 {
-    left_data: Shared<Array<f32, D>>,
-    right_data: Shared<Array<f32, E>>,
-    data: Shared<Array<f32, Broadcast<D, E>>>,
+    left_data: Shared<Array<T, D>>,
+    right_data: Shared<Array<T, E>>,
+    data: Shared<Array<T, Broadcast<D, E>>>,
 }
 
-impl<D, E> Multiplication<D, E>
+impl<D, E, T> Multiplication<D, E, T>
 where
     D: Dimension + DimMax<E>,
     E: Dimension,
+    T: Float,
 {
     pub(crate) fn new(
-        left_data: Shared<Array<f32, D>>,
-        right_data: Shared<Array<f32, E>>,
-        data: Shared<Array<f32, Broadcast<D, E>>>,
+        left_data: Shared<Array<T, D>>,
+        right_data: Shared<Array<T, E>>,
+        data: Shared<Array<T, Broadcast<D, E>>>,
     ) -> Self {
         Self {
             left_data,
@@ -36,10 +45,11 @@ where
     }
 }
 
-impl<D, E> Forward for Multiplication<D, E>
+impl<D, E, T> Forward for Multiplication<D, E, T>
 where
-    D: Dimension + DimMax<E>,
-    E: Dimension,
+    D: 'static + Dimension + DimMax<E>,
+    E: 'static + Dimension,
+    T: Float,
 {
     fn forward(&self) {
         Zip::from(&mut *self.data.borrow_mut())
@@ -47,27 +57,72 @@ where
             .and_broadcast(&*self.right_data.borrow())
             .for_each(|v, &l, &r| *v = l * r);
     }
+
+    fn name(&self) -> &'static str {
+        "Multiplication"
+    }
+
+    fn operands(&self) -> Vec<Buffer> {
+        vec![Buffer::new(&self.left_data), Buffer::new(&self.right_data)]
+    }
+
+    fn data(&self) -> Buffer {
+        Buffer::new(&self.data)
+    }
+
+    fn elementwise(&self) -> Option<&dyn ElementwiseForward> {
+        Some(self)
+    }
 }
 
-pub(crate) struct MultiplicationBackwardLeft<D, E>
+impl<D, E, T> ElementwiseForward for Multiplication<D, E, T>
+where
+    D: 'static + Dimension + DimMax<E>,
+    E: 'static + Dimension,
+    T: Float,
+{
+    fn fusible_len(&self) -> Option<usize> {
+        fusible_len(
+            standard_len(&self.data.borrow()),
+            &[],
+            &[
+                standard_len(&self.left_data.borrow()),
+                standard_len(&self.right_data.borrow()),
+            ],
+        )
+    }
+
+    fn kernel(&self) -> Box<dyn Any> {
+        Box::new(ForwardKernel::binary(
+            &self.left_data,
+            &self.right_data,
+            &self.data,
+            |l, r| l * r,
+        ))
+    }
+}
+
+pub(crate) struct MultiplicationBackwardLeft<D, E, T>
 where
     D: Dimension + DimMax<E>,
     E: Dimension,
+    T: Float,
 {
-    right_data: Shared<Array<f32, E>>,
-    left_gradient: Rc<Gradient<Array<f32, D>, D>>,
-    gradient: Rc<BufferedGradient<Array<f32, Broadcast<D, E>>, Broadcast<D, E>>>,
+    right_data: Shared<Array<T, E>>,
+    left_gradient: Rc<Gradient<Array<T, D>, D>>,
+    gradient: Rc<BufferedGradient<Array<T, Broadcast<D, E>>, Broadcast<D, E>>>,
 }
 
-impl<D, E> MultiplicationBackwardLeft<D, E>
+impl<D, E, T> MultiplicationBackwardLeft<D, E, T>
 where
     D: Dimension + DimMax<E>,
     E: Dimension,
+    T: Float,
 {
     pub(crate) fn new(
-        right_data: Shared<Array<f32, E>>,
-        left_gradient: Rc<Gradient<Array<f32, D>, D>>,
-        gradient: Rc<BufferedGradient<Array<f32, Broadcast<D, E>>, Broadcast<D, E>>>,
+        right_data: Shared<Array<T, E>>,
+        left_gradient: Rc<Gradient<Array<T, D>, D>>,
+        gradient: Rc<BufferedGradient<Array<T, Broadcast<D, E>>, Broadcast<D, E>>>,
     ) -> Self {
         debug_assert!(left_gradient
             .borrow()
@@ -82,10 +137,11 @@ where
     }
 }
 
-impl<D, E> Backward for MultiplicationBackwardLeft<D, E>
+impl<D, E, T> Backward for MultiplicationBackwardLeft<D, E, T>
 where
-    D: Dimension + DimMax<E>,
-    E: Dimension,
+    D: 'static + Dimension + DimMax<E>,
+    E: 'static + Dimension,
+    T: Float,
 {
     fn backward(&self) {
         let mut buffer = self.gradient.buffer_mut();
@@ -97,27 +153,60 @@ where
 
         accumulate(&mut self.left_gradient.borrow_mut(), &buffer);
     }
+
+    fn elementwise(&self) -> Option<&dyn ElementwiseBackward> {
+        Some(self)
+    }
+
+    fn reads(&self) -> Reads {
+        Reads::Operands
+    }
+}
+
+impl<D, E, T> ElementwiseBackward for MultiplicationBackwardLeft<D, E, T>
+where
+    D: 'static + Dimension + DimMax<E>,
+    E: 'static + Dimension,
+    T: Float,
+{
+    fn fusible_len(&self) -> Option<usize> {
+        fusible_len(
+            standard_len(&self.gradient.borrow()),
+            &[standard_len(&self.left_gradient.borrow())],
+            &[standard_len(&self.right_data.borrow())],
+        )
+    }
+
+    fn kernel(&self) -> Box<dyn Any> {
+        Box::new(BackwardKernel::new(self.gradient.clone()).accumulate_with(
+            self.left_gradient.clone(),
+            &self.right_data,
+            |g, r| g * r,
+        ))
+    }
 }
 
-pub(crate) struct MultiplicationBackwardRight<D, E>
+pub(crate) struct MultiplicationBackwardRight<D, E, T>
 where
     D: Dimension + DimMax<E>,
     E: Dimension,
+    T: Float,
 {
-    left_data: Shared<Array<f32, D>>,
-    right_gradient: Rc<Gradient<Array<f32, E>, E>>,
-    gradient: Rc<BufferedGradient<Array<f32, Broadcast<D, E>>, Broadcast<D, E>>>,
+    left_data: Shared<Array<T, D>>,
+    right_gradient: Rc<Gradient<Array<T, E>, E>>,
+    gradient: Rc<BufferedGradient<Array<T, Broadcast<D, E>>, Broadcast<D, E>>>,
 }
 
-impl<D, E> MultiplicationBackwardRight<D, E>
+impl<D, E, T> MultiplicationBackwardRight<D, E, T>
 where
     D: Dimension + DimMax<E>,
     E: Dimension,
+    T: Float,
 {
     pub(crate) fn new(
-        left_data: Shared<Array<f32, D>>,
-        right_gradient: Rc<Gradient<Array<f32, E>, E>>,
-        gradient: Rc<BufferedGradient<Array<f32, Broadcast<D, E>>, Broadcast<D, E>>>,
+        left_data: Shared<Array<T, D>>,
+        right_gradient: Rc<Gradient<Array<T, E>, E>>,
+        gradient: Rc<BufferedGradient<Array<T, Broadcast<D, E>>, Broadcast<D, E>>>,
     ) -> Self {
         debug_assert!(right_gradient
             .borrow()
@@ -132,10 +221,11 @@ where
     }
 }
 
-impl<D, E> Backward for MultiplicationBackwardRight<D, E>
+impl<D, E, T> Backward for MultiplicationBackwardRight<D, E, T>
 where
-    D: Dimension + DimMax<E>,
-    E: Dimension,
+    D: 'static + Dimension + DimMax<E>,
+    E: 'static + Dimension,
+    T: Float,
 {
     fn backward(&self) {
         let mut buffer = self.gradient.buffer_mut();
@@ -146,39 +236,102 @@ where
 
         accumulate(&mut self.right_gradient.borrow_mut(), &buffer);
     }
+
+    fn elementwise(&self) -> Option<&dyn ElementwiseBackward> {
+        Some(self)
+    }
+
+    fn reads(&self) -> Reads {
+        Reads::Operands
+    }
 }
 
-pub(crate) struct MultiplicationBackward<D, E>
+impl<D, E, T> ElementwiseBackward for MultiplicationBackwardRight<D, E, T>
+where
+    D: 'static + Dimension + DimMax<E>,
+    E: 'static + Dimension,
+    T: Float,
+{
+    fn fusible_len(&self) -> Option<usize> {
+        fusible_len(
+            standard_len(&self.gradient.borrow()),
+            &[standard_len(&self.right_gradient.borrow())],
+            &[standard_len(&self.left_data.borrow())],
+        )
+    }
+
+    fn kernel(&self) -> Box<dyn Any> {
+        Box::new(BackwardKernel::new(self.gradient.clone()).accumulate_with(
+            self.right_gradient.clone(),
+            &self.left_data,
+            |g, l| g * l,
+        ))
+    }
+}
+
+pub(crate) struct MultiplicationBackward<D, E, T>
 where
     D: Dimension + DimMax<E>,
     E: Dimension,
+    T: Float,
 {
-    left: MultiplicationBackwardLeft<D, E>,
-    right: MultiplicationBackwardRight<D, E>,
+    left: MultiplicationBackwardLeft<D, E, T>,
+    right: MultiplicationBackwardRight<D, E, T>,
 }
 
-impl<D, E> MultiplicationBackward<D, E>
+impl<D, E, T> MultiplicationBackward<D, E, T>
 where
     D: Dimension + DimMax<E>,
     E: Dimension,
+    T: Float,
 {
     pub(crate) fn new(
-        left: MultiplicationBackwardLeft<D, E>,
-        right: MultiplicationBackwardRight<D, E>,
+        left: MultiplicationBackwardLeft<D, E, T>,
+        right: MultiplicationBackwardRight<D, E, T>,
     ) -> Self {
         Self { left, right }
     }
 }
 
-impl<D, E> Backward for MultiplicationBackward<D, E>
+impl<D, E, T> Backward for MultiplicationBackward<D, E, T>
 where
-    D: Dimension + DimMax<E>,
-    E: Dimension,
+    D: 'static + Dimension + DimMax<E>,
+    E: 'static + Dimension,
+    T: Float,
 {
     fn backward(&self) {
         self.left.backward();
         self.right.backward();
     }
+
+    fn elementwise(&self) -> Option<&dyn ElementwiseBackward> {
+        Some(self)
+    }
+
+    fn reads(&self) -> Reads {
+        Reads::Operands
+    }
+}
+
+impl<D, E, T> ElementwiseBackward for MultiplicationBackward<D, E, T>
+where
+    D: 'static + Dimension + DimMax<E>,
+    E: 'static + Dimension,
+    T: Float,
+{
+    fn fusible_len(&self) -> Option<usize> {
+        let len = self.left.fusible_len()?;
+
+        if self.right.fusible_len()? == len {
+            Some(len)
+        } else {
+            None
+        }
+    }
+
+    fn kernel(&self) -> Box<dyn Any> {
+        Box::new(BackwardKernel::<T>::of(&self.left).and(BackwardKernel::of(&self.right)))
+    }
 }
 
 #[cfg(test)]
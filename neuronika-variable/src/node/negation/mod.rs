@@ -1,56 +1,102 @@
-use std::rc::Rc;
+use std::any::Any;
 
 use ndarray::{Array, Dimension, Zip};
 
 use crate::{
-    autograd::{Backward, Forward},
+    autograd::{Backward, Forward, Reads},
+    cell::Rc,
+    fusion::{
+        fusible_len, standard_len, BackwardKernel, ElementwiseBackward, ElementwiseForward,
+        ForwardKernel,
+    },
     gradient::Gradient,
+    graph::Buffer,
     utils::Shared,
+    Float,
 };
 
-pub(crate) struct Negation<D>
+pub(crate) struct Negation<D, T>
 where
     D: Dimension,
+    T: Float,
 {
-    operand_data: Shared<Array<f32, D>>,
-    data: Shared<Array<f32, D>>,
+    operand_data: Shared<Array<T, D>>,
+    data: Shared<Array<T, D>>,
 }
 
-impl<D> Negation<D>
+impl<D, T> Negation<D, T>
 where
     D: Dimension,
+    T: Float,
 {
-    pub(crate) fn new(operand_data: Shared<Array<f32, D>>, data: Shared<Array<f32, D>>) -> Self {
+    pub(crate) fn new(operand_data: Shared<Array<T, D>>, data: Shared<Array<T, D>>) -> Self {
         Self { operand_data, data }
     }
 }
 
-impl<D> Forward for Negation<D>
+impl<D, T> Forward for Negation<D, T>
 where
-    D: Dimension,
+    D: 'static + Dimension,
+    T: Float,
 {
     fn forward(&self) {
         Zip::from(&mut *self.data.borrow_mut())
             .and(&*self.operand_data.borrow())
             .for_each(|v, &o| *v = -o);
     }
+
+    fn name(&self) -> &'static str {
+        "Negation"
+    }
+
+    fn operands(&self) -> Vec<Buffer> {
+        vec![Buffer::new(&self.operand_data)]
+    }
+
+    fn data(&self) -> Buffer {
+        Buffer::new(&self.data)
+    }
+
+    fn elementwise(&self) -> Option<&dyn ElementwiseForward> {
+        Some(self)
+    }
+}
+
+impl<D, T> ElementwiseForward for Negation<D, T>
+where
+    D: 'static + Dimension,
+    T: Float,
+{
+    fn fusible_len(&self) -> Option<usize> {
+        fusible_len(
+            standard_len(&self.data.borrow()),
+            &[],
+            &[standard_len(&self.operand_data.borrow())],
+        )
+    }
+
+    fn kernel(&self) -> Box<dyn Any> {
+        Box::new(ForwardKernel::unary(&self.operand_data, &self.data, |o| -o))
+    }
 }
 
-pub(crate) struct NegationBackward<D>
+pub(crate) struct NegationBackward<D, T>
 where
     D: Dimension,
+    T: Float,
 {
-    operand_gradient: Rc<Gradient<Array<f32, D>, D>>,
-    gradient: Rc<Gradient<Array<f32, D>, D>>,
+    operand_gradient: Rc<Gradient<Array<T, D>, D>>,
+    gradient: Rc<Gradient<Array<T, D>, D>>,
 }
 
-impl<D> NegationBackward<D>
+impl<D, T> NegationBackward<D, T>
 where
     D: Dimension,
+    T: Float,
 {
     pub(crate) fn new(
-        operand_gradient: Rc<Gradient<Array<f32, D>, D>>,
-        gradient: Rc<Gradient<Array<f32, D>, D>>,
+        operand_gradient: Rc<Gradient<Array<T, D>, D>>,
+        gradient: Rc<Gradient<Array<T, D>, D>>,
     ) -> Self {
         Self {
             operand_gradient,
@@ -59,13 +105,43 @@ where
     }
 }
 
-impl<D> Backward for NegationBackward<D>
+impl<D, T> Backward for NegationBackward<D, T>
 where
-    D: Dimension,
+    D: 'static + Dimension,
+    T: Float,
 {
     fn backward(&self) {
         *self.operand_gradient.borrow_mut() -= &*self.gradient.borrow();
     }
+
+    fn elementwise(&self) -> Option<&dyn ElementwiseBackward> {
+        Some(self)
+    }
+
+    fn reads(&self) -> Reads {
+        Reads::Nothing
+    }
+}
+
+impl<D, T> ElementwiseBackward for NegationBackward<D, T>
+where
+    D: 'static + Dimension,
+    T: Float,
+{
+    fn fusible_len(&self) -> Option<usize> {
+        fusible_len(
+            standard_len(&self.gradient.borrow()),
+            &[standard_len(&self.operand_gradient.borrow())],
+            &[],
+        )
+    }
+
+    fn kernel(&self) -> Box<dyn Any> {
+        Box::new(
+            BackwardKernel::new(self.gradient.clone())
+                .accumulate(self.operand_gradient.clone(), |g| -g),
+        )
+    }
 }
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
@@ -1,33 +1,37 @@
-use std::rc::Rc;
-
 use ndarray::{arr0, Array, Axis, Dimension, Ix0, RemoveAxis, Zip};
 
 use crate::{
-    autograd::{Backward, Forward},
+    autograd::{Backward, Forward, Reads},
+    cell::Rc,
     gradient::Gradient,
+    graph::Buffer,
     utils::Shared,
-    Reduction,
+    AsIndex, Float, Reduction,
 };
 
 #[allow(clippy::upper_case_acronyms)]
-pub(crate) struct NegativeLogLikelihood<D>
+pub(crate) struct NegativeLogLikelihood<D, T, L>
 where
     D: Dimension + RemoveAxis,
+    T: Float,
+    L: AsIndex,
 {
-    input_data: Shared<Array<f32, D>>,
-    target_data: Shared<Array<f32, D::Smaller>>,
-    data: Shared<Array<f32, Ix0>>,
+    input_data: Shared<Array<T, D>>,
+    target_data: Shared<Array<L, D::Smaller>>,
+    data: Shared<Array<T, Ix0>>,
     reduction: Reduction,
 }
 
-impl<D> NegativeLogLikelihood<D>
+impl<D, T, L> NegativeLogLikelihood<D, T, L>
 where
     D: Dimension + RemoveAxis,
+    T: Float,
+    L: AsIndex,
 {
     pub(crate) fn new(
-        input_data: Shared<Array<f32, D>>,
-        target_data: Shared<Array<f32, D::Smaller>>,
-        data: Shared<Array<f32, Ix0>>,
+        input_data: Shared<Array<T, D>>,
+        target_data: Shared<Array<L, D::Smaller>>,
+        data: Shared<Array<T, Ix0>>,
         reduction: Reduction,
     ) -> Self {
         Self {
@@ -39,53 +43,79 @@ where
     }
 }
 
-impl<D> Forward for NegativeLogLikelihood<D>
+impl<D, T, L> Forward for NegativeLogLikelihood<D, T, L>
 where
     D: Dimension + RemoveAxis,
+    T: Float,
+    L: AsIndex,
 {
     fn forward(&self) {
         let (input_data, target_data) = (self.input_data.borrow(), self.target_data.borrow());
 
         *self.data.borrow_mut() = {
-            let total_loss = input_data
-                .outer_iter()
-                .enumerate()
-                .fold(0., |loss, (idx, logits)| {
-                    loss + Zip::from(logits).and(&*target_data).fold(
-                        0.,
-                        |partial_loss, &logit, &target| {
-                            partial_loss + ((target as usize == idx) as u8 as f32) * logit
-                        },
-                    )
-                });
+            let total_loss =
+                input_data
+                    .axis_iter(Axis(1))
+                    .enumerate()
+                    .fold(T::zero(), |loss, (idx, logits)| {
+                        loss + Zip::from(logits).and(&*target_data).fold(
+                            T::zero(),
+                            |partial_loss, &logit, &target| {
+                                if target.to_index() == Some(idx) {
+                                    partial_loss + logit
+                                } else {
+                                    partial_loss
+                                }
+                            },
+                        )
+                    });
 
             match self.reduction {
-                Reduction::Mean => arr0(-total_loss / input_data.len_of(Axis(0)) as f32),
+                Reduction::Mean => arr0(-total_loss / T::from_usize(target_data.len())),
                 Reduction::Sum => arr0(-total_loss),
             }
         };
     }
+
+    fn name(&self) -> &'static str {
+        "NegativeLogLikelihood"
+    }
+
+    fn operands(&self) -> Vec<Buffer> {
+        vec![
+            Buffer::new(&self.input_data),
+            Buffer::new(&self.target_data),
+        ]
+    }
+
+    fn data(&self) -> Buffer {
+        Buffer::new(&self.data)
+    }
 }
 
 #[allow(clippy::upper_case_acronyms)]
-pub(crate) struct NegativeLogLikelihoodBackward<D>
+pub(crate) struct NegativeLogLikelihoodBackward<D, T, L>
 where
     D: Dimension + RemoveAxis,
+    T: Float,
+    L: AsIndex,
 {
-    target_data: Shared<Array<f32, D::Smaller>>,
-    input_gradient: Rc<Gradient<Array<f32, D>, D>>,
-    gradient: Rc<Gradient<Array<f32, Ix0>, Ix0>>,
+    target_data: Shared<Array<L, D::Smaller>>,
+    input_gradient: Rc<Gradient<Array<T, D>, D>>,
+    gradient: Rc<Gradient<Array<T, Ix0>, Ix0>>,
     reduction: Reduction,
 }
 
-impl<D> NegativeLogLikelihoodBackward<D>
+impl<D, T, L> NegativeLogLikelihoodBackward<D, T, L>
 where
     D: Dimension + RemoveAxis,
+    T: Float,
+    L: AsIndex,
 {
     pub(crate) fn new(
-        target_data: Shared<Array<f32, D::Smaller>>,
-        input_gradient: Rc<Gradient<Array<f32, D>, D>>,
-        gradient: Rc<Gradient<Array<f32, Ix0>, Ix0>>,
+        target_data: Shared<Array<L, D::Smaller>>,
+        input_gradient: Rc<Gradient<Array<T, D>, D>>,
+        gradient: Rc<Gradient<Array<T, Ix0>, Ix0>>,
         reduction: Reduction,
     ) -> Self {
         Self {
@@ -97,25 +127,29 @@ where
     }
 }
 
-impl<D> Backward for NegativeLogLikelihoodBackward<D>
+impl<D, T, L> Backward for NegativeLogLikelihoodBackward<D, T, L>
 where
     D: Dimension + RemoveAxis,
+    T: Float,
+    L: AsIndex,
 {
     fn backward(&self) {
         let mut input_gradient = self.input_gradient.borrow_mut();
         let gradient = self.gradient.borrow()[()];
         let target_data = self.target_data.borrow();
 
-        let iter = input_gradient.outer_iter_mut().enumerate();
+        let iter = input_gradient.axis_iter_mut(Axis(1)).enumerate();
 
         match self.reduction {
             Reduction::Mean => {
-                let n = target_data.len() as f32;
+                let n = T::from_usize(target_data.len());
                 iter.for_each(|(idx, gradient_channel)| {
                     Zip::from(gradient_channel)
                         .and(&*target_data)
                         .for_each(|grad_el, &target| {
-                            *grad_el -= gradient * ((target as usize == idx) as u8 as f32) / n
+                            if target.to_index() == Some(idx) {
+                                *grad_el -= gradient / n
+                            }
                         })
                 });
             }
@@ -124,12 +158,18 @@ where
                     Zip::from(gradient_channel)
                         .and(&*target_data)
                         .for_each(|grad_el, &target| {
-                            *grad_el -= gradient * (target as usize == idx) as u8 as f32
+                            if target.to_index() == Some(idx) {
+                                *grad_el -= gradient
+                            }
                         })
                 });
             }
         }
     }
+
+    fn reads(&self) -> Reads {
+        Reads::Operands
+    }
 }
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
@@ -1,20 +1,23 @@
 use ndarray::{ArrayView, ArrayViewMut, Dimension, RemoveAxis, Slice};
 
+use crate::Float;
+
 use super::{PaddingMode, SampleDim};
 
 /// Constant padding.
 #[derive(Copy, Clone, Debug)]
-pub struct Constant(pub f32);
+pub struct Constant<T = f32>(pub T);
 
-impl<D> PaddingMode<D> for Constant
+impl<D, T> PaddingMode<D, T> for Constant<T>
 where
     D: Dimension,
     D::Smaller: RemoveAxis,
+    T: Float,
 {
     fn pad(
         &self,
-        padded: &mut ArrayViewMut<f32, SampleDim<D>>,
-        base: &ArrayView<f32, SampleDim<D>>,
+        padded: &mut ArrayViewMut<T, SampleDim<D>>,
+        base: &ArrayView<T, SampleDim<D>>,
         padding: SampleDim<D>,
     ) {
         padded.map_inplace(|el| *el = self.0);
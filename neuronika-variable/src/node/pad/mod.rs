@@ -4,16 +4,17 @@ mod reflective;
 mod replicative;
 mod zero;
 
-use std::rc::Rc;
-
 use ndarray::{Array, Dimension, RemoveAxis, Slice};
 
 use rayon::iter::{IndexedParallelIterator, IntoParallelIterator, ParallelIterator};
 
 use crate::{
-    autograd::{Backward, Forward},
+    autograd::{Backward, Forward, Reads},
+    cell::Rc,
     gradient::Gradient,
+    graph::Buffer,
     utils::Shared,
+    Float,
 };
 
 pub use constant::Constant;
@@ -24,32 +25,32 @@ pub use zero::Zero;
 
 type SampleDim<D> = <<D as Dimension>::Smaller as Dimension>::Smaller;
 
-pub(crate) struct Pad<D, T>
+pub(crate) struct Pad<D, P, T>
 where
     D: Dimension,
     D::Smaller: RemoveAxis,
-    SampleDim<D>: Copy,
-    T: PaddingMode<D>,
+    P: PaddingMode<D, T>,
+    T: Float,
 {
-    operand_data: Shared<Array<f32, D>>,
-    data: Shared<Array<f32, D>>,
-    mode: T,
+    operand_data: Shared<Array<T, D>>,
+    data: Shared<Array<T, D>>,
+    mode: P,
     padding: SampleDim<D>,
     batch_collapsed_dim: D::Smaller,
     batch_collapsed_padded_dim: D::Smaller,
 }
 
-impl<D, T> Pad<D, T>
+impl<D, P, T> Pad<D, P, T>
 where
     D: Dimension,
     D::Smaller: RemoveAxis,
-    SampleDim<D>: Copy,
-    T: PaddingMode<D>,
+    P: PaddingMode<D, T>,
+    T: Float,
 {
     pub(crate) fn new(
-        operand_data: Shared<Array<f32, D>>,
-        data: Shared<Array<f32, D>>,
-        mode: T,
+        operand_data: Shared<Array<T, D>>,
+        data: Shared<Array<T, D>>,
+        mode: P,
         padding: SampleDim<D>,
     ) -> Self {
         let operand_data_dim = operand_data.borrow().raw_dim();
@@ -94,12 +95,12 @@ where
     }
 }
 
-impl<D, T> Forward for Pad<D, T>
+impl<D, P, T> Forward for Pad<D, P, T>
 where
     D: Dimension,
     D::Smaller: RemoveAxis,
-    SampleDim<D>: Copy,
-    T: PaddingMode<D>,
+    P: PaddingMode<D, T>,
+    T: Float,
 {
     fn forward(&self) {
         let operand_data = self.operand_data.borrow();
@@ -116,34 +117,48 @@ where
         );
 
         let mode = self.mode;
-        let padding = self.padding;
+        let padding = &self.padding;
 
         data_view_mut
             .outer_iter_mut()
             .into_par_iter()
             .zip(operand_data_view.outer_iter())
             .for_each(|(mut padded_sample, base_sample)| {
-                mode.pad(&mut padded_sample, &base_sample, padding)
+                mode.pad(&mut padded_sample, &base_sample, padding.clone())
             });
     }
+
+    fn name(&self) -> &'static str {
+        "Pad"
+    }
+
+    fn operands(&self) -> Vec<Buffer> {
+        vec![Buffer::new(&self.operand_data)]
+    }
+
+    fn data(&self) -> Buffer {
+        Buffer::new(&self.data)
+    }
 }
 
-pub(crate) struct PadBackward<D>
+pub(crate) struct PadBackward<D, T>
 where
     D: Dimension,
+    T: Float,
 {
-    operand_gradient: Rc<Gradient<Array<f32, D>, D>>,
-    gradient: Rc<Gradient<Array<f32, D>, D>>,
+    operand_gradient: Rc<Gradient<Array<T, D>, D>>,
+    gradient: Rc<Gradient<Array<T, D>, D>>,
     padding: SampleDim<D>,
 }
 
-impl<D> PadBackward<D>
+impl<D, T> PadBackward<D, T>
 where
     D: Dimension,
+    T: Float,
 {
     pub(crate) fn new(
-        operand_gradient: Rc<Gradient<Array<f32, D>, D>>,
-        gradient: Rc<Gradient<Array<f32, D>, D>>,
+        operand_gradient: Rc<Gradient<Array<T, D>, D>>,
+        gradient: Rc<Gradient<Array<T, D>, D>>,
         padding: SampleDim<D>,
     ) -> Self {
         Self {
@@ -154,9 +169,10 @@ where
     }
 }
 
-impl<D> Backward for PadBackward<D>
+impl<D, T> Backward for PadBackward<D, T>
 where
     D: Dimension,
+    T: Float,
 {
     fn backward(&self) {
         let mut operand_gradient = self.operand_gradient.borrow_mut();
@@ -179,4 +195,8 @@ where
 
         *operand_gradient += &gradient_slice;
     }
+
+    fn reads(&self) -> Reads {
+        Reads::Nothing
+    }
 }
@@ -1,17 +1,57 @@
-use ndarray::{ArrayView, ArrayViewMut, Dimension, RemoveAxis};
+use ndarray::{ArrayView, ArrayViewMut, Dimension, Ix1, Ix2, Ix3, Ix4, Ix5, IxDyn, RemoveAxis};
+
+use crate::Float;
 
 use super::SampleDim;
 
 /// Padding mode.
-pub trait PaddingMode<D>: Send + Sync + Copy
+pub trait PaddingMode<D, T = f32>: Send + Sync + Copy
 where
     D: Dimension,
     D::Smaller: RemoveAxis,
+    T: Float,
 {
     fn pad(
         &self,
-        padded: &mut ArrayViewMut<f32, SampleDim<D>>,
-        base: &ArrayView<f32, SampleDim<D>>,
+        padded: &mut ArrayViewMut<T, SampleDim<D>>,
+        base: &ArrayView<T, SampleDim<D>>,
         padding: SampleDim<D>,
     );
 }
+
+/// Pads a sample of dynamic dimensionality by dispatching on the number of its spatial dimensions
+/// to the implementation of `mode` for the corresponding static dimensionality.
+pub(super) fn pad_dynamic<P, T>(
+    mode: &P,
+    padded: &mut ArrayViewMut<T, IxDyn>,
+    base: &ArrayView<T, IxDyn>,
+    padding: IxDyn,
+) where
+    P: PaddingMode<Ix3, T> + PaddingMode<Ix4, T> + PaddingMode<Ix5, T>,
+    T: Float,
+{
+    match padding.ndim() {
+        1 => PaddingMode::<Ix3, T>::pad(
+            mode,
+            &mut padded.view_mut().into_dimensionality().unwrap(),
+            &base.view().into_dimensionality().unwrap(),
+            Ix1::from_dimension(&padding).unwrap(),
+        ),
+        2 => PaddingMode::<Ix4, T>::pad(
+            mode,
+            &mut padded.view_mut().into_dimensionality().unwrap(),
+            &base.view().into_dimensionality().unwrap(),
+            Ix2::from_dimension(&padding).unwrap(),
+        ),
+        3 => PaddingMode::<Ix5, T>::pad(
+            mode,
+            &mut padded.view_mut().into_dimensionality().unwrap(),
+            &base.view().into_dimensionality().unwrap(),
+            Ix3::from_dimension(&padding).unwrap(),
+        ),
+        spatial => panic!(
+            "error: padding mode not supported over {} spatial dimensions.",
+            spatial
+        ),
+    }
+}
@@ -1,16 +1,21 @@
-use ndarray::{ArrayView, ArrayViewMut, Dimension, Ix3, Ix4, Ix5};
+use ndarray::{ArrayView, ArrayViewMut, Dimension, Ix3, Ix4, Ix5, IxDyn};
 
-use super::{PaddingMode, SampleDim};
+use crate::Float;
+
+use super::{padding_mode::pad_dynamic, PaddingMode, SampleDim};
 
 /// Reflective padding.
 #[derive(Copy, Clone, Debug)]
 pub struct Reflective;
 
-impl PaddingMode<Ix3> for Reflective {
+impl<T> PaddingMode<Ix3, T> for Reflective
+where
+    T: Float,
+{
     fn pad(
         &self,
-        padded: &mut ArrayViewMut<f32, SampleDim<Ix3>>,
-        base: &ArrayView<f32, SampleDim<Ix3>>,
+        padded: &mut ArrayViewMut<T, SampleDim<Ix3>>,
+        base: &ArrayView<T, SampleDim<Ix3>>,
         padding: SampleDim<Ix3>,
     ) {
         let mut pos;
@@ -33,11 +38,14 @@ impl PaddingMode<Ix3> for Reflective {
     }
 }
 
-impl PaddingMode<Ix4> for Reflective {
+impl<T> PaddingMode<Ix4, T> for Reflective
+where
+    T: Float,
+{
     fn pad(
         &self,
-        padded: &mut ArrayViewMut<f32, SampleDim<Ix4>>,
-        base: &ArrayView<f32, SampleDim<Ix4>>,
+        padded: &mut ArrayViewMut<T, SampleDim<Ix4>>,
+        base: &ArrayView<T, SampleDim<Ix4>>,
         padding: SampleDim<Ix4>,
     ) {
         let (mut pos_x, mut pos_y);
@@ -77,11 +85,14 @@ impl PaddingMode<Ix4> for Reflective {
     }
 }
 
-impl PaddingMode<Ix5> for Reflective {
+impl<T> PaddingMode<Ix5, T> for Reflective
+where
+    T: Float,
+{
     fn pad(
         &self,
-        padded: &mut ArrayViewMut<f32, SampleDim<Ix5>>,
-        base: &ArrayView<f32, SampleDim<Ix5>>,
+        padded: &mut ArrayViewMut<T, SampleDim<Ix5>>,
+        base: &ArrayView<T, SampleDim<Ix5>>,
         padding: SampleDim<Ix5>,
     ) {
         let (mut pos_x, mut pos_y, mut pos_z);
@@ -134,5 +145,19 @@ impl PaddingMode<Ix5> for Reflective {
     }
 }
 
+impl<T> PaddingMode<IxDyn, T> for Reflective
+where
+    T: Float,
+{
+    fn pad(
+        &self,
+        padded: &mut ArrayViewMut<T, SampleDim<IxDyn>>,
+        base: &ArrayView<T, SampleDim<IxDyn>>,
+        padding: SampleDim<IxDyn>,
+    ) {
+        pad_dynamic(self, padded, base, padding)
+    }
+}
+
 #[cfg(test)]
 mod test;
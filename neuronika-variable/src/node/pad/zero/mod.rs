@@ -1,23 +1,26 @@
 use ndarray::{ArrayView, ArrayViewMut, Dimension, RemoveAxis};
 
+use crate::Float;
+
 use super::{Constant, PaddingMode, SampleDim};
 
 /// Zero padding.
 #[derive(Copy, Clone, Debug)]
 pub struct Zero;
 
-impl<D> PaddingMode<D> for Zero
+impl<D, T> PaddingMode<D, T> for Zero
 where
     D: Dimension,
     D::Smaller: RemoveAxis,
+    T: Float,
 {
     fn pad(
         &self,
-        padded: &mut ArrayViewMut<f32, SampleDim<D>>,
-        base: &ArrayView<f32, SampleDim<D>>,
+        padded: &mut ArrayViewMut<T, SampleDim<D>>,
+        base: &ArrayView<T, SampleDim<D>>,
         padding: SampleDim<D>,
     ) {
-        PaddingMode::<D>::pad(&Constant(0.0), padded, base, padding);
+        PaddingMode::<D, T>::pad(&Constant(T::zero()), padded, base, padding);
     }
 }
 
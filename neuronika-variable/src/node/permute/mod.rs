@@ -0,0 +1,121 @@
+use ndarray::{Array, Dimension};
+
+use crate::{
+    autograd::{Backward, Forward, Reads},
+    cell::Rc,
+    gradient::Gradient,
+    graph::Buffer,
+    utils::Shared,
+    Float,
+};
+
+pub(crate) struct Permute<D, T>
+where
+    D: Dimension,
+    T: Float,
+{
+    operand_data: Shared<Array<T, D>>,
+    data: Shared<Array<T, D>>,
+    axes: D,
+}
+
+impl<D, T> Permute<D, T>
+where
+    D: Dimension,
+    T: Float,
+{
+    pub(crate) fn new(
+        operand_data: Shared<Array<T, D>>,
+        data: Shared<Array<T, D>>,
+        axes: D,
+    ) -> Self {
+        Self {
+            operand_data,
+            data,
+            axes,
+        }
+    }
+}
+
+impl<D, T> Forward for Permute<D, T>
+where
+    D: Dimension,
+    T: Float,
+{
+    fn forward(&self) {
+        self.data.borrow_mut().assign(
+            &self
+                .operand_data
+                .borrow()
+                .view()
+                .permuted_axes(self.axes.clone()),
+        );
+    }
+
+    fn name(&self) -> &'static str {
+        "Permute"
+    }
+
+    fn operands(&self) -> Vec<Buffer> {
+        vec![Buffer::new(&self.operand_data)]
+    }
+
+    fn data(&self) -> Buffer {
+        Buffer::new(&self.data)
+    }
+}
+
+pub(crate) struct PermuteBackward<D, T>
+where
+    D: Dimension,
+    T: Float,
+{
+    operand_gradient: Rc<Gradient<Array<T, D>, D>>,
+    gradient: Rc<Gradient<Array<T, D>, D>>,
+    inverse_axes: D,
+}
+
+impl<D, T> PermuteBackward<D, T>
+where
+    D: Dimension,
+    T: Float,
+{
+    pub(crate) fn new(
+        operand_gradient: Rc<Gradient<Array<T, D>, D>>,
+        gradient: Rc<Gradient<Array<T, D>, D>>,
+        axes: D,
+    ) -> Self {
+        let mut inverse_axes = axes.clone();
+        axes.slice()
+            .iter()
+            .enumerate()
+            .for_each(|(position, &axis)| inverse_axes[axis] = position);
+
+        Self {
+            operand_gradient,
+            gradient,
+            inverse_axes,
+        }
+    }
+}
+
+impl<D, T> Backward for PermuteBackward<D, T>
+where
+    D: Dimension,
+    T: Float,
+{
+    fn backward(&self) {
+        *self.operand_gradient.borrow_mut() += &self
+            .gradient
+            .borrow()
+            .view()
+            .permuted_axes(self.inverse_axes.clone());
+    }
+
+    fn reads(&self) -> Reads {
+        Reads::Nothing
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,97 @@
+use std::error::Error;
+
+use ndarray::{array, Array};
+
+use crate::cell::Rc;
+use crate::utils::are_similar;
+
+mod forward {
+    use ndarray::array;
+
+    #[test]
+    fn permute_swap_axes() {
+        let x = crate::from_ndarray(array![[[1., 2., 3.], [4., 5., 6.]]]);
+        let permuted = x.clone().permute((2, 0, 1));
+        let swapped = x.swap_axes(0, 2);
+        for y in [&permuted, &swapped] {
+            y.forward();
+        }
+        assert_eq!(*permuted.data(), array![[[1., 4.]], [[2., 5.]], [[3., 6.]]]);
+        assert_eq!(
+            *swapped.data(),
+            array![[[1.], [4.]], [[2.], [5.]], [[3.], [6.]]]
+        );
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "error: [0, 0, 1] is not a permutation of the axes of a variable of 3 dimensions."
+    )]
+    fn not_a_permutation() {
+        let _ = crate::zeros((1, 2, 3)).permute((0, 0, 1));
+    }
+}
+
+mod backward {
+    use ndarray::{Dim, Ix3};
+
+    use super::super::{Backward, PermuteBackward};
+    use super::*;
+    use crate::gradient::Gradient;
+
+    #[test]
+    fn creation() -> Result<(), Box<dyn Error>> {
+        let op = PermuteBackward::<Ix3, f32>::new(
+            Rc::new(Gradient::ndarray_zeros(Dim([1, 2, 3]))),
+            Rc::new(Gradient::ndarray_zeros(Dim([3, 1, 2]))),
+            Dim([2, 0, 1]),
+        );
+
+        are_similar(op.operand_gradient.borrow(), &Array::zeros((1, 2, 3)))?;
+        are_similar(op.gradient.borrow(), &Array::zeros((3, 1, 2)))
+    }
+
+    #[test]
+    fn base_case() -> Result<(), Box<dyn Error>> {
+        let op = PermuteBackward::<Ix3, f32>::new(
+            Rc::new(Gradient::ndarray_zeros(Dim([1, 2, 3]))),
+            Rc::new(Gradient::from_ndarray(array![
+                [[1., 2.]],
+                [[3., 4.]],
+                [[5., 6.]]
+            ])),
+            Dim([2, 0, 1]),
+        );
+
+        op.backward();
+        are_similar(
+            op.operand_gradient.borrow(),
+            &array![[[1., 3., 5.], [2., 4., 6.]]],
+        )?;
+
+        op.backward();
+        are_similar(
+            op.operand_gradient.borrow(),
+            &array![[[2., 6., 10.], [4., 8., 12.]]],
+        )
+    }
+
+    #[test]
+    fn swap_axes() -> Result<(), Box<dyn Error>> {
+        let op = PermuteBackward::<Ix3, f32>::new(
+            Rc::new(Gradient::ndarray_zeros(Dim([1, 2, 3]))),
+            Rc::new(Gradient::from_ndarray(array![[
+                [1., 2.],
+                [3., 4.],
+                [5., 6.]
+            ]])),
+            Dim([0, 2, 1]),
+        );
+
+        op.backward();
+        are_similar(
+            op.operand_gradient.borrow(),
+            &array![[[1., 3., 5.], [2., 4., 6.]]],
+        )
+    }
+}
@@ -0,0 +1,166 @@
+use ndarray::{Array, Dimension, Ix4, Ix6};
+
+use crate::{
+    autograd::{Backward, Forward, Reads},
+    cell::Rc,
+    gradient::Gradient,
+    graph::Buffer,
+    utils::Shared,
+    Float,
+};
+
+/// Describes the rearrangement of the elements of a variable of shape `shape` performed by a pixel
+/// shuffle of factor `factor`, or by its inverse if `unshuffle` is `true`.
+///
+/// The variable is viewed with the first of the returned shapes, its axes are permuted as
+/// described by the second one and the result is viewed with the third one.
+fn rearrangement(shape: Ix4, factor: usize, unshuffle: bool) -> (Ix6, Ix6, Ix6) {
+    let (n, c, h, w) = shape.into_pattern();
+    if unshuffle {
+        let (h, w) = (h / factor, w / factor);
+        (
+            Ix6(n, c, h, factor, w, factor),
+            Ix6(0, 1, 3, 5, 2, 4),
+            Ix6(n, c, factor, factor, h, w),
+        )
+    } else {
+        let c = c / (factor * factor);
+        (
+            Ix6(n, c, factor, factor, h, w),
+            Ix6(0, 1, 4, 2, 5, 3),
+            Ix6(n, c, h, factor, w, factor),
+        )
+    }
+}
+
+pub(crate) struct PixelShuffle<T>
+where
+    T: Float,
+{
+    operand_data: Shared<Array<T, Ix4>>,
+    data: Shared<Array<T, Ix4>>,
+    operand_shape: Ix6,
+    axes: Ix6,
+    shape: Ix6,
+    unshuffle: bool,
+}
+
+impl<T> PixelShuffle<T>
+where
+    T: Float,
+{
+    pub(crate) fn new(
+        operand_data: Shared<Array<T, Ix4>>,
+        data: Shared<Array<T, Ix4>>,
+        factor: usize,
+        unshuffle: bool,
+    ) -> Self {
+        let (operand_shape, axes, shape) =
+            rearrangement(operand_data.borrow().raw_dim(), factor, unshuffle);
+
+        Self {
+            operand_data,
+            data,
+            operand_shape,
+            axes,
+            shape,
+            unshuffle,
+        }
+    }
+}
+
+impl<T> Forward for PixelShuffle<T>
+where
+    T: Float,
+{
+    fn forward(&self) {
+        let operand_data = self.operand_data.borrow();
+        let view = operand_data.to_shape(self.operand_shape).unwrap();
+
+        self.data
+            .borrow_mut()
+            .view_mut()
+            .into_shape(self.shape)
+            .unwrap()
+            .assign(&view.permuted_axes(self.axes));
+    }
+
+    fn name(&self) -> &'static str {
+        if self.unshuffle {
+            "PixelUnshuffle"
+        } else {
+            "PixelShuffle"
+        }
+    }
+
+    fn operands(&self) -> Vec<Buffer> {
+        vec![Buffer::new(&self.operand_data)]
+    }
+
+    fn data(&self) -> Buffer {
+        Buffer::new(&self.data)
+    }
+}
+
+pub(crate) struct PixelShuffleBackward<T>
+where
+    T: Float,
+{
+    operand_gradient: Rc<Gradient<Array<T, Ix4>, Ix4>>,
+    gradient: Rc<Gradient<Array<T, Ix4>, Ix4>>,
+    operand_shape: Ix6,
+    inverse_axes: Ix6,
+    shape: Ix6,
+}
+
+impl<T> PixelShuffleBackward<T>
+where
+    T: Float,
+{
+    pub(crate) fn new(
+        operand_gradient: Rc<Gradient<Array<T, Ix4>, Ix4>>,
+        gradient: Rc<Gradient<Array<T, Ix4>, Ix4>>,
+        factor: usize,
+        unshuffle: bool,
+    ) -> Self {
+        let (operand_shape, axes, shape) =
+            rearrangement(operand_gradient.shape(), factor, unshuffle);
+        let mut inverse_axes = axes;
+        axes.slice()
+            .iter()
+            .enumerate()
+            .for_each(|(position, &axis)| inverse_axes[axis] = position);
+
+        Self {
+            operand_gradient,
+            gradient,
+            operand_shape,
+            inverse_axes,
+            shape,
+        }
+    }
+}
+
+impl<T> Backward for PixelShuffleBackward<T>
+where
+    T: Float,
+{
+    fn backward(&self) {
+        let gradient = self.gradient.borrow();
+        let view = gradient.view().into_shape(self.shape).unwrap();
+
+        let mut operand_gradient = self.operand_gradient.borrow_mut();
+        let mut operand_view = operand_gradient
+            .view_mut()
+            .into_shape(self.operand_shape)
+            .unwrap();
+        operand_view += &view.permuted_axes(self.inverse_axes);
+    }
+
+    fn reads(&self) -> Reads {
+        Reads::Nothing
+    }
+}
+
+#[cfg(test)]
+mod test;
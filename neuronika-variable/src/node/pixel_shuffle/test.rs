@@ -0,0 +1,46 @@
+mod forward {
+    use ndarray::array;
+
+    #[test]
+    fn shuffle_unshuffle() {
+        let x = crate::from_ndarray(
+            ndarray::Array::from_iter((0..16).map(|i| i as f64))
+                .into_shape((1, 4, 2, 2))
+                .unwrap(),
+        );
+        let shuffled = x.clone().pixel_shuffle(2);
+        let unshuffled = shuffled.clone().pixel_unshuffle(2);
+        unshuffled.forward();
+        assert_eq!(
+            *shuffled.data(),
+            array![
+                [0., 4., 1., 5.],
+                [8., 12., 9., 13.],
+                [2., 6., 3., 7.],
+                [10., 14., 11., 15.]
+            ]
+            .into_shape((1, 1, 4, 4))
+            .unwrap()
+        );
+        assert_eq!(*unshuffled.data(), *x.data());
+    }
+}
+
+mod backward {
+    use crate::utils::weights;
+
+    #[test]
+    fn shuffle_unshuffle() {
+        let x = crate::from_ndarray(
+            ndarray::Array::from_iter((0..32).map(|i| (i as f64 * 1.3).cos()))
+                .into_shape((2, 4, 2, 2))
+                .unwrap(),
+        )
+        .requires_grad();
+        let inputs: [crate::Parameter<f64>; 1] = [x.clone().into()];
+        let f = || x.clone().pixel_shuffle(2) * weights(&[2, 1, 4, 4], 0.7);
+        assert_eq!(crate::gradcheck(f, &inputs, 1e-6, 1e-7, 1e-5), Ok(()));
+        let f = || x.clone().pixel_unshuffle(2) * weights(&[2, 16, 1, 1], 0.7);
+        assert_eq!(crate::gradcheck(f, &inputs, 1e-6, 1e-7, 1e-5), Ok(()));
+    }
+}
@@ -1,29 +1,38 @@
-use std::rc::Rc;
+use std::any::Any;
 
 use ndarray::{Array, Dimension, Zip};
 
 use crate::{
-    autograd::{Backward, Forward},
+    autograd::{Backward, Forward, Reads},
+    cell::Rc,
+    fusion::{
+        fusible_len, standard_len, BackwardKernel, ElementwiseBackward, ElementwiseForward,
+        ForwardKernel,
+    },
     gradient::Gradient,
+    graph::Buffer,
     utils::Shared,
+    Float,
 };
 
-pub(crate) struct Power<D>
+pub(crate) struct Power<D, T>
 where
     D: Dimension,
+    T: Float,
 {
-    operand_data: Shared<Array<f32, D>>,
-    data: Shared<Array<f32, D>>,
+    operand_data: Shared<Array<T, D>>,
+    data: Shared<Array<T, D>>,
     exp: i32,
 }
 
-impl<D> Power<D>
+impl<D, T> Power<D, T>
 where
     D: Dimension,
+    T: Float,
 {
     pub(crate) fn new(
-        operand_data: Shared<Array<f32, D>>,
-        data: Shared<Array<f32, D>>,
+        operand_data: Shared<Array<T, D>>,
+        data: Shared<Array<T, D>>,
         exp: i32,
     ) -> Self {
         Self {
@@ -34,35 +43,77 @@ where
     }
 }
 
-impl<D> Forward for Power<D>
+impl<D, T> Forward for Power<D, T>
 where
-    D: Dimension,
+    D: 'static + Dimension,
+    T: Float,
 {
     fn forward(&self) {
         Zip::from(&mut *self.data.borrow_mut())
             .and(&*self.operand_data.borrow())
             .for_each(|v, &o| *v = o.powi(self.exp));
     }
+
+    fn name(&self) -> &'static str {
+        "Power"
+    }
+
+    fn operands(&self) -> Vec<Buffer> {
+        vec![Buffer::new(&self.operand_data)]
+    }
+
+    fn data(&self) -> Buffer {
+        Buffer::new(&self.data)
+    }
+
+    fn elementwise(&self) -> Option<&dyn ElementwiseForward> {
+        Some(self)
+    }
+}
+
+impl<D, T> ElementwiseForward for Power<D, T>
+where
+    D: 'static + Dimension,
+    T: Float,
+{
+    fn fusible_len(&self) -> Option<usize> {
+        fusible_len(
+            standard_len(&self.data.borrow()),
+            &[],
+            &[standard_len(&self.operand_data.borrow())],
+        )
+    }
+
+    fn kernel(&self) -> Box<dyn Any> {
+        let exp = self.exp;
+        Box::new(ForwardKernel::unary(
+            &self.operand_data,
+            &self.data,
+            move |o| o.powi(exp),
+        ))
+    }
 }
 
-pub(crate) struct PowerBackward<D>
+pub(crate) struct PowerBackward<D, T>
 where
     D: Dimension,
+    T: Float,
 {
-    operand_gradient: Rc<Gradient<Array<f32, D>, D>>,
-    operand_data: Shared<Array<f32, D>>,
-    gradient: Rc<Gradient<Array<f32, D>, D>>,
+    operand_gradient: Rc<Gradient<Array<T, D>, D>>,
+    operand_data: Shared<Array<T, D>>,
+    gradient: Rc<Gradient<Array<T, D>, D>>,
     exp: i32,
 }
 
-impl<D> PowerBackward<D>
+impl<D, T> PowerBackward<D, T>
 where
     D: Dimension,
+    T: Float,
 {
     pub(crate) fn new(
-        operand_gradient: Rc<Gradient<Array<f32, D>, D>>,
-        operand_data: Shared<Array<f32, D>>,
-        gradient: Rc<Gradient<Array<f32, D>, D>>,
+        operand_gradient: Rc<Gradient<Array<T, D>, D>>,
+        operand_data: Shared<Array<T, D>>,
+        gradient: Rc<Gradient<Array<T, D>, D>>,
         exp: i32,
     ) -> Self {
         Self {
@@ -74,18 +125,51 @@ where
     }
 }
 
-impl<D> Backward for PowerBackward<D>
+impl<D, T> Backward for PowerBackward<D, T>
 where
-    D: Dimension,
+    D: 'static + Dimension,
+    T: Float,
 {
     fn backward(&self) {
         Zip::from(&mut *self.operand_gradient.borrow_mut())
             .and(&*self.gradient.borrow())
             .and(&*self.operand_data.borrow())
             .for_each(|op_grad_el, &grad_el, &op_data_el| {
-                *op_grad_el += grad_el * op_data_el.powi(self.exp - 1) * self.exp as f32;
+                *op_grad_el +=
+                    grad_el * op_data_el.powi(self.exp - 1) * T::from_f64(self.exp as f64);
             });
     }
+
+    fn elementwise(&self) -> Option<&dyn ElementwiseBackward> {
+        Some(self)
+    }
+
+    fn reads(&self) -> Reads {
+        Reads::Operands
+    }
+}
+
+impl<D, T> ElementwiseBackward for PowerBackward<D, T>
+where
+    D: 'static + Dimension,
+    T: Float,
+{
+    fn fusible_len(&self) -> Option<usize> {
+        fusible_len(
+            standard_len(&self.gradient.borrow()),
+            &[standard_len(&self.operand_gradient.borrow())],
+            &[standard_len(&self.operand_data.borrow())],
+        )
+    }
+
+    fn kernel(&self) -> Box<dyn Any> {
+        let exp = self.exp;
+        Box::new(BackwardKernel::new(self.gradient.clone()).accumulate_with(
+            self.operand_gradient.clone(),
+            &self.operand_data,
+            move |g, o| g * o.powi(exp - 1) * T::from_f64(exp as f64),
+        ))
+    }
 }
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
@@ -1,60 +1,108 @@
-use std::rc::Rc;
+use std::any::Any;
 
 use ndarray::{Array, Dimension, Zip};
 
 use crate::{
-    autograd::{Backward, Forward},
+    autograd::{Backward, Forward, Reads},
+    cell::Rc,
+    fusion::{
+        fusible_len, standard_len, BackwardKernel, ElementwiseBackward, ElementwiseForward,
+        ForwardKernel,
+    },
     gradient::Gradient,
+    graph::Buffer,
     utils::Shared,
+    Float,
 };
 
 #[allow(clippy::upper_case_acronyms)]
-pub(crate) struct ReLU<D>
+pub(crate) struct ReLU<D, T>
 where
     D: Dimension,
+    T: Float,
 {
-    operand_data: Shared<Array<f32, D>>,
-    data: Shared<Array<f32, D>>,
+    operand_data: Shared<Array<T, D>>,
+    data: Shared<Array<T, D>>,
 }
 
-impl<D> ReLU<D>
+impl<D, T> ReLU<D, T>
 where
     D: Dimension,
+    T: Float,
 {
-    pub(crate) fn new(operand_data: Shared<Array<f32, D>>, data: Shared<Array<f32, D>>) -> Self {
+    pub(crate) fn new(operand_data: Shared<Array<T, D>>, data: Shared<Array<T, D>>) -> Self {
         Self { operand_data, data }
     }
 }
 
-impl<D> Forward for ReLU<D>
+impl<D, T> Forward for ReLU<D, T>
 where
-    D: Dimension,
+    D: 'static + Dimension,
+    T: Float,
 {
     fn forward(&self) {
         Zip::from(&mut *self.data.borrow_mut())
             .and(&*self.operand_data.borrow())
-            .for_each(|v, &o| *v = o.max(0.));
+            .for_each(|v, &o| *v = o.max(T::zero()));
+    }
+
+    fn name(&self) -> &'static str {
+        "ReLU"
+    }
+
+    fn operands(&self) -> Vec<Buffer> {
+        vec![Buffer::new(&self.operand_data)]
+    }
+
+    fn data(&self) -> Buffer {
+        Buffer::new(&self.data)
+    }
+
+    fn elementwise(&self) -> Option<&dyn ElementwiseForward> {
+        Some(self)
+    }
+}
+
+impl<D, T> ElementwiseForward for ReLU<D, T>
+where
+    D: 'static + Dimension,
+    T: Float,
+{
+    fn fusible_len(&self) -> Option<usize> {
+        fusible_len(
+            standard_len(&self.data.borrow()),
+            &[],
+            &[standard_len(&self.operand_data.borrow())],
+        )
+    }
+
+    fn kernel(&self) -> Box<dyn Any> {
+        Box::new(ForwardKernel::unary(&self.operand_data, &self.data, |o| {
+            o.max(T::zero())
+        }))
     }
 }
 
 #[allow(clippy::upper_case_acronyms)]
-pub(crate) struct ReLUBackward<D>
+pub(crate) struct ReLUBackward<D, T>
 where
     D: Dimension,
+    T: Float,
 {
-    operand_gradient: Rc<Gradient<Array<f32, D>, D>>,
-    operand_data: Shared<Array<f32, D>>,
-    gradient: Rc<Gradient<Array<f32, D>, D>>,
+    operand_gradient: Rc<Gradient<Array<T, D>, D>>,
+    operand_data: Shared<Array<T, D>>,
+    gradient: Rc<Gradient<Array<T, D>, D>>,
 }
 
-impl<D> ReLUBackward<D>
+impl<D, T> ReLUBackward<D, T>
 where
     D: Dimension,
+    T: Float,
 {
     pub(crate) fn new(
-        operand_gradient: Rc<Gradient<Array<f32, D>, D>>,
-        operand_data: Shared<Array<f32, D>>,
-        gradient: Rc<Gradient<Array<f32, D>, D>>,
+        operand_gradient: Rc<Gradient<Array<T, D>, D>>,
+        operand_data: Shared<Array<T, D>>,
+        gradient: Rc<Gradient<Array<T, D>, D>>,
     ) -> Self {
         Self {
             operand_gradient,
@@ -64,18 +112,57 @@ where
     }
 }
 
-impl<D> Backward for ReLUBackward<D>
+impl<D, T> Backward for ReLUBackward<D, T>
 where
-    D: Dimension,
+    D: 'static + Dimension,
+    T: Float,
 {
     fn backward(&self) {
         Zip::from(&mut *self.operand_gradient.borrow_mut())
             .and(&*self.gradient.borrow())
             .and(&*self.operand_data.borrow())
             .for_each(|op_grad_el, &grad_el, &op_data_el| {
-                *op_grad_el += ((op_data_el > 0.) as usize as f32) * grad_el;
+                if op_data_el > T::zero() {
+                    *op_grad_el += grad_el;
+                }
             });
     }
+
+    fn elementwise(&self) -> Option<&dyn ElementwiseBackward> {
+        Some(self)
+    }
+
+    fn reads(&self) -> Reads {
+        Reads::Operands
+    }
+}
+
+impl<D, T> ElementwiseBackward for ReLUBackward<D, T>
+where
+    D: 'static + Dimension,
+    T: Float,
+{
+    fn fusible_len(&self) -> Option<usize> {
+        fusible_len(
+            standard_len(&self.gradient.borrow()),
+            &[standard_len(&self.operand_gradient.borrow())],
+            &[standard_len(&self.operand_data.borrow())],
+        )
+    }
+
+    fn kernel(&self) -> Box<dyn Any> {
+        Box::new(BackwardKernel::new(self.gradient.clone()).accumulate_with(
+            self.operand_gradient.clone(),
+            &self.operand_data,
+            |g, o| {
+                if o > T::zero() {
+                    g
+                } else {
+                    T::zero()
+                }
+            },
+        ))
+    }
 }
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
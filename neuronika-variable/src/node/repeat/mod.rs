@@ -0,0 +1,141 @@
+use ndarray::{Array, Dimension, IxDyn};
+
+use crate::{
+    autograd::{Backward, Forward, Reads},
+    cell::Rc,
+    gradient::Gradient,
+    graph::Buffer,
+    utils::{accumulate, Shared},
+    Float,
+};
+
+/// Repeats the elements of a variable.
+///
+/// The result, once viewed with shape `blocks`, is the operand viewed with shape `operand_shape`
+/// broadcast to `blocks`. Interleaving ones in `operand_shape` expresses tiling, expansion and
+/// element-wise repetition alike.
+pub(crate) struct Repeat<D, E, T>
+where
+    D: Dimension,
+    E: Dimension,
+    T: Float,
+{
+    operand_data: Shared<Array<T, D>>,
+    data: Shared<Array<T, E>>,
+    operand_shape: IxDyn,
+    blocks: IxDyn,
+}
+
+impl<D, E, T> Repeat<D, E, T>
+where
+    D: Dimension,
+    E: Dimension,
+    T: Float,
+{
+    pub(crate) fn new(
+        operand_data: Shared<Array<T, D>>,
+        data: Shared<Array<T, E>>,
+        operand_shape: IxDyn,
+        blocks: IxDyn,
+    ) -> Self {
+        debug_assert_eq!(operand_shape.size(), operand_data.borrow().len());
+        debug_assert_eq!(blocks.size(), data.borrow().len());
+
+        Self {
+            operand_data,
+            data,
+            operand_shape,
+            blocks,
+        }
+    }
+}
+
+impl<D, E, T> Forward for Repeat<D, E, T>
+where
+    D: Dimension,
+    E: Dimension,
+    T: Float,
+{
+    fn forward(&self) {
+        let mut data = self.data.borrow_mut();
+        let operand_data = self.operand_data.borrow();
+        let operand_view = operand_data.to_shape(self.operand_shape.clone()).unwrap();
+
+        data.view_mut()
+            .into_shape(self.blocks.clone())
+            .unwrap()
+            .assign(&operand_view.broadcast(self.blocks.clone()).unwrap());
+    }
+
+    fn name(&self) -> &'static str {
+        "Repeat"
+    }
+
+    fn operands(&self) -> Vec<Buffer> {
+        vec![Buffer::new(&self.operand_data)]
+    }
+
+    fn data(&self) -> Buffer {
+        Buffer::new(&self.data)
+    }
+}
+
+pub(crate) struct RepeatBackward<D, E, T>
+where
+    D: Dimension,
+    E: Dimension,
+    T: Float,
+{
+    operand_gradient: Rc<Gradient<Array<T, D>, D>>,
+    gradient: Rc<Gradient<Array<T, E>, E>>,
+    operand_shape: IxDyn,
+    blocks: IxDyn,
+}
+
+impl<D, E, T> RepeatBackward<D, E, T>
+where
+    D: Dimension,
+    E: Dimension,
+    T: Float,
+{
+    pub(crate) fn new(
+        operand_gradient: Rc<Gradient<Array<T, D>, D>>,
+        gradient: Rc<Gradient<Array<T, E>, E>>,
+        operand_shape: IxDyn,
+        blocks: IxDyn,
+    ) -> Self {
+        Self {
+            operand_gradient,
+            gradient,
+            operand_shape,
+            blocks,
+        }
+    }
+}
+
+impl<D, E, T> Backward for RepeatBackward<D, E, T>
+where
+    D: Dimension,
+    E: Dimension,
+    T: Float,
+{
+    fn backward(&self) {
+        let gradient = self.gradient.borrow();
+        let mut operand_gradient = self.operand_gradient.borrow_mut();
+
+        accumulate(
+            &mut operand_gradient
+                .view_mut()
+                .into_shape(self.operand_shape.clone())
+                .unwrap(),
+            &gradient.view().into_shape(self.blocks.clone()).unwrap(),
+        );
+    }
+
+    fn reads(&self) -> Reads {
+        Reads::Nothing
+    }
+}
+
+#[cfg(test)]
+mod test;
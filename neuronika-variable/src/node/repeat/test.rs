@@ -0,0 +1,140 @@
+use std::error::Error;
+
+use ndarray::{array, IxDyn};
+
+use crate::cell::Rc;
+use crate::utils::are_similar;
+
+mod forward {
+    use ndarray::array;
+
+    #[test]
+    fn broadcast_repeat_interleave() {
+        let x = crate::from_ndarray(array![[1., 2.], [3., 4.]]);
+        let broadcast = x.clone().broadcast_to((2, 2, 2));
+        let tiled = x.clone().repeat((1, 2));
+        let interleaved = x.clone().repeat_interleave(2, 0);
+        for y in [&tiled, &interleaved] {
+            y.forward();
+        }
+        broadcast.forward();
+        assert_eq!(
+            *broadcast.data(),
+            array![[[1., 2.], [3., 4.]], [[1., 2.], [3., 4.]]]
+        );
+        assert_eq!(*tiled.data(), array![[1., 2., 1., 2.], [3., 4., 3., 4.]]);
+        assert_eq!(
+            *interleaved.data(),
+            array![[1., 2.], [1., 2.], [3., 4.], [3., 4.]]
+        );
+    }
+
+    #[test]
+    fn expand() {
+        // Repeats a context vector across timesteps.
+        let context = crate::from_ndarray(array![[1., 2.], [3., 4.]]);
+        let y = context.unsqueeze(1).expand(1, 3);
+        y.forward();
+        assert_eq!(
+            *y.data(),
+            array![
+                [[1., 2.], [1., 2.], [1., 2.]],
+                [[3., 4.], [3., 4.], [3., 4.]]
+            ]
+        );
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "error: variable of shape [2, 3] cannot be broadcast to shape [3, 3]."
+    )]
+    fn broadcast_to_incompatible() {
+        let _ = crate::zeros((2, 3)).broadcast_to((3, 3));
+    }
+}
+
+mod backward {
+    use ndarray::{Ix2, Ix3};
+
+    use super::super::{Backward, RepeatBackward};
+    use super::*;
+    use crate::gradient::Gradient;
+
+    #[test]
+    fn broadcast() -> Result<(), Box<dyn Error>> {
+        let op = RepeatBackward::<Ix2, Ix3, f32>::new(
+            Rc::new(Gradient::ndarray_zeros((2, 3))),
+            Rc::new(Gradient::from_ndarray(array![
+                [[1., 2., 3.], [4., 5., 6.]],
+                [[7., 8., 9.], [10., 11., 12.]]
+            ])),
+            IxDyn(&[1, 2, 3]),
+            IxDyn(&[2, 2, 3]),
+        );
+
+        op.backward();
+        are_similar(
+            op.operand_gradient.borrow(),
+            &array![[8., 10., 12.], [14., 16., 18.]],
+        )?;
+
+        op.backward();
+        are_similar(
+            op.operand_gradient.borrow(),
+            &array![[16., 20., 24.], [28., 32., 36.]],
+        )
+    }
+
+    #[test]
+    fn expand() -> Result<(), Box<dyn Error>> {
+        let op = RepeatBackward::<Ix3, Ix3, f32>::new(
+            Rc::new(Gradient::ndarray_zeros((2, 1, 2))),
+            Rc::new(Gradient::from_ndarray(array![
+                [[1., 2.], [3., 4.], [5., 6.]],
+                [[7., 8.], [9., 10.], [11., 12.]]
+            ])),
+            IxDyn(&[2, 1, 2]),
+            IxDyn(&[2, 3, 2]),
+        );
+
+        op.backward();
+        are_similar(
+            op.operand_gradient.borrow(),
+            &array![[[9., 12.]], [[27., 30.]]],
+        )
+    }
+
+    #[test]
+    fn tile() -> Result<(), Box<dyn Error>> {
+        // Tiling twice along the columns views the result as two blocks of each row.
+        let op = RepeatBackward::<Ix2, Ix2, f32>::new(
+            Rc::new(Gradient::ndarray_zeros((2, 2))),
+            Rc::new(Gradient::from_ndarray(array![
+                [1., 2., 3., 4.],
+                [5., 6., 7., 8.]
+            ])),
+            IxDyn(&[2, 1, 2]),
+            IxDyn(&[2, 2, 2]),
+        );
+
+        op.backward();
+        are_similar(op.operand_gradient.borrow(), &array![[4., 6.], [12., 14.]])
+    }
+
+    #[test]
+    fn interleave() -> Result<(), Box<dyn Error>> {
+        // Repeating each column twice views the result as two copies of each element.
+        let op = RepeatBackward::<Ix2, Ix2, f32>::new(
+            Rc::new(Gradient::ndarray_zeros((2, 2))),
+            Rc::new(Gradient::from_ndarray(array![
+                [1., 2., 3., 4.],
+                [5., 6., 7., 8.]
+            ])),
+            IxDyn(&[2, 2, 1]),
+            IxDyn(&[2, 2, 2]),
+        );
+
+        op.backward();
+        are_similar(op.operand_gradient.borrow(), &array![[3., 7.], [11., 15.]])
+    }
+}
@@ -0,0 +1,111 @@
+use ndarray::{Array, Dimension};
+
+use crate::{
+    autograd::{Backward, Forward, Reads},
+    cell::Rc,
+    gradient::Gradient,
+    graph::Buffer,
+    utils::Shared,
+    Element, Float,
+};
+
+/// Lays out the elements of a variable, in logical order, into one of a different shape.
+pub(crate) struct Reshape<D, E, T>
+where
+    D: Dimension,
+    E: Dimension,
+    T: Element,
+{
+    operand_data: Shared<Array<T, D>>,
+    data: Shared<Array<T, E>>,
+}
+
+impl<D, E, T> Reshape<D, E, T>
+where
+    D: Dimension,
+    E: Dimension,
+    T: Element,
+{
+    pub(crate) fn new(operand_data: Shared<Array<T, D>>, data: Shared<Array<T, E>>) -> Self {
+        Self { operand_data, data }
+    }
+}
+
+impl<D, E, T> Forward for Reshape<D, E, T>
+where
+    D: Dimension,
+    E: Dimension,
+    T: Element,
+{
+    fn forward(&self) {
+        self.data
+            .borrow_mut()
+            .iter_mut()
+            .zip(self.operand_data.borrow().iter())
+            .for_each(|(data_el, &operand_data_el)| *data_el = operand_data_el);
+    }
+
+    fn name(&self) -> &'static str {
+        "Reshape"
+    }
+
+    fn operands(&self) -> Vec<Buffer> {
+        vec![Buffer::new(&self.operand_data)]
+    }
+
+    fn data(&self) -> Buffer {
+        Buffer::new(&self.data)
+    }
+}
+
+pub(crate) struct ReshapeBackward<D, E, T>
+where
+    D: Dimension,
+    E: Dimension,
+    T: Float,
+{
+    operand_gradient: Rc<Gradient<Array<T, D>, D>>,
+    gradient: Rc<Gradient<Array<T, E>, E>>,
+}
+
+impl<D, E, T> ReshapeBackward<D, E, T>
+where
+    D: Dimension,
+    E: Dimension,
+    T: Float,
+{
+    pub(crate) fn new(
+        operand_gradient: Rc<Gradient<Array<T, D>, D>>,
+        gradient: Rc<Gradient<Array<T, E>, E>>,
+    ) -> Self {
+        Self {
+            operand_gradient,
+            gradient,
+        }
+    }
+}
+
+impl<D, E, T> Backward for ReshapeBackward<D, E, T>
+where
+    D: Dimension,
+    E: Dimension,
+    T: Float,
+{
+    fn backward(&self) {
+        let mut operand_gradient = self.operand_gradient.borrow_mut();
+        let gradient = self.gradient.borrow();
+
+        // Both are traversed in logical order, regardless of their memory layout.
+        operand_gradient
+            .iter_mut()
+            .zip(gradient.iter())
+            .for_each(|(operand_gradient_el, &gradient_el)| *operand_gradient_el += gradient_el);
+    }
+
+    fn reads(&self) -> Reads {
+        Reads::Nothing
+    }
+}
+
+#[cfg(test)]
+mod test;
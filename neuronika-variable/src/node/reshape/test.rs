@@ -0,0 +1,34 @@
+mod forward {
+    use ndarray::array;
+
+    #[test]
+    fn reshape() {
+        let x = crate::from_ndarray(array![[1., 2., 3.], [4., 5., 6.]]);
+        let y = x.clone().t().reshape((3, 2));
+        y.forward();
+        assert_eq!(*y.data(), array![[1., 4.], [2., 5.], [3., 6.]]);
+    }
+
+    #[test]
+    #[should_panic(expected = "error: variable of shape [2, 3] cannot be reshaped into [4, 2].")]
+    fn wrong_size() {
+        let _ = crate::zeros((2, 3)).reshape((4, 2));
+    }
+}
+
+mod backward {
+    use crate::utils::weights;
+
+    #[test]
+    fn reshape() {
+        let x = crate::from_ndarray(
+            ndarray::Array::from_iter((0..24).map(|i| (i as f64 * 0.3).cos()))
+                .into_shape((2, 3, 4))
+                .unwrap(),
+        )
+        .requires_grad();
+        let inputs: [crate::Parameter<f64>; 1] = [x.clone().into()];
+        let f = || x.clone().permute((1, 0, 2)).reshape((6, 4)) * weights(&[6, 4], 0.7);
+        assert_eq!(crate::gradcheck(f, &inputs, 1e-6, 1e-7, 1e-5), Ok(()));
+    }
+}
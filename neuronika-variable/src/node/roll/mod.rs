@@ -0,0 +1,141 @@
+use ndarray::{Array, Axis, Dimension, Slice};
+
+use crate::{
+    autograd::{Backward, Forward, Reads},
+    cell::Rc,
+    gradient::Gradient,
+    graph::Buffer,
+    utils::Shared,
+    Float,
+};
+
+/// Returns the non-negative shift equivalent to `shift` along an axis of length `len`.
+fn wrapped_shift(shift: isize, len: usize) -> usize {
+    if len == 0 {
+        return 0;
+    }
+
+    shift.rem_euclid(len as isize) as usize
+}
+
+pub(crate) struct Roll<D, T>
+where
+    D: Dimension,
+    T: Float,
+{
+    operand_data: Shared<Array<T, D>>,
+    data: Shared<Array<T, D>>,
+    shift: usize,
+    axis: Axis,
+}
+
+impl<D, T> Roll<D, T>
+where
+    D: Dimension,
+    T: Float,
+{
+    pub(crate) fn new(
+        operand_data: Shared<Array<T, D>>,
+        data: Shared<Array<T, D>>,
+        shift: isize,
+        axis: usize,
+    ) -> Self {
+        let shift = wrapped_shift(shift, operand_data.borrow().len_of(Axis(axis)));
+
+        Self {
+            operand_data,
+            data,
+            shift,
+            axis: Axis(axis),
+        }
+    }
+}
+
+impl<D, T> Forward for Roll<D, T>
+where
+    D: Dimension,
+    T: Float,
+{
+    fn forward(&self) {
+        let mut data = self.data.borrow_mut();
+        let operand_data = self.operand_data.borrow();
+        let (axis, shift) = (self.axis, self.shift);
+        let len = data.len_of(axis);
+
+        // The element in position i moves to position i + shift, wrapping around.
+        data.slice_axis_mut(axis, Slice::from(shift..))
+            .assign(&operand_data.slice_axis(axis, Slice::from(..len - shift)));
+        data.slice_axis_mut(axis, Slice::from(..shift))
+            .assign(&operand_data.slice_axis(axis, Slice::from(len - shift..)));
+    }
+
+    fn name(&self) -> &'static str {
+        "Roll"
+    }
+
+    fn operands(&self) -> Vec<Buffer> {
+        vec![Buffer::new(&self.operand_data)]
+    }
+
+    fn data(&self) -> Buffer {
+        Buffer::new(&self.data)
+    }
+}
+
+pub(crate) struct RollBackward<D, T>
+where
+    D: Dimension,
+    T: Float,
+{
+    operand_gradient: Rc<Gradient<Array<T, D>, D>>,
+    gradient: Rc<Gradient<Array<T, D>, D>>,
+    shift: usize,
+    axis: Axis,
+}
+
+impl<D, T> RollBackward<D, T>
+where
+    D: Dimension,
+    T: Float,
+{
+    pub(crate) fn new(
+        operand_gradient: Rc<Gradient<Array<T, D>, D>>,
+        gradient: Rc<Gradient<Array<T, D>, D>>,
+        shift: isize,
+        axis: usize,
+    ) -> Self {
+        let shift = wrapped_shift(shift, operand_gradient.shape()[axis]);
+
+        Self {
+            operand_gradient,
+            gradient,
+            shift,
+            axis: Axis(axis),
+        }
+    }
+}
+
+impl<D, T> Backward for RollBackward<D, T>
+where
+    D: Dimension,
+    T: Float,
+{
+    fn backward(&self) {
+        let mut operand_gradient = self.operand_gradient.borrow_mut();
+        let gradient = self.gradient.borrow();
+        let (axis, shift) = (self.axis, self.shift);
+        let len = gradient.len_of(axis);
+
+        let mut head = operand_gradient.slice_axis_mut(axis, Slice::from(..len - shift));
+        head += &gradient.slice_axis(axis, Slice::from(shift..));
+        let mut tail = operand_gradient.slice_axis_mut(axis, Slice::from(len - shift..));
+        tail += &gradient.slice_axis(axis, Slice::from(..shift));
+    }
+
+    fn reads(&self) -> Reads {
+        Reads::Nothing
+    }
+}
+
+#[cfg(test)]
+mod test;
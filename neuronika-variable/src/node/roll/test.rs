@@ -0,0 +1,88 @@
+use std::error::Error;
+
+use ndarray::array;
+
+use crate::cell::Rc;
+use crate::utils::are_similar;
+
+mod forward {
+    use ndarray::array;
+
+    #[test]
+    fn roll() {
+        let x = crate::from_ndarray(array![[[1., 2., 3.], [4., 5., 6.]]]);
+        let y = x.clone().roll(-1, 2);
+        y.forward();
+        assert_eq!(*y.data(), array![[[2., 3., 1.], [5., 6., 4.]]]);
+
+        // Shifts by a multiple of the length leave the variable untouched.
+        for shift in [-3, 0, 3, 6] {
+            let y = x.clone().roll(shift, 2);
+            y.forward();
+            assert_eq!(*y.data(), *x.data());
+        }
+    }
+}
+
+mod backward {
+    use ndarray::Ix3;
+
+    use super::super::{Backward, RollBackward};
+    use super::*;
+    use crate::gradient::Gradient;
+
+    #[test]
+    fn base_case() -> Result<(), Box<dyn Error>> {
+        let op = RollBackward::<Ix3, f32>::new(
+            Rc::new(Gradient::ndarray_zeros((1, 2, 3))),
+            Rc::new(Gradient::from_ndarray(array![[[1., 2., 3.], [4., 5., 6.]]])),
+            -1,
+            2,
+        );
+
+        op.backward();
+        are_similar(
+            op.operand_gradient.borrow(),
+            &array![[[3., 1., 2.], [6., 4., 5.]]],
+        )?;
+
+        op.backward();
+        are_similar(
+            op.operand_gradient.borrow(),
+            &array![[[6., 2., 4.], [12., 8., 10.]]],
+        )
+    }
+
+    #[test]
+    fn wrapped_shift() -> Result<(), Box<dyn Error>> {
+        // A shift of 4 along an axis of length 3 is a shift of 1.
+        let op = RollBackward::<Ix3, f32>::new(
+            Rc::new(Gradient::ndarray_zeros((1, 2, 3))),
+            Rc::new(Gradient::from_ndarray(array![[[1., 2., 3.], [4., 5., 6.]]])),
+            4,
+            2,
+        );
+
+        op.backward();
+        are_similar(
+            op.operand_gradient.borrow(),
+            &array![[[2., 3., 1.], [5., 6., 4.]]],
+        )
+    }
+
+    #[test]
+    fn no_shift() -> Result<(), Box<dyn Error>> {
+        let op = RollBackward::<Ix3, f32>::new(
+            Rc::new(Gradient::ndarray_zeros((1, 2, 3))),
+            Rc::new(Gradient::from_ndarray(array![[[1., 2., 3.], [4., 5., 6.]]])),
+            0,
+            1,
+        );
+
+        op.backward();
+        are_similar(
+            op.operand_gradient.borrow(),
+            &array![[[1., 2., 3.], [4., 5., 6.]]],
+        )
+    }
+}
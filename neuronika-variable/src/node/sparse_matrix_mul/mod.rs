@@ -0,0 +1,120 @@
+use ndarray::{Array2, Axis, Ix2};
+
+use crate::{
+    autograd::{Backward, Forward, Reads},
+    cell::Rc,
+    gradient::Gradient,
+    graph::Buffer,
+    sparse::Csr,
+    utils::Shared,
+    Float,
+};
+
+pub(crate) struct SparseMatrixMul<T>
+where
+    T: Float,
+{
+    left: Rc<Csr<T>>,
+    right_data: Shared<Array2<T>>,
+    data: Shared<Array2<T>>,
+}
+
+impl<T> SparseMatrixMul<T>
+where
+    T: Float,
+{
+    pub(crate) fn new(
+        left: Rc<Csr<T>>,
+        right_data: Shared<Array2<T>>,
+        data: Shared<Array2<T>>,
+    ) -> Self {
+        Self {
+            left,
+            right_data,
+            data,
+        }
+    }
+}
+
+impl<T> Forward for SparseMatrixMul<T>
+where
+    T: Float,
+{
+    fn forward(&self) {
+        let right_data = self.right_data.borrow();
+        let mut data = self.data.borrow_mut();
+
+        data.axis_iter_mut(Axis(0)).zip(self.left.rows()).for_each(
+            |(mut row, (columns, values))| {
+                row.fill(T::zero());
+                columns.iter().zip(values).for_each(|(&column, &value)| {
+                    row.scaled_add(value, &right_data.row(column));
+                });
+            },
+        );
+    }
+
+    fn name(&self) -> &'static str {
+        "SparseMatrixMul"
+    }
+
+    fn operands(&self) -> Vec<Buffer> {
+        vec![Buffer::new(&self.right_data)]
+    }
+
+    fn data(&self) -> Buffer {
+        Buffer::new(&self.data)
+    }
+}
+
+pub(crate) struct SparseMatrixMulBackward<T>
+where
+    T: Float,
+{
+    left: Rc<Csr<T>>,
+    right_gradient: Rc<Gradient<Array2<T>, Ix2>>,
+    gradient: Rc<Gradient<Array2<T>, Ix2>>,
+}
+
+impl<T> SparseMatrixMulBackward<T>
+where
+    T: Float,
+{
+    pub(crate) fn new(
+        left: Rc<Csr<T>>,
+        right_gradient: Rc<Gradient<Array2<T>, Ix2>>,
+        gradient: Rc<Gradient<Array2<T>, Ix2>>,
+    ) -> Self {
+        Self {
+            left,
+            right_gradient,
+            gradient,
+        }
+    }
+}
+
+impl<T> Backward for SparseMatrixMulBackward<T>
+where
+    T: Float,
+{
+    fn backward(&self) {
+        let mut right_gradient = self.right_gradient.borrow_mut();
+        let gradient = self.gradient.borrow();
+
+        gradient
+            .outer_iter()
+            .zip(self.left.rows())
+            .for_each(|(row, (columns, values))| {
+                columns.iter().zip(values).for_each(|(&column, &value)| {
+                    right_gradient.row_mut(column).scaled_add(value, &row);
+                });
+            });
+    }
+
+    fn reads(&self) -> Reads {
+        Reads::Nothing
+    }
+}
+
+#[cfg(test)]
+mod test;
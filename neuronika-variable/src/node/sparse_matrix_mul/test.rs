@@ -0,0 +1,121 @@
+use std::error::Error;
+
+use ndarray::{array, Array2};
+
+use crate::cell::Rc;
+use crate::utils::are_similar;
+use crate::SparseVar;
+
+fn dense() -> Array2<f32> {
+    array![[0., 2., 0., 1.], [0., 0., 0., 0.], [3., 0., -1., 0.]]
+}
+
+fn coo() -> SparseVar {
+    SparseVar::from_coo(
+        (3, 4),
+        vec![2, 0, 2, 0],
+        vec![2, 1, 0, 3],
+        vec![-1., 2., 3., 1.],
+    )
+}
+
+fn csr() -> SparseVar {
+    SparseVar::from_csr(
+        (3, 4),
+        vec![0, 2, 2, 4],
+        vec![1, 3, 0, 2],
+        vec![2., 1., 3., -1.],
+    )
+}
+
+mod forward {
+    use super::*;
+    use crate::{MatMatMul, SparseLayout};
+    use ndarray::Array;
+
+    #[test]
+    fn layouts() {
+        let (dense, coo, csr) = (dense(), coo(), csr());
+
+        assert_eq!(coo.layout(), SparseLayout::Coo);
+        assert_eq!(csr.layout(), SparseLayout::Csr);
+        assert_eq!(coo.nnz(), 4);
+        assert_eq!(*coo.to_dense().data(), dense);
+        assert_eq!(*csr.to_dense().data(), dense);
+        assert_eq!(*coo.to_csr().to_dense().data(), dense);
+        assert_eq!(*csr.to_coo().to_dense().data(), dense);
+        assert_eq!(
+            *SparseVar::from_dense(dense.view()).to_dense().data(),
+            dense
+        );
+        assert_eq!(SparseVar::from_dense(dense.view()).nnz(), 4);
+        assert_eq!(*coo.row_sum().data(), array![3., 0., 2.]);
+        assert_eq!(*coo.scale(2.).to_dense().data(), &dense * 2.);
+        assert_eq!(
+            *csr.scale_rows(array![1., 5., -2.].view()).to_dense().data(),
+            &dense * &array![[1.], [5.], [-2.]]
+        );
+
+        // Duplicates are summed.
+        let duplicates = SparseVar::from_coo((1, 2), vec![0, 0], vec![1, 1], vec![1., 2.]);
+        assert_eq!(*duplicates.to_dense().data(), array![[0., 3.]]);
+    }
+
+    #[test]
+    fn matrix_mul() {
+        let rhs = Array::from_shape_fn((4, 2), |(i, j)| (i + 3 * j) as f32 / 4. - 0.5);
+
+        for sparse in [coo(), csr()] {
+            let y = sparse.mm(crate::from_ndarray(rhs.clone()));
+            y.forward();
+            assert!(y.data().abs_diff_eq(&dense().dot(&rhs), 1e-6));
+        }
+    }
+}
+
+mod backward {
+    use ndarray::Array;
+
+    use super::super::{Backward, SparseMatrixMulBackward};
+    use super::*;
+    use crate::{gradient::Gradient, sparse::Csr};
+
+    #[test]
+    fn base_case() -> Result<(), Box<dyn Error>> {
+        let left = Csr {
+            offsets: vec![0, 2, 2, 4],
+            columns: vec![1, 3, 0, 2],
+            values: vec![2., 1., 3., -1.],
+        };
+        let gradient = array![[1., 2.], [3., 4.], [5., 6.]];
+        let op = SparseMatrixMulBackward::new(
+            Rc::new(left),
+            Rc::new(Gradient::ndarray_zeros((4, 2))),
+            Rc::new(Gradient::from_ndarray(gradient.clone())),
+        );
+
+        op.backward();
+        let expected = array![[15., 18.], [2., 4.], [-5., -6.], [1., 2.]];
+        are_similar(op.right_gradient.borrow(), &expected)?;
+        are_similar(op.right_gradient.borrow(), &dense().t().dot(&gradient))?;
+
+        op.backward();
+        are_similar(op.right_gradient.borrow(), &(expected * 2.))
+    }
+
+    #[test]
+    fn empty_rows() -> Result<(), Box<dyn Error>> {
+        let op = SparseMatrixMulBackward::new(
+            Rc::new(Csr {
+                offsets: vec![0, 0, 0],
+                columns: Vec::new(),
+                values: Vec::new(),
+            }),
+            Rc::new(Gradient::ndarray_zeros((3, 2))),
+            Rc::new(Gradient::from_ndarray(Array::ones((2, 2)))),
+        );
+
+        op.backward();
+        are_similar(op.right_gradient.borrow(), &Array::zeros((3, 2)))
+    }
+}
@@ -1,33 +1,35 @@
-use std::rc::Rc;
-
 use ndarray::{arr0, Array, Dimension, Ix0, Zip};
 
 use crate::{
-    autograd::{Backward, Forward},
+    autograd::{Backward, Forward, Reads},
+    cell::Rc,
     gradient::Gradient,
+    graph::Buffer,
     utils::Shared,
-    Reduction,
+    Float, Reduction,
 };
 
 #[allow(clippy::upper_case_acronyms)]
-pub(crate) struct SquaredError<D>
+pub(crate) struct SquaredError<D, T>
 where
     D: Dimension,
+    T: Float,
 {
-    input_data: Shared<Array<f32, D>>,
-    target_data: Shared<Array<f32, D>>,
-    data: Shared<Array<f32, Ix0>>,
+    input_data: Shared<Array<T, D>>,
+    target_data: Shared<Array<T, D>>,
+    data: Shared<Array<T, Ix0>>,
     reduction: Reduction,
 }
 
-impl<D> SquaredError<D>
+impl<D, T> SquaredError<D, T>
 where
     D: Dimension,
+    T: Float,
 {
     pub(crate) fn new(
-        input_data: Shared<Array<f32, D>>,
-        target_data: Shared<Array<f32, D>>,
-        data: Shared<Array<f32, Ix0>>,
+        input_data: Shared<Array<T, D>>,
+        target_data: Shared<Array<T, D>>,
+        data: Shared<Array<T, Ix0>>,
         reduction: Reduction,
     ) -> Self {
         Self {
@@ -39,46 +41,66 @@ where
     }
 }
 
-impl<D> Forward for SquaredError<D>
+impl<D, T> Forward for SquaredError<D, T>
 where
     D: Dimension,
+    T: Float,
 {
     fn forward(&self) {
         let (input_data, target_data) = (self.input_data.borrow(), self.target_data.borrow());
         *self.data.borrow_mut() = {
             let total_loss = Zip::from(&*input_data)
                 .and(&*target_data)
-                .fold(0., |loss, &input, &target| loss + (input - target).powi(2));
+                .fold(T::zero(), |loss, &input, &target| {
+                    loss + (input - target).powi(2)
+                });
 
             match self.reduction {
-                Reduction::Mean => arr0(total_loss / input_data.len() as f32),
+                Reduction::Mean => arr0(total_loss / T::from_usize(input_data.len())),
                 Reduction::Sum => arr0(total_loss),
             }
         };
     }
+
+    fn name(&self) -> &'static str {
+        "SquaredError"
+    }
+
+    fn operands(&self) -> Vec<Buffer> {
+        vec![
+            Buffer::new(&self.input_data),
+            Buffer::new(&self.target_data),
+        ]
+    }
+
+    fn data(&self) -> Buffer {
+        Buffer::new(&self.data)
+    }
 }
 
 #[allow(clippy::upper_case_acronyms)]
-pub(crate) struct SquaredErrorBackward<D>
+pub(crate) struct SquaredErrorBackward<D, T>
 where
     D: Dimension,
+    T: Float,
 {
-    input_data: Shared<Array<f32, D>>,
-    target_data: Shared<Array<f32, D>>,
-    input_gradient: Rc<Gradient<Array<f32, D>, D>>,
-    gradient: Rc<Gradient<Array<f32, Ix0>, Ix0>>,
+    input_data: Shared<Array<T, D>>,
+    target_data: Shared<Array<T, D>>,
+    input_gradient: Rc<Gradient<Array<T, D>, D>>,
+    gradient: Rc<Gradient<Array<T, Ix0>, Ix0>>,
     reduction: Reduction,
 }
 
-impl<D> SquaredErrorBackward<D>
+impl<D, T> SquaredErrorBackward<D, T>
 where
     D: Dimension,
+    T: Float,
 {
     pub(crate) fn new(
-        input_data: Shared<Array<f32, D>>,
-        target_data: Shared<Array<f32, D>>,
-        input_gradient: Rc<Gradient<Array<f32, D>, D>>,
-        gradient: Rc<Gradient<Array<f32, Ix0>, Ix0>>,
+        input_data: Shared<Array<T, D>>,
+        target_data: Shared<Array<T, D>>,
+        input_gradient: Rc<Gradient<Array<T, D>, D>>,
+        gradient: Rc<Gradient<Array<T, Ix0>, Ix0>>,
         reduction: Reduction,
     ) -> Self {
         Self {
@@ -91,9 +113,10 @@ where
     }
 }
 
-impl<D> Backward for SquaredErrorBackward<D>
+impl<D, T> Backward for SquaredErrorBackward<D, T>
 where
     D: Dimension,
+    T: Float,
 {
     fn backward(&self) {
         let mut input_gradient = self.input_gradient.borrow_mut();
@@ -108,18 +131,22 @@ where
 
         match self.reduction {
             Reduction::Mean => {
-                let n = input_data.len() as f32;
+                let n = T::from_usize(input_data.len());
                 zip.for_each(|op_grad, &grad, &input, &target| {
-                    *op_grad += (2. * (input - target)) * grad / n
+                    *op_grad += (T::from_f64(2.) * (input - target)) * grad / n
                 });
             }
             Reduction::Sum => {
                 zip.for_each(|op_grad, &grad, &input, &target| {
-                    *op_grad += (2. * (input - target)) * grad
+                    *op_grad += (T::from_f64(2.) * (input - target)) * grad
                 });
             }
         }
     }
+
+    fn reads(&self) -> Reads {
+        Reads::Operands
+    }
 }
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
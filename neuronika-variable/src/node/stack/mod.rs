@@ -1,31 +1,34 @@
-use std::rc::Rc;
-
 use ndarray::{Array, Axis, Dimension, RemoveAxis, Zip};
 
 use crate::{
-    autograd::{Backward, Forward},
+    autograd::{Backward, Forward, Reads},
+    cell::Rc,
     gradient::Gradient,
+    graph::Buffer,
     utils::Shared,
+    Float,
 };
 
-pub(crate) struct Stack<D>
+pub(crate) struct Stack<D, T>
 where
     D: Dimension + RemoveAxis,
+    T: Float,
 {
-    left: Shared<Array<f32, D>>,
-    right: Shared<Array<f32, D>>,
-    data: Shared<Array<f32, D::Larger>>,
+    left: Shared<Array<T, D>>,
+    right: Shared<Array<T, D>>,
+    data: Shared<Array<T, D::Larger>>,
     axis: Axis,
 }
 
-impl<D> Stack<D>
+impl<D, T> Stack<D, T>
 where
     D: Dimension + RemoveAxis,
+    T: Float,
 {
     pub(crate) fn new(
-        left: Shared<Array<f32, D>>,
-        right: Shared<Array<f32, D>>,
-        data: Shared<Array<f32, D::Larger>>,
+        left: Shared<Array<T, D>>,
+        right: Shared<Array<T, D>>,
+        data: Shared<Array<T, D::Larger>>,
         axis: usize,
     ) -> Self {
         Self {
@@ -37,9 +40,10 @@ where
     }
 }
 
-impl<D> Forward for Stack<D>
+impl<D, T> Forward for Stack<D, T>
 where
     D: Dimension + RemoveAxis,
+    T: Float,
 {
     fn forward(&self) {
         let lhs_data = self.left.borrow();
@@ -68,24 +72,38 @@ where
             .and(&*rhs_data)
             .for_each(|fused_el, &single_el| *fused_el = single_el);
     }
+
+    fn name(&self) -> &'static str {
+        "Stack"
+    }
+
+    fn operands(&self) -> Vec<Buffer> {
+        vec![Buffer::new(&self.left), Buffer::new(&self.right)]
+    }
+
+    fn data(&self) -> Buffer {
+        Buffer::new(&self.data)
+    }
 }
 
-pub(crate) struct StackBackwardLeft<D>
+pub(crate) struct StackBackwardLeft<D, T>
 where
     D: Dimension + RemoveAxis,
+    T: Float,
 {
-    operand_gradient: Rc<Gradient<Array<f32, D>, D>>,
-    gradient: Rc<Gradient<Array<f32, D::Larger>, D::Larger>>,
+    operand_gradient: Rc<Gradient<Array<T, D>, D>>,
+    gradient: Rc<Gradient<Array<T, D::Larger>, D::Larger>>,
     axis: Axis,
 }
 
-impl<D> StackBackwardLeft<D>
+impl<D, T> StackBackwardLeft<D, T>
 where
     D: Dimension + RemoveAxis,
+    T: Float,
 {
     pub(crate) fn new(
-        operand_gradient: Rc<Gradient<Array<f32, D>, D>>,
-        gradient: Rc<Gradient<Array<f32, D::Larger>, D::Larger>>,
+        operand_gradient: Rc<Gradient<Array<T, D>, D>>,
+        gradient: Rc<Gradient<Array<T, D::Larger>, D::Larger>>,
         axis: usize,
     ) -> Self {
         Self {
@@ -96,9 +114,10 @@ where
     }
 }
 
-impl<D> Backward for StackBackwardLeft<D>
+impl<D, T> Backward for StackBackwardLeft<D, T>
 where
     D: Dimension + RemoveAxis,
+    T: Float,
 {
     fn backward(&self) {
         let gradient = self.gradient.borrow();
@@ -111,24 +130,30 @@ where
 
         *self.operand_gradient.borrow_mut() += &operand_gradient_slice;
     }
+
+    fn reads(&self) -> Reads {
+        Reads::Nothing
+    }
 }
 
-pub(crate) struct StackBackwardRight<D>
+pub(crate) struct StackBackwardRight<D, T>
 where
     D: Dimension + RemoveAxis,
+    T: Float,
 {
-    operand_gradient: Rc<Gradient<Array<f32, D>, D>>,
-    gradient: Rc<Gradient<Array<f32, D::Larger>, D::Larger>>,
+    operand_gradient: Rc<Gradient<Array<T, D>, D>>,
+    gradient: Rc<Gradient<Array<T, D::Larger>, D::Larger>>,
     axis: Axis,
 }
 
-impl<D> StackBackwardRight<D>
+impl<D, T> StackBackwardRight<D, T>
 where
     D: Dimension + RemoveAxis,
+    T: Float,
 {
     pub(crate) fn new(
-        operand_gradient: Rc<Gradient<Array<f32, D>, D>>,
-        gradient: Rc<Gradient<Array<f32, D::Larger>, D::Larger>>,
+        operand_gradient: Rc<Gradient<Array<T, D>, D>>,
+        gradient: Rc<Gradient<Array<T, D::Larger>, D::Larger>>,
         axis: usize,
     ) -> Self {
         Self {
@@ -139,9 +164,10 @@ where
     }
 }
 
-impl<D> Backward for StackBackwardRight<D>
+impl<D, T> Backward for StackBackwardRight<D, T>
 where
     D: Dimension + RemoveAxis,
+    T: Float,
 {
     fn backward(&self) {
         let gradient = self.gradient.borrow();
@@ -154,33 +180,44 @@ where
 
         *self.operand_gradient.borrow_mut() += &operand_gradient_slice;
     }
+
+    fn reads(&self) -> Reads {
+        Reads::Nothing
+    }
 }
 
-pub(crate) struct StackBackward<D>
+pub(crate) struct StackBackward<D, T>
 where
     D: Dimension + RemoveAxis,
+    T: Float,
 {
-    left: StackBackwardLeft<D>,
-    right: StackBackwardRight<D>,
+    left: StackBackwardLeft<D, T>,
+    right: StackBackwardRight<D, T>,
 }
 
-impl<D> StackBackward<D>
+impl<D, T> StackBackward<D, T>
 where
     D: Dimension + RemoveAxis,
+    T: Float,
 {
-    pub(crate) fn new(left: StackBackwardLeft<D>, right: StackBackwardRight<D>) -> Self {
+    pub(crate) fn new(left: StackBackwardLeft<D, T>, right: StackBackwardRight<D, T>) -> Self {
         Self { left, right }
     }
 }
 
-impl<D> Backward for StackBackward<D>
+impl<D, T> Backward for StackBackward<D, T>
 where
     D: Dimension + RemoveAxis,
+    T: Float,
 {
     fn backward(&self) {
         self.left.backward();
         self.right.backward();
     }
+
+    fn reads(&self) -> Reads {
+        Reads::Nothing
+    }
 }
 
 // // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
@@ -1,32 +1,41 @@
-use std::rc::Rc;
+use std::any::Any;
 
 use ndarray::{Array, DimMax, Dimension, Zip};
 
 use crate::{
-    autograd::{Backward, Forward},
+    autograd::{Backward, Forward, Reads},
+    cell::Rc,
+    fusion::{
+        fusible_len, standard_len, BackwardKernel, ElementwiseBackward, ElementwiseForward,
+        ForwardKernel,
+    },
     gradient::Gradient,
+    graph::Buffer,
     utils::{accumulate, Broadcast, Shared},
+    Float,
 };
 
-pub(crate) struct Subtraction<D, E>
+pub(crate) struct Subtraction<D, E, T>
 where
     D: Dimension + DimMax<E>,
     E: Dimension,
+    T: Float,
 {
-    left_data: Shared<Array<f32, D>>,
-    right_data: Shared<Array<f32, E>>,
-    data: Shared<Array<f32, Broadcast<D, E>>>,
+    left_data: Shared<Array<T, D>>,
+    right_data: Shared<Array<T, E>>,
+    data: Shared<Array<T, Broadcast<D, E>>>,
 }
 
-impl<D, E> Subtraction<D, E>
+impl<D, E, T> Subtraction<D, E, T>
 where
     D: Dimension + DimMax<E>,
     E: Dimension,
+    T: Float,
 {
     pub(crate) fn new(
-        left_data: Shared<Array<f32, D>>,
-        right_data: Shared<Array<f32, E>>,
-        data: Shared<Array<f32, Broadcast<D, E>>>,
+        left_data: Shared<Array<T, D>>,
+        right_data: Shared<Array<T, E>>,
+        data: Shared<Array<T, Broadcast<D, E>>>,
     ) -> Self {
         Self {
             left_data,
@@ -36,10 +45,11 @@ where
     }
 }
 
-impl<D, E> Forward for Subtraction<D, E>
+impl<D, E, T> Forward for Subtraction<D, E, T>
 where
-    D: Dimension + DimMax<E>,
-    E: Dimension,
+    D: 'static + Dimension + DimMax<E>,
+    E: 'static + Dimension,
+    T: Float,
 {
     fn forward(&self) {
         Zip::from(&mut *self.data.borrow_mut())
@@ -47,25 +57,70 @@ where
             .and_broadcast(&*self.right_data.borrow())
             .for_each(|v, &l, &r| *v = l - r);
     }
+
+    fn name(&self) -> &'static str {
+        "Subtraction"
+    }
+
+    fn operands(&self) -> Vec<Buffer> {
+        vec![Buffer::new(&self.left_data), Buffer::new(&self.right_data)]
+    }
+
+    fn data(&self) -> Buffer {
+        Buffer::new(&self.data)
+    }
+
+    fn elementwise(&self) -> Option<&dyn ElementwiseForward> {
+        Some(self)
+    }
 }
 
-pub(crate) struct SubtractionBackwardLeft<D, E>
+impl<D, E, T> ElementwiseForward for Subtraction<D, E, T>
+where
+    D: 'static + Dimension + DimMax<E>,
+    E: 'static + Dimension,
+    T: Float,
+{
+    fn fusible_len(&self) -> Option<usize> {
+        fusible_len(
+            standard_len(&self.data.borrow()),
+            &[],
+            &[
+                standard_len(&self.left_data.borrow()),
+                standard_len(&self.right_data.borrow()),
+            ],
+        )
+    }
+
+    fn kernel(&self) -> Box<dyn Any> {
+        Box::new(ForwardKernel::binary(
+            &self.left_data,
+            &self.right_data,
+            &self.data,
+            |l, r| l - r,
+        ))
+    }
+}
+
+pub(crate) struct SubtractionBackwardLeft<D, E, T>
 where
     D: Dimension + DimMax<E>,
     E: Dimension,
+    T: Float,
 {
-    operand_gradient: Rc<Gradient<Array<f32, D>, D>>,
-    gradient: Rc<Gradient<Array<f32, Broadcast<D, E>>, Broadcast<D, E>>>,
+    operand_gradient: Rc<Gradient<Array<T, D>, D>>,
+    gradient: Rc<Gradient<Array<T, Broadcast<D, E>>, Broadcast<D, E>>>,
 }
 
-impl<D, E> SubtractionBackwardLeft<D, E>
+impl<D, E, T> SubtractionBackwardLeft<D, E, T>
 where
     D: Dimension + DimMax<E>,
     E: Dimension,
+    T: Float,
 {
     pub(crate) fn new(
-        operand_gradient: Rc<Gradient<Array<f32, D>, D>>,
-        gradient: Rc<Gradient<Array<f32, Broadcast<D, E>>, Broadcast<D, E>>>,
+        operand_gradient: Rc<Gradient<Array<T, D>, D>>,
+        gradient: Rc<Gradient<Array<T, Broadcast<D, E>>, Broadcast<D, E>>>,
     ) -> Self {
         debug_assert!(operand_gradient
             .borrow()
@@ -79,10 +134,11 @@ where
     }
 }
 
-impl<D, E> Backward for SubtractionBackwardLeft<D, E>
+impl<D, E, T> Backward for SubtractionBackwardLeft<D, E, T>
 where
-    D: Dimension + DimMax<E>,
-    E: Dimension,
+    D: 'static + Dimension + DimMax<E>,
+    E: 'static + Dimension,
+    T: Float,
 {
     fn backward(&self) {
         accumulate(
@@ -90,25 +146,57 @@ where
             &self.gradient.borrow(),
         );
     }
+
+    fn elementwise(&self) -> Option<&dyn ElementwiseBackward> {
+        Some(self)
+    }
+
+    fn reads(&self) -> Reads {
+        Reads::Nothing
+    }
+}
+
+impl<D, E, T> ElementwiseBackward for SubtractionBackwardLeft<D, E, T>
+where
+    D: 'static + Dimension + DimMax<E>,
+    E: 'static + Dimension,
+    T: Float,
+{
+    fn fusible_len(&self) -> Option<usize> {
+        fusible_len(
+            standard_len(&self.gradient.borrow()),
+            &[standard_len(&self.operand_gradient.borrow())],
+            &[],
+        )
+    }
+
+    fn kernel(&self) -> Box<dyn Any> {
+        Box::new(
+            BackwardKernel::new(self.gradient.clone())
+                .accumulate(self.operand_gradient.clone(), |g| g),
+        )
+    }
 }
 
-pub(crate) struct SubtractionBackwardRight<D, E>
+pub(crate) struct SubtractionBackwardRight<D, E, T>
 where
     D: Dimension + DimMax<E>,
     E: Dimension,
+    T: Float,
 {
-    operand_gradient: Rc<Gradient<Array<f32, E>, E>>,
-    gradient: Rc<Gradient<Array<f32, Broadcast<D, E>>, Broadcast<D, E>>>,
+    operand_gradient: Rc<Gradient<Array<T, E>, E>>,
+    gradient: Rc<Gradient<Array<T, Broadcast<D, E>>, Broadcast<D, E>>>,
 }
 
-impl<D, E> SubtractionBackwardRight<D, E>
+impl<D, E, T> SubtractionBackwardRight<D, E, T>
 where
     D: Dimension + DimMax<E>,
     E: Dimension,
+    T: Float,
 {
     pub(crate) fn new(
-        operand_gradient: Rc<Gradient<Array<f32, E>, E>>,
-        gradient: Rc<Gradient<Array<f32, Broadcast<D, E>>, Broadcast<D, E>>>,
+        operand_gradient: Rc<Gradient<Array<T, E>, E>>,
+        gradient: Rc<Gradient<Array<T, Broadcast<D, E>>, Broadcast<D, E>>>,
     ) -> Self {
         debug_assert!(operand_gradient
             .borrow()
@@ -122,50 +210,113 @@ where
     }
 }
 
-impl<D, E> Backward for SubtractionBackwardRight<D, E>
+impl<D, E, T> Backward for SubtractionBackwardRight<D, E, T>
 where
-    D: Dimension + DimMax<E>,
-    E: Dimension,
+    D: 'static + Dimension + DimMax<E>,
+    E: 'static + Dimension,
+    T: Float,
 {
     fn backward(&self) {
         accumulate(
             &mut self.operand_gradient.borrow_mut(),
-            &self.gradient.borrow().map(|g| -g),
+            &self.gradient.borrow().map(|&g| -g),
         );
     }
+
+    fn elementwise(&self) -> Option<&dyn ElementwiseBackward> {
+        Some(self)
+    }
+
+    fn reads(&self) -> Reads {
+        Reads::Nothing
+    }
 }
 
-pub(crate) struct SubtractionBackward<D, E>
+impl<D, E, T> ElementwiseBackward for SubtractionBackwardRight<D, E, T>
+where
+    D: 'static + Dimension + DimMax<E>,
+    E: 'static + Dimension,
+    T: Float,
+{
+    fn fusible_len(&self) -> Option<usize> {
+        fusible_len(
+            standard_len(&self.gradient.borrow()),
+            &[standard_len(&self.operand_gradient.borrow())],
+            &[],
+        )
+    }
+
+    fn kernel(&self) -> Box<dyn Any> {
+        Box::new(
+            BackwardKernel::new(self.gradient.clone())
+                .accumulate(self.operand_gradient.clone(), |g| -g),
+        )
+    }
+}
+
+pub(crate) struct SubtractionBackward<D, E, T>
 where
     D: Dimension + DimMax<E>,
     E: Dimension,
+    T: Float,
 {
-    left: SubtractionBackwardLeft<D, E>,
-    right: SubtractionBackwardRight<D, E>,
+    left: SubtractionBackwardLeft<D, E, T>,
+    right: SubtractionBackwardRight<D, E, T>,
 }
 
-impl<D, E> SubtractionBackward<D, E>
+impl<D, E, T> SubtractionBackward<D, E, T>
 where
     D: Dimension + DimMax<E>,
     E: Dimension,
+    T: Float,
 {
     pub(crate) fn new(
-        left: SubtractionBackwardLeft<D, E>,
-        right: SubtractionBackwardRight<D, E>,
+        left: SubtractionBackwardLeft<D, E, T>,
+        right: SubtractionBackwardRight<D, E, T>,
     ) -> Self {
         Self { left, right }
     }
 }
 
-impl<D, E> Backward for SubtractionBackward<D, E>
+impl<D, E, T> Backward for SubtractionBackward<D, E, T>
 where
-    D: Dimension + DimMax<E>,
-    E: Dimension,
+    D: 'static + Dimension + DimMax<E>,
+    E: 'static + Dimension,
+    T: Float,
 {
     fn backward(&self) {
         self.left.backward();
         self.right.backward();
     }
+
+    fn elementwise(&self) -> Option<&dyn ElementwiseBackward> {
+        Some(self)
+    }
+
+    fn reads(&self) -> Reads {
+        Reads::Nothing
+    }
+}
+
+impl<D, E, T> ElementwiseBackward for SubtractionBackward<D, E, T>
+where
+    D: 'static + Dimension + DimMax<E>,
+    E: 'static + Dimension,
+    T: Float,
+{
+    fn fusible_len(&self) -> Option<usize> {
+        let len = self.left.fusible_len()?;
+
+        if self.right.fusible_len()? == len {
+            Some(len)
+        } else {
+            None
+        }
+    }
+
+    fn kernel(&self) -> Box<dyn Any> {
+        Box::new(BackwardKernel::<T>::of(&self.left).and(BackwardKernel::of(&self.right)))
+    }
 }
 
 #[cfg(test)]
@@ -1,54 +1,72 @@
-use std::rc::Rc;
-
 use ndarray::{arr0, Array, Array0, Dimension, Ix0};
 
 use crate::{
-    autograd::{Backward, Forward},
+    autograd::{Backward, Forward, Reads},
+    cell::Rc,
     gradient::Gradient,
+    graph::Buffer,
     utils::Shared,
+    Float,
 };
 
-pub(crate) struct Sum<D>
+pub(crate) struct Sum<D, T>
 where
     D: Dimension,
+    T: Float,
 {
-    operand_data: Shared<Array<f32, D>>,
-    data: Shared<Array0<f32>>,
+    operand_data: Shared<Array<T, D>>,
+    data: Shared<Array0<T>>,
 }
 
-impl<D> Sum<D>
+impl<D, T> Sum<D, T>
 where
     D: Dimension,
+    T: Float,
 {
-    pub(crate) fn new(operand_data: Shared<Array<f32, D>>, data: Shared<Array0<f32>>) -> Self {
+    pub(crate) fn new(operand_data: Shared<Array<T, D>>, data: Shared<Array0<T>>) -> Self {
         Self { operand_data, data }
     }
 }
 
-impl<D> Forward for Sum<D>
+impl<D, T> Forward for Sum<D, T>
 where
     D: Dimension,
+    T: Float,
 {
     fn forward(&self) {
         *self.data.borrow_mut() = arr0(self.operand_data.borrow().sum());
     }
+
+    fn name(&self) -> &'static str {
+        "Sum"
+    }
+
+    fn operands(&self) -> Vec<Buffer> {
+        vec![Buffer::new(&self.operand_data)]
+    }
+
+    fn data(&self) -> Buffer {
+        Buffer::new(&self.data)
+    }
 }
 
-pub(crate) struct SumBackward<D>
+pub(crate) struct SumBackward<D, T>
 where
     D: Dimension,
+    T: Float,
 {
-    operand_gradient: Rc<Gradient<Array<f32, D>, D>>,
-    gradient: Rc<Gradient<Array<f32, Ix0>, Ix0>>,
+    operand_gradient: Rc<Gradient<Array<T, D>, D>>,
+    gradient: Rc<Gradient<Array<T, Ix0>, Ix0>>,
 }
 
-impl<D> SumBackward<D>
+impl<D, T> SumBackward<D, T>
 where
     D: Dimension,
+    T: Float,
 {
     pub(crate) fn new(
-        operand_gradient: Rc<Gradient<Array<f32, D>, D>>,
-        gradient: Rc<Gradient<Array<f32, Ix0>, Ix0>>,
+        operand_gradient: Rc<Gradient<Array<T, D>, D>>,
+        gradient: Rc<Gradient<Array<T, Ix0>, Ix0>>,
     ) -> Self {
         Self {
             operand_gradient,
@@ -57,13 +75,18 @@ where
     }
 }
 
-impl<D> Backward for SumBackward<D>
+impl<D, T> Backward for SumBackward<D, T>
 where
     D: Dimension,
+    T: Float,
 {
     fn backward(&self) {
         *self.operand_gradient.borrow_mut() += self.gradient.borrow()[()];
     }
+
+    fn reads(&self) -> Reads {
+        Reads::Nothing
+    }
 }
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
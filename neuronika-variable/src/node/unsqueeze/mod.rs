@@ -1,36 +1,40 @@
-use std::rc::Rc;
-
 use ndarray::{Array, Dimension};
 
 use crate::{
-    autograd::{Backward, Forward},
+    autograd::{Backward, Forward, Reads},
+    cell::Rc,
     gradient::Gradient,
+    graph::Buffer,
     utils::Shared,
+    Float,
 };
 
-pub(crate) struct Unsqueeze<D>
+pub(crate) struct Unsqueeze<D, T>
 where
     D: Dimension,
+    T: Float,
 {
-    operand_data: Shared<Array<f32, D>>,
-    data: Shared<Array<f32, D::Larger>>,
+    operand_data: Shared<Array<T, D>>,
+    data: Shared<Array<T, D::Larger>>,
 }
 
-impl<D> Unsqueeze<D>
+impl<D, T> Unsqueeze<D, T>
 where
     D: Dimension,
+    T: Float,
 {
     pub(crate) fn new(
-        operand_data: Shared<Array<f32, D>>,
-        data: Shared<Array<f32, D::Larger>>,
+        operand_data: Shared<Array<T, D>>,
+        data: Shared<Array<T, D::Larger>>,
     ) -> Self {
         Self { operand_data, data }
     }
 }
 
-impl<D> Forward for Unsqueeze<D>
+impl<D, T> Forward for Unsqueeze<D, T>
 where
     D: Dimension,
+    T: Float,
 {
     fn forward(&self) {
         let mut data = self.data.borrow_mut();
@@ -38,23 +42,37 @@ where
         let mut unsqueezed = data.view_mut().into_shape(operand_data.raw_dim()).unwrap();
         unsqueezed.assign(&operand_data);
     }
+
+    fn name(&self) -> &'static str {
+        "Unsqueeze"
+    }
+
+    fn operands(&self) -> Vec<Buffer> {
+        vec![Buffer::new(&self.operand_data)]
+    }
+
+    fn data(&self) -> Buffer {
+        Buffer::new(&self.data)
+    }
 }
 
-pub(crate) struct UnsqueezeBackward<D>
+pub(crate) struct UnsqueezeBackward<D, T>
 where
     D: Dimension,
+    T: Float,
 {
-    operand_gradient: Rc<Gradient<Array<f32, D>, D>>,
-    gradient: Rc<Gradient<Array<f32, D::Larger>, D::Larger>>,
+    operand_gradient: Rc<Gradient<Array<T, D>, D>>,
+    gradient: Rc<Gradient<Array<T, D::Larger>, D::Larger>>,
 }
 
-impl<D> UnsqueezeBackward<D>
+impl<D, T> UnsqueezeBackward<D, T>
 where
     D: Dimension,
+    T: Float,
 {
     pub(crate) fn new(
-        operand_gradient: Rc<Gradient<Array<f32, D>, D>>,
-        gradient: Rc<Gradient<Array<f32, D::Larger>, D::Larger>>,
+        operand_gradient: Rc<Gradient<Array<T, D>, D>>,
+        gradient: Rc<Gradient<Array<T, D::Larger>, D::Larger>>,
     ) -> Self {
         Self {
             operand_gradient,
@@ -63,9 +81,10 @@ where
     }
 }
 
-impl<D> Backward for UnsqueezeBackward<D>
+impl<D, T> Backward for UnsqueezeBackward<D, T>
 where
     D: Dimension,
+    T: Float,
 {
     fn backward(&self) {
         let gradient = self.gradient.borrow();
@@ -76,6 +95,10 @@ where
 
         *self.operand_gradient.borrow_mut() += &view;
     }
+
+    fn reads(&self) -> Reads {
+        Reads::Nothing
+    }
 }
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
@@ -1,24 +1,31 @@
-use std::rc::Rc;
-
 use ndarray::{linalg::general_mat_vec_mul, s, Array1, Array2, Ix1, Ix2, NewAxis, Zip};
 
 use crate::{
-    autograd::{Backward, Forward},
+    autograd::{Backward, Forward, Reads},
+    cell::Rc,
     gradient::Gradient,
+    graph::Buffer,
     utils::Shared,
+    Float,
 };
 
-pub(crate) struct VectorMatrixMul {
-    left_data: Shared<Array1<f32>>,
-    right_data: Shared<Array2<f32>>,
-    data: Shared<Array1<f32>>,
+pub(crate) struct VectorMatrixMul<T>
+where
+    T: Float,
+{
+    left_data: Shared<Array1<T>>,
+    right_data: Shared<Array2<T>>,
+    data: Shared<Array1<T>>,
 }
 
-impl VectorMatrixMul {
+impl<T> VectorMatrixMul<T>
+where
+    T: Float,
+{
     pub(crate) fn new(
-        left_data: Shared<Array1<f32>>,
-        right_data: Shared<Array2<f32>>,
-        data: Shared<Array1<f32>>,
+        left_data: Shared<Array1<T>>,
+        right_data: Shared<Array2<T>>,
+        data: Shared<Array1<T>>,
     ) -> Self {
         Self {
             left_data,
@@ -28,29 +35,50 @@ impl VectorMatrixMul {
     }
 }
 
-impl Forward for VectorMatrixMul {
+impl<T> Forward for VectorMatrixMul<T>
+where
+    T: Float,
+{
     fn forward(&self) {
         general_mat_vec_mul(
-            1.,
+            T::one(),
             &self.right_data.borrow().t(),
             &*self.left_data.borrow(),
-            0.,
+            T::zero(),
             &mut *self.data.borrow_mut(),
         );
     }
+
+    fn name(&self) -> &'static str {
+        "VectorMatrixMul"
+    }
+
+    fn operands(&self) -> Vec<Buffer> {
+        vec![Buffer::new(&self.left_data), Buffer::new(&self.right_data)]
+    }
+
+    fn data(&self) -> Buffer {
+        Buffer::new(&self.data)
+    }
 }
 
-pub(crate) struct VectorMatrixMulBackwardLeft {
-    left_gradient: Rc<Gradient<Array1<f32>, Ix1>>,
-    right_data: Shared<Array2<f32>>,
-    gradient: Rc<Gradient<Array1<f32>, Ix1>>,
+pub(crate) struct VectorMatrixMulBackwardLeft<T>
+where
+    T: Float,
+{
+    left_gradient: Rc<Gradient<Array1<T>, Ix1>>,
+    right_data: Shared<Array2<T>>,
+    gradient: Rc<Gradient<Array1<T>, Ix1>>,
 }
 
-impl VectorMatrixMulBackwardLeft {
+impl<T> VectorMatrixMulBackwardLeft<T>
+where
+    T: Float,
+{
     pub(crate) fn new(
-        left_gradient: Rc<Gradient<Array1<f32>, Ix1>>,
-        right_data: Shared<Array2<f32>>,
-        gradient: Rc<Gradient<Array1<f32>, Ix1>>,
+        left_gradient: Rc<Gradient<Array1<T>, Ix1>>,
+        right_data: Shared<Array2<T>>,
+        gradient: Rc<Gradient<Array1<T>, Ix1>>,
     ) -> Self {
         Self {
             left_gradient,
@@ -60,29 +88,42 @@ impl VectorMatrixMulBackwardLeft {
     }
 }
 
-impl Backward for VectorMatrixMulBackwardLeft {
+impl<T> Backward for VectorMatrixMulBackwardLeft<T>
+where
+    T: Float,
+{
     fn backward(&self) {
         general_mat_vec_mul(
-            1.,
+            T::one(),
             &self.right_data.borrow(),
             &*self.gradient.borrow(),
-            1.,
+            T::one(),
             &mut *self.left_gradient.borrow_mut(),
         );
     }
+
+    fn reads(&self) -> Reads {
+        Reads::Operands
+    }
 }
 
-pub(crate) struct VectorMatrixMulBackwardRight {
-    left_data: Shared<Array1<f32>>,
-    right_gradient: Rc<Gradient<Array2<f32>, Ix2>>,
-    gradient: Rc<Gradient<Array1<f32>, Ix1>>,
+pub(crate) struct VectorMatrixMulBackwardRight<T>
+where
+    T: Float,
+{
+    left_data: Shared<Array1<T>>,
+    right_gradient: Rc<Gradient<Array2<T>, Ix2>>,
+    gradient: Rc<Gradient<Array1<T>, Ix1>>,
 }
 
-impl VectorMatrixMulBackwardRight {
+impl<T> VectorMatrixMulBackwardRight<T>
+where
+    T: Float,
+{
     pub(crate) fn new(
-        left_data: Shared<Array1<f32>>,
-        right_gradient: Rc<Gradient<Array2<f32>, Ix2>>,
-        gradient: Rc<Gradient<Array1<f32>, Ix1>>,
+        left_data: Shared<Array1<T>>,
+        right_gradient: Rc<Gradient<Array2<T>, Ix2>>,
+        gradient: Rc<Gradient<Array1<T>, Ix1>>,
     ) -> Self {
         Self {
             left_data,
@@ -92,34 +133,54 @@ impl VectorMatrixMulBackwardRight {
     }
 }
 
-impl Backward for VectorMatrixMulBackwardRight {
+impl<T> Backward for VectorMatrixMulBackwardRight<T>
+where
+    T: Float,
+{
     fn backward(&self) {
         Zip::from(&mut *self.right_gradient.borrow_mut())
             .and_broadcast(&self.left_data.borrow().slice(s![.., NewAxis]))
             .and_broadcast(&*self.gradient.borrow())
             .for_each(|d, &f, &s| *d += f * s);
     }
+
+    fn reads(&self) -> Reads {
+        Reads::Operands
+    }
 }
 
-pub(crate) struct VectorMatrixMulBackward {
-    left: VectorMatrixMulBackwardLeft,
-    right: VectorMatrixMulBackwardRight,
+pub(crate) struct VectorMatrixMulBackward<T>
+where
+    T: Float,
+{
+    left: VectorMatrixMulBackwardLeft<T>,
+    right: VectorMatrixMulBackwardRight<T>,
 }
 
-impl VectorMatrixMulBackward {
+impl<T> VectorMatrixMulBackward<T>
+where
+    T: Float,
+{
     pub(crate) fn new(
-        left: VectorMatrixMulBackwardLeft,
-        right: VectorMatrixMulBackwardRight,
+        left: VectorMatrixMulBackwardLeft<T>,
+        right: VectorMatrixMulBackwardRight<T>,
     ) -> Self {
         Self { left, right }
     }
 }
 
-impl Backward for VectorMatrixMulBackward {
+impl<T> Backward for VectorMatrixMulBackward<T>
+where
+    T: Float,
+{
     fn backward(&self) {
         self.left.backward();
         self.right.backward();
     }
+
+    fn reads(&self) -> Reads {
+        Reads::Operands
+    }
 }
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
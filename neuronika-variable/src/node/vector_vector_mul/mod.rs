@@ -1,24 +1,31 @@
-use std::rc::Rc;
-
 use ndarray::{arr0, Array, Ix0, Ix1, Zip};
 
 use crate::{
-    autograd::{Backward, Forward},
+    autograd::{Backward, Forward, Reads},
+    cell::Rc,
     gradient::Gradient,
+    graph::Buffer,
     utils::Shared,
+    Float,
 };
 
-pub(crate) struct VectorVectorMul {
-    left_data: Shared<Array<f32, Ix1>>,
-    right_data: Shared<Array<f32, Ix1>>,
-    data: Shared<Array<f32, Ix0>>,
+pub(crate) struct VectorVectorMul<T>
+where
+    T: Float,
+{
+    left_data: Shared<Array<T, Ix1>>,
+    right_data: Shared<Array<T, Ix1>>,
+    data: Shared<Array<T, Ix0>>,
 }
 
-impl VectorVectorMul {
+impl<T> VectorVectorMul<T>
+where
+    T: Float,
+{
     pub(crate) fn new(
-        left_data: Shared<Array<f32, Ix1>>,
-        right_data: Shared<Array<f32, Ix1>>,
-        data: Shared<Array<f32, Ix0>>,
+        left_data: Shared<Array<T, Ix1>>,
+        right_data: Shared<Array<T, Ix1>>,
+        data: Shared<Array<T, Ix0>>,
     ) -> Self {
         Self {
             left_data,
@@ -28,23 +35,44 @@ impl VectorVectorMul {
     }
 }
 
-impl Forward for VectorVectorMul {
+impl<T> Forward for VectorVectorMul<T>
+where
+    T: Float,
+{
     fn forward(&self) {
         *self.data.borrow_mut() = arr0(self.left_data.borrow().dot(&*self.right_data.borrow()));
     }
+
+    fn name(&self) -> &'static str {
+        "VectorVectorMul"
+    }
+
+    fn operands(&self) -> Vec<Buffer> {
+        vec![Buffer::new(&self.left_data), Buffer::new(&self.right_data)]
+    }
+
+    fn data(&self) -> Buffer {
+        Buffer::new(&self.data)
+    }
 }
 
-pub(crate) struct VectorVectorMulBackwardUnary {
-    operand_data: Shared<Array<f32, Ix1>>,
-    operand_gradient: Rc<Gradient<Array<f32, Ix1>, Ix1>>,
-    gradient: Rc<Gradient<Array<f32, Ix0>, Ix0>>,
+pub(crate) struct VectorVectorMulBackwardUnary<T>
+where
+    T: Float,
+{
+    operand_data: Shared<Array<T, Ix1>>,
+    operand_gradient: Rc<Gradient<Array<T, Ix1>, Ix1>>,
+    gradient: Rc<Gradient<Array<T, Ix0>, Ix0>>,
 }
 
-impl VectorVectorMulBackwardUnary {
+impl<T> VectorVectorMulBackwardUnary<T>
+where
+    T: Float,
+{
     pub(crate) fn new(
-        operand_data: Shared<Array<f32, Ix1>>,
-        operand_gradient: Rc<Gradient<Array<f32, Ix1>, Ix1>>,
-        gradient: Rc<Gradient<Array<f32, Ix0>, Ix0>>,
+        operand_data: Shared<Array<T, Ix1>>,
+        operand_gradient: Rc<Gradient<Array<T, Ix1>, Ix1>>,
+        gradient: Rc<Gradient<Array<T, Ix0>, Ix0>>,
     ) -> Self {
         Self {
             operand_gradient,
@@ -54,34 +82,54 @@ impl VectorVectorMulBackwardUnary {
     }
 }
 
-impl Backward for VectorVectorMulBackwardUnary {
+impl<T> Backward for VectorVectorMulBackwardUnary<T>
+where
+    T: Float,
+{
     fn backward(&self) {
         Zip::from(&mut *self.operand_gradient.borrow_mut())
             .and(&*self.operand_data.borrow())
             .and_broadcast(&*self.gradient.borrow())
             .for_each(|op_grad_el, &data_el, &grad_el| *op_grad_el += data_el * grad_el);
     }
+
+    fn reads(&self) -> Reads {
+        Reads::Operands
+    }
 }
 
-pub(crate) struct VectorVectorMulBackward {
-    left: VectorVectorMulBackwardUnary,
-    right: VectorVectorMulBackwardUnary,
+pub(crate) struct VectorVectorMulBackward<T>
+where
+    T: Float,
+{
+    left: VectorVectorMulBackwardUnary<T>,
+    right: VectorVectorMulBackwardUnary<T>,
 }
 
-impl VectorVectorMulBackward {
+impl<T> VectorVectorMulBackward<T>
+where
+    T: Float,
+{
     pub(crate) fn new(
-        left: VectorVectorMulBackwardUnary,
-        right: VectorVectorMulBackwardUnary,
+        left: VectorVectorMulBackwardUnary<T>,
+        right: VectorVectorMulBackwardUnary<T>,
     ) -> Self {
         Self { left, right }
     }
 }
 
-impl Backward for VectorVectorMulBackward {
+impl<T> Backward for VectorVectorMulBackward<T>
+where
+    T: Float,
+{
     fn backward(&self) {
         self.left.backward();
         self.right.backward();
     }
+
+    fn reads(&self) -> Reads {
+        Reads::Operands
+    }
 }
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
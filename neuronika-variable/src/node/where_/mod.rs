@@ -0,0 +1,221 @@
+use ndarray::{Array, Dimension, Zip};
+
+use crate::{
+    autograd::{Backward, Forward, Reads},
+    cell::Rc,
+    gradient::Gradient,
+    graph::Buffer,
+    utils::Shared,
+    Float,
+};
+
+pub(crate) struct Where<D, M, T>
+where
+    D: Dimension,
+    M: Dimension,
+    T: Float,
+{
+    mask_data: Shared<Array<bool, M>>,
+    left_data: Shared<Array<T, D>>,
+    right_data: Shared<Array<T, D>>,
+    data: Shared<Array<T, D>>,
+}
+
+impl<D, M, T> Where<D, M, T>
+where
+    D: Dimension,
+    M: Dimension,
+    T: Float,
+{
+    pub(crate) fn new(
+        mask_data: Shared<Array<bool, M>>,
+        left_data: Shared<Array<T, D>>,
+        right_data: Shared<Array<T, D>>,
+        data: Shared<Array<T, D>>,
+    ) -> Self {
+        Self {
+            mask_data,
+            left_data,
+            right_data,
+            data,
+        }
+    }
+}
+
+impl<D, M, T> Forward for Where<D, M, T>
+where
+    D: Dimension,
+    M: Dimension,
+    T: Float,
+{
+    fn forward(&self) {
+        Zip::from(&mut *self.data.borrow_mut())
+            .and_broadcast(&*self.mask_data.borrow())
+            .and(&*self.left_data.borrow())
+            .and(&*self.right_data.borrow())
+            .for_each(|v, &mask, &l, &r| *v = if mask { l } else { r });
+    }
+
+    fn name(&self) -> &'static str {
+        "Where"
+    }
+
+    fn operands(&self) -> Vec<Buffer> {
+        vec![
+            Buffer::new(&self.mask_data),
+            Buffer::new(&self.left_data),
+            Buffer::new(&self.right_data),
+        ]
+    }
+
+    fn data(&self) -> Buffer {
+        Buffer::new(&self.data)
+    }
+}
+
+pub(crate) struct WhereBackwardLeft<D, M, T>
+where
+    D: Dimension,
+    M: Dimension,
+    T: Float,
+{
+    mask_data: Shared<Array<bool, M>>,
+    operand_gradient: Rc<Gradient<Array<T, D>, D>>,
+    gradient: Rc<Gradient<Array<T, D>, D>>,
+}
+
+impl<D, M, T> WhereBackwardLeft<D, M, T>
+where
+    D: Dimension,
+    M: Dimension,
+    T: Float,
+{
+    pub(crate) fn new(
+        mask_data: Shared<Array<bool, M>>,
+        operand_gradient: Rc<Gradient<Array<T, D>, D>>,
+        gradient: Rc<Gradient<Array<T, D>, D>>,
+    ) -> Self {
+        Self {
+            mask_data,
+            operand_gradient,
+            gradient,
+        }
+    }
+}
+
+impl<D, M, T> Backward for WhereBackwardLeft<D, M, T>
+where
+    D: Dimension,
+    M: Dimension,
+    T: Float,
+{
+    fn backward(&self) {
+        Zip::from(&mut *self.operand_gradient.borrow_mut())
+            .and(&*self.gradient.borrow())
+            .and_broadcast(&*self.mask_data.borrow())
+            .for_each(|op_grad_el, &grad_el, &mask| {
+                if mask {
+                    *op_grad_el += grad_el
+                }
+            });
+    }
+
+    fn reads(&self) -> Reads {
+        Reads::Operands
+    }
+}
+
+pub(crate) struct WhereBackwardRight<D, M, T>
+where
+    D: Dimension,
+    M: Dimension,
+    T: Float,
+{
+    mask_data: Shared<Array<bool, M>>,
+    operand_gradient: Rc<Gradient<Array<T, D>, D>>,
+    gradient: Rc<Gradient<Array<T, D>, D>>,
+}
+
+impl<D, M, T> WhereBackwardRight<D, M, T>
+where
+    D: Dimension,
+    M: Dimension,
+    T: Float,
+{
+    pub(crate) fn new(
+        mask_data: Shared<Array<bool, M>>,
+        operand_gradient: Rc<Gradient<Array<T, D>, D>>,
+        gradient: Rc<Gradient<Array<T, D>, D>>,
+    ) -> Self {
+        Self {
+            mask_data,
+            operand_gradient,
+            gradient,
+        }
+    }
+}
+
+impl<D, M, T> Backward for WhereBackwardRight<D, M, T>
+where
+    D: Dimension,
+    M: Dimension,
+    T: Float,
+{
+    fn backward(&self) {
+        Zip::from(&mut *self.operand_gradient.borrow_mut())
+            .and(&*self.gradient.borrow())
+            .and_broadcast(&*self.mask_data.borrow())
+            .for_each(|op_grad_el, &grad_el, &mask| {
+                if !mask {
+                    *op_grad_el += grad_el
+                }
+            });
+    }
+
+    fn reads(&self) -> Reads {
+        Reads::Operands
+    }
+}
+
+pub(crate) struct WhereBackward<D, M, T>
+where
+    D: Dimension,
+    M: Dimension,
+    T: Float,
+{
+    left: WhereBackwardLeft<D, M, T>,
+    right: WhereBackwardRight<D, M, T>,
+}
+
+impl<D, M, T> WhereBackward<D, M, T>
+where
+    D: Dimension,
+    M: Dimension,
+    T: Float,
+{
+    pub(crate) fn new(
+        left: WhereBackwardLeft<D, M, T>,
+        right: WhereBackwardRight<D, M, T>,
+    ) -> Self {
+        Self { left, right }
+    }
+}
+
+impl<D, M, T> Backward for WhereBackward<D, M, T>
+where
+    D: Dimension,
+    M: Dimension,
+    T: Float,
+{
+    fn backward(&self) {
+        self.left.backward();
+        self.right.backward();
+    }
+
+    fn reads(&self) -> Reads {
+        Reads::Operands
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,76 @@
+use std::error::Error;
+
+use ndarray::{array, Array};
+
+use crate::cell::Rc;
+use crate::utils::{are_similar, new_shared};
+
+mod forward {
+    use super::super::{Forward, Where};
+    use super::*;
+
+    #[test]
+    fn base_case() -> Result<(), Box<dyn Error>> {
+        let op = Where::new(
+            new_shared(array![[true, false], [false, true]]),
+            new_shared(array![[1., 2.], [3., 4.]]),
+            new_shared(array![[5., 6.], [7., 8.]]),
+            new_shared(Array::zeros((2, 2))),
+        );
+
+        op.forward();
+        are_similar(op.data.borrow(), &array![[1., 6.], [7., 4.]])
+    }
+
+    #[test]
+    fn mask_broadcast() -> Result<(), Box<dyn Error>> {
+        let op = Where::new(
+            new_shared(array![true, false]),
+            new_shared(array![[1., 2.], [3., 4.]]),
+            new_shared(array![[5., 6.], [7., 8.]]),
+            new_shared(Array::zeros((2, 2))),
+        );
+
+        op.forward();
+        are_similar(op.data.borrow(), &array![[1., 6.], [3., 8.]])
+    }
+}
+
+mod backward {
+    use super::super::{Backward, Gradient, WhereBackward, WhereBackwardLeft, WhereBackwardRight};
+    use super::*;
+
+    #[test]
+    fn backward() -> Result<(), Box<dyn Error>> {
+        let mask = new_shared(array![[true, false], [false, true]]);
+        let grad = Rc::new(Gradient::from_ndarray(array![[1., 2.], [3., 4.]]));
+        let op = WhereBackward::new(
+            WhereBackwardLeft::new(
+                mask.clone(),
+                Rc::new(Gradient::ndarray_zeros((2, 2))),
+                grad.clone(),
+            ),
+            WhereBackwardRight::new(mask, Rc::new(Gradient::ndarray_zeros((2, 2))), grad),
+        );
+
+        op.backward();
+        are_similar(
+            op.left.operand_gradient.borrow(),
+            &array![[1., 0.], [0., 4.]],
+        )?;
+        are_similar(
+            op.right.operand_gradient.borrow(),
+            &array![[0., 2.], [3., 0.]],
+        )?;
+
+        op.backward();
+        are_similar(
+            op.left.operand_gradient.borrow(),
+            &array![[2., 0.], [0., 8.]],
+        )?;
+        are_similar(
+            op.right.operand_gradient.borrow(),
+            &array![[0., 4.], [6., 0.]],
+        )
+    }
+}
@@ -0,0 +1,201 @@
+use ndarray::{ArrayView, Axis, Ix0, RemoveAxis};
+use rand::RngCore;
+use rayon::prelude::*;
+
+use crate::{with_generator, Element, Float, Generator, Parameter, Var, VarDiff};
+
+/// A model that can be replicated across threads by [`DataParallel`].
+pub trait Replicate {
+    /// Type of the elements of the model's parameters.
+    type Elem: Float;
+
+    /// Returns a copy of `self` whose parameters are new differentiable leaves holding a copy of
+    /// the data of the original ones.
+    fn replicate(&self) -> Self;
+
+    /// Returns the parameters of `self`.
+    ///
+    /// The parameters must always be returned in the same order, so that the ones of a replica
+    /// match the ones of the model it was replicated from.
+    fn parameters(&self) -> Vec<Parameter<Self::Elem>>;
+}
+
+/// Data-parallel wrapper of a model.
+///
+/// The model is replicated once per shard, each mini-batch is split along its first axis into
+/// as many shards as there are replicas and each shard is processed by its own replica in
+/// parallel, using the **[rayon]** global thread pool. The gradients of the replicas are then
+/// summed into the ones of the wrapped model, which is the only one that should be registered
+/// with an optimizer.
+///
+/// The result matches the one of processing the whole mini-batch with the wrapped model, as long
+/// as the loss is the mean of the per-example losses, up to floating point reassociation.
+///
+/// [rayon]: https://docs.rs/rayon
+pub struct DataParallel<M>
+where
+    M: Replicate,
+{
+    module: M,
+    replicas: Vec<M>,
+}
+
+impl<M> DataParallel<M>
+where
+    M: Replicate + Sync,
+{
+    /// Wraps `module`, replicating it `replicas` times.
+    ///
+    /// # Arguments
+    ///
+    /// * `module` - model to wrap.
+    ///
+    /// * `replicas` - number of replicas, usually the size of the **[rayon]** global thread pool.
+    ///
+    /// # Panics
+    ///
+    /// If `replicas` is zero or if the parameters of a replica don't match the ones of `module`.
+    ///
+    /// [rayon]: https://docs.rs/rayon
+    pub fn new(module: M, replicas: usize) -> Self {
+        assert!(
+            replicas > 0,
+            "error: the number of replicas must be positive."
+        );
+
+        let parameters = module.parameters();
+        let replicas: Vec<M> = (0..replicas).map(|_| module.replicate()).collect();
+        for replica in &replicas {
+            let replica_parameters = replica.parameters();
+            assert_eq!(
+                parameters.len(),
+                replica_parameters.len(),
+                "error: a replica has a different number of parameters."
+            );
+            for (parameter, replica_parameter) in parameters.iter().zip(&replica_parameters) {
+                assert_eq!(
+                    parameter.shape(),
+                    replica_parameter.shape(),
+                    "error: the parameters of a replica have different shapes."
+                );
+            }
+        }
+
+        Self { module, replicas }
+    }
+
+    /// Returns a reference to the wrapped model.
+    pub fn module(&self) -> &M {
+        &self.module
+    }
+
+    /// Consumes `self`, returning the wrapped model.
+    pub fn into_module(self) -> M {
+        self.module
+    }
+
+    /// Computes the loss on a mini-batch and accumulates its gradient into the parameters of the
+    /// wrapped model, returning the value of the loss.
+    ///
+    /// The data of the replicas is first synchronized with the one of the wrapped model, then
+    /// each replica computes `loss` on its shard of `records` and `labels` and back-propagates it,
+    /// scaled by the fraction of the mini-batch the shard accounts for. Lastly, the gradients of
+    /// the replicas are summed into the ones of the wrapped model.
+    ///
+    /// Each replica draws its random numbers, such as the ones of dropout, from its own
+    /// [`Generator`], seeded from the one of the calling thread. Runs seeded with
+    /// [`manual_seed()`](crate::manual_seed()) are thus reproducible regardless of the thread
+    /// each replica runs on.
+    ///
+    /// As with [`.backward()`](VarDiff::backward()), the gradients of the wrapped model are
+    /// accumulated, so they should be zeroed, usually by the optimizer, between two calls.
+    ///
+    /// # Arguments
+    ///
+    /// * `records` - records of the mini-batch.
+    ///
+    /// * `labels` - labels of the mini-batch.
+    ///
+    /// * `loss` - computes the mean loss of a replica on a shard of records and labels.
+    ///
+    /// # Panics
+    ///
+    /// If `records` and `labels` have a different length along the first axis or if they are
+    /// empty.
+    pub fn forward_backward<D, E, L, F>(
+        &self,
+        records: ArrayView<M::Elem, D>,
+        labels: ArrayView<L, E>,
+        loss: F,
+    ) -> M::Elem
+    where
+        D: RemoveAxis,
+        E: RemoveAxis,
+        L: Element,
+        F: Fn(&M, Var<D, M::Elem>, Var<E, L>) -> VarDiff<Ix0, M::Elem> + Sync,
+    {
+        let len = records.len_of(Axis(0));
+        assert_eq!(
+            len,
+            labels.len_of(Axis(0)),
+            "error: records and labels have different lengths."
+        );
+        assert!(len > 0, "error: the mini-batch is empty.");
+
+        let shard_len = len.div_ceil(self.replicas.len());
+        let shards: Vec<_> = records
+            .axis_chunks_iter(Axis(0), shard_len)
+            .zip(labels.axis_chunks_iter(Axis(0), shard_len))
+            .collect();
+        let replicas = &self.replicas[..shards.len()];
+
+        let parameters = self.module.parameters();
+        let total = M::Elem::from_usize(len);
+        let generators: Vec<_> = with_generator(|generator| {
+            replicas
+                .iter()
+                .map(|_| Generator::new(generator.next_u64()))
+                .collect()
+        });
+
+        let (replicas_parameters, losses): (Vec<_>, Vec<_>) = replicas
+            .par_iter()
+            .zip(shards)
+            .zip(generators)
+            .map(|((replica, (records, labels)), generator)| {
+                let replica_parameters = replica.parameters();
+                for (parameter, replica_parameter) in parameters.iter().zip(&replica_parameters) {
+                    replica_parameter.assign(parameter);
+                    replica_parameter.zero_grad();
+                }
+
+                // The generator of the thread is restored once the replica is done, as the
+                // thread may run other tasks afterwards.
+                let previous = with_generator(|current| std::mem::replace(current, generator));
+                let weight = M::Elem::from_usize(records.len_of(Axis(0))) / total;
+                let shard_loss = loss(
+                    replica,
+                    crate::from_ndarray(records.to_owned()),
+                    crate::from_ndarray(labels.to_owned()),
+                );
+                shard_loss.forward();
+                shard_loss.backward(weight);
+                with_generator(|current| *current = previous);
+
+                (replica_parameters, shard_loss.item() * weight)
+            })
+            .unzip();
+
+        // All-reduce, each parameter sums the gradients of the replicas in a fixed order.
+        parameters
+            .par_iter()
+            .enumerate()
+            .for_each(|(i, parameter)| {
+                for replica_parameters in &replicas_parameters {
+                    replica_parameters[i].accumulate_into(parameter);
+                }
+            });
+
+        losses.into_iter().sum()
+    }
+}
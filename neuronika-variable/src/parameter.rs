@@ -0,0 +1,214 @@
+use std::any::Any;
+
+use ndarray::{Array, ArrayD, ArrayViewD, ArrayViewMutD, Dimension};
+
+use crate::{
+    cell::{MaybeSync, Rc},
+    gradient::{Gradient, LeafGradient},
+    Float, Var, VarDiff,
+};
+
+/// A type-erased handle to a parameter of a model, that is, a differentiable variable of any
+/// dimensionality.
+pub struct Parameter<T = f32>
+where
+    T: Float,
+{
+    inner: Box<dyn DynParameter<T>>,
+}
+
+impl<T> Parameter<T>
+where
+    T: Float,
+{
+    /// Returns the shape of the parameter.
+    pub fn shape(&self) -> Vec<usize> {
+        self.inner.shape()
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.shape().iter().product()
+    }
+
+    /// Returns a copy of the gradient of `self`, with dynamic dimensionality.
+    pub fn grad(&self) -> ArrayD<T> {
+        let mut grad = None;
+        self.inner
+            .with_grad(&mut |view| grad = Some(view.to_owned()));
+
+        grad.unwrap()
+    }
+
+    /// Sets the element of the data of `self` at the logical position `index` to `value`,
+    /// returning the previous one.
+    pub(crate) fn replace(&self, index: usize, value: T) -> T {
+        self.update(index, &|_| value)
+    }
+
+    /// Adds `delta` to the element of the data of `self` at the logical position `index`,
+    /// returning the previous one.
+    pub(crate) fn shift(&self, index: usize, delta: T) -> T {
+        self.update(index, &|element| element + delta)
+    }
+
+    fn update(&self, index: usize, f: &dyn Fn(T) -> T) -> T {
+        let mut previous = None;
+        self.inner.with_data_mut(&mut |mut view| {
+            let element = view.iter_mut().nth(index).unwrap();
+            previous = Some(*element);
+            *element = f(*element);
+        });
+
+        previous.unwrap()
+    }
+
+    /// Overwrites the data of `self` with the one of `source`.
+    #[cfg(feature = "sync")]
+    pub(crate) fn assign(&self, source: &Self) {
+        source
+            .inner
+            .with_data(&mut |data| self.inner.assign_data(data));
+    }
+
+    /// Adds the gradient of `self` to the one of `target`.
+    #[cfg(feature = "sync")]
+    pub(crate) fn accumulate_into(&self, target: &Self) {
+        self.inner
+            .with_grad(&mut |grad| target.inner.add_grad(grad));
+    }
+
+    /// Zeroes the gradient of `self`.
+    pub fn zero_grad(&self) {
+        self.inner.zero_grad()
+    }
+}
+
+impl<D, T> From<VarDiff<D, T>> for Parameter<T>
+where
+    D: 'static + Dimension,
+    T: Float,
+{
+    fn from(variable: VarDiff<D, T>) -> Self {
+        Self {
+            inner: Box::new(variable),
+        }
+    }
+}
+
+trait DynParameter<T>: MaybeSync {
+    fn shape(&self) -> Vec<usize>;
+
+    #[cfg(feature = "sync")]
+    fn with_data(&self, f: &mut dyn FnMut(ArrayViewD<T>));
+
+    fn with_data_mut(&self, f: &mut dyn FnMut(ArrayViewMutD<T>));
+
+    fn with_grad(&self, f: &mut dyn FnMut(ArrayViewD<T>));
+
+    #[cfg(feature = "sync")]
+    fn assign_data(&self, data: ArrayViewD<T>);
+
+    #[cfg(feature = "sync")]
+    fn add_grad(&self, grad: ArrayViewD<T>);
+
+    fn zero_grad(&self);
+}
+
+impl<D, T> DynParameter<T> for VarDiff<D, T>
+where
+    D: 'static + Dimension,
+    T: Float,
+{
+    fn shape(&self) -> Vec<usize> {
+        self.data().shape().to_vec()
+    }
+
+    #[cfg(feature = "sync")]
+    fn with_data(&self, f: &mut dyn FnMut(ArrayViewD<T>)) {
+        f(self.data().view().into_dyn())
+    }
+
+    fn with_data_mut(&self, f: &mut dyn FnMut(ArrayViewMutD<T>)) {
+        f(self.data_mut().view_mut().into_dyn())
+    }
+
+    fn with_grad(&self, f: &mut dyn FnMut(ArrayViewD<T>)) {
+        f(self.grad().view().into_dyn())
+    }
+
+    #[cfg(feature = "sync")]
+    fn assign_data(&self, data: ArrayViewD<T>) {
+        self.data_mut().view_mut().into_dyn().assign(&data);
+    }
+
+    #[cfg(feature = "sync")]
+    fn add_grad(&self, grad: ArrayViewD<T>) {
+        let mut gradient = self.grad_mut();
+        let mut gradient = gradient.view_mut().into_dyn();
+        gradient += &grad;
+    }
+
+    fn zero_grad(&self) {
+        VarDiff::zero_grad(self)
+    }
+}
+
+/// A differentiable leaf, as registered in the history of every variable depending on it.
+pub(crate) trait Leaf: LeafGradient {
+    /// Returns a handle to the leaf, that is, a boxed [`Parameter`] of its element type.
+    fn parameter(&self) -> Box<dyn Any>;
+}
+
+/// The variable and the gradient of a differentiable leaf.
+pub(crate) struct LeafVariable<D, T>
+where
+    D: Dimension,
+    T: Float,
+{
+    var: Var<D, T>,
+    grad: Rc<Gradient<Array<T, D>, D>>,
+}
+
+impl<D, T> LeafVariable<D, T>
+where
+    D: Dimension,
+    T: Float,
+{
+    pub(crate) fn new(var: Var<D, T>, grad: Rc<Gradient<Array<T, D>, D>>) -> Self {
+        Self { var, grad }
+    }
+}
+
+impl<D, T> LeafGradient for LeafVariable<D, T>
+where
+    D: Dimension,
+    T: Float,
+{
+    fn begin_accumulation(&self) {
+        self.grad.begin_accumulation();
+    }
+
+    fn end_accumulation(&self) {
+        self.grad.end_accumulation();
+    }
+
+    fn bytes(&self) -> usize {
+        LeafGradient::bytes(&*self.grad)
+    }
+
+    fn is_finite(&self) -> bool {
+        LeafGradient::is_finite(&*self.grad)
+    }
+}
+
+impl<D, T> Leaf for LeafVariable<D, T>
+where
+    D: 'static + Dimension,
+    T: Float,
+{
+    fn parameter(&self) -> Box<dyn Any> {
+        let variable = VarDiff::from_leaf(self.var.clone(), self.grad.clone());
+
+        Box::new(Parameter::from(variable))
+    }
+}
@@ -5,11 +5,12 @@ use serde::{
 
 use ndarray::{Array, Dimension};
 
-use super::{Var, VarDiff};
+use super::{Float, Var, VarDiff};
 
-impl<D> Serialize for Var<D>
+impl<D, T> Serialize for Var<D, T>
 where
     D: 'static + Dimension + Serialize,
+    T: Float + Serialize,
 {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -19,22 +20,24 @@ where
     }
 }
 
-impl<'d, D> Deserialize<'d> for Var<D>
+impl<'d, D, T> Deserialize<'d> for Var<D, T>
 where
     D: 'static + Dimension + Deserialize<'d>,
+    T: Float + Deserialize<'d>,
 {
     fn deserialize<De>(deserializer: De) -> Result<Self, De::Error>
     where
         De: Deserializer<'d>,
     {
-        let data = Array::<f32, D>::deserialize(deserializer).unwrap();
+        let data = Array::<T, D>::deserialize(deserializer).unwrap();
         Ok(Self::leaf(data))
     }
 }
 
-impl<D> Serialize for VarDiff<D>
+impl<D, T> Serialize for VarDiff<D, T>
 where
     D: Dimension + Serialize,
+    T: Float + Serialize,
 {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -44,15 +47,16 @@ where
     }
 }
 
-impl<'d, D> Deserialize<'d> for VarDiff<D>
+impl<'d, D, T> Deserialize<'d> for VarDiff<D, T>
 where
-    D: Dimension + Deserialize<'d>,
+    D: 'static + Dimension + Deserialize<'d>,
+    T: Float + Deserialize<'d>,
 {
     fn deserialize<De>(deserializer: De) -> Result<Self, De::Error>
     where
         De: Deserializer<'d>,
     {
-        let data = Array::<f32, D>::deserialize(deserializer).unwrap();
+        let data = Array::<T, D>::deserialize(deserializer).unwrap();
         Ok(Var::leaf(data).requires_grad())
     }
 }
@@ -0,0 +1,430 @@
+use ndarray::{Array, Array1, Array2, ArrayView1, ArrayView2, Ix1, Ix2};
+
+use crate::{
+    cell::{Rc, RefCell},
+    gradient::Gradient,
+    node::{SparseMatrixMul, SparseMatrixMulBackward},
+    Float, MatMatMul, Var, VarDiff,
+};
+
+/// Storage layout of a [`SparseVar`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SparseLayout {
+    /// Coordinate layout, storing the row, the column and the value of each element.
+    Coo,
+    /// Compressed sparse row layout, storing the columns and the values of the elements row by
+    /// row, together with the offset of each row.
+    Csr,
+}
+
+/// A sparse matrix in the compressed sparse row layout.
+#[derive(Clone, Debug)]
+pub(crate) struct Csr<T> {
+    /// Offsets of the rows in `columns` and `values`, with one trailing entry equal to their
+    /// length.
+    pub(crate) offsets: Vec<usize>,
+    pub(crate) columns: Vec<usize>,
+    pub(crate) values: Vec<T>,
+}
+
+impl<T> Csr<T>
+where
+    T: Float,
+{
+    /// Iterates over the rows, yielding their columns and values.
+    pub(crate) fn rows(&self) -> impl Iterator<Item = (&[usize], &[T])> {
+        self.offsets.windows(2).map(move |bounds| {
+            let range = bounds[0]..bounds[1];
+
+            (&self.columns[range.clone()], &self.values[range])
+        })
+    }
+}
+
+/// A sparse matrix in the coordinate layout.
+#[derive(Debug)]
+struct Coo<T> {
+    rows: Vec<usize>,
+    columns: Vec<usize>,
+    values: Vec<T>,
+}
+
+#[derive(Clone, Debug)]
+enum Storage<T> {
+    Coo(Rc<Coo<T>>),
+    Csr(Rc<Csr<T>>),
+}
+
+/// A non-differentiable, sparse, two-dimensional leaf variable.
+///
+/// Only the non-zero elements are stored, either in the [`Coo`](SparseLayout::Coo) or in the
+/// [`Csr`](SparseLayout::Csr) layout. The latter is the one used by the computations, hence
+/// variables in the former are converted each time they take part to one.
+///
+/// Sparse variables can be multiplied by dense ones with [`.mm()`](MatMatMul::mm()), and the
+/// gradient flows to the dense operand if it's differentiable. Duplicate elements are allowed and
+/// are summed together.
+///
+/// # Examples
+///
+/// ```
+/// # use neuronika_variable as neuronika;
+/// use neuronika::SparseVar;
+/// use neuronika_core::MatMatMul;
+///
+/// let a = SparseVar::from_coo((2, 3), vec![0, 1], vec![2, 0], vec![2., 3.]);
+/// let x = neuronika::ones((3, 2)).requires_grad();
+/// let y = a.mm(x.clone()).sum();
+///
+/// y.forward();
+/// y.backward(1.);
+///
+/// assert_eq!(y.item(), 10.);
+/// assert_eq!(*x.grad(), ndarray::array![[3., 3.], [0., 0.], [2., 2.]]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct SparseVar<T = f32>
+where
+    T: Float,
+{
+    shape: (usize, usize),
+    storage: Storage<T>,
+}
+
+impl<T> SparseVar<T>
+where
+    T: Float,
+{
+    /// Creates a sparse variable in the coordinate layout.
+    ///
+    /// # Arguments
+    ///
+    /// * `shape` - number of rows and columns.
+    ///
+    /// * `rows` - row of each element.
+    ///
+    /// * `columns` - column of each element.
+    ///
+    /// * `values` - value of each element.
+    ///
+    /// # Panics
+    ///
+    /// If `rows`, `columns` and `values` have different lengths or if an element is out of bounds.
+    pub fn from_coo(
+        shape: (usize, usize),
+        rows: Vec<usize>,
+        columns: Vec<usize>,
+        values: Vec<T>,
+    ) -> Self {
+        assert!(
+            rows.len() == values.len() && columns.len() == values.len(),
+            "error: {} rows, {} columns and {} values given for a sparse variable.",
+            rows.len(),
+            columns.len(),
+            values.len()
+        );
+        assert!(
+            rows.iter().all(|&row| row < shape.0),
+            "error: row out of bounds for a sparse variable with {} rows.",
+            shape.0
+        );
+        check_columns(&columns, shape.1);
+
+        Self {
+            shape,
+            storage: Storage::Coo(Rc::new(Coo {
+                rows,
+                columns,
+                values,
+            })),
+        }
+    }
+
+    /// Creates a sparse variable in the compressed sparse row layout.
+    ///
+    /// # Arguments
+    ///
+    /// * `shape` - number of rows and columns.
+    ///
+    /// * `offsets` - offsets of the rows in `columns` and `values`, followed by their length.
+    ///
+    /// * `columns` - column of each element.
+    ///
+    /// * `values` - value of each element.
+    ///
+    /// # Panics
+    ///
+    /// If `offsets` doesn't have one more entry than the rows, if it's not non-decreasing, if it
+    /// doesn't start at 0 and end at the number of elements, if `columns` and `values` have
+    /// different lengths or if a column is out of bounds.
+    pub fn from_csr(
+        shape: (usize, usize),
+        offsets: Vec<usize>,
+        columns: Vec<usize>,
+        values: Vec<T>,
+    ) -> Self {
+        assert_eq!(
+            columns.len(),
+            values.len(),
+            "error: {} columns and {} values given for a sparse variable.",
+            columns.len(),
+            values.len()
+        );
+        assert!(
+            offsets.len() == shape.0 + 1
+                && offsets.first() == Some(&0)
+                && offsets.last() == Some(&values.len())
+                && offsets.windows(2).all(|bounds| bounds[0] <= bounds[1]),
+            "error: invalid row offsets for a sparse variable with {} rows and {} elements.",
+            shape.0,
+            values.len()
+        );
+        check_columns(&columns, shape.1);
+
+        Self {
+            shape,
+            storage: Storage::Csr(Rc::new(Csr {
+                offsets,
+                columns,
+                values,
+            })),
+        }
+    }
+
+    /// Creates a sparse variable in the compressed sparse row layout from the non-zero elements of
+    /// `array`.
+    pub fn from_dense(array: ArrayView2<T>) -> Self {
+        let mut offsets = Vec::with_capacity(array.nrows() + 1);
+        let (mut columns, mut values) = (Vec::new(), Vec::new());
+
+        offsets.push(0);
+        for row in array.outer_iter() {
+            for (column, &value) in row.indexed_iter() {
+                if value != T::zero() {
+                    columns.push(column);
+                    values.push(value);
+                }
+            }
+            offsets.push(values.len());
+        }
+
+        Self::from_csr(array.dim(), offsets, columns, values)
+    }
+
+    /// Returns the number of rows and columns of the variable.
+    pub fn shape(&self) -> (usize, usize) {
+        self.shape
+    }
+
+    /// Returns the storage layout of the variable.
+    pub fn layout(&self) -> SparseLayout {
+        match self.storage {
+            Storage::Coo(_) => SparseLayout::Coo,
+            Storage::Csr(_) => SparseLayout::Csr,
+        }
+    }
+
+    /// Returns the number of stored elements.
+    pub fn nnz(&self) -> usize {
+        match &self.storage {
+            Storage::Coo(coo) => coo.values.len(),
+            Storage::Csr(csr) => csr.values.len(),
+        }
+    }
+
+    /// Returns the variable in the coordinate layout.
+    pub fn to_coo(&self) -> Self {
+        let csr = match &self.storage {
+            Storage::Coo(_) => return self.clone(),
+            Storage::Csr(csr) => csr,
+        };
+
+        let rows = csr
+            .rows()
+            .enumerate()
+            .flat_map(|(row, (columns, _))| std::iter::repeat_n(row, columns.len()))
+            .collect();
+
+        Self {
+            shape: self.shape,
+            storage: Storage::Coo(Rc::new(Coo {
+                rows,
+                columns: csr.columns.clone(),
+                values: csr.values.clone(),
+            })),
+        }
+    }
+
+    /// Returns the variable in the compressed sparse row layout.
+    ///
+    /// The order of the elements of each row is preserved.
+    pub fn to_csr(&self) -> Self {
+        Self {
+            shape: self.shape,
+            storage: Storage::Csr(self.csr()),
+        }
+    }
+
+    /// Returns a dense variable with the same elements.
+    pub fn to_dense(&self) -> Var<Ix2, T> {
+        let mut array = Array::zeros(self.shape);
+        for (row, (columns, values)) in self.csr().rows().enumerate() {
+            for (&column, &value) in columns.iter().zip(values) {
+                array[[row, column]] += value;
+            }
+        }
+
+        Var::leaf(array)
+    }
+
+    /// Returns a dense variable with the sum of the elements of each row.
+    pub fn row_sum(&self) -> Var<Ix1, T> {
+        let sums = self
+            .csr()
+            .rows()
+            .map(|(_, values)| values.iter().fold(T::zero(), |sum, &value| sum + value))
+            .collect::<Array1<T>>();
+
+        Var::leaf(sums)
+    }
+
+    /// Returns a sparse variable with the elements of `self` multiplied by `factor`, in the same
+    /// layout.
+    pub fn scale(&self, factor: T) -> Self {
+        self.map_values(|_, value| value * factor)
+    }
+
+    /// Returns a sparse variable with the elements of each row of `self` multiplied by the
+    /// corresponding element of `factors`, in the same layout.
+    ///
+    /// # Panics
+    ///
+    /// If the length of `factors` differs from the number of rows.
+    pub fn scale_rows(&self, factors: ArrayView1<T>) -> Self {
+        assert_eq!(
+            factors.len(),
+            self.shape.0,
+            "error: {} factors given for a sparse variable with {} rows.",
+            factors.len(),
+            self.shape.0
+        );
+
+        self.map_values(|row, value| value * factors[row])
+    }
+
+    /// Applies `f` to the row and the value of each element.
+    fn map_values(&self, f: impl Fn(usize, T) -> T) -> Self {
+        let storage = match &self.storage {
+            Storage::Coo(coo) => Storage::Coo(Rc::new(Coo {
+                rows: coo.rows.clone(),
+                columns: coo.columns.clone(),
+                values: coo
+                    .rows
+                    .iter()
+                    .zip(&coo.values)
+                    .map(|(&row, &value)| f(row, value))
+                    .collect(),
+            })),
+            Storage::Csr(csr) => Storage::Csr(Rc::new(Csr {
+                offsets: csr.offsets.clone(),
+                columns: csr.columns.clone(),
+                values: csr
+                    .rows()
+                    .enumerate()
+                    .flat_map(|(row, (_, values))| values.iter().map(move |&value| (row, value)))
+                    .map(|(row, value)| f(row, value))
+                    .collect(),
+            })),
+        };
+
+        Self {
+            shape: self.shape,
+            storage,
+        }
+    }
+
+    /// Returns the elements in the compressed sparse row layout, converting them if needed.
+    fn csr(&self) -> Rc<Csr<T>> {
+        let Coo {
+            rows,
+            columns,
+            values,
+        } = match &self.storage {
+            Storage::Csr(csr) => return csr.clone(),
+            Storage::Coo(coo) => coo.as_ref(),
+        };
+
+        // Counting sort of the elements by row, which keeps the order within each row.
+        let mut offsets = vec![0; self.shape.0 + 1];
+        rows.iter().for_each(|&row| offsets[row + 1] += 1);
+        for row in 0..self.shape.0 {
+            offsets[row + 1] += offsets[row];
+        }
+
+        let mut positions = offsets.clone();
+        let mut order = vec![0; values.len()];
+        rows.iter().enumerate().for_each(|(element, &row)| {
+            order[positions[row]] = element;
+            positions[row] += 1;
+        });
+
+        Rc::new(Csr {
+            offsets,
+            columns: order.iter().map(|&element| columns[element]).collect(),
+            values: order.iter().map(|&element| values[element]).collect(),
+        })
+    }
+
+    /// Panics if `self` can't be multiplied by a dense matrix of shape `shape`, returning the shape
+    /// of the result otherwise.
+    fn mm_shape(&self, shape: Ix2) -> Ix2 {
+        assert_eq!(
+            self.shape.1,
+            shape[0],
+            "error: cannot multiply a sparse variable of shape {:?} by a variable of shape {:?}.",
+            self.shape,
+            (shape[0], shape[1])
+        );
+
+        Ix2(self.shape.0, shape[1])
+    }
+}
+
+fn check_columns(columns: &[usize], len: usize) {
+    assert!(
+        columns.iter().all(|&column| column < len),
+        "error: column out of bounds for a sparse variable with {} columns.",
+        len
+    );
+}
+
+impl<T> MatMatMul<Var<Ix2, T>> for SparseVar<T>
+where
+    T: Float,
+{
+    type Output = Var<Ix2, T>;
+
+    fn mm(self, rhs: Var<Ix2, T>) -> Self::Output {
+        let shape = self.mm_shape(rhs.data.borrow().raw_dim());
+        let data = Rc::new(RefCell::new(Array2::zeros(shape)));
+        let op = SparseMatrixMul::new(self.csr(), rhs.data, data.clone());
+
+        Var::node(data, Rc::new(op), rhs.history)
+    }
+}
+
+impl<T> MatMatMul<VarDiff<Ix2, T>> for SparseVar<T>
+where
+    T: Float,
+{
+    type Output = VarDiff<Ix2, T>;
+
+    fn mm(self, rhs: VarDiff<Ix2, T>) -> Self::Output {
+        let csr = self.csr();
+        let var = self.mm(rhs.var);
+        let grad = Rc::new(Gradient::ndarray_zeros(var.data().raw_dim()));
+        let op = SparseMatrixMulBackward::new(csr, rhs.grad, grad.clone());
+
+        VarDiff::node(var, grad.clone(), (Rc::new(op), grad), rhs.history)
+    }
+}
@@ -367,7 +367,7 @@ fn t_diff() {
 #[test]
 fn dropout() {
     let input = crate::ones((2, 2));
-    let status = std::rc::Rc::new(std::cell::Cell::default());
+    let status = crate::cell::Rc::new(crate::cell::Cell::default());
     let dropout = input.dropout(0.5, status);
 
     assert_eq!(dropout.history.len(), 1);
@@ -376,7 +376,7 @@ fn dropout() {
 #[test]
 fn dropout_diff() {
     let input = crate::ones((2, 2)).requires_grad();
-    let status = std::rc::Rc::new(std::cell::Cell::default());
+    let status = crate::cell::Rc::new(crate::cell::Cell::default());
     let dropout = input.dropout(0.5, status);
 
     assert_eq!(dropout.history.len(), 1);
@@ -805,6 +805,102 @@ fn mm_t_diff() {
     assert_eq!(mm_t.history.len(), 1);
 }
 
+#[test]
+fn backward_twice() {
+    let x = crate::ones(3).requires_grad();
+    let y = (x.clone() * 2.).sum();
+
+    y.forward();
+    y.backward(1.);
+    y.backward(1.);
+
+    assert_eq!(*x.grad(), ndarray::array![4., 4., 4.]);
+}
+
+#[test]
+fn register_hook() {
+    use crate::cell::{Rc, RefCell};
+
+    let x = crate::ones(3).requires_grad();
+    let y = x.clone() * 2.;
+    let z = (y.clone() * y.clone()).sum();
+
+    let seen = Rc::new(RefCell::new(Vec::new()));
+    let seen_clone = seen.clone();
+    let handle = y.register_hook(move |grad| {
+        seen_clone.borrow_mut().push(grad.clone());
+        grad.mapv_inplace(|el| -el);
+    });
+
+    z.forward();
+    z.backward(1.);
+
+    // The hook sees the gradient accumulated from both operands of the multiplication.
+    assert_eq!(seen.borrow().len(), 1);
+    assert_eq!(seen.borrow()[0], ndarray::array![4., 4., 4.]);
+    assert_eq!(*x.grad(), ndarray::array![-8., -8., -8.]);
+
+    handle.remove();
+    x.zero_grad();
+    z.backward(1.);
+
+    assert_eq!(seen.borrow().len(), 1);
+    assert_eq!(*x.grad(), ndarray::array![8., 8., 8.]);
+}
+
+#[test]
+fn register_hook_leaf() {
+    let x = crate::ones(3).requires_grad();
+    let y = (x.clone() * 2.).sum();
+
+    x.register_hook(|grad| grad.mapv_inplace(|el| el.min(1.)));
+
+    y.forward();
+    y.backward(1.);
+    assert_eq!(*x.grad(), ndarray::array![1., 1., 1.]);
+
+    // Only the contribution of the current pass is clipped.
+    y.backward(1.);
+    assert_eq!(*x.grad(), ndarray::array![2., 2., 2.]);
+}
+
+#[test]
+fn register_post_accumulate_hook() {
+    use crate::cell::{Cell, Rc};
+
+    let x = crate::ones(3).requires_grad();
+    let y = (x.clone() * 2.).sum();
+
+    let calls = Rc::new(Cell::new(0));
+    let calls_clone = calls.clone();
+    let handle = x.register_post_accumulate_hook(move |grad| {
+        calls_clone.set(calls_clone.get() + 1);
+        grad.mapv_inplace(|el| el.min(3.));
+    });
+
+    y.forward();
+    y.backward(1.);
+    y.backward(1.);
+
+    assert_eq!(calls.get(), 2);
+    assert_eq!(*x.grad(), ndarray::array![3., 3., 3.]);
+
+    handle.remove();
+    y.backward(1.);
+
+    assert_eq!(calls.get(), 2);
+    assert_eq!(*x.grad(), ndarray::array![5., 5., 5.]);
+}
+
+#[test]
+#[should_panic]
+fn register_post_accumulate_hook_non_leaf() {
+    let x = crate::ones(3).requires_grad();
+    let y = x * 2.;
+
+    y.register_post_accumulate_hook(|_| {});
+}
+
 // #[test]
 // fn convolve() {
 //     use crate::Convolve;
@@ -911,3 +1007,730 @@ fn mm_t_diff() {
 //     assert_eq!(convolve.history.len(), 1);
 //     assert_eq!(convolve.history.parameters.len(), 2);
 // }
+
+#[test]
+fn to_dot() {
+    let x = crate::ones((2, 3));
+    let y = crate::ones(3);
+    let z = (x + y).sum();
+
+    let dot = z.to_dot();
+    assert!(dot.starts_with("digraph {"));
+    assert!(dot.contains("0 [label=\"Leaf\\n[2, 3]\", shape=ellipse];"));
+    assert!(dot.contains("1 [label=\"Leaf\\n[3]\", shape=ellipse];"));
+    assert!(dot.contains("2 [label=\"Addition\\n[2, 3]\", shape=box];"));
+    assert!(dot.contains("3 [label=\"Sum\\n[]\", shape=box];"));
+    assert!(dot.contains("0 -> 2;"));
+    assert!(dot.contains("1 -> 2;"));
+    assert!(dot.contains("2 -> 3;"));
+    assert!(!dot.contains("color=red"));
+}
+
+#[test]
+fn to_dot_diff() {
+    let w = crate::ones((2, 3)).requires_grad();
+    let x = crate::ones((4, 3));
+    let y = x.mm_t(w).sum();
+
+    let dot = y.to_dot();
+    assert!(dot.contains("0 [label=\"Leaf\\n[4, 3]\", shape=ellipse];"));
+    assert!(dot.contains(
+        "1 [label=\"Parameter\\n[2, 3]\", shape=ellipse, style=filled, fillcolor=lightblue];"
+    ));
+    assert!(dot.contains("2 [label=\"MatrixMatrixMulT\\n[4, 2]\", shape=box];"));
+    assert!(dot.contains("0 -> 2;"));
+    assert!(dot.contains("1 -> 2 [color=red, penwidth=2];"));
+    assert!(dot.contains("2 -> 3 [color=red, penwidth=2];"));
+}
+
+#[test]
+fn graph() {
+    let x = crate::ones((2, 3));
+    let y = x.clone().exp() * x;
+
+    let graph = y.graph();
+    let nodes = graph.nodes();
+
+    assert_eq!(nodes.len(), 3);
+    assert!(nodes[0].is_leaf());
+    assert_eq!(nodes[1].op(), Some("Exp"));
+    assert_eq!(nodes[1].parents(), &[0]);
+    assert_eq!(nodes[2].op(), Some("Multiplication"));
+    assert_eq!(nodes[2].parents(), &[1, 0]);
+    assert_eq!(nodes[2].shape(), &[2, 3]);
+    assert_eq!(nodes[2].data_bytes(), 24);
+    assert!(nodes.iter().all(|node| !node.requires_grad()));
+    assert_eq!(graph.differentiable_leaves().count(), 0);
+}
+
+#[test]
+fn graph_diff() {
+    let w = crate::ones((2, 3)).requires_grad();
+    let b = crate::zeros(2).requires_grad();
+    let x = crate::ones((4, 3));
+    let y = (x.mm_t(w.clone()) * b.clone()).sum();
+
+    let graph = y.graph();
+    let nodes = graph.nodes();
+
+    assert_eq!(nodes.len(), 6);
+    assert!(!nodes[0].requires_grad());
+    assert_eq!(nodes[0].grad_bytes(), 0);
+    assert_eq!(nodes[2].op(), Some("MatrixMatrixMulT"));
+    assert_eq!(nodes[2].grad_bytes(), 32);
+    // The multiplication keeps an additional buffer for its backward pass.
+    assert_eq!(nodes[4].op(), Some("Multiplication"));
+    assert_eq!(nodes[4].grad_bytes(), 64);
+    assert_eq!(nodes[5].op(), Some("Sum"));
+    assert_eq!(nodes[5].shape(), &[] as &[usize]);
+
+    let leaves: Vec<_> = graph
+        .differentiable_leaves()
+        .map(|node| node.shape().to_vec())
+        .collect();
+    assert_eq!(leaves, vec![vec![2, 3], vec![2]]);
+
+    // The parameters are handles to the leaves, in the same order.
+    let parameters = y.parameters();
+    let shapes: Vec<_> = parameters
+        .iter()
+        .map(|parameter| parameter.shape())
+        .collect();
+    assert_eq!(shapes, leaves);
+
+    y.forward();
+    y.backward(1.);
+    assert_eq!(parameters[0].grad(), w.grad().clone().into_dyn());
+    assert_eq!(parameters[1].grad(), b.grad().clone().into_dyn());
+    parameters[1].zero_grad();
+    assert_eq!(*b.grad(), ndarray::Array::zeros(2));
+
+    y.no_grad();
+    assert_eq!(y.graph().nodes()[4].grad_bytes(), 0);
+}
+
+#[test]
+fn graph_leaf() {
+    let x = crate::ones(3).requires_grad();
+
+    let graph = x.graph();
+    assert_eq!(graph.nodes().len(), 1);
+    assert_eq!(graph.differentiable_leaves().count(), 1);
+    assert_eq!(graph.nodes()[0].grad_bytes(), 12);
+    assert_eq!(x.parameters().len(), 1);
+}
+
+#[test]
+fn detach() {
+    let x = crate::ones(3).requires_grad();
+    let y = x.clone() * 2.;
+    let detached = y.detach();
+    let z = (y * detached.clone()).sum();
+
+    assert_eq!(detached.history.len(), 1);
+    assert_eq!(z.history.len(), 3);
+
+    z.forward();
+    z.backward(1.);
+    assert_eq!(*detached.data(), ndarray::array![2., 2., 2.]);
+    assert_eq!(*x.grad(), ndarray::array![4., 4., 4.]);
+
+    // The detached variable is recomputed along with the rest of the graph.
+    *x.data_mut() += 1.;
+    x.zero_grad();
+    z.forward();
+    z.backward(1.);
+    assert_eq!(*detached.data(), ndarray::array![4., 4., 4.]);
+    assert_eq!(*x.grad(), ndarray::array![8., 8., 8.]);
+}
+
+#[test]
+fn double_precision() {
+    let x = crate::from_ndarray(ndarray::array![0.1_f64, 0.2, 0.3]).requires_grad();
+    let y = (x.clone() * x.clone() + x.clone()).sum();
+
+    y.forward();
+    y.backward(1.);
+    // Well below the resolution of single precision.
+    assert!((y.item() - 0.74).abs() < 1e-12);
+    assert!(x
+        .grad()
+        .iter()
+        .zip(&[1.2, 1.4, 1.6])
+        .all(|(grad, expected)| (grad - expected).abs() < 1e-12));
+    assert_eq!(y.graph().nodes()[0].data_bytes(), 24);
+}
+
+#[test]
+fn half_precision() {
+    fn check<T: crate::Float>(from_f32: fn(f32) -> T) {
+        let x = crate::from_ndarray(ndarray::array![1., 2., 3.].mapv(from_f32)).requires_grad();
+        let y = (x.clone() * x.clone() + x.clone()).sum();
+
+        y.forward();
+        y.backward(T::one());
+        assert_eq!(y.item(), from_f32(20.));
+        assert_eq!(*x.grad(), ndarray::array![3., 5., 7.].mapv(from_f32));
+        assert_eq!(y.graph().nodes()[0].data_bytes(), 6);
+    }
+
+    check(crate::f16::from_f32);
+    check(crate::bf16::from_f32);
+}
+
+#[test]
+fn comparison() {
+    let x = crate::from_ndarray(ndarray::array![1, 2, 3_i64]);
+    let y = crate::from_ndarray(ndarray::array![[2_i64], [3]]);
+    let mask = x.clone().ge(y.clone());
+
+    mask.forward();
+    assert_eq!(
+        *mask.data(),
+        ndarray::array![[false, true, true], [false, false, true]]
+    );
+
+    let mask = x.ne(y);
+    mask.forward();
+    assert_eq!(
+        *mask.data(),
+        ndarray::array![[true, false, true], [true, true, false]]
+    );
+}
+
+#[test]
+fn where_diff() {
+    let x = crate::from_ndarray(ndarray::array![1., 2., 3.]).requires_grad();
+    let y = crate::from_ndarray(ndarray::array![3., 2., 1.]).requires_grad();
+    let mask = x.clone().lt(y.detach());
+    let z = crate::where_(mask, x.clone(), y.clone()) * 2.;
+
+    z.forward();
+    z.backward(1.);
+    assert_eq!(*z.data(), ndarray::array![2., 4., 2.]);
+    assert_eq!(*x.grad(), ndarray::array![2., 0., 0.]);
+    assert_eq!(*y.grad(), ndarray::array![0., 2., 2.]);
+}
+
+#[test]
+fn masked_fill_diff() {
+    let x = crate::from_ndarray(ndarray::array![[1., 2.], [3., 4.]]).requires_grad();
+    let mask = crate::from_ndarray(ndarray::array![false, true]);
+    let y = x.clone().masked_fill(mask, f32::NEG_INFINITY).softmax(1);
+
+    y.forward();
+    y.backward(1.);
+    assert_eq!(*y.data(), ndarray::array![[1., 0.], [1., 0.]]);
+    assert_eq!(*x.grad(), ndarray::Array::zeros((2, 2)));
+}
+
+#[test]
+fn gather_diff() {
+    let x = crate::from_ndarray(ndarray::array![[1., 2., 3.], [4., 5., 6.]]).requires_grad();
+    let index = crate::from_ndarray(ndarray::array![[2_usize, 2], [0, 1]]);
+    let y = x.clone().gather(1, index);
+
+    y.forward();
+    y.backward(1.);
+    assert_eq!(*y.data(), ndarray::array![[3., 3.], [4., 5.]]);
+    assert_eq!(*x.grad(), ndarray::array![[0., 0., 2.], [1., 1., 0.]]);
+}
+
+#[test]
+fn nll_integer_labels() {
+    let x = crate::from_ndarray(ndarray::array![[-1., -2.], [-3., -4.]]).requires_grad();
+    let target = crate::from_ndarray(ndarray::array![1_i64, 0]);
+    let loss = x.clone().nll(target, crate::Reduction::Mean);
+
+    loss.forward();
+    loss.backward(1.);
+    assert_eq!(loss.item(), 2.5);
+    assert_eq!(*x.grad(), ndarray::array![[0., -0.5], [-0.5, 0.]]);
+}
+
+#[test]
+fn plan_memory() {
+    let x = crate::from_ndarray(ndarray::Array::from_shape_fn((4, 6), |(i, j)| {
+        (i * 6 + j) as f32 / 12. - 1.
+    }));
+    let w = ndarray::Array::from_shape_fn((6, 6), |(i, j)| ((i + 2 * j) % 5) as f32 / 5. - 0.4);
+    let b = ndarray::Array::from_shape_fn(6, |i| i as f32 / 10.);
+
+    let build = || {
+        let w = crate::from_ndarray(w.clone()).requires_grad();
+        let b = crate::from_ndarray(b.clone()).requires_grad();
+        let h = (x.clone().mm(w.clone()) + b.clone()).sigmoid();
+        let chunks = h.chunks((4, 3));
+        let h = chunks[1].clone().cat(&[chunks[0].clone()], 1) * b.clone();
+        let y = (h.mm(w.clone()).tanh() * 2. - 1.).pow(2).sum();
+
+        (y, w, b)
+    };
+
+    let (expected, expected_w, expected_b) = build();
+    let (y, w, b) = build();
+
+    expected.forward();
+    y.forward();
+    let report = y.plan_memory();
+    assert!(report.after() < report.before());
+
+    // The intermediate gradients are de-allocated, the ones of the root and of the leaves are not.
+    let graph = y.graph();
+    let nodes = graph.nodes();
+    assert!(nodes[..nodes.len() - 1]
+        .iter()
+        .filter(|node| !node.is_leaf())
+        .all(|node| node.grad_bytes() == 0));
+    assert_eq!(nodes[nodes.len() - 1].grad_bytes(), 4);
+    assert_eq!(
+        graph
+            .differentiable_leaves()
+            .map(|node| node.grad_bytes())
+            .sum::<usize>(),
+        168
+    );
+
+    for _ in 0..2 {
+        expected.forward();
+        expected.backward(1.);
+        y.forward();
+        y.backward(1.);
+
+        assert_eq!(y.item(), expected.item());
+        assert_eq!(*w.grad(), *expected_w.grad());
+        assert_eq!(*b.grad(), *expected_b.grad());
+    }
+    assert!(y.graph().nodes()[..nodes.len() - 1]
+        .iter()
+        .filter(|node| !node.is_leaf())
+        .all(|node| node.grad_bytes() == 0));
+}
+
+#[test]
+#[should_panic(
+    expected = "error: the memory of a node of the graph has been planned by another variable."
+)]
+fn plan_memory_reuse() {
+    let x = crate::rand((4, 6)).requires_grad();
+    let y = (x.clone().sigmoid() * 2.).sum();
+
+    y.forward();
+    y.plan_memory();
+
+    let z = y * 3.;
+    z.forward();
+    z.backward(1.);
+}
+
+#[test]
+#[should_panic(
+    expected = "error: the gradient of an intermediate node is de-allocated by the memory plan of its graph, see `.plan_memory()`."
+)]
+fn plan_memory_intermediate_grad() {
+    let x = crate::rand((4, 6)).requires_grad();
+    let h = x.sigmoid();
+    let y = (h.clone() * 2.).sum();
+
+    y.forward();
+    y.plan_memory();
+    y.backward(1.);
+
+    let _ = h.grad();
+}
+
+#[test]
+fn plan_memory_data() {
+    let x = ndarray::Array::from_shape_fn((8, 8), |(i, j)| (i * 8 + j) as f32 / 32. - 1.);
+
+    let build = || {
+        let x = crate::from_ndarray(x.clone()).requires_grad();
+        let h = ((x.clone() + 1.) - x.clone() * 0.5 + 2.).sigmoid();
+        let y = (h.clone() - 1. + h).mm(x.clone()).sum();
+
+        (y, x)
+    };
+
+    let (expected, expected_x) = build();
+    let (y, x) = build();
+
+    y.forward();
+    let report = y.plan_memory();
+    assert!(report.after() < report.before());
+
+    for _ in 0..2 {
+        expected.forward();
+        expected.backward(1.);
+        y.forward();
+        y.backward(1.);
+
+        assert_eq!(y.item(), expected.item());
+        assert_eq!(*x.grad(), *expected_x.grad());
+    }
+
+    // The data of the additions, of the subtractions, of the multiplications and of the matrix
+    // product is read by no back-propagation and is de-allocated, the one of the sigmoid and of
+    // the operands of the matrix product is not.
+    let graph = y.graph();
+    let released: Vec<&str> = graph
+        .nodes()
+        .iter()
+        .filter(|node| !node.is_leaf() && node.data_bytes() == 0)
+        .filter_map(|node| node.op())
+        .collect();
+    assert_eq!(released.len(), 6);
+    assert!(graph
+        .nodes()
+        .iter()
+        .filter(|node| matches!(node.op(), Some("Sigmoid") | Some("Sum")))
+        .all(|node| node.data_bytes() > 0));
+}
+
+#[test]
+fn fuse() {
+    let x = ndarray::Array::from_shape_fn((100, 50), |(i, j)| (i * 50 + j) as f32 / 2500. - 1.);
+    let b = ndarray::Array::from_shape_fn(50, |j| j as f32 / 50.);
+
+    let build = || {
+        let x = crate::from_ndarray(x.clone()).requires_grad();
+        let b = crate::from_ndarray(b.clone()).requires_grad();
+        let h = ((x.clone() * 3. + 1.).sigmoid() * x.clone() - 0.5).tanh();
+        let h = (h.clone() / (h.exp() + 1.)).leaky_relu() + b.clone();
+        h.register_hook(|grad| grad.mapv_inplace(|el| el * 2.));
+        let y = (-(h.softplus().sqrt().ln() - x.clone().relu()).pow(2)).sum();
+
+        (y, x, b)
+    };
+
+    let (expected, expected_x, expected_b) = build();
+    let (y, x, b) = build();
+    y.fuse();
+
+    for _ in 0..2 {
+        expected.forward();
+        expected.backward(1.);
+        y.forward();
+        y.backward(1.);
+
+        assert_eq!(y.item(), expected.item());
+        assert_eq!(*x.grad(), *expected_x.grad());
+        assert_eq!(*b.grad(), *expected_b.grad());
+    }
+}
+
+#[test]
+fn fuse_intermediate() {
+    let x = crate::from_ndarray(ndarray::Array::linspace(-1_f64, 1., 64));
+    let h = x.clone() * 3. + 1.;
+    let y = (h.clone().sigmoid() * 2. - x.clone()).tanh();
+    y.fuse();
+    y.forward();
+
+    // Intermediate results that can still be read are stored.
+    let expected_h = x.data().mapv(|x| x * 3. + 1.);
+    let expected = ndarray::Zip::from(&expected_h)
+        .and(&*x.data())
+        .map_collect(|&h, &x| (2. / (1. + (-h).exp()) - x).tanh());
+    assert_eq!(*h.data(), expected_h);
+    assert_eq!(*y.data(), expected);
+
+    // The ones whose variables are gone are not, regardless of the references to their data.
+    let g = x.clone() * 2.;
+    let data = g.data.clone();
+    let z = (g + 1.).exp();
+    z.fuse();
+    z.forward();
+    assert!(data.borrow().iter().all(|&el| el == 0.));
+    assert_eq!(*z.data(), x.data().mapv(|x| (x * 2. + 1.).exp()));
+}
+
+#[test]
+fn gradcheck() {
+    let w = ndarray::Array::from_shape_fn((3, 2), |(i, j)| (i + 2 * j) as f64 / 5. - 0.4);
+    let x = crate::from_ndarray(ndarray::array![[0.5, -1., 2.], [1.5, 0.2, -0.3]]);
+    let w = crate::from_ndarray(w).requires_grad();
+    let b = crate::from_ndarray(ndarray::array![0.1, -0.2]).requires_grad();
+    let inputs: [crate::Parameter<f64>; 2] = [w.clone().into(), b.clone().into()];
+    let data = w.data().clone();
+
+    let f = || {
+        (x.clone().mm(w.clone()) + b.clone())
+            .sigmoid()
+            .pow(3)
+            .mean()
+    };
+    assert_eq!(crate::gradcheck(f, &inputs, 1e-6, 1e-7, 1e-5), Ok(()));
+    assert_eq!(crate::gradgradcheck(f, &inputs, 1e-4, 1e-5, 1e-3), Ok(()));
+    assert_eq!(*w.data(), data);
+
+    // A hook doubling the gradient of an intermediate node breaks the gradient of both inputs.
+    let wrong = || {
+        let h = x.clone().mm(w.clone()) + b.clone();
+        h.register_hook(|grad| grad.mapv_inplace(|el| el * 2.));
+        h.tanh().sum()
+    };
+    let mismatches = crate::gradcheck(wrong, &inputs, 1e-6, 1e-7, 1e-5).unwrap_err();
+    assert_eq!(mismatches.len(), 2);
+    assert_eq!(mismatches[0].input(), 0);
+    assert_eq!(mismatches[0].index().len(), 2);
+    assert_eq!(mismatches[1].input(), 1);
+    assert!(mismatches
+        .iter()
+        .all(|mismatch| (mismatch.analytical() - 2. * mismatch.numerical()).abs() < 1e-6));
+    assert_eq!(*w.data(), data);
+}
+
+#[test]
+#[should_panic(
+    expected = "error: the forward pass of Logn with shape [3], at position 1 of the tape, produced \
+                infinite or NaN values."
+)]
+fn anomaly_forward() {
+    crate::set_anomaly_detection(true);
+
+    let x = crate::from_ndarray(ndarray::array![1., 0., 2.]).requires_grad();
+    let y = ((x * 2.).ln() * 2.).exp().sum();
+    y.forward();
+}
+
+#[test]
+#[should_panic(
+    expected = "error: the backward pass of Sqrt with shape [3], at position 0 of the tape, \
+                produced infinite or NaN values."
+)]
+fn anomaly_backward() {
+    crate::set_anomaly_detection(true);
+
+    let x = crate::from_ndarray(ndarray::array![1., 0., 4.]).requires_grad();
+    let y = (x.sqrt() * 2.).sum();
+    y.fuse();
+    y.forward();
+    assert_eq!(*y.data(), ndarray::arr0(6.));
+    y.backward(1.);
+}
+
+#[test]
+fn anomaly_disabled() {
+    assert!(!crate::is_anomaly_detection_enabled());
+
+    let x = crate::from_ndarray(ndarray::array![1_f32, 0., 4.]).requires_grad();
+    let y = (x.clone().sqrt() * 2.).sum();
+    y.forward();
+    y.backward(1.);
+    assert!(x.grad()[1].is_infinite());
+}
+
+#[test]
+fn shared_subexpression_history() {
+    let values = |shape: (usize, usize), step: f64| {
+        ndarray::Array::from_iter((0..shape.0 * shape.1).map(|i| (i as f64 * step).sin() * 0.5))
+            .into_shape(shape)
+            .unwrap()
+    };
+
+    // The second state depends on the first one, so that merging their histories yields many
+    // operations inserted at the same position in different tapes.
+    let wi = crate::from_ndarray(values((5, 3), 0.7)).requires_grad();
+    let wh = crate::from_ndarray(values((5, 5), 1.3)).requires_grad();
+    let inputs: [crate::Parameter<f64>; 2] = [wi.clone().into(), wh.clone().into()];
+    let x0 = crate::from_ndarray(values((2, 3), 0.3));
+    let x1 = crate::from_ndarray(values((2, 3), 0.9));
+    let states = || {
+        let h1 = x0.clone().mm_t(wi.clone()).tanh();
+        let h2 = (x1.clone().mm_t(wi.clone()) + h1.clone().mm_t(wh.clone())).tanh();
+        (h1, h2)
+    };
+
+    let f = || {
+        let (h1, h2) = states();
+        h1 + h2
+    };
+    assert_eq!(crate::gradcheck(f, &inputs, 1e-6, 1e-7, 1e-5), Ok(()));
+
+    let f = || {
+        let (h1, h2) = states();
+        h1.stack(&[h2], 0)
+    };
+    assert_eq!(crate::gradcheck(f, &inputs, 1e-6, 1e-7, 1e-5), Ok(()));
+
+    // Identical graphs must accumulate their gradients in the same order.
+    let gradients = || {
+        let wi = crate::from_ndarray(values((5, 3), 0.7)).requires_grad();
+        let wh = crate::from_ndarray(values((5, 5), 1.3)).requires_grad();
+        let h1 = x0.clone().mm_t(wi.clone()).tanh();
+        let h2 = (x1.clone().mm_t(wi.clone()) + h1.clone().mm_t(wh.clone())).tanh();
+        let y = (h1.clone() + h2.clone() + h1 * h2).sum();
+        y.forward();
+        y.backward(1.);
+
+        let gradients = (wi.grad().clone(), wh.grad().clone());
+        gradients
+    };
+    assert_eq!(gradients(), gradients());
+}
+
+#[cfg(feature = "sync")]
+#[test]
+fn thread_safe() {
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    assert_send_sync::<crate::Var<ndarray::Ix2>>();
+    assert_send_sync::<crate::VarDiff<ndarray::Ix2, f64>>();
+    assert_send_sync::<crate::HookHandle>();
+
+    let w = crate::from_ndarray(ndarray::array![[1., 2.], [3., 4.]]).requires_grad();
+    let inputs = [
+        ndarray::array![[1., 0.], [0., 1.]],
+        ndarray::array![[2., 1.], [1., 2.]],
+    ];
+
+    // Gradients computed concurrently on worker threads, each one with its own replica.
+    let handles: Vec<_> = inputs
+        .iter()
+        .cloned()
+        .map(|input| {
+            let w = crate::from_ndarray(w.data().clone()).requires_grad();
+            std::thread::spawn(move || {
+                let y = crate::from_ndarray(input).mm(w.clone()).sum();
+                y.forward();
+                y.backward(1.);
+                let grad = w.grad().clone();
+                (y.item(), grad)
+            })
+        })
+        .collect();
+
+    for (input, handle) in inputs.iter().zip(handles) {
+        let (item, grad) = handle.join().unwrap();
+        let y = crate::from_ndarray(input.clone()).mm(w.clone()).sum();
+        y.forward();
+        w.zero_grad();
+        y.backward(1.);
+        assert_eq!(y.item(), item);
+        assert_eq!(*w.grad(), grad);
+    }
+
+    // A model served concurrently from several threads.
+    let w = w.detach();
+    let handles: Vec<_> = inputs
+        .iter()
+        .cloned()
+        .map(|input| {
+            let w = w.clone();
+            std::thread::spawn(move || {
+                let y = crate::from_ndarray(input).mm(w);
+                y.forward();
+                let data = y.data().clone();
+                data
+            })
+        })
+        .collect();
+
+    for (input, handle) in inputs.iter().zip(handles) {
+        assert_eq!(handle.join().unwrap(), input.dot(&*w.data()));
+    }
+}
+
+#[cfg(feature = "sync")]
+#[test]
+fn data_parallel() {
+    use crate::{DataParallel, Parameter, Replicate, Var, VarDiff};
+    use ndarray::{Ix1, Ix2};
+
+    struct Model {
+        weight: VarDiff<Ix2>,
+        bias: VarDiff<Ix1>,
+    }
+
+    impl Model {
+        fn forward(&self, input: Var<Ix2>) -> VarDiff<Ix2> {
+            input.mm(self.weight.clone()) + self.bias.clone()
+        }
+    }
+
+    impl Replicate for Model {
+        type Elem = f32;
+
+        fn replicate(&self) -> Self {
+            Self {
+                weight: crate::from_ndarray(self.weight.data().clone()).requires_grad(),
+                bias: crate::from_ndarray(self.bias.data().clone()).requires_grad(),
+            }
+        }
+
+        fn parameters(&self) -> Vec<Parameter> {
+            vec![self.weight.clone().into(), self.bias.clone().into()]
+        }
+    }
+
+    let model = Model {
+        weight: crate::from_ndarray(ndarray::array![[0.5, -1., 2.], [1.5, 0.25, -0.75]])
+            .requires_grad(),
+        bias: crate::from_ndarray(ndarray::array![0.1, -0.2, 0.3]).requires_grad(),
+    };
+    let records = ndarray::Array::from_shape_fn((7, 2), |(i, j)| (i * 2 + j) as f32 / 7. - 1.);
+    let labels = ndarray::Array::from_shape_fn((7, 3), |(i, j)| ((i + j) % 3) as f32);
+
+    // Single-threaded reference.
+    let loss = model
+        .forward(crate::from_ndarray(records.clone()))
+        .mse(crate::from_ndarray(labels.clone()), crate::Reduction::Mean);
+    loss.forward();
+    loss.backward(1.);
+    let expected = (
+        loss.item(),
+        model.weight.grad().clone(),
+        model.bias.grad().clone(),
+    );
+    model.weight.zero_grad();
+    model.bias.zero_grad();
+
+    let parallel = DataParallel::new(model, 3);
+    let loss =
+        parallel.forward_backward(records.view(), labels.view(), |model, records, labels| {
+            model.forward(records).mse(labels, crate::Reduction::Mean)
+        });
+
+    let model = parallel.module();
+    assert!((loss - expected.0).abs() < 1e-5);
+    assert!(model.weight.grad().abs_diff_eq(&expected.1, 1e-5));
+    assert!(model.bias.grad().abs_diff_eq(&expected.2, 1e-5));
+
+    // Replicas are synchronized with the updated parameters at each call.
+    model.weight.data_mut().fill(0.);
+    model.weight.zero_grad();
+    model.bias.zero_grad();
+    let loss =
+        parallel.forward_backward(records.view(), labels.view(), |model, records, labels| {
+            model.forward(records).mse(labels, crate::Reduction::Mean)
+        });
+    let reference =
+        (crate::from_ndarray(labels.clone()) - crate::from_ndarray(model.bias.data().clone())).mse(
+            crate::from_ndarray(ndarray::Array::zeros((7, 3))),
+            crate::Reduction::Mean,
+        );
+    reference.forward();
+    assert!((loss - reference.item()).abs() < 1e-5);
+
+    // Seeded steps with dropout are reproducible, whichever thread each replica runs on.
+    let dropout = |model: &Model, records: Var<Ix2>, labels: Var<Ix2>| {
+        let status = crate::cell::Rc::new(crate::cell::Cell::new(true));
+        model
+            .forward(records)
+            .dropout(0.5, status)
+            .mse(labels, crate::Reduction::Mean)
+    };
+    let steps: Vec<_> = [7, 7, 8]
+        .into_iter()
+        .map(|seed| {
+            crate::manual_seed(seed);
+            model.weight.zero_grad();
+            model.bias.zero_grad();
+            let loss = parallel.forward_backward(records.view(), labels.view(), dropout);
+
+            (loss, model.weight.grad().clone(), model.bias.grad().clone())
+        })
+        .collect();
+    assert_eq!(steps[0], steps[1]);
+    assert_ne!(steps[0], steps[2]);
+}
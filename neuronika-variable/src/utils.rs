@@ -1,8 +1,11 @@
-use std::{cell::RefCell, rc::Rc};
-
 use ndarray::{
-    Array, ArrayBase, ArrayViewD, ArrayViewMutD, Axis, Data, DataMut, DimMax, Dimension, Ix1, Ix2,
-    Ix3, ShapeBuilder, Slice,
+    concatenate, stack, Array, ArrayBase, ArrayD, ArrayView, ArrayViewD, ArrayViewMutD, Axis, Data,
+    DataMut, DimMax, Dimension, Ix1, Ix2, Ix3, Ix4, IxDyn, RemoveAxis, ShapeBuilder, Slice,
+};
+
+use crate::{
+    cell::{Rc, RefCell},
+    Float,
 };
 
 /// Shorthand for `Rc<RefCell<T>>`.
@@ -71,7 +74,12 @@ where
     let padding = padding.slice();
 
     // Checks that the number of spatial dimension and input dimensions is the same.
-    assert!(shape.len() - 2 == padding.len());
+    assert!(
+        shape.len() == padding.len() + 2,
+        "error: padding of {} spatial dimensions applied to an input of shape {:?}.",
+        padding.len(),
+        shape
+    );
 
     let mut padded_input_shape = D::zeros(shape.len());
     padded_input_shape[0] = shape[0]; // Copy batch size.
@@ -87,6 +95,299 @@ where
     padded_input_shape
 }
 
+/// Concatenates `arrays` along `axis`.
+///
+/// # Panics
+///
+/// If the arrays have different dimensionalities, if `axis` is out of bounds or if their shapes
+/// differ along any other axis.
+pub(crate) fn concatenate_checked<D, T>(axis: usize, arrays: &[ArrayView<T, D>]) -> Array<T, D>
+where
+    D: RemoveAxis,
+    T: Clone,
+{
+    concatenate(Axis(axis), arrays).unwrap_or_else(|_| {
+        panic!(
+            "error: variables of shapes {:?} cannot be concatenated along axis {}.",
+            arrays.iter().map(|array| array.shape()).collect::<Vec<_>>(),
+            axis
+        )
+    })
+}
+
+/// Stacks `arrays` along the new axis `axis`.
+///
+/// # Panics
+///
+/// If the arrays have different shapes or if `axis` is out of bounds.
+pub(crate) fn stack_checked<D, T>(axis: usize, arrays: &[ArrayView<T, D>]) -> Array<T, D::Larger>
+where
+    D: Dimension,
+    T: Clone,
+{
+    stack(Axis(axis), arrays).unwrap_or_else(|_| {
+        panic!(
+            "error: variables of shapes {:?} cannot be stacked along axis {}.",
+            arrays.iter().map(|array| array.shape()).collect::<Vec<_>>(),
+            axis
+        )
+    })
+}
+
+/// Returns the number of rows and columns of the batch of matrices of shape `shape`.
+///
+/// # Panics
+///
+/// If `shape` has less than two dimensions or if `square` is `true` and the matrices are not
+/// square.
+pub(crate) fn matrices_shape<D>(shape: &D, square: bool) -> (usize, usize)
+where
+    D: Dimension,
+{
+    let shape = shape.slice();
+    assert!(
+        shape.len() >= 2,
+        "error: variable of shape {:?} is not a batch of matrices.",
+        shape
+    );
+
+    let (rows, columns) = (shape[shape.len() - 2], shape[shape.len() - 1]);
+    assert!(
+        !square || rows == columns,
+        "error: variable of shape {:?} is not a batch of square matrices.",
+        shape
+    );
+
+    (rows, columns)
+}
+
+/// Returns the shape of the batch of square matrices of shape `shape`.
+///
+/// # Panics
+///
+/// If `shape` is not a batch of square matrices.
+pub(crate) fn batch_shape<D>(shape: D) -> <D::Smaller as Dimension>::Smaller
+where
+    D: RemoveAxis,
+    D::Smaller: RemoveAxis,
+{
+    matrices_shape(&shape, true);
+
+    let ndim = shape.ndim();
+    shape
+        .remove_axis(Axis(ndim - 1))
+        .remove_axis(Axis(ndim - 2))
+}
+
+/// Returns the shapes of the factors of the reduced QR decomposition of the batch of matrices of
+/// shape `shape`.
+///
+/// # Panics
+///
+/// If `shape` is not a batch of matrices.
+pub(crate) fn qr_shapes<D>(shape: &D) -> (D, D)
+where
+    D: Dimension,
+{
+    let (rows, columns) = matrices_shape(shape, false);
+    let k = rows.min(columns);
+
+    let ndim = shape.ndim();
+    let (mut q_shape, mut r_shape) = (shape.clone(), shape.clone());
+    q_shape[ndim - 1] = k;
+    r_shape[ndim - 2] = k;
+
+    (q_shape, r_shape)
+}
+
+/// Returns the shapes of the factors of the reduced singular value decomposition of the batch of
+/// matrices of shape `shape`.
+///
+/// # Panics
+///
+/// If `shape` is not a batch of matrices.
+pub(crate) fn svd_shapes<D>(shape: &D) -> (D, D::Smaller, D)
+where
+    D: RemoveAxis,
+{
+    let (u_shape, vt_shape) = qr_shapes(shape);
+
+    let ndim = shape.ndim();
+    let mut s_shape = shape.remove_axis(Axis(ndim - 1));
+    s_shape[ndim - 2] = u_shape[ndim - 1];
+
+    (u_shape, s_shape, vt_shape)
+}
+
+/// Returns the shape of a variable of shape `shape` once broadcast to `target`, together with the
+/// shape the variable must be viewed with to be broadcast to the result.
+///
+/// # Panics
+///
+/// If `shape` cannot be broadcast to `target`.
+pub(crate) fn broadcast_shapes<D, E>(shape: &D, target: E) -> (E, IxDyn, IxDyn)
+where
+    D: Dimension,
+    E: Dimension,
+{
+    let (shape, target_shape) = (shape.slice(), target.slice());
+    assert!(
+        shape.len() <= target_shape.len()
+            && shape
+                .iter()
+                .rev()
+                .zip(target_shape.iter().rev())
+                .all(|(&len, &target_len)| len == target_len || len == 1),
+        "error: variable of shape {:?} cannot be broadcast to shape {:?}.",
+        shape,
+        target_shape
+    );
+
+    let mut operand_shape = vec![1; target_shape.len() - shape.len()];
+    operand_shape.extend_from_slice(shape);
+    let blocks = IxDyn(target_shape);
+
+    (target, IxDyn(&operand_shape), blocks)
+}
+
+/// Returns the shape of a variable of shape `shape` once tiled `reps` times along each axis,
+/// together with the shapes the variable and the result must be viewed with for the former to be
+/// broadcast to the latter.
+///
+/// # Panics
+///
+/// If `reps` and `shape` have a different number of dimensions.
+pub(crate) fn tiled_shapes<D>(shape: &D, reps: &D) -> (D, IxDyn, IxDyn)
+where
+    D: Dimension,
+{
+    assert_eq!(
+        shape.ndim(),
+        reps.ndim(),
+        "error: {} repetitions given for a variable of {} dimensions.",
+        reps.ndim(),
+        shape.ndim()
+    );
+
+    let mut tiled = shape.clone();
+    tiled
+        .slice_mut()
+        .iter_mut()
+        .zip(reps.slice())
+        .for_each(|(len, &rep)| *len *= rep);
+    let operand_shape = shape.slice().iter().flat_map(|&len| [1, len]);
+    let blocks = reps
+        .slice()
+        .iter()
+        .zip(shape.slice())
+        .flat_map(|(&rep, &len)| [rep, len]);
+
+    (
+        tiled,
+        IxDyn(&operand_shape.collect::<Vec<_>>()),
+        IxDyn(&blocks.collect::<Vec<_>>()),
+    )
+}
+
+/// Returns the shape of a variable of shape `shape` once each of its elements is repeated
+/// `repeats` times along `axis`, together with the shapes the variable and the result must be
+/// viewed with for the former to be broadcast to the latter.
+///
+/// # Panics
+///
+/// If `axis` is out of bounds.
+pub(crate) fn interleaved_shapes<D>(shape: &D, repeats: usize, axis: usize) -> (D, IxDyn, IxDyn)
+where
+    D: Dimension,
+{
+    assert!(
+        axis < shape.ndim(),
+        "error: axis {} is out of bounds.",
+        axis
+    );
+
+    let mut interleaved = shape.clone();
+    interleaved[axis] *= repeats;
+    let mut operand_shape = shape.slice().to_vec();
+    operand_shape.insert(axis + 1, 1);
+    let mut blocks = operand_shape.clone();
+    blocks[axis + 1] = repeats;
+
+    (interleaved, IxDyn(&operand_shape), IxDyn(&blocks))
+}
+
+/// Checks that `axes` is a permutation of the axes of a variable of `ndim` dimensions.
+///
+/// # Panics
+///
+/// If `axes` is not a permutation of `0..ndim`.
+pub(crate) fn check_permutation<D>(ndim: usize, axes: &D)
+where
+    D: Dimension,
+{
+    let mut seen = vec![false; ndim];
+    let is_permutation = axes.ndim() == ndim
+        && axes
+            .slice()
+            .iter()
+            .all(|&axis| axis < ndim && !std::mem::replace(&mut seen[axis], true));
+
+    assert!(
+        is_permutation,
+        "error: {:?} is not a permutation of the axes of a variable of {} dimensions.",
+        axes.slice(),
+        ndim
+    );
+}
+
+/// Returns the permutation of the axes of a variable of `ndim` dimensions that swaps `a` and `b`.
+///
+/// # Panics
+///
+/// If either `a` or `b` is out of bounds.
+pub(crate) fn swapped_axes<D>(ndim: usize, a: usize, b: usize) -> D
+where
+    D: Dimension,
+{
+    for axis in [a, b] {
+        assert!(axis < ndim, "error: axis {} is out of bounds.", axis);
+    }
+
+    let mut axes = D::zeros(ndim);
+    axes.slice_mut()
+        .iter_mut()
+        .enumerate()
+        .for_each(|(position, axis)| *axis = position);
+    axes.slice_mut().swap(a, b);
+
+    axes
+}
+
+/// Returns the shapes of the result and of the attention weights of the scaled dot-product
+/// attention of queries, keys and values of shapes `query`, `key` and `value`.
+///
+/// # Panics
+///
+/// If the shapes are not those of queries, keys and values of the same samples and heads.
+pub(crate) fn attention_shapes(query: Ix4, key: Ix4, value: Ix4) -> (Ix4, Ix4) {
+    let (batch_size, heads, length, features) = query.into_pattern();
+    let (key_batch_size, key_heads, key_length, key_features) = key.into_pattern();
+    assert!(
+        (key_batch_size, key_heads, key_features) == (batch_size, heads, features)
+            && (value[0], value[1], value[2]) == (batch_size, heads, key_length),
+        "error: cannot attend with queries of shape {:?}, keys of shape {:?} and values of shape \
+         {:?}.",
+        query.slice(),
+        key.slice(),
+        value.slice()
+    );
+
+    (
+        Ix4(batch_size, heads, length, value[3]),
+        Ix4(batch_size, heads, length, key_length),
+    )
+}
+
 /// Computes the result of broadcasting between `left` and `right`.
 ///
 /// # Arguments
@@ -132,63 +433,67 @@ where
 /// * `left` - left operand in the binary operations that admits broadcasting.
 ///
 /// * `right` - right operand in the binary operations that admits broadcasting.
-pub(crate) fn cobroadcasted_zeros<D, E>(
-    left: &Array<f32, D>,
-    right: &Array<f32, E>,
-) -> Array<f32, Broadcast<D, E>>
+pub(crate) fn cobroadcasted_zeros<D, E, T>(
+    left: &Array<T, D>,
+    right: &Array<T, E>,
+) -> Array<T, Broadcast<D, E>>
 where
     D: Dimension + DimMax<E>,
     E: Dimension,
+    T: Float,
 {
     Array::zeros(cobroadcast(left.raw_dim(), right.raw_dim()))
 }
 
 /// Accumulates `source` into `target`, reverting the broadcasting.
 ///
+/// The leading axes of `source` that are missing in `target` and those along which `target` has
+/// length one are summed over.
+///
 /// ## Arguments
 ///
 /// * `source` - Tensor to reduce.
 /// * `target` - Tensor in which the accumulation must be pushed.
-pub(crate) fn accumulate<D, E>(target: &mut Array<f32, D>, source: &Array<f32, E>)
+pub(crate) fn accumulate<S, U, D, E, T>(target: &mut ArrayBase<S, D>, source: &ArrayBase<U, E>)
 where
+    S: DataMut<Elem = T>,
+    U: Data<Elem = T>,
     D: Dimension,
     E: Dimension,
+    T: Float,
 {
-    let source_dims = source.ndim();
-    let target_dims = target.ndim();
-
-    debug_assert!(target_dims <= source_dims);
+    debug_assert!(target.ndim() <= source.ndim());
 
     if source.shape() == target.shape() {
         *target += source;
         return;
     }
 
-    // Computes the difference between the number of dimensions
-    let source_shape = source.shape();
-    let k = source_shape.len() - target_dims;
-    let mut reshape = D::Larger::zeros(target_dims + 1);
-    reshape[0] = source_shape[..k].iter().product();
-    reshape
-        .slice_mut()
-        .iter_mut()
-        .skip(1)
-        .zip(source_shape.iter().skip(k))
-        .for_each(|(r, &s)| *r = s);
-
-    // Reshapes such that the sub-views match the dimensionality of target
-    let view = source.view().into_shape(reshape).unwrap();
-    let mut lanes_v = view.lanes(Axis(source_dims - 1)).into_iter();
-    while lanes_v.len() > 0 {
-        for mut lane_t in target.lanes_mut(Axis(target_dims - 1)) {
-            if let Some(lane_v) = lanes_v.next() {
-                if lane_t.len() == 1 {
-                    lane_t[0] += lane_v.sum();
-                } else {
-                    lane_t += &lane_v;
-                }
-            }
-        }
+    // The leading axes of the source are summed away, the ones broadcast along by the target are
+    // summed keeping them. The first sum reads the source in place.
+    let k = source.ndim() - target.ndim();
+    let source = source.view().into_dyn();
+    let axes = std::iter::repeat_n((Axis(0), false), k).chain(
+        target
+            .shape()
+            .iter()
+            .enumerate()
+            .filter(|&(axis, &len)| len == 1 && source.len_of(Axis(k + axis)) != 1)
+            .map(|(axis, _)| (Axis(axis), true)),
+    );
+
+    let mut reduced: Option<ArrayD<T>> = None;
+    for (axis, keep) in axes {
+        let sum = match &reduced {
+            Some(reduced) => reduced.sum_axis(axis),
+            None => source.sum_axis(axis),
+        };
+        reduced = Some(if keep { sum.insert_axis(axis) } else { sum });
+    }
+
+    match reduced {
+        Some(reduced) => *target += &reduced,
+        None => *target += &source,
     }
 }
 
@@ -247,7 +552,7 @@ where
 /// * `stride` - stride.
 ///
 /// * `dilation` - spacing between each element of the windows.
-fn compute_rolling_window_shape<D, S>(
+fn compute_rolling_window_shape<D, S, T>(
     input: &ArrayBase<S, D>,
     window_shape: &[usize],
     stride: &[usize],
@@ -255,7 +560,8 @@ fn compute_rolling_window_shape<D, S>(
 ) -> Vec<usize>
 where
     D: Dimension,
-    S: Data<Elem = f32>,
+    S: Data<Elem = T>,
+    T: Float,
 {
     let mut indices: D = conv_out_shape(input.shape(), window_shape, stride, dilation);
     indices[1] = 1;
@@ -276,14 +582,15 @@ where
 /// * `stride` - stride.
 ///
 /// * `dilation` - spacing between each element of the windows.
-fn compute_rolling_window_strides<D, S>(
+fn compute_rolling_window_strides<D, S, T>(
     input: &ArrayBase<S, D>,
     stride: &[usize],
     dilation: &[usize],
 ) -> Vec<usize>
 where
     D: Dimension,
-    S: Data<Elem = f32>,
+    S: Data<Elem = T>,
+    T: Float,
 {
     let indexing_strides: Vec<isize> = {
         let view = input.slice_each_axis(|ax| {
@@ -330,15 +637,16 @@ where
 /// * `stride` - stride.
 ///
 /// * `dilation` - spacing between each element of the windows.
-pub(crate) fn as_windows<'a, D, S>(
+pub(crate) fn as_windows<'a, D, S, T>(
     input: &ArrayBase<S, D>,
     window_shape: &[usize],
     stride: &[usize],
     dilation: &[usize],
-) -> ArrayViewD<'a, f32>
+) -> ArrayViewD<'a, T>
 where
     D: Dimension,
-    S: Data<Elem = f32>,
+    S: Data<Elem = T>,
+    T: Float,
 {
     let rolling_window_shape: Vec<usize> =
         compute_rolling_window_shape(input, window_shape, stride, dilation);
@@ -364,15 +672,16 @@ where
 /// * `stride` - stride.
 ///
 /// * `dilation` - spacing between each element of the windows.
-pub(crate) fn as_windows_mut<'a, D, S>(
+pub(crate) fn as_windows_mut<'a, D, S, T>(
     input: &mut ArrayBase<S, D>,
     window_shape: &[usize],
     stride: &[usize],
     dilation: &[usize],
-) -> ArrayViewMutD<'a, f32>
+) -> ArrayViewMutD<'a, T>
 where
     D: Dimension,
-    S: DataMut<Elem = f32>,
+    S: DataMut<Elem = T>,
+    T: Float,
 {
     let rolling_window_shape: Vec<usize> =
         compute_rolling_window_shape(input, window_shape, stride, dilation);
@@ -401,7 +710,7 @@ where
 /// * `stride` - stride.
 ///
 /// * `dilation` - dilation.
-pub(crate) fn columns_shape<D, S>(
+pub(crate) fn columns_shape<D, S, T>(
     input: &ArrayBase<S, D>,
     kernel_shape: &[usize],
     stride: &[usize],
@@ -409,7 +718,8 @@ pub(crate) fn columns_shape<D, S>(
 ) -> Ix3
 where
     D: Dimension,
-    S: Data<Elem = f32>,
+    S: Data<Elem = T>,
+    T: Float,
 {
     let output_map_shape = conv_out_shape::<D>(input.shape(), kernel_shape, stride, dilation);
     let mut columns_shape = Ix3::zeros(3);
@@ -434,6 +744,12 @@ pub(crate) fn check_conv_args(
     // The type of convolution can be derived by considering the number of input's dimension
     // skipping the first two, that are the batch size and input channels. The first two axes of
     // the input are always for the batch size and the number of input channels.
+    assert!(
+        input_shape.len() > 2,
+        "error: the input of a convolution must have at least one spatial dimension, found shape \
+         {:?}.",
+        input_shape
+    );
     let convolution_dimension = input_shape.len() - 2;
 
     assert_eq!(
@@ -505,9 +821,19 @@ pub(crate) fn new_shared<T>(item: T) -> Rc<RefCell<T>> {
     Rc::new(RefCell::new(item))
 }
 
+/// Returns a variable of shape `shape` whose elements are *sin(i · frequency)*, used to weight
+/// the results of the operations under a gradient check.
+#[cfg(test)]
+pub(crate) fn weights(shape: &[usize], frequency: f64) -> crate::Var<IxDyn, f64> {
+    let len = shape.iter().product::<usize>();
+    let weights = Array::from_iter((0..len).map(|i| (i as f64 * frequency).sin()));
+
+    crate::from_ndarray(weights.into_shape(shape).unwrap())
+}
+
 #[cfg(test)]
 pub(crate) fn are_similar<D: Dimension>(
-    result: std::cell::Ref<Array<f32, D>>,
+    result: crate::cell::Ref<Array<f32, D>>,
     expected: &Array<f32, D>,
 ) -> Result<(), Box<dyn std::error::Error>> {
     if !result.abs_diff_eq(expected, F16_EPSILON) {
@@ -1,28 +1,42 @@
 use std::{
-    cell::{Cell, Ref, RefCell, RefMut},
+    collections::{HashMap, HashSet},
     fmt::{Debug, Display},
-    ops::{Add, Div, Mul, Neg, Sub},
-    rc::Rc,
+    ops::{Add, Div, Mul, Neg, Range, Sub},
 };
 
 use ndarray::{
-    arr0, concatenate, stack, Array, Axis, DimMax, Dimension, IntoDimension, Ix0, Ix1, Ix2,
-    RemoveAxis,
+    arr0, Array, Axis, DimMax, Dimension, IntoDimension, Ix0, Ix1, Ix2, Ix3, Ix4, IxDyn, RemoveAxis,
 };
 
 use crate::{
+    anomaly,
     autograd::Forward,
+    bf16,
+    cell::{Cell, Rc, Ref, RefCell, RefMut},
+    f16, fusion,
     gradient::{BufferedGradient, Gradient},
+    graph::{Buffer, Graph},
     history::History,
+    memory::{assert_data_unplanned, DataBuffer, MemoryPlan, NodeData},
     node::{self, *},
     utils::{
-        check_conv_args, check_groups_args, cobroadcasted_zeros, conv_out_shape, padded_shape,
-        DotDim, Shared,
+        attention_shapes, batch_shape, broadcast_shapes, check_conv_args, check_groups_args,
+        check_permutation, cobroadcast, cobroadcasted_zeros, concatenate_checked, conv_out_shape,
+        interleaved_shapes, matrices_shape, padded_shape, qr_shapes, stack_checked, svd_shapes,
+        swapped_axes, tiled_shapes, DotDim, Shared,
     },
     vardiff::VarDiff,
-    Cat, Convolution, MatMatMul, MatMatMulT, MatVecMul, Reduction, Stack, VecMatMul, VecVecMul,
+    AsIndex, Attention, Cat, Convolution, Element, Float, MatMatMul, MatMatMulT, MatVecMul,
+    Reduction, Solve, Stack, VecMatMul, VecVecMul, Where,
 };
 
+/// An op of the tape of a variable, together with whether it has been computed and the data of
+/// its node.
+pub(crate) type ForwardOp = (Rc<dyn Forward>, Cell<bool>, Rc<dyn NodeData>);
+
+/// The tape of a variable.
+pub(crate) type VarHistory = History<ForwardOp>;
+
 /// A non-differentiable variable.
 ///
 /// This, together with its differentiable counterpart [`VarDiff`], is the main building block of
@@ -30,41 +44,63 @@ use crate::{
 ///
 /// Conceptually, it can be thought of as a [`ndarray::Array`] for which the computations are
 /// automatically kept track of.
+///
+/// The elements are of type `T`, which defaults to `f32` and can be any type implementing
+/// [`Float`], such as `f64` or the half precision [`f16`](struct@f16) and [`bf16`]. Integer and
+/// boolean variables, holding `i64`, `usize` or `bool` elements, are used as labels, indices and
+/// masks.
 #[derive(Clone)]
-pub struct Var<D>
+pub struct Var<D, T = f32>
 where
     D: Dimension,
+    T: Element,
 {
-    pub(crate) data: Shared<Array<f32, D>>,
-    pub(crate) history: History<(Rc<dyn Forward>, Cell<bool>)>,
+    pub(crate) data: Shared<Array<T, D>>,
+    pub(crate) history: VarHistory,
+    pub(crate) fused: Rc<Cell<bool>>,
+    pub(crate) handle: Rc<()>,
 }
 
-impl<D> Var<D>
+impl<D, T> Var<D, T>
 where
     D: Dimension,
+    T: Element,
 {
-    pub(crate) fn leaf(array: Array<f32, D>) -> Self {
+    pub(crate) fn leaf(array: Array<T, D>) -> Self {
         Self {
             data: Rc::new(RefCell::new(array)),
             history: History::default(),
+            fused: Rc::default(),
+            handle: Rc::default(),
         }
     }
 
-    pub(crate) fn node(
-        data: Shared<Array<f32, D>>,
-        op: Rc<dyn Forward>,
-        mut history: History<(Rc<dyn Forward>, Cell<bool>)>,
-    ) -> Self {
-        history.insert(Rc::as_ptr(&op) as *const () as usize, (op, Cell::default()));
+    pub(crate) fn node(data: Shared<Array<T, D>>, op: Rc<dyn Forward>, history: VarHistory) -> Self
+    where
+        D: 'static,
+    {
+        let mut var = Self {
+            data,
+            history,
+            fused: Rc::default(),
+            handle: Rc::default(),
+        };
+
+        // The buffer tells whether the node can still be read through any of its variables.
+        let buffer = DataBuffer::new(var.data.clone(), &var.handle);
+        var.history.insert(
+            Rc::as_ptr(&op) as *const () as usize,
+            (op, Cell::default(), Rc::new(buffer)),
+        );
 
-        Self { data, history }
+        var
     }
 
     /// Returns an immutable reference to the data inside `self`.
     ///
     /// At the variable's creation the data is filled with zeros. You can populate it with a
     /// call to [`.forward()`](Var::forward()).
-    pub fn data(&self) -> Ref<Array<f32, D>> {
+    pub fn data(&self) -> Ref<Array<T, D>> {
         self.data.borrow()
     }
 
@@ -72,42 +108,21 @@ where
     ///
     /// At the variable's creation the data is filled with zeros. You can populate it with a
     /// call to [`.forward()`](Var::forward()).
-    pub fn data_mut(&self) -> RefMut<Array<f32, D>> {
+    pub fn data_mut(&self) -> RefMut<Array<T, D>> {
         self.data.borrow_mut()
     }
 
-    /// Promotes `self` to a differentiable variable. A subsequent call to [`.backward()`]
-    /// will compute its grad.
-    ///
-    /// [`.backward()`]: VarDiff::backward()
-    ///
-    /// # Examples
-    ///
-    /// This is the preferred usage.
-    ///
-    ///```
-    /// # use neuronika_variable as neuronika;
-    /// let x = neuronika::ones(5).requires_grad();
-    ///```
-    ///
-    /// This is also permitted, however, one should be aware of the difference between `x_diff` and
-    /// `x`.
-    ///
-    ///```
-    /// # use neuronika_variable as neuronika;
-    /// let x = neuronika::ones(5);
-    /// let y = x.clone() + neuronika::ones(1);
-    ///
-    /// let x_diff = x.requires_grad();
-    ///```
-    pub fn requires_grad(self) -> VarDiff<D> {
-        let grad = Array::zeros(self.data.borrow().raw_dim());
-        VarDiff::leaf(self, grad)
-    }
-
     /// Propagates the computations forwards and populates all the variables from the leaves of the
     /// graph to `self`.
     pub fn forward(&self) {
+        self.run_forward(None, &HashSet::new())
+    }
+
+    /// Propagates the computations forwards, allocating and de-allocating the data of the nodes
+    /// as scheduled by `plan`, if any.
+    ///
+    /// `read` holds the ids of the data read by the backward pass, which fused ops must store.
+    pub(crate) fn run_forward(&self, plan: Option<&MemoryPlan>, read: &HashSet<usize>) {
         let mut buffer = self.history.buffer_mut(); // Borrows for the scope
 
         // If the length of the buffer is greater than 0 it means that forward has already been
@@ -115,27 +130,310 @@ where
         if buffer.is_empty() {
             *buffer = self.history.to_vec()
         } else {
-            buffer.iter().for_each(|(_, computed)| computed.set(false));
+            buffer
+                .iter()
+                .for_each(|(_, computed, _)| computed.set(false));
         }
 
+        let detect_anomaly = anomaly::is_anomaly_detection_enabled();
+        if plan.is_none() {
+            assert_data_unplanned(&buffer);
+
+            if self.fused.get() && !detect_anomaly {
+                return fusion::forward::<T>(&buffer, read);
+            }
+        }
+
+        let mut pool = plan.map(MemoryPlan::take_pool).unwrap_or_default();
         buffer
             .iter()
-            .filter(|(_, computed)| !computed.get())
-            .for_each(|(op, computed)| {
-                op.forward();
-                computed.set(true)
+            .enumerate()
+            .filter(|(_, (_, computed, _))| !computed.get())
+            .for_each(|(position, op)| {
+                if let Some(plan) = plan {
+                    plan.allocate_data(position, &buffer, &mut pool);
+                }
+
+                op.0.forward();
+                op.1.set(true);
+
+                if detect_anomaly {
+                    anomaly::check_forward(position, op);
+                }
+
+                if let Some(plan) = plan {
+                    plan.release_data(position, &buffer, &mut pool);
+                }
             });
+
+        if let Some(plan) = plan {
+            plan.keep_pool(pool);
+        }
+    }
+
+    /// Enables the fusion of the element-wise operations of the computational graph of `self`.
+    ///
+    /// Once enabled, the consecutive element-wise operations of the forward pass, such as
+    /// additions, multiplications, negations and activation functions, are computed in a single
+    /// pass over the data, one tile of elements at a time, rather than in a pass per operation.
+    /// Intermediate results are stored only if they are observable: if a variable of their node
+    /// is alive, or if they are read by the backward pass or by an operation that is not fused.
+    /// Operations involving broadcasting, other than by scalars, or arrays not in standard layout
+    /// are not fused. The results are exactly the same as the ones of the unfused operations.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use neuronika_variable as neuronika;
+    /// let x = neuronika::rand((100, 100));
+    /// let y = ((x.clone() * 3. + 1.).sigmoid() * 2. - 1.).tanh();
+    ///
+    /// y.fuse();
+    /// y.forward();
+    ///
+    /// let expected = x.data().mapv(|x| (2. / (1. + (-(x * 3. + 1.)).exp()) - 1.).tanh());
+    /// assert!(y.data().abs_diff_eq(&expected, 1e-6));
+    /// ```
+    pub fn fuse(&self) {
+        self.fused.set(true);
+    }
+
+    /// Returns the computational graph of `self` in the [Graphviz](https://graphviz.org/) DOT
+    /// language.
+    ///
+    /// Each operation is labelled with its name and the shape of its result, leaves are drawn as
+    /// ellipses.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use neuronika_variable as neuronika;
+    /// let x = neuronika::ones((2, 3));
+    /// let y = x.clone() + x;
+    ///
+    /// assert!(y.to_dot().starts_with("digraph"));
+    /// ```
+    pub fn to_dot(&self) -> String {
+        self.graph().to_dot()
+    }
+
+    /// Returns a read-only snapshot of the computational graph of `self`.
+    ///
+    /// See [`Graph`] for an example.
+    pub fn graph(&self) -> Graph {
+        self.graph_with(&HashMap::new())
+    }
+
+    /// Returns the computational graph of `self`.
+    ///
+    /// # Arguments
+    ///
+    /// `gradients` - sizes in bytes of the gradients, indexed by the id of the corresponding
+    /// buffers.
+    pub(crate) fn graph_with(&self, gradients: &HashMap<usize, usize>) -> Graph {
+        if self.history.len() == 0 {
+            return Graph::leaf(Buffer::new(&self.data), gradients);
+        }
+
+        Graph::new(self.history.to_vec().iter().map(|(op, _, _)| op), gradients)
     }
 }
 
-impl Var<Ix0> {
+impl<T> Var<Ix0, T>
+where
+    T: Element,
+{
     /// Returns the scalar contained in the variable.
-    pub fn item(&self) -> f32 {
+    pub fn item(&self) -> T {
         self.data()[()]
     }
 }
 
-impl Var<Ix1> {
+impl<D, T> Var<D, T>
+where
+    D: 'static + Dimension,
+    T: Element,
+{
+    fn compare<E>(
+        mut self,
+        rhs: Var<E, T>,
+        kind: ComparisonKind,
+    ) -> Var<<D as DimMax<E>>::Output, bool>
+    where
+        D: DimMax<E>,
+        E: 'static + Dimension,
+    {
+        self.history.merge(rhs.history);
+
+        let shape = cobroadcast(self.data.borrow().raw_dim(), rhs.data.borrow().raw_dim());
+        let data = Rc::new(RefCell::new(Array::from_elem(shape, false)));
+        let op = Comparison::new(self.data, rhs.data, data.clone(), kind);
+
+        Var::node(data, Rc::new(op), self.history)
+    }
+
+    /// Computes *self > rhs* element-wise and returns a boolean mask with the result.
+    ///
+    /// The operands are broadcast together.
+    pub fn gt<E>(self, rhs: Var<E, T>) -> Var<<D as DimMax<E>>::Output, bool>
+    where
+        D: DimMax<E>,
+        E: 'static + Dimension,
+    {
+        self.compare(rhs, ComparisonKind::Greater)
+    }
+
+    /// Computes *self >= rhs* element-wise and returns a boolean mask with the result.
+    ///
+    /// The operands are broadcast together.
+    pub fn ge<E>(self, rhs: Var<E, T>) -> Var<<D as DimMax<E>>::Output, bool>
+    where
+        D: DimMax<E>,
+        E: 'static + Dimension,
+    {
+        self.compare(rhs, ComparisonKind::GreaterEqual)
+    }
+
+    /// Computes *self < rhs* element-wise and returns a boolean mask with the result.
+    ///
+    /// The operands are broadcast together.
+    pub fn lt<E>(self, rhs: Var<E, T>) -> Var<<D as DimMax<E>>::Output, bool>
+    where
+        D: DimMax<E>,
+        E: 'static + Dimension,
+    {
+        self.compare(rhs, ComparisonKind::Less)
+    }
+
+    /// Computes *self <= rhs* element-wise and returns a boolean mask with the result.
+    ///
+    /// The operands are broadcast together.
+    pub fn le<E>(self, rhs: Var<E, T>) -> Var<<D as DimMax<E>>::Output, bool>
+    where
+        D: DimMax<E>,
+        E: 'static + Dimension,
+    {
+        self.compare(rhs, ComparisonKind::LessEqual)
+    }
+
+    /// Computes *self == rhs* element-wise and returns a boolean mask with the result.
+    ///
+    /// The operands are broadcast together.
+    pub fn eq<E>(self, rhs: Var<E, T>) -> Var<<D as DimMax<E>>::Output, bool>
+    where
+        D: DimMax<E>,
+        E: 'static + Dimension,
+    {
+        self.compare(rhs, ComparisonKind::Equal)
+    }
+
+    /// Computes *self != rhs* element-wise and returns a boolean mask with the result.
+    ///
+    /// The operands are broadcast together.
+    pub fn ne<E>(self, rhs: Var<E, T>) -> Var<<D as DimMax<E>>::Output, bool>
+    where
+        D: DimMax<E>,
+        E: 'static + Dimension,
+    {
+        self.compare(rhs, ComparisonKind::NotEqual)
+    }
+
+    /// Converts the variable into one with a dynamic number of dimensions.
+    ///
+    /// The result is still connected to `self` in the computational graph.
+    pub fn into_dyn(self) -> Var<IxDyn, T> {
+        self.into_dimensionality()
+    }
+
+    /// Converts the variable into one of dimensionality `E`.
+    ///
+    /// The result is still connected to `self` in the computational graph.
+    ///
+    /// # Panics
+    ///
+    /// If the number of dimensions of the variable doesn't match the one of `E`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use neuronika_variable as neuronika;
+    /// use ndarray::{Ix2, IxDyn};
+    ///
+    /// let x = neuronika::ones(IxDyn(&[2, 3]));
+    /// let y = x.into_dimensionality::<Ix2>();
+    ///
+    /// y.forward();
+    /// assert_eq!(*y.data(), ndarray::Array::ones((2, 3)));
+    /// ```
+    pub fn into_dimensionality<E>(self) -> Var<E, T>
+    where
+        E: 'static + Dimension,
+    {
+        let shape = {
+            let shape = self.data.borrow().raw_dim();
+            E::from_dimension(&shape).unwrap_or_else(|| {
+                panic!(
+                    "error: variable of shape {:?} cannot be converted into one of {} dimensions.",
+                    shape.slice(),
+                    E::NDIM.unwrap()
+                )
+            })
+        };
+        let data = Rc::new(RefCell::new(Array::from_elem(shape, T::default())));
+        let op = IntoDimensionality::new(self.data, data.clone());
+
+        Var::node(data, Rc::new(op), self.history)
+    }
+
+    /// Lays out the elements of the variable, in logical order, into one of shape `shape`.
+    ///
+    /// The result is still connected to `self` in the computational graph.
+    ///
+    /// # Arguments
+    ///
+    /// `shape` - shape of the result.
+    ///
+    /// # Panics
+    ///
+    /// If `shape` doesn't have the same number of elements as the variable.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use neuronika_variable as neuronika;
+    /// let x = neuronika::from_ndarray(ndarray::array![[1., 2., 3.], [4., 5., 6.]]);
+    /// let y = x.reshape((3, 2));
+    ///
+    /// y.forward();
+    /// assert_eq!(*y.data(), ndarray::array![[1., 2.], [3., 4.], [5., 6.]]);
+    /// ```
+    pub fn reshape<S>(self, shape: S) -> Var<S::Dim, T>
+    where
+        S: IntoDimension,
+        S::Dim: 'static,
+    {
+        let shape = shape.into_dimension();
+        {
+            let data = self.data.borrow();
+            assert_eq!(
+                data.len(),
+                shape.size(),
+                "error: variable of shape {:?} cannot be reshaped into {:?}.",
+                data.shape(),
+                shape.slice()
+            );
+        }
+        let data = Rc::new(RefCell::new(Array::from_elem(shape, T::default())));
+        let op = Reshape::new(self.data, data.clone());
+
+        Var::node(data, Rc::new(op), self.history)
+    }
+}
+
+impl<T> Var<Ix1, T>
+where
+    T: Float,
+{
     /// Performs a vector-matrix multiplication between the vector variable `self` and the matrix
     /// variable `rhs`.
     ///
@@ -158,7 +456,10 @@ impl Var<Ix1> {
     }
 }
 
-impl Var<Ix2> {
+impl<T> Var<Ix2, T>
+where
+    T: Float,
+{
     /// Performs a matrix multiplication between the matrix variables `self` and `rhs`. If `self`
     /// is *(n, m)* and `rhs` is *(m, o)* the output will be *(n, o)*.
     pub fn mm<Rhs>(self, rhs: Rhs) -> <Self as MatMatMul<Rhs>>::Output
@@ -193,21 +494,187 @@ impl Var<Ix2> {
     }
 }
 
-impl<D> Var<D>
+impl<T> Var<Ix4, T>
+where
+    T: Float,
+{
+    /// Rearranges the elements of a variable of shape *(N, C × r², H, W)* into one of shape
+    /// *(N, C, H × r, W × r)*, where *r* is `upscale_factor`, and returns it.
+    ///
+    /// # Arguments
+    ///
+    /// * `upscale_factor` - factor to increase the spatial size by.
+    ///
+    /// # Panics
+    ///
+    /// If `upscale_factor` is zero or if the number of channels is not divisible by its square.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use neuronika_variable as neuronika;
+    /// let x = neuronika::zeros((1, 8, 3, 3));
+    /// let y = x.pixel_shuffle(2);
+    ///
+    /// assert_eq!(y.data().shape(), &[1, 2, 6, 6]);
+    /// ```
+    pub fn pixel_shuffle(self, upscale_factor: usize) -> Var<Ix4, T> {
+        let (n, c, h, w) = self.data.borrow().dim();
+        let squared = upscale_factor * upscale_factor;
+        assert!(
+            upscale_factor > 0 && c % squared == 0,
+            "error: {} channels cannot be shuffled with an upscale factor of {}.",
+            c,
+            upscale_factor
+        );
+        let shape = (n, c / squared, h * upscale_factor, w * upscale_factor);
+
+        self.shuffled(shape.into_dimension(), upscale_factor, false)
+    }
+
+    /// Rearranges the elements of a variable of shape *(N, C, H × r, W × r)* into one of shape
+    /// *(N, C × r², H, W)*, where *r* is `downscale_factor`, and returns it. This is the inverse of
+    /// [`.pixel_shuffle()`](Var::pixel_shuffle()).
+    ///
+    /// # Arguments
+    ///
+    /// * `downscale_factor` - factor to decrease the spatial size by.
+    ///
+    /// # Panics
+    ///
+    /// If `downscale_factor` is zero or if the spatial size is not divisible by it.
+    pub fn pixel_unshuffle(self, downscale_factor: usize) -> Var<Ix4, T> {
+        let (n, c, h, w) = self.data.borrow().dim();
+        assert!(
+            downscale_factor > 0 && h % downscale_factor == 0 && w % downscale_factor == 0,
+            "error: spatial size ({}, {}) is not divisible by the downscale factor {}.",
+            h,
+            w,
+            downscale_factor
+        );
+        let shape = (
+            n,
+            c * downscale_factor * downscale_factor,
+            h / downscale_factor,
+            w / downscale_factor,
+        );
+
+        self.shuffled(shape.into_dimension(), downscale_factor, true)
+    }
+
+    pub(crate) fn shuffled(self, shape: Ix4, factor: usize, unshuffle: bool) -> Var<Ix4, T> {
+        let data = Rc::new(RefCell::new(Array::zeros(shape)));
+        let op = PixelShuffle::new(self.data, data.clone(), factor, unshuffle);
+
+        Var::node(data, Rc::new(op), self.history)
+    }
+
+    #[allow(clippy::type_complexity)]
+    pub(crate) fn attention(
+        mut self,
+        key: Var<Ix4, T>,
+        value: Var<Ix4, T>,
+        mask: Option<AttentionMask<T>>,
+        dropout: (f64, Rc<Cell<bool>>, Option<DropoutState>),
+        causal: bool,
+        logsumexp: Shared<Array<T, Ix3>>,
+    ) -> Var<Ix4, T> {
+        let (shape, weights_shape) = attention_shapes(
+            self.data.borrow().raw_dim(),
+            key.data.borrow().raw_dim(),
+            value.data.borrow().raw_dim(),
+        );
+        let (p, status, state) = dropout;
+        assert!(
+            (0. ..=1.).contains(&p),
+            "error: dropout probability {} is not between 0 and 1.",
+            p
+        );
+
+        self.history.merge(key.history);
+        self.history.merge(value.history);
+        let mask = mask.map(|mask| {
+            let data = mask.data();
+            match mask {
+                AttentionMask::Boolean(mask) => {
+                    assert!(
+                        mask.data.borrow().broadcast(weights_shape).is_some(),
+                        "error: the mask cannot be broadcast to the shape of the attention weights."
+                    );
+                    self.history.merge(mask.history);
+                }
+                AttentionMask::Additive(mask) => {
+                    assert!(
+                        mask.data.borrow().broadcast(weights_shape).is_some(),
+                        "error: the mask cannot be broadcast to the shape of the attention weights."
+                    );
+                    self.history.merge(mask.history);
+                }
+            }
+            data
+        });
+
+        let data = Rc::new(RefCell::new(Array::zeros(shape)));
+        let op = ScaledDotProductAttention::new(
+            self.data,
+            key.data,
+            value.data,
+            mask,
+            causal,
+            state.map(|state| (p, status, state)),
+            logsumexp,
+            data.clone(),
+        );
+
+        Var::node(data, Rc::new(op), self.history)
+    }
+}
+
+impl<D, T> Var<D, T>
 where
     D: 'static + Dimension,
+    T: Float,
 {
+    /// Promotes `self` to a differentiable variable. A subsequent call to [`.backward()`]
+    /// will compute its grad.
+    ///
+    /// [`.backward()`]: VarDiff::backward()
+    ///
+    /// # Examples
+    ///
+    /// This is the preferred usage.
+    ///
+    ///```
+    /// # use neuronika_variable as neuronika;
+    /// let x = neuronika::ones(5).requires_grad();
+    ///```
+    ///
+    /// This is also permitted, however, one should be aware of the difference between `x_diff` and
+    /// `x`.
+    ///
+    ///```
+    /// # use neuronika_variable as neuronika;
+    /// let x = neuronika::ones(5);
+    /// let y = x.clone() + neuronika::ones(1);
+    ///
+    /// let x_diff = x.requires_grad();
+    ///```
+    pub fn requires_grad(self) -> VarDiff<D, T> {
+        let grad = Array::zeros(self.data.borrow().raw_dim());
+        VarDiff::leaf(self, grad)
+    }
+
     /// Returns the sum of all elements in `self`.
-    pub fn sum(self) -> Var<Ix0> {
-        let data = Rc::new(RefCell::new(arr0(0.)));
+    pub fn sum(self) -> Var<Ix0, T> {
+        let data = Rc::new(RefCell::new(arr0(T::zero())));
         let op = Sum::new(self.data, data.clone());
 
         Var::node(data, Rc::new(op), self.history)
     }
 
     /// Returns the mean of all elements in `self`.
-    pub fn mean(self) -> Var<Ix0> {
-        let data = Rc::new(RefCell::new(arr0(0.)));
+    pub fn mean(self) -> Var<Ix0, T> {
+        let data = Rc::new(RefCell::new(arr0(T::zero())));
         let op = Mean::new(self.data, data.clone());
 
         Var::node(data, Rc::new(op), self.history)
@@ -219,7 +686,7 @@ where
     /// # Arguments
     ///
     /// `exp` - exponent.
-    pub fn pow(self, exp: i32) -> Var<D> {
+    pub fn pow(self, exp: i32) -> Var<D, T> {
         let shape = self.data.borrow().raw_dim();
         let data = Rc::new(RefCell::new(Array::zeros(shape)));
         let op = Power::new(self.data, data.clone(), exp);
@@ -228,7 +695,7 @@ where
     }
 
     /// Takes the square root element-wise and returns a variable with the result.
-    pub fn sqrt(self) -> Var<D> {
+    pub fn sqrt(self) -> Var<D, T> {
         let shape = self.data.borrow().raw_dim();
         let data = Rc::new(RefCell::new(Array::zeros(shape)));
         let op = Sqrt::new(self.data, data.clone());
@@ -240,7 +707,7 @@ where
     /// result.
     ///
     /// *ReLU(x) = max(0, x)*
-    pub fn relu(self) -> Var<D> {
+    pub fn relu(self) -> Var<D, T> {
         let shape = self.data.borrow().raw_dim();
         let data = Rc::new(RefCell::new(Array::zeros(shape)));
         let op = ReLU::new(self.data, data.clone());
@@ -252,7 +719,7 @@ where
     /// the result.
     ///
     /// *LeakyReLU(x) = max(0, x) + 0.01 * min(0, x)*
-    pub fn leaky_relu(self) -> Var<D> {
+    pub fn leaky_relu(self) -> Var<D, T> {
         let shape = self.data.borrow().raw_dim();
         let data = Rc::new(RefCell::new(Array::zeros(shape)));
         let op = LeakyReLU::new(self.data, data.clone());
@@ -263,7 +730,7 @@ where
     /// Applies the *softplus* element-wise and returns a variable with the result.
     ///
     /// *Softplus(x) = log(1 + exp(x))*
-    pub fn softplus(self) -> Var<D> {
+    pub fn softplus(self) -> Var<D, T> {
         let shape = self.data.borrow().raw_dim();
         let data = Rc::new(RefCell::new(Array::zeros(shape)));
         let op = SoftPlus::new(self.data, data.clone());
@@ -272,7 +739,7 @@ where
     }
 
     /// Applies the *sigmoid* element-wise and returns a variable with the result.
-    pub fn sigmoid(self) -> Var<D> {
+    pub fn sigmoid(self) -> Var<D, T> {
         let shape = self.data.borrow().raw_dim();
         let data = Rc::new(RefCell::new(Array::zeros(shape)));
         let op = Sigmoid::new(self.data, data.clone());
@@ -281,7 +748,7 @@ where
     }
 
     /// Applies the *tanh* element-wise and returns a variable with the result.
-    pub fn tanh(self) -> Var<D> {
+    pub fn tanh(self) -> Var<D, T> {
         let shape = self.data.borrow().raw_dim();
         let data = Rc::new(RefCell::new(Array::zeros(shape)));
         let op = TanH::new(self.data, data.clone());
@@ -290,7 +757,7 @@ where
     }
 
     /// Applies the *natural logarithm* element-wise and returns a variable with the result.
-    pub fn ln(self) -> Var<D> {
+    pub fn ln(self) -> Var<D, T> {
         let shape = self.data.borrow().raw_dim();
         let data = Rc::new(RefCell::new(Array::zeros(shape)));
         let op = Logn::new(self.data, data.clone());
@@ -299,7 +766,7 @@ where
     }
 
     /// Applies the *exponential* element-wise and returns a variable with the result.
-    pub fn exp(self) -> Var<D> {
+    pub fn exp(self) -> Var<D, T> {
         let shape = self.data.borrow().raw_dim();
         let data = Rc::new(RefCell::new(Array::zeros(shape)));
         let op = Exp::new(self.data, data.clone());
@@ -315,7 +782,7 @@ where
     /// # Arguments    
     ///  
     /// `axis` - axis along which softmax will be computed.
-    pub fn softmax(self, axis: usize) -> Var<D> {
+    pub fn softmax(self, axis: usize) -> Var<D, T> {
         let shape = self.data.borrow().raw_dim();
         let data = Rc::new(RefCell::new(Array::zeros(shape)));
         let op = Softmax::new(self.data, data.clone(), axis);
@@ -335,7 +802,7 @@ where
     /// # Arguments
     ///
     /// `axis` - axis along which log-softmax will be computed.
-    pub fn log_softmax(self, axis: usize) -> Var<D> {
+    pub fn log_softmax(self, axis: usize) -> Var<D, T> {
         let shape = self.data.borrow().raw_dim();
         let data = Rc::new(RefCell::new(Array::zeros(shape)));
         let op = LogSoftmax::new(self.data, data.clone(), axis);
@@ -343,16 +810,223 @@ where
         Var::node(data, Rc::new(op), self.history)
     }
 
-    /// Returns a variable equivalent to `self` with its dimensions reversed.
-    pub fn t(self) -> Var<D> {
-        let shape = self.data.borrow().t().raw_dim();
-        let data = Rc::new(RefCell::new(Array::zeros(shape)));
-        let op = Transpose::new(self.data, data.clone());
-
-        Var::node(data, Rc::new(op), self.history)
-    }
-
-    /// Applies *dropout* to `self` and returns a variable with the result.
+    /// Computes the cumulative sum of the elements of `self` along `axis`.
+    ///
+    /// # Arguments
+    ///
+    /// `axis` - axis along which the sum is accumulated.
+    ///
+    /// # Panics
+    ///
+    /// If `axis` is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use neuronika_variable as neuronika;
+    /// let x = neuronika::from_ndarray(ndarray::array![[1., 2., 3.], [4., 5., 6.]]);
+    /// let y = x.cumsum(1);
+    /// y.forward();
+    ///
+    /// assert_eq!(*y.data(), ndarray::array![[1., 3., 6.], [4., 9., 15.]]);
+    /// ```
+    pub fn cumsum(self, axis: usize) -> Var<D, T> {
+        self.cumulative(axis, Scan::Sum)
+    }
+
+    /// Computes the cumulative product of the elements of `self` along `axis`.
+    ///
+    /// # Arguments
+    ///
+    /// `axis` - axis along which the product is accumulated.
+    ///
+    /// # Panics
+    ///
+    /// If `axis` is out of bounds.
+    pub fn cumprod(self, axis: usize) -> Var<D, T> {
+        self.cumulative(axis, Scan::Prod)
+    }
+
+    /// Computes the cumulative maximum of the elements of `self` along `axis`.
+    ///
+    /// # Arguments
+    ///
+    /// `axis` - axis along which the maximum is accumulated.
+    ///
+    /// # Panics
+    ///
+    /// If `axis` is out of bounds.
+    pub fn cummax(self, axis: usize) -> Var<D, T> {
+        self.cumulative(axis, Scan::Max)
+    }
+
+    /// Computes the logarithm of the cumulative sum of the exponentials of the elements of `self`
+    /// along `axis`.
+    ///
+    /// This is computed without overflowing even for large elements.
+    ///
+    /// # Arguments
+    ///
+    /// `axis` - axis along which the sum is accumulated.
+    ///
+    /// # Panics
+    ///
+    /// If `axis` is out of bounds.
+    pub fn logcumsumexp(self, axis: usize) -> Var<D, T> {
+        self.cumulative(axis, Scan::LogSumExp)
+    }
+
+    pub(crate) fn cumulative(self, axis: usize, scan: Scan) -> Var<D, T> {
+        let shape = self.data.borrow().raw_dim();
+        assert!(
+            axis < shape.ndim(),
+            "error: axis {} is out of bounds.",
+            axis
+        );
+        let data = Rc::new(RefCell::new(Array::zeros(shape)));
+        let op = Cumulative::new(self.data, data.clone(), axis, scan);
+
+        Var::node(data, Rc::new(op), self.history)
+    }
+
+    /// Returns a variable equivalent to `self` with its dimensions reversed.
+    pub fn t(self) -> Var<D, T> {
+        let shape = self.data.borrow().t().raw_dim();
+        let data = Rc::new(RefCell::new(Array::zeros(shape)));
+        let op = Transpose::new(self.data, data.clone());
+
+        Var::node(data, Rc::new(op), self.history)
+    }
+
+    /// Returns a variable equivalent to `self` with its axes permuted.
+    ///
+    /// The *i*-th axis of the result is the `axes[i]`-th axis of `self`.
+    ///
+    /// # Arguments
+    ///
+    /// * `axes` - permutation of the axes of `self`.
+    ///
+    /// # Panics
+    ///
+    /// If `axes` is not a permutation of the axes of `self`.
+    ///
+    /// # Examples
+    ///
+    /// Converting a batch of images from NCHW to NHWC.
+    ///
+    /// ```
+    /// # use neuronika_variable as neuronika;
+    /// let x = neuronika::zeros((8, 3, 32, 32));
+    /// let y = x.permute((0, 2, 3, 1));
+    ///
+    /// assert_eq!(y.data().shape(), &[8, 32, 32, 3]);
+    /// ```
+    pub fn permute<S>(self, axes: S) -> Var<D, T>
+    where
+        S: IntoDimension<Dim = D>,
+    {
+        let axes = axes.into_dimension();
+        let shape = {
+            let data = self.data.borrow();
+            check_permutation(data.ndim(), &axes);
+            data.view().permuted_axes(axes.clone()).raw_dim()
+        };
+        let data = Rc::new(RefCell::new(Array::zeros(shape)));
+        let op = Permute::new(self.data, data.clone(), axes);
+
+        Var::node(data, Rc::new(op), self.history)
+    }
+
+    /// Returns a variable equivalent to `self` with axes `a` and `b` swapped.
+    ///
+    /// # Arguments
+    ///
+    /// * `a` - first axis.
+    ///
+    /// * `b` - second axis.
+    ///
+    /// # Panics
+    ///
+    /// If either `a` or `b` is out of bounds.
+    pub fn swap_axes(self, a: usize, b: usize) -> Var<D, T> {
+        let axes: D = swapped_axes(self.data.borrow().ndim(), a, b);
+
+        self.permute(axes)
+    }
+
+    /// Reverses the order of the elements of `self` along `axes` and returns a variable with the
+    /// result.
+    ///
+    /// # Arguments
+    ///
+    /// * `axes` - axes to reverse.
+    ///
+    /// # Panics
+    ///
+    /// If any of `axes` is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use neuronika_variable as neuronika;
+    /// let x = neuronika::from_ndarray(ndarray::array![[1., 2., 3.], [4., 5., 6.]]);
+    /// let y = x.flip(&[1]);
+    /// y.forward();
+    ///
+    /// assert_eq!(*y.data(), ndarray::array![[3., 2., 1.], [6., 5., 4.]]);
+    /// ```
+    pub fn flip(self, axes: &[usize]) -> Var<D, T> {
+        let shape = self.data.borrow().raw_dim();
+        for &axis in axes {
+            assert!(
+                axis < shape.ndim(),
+                "error: axis {} is out of bounds.",
+                axis
+            );
+        }
+        let data = Rc::new(RefCell::new(Array::zeros(shape)));
+        let op = Flip::new(self.data, data.clone(), axes);
+
+        Var::node(data, Rc::new(op), self.history)
+    }
+
+    /// Shifts the elements of `self` by `shift` positions along `axis`, wrapping around the ones
+    /// going past its end, and returns a variable with the result.
+    ///
+    /// # Arguments
+    ///
+    /// * `shift` - number of positions to shift by, negative values shift backwards.
+    ///
+    /// * `axis` - axis to shift along.
+    ///
+    /// # Panics
+    ///
+    /// If `axis` is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use neuronika_variable as neuronika;
+    /// let x = neuronika::from_ndarray(ndarray::array![1., 2., 3., 4.]);
+    /// let y = x.roll(1, 0);
+    /// y.forward();
+    ///
+    /// assert_eq!(*y.data(), ndarray::array![4., 1., 2., 3.]);
+    /// ```
+    pub fn roll(self, shift: isize, axis: usize) -> Var<D, T> {
+        let shape = self.data.borrow().raw_dim();
+        assert!(
+            axis < shape.ndim(),
+            "error: axis {} is out of bounds.",
+            axis
+        );
+        let data = Rc::new(RefCell::new(Array::zeros(shape)));
+        let op = Roll::new(self.data, data.clone(), shift, axis);
+
+        Var::node(data, Rc::new(op), self.history)
+    }
+
+    /// Applies *dropout* to `self` and returns a variable with the result.
     ///
     /// During training, randomly zeroes some of the elements of `self` with probability *p* using
     /// samples from a Bernoulli distribution. Each channel will be zeroed out independently on
@@ -372,7 +1046,7 @@ where
     /// * `p` - dropout factor.
     ///
     /// * `status` - dropout status.
-    pub fn dropout(self, p: f64, status: Rc<Cell<bool>>) -> Var<D> {
+    pub fn dropout(self, p: f64, status: Rc<Cell<bool>>) -> Var<D, T> {
         let shape = self.data.borrow().raw_dim();
         let noise = Rc::new(RefCell::new(Array::zeros(shape.clone())));
 
@@ -383,9 +1057,9 @@ where
         self,
         shape: D,
         p: f64,
-        noise: Shared<Array<f32, D>>,
+        noise: Shared<Array<T, D>>,
         status: Rc<Cell<bool>>,
-    ) -> Var<D> {
+    ) -> Var<D, T> {
         let data = Rc::new(RefCell::new(Array::zeros(shape)));
         let op = Dropout::new(self.data, data.clone(), p, noise, status);
 
@@ -398,7 +1072,7 @@ where
     /// # Arguments
     ///
     /// `chunk_size` - shape for each chunk.
-    pub fn chunks<E>(self, chunk_size: E) -> Vec<Var<D>>
+    pub fn chunks<E>(self, chunk_size: E) -> Vec<Var<D, T>>
     where
         E: IntoDimension<Dim = D>,
     {
@@ -416,18 +1090,371 @@ where
             .collect()
     }
 
-    /// Returns a new variable with a dimension of size one inserted at the position specified by
-    /// `axis`.
-    ///
-    /// # Arguments
-    ///
-    /// `axis` - dimension to insert the new axis at.
-    pub fn unsqueeze(self, axis: usize) -> Var<D::Larger> {
+    /// Returns a new variable with a dimension of size one inserted at the position specified by
+    /// `axis`.
+    ///
+    /// # Arguments
+    ///
+    /// `axis` - dimension to insert the new axis at.
+    ///
+    /// # Panics
+    ///
+    /// If `axis` is greater than the number of dimensions of the variable.
+    pub fn unsqueeze(self, axis: usize) -> Var<D::Larger, T> {
+        let shape = self.data.borrow().raw_dim();
+        assert!(
+            axis <= shape.ndim(),
+            "error: axis {} is out of bounds.",
+            axis
+        );
+        let data = Rc::new(RefCell::new(Array::zeros(shape.insert_axis(Axis(axis)))));
+        let op = Unsqueeze::new(self.data, data.clone());
+
+        Var::node(data, Rc::new(op), self.history)
+    }
+
+    /// Broadcasts `self` to `shape` and returns a variable with the result.
+    ///
+    /// The usual broadcasting rules apply: the shape of `self` is aligned to the trailing axes of
+    /// `shape` and its axes of length one are repeated as needed.
+    ///
+    /// # Arguments
+    ///
+    /// * `shape` - shape to broadcast to.
+    ///
+    /// # Panics
+    ///
+    /// If `self` cannot be broadcast to `shape`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use neuronika_variable as neuronika;
+    /// let x = neuronika::from_ndarray(ndarray::array![1., 2., 3.]);
+    /// let y = x.broadcast_to((2, 3));
+    /// y.forward();
+    ///
+    /// assert_eq!(*y.data(), ndarray::array![[1., 2., 3.], [1., 2., 3.]]);
+    /// ```
+    pub fn broadcast_to<S>(self, shape: S) -> Var<S::Dim, T>
+    where
+        S: IntoDimension,
+        S::Dim: 'static,
+    {
+        let shape = broadcast_shapes(&self.data.borrow().raw_dim(), shape.into_dimension());
+
+        self.repeated(shape)
+    }
+
+    /// Repeats `self` `size` times along `axis`, which must have length one, and returns a
+    /// variable with the result.
+    ///
+    /// # Arguments
+    ///
+    /// * `axis` - axis to expand.
+    ///
+    /// * `size` - length of the expanded axis.
+    ///
+    /// # Panics
+    ///
+    /// If `axis` is out of bounds or if its length is not one.
+    pub fn expand(self, axis: usize, size: usize) -> Var<D, T> {
+        let mut shape = self.data.borrow().raw_dim();
+        assert!(
+            axis < shape.ndim(),
+            "error: axis {} is out of bounds.",
+            axis
+        );
+        assert_eq!(
+            shape[axis], 1,
+            "error: axis {} of length {} cannot be expanded.",
+            axis, shape[axis]
+        );
+        shape[axis] = size;
+
+        self.broadcast_to(shape)
+    }
+
+    /// Tiles `self`, repeating it `reps[i]` times along the *i*-th axis, and returns a variable
+    /// with the result.
+    ///
+    /// # Arguments
+    ///
+    /// * `reps` - number of repetitions along each axis.
+    ///
+    /// # Panics
+    ///
+    /// If `reps` and `self` have a different number of dimensions.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use neuronika_variable as neuronika;
+    /// let x = neuronika::from_ndarray(ndarray::array![[1., 2.]]);
+    /// let y = x.repeat((2, 2));
+    /// y.forward();
+    ///
+    /// assert_eq!(*y.data(), ndarray::array![[1., 2., 1., 2.], [1., 2., 1., 2.]]);
+    /// ```
+    pub fn repeat<S>(self, reps: S) -> Var<D, T>
+    where
+        S: IntoDimension<Dim = D>,
+    {
+        let shape = tiled_shapes(&self.data.borrow().raw_dim(), &reps.into_dimension());
+
+        self.repeated(shape)
+    }
+
+    /// Repeats each element of `self` `repeats` times along `axis` and returns a variable with the
+    /// result.
+    ///
+    /// # Arguments
+    ///
+    /// * `repeats` - number of repetitions of each element.
+    ///
+    /// * `axis` - axis to repeat along.
+    ///
+    /// # Panics
+    ///
+    /// If `axis` is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use neuronika_variable as neuronika;
+    /// let x = neuronika::from_ndarray(ndarray::array![[1., 2.]]);
+    /// let y = x.repeat_interleave(2, 1);
+    /// y.forward();
+    ///
+    /// assert_eq!(*y.data(), ndarray::array![[1., 1., 2., 2.]]);
+    /// ```
+    pub fn repeat_interleave(self, repeats: usize, axis: usize) -> Var<D, T> {
+        let shape = interleaved_shapes(&self.data.borrow().raw_dim(), repeats, axis);
+
+        self.repeated(shape)
+    }
+
+    pub(crate) fn repeated<E>(self, (shape, operand_shape, blocks): (E, IxDyn, IxDyn)) -> Var<E, T>
+    where
+        E: 'static + Dimension,
+    {
+        let data = Rc::new(RefCell::new(Array::zeros(shape)));
+        let op = Repeat::new(self.data, data.clone(), operand_shape, blocks);
+
+        Var::node(data, Rc::new(op), self.history)
+    }
+
+    /// Fills the elements of `self` where `mask` is `true` with `value` and returns a variable
+    /// with the result.
+    ///
+    /// # Arguments
+    ///
+    /// * `mask` - boolean mask, must be broadcastable to the shape of `self`.
+    ///
+    /// * `value` - value to fill with.
+    ///
+    /// # Panics
+    ///
+    /// If `mask` cannot be broadcast to the shape of `self`.
+    pub fn masked_fill<M>(mut self, mask: Var<M, bool>, value: T) -> Var<D, T>
+    where
+        M: 'static + Dimension,
+    {
+        let shape = self.data.borrow().raw_dim();
+        assert!(
+            mask.data.borrow().broadcast(shape.clone()).is_some(),
+            "error: the mask cannot be broadcast to the shape of the variable."
+        );
+
+        self.history.merge(mask.history);
+
+        let data = Rc::new(RefCell::new(Array::zeros(shape)));
+        let op = MaskedFill::new(self.data, mask.data, value, data.clone());
+
+        Var::node(data, Rc::new(op), self.history)
+    }
+
+    /// Gathers the values of `self` along `axis` at the positions specified by `index` and
+    /// returns a variable with the result.
+    ///
+    /// For a 3-dimensional variable and `axis` equal to 1 the result is such that
+    /// *out[i][j][k] = self[i][index[i][j][k]][k]*.
+    ///
+    /// # Arguments
+    ///
+    /// * `axis` - axis to gather along.
+    ///
+    /// * `index` - indices of the elements to gather, such as `i64` or `usize` ones.
+    ///
+    /// # Panics
+    ///
+    /// If `axis` is out of bounds or if `index` is larger than `self` along any axis other than
+    /// `axis`. Computing the result panics if an index is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use neuronika_variable as neuronika;
+    /// use ndarray;
+    ///
+    /// let x = neuronika::from_ndarray(ndarray::array![[1., 2.], [3., 4.]]);
+    /// let index = neuronika::from_ndarray(ndarray::array![[1_i64], [0]]);
+    ///
+    /// let y = x.gather(1, index);
+    /// y.forward();
+    ///
+    /// assert_eq!(*y.data(), ndarray::array![[2.], [3.]]);
+    /// ```
+    pub fn gather<L>(mut self, axis: usize, index: Var<D, L>) -> Var<D, T>
+    where
+        L: AsIndex,
+    {
+        let shape = index.data.borrow().raw_dim();
+        {
+            let operand_shape = self.data.borrow().raw_dim();
+            assert!(
+                axis < shape.ndim(),
+                "error: axis {} is out of bounds.",
+                axis
+            );
+            assert!(
+                shape
+                    .slice()
+                    .iter()
+                    .zip(operand_shape.slice())
+                    .enumerate()
+                    .all(|(i, (index_len, len))| i == axis || index_len <= len),
+                "error: index of shape {:?} is larger than variable of shape {:?}.",
+                shape.slice(),
+                operand_shape.slice()
+            );
+        }
+
+        self.history.merge(index.history);
+
+        let data = Rc::new(RefCell::new(Array::zeros(shape)));
+        let op = Gather::new(self.data, index.data, data.clone(), axis);
+
+        Var::node(data, Rc::new(op), self.history)
+    }
+
+    /// Sorts the elements of `self` along `axis`, returning a variable with the sorted elements
+    /// and one with their positions in `self`.
+    ///
+    /// The sort is stable, so equal elements keep their relative order. NaNs are considered
+    /// larger than any other element.
+    ///
+    /// # Arguments
+    ///
+    /// * `axis` - axis to sort along.
+    ///
+    /// * `descending` - whether to sort in descending order.
+    ///
+    /// # Panics
+    ///
+    /// If `axis` is out of bounds.
+    pub fn sort(self, axis: usize, descending: bool) -> (Var<D, T>, Var<D, usize>) {
+        let len = self.axis_len(axis);
+
+        self.ranked(axis, 0..len, descending)
+    }
+
+    /// Selects the `k` largest or smallest elements of `self` along `axis`, returning a variable
+    /// with them, sorted, and one with their positions in `self`.
+    ///
+    /// Ties are broken in favour of the elements coming first along `axis`. The positions can be
+    /// used with [`.gather()`](Var::gather()) to select the corresponding elements of other
+    /// variables.
+    ///
+    /// # Arguments
+    ///
+    /// * `k` - number of elements to select.
+    ///
+    /// * `axis` - axis to select along.
+    ///
+    /// * `largest` - whether to select the largest elements rather than the smallest.
+    ///
+    /// # Panics
+    ///
+    /// If `axis` is out of bounds or if `k` is larger than the length of `axis`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use neuronika_variable as neuronika;
+    /// let x = neuronika::from_ndarray(ndarray::array![[3., 1., 4., 1.], [5., 9., 2., 6.]]);
+    /// let (values, indices) = x.topk(2, 1, true);
+    /// values.forward();
+    ///
+    /// assert_eq!(*values.data(), ndarray::array![[4., 3.], [9., 6.]]);
+    /// assert_eq!(*indices.data(), ndarray::array![[2, 0], [1, 3]]);
+    /// ```
+    pub fn topk(self, k: usize, axis: usize, largest: bool) -> (Var<D, T>, Var<D, usize>) {
+        let len = self.axis_len(axis);
+        assert!(
+            k <= len,
+            "error: cannot select {} elements out of {} along axis {}.",
+            k,
+            len,
+            axis
+        );
+
+        self.ranked(axis, 0..k, largest)
+    }
+
+    /// Selects the `k`-th smallest element of each lane of `self` along `axis`, returning a
+    /// variable with them and one with their positions in `self`.
+    ///
+    /// `axis` is kept in the results with length one, so that the positions can be used with
+    /// [`.gather()`](Var::gather()).
+    ///
+    /// # Arguments
+    ///
+    /// * `k` - rank of the element to select, starting from 1.
+    ///
+    /// * `axis` - axis to select along.
+    ///
+    /// # Panics
+    ///
+    /// If `axis` is out of bounds or if `k` is not between 1 and the length of `axis`.
+    pub fn kthvalue(self, k: usize, axis: usize) -> (Var<D, T>, Var<D, usize>) {
+        let len = self.axis_len(axis);
+        assert!(
+            k >= 1 && k <= len,
+            "error: cannot select the element of rank {} out of {} along axis {}.",
+            k,
+            len,
+            axis
+        );
+
+        self.ranked(axis, k - 1..k, false)
+    }
+
+    /// Returns the length of `axis`, panicking if it is out of bounds.
+    fn axis_len(&self, axis: usize) -> usize {
         let shape = self.data.borrow().raw_dim();
-        let data = Rc::new(RefCell::new(Array::zeros(shape.insert_axis(Axis(axis)))));
-        let op = Unsqueeze::new(self.data, data.clone());
+        assert!(
+            axis < shape.ndim(),
+            "error: axis {} is out of bounds.",
+            axis
+        );
 
-        Var::node(data, Rc::new(op), self.history)
+        shape[axis]
+    }
+
+    pub(crate) fn ranked(
+        self,
+        axis: usize,
+        ranks: Range<usize>,
+        descending: bool,
+    ) -> (Var<D, T>, Var<D, usize>) {
+        let mut shape = self.data.borrow().raw_dim();
+        shape[axis] = ranks.len();
+        let data = Rc::new(RefCell::new(Array::zeros(shape)));
+        let op = ArgSort::new(self.data.clone(), data.clone(), axis, ranks, descending);
+        let index = Var::node(data, Rc::new(op), self.history.clone());
+
+        (self.gather(axis, index.clone()), index)
     }
 
     /// Computes the mean absolute error between the two variables.
@@ -437,8 +1464,8 @@ where
     /// * `target` - target variable.
     ///
     /// * `reduction` - reduction to apply to the criterion's output.
-    pub fn mae(self, target: Var<D>, reduction: Reduction) -> Var<Ix0> {
-        let data = Rc::new(RefCell::new(arr0(0.0)));
+    pub fn mae(self, target: Var<D, T>, reduction: Reduction) -> Var<Ix0, T> {
+        let data = Rc::new(RefCell::new(arr0(T::zero())));
         let op = AbsoluteError::new(self.data, target.data, data.clone(), reduction);
 
         Var::node(data, Rc::new(op), self.history)
@@ -451,8 +1478,8 @@ where
     /// * `target` - target variable.
     ///
     /// * `reduction` - reduction to apply to the criterion's output.
-    pub fn mse(self, target: Var<D>, reduction: Reduction) -> Var<Ix0> {
-        let data = Rc::new(RefCell::new(arr0(0.0)));
+    pub fn mse(self, target: Var<D, T>, reduction: Reduction) -> Var<Ix0, T> {
+        let data = Rc::new(RefCell::new(arr0(T::zero())));
         let op = SquaredError::new(self.data, target.data, data.clone(), reduction);
 
         Var::node(data, Rc::new(op), self.history)
@@ -474,8 +1501,8 @@ where
     /// * `target` - target variable.
     ///
     /// * `reduction` - reduction to apply to the criterion's output.
-    pub fn bce(self, target: Var<D>, reduction: Reduction) -> Var<Ix0> {
-        let data = Rc::new(RefCell::new(arr0(0.0)));
+    pub fn bce(self, target: Var<D, T>, reduction: Reduction) -> Var<Ix0, T> {
+        let data = Rc::new(RefCell::new(arr0(T::zero())));
         let op = BinaryCrossEntropy::new(self.data, target.data, data.clone(), reduction);
 
         Var::node(data, Rc::new(op), self.history)
@@ -496,8 +1523,8 @@ where
     /// * `target` - target variable.
     ///
     /// * `reduction` - reduction to apply to the criterion's output.
-    pub fn bce_with_logits(self, target: Var<D>, reduction: Reduction) -> Var<Ix0> {
-        let data = Rc::new(RefCell::new(arr0(0.0)));
+    pub fn bce_with_logits(self, target: Var<D, T>, reduction: Reduction) -> Var<Ix0, T> {
+        let data = Rc::new(RefCell::new(arr0(T::zero())));
         let op = BCEWithLogits::new(self.data, target.data, data.clone(), reduction);
 
         Var::node(data, Rc::new(op), self.history)
@@ -518,17 +1545,18 @@ where
     /// * `target` - target variable.
     ///
     /// * `reduction` - reduction to apply to the criterion's output.
-    pub fn kldiv(self, target: Var<D>, reduction: Reduction) -> Var<Ix0> {
-        let data = Rc::new(RefCell::new(arr0(0.0)));
+    pub fn kldiv(self, target: Var<D, T>, reduction: Reduction) -> Var<Ix0, T> {
+        let data = Rc::new(RefCell::new(arr0(T::zero())));
         let op = KLDiv::new(self.data, target.data, data.clone(), reduction);
 
         Var::node(data, Rc::new(op), self.history)
     }
 }
 
-impl<D> Var<D>
+impl<D, T> Var<D, T>
 where
     D: 'static + Dimension + RemoveAxis,
+    T: Float,
 {
     /// Concatenates the given sequence of non-differentiable variables, including self`, along
     /// the given axis, and returns a non-differentiable variable with the results.
@@ -561,7 +1589,7 @@ where
     ///                                       [1., 1., 4., 4., 3., 3.],
     ///                                       [1., 1., 4., 4., 3., 3.]]);
     /// ```
-    pub fn cat(mut self, variables: &[Self], axis: usize) -> Var<D> {
+    pub fn cat(mut self, variables: &[Self], axis: usize) -> Var<D, T> {
         let mut operands_data = Vec::with_capacity(variables.len());
         operands_data.push(self.data);
 
@@ -571,12 +1599,12 @@ where
         });
 
         let data = {
-            let tensors: Vec<Ref<Array<f32, D>>> = operands_data
+            let tensors: Vec<Ref<Array<T, D>>> = operands_data
                 .iter()
                 .map(|operand| operand.borrow())
                 .collect();
             let views: Vec<_> = tensors.iter().map(|tensor| tensor.view()).collect();
-            Rc::new(RefCell::new(concatenate(Axis(axis), &views).unwrap()))
+            Rc::new(RefCell::new(concatenate_checked(axis, &views)))
         };
         let op = MultiConcatenate::new(operands_data, data.clone(), axis);
 
@@ -619,7 +1647,7 @@ where
     ///                                       [[1., 1.],
     ///                                        [1., 1.]]]);
     /// ```
-    pub fn stack(mut self, variables: &[Self], axis: usize) -> Var<D::Larger> {
+    pub fn stack(mut self, variables: &[Self], axis: usize) -> Var<D::Larger, T> {
         let mut operands_data = Vec::with_capacity(variables.len());
         operands_data.push(self.data);
 
@@ -629,12 +1657,12 @@ where
         });
 
         let data = {
-            let tensors: Vec<Ref<Array<f32, D>>> = operands_data
+            let tensors: Vec<Ref<Array<T, D>>> = operands_data
                 .iter()
                 .map(|operand| operand.borrow())
                 .collect();
             let views: Vec<_> = tensors.iter().map(|tensor| tensor.view()).collect();
-            Rc::new(RefCell::new(stack(Axis(axis), &views).unwrap()))
+            Rc::new(RefCell::new(stack_checked(axis, &views)))
         };
         let op = Rc::new(MultiStack::new(operands_data, data.clone(), axis));
 
@@ -648,10 +1676,12 @@ where
     /// or (minibatch, C, d1, d2, ..., dk) with k >= 1 for the K-dimensional
     /// case.
     ///
-    /// The target variable should be a class index in the range [0, C) where C = number of classes.
+    /// The target variable should contain class indices in the range [0, C) where C = number of
+    /// classes. Its elements can be of any type implementing [`AsIndex`], such as `i64` or `usize`.
     ///
     /// When the given reduction is equal to [`Reduction::Mean`] the total negative likelihood is
-    /// divided by the batch size.
+    /// divided by the number of elements of the target, that is, the batch size in the
+    /// 1-dimensional case.
     ///
     /// As mentioned before, this criterion can also be used for higher dimensional inputs, such as 2D
     /// images, by providing an input of size (minibatch, C, d1, d2, ..., dk) with k >= 1 where
@@ -667,8 +1697,27 @@ where
     /// * `target` - target variable.
     ///
     /// * `reduction` - reduction to apply to the criterion's output.
-    pub fn nll(self, target: Var<D::Smaller>, reduction: Reduction) -> Var<Ix0> {
-        let data = Rc::new(RefCell::new(arr0(0.0)));
+    ///
+    /// # Panics
+    ///
+    /// If the shape of `target` differs from the one of the input without the class axis.
+    pub fn nll<L>(mut self, target: Var<D::Smaller, L>, reduction: Reduction) -> Var<Ix0, T>
+    where
+        L: AsIndex,
+    {
+        {
+            let (shape, target_shape) =
+                (self.data.borrow().raw_dim(), target.data.borrow().raw_dim());
+            assert!(
+                shape.ndim() > 1 && shape.remove_axis(Axis(1)) == target_shape,
+                "error: target of shape {:?} doesn't match input of shape {:?}.",
+                target_shape.slice(),
+                shape.slice()
+            );
+        }
+
+        self.history.merge(target.history);
+        let data = Rc::new(RefCell::new(arr0(T::zero())));
         let op = NegativeLogLikelihood::new(self.data, target.data, data.clone(), reduction);
 
         Var::node(data, Rc::new(op), self.history)
@@ -701,149 +1750,402 @@ where
     ///
     /// * `groups` - controls the connection between inputs and outputs. Both the channels in the
     /// input and the number of kernels in `self` must be divisible by `groups`.
-    pub fn convolution<Rhs, T>(
+    pub fn convolution<Rhs, S>(
         self,
         input: Rhs,
-        stride: T,
-        dilation: T,
+        stride: S,
+        dilation: S,
         groups: usize,
     ) -> <Self as Convolution<Rhs, <D::Smaller as Dimension>::Smaller>>::Output
     where
         Self: Convolution<Rhs, <D::Smaller as Dimension>::Smaller>,
-        T: Copy + IntoDimension<Dim = <D::Smaller as Dimension>::Smaller>,
+        S: Copy + IntoDimension<Dim = <D::Smaller as Dimension>::Smaller>,
     {
-        Convolution::convolution::<T>(self, input, stride, dilation, groups)
+        Convolution::convolution::<S>(self, input, stride, dilation, groups)
     }
 }
 
-impl<D> Var<D>
+impl<D, T> Var<D, T>
 where
     D: 'static + Dimension,
     D::Smaller: RemoveAxis,
-    <D::Smaller as Dimension>::Smaller: Copy,
+    T: Float,
 {
     /// Applies the specified padding over the spatial dimensions of the variable.
-    pub fn pad<T, E>(self, padding: E, mode: T) -> Var<D>
+    pub fn pad<P, E>(self, padding: E, mode: P) -> Var<D, T>
     where
-        T: 'static + PaddingMode<D>,
+        P: 'static + PaddingMode<D, T>,
         E: IntoDimension<Dim = <D::Smaller as Dimension>::Smaller>,
     {
         let padding = padding.into_dimension();
-        let shape = padded_shape(self.data().raw_dim(), padding);
+        let shape = padded_shape(self.data().raw_dim(), padding.clone());
         let data = Rc::new(RefCell::new(Array::zeros(shape)));
         let op = Rc::new(Pad::new(self.data, data.clone(), mode, padding));
 
         Var::node(data, op, self.history)
     }
-}
 
-// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
-// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Arithmetic Operations Implementation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
-// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    /// Resizes the spatial dimensions of `self` and returns a variable with the result.
+    ///
+    /// The variable must be of shape *(N, C, ...)*, with one, two or three spatial dimensions
+    /// depending on `mode`. When `align_corners` is `true` the centers of the corner elements of
+    /// `self` and of the result are aligned, otherwise their outer edges are. Nearest neighbour
+    /// interpolation ignores it. Scale factors are only used to compute the size of the result.
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - size of the spatial dimensions of the result or multiplier of the current one.
+    ///
+    /// * `mode` - interpolation algorithm.
+    ///
+    /// * `align_corners` - whether to align the corner elements.
+    ///
+    /// # Panics
+    ///
+    /// If `mode` doesn't support the number of spatial dimensions of `self`, if `size` has a
+    /// different number of spatial dimensions or if any of the resulting lengths is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use neuronika_variable as neuronika;
+    /// use neuronika::{Interpolation, Resize};
+    ///
+    /// let x = neuronika::from_ndarray(ndarray::array![[[1., 2., 3., 4.]]]);
+    /// let y = x.interpolate(Resize::ScaleFactor(2.), Interpolation::Linear, false);
+    /// y.forward();
+    ///
+    /// assert_eq!(
+    ///     *y.data(),
+    ///     ndarray::array![[[1., 1.25, 1.75, 2.25, 2.75, 3.25, 3.75, 4.]]]
+    /// );
+    /// ```
+    pub fn interpolate(
+        self,
+        size: Resize<<D::Smaller as Dimension>::Smaller>,
+        mode: Interpolation,
+        align_corners: bool,
+    ) -> Var<D, T> {
+        let (shape, taps) = resampling(&self.data.borrow().raw_dim(), &size, mode, align_corners);
 
-// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Var - f32 ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        self.resampled(shape, taps)
+    }
 
-impl<D> Add<f32> for Var<D>
-where
-    D: 'static + Dimension + DimMax<Ix0>,
-{
-    type Output = Var<<D as DimMax<Ix0>>::Output>;
+    pub(crate) fn resampled(self, shape: D, taps: Vec<Taps<T>>) -> Var<D, T> {
+        let data = Rc::new(RefCell::new(Array::zeros(shape)));
+        let op = Interpolate::new(self.data, data.clone(), taps);
 
-    fn add(self, rhs: f32) -> Self::Output {
-        self + crate::full((), rhs)
+        Var::node(data, Rc::new(op), self.history)
     }
 }
 
-impl<D> Sub<f32> for Var<D>
+impl<D, T> Var<D, T>
 where
-    D: 'static + Dimension + DimMax<Ix0>,
+    D: 'static + RemoveAxis,
+    D::Smaller: 'static + RemoveAxis,
+    <D::Smaller as Dimension>::Smaller: 'static,
+    T: Float,
 {
-    type Output = Var<<D as DimMax<Ix0>>::Output>;
+    /// Computes the inverse of each matrix of `self`, which is a matrix or a batch of matrices
+    /// stacked along the leading axes.
+    ///
+    /// Singular matrices result in non-finite values.
+    ///
+    /// # Panics
+    ///
+    /// If the matrices are not square.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use neuronika_variable as neuronika;
+    /// let a = neuronika::from_ndarray(ndarray::array![[2., 0.], [1., 4.]]);
+    /// let inverse = a.inv();
+    /// inverse.forward();
+    ///
+    /// assert_eq!(*inverse.data(), ndarray::array![[0.5, 0.], [-0.125, 0.25]]);
+    /// ```
+    pub fn inv(self) -> Var<D, T> {
+        let shape = self.data.borrow().raw_dim();
+        let (rows, columns) = matrices_shape(&shape, true);
+        let ranges = Decomposition::Inverse.ranges(rows, columns);
 
-    fn sub(self, rhs: f32) -> Self::Output {
-        self - crate::full((), rhs)
+        self.linalg(Decomposition::Inverse)
+            .unpacked(shape, ranges[0].clone())
     }
-}
 
-impl<D> Mul<f32> for Var<D>
-where
-    D: 'static + Dimension + DimMax<Ix0>,
-{
-    type Output = Var<<D as DimMax<Ix0>>::Output>;
+    /// Computes the determinant of each matrix of `self`.
+    ///
+    /// # Panics
+    ///
+    /// If the matrices are not square.
+    pub fn det(self) -> Var<<D::Smaller as Dimension>::Smaller, T> {
+        let shape = batch_shape(self.data.borrow().raw_dim());
+
+        self.linalg(Decomposition::Determinant)
+            .unpacked(shape, 0..1)
+    }
+
+    /// Computes the sign and the natural logarithm of the absolute value of the determinant of
+    /// each matrix of `self`.
+    ///
+    /// This is more accurate than [`.det()`](Var::det()) for matrices whose determinant is either
+    /// very small or very large. The sign of singular matrices is zero.
+    ///
+    /// # Panics
+    ///
+    /// If the matrices are not square.
+    #[allow(clippy::type_complexity)]
+    pub fn slogdet(
+        self,
+    ) -> (
+        Var<<D::Smaller as Dimension>::Smaller, T>,
+        Var<<D::Smaller as Dimension>::Smaller, T>,
+    ) {
+        let shape = batch_shape(self.data.borrow().raw_dim());
+        let packed = self.linalg(Decomposition::Slogdet);
+
+        (
+            packed.unpacked(shape.clone(), 0..1),
+            packed.unpacked(shape, 1..2),
+        )
+    }
+
+    /// Computes the lower triangular Cholesky factor `L` of each matrix `A` of `self`, such that
+    /// `A = L · Lᵀ`.
+    ///
+    /// The matrices must be symmetric and positive-definite, only their symmetric part is
+    /// considered. Matrices that are not positive-definite result in non-finite values.
+    ///
+    /// # Panics
+    ///
+    /// If the matrices are not square.
+    pub fn cholesky(self) -> Var<D, T> {
+        let shape = self.data.borrow().raw_dim();
+        let (rows, columns) = matrices_shape(&shape, true);
+        let ranges = Decomposition::Cholesky.ranges(rows, columns);
+
+        self.linalg(Decomposition::Cholesky)
+            .unpacked(shape, ranges[0].clone())
+    }
+
+    /// Computes the reduced QR decomposition of each matrix of `self`, returning the matrices
+    /// with orthonormal columns `Q` and the upper triangular ones `R`.
+    ///
+    /// Given matrices of *m* rows and *n* columns and *k = min(m, n)*, `Q` has *k* columns and
+    /// `R` has *k* rows. The diagonal elements of `R` are non-negative.
+    ///
+    /// # Panics
+    ///
+    /// If `self` is not a matrix or a batch of matrices.
+    pub fn qr(self) -> (Var<D, T>, Var<D, T>) {
+        let shape = self.data.borrow().raw_dim();
+        let (q_shape, r_shape) = qr_shapes(&shape);
+        let (rows, columns) = matrices_shape(&shape, false);
+        let ranges = Decomposition::Qr.ranges(rows, columns);
+        let packed = self.linalg(Decomposition::Qr);
+
+        (
+            packed.unpacked(q_shape, ranges[0].clone()),
+            packed.unpacked(r_shape, ranges[1].clone()),
+        )
+    }
 
-    fn mul(self, rhs: f32) -> Self::Output {
-        self * crate::full((), rhs)
+    /// Computes the eigenvalues, in ascending order, and the eigenvectors, as columns, of each
+    /// matrix of `self`.
+    ///
+    /// The matrices must be symmetric, only their symmetric part is considered.
+    ///
+    /// # Panics
+    ///
+    /// If the matrices are not square.
+    pub fn eigh(self) -> (Var<D::Smaller, T>, Var<D, T>) {
+        let shape = self.data.borrow().raw_dim();
+        let (rows, columns) = matrices_shape(&shape, true);
+        let ranges = Decomposition::Eigh.ranges(rows, columns);
+        let packed = self.linalg(Decomposition::Eigh);
+
+        (
+            packed.unpacked(shape.remove_axis(Axis(shape.ndim() - 1)), ranges[0].clone()),
+            packed.unpacked(shape, ranges[1].clone()),
+        )
+    }
+
+    /// Computes the reduced singular value decomposition `U · diag(S) · Vᵀ` of each matrix of
+    /// `self`, returning `U`, `S` and `Vᵀ`.
+    ///
+    /// Given matrices of *m* rows and *n* columns and *k = min(m, n)*, `U` has *k* columns, `S` has
+    /// *k* elements, sorted in descending order, and `Vᵀ` has *k* rows. The columns of `U` and the
+    /// rows of `Vᵀ` matching zero singular values complete orthonormal bases.
+    ///
+    /// # Panics
+    ///
+    /// If `self` is not a matrix or a batch of matrices.
+    #[allow(clippy::type_complexity)]
+    pub fn svd(self) -> (Var<D, T>, Var<D::Smaller, T>, Var<D, T>) {
+        let shape = self.data.borrow().raw_dim();
+        let (u_shape, s_shape, vt_shape) = svd_shapes(&shape);
+        let (rows, columns) = matrices_shape(&shape, false);
+        let ranges = Decomposition::Svd.ranges(rows, columns);
+        let packed = self.linalg(Decomposition::Svd);
+
+        (
+            packed.unpacked(u_shape, ranges[0].clone()),
+            packed.unpacked(s_shape, ranges[1].clone()),
+            packed.unpacked(vt_shape, ranges[2].clone()),
+        )
+    }
+
+    /// Computes `decomposition` of each matrix of `self`, returning a variable with one row per
+    /// matrix packing all of its outputs.
+    pub(crate) fn linalg(&self, decomposition: Decomposition) -> Var<Ix2, T> {
+        let shape = self.data.borrow().raw_dim();
+        let (rows, columns) = matrices_shape(&shape, false);
+        let batch = shape.slice()[..shape.ndim() - 2].iter().product::<usize>();
+        let len = decomposition.ranges(rows, columns).last().unwrap().end;
+
+        let data = Rc::new(RefCell::new(Array::zeros((batch, len))));
+        let op = Linalg::new(self.data.clone(), data.clone(), decomposition);
+
+        Var::node(data, Rc::new(op), self.history.clone())
     }
 }
 
-impl<D> Div<f32> for Var<D>
+impl<T> Var<Ix2, T>
 where
-    D: 'static + Dimension + DimMax<Ix0>,
+    T: Float,
 {
-    type Output = Var<<D as DimMax<Ix0>>::Output>;
+    /// Copies the output at `range` out of the packed outputs `self` of a decomposition, returning
+    /// a variable of shape `shape` with the result.
+    pub(crate) fn unpacked<E>(&self, shape: E, range: Range<usize>) -> Var<E, T>
+    where
+        E: 'static + Dimension,
+    {
+        let data = Rc::new(RefCell::new(Array::zeros(shape)));
+        let op = Unpack::new(self.data.clone(), data.clone(), range);
 
-    fn div(self, rhs: f32) -> Self::Output {
-        self / crate::full((), rhs)
+        Var::node(data, Rc::new(op), self.history.clone())
     }
 }
 
-// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ f32 - Var ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Arithmetic Operations Implementation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Var - Scalar ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
-impl<D> Add<Var<D>> for f32
+impl<D, T> Add<T> for Var<D, T>
 where
-    D: 'static + Dimension,
-    Ix0: DimMax<D>,
+    D: 'static + Dimension + DimMax<Ix0>,
+    T: Float,
 {
-    type Output = Var<<Ix0 as DimMax<D>>::Output>;
+    type Output = Var<<D as DimMax<Ix0>>::Output, T>;
 
-    fn add(self, rhs: Var<D>) -> Self::Output {
-        crate::full((), self) + rhs
+    fn add(self, rhs: T) -> Self::Output {
+        self + Var::leaf(arr0(rhs))
     }
 }
 
-impl<D> Sub<Var<D>> for f32
+impl<D, T> Sub<T> for Var<D, T>
 where
-    D: 'static + Dimension,
-    Ix0: DimMax<D>,
+    D: 'static + Dimension + DimMax<Ix0>,
+    T: Float,
 {
-    type Output = Var<<Ix0 as DimMax<D>>::Output>;
+    type Output = Var<<D as DimMax<Ix0>>::Output, T>;
 
-    fn sub(self, rhs: Var<D>) -> Self::Output {
-        crate::full((), self) - rhs
+    fn sub(self, rhs: T) -> Self::Output {
+        self - Var::leaf(arr0(rhs))
     }
 }
 
-impl<D> Mul<Var<D>> for f32
+impl<D, T> Mul<T> for Var<D, T>
 where
-    D: 'static + Dimension,
-    Ix0: DimMax<D>,
+    D: 'static + Dimension + DimMax<Ix0>,
+    T: Float,
 {
-    type Output = Var<<Ix0 as DimMax<D>>::Output>;
+    type Output = Var<<D as DimMax<Ix0>>::Output, T>;
 
-    fn mul(self, rhs: Var<D>) -> Self::Output {
-        crate::full((), self) * rhs
+    fn mul(self, rhs: T) -> Self::Output {
+        self * Var::leaf(arr0(rhs))
     }
 }
 
-impl<D> Div<Var<D>> for f32
+impl<D, T> Div<T> for Var<D, T>
 where
-    D: 'static + Dimension,
-    Ix0: DimMax<D>,
+    D: 'static + Dimension + DimMax<Ix0>,
+    T: Float,
 {
-    type Output = Var<<Ix0 as DimMax<D>>::Output>;
+    type Output = Var<<D as DimMax<Ix0>>::Output, T>;
 
-    fn div(self, rhs: Var<D>) -> Self::Output {
-        crate::full((), self) / rhs
+    fn div(self, rhs: T) -> Self::Output {
+        self / Var::leaf(arr0(rhs))
     }
 }
 
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Scalar - Var ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+macro_rules! impl_scalar_var_ops {
+    ($($t:ty),*) => {$(
+        impl<D> Add<Var<D, $t>> for $t
+        where
+            D: 'static + Dimension,
+            Ix0: DimMax<D>,
+        {
+            type Output = Var<<Ix0 as DimMax<D>>::Output, $t>;
+
+            fn add(self, rhs: Var<D, $t>) -> Self::Output {
+                Var::leaf(arr0(self)) + rhs
+            }
+        }
+
+        impl<D> Sub<Var<D, $t>> for $t
+        where
+            D: 'static + Dimension,
+            Ix0: DimMax<D>,
+        {
+            type Output = Var<<Ix0 as DimMax<D>>::Output, $t>;
+
+            fn sub(self, rhs: Var<D, $t>) -> Self::Output {
+                Var::leaf(arr0(self)) - rhs
+            }
+        }
+
+        impl<D> Mul<Var<D, $t>> for $t
+        where
+            D: 'static + Dimension,
+            Ix0: DimMax<D>,
+        {
+            type Output = Var<<Ix0 as DimMax<D>>::Output, $t>;
+
+            fn mul(self, rhs: Var<D, $t>) -> Self::Output {
+                Var::leaf(arr0(self)) * rhs
+            }
+        }
+
+        impl<D> Div<Var<D, $t>> for $t
+        where
+            D: 'static + Dimension,
+            Ix0: DimMax<D>,
+        {
+            type Output = Var<<Ix0 as DimMax<D>>::Output, $t>;
+
+            fn div(self, rhs: Var<D, $t>) -> Self::Output {
+                Var::leaf(arr0(self)) / rhs
+            }
+        }
+    )*};
+}
+
+impl_scalar_var_ops!(f32, f64, f16, bf16);
+
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Negation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
-impl<D> Neg for Var<D>
+impl<D, T> Neg for Var<D, T>
 where
     D: 'static + Dimension,
+    T: Float,
 {
-    type Output = Var<D>;
+    type Output = Var<D, T>;
 
     fn neg(self) -> Self::Output {
         let shape = self.data.borrow().raw_dim();
@@ -856,14 +2158,15 @@ where
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Addition ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
-impl<D, E> Add<Var<E>> for Var<D>
+impl<D, E, T> Add<Var<E, T>> for Var<D, T>
 where
     D: 'static + Dimension + DimMax<E>,
     E: 'static + Dimension,
+    T: Float,
 {
-    type Output = Var<<D as DimMax<E>>::Output>;
+    type Output = Var<<D as DimMax<E>>::Output, T>;
 
-    fn add(mut self, rhs: Var<E>) -> Self::Output {
+    fn add(mut self, rhs: Var<E, T>) -> Self::Output {
         self.history.merge(rhs.history);
 
         let data = Rc::new(RefCell::new(cobroadcasted_zeros(
@@ -876,19 +2179,20 @@ where
     }
 }
 
-impl<D, E> Add<VarDiff<E>> for Var<D>
+impl<D, E, T> Add<VarDiff<E, T>> for Var<D, T>
 where
     D: 'static + Dimension + DimMax<E>,
     E: 'static + Dimension,
+    T: Float,
 {
-    type Output = VarDiff<<D as DimMax<E>>::Output>;
+    type Output = VarDiff<<D as DimMax<E>>::Output, T>;
 
-    fn add(self, rhs: VarDiff<E>) -> Self::Output {
+    fn add(self, rhs: VarDiff<E, T>) -> Self::Output {
         let grad = Rc::new(Gradient::from_ndarray(cobroadcasted_zeros(
             &self.data.borrow(),
             &rhs.var.data.borrow(),
         )));
-        let op = AdditionBackwardRight::<D, E>::new(rhs.grad, grad.clone());
+        let op = AdditionBackwardRight::<D, E, T>::new(rhs.grad, grad.clone());
         let var = self.add(rhs.var);
 
         VarDiff::node(var, grad.clone(), (Rc::new(op), grad), rhs.history)
@@ -897,14 +2201,15 @@ where
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Subtraction ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
-impl<D, E> Sub<Var<E>> for Var<D>
+impl<D, E, T> Sub<Var<E, T>> for Var<D, T>
 where
     D: 'static + Dimension + DimMax<E>,
     E: 'static + Dimension,
+    T: Float,
 {
-    type Output = Var<<D as DimMax<E>>::Output>;
+    type Output = Var<<D as DimMax<E>>::Output, T>;
 
-    fn sub(mut self, rhs: Var<E>) -> Self::Output {
+    fn sub(mut self, rhs: Var<E, T>) -> Self::Output {
         self.history.merge(rhs.history);
 
         let data = Rc::new(RefCell::new(cobroadcasted_zeros(
@@ -917,19 +2222,20 @@ where
     }
 }
 
-impl<D, E> Sub<VarDiff<E>> for Var<D>
+impl<D, E, T> Sub<VarDiff<E, T>> for Var<D, T>
 where
     D: 'static + Dimension + DimMax<E>,
     E: 'static + Dimension,
+    T: Float,
 {
-    type Output = VarDiff<<D as DimMax<E>>::Output>;
+    type Output = VarDiff<<D as DimMax<E>>::Output, T>;
 
-    fn sub(self, rhs: VarDiff<E>) -> Self::Output {
+    fn sub(self, rhs: VarDiff<E, T>) -> Self::Output {
         let grad = Rc::new(Gradient::from_ndarray(cobroadcasted_zeros(
             &self.data.borrow(),
             &rhs.var.data.borrow(),
         )));
-        let op = SubtractionBackwardRight::<D, E>::new(rhs.grad, grad.clone());
+        let op = SubtractionBackwardRight::<D, E, T>::new(rhs.grad, grad.clone());
         let var = self.sub(rhs.var);
 
         VarDiff::node(var, grad.clone(), (Rc::new(op), grad), rhs.history)
@@ -938,14 +2244,15 @@ where
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Multiplication ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
-impl<D, E> Mul<Var<E>> for Var<D>
+impl<D, E, T> Mul<Var<E, T>> for Var<D, T>
 where
     D: 'static + Dimension + DimMax<E>,
     E: 'static + Dimension,
+    T: Float,
 {
-    type Output = Var<<D as DimMax<E>>::Output>;
+    type Output = Var<<D as DimMax<E>>::Output, T>;
 
-    fn mul(mut self, rhs: Var<E>) -> Self::Output {
+    fn mul(mut self, rhs: Var<E, T>) -> Self::Output {
         self.history.merge(rhs.history);
 
         let data = Rc::new(RefCell::new(cobroadcasted_zeros(
@@ -958,14 +2265,15 @@ where
     }
 }
 
-impl<D, E> Mul<VarDiff<E>> for Var<D>
+impl<D, E, T> Mul<VarDiff<E, T>> for Var<D, T>
 where
     D: 'static + Dimension + DimMax<E>,
     E: 'static + Dimension,
+    T: Float,
 {
-    type Output = VarDiff<<D as DimMax<E>>::Output>;
+    type Output = VarDiff<<D as DimMax<E>>::Output, T>;
 
-    fn mul(self, rhs: VarDiff<E>) -> Self::Output {
+    fn mul(self, rhs: VarDiff<E, T>) -> Self::Output {
         let grad = Rc::new(Gradient::from_ndarray(cobroadcasted_zeros(
             &self.data.borrow(),
             &rhs.var.data.borrow(),
@@ -980,14 +2288,15 @@ where
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Division ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
-impl<D, E> Div<Var<E>> for Var<D>
+impl<D, E, T> Div<Var<E, T>> for Var<D, T>
 where
     D: 'static + Dimension + DimMax<E>,
     E: 'static + Dimension,
+    T: Float,
 {
-    type Output = Var<<D as DimMax<E>>::Output>;
+    type Output = Var<<D as DimMax<E>>::Output, T>;
 
-    fn div(mut self, rhs: Var<E>) -> Self::Output {
+    fn div(mut self, rhs: Var<E, T>) -> Self::Output {
         self.history.merge(rhs.history);
 
         let data = Rc::new(RefCell::new(cobroadcasted_zeros(
@@ -1000,14 +2309,15 @@ where
     }
 }
 
-impl<D, E> Div<VarDiff<E>> for Var<D>
+impl<D, E, T> Div<VarDiff<E, T>> for Var<D, T>
 where
     D: 'static + Dimension + DimMax<E>,
     E: 'static + Dimension,
+    T: Float,
 {
-    type Output = VarDiff<<D as DimMax<E>>::Output>;
+    type Output = VarDiff<<D as DimMax<E>>::Output, T>;
 
-    fn div(self, rhs: VarDiff<E>) -> Self::Output {
+    fn div(self, rhs: VarDiff<E, T>) -> Self::Output {
         let grad = Rc::new(Gradient::from_ndarray(cobroadcasted_zeros(
             &self.data.borrow(),
             &rhs.var.data.borrow(),
@@ -1031,10 +2341,13 @@ where
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Matrix Multiplication ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
-impl MatMatMul<Var<Ix2>> for Var<Ix2> {
-    type Output = Var<Ix2>;
+impl<T> MatMatMul<Var<Ix2, T>> for Var<Ix2, T>
+where
+    T: Float,
+{
+    type Output = Var<Ix2, T>;
 
-    fn mm(mut self, rhs: Var<Ix2>) -> Self::Output {
+    fn mm(mut self, rhs: Var<Ix2, T>) -> Self::Output {
         self.history.merge(rhs.history);
 
         let shape = DotDim::shape(self.data.borrow().raw_dim(), rhs.data.borrow().raw_dim());
@@ -1045,10 +2358,13 @@ impl MatMatMul<Var<Ix2>> for Var<Ix2> {
     }
 }
 
-impl MatMatMul<VarDiff<Ix2>> for Var<Ix2> {
-    type Output = VarDiff<Ix2>;
+impl<T> MatMatMul<VarDiff<Ix2, T>> for Var<Ix2, T>
+where
+    T: Float,
+{
+    type Output = VarDiff<Ix2, T>;
 
-    fn mm(self, rhs: VarDiff<Ix2>) -> Self::Output {
+    fn mm(self, rhs: VarDiff<Ix2, T>) -> Self::Output {
         let grad = Rc::new(Gradient::ndarray_zeros(DotDim::shape(
             self.data.borrow().raw_dim(),
             rhs.var.data().raw_dim(),
@@ -1062,10 +2378,13 @@ impl MatMatMul<VarDiff<Ix2>> for Var<Ix2> {
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~ Matrix Multiplication with Transposition  ~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
-impl MatMatMulT<Var<Ix2>> for Var<Ix2> {
-    type Output = Var<Ix2>;
+impl<T> MatMatMulT<Var<Ix2, T>> for Var<Ix2, T>
+where
+    T: Float,
+{
+    type Output = Var<Ix2, T>;
 
-    fn mm_t(mut self, rhs: Var<Ix2>) -> Self::Output {
+    fn mm_t(mut self, rhs: Var<Ix2, T>) -> Self::Output {
         self.history.merge(rhs.history);
 
         let data = Rc::new(RefCell::new(Array::zeros(DotDim::shape(
@@ -1078,10 +2397,13 @@ impl MatMatMulT<Var<Ix2>> for Var<Ix2> {
     }
 }
 
-impl MatMatMulT<VarDiff<Ix2>> for Var<Ix2> {
-    type Output = VarDiff<Ix2>;
+impl<T> MatMatMulT<VarDiff<Ix2, T>> for Var<Ix2, T>
+where
+    T: Float,
+{
+    type Output = VarDiff<Ix2, T>;
 
-    fn mm_t(self, rhs: VarDiff<Ix2>) -> Self::Output {
+    fn mm_t(self, rhs: VarDiff<Ix2, T>) -> Self::Output {
         let grad = Rc::new(Gradient::ndarray_zeros(DotDim::shape(
             self.data.borrow().raw_dim(),
             rhs.var.data().t().raw_dim(),
@@ -1095,10 +2417,13 @@ impl MatMatMulT<VarDiff<Ix2>> for Var<Ix2> {
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ MatrixVectorMul ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
-impl MatVecMul<Var<Ix1>> for Var<Ix2> {
-    type Output = Var<Ix1>;
+impl<T> MatVecMul<Var<Ix1, T>> for Var<Ix2, T>
+where
+    T: Float,
+{
+    type Output = Var<Ix1, T>;
 
-    fn mv(mut self, rhs: Var<Ix1>) -> Self::Output {
+    fn mv(mut self, rhs: Var<Ix1, T>) -> Self::Output {
         self.history.merge(rhs.history);
 
         let data = Rc::new(RefCell::new(Array::zeros(DotDim::shape(
@@ -1111,10 +2436,13 @@ impl MatVecMul<Var<Ix1>> for Var<Ix2> {
     }
 }
 
-impl MatVecMul<VarDiff<Ix1>> for Var<Ix2> {
-    type Output = VarDiff<Ix1>;
+impl<T> MatVecMul<VarDiff<Ix1, T>> for Var<Ix2, T>
+where
+    T: Float,
+{
+    type Output = VarDiff<Ix1, T>;
 
-    fn mv(self, rhs: VarDiff<Ix1>) -> Self::Output {
+    fn mv(self, rhs: VarDiff<Ix1, T>) -> Self::Output {
         let grad = Rc::new(Gradient::ndarray_zeros(DotDim::shape(
             self.data.borrow().raw_dim(),
             rhs.var.data().raw_dim(),
@@ -1128,10 +2456,13 @@ impl MatVecMul<VarDiff<Ix1>> for Var<Ix2> {
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ VectorMatrixMul ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
-impl VecMatMul<Var<Ix2>> for Var<Ix1> {
-    type Output = Var<Ix1>;
+impl<T> VecMatMul<Var<Ix2, T>> for Var<Ix1, T>
+where
+    T: Float,
+{
+    type Output = Var<Ix1, T>;
 
-    fn vm(mut self, rhs: Var<Ix2>) -> Self::Output {
+    fn vm(mut self, rhs: Var<Ix2, T>) -> Self::Output {
         self.history.merge(rhs.history);
 
         let data = Rc::new(RefCell::new(Array::zeros(DotDim::shape(
@@ -1144,10 +2475,13 @@ impl VecMatMul<Var<Ix2>> for Var<Ix1> {
     }
 }
 
-impl VecMatMul<VarDiff<Ix2>> for Var<Ix1> {
-    type Output = VarDiff<Ix1>;
+impl<T> VecMatMul<VarDiff<Ix2, T>> for Var<Ix1, T>
+where
+    T: Float,
+{
+    type Output = VarDiff<Ix1, T>;
 
-    fn vm(self, rhs: VarDiff<Ix2>) -> Self::Output {
+    fn vm(self, rhs: VarDiff<Ix2, T>) -> Self::Output {
         let grad = Rc::new(Gradient::ndarray_zeros(DotDim::shape(
             self.data.borrow().raw_dim(),
             rhs.var.data().raw_dim(),
@@ -1161,23 +2495,29 @@ impl VecMatMul<VarDiff<Ix2>> for Var<Ix1> {
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ VectorVectorMul ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
-impl VecVecMul<Var<Ix1>> for Var<Ix1> {
-    type Output = Var<Ix0>;
+impl<T> VecVecMul<Var<Ix1, T>> for Var<Ix1, T>
+where
+    T: Float,
+{
+    type Output = Var<Ix0, T>;
 
-    fn vv(mut self, rhs: Var<Ix1>) -> Self::Output {
+    fn vv(mut self, rhs: Var<Ix1, T>) -> Self::Output {
         self.history.merge(rhs.history);
 
-        let data = Rc::new(RefCell::new(arr0(0.)));
+        let data = Rc::new(RefCell::new(arr0(T::zero())));
         let op = VectorVectorMul::new(self.data, rhs.data, data.clone());
 
         Var::node(data, Rc::new(op), self.history)
     }
 }
 
-impl VecVecMul<VarDiff<Ix1>> for Var<Ix1> {
-    type Output = VarDiff<Ix0>;
+impl<T> VecVecMul<VarDiff<Ix1, T>> for Var<Ix1, T>
+where
+    T: Float,
+{
+    type Output = VarDiff<Ix0, T>;
 
-    fn vv(self, rhs: VarDiff<Ix1>) -> Self::Output {
+    fn vv(self, rhs: VarDiff<Ix1, T>) -> Self::Output {
         let grad = Rc::new(Gradient::from_ndarray(Array::zeros(())));
         let op = VectorVectorMulBackwardUnary::new(self.data.clone(), rhs.grad, grad.clone());
         let var = self.vv(rhs.var);
@@ -1192,46 +2532,44 @@ impl VecVecMul<VarDiff<Ix1>> for Var<Ix1> {
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Concatenate ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
-impl<D> Cat<Var<D>> for Var<D>
+impl<D, T> Cat<Var<D, T>> for Var<D, T>
 where
     D: 'static + Dimension + RemoveAxis,
+    T: Float,
 {
-    type Output = Var<D>;
+    type Output = Var<D, T>;
 
-    fn cat(mut self, rhs: Var<D>, axis: usize) -> Self::Output {
+    fn cat(mut self, rhs: Var<D, T>, axis: usize) -> Self::Output {
         self.history.merge(rhs.history);
 
-        let data = Rc::new(RefCell::new(
-            concatenate(
-                Axis(axis),
-                &[
-                    Array::zeros(self.data.borrow().raw_dim()).view(),
-                    Array::zeros(rhs.data.borrow().raw_dim()).view(),
-                ],
-            )
-            .unwrap(),
-        ));
+        let data = Rc::new(RefCell::new(concatenate_checked(
+            axis,
+            &[
+                Array::zeros(self.data.borrow().raw_dim()).view(),
+                Array::zeros(rhs.data.borrow().raw_dim()).view(),
+            ],
+        )));
         let op = Concatenate::new(self.data, rhs.data, data.clone(), axis);
 
         Var::node(data, Rc::new(op), self.history)
     }
 }
 
-impl<D> Cat<VarDiff<D>> for Var<D>
+impl<D, T> Cat<VarDiff<D, T>> for Var<D, T>
 where
     D: 'static + Dimension + RemoveAxis,
+    T: Float,
 {
-    type Output = VarDiff<D>;
+    type Output = VarDiff<D, T>;
 
-    fn cat(self, rhs: VarDiff<D>, axis: usize) -> Self::Output {
-        let array = concatenate(
-            Axis(axis),
+    fn cat(self, rhs: VarDiff<D, T>, axis: usize) -> Self::Output {
+        let array = concatenate_checked(
+            axis,
             &[
                 Array::zeros(self.data.borrow().raw_dim()).view(),
                 Array::zeros(rhs.var.data.borrow().raw_dim()).view(),
             ],
-        )
-        .unwrap();
+        );
         let grad = Rc::new(Gradient::from_ndarray(array));
         let offset = self.data.borrow().len_of(Axis(axis));
         let op = ConcatenateBackwardRight::new(rhs.grad, grad.clone(), axis, offset);
@@ -1243,46 +2581,44 @@ where
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Stack ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
-impl<D> Stack<Var<D>> for Var<D>
+impl<D, T> Stack<Var<D, T>> for Var<D, T>
 where
     D: 'static + Dimension + RemoveAxis,
+    T: Float,
 {
-    type Output = Var<D::Larger>;
+    type Output = Var<D::Larger, T>;
 
-    fn stack(mut self, rhs: Var<D>, axis: usize) -> Self::Output {
+    fn stack(mut self, rhs: Var<D, T>, axis: usize) -> Self::Output {
         self.history.merge(rhs.history);
 
-        let data = Rc::new(RefCell::new(
-            stack(
-                Axis(axis),
-                &[
-                    Array::zeros(self.data.borrow().raw_dim()).view(),
-                    Array::zeros(rhs.data.borrow().raw_dim()).view(),
-                ],
-            )
-            .unwrap(),
-        ));
+        let data = Rc::new(RefCell::new(stack_checked(
+            axis,
+            &[
+                Array::zeros(self.data.borrow().raw_dim()).view(),
+                Array::zeros(rhs.data.borrow().raw_dim()).view(),
+            ],
+        )));
         let op = node::Stack::new(self.data, rhs.data, data.clone(), axis);
 
         Var::node(data, Rc::new(op), self.history)
     }
 }
 
-impl<D> Stack<VarDiff<D>> for Var<D>
+impl<D, T> Stack<VarDiff<D, T>> for Var<D, T>
 where
     D: 'static + Dimension + RemoveAxis,
+    T: Float,
 {
-    type Output = VarDiff<D::Larger>;
+    type Output = VarDiff<D::Larger, T>;
 
-    fn stack(self, rhs: VarDiff<D>, axis: usize) -> Self::Output {
-        let array = stack(
-            Axis(axis),
+    fn stack(self, rhs: VarDiff<D, T>, axis: usize) -> Self::Output {
+        let array = stack_checked(
+            axis,
             &[
                 Array::zeros(self.data.borrow().raw_dim()).view(),
                 Array::zeros(rhs.var.data.borrow().raw_dim()).view(),
             ],
-        )
-        .unwrap();
+        );
         let grad = Rc::new(Gradient::from_ndarray(array));
         let op = StackBackwardRight::new(rhs.grad, grad.clone(), axis);
         let var = Stack::stack(self, rhs.var, axis);
@@ -1291,17 +2627,188 @@ where
     }
 }
 
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Where ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl<D, M, T> Where<Var<M, bool>, Var<D, T>> for Var<D, T>
+where
+    D: 'static + Dimension,
+    M: 'static + Dimension,
+    T: Float,
+{
+    type Output = Var<D, T>;
+
+    fn where_(mut self, mask: Var<M, bool>, rhs: Var<D, T>) -> Self::Output {
+        let shape = self.data.borrow().raw_dim();
+        assert_eq!(
+            shape,
+            rhs.data.borrow().raw_dim(),
+            "error: the two variables have mismatching shapes."
+        );
+        assert!(
+            mask.data.borrow().broadcast(shape.clone()).is_some(),
+            "error: the mask cannot be broadcast to the shape of the variables."
+        );
+
+        self.history.merge(mask.history);
+        self.history.merge(rhs.history);
+
+        let data = Rc::new(RefCell::new(Array::zeros(shape)));
+        let op = node::Where::new(mask.data, self.data, rhs.data, data.clone());
+
+        Var::node(data, Rc::new(op), self.history)
+    }
+}
+
+impl<D, M, T> Where<Var<M, bool>, VarDiff<D, T>> for Var<D, T>
+where
+    D: 'static + Dimension,
+    M: 'static + Dimension,
+    T: Float,
+{
+    type Output = VarDiff<D, T>;
+
+    fn where_(self, mask: Var<M, bool>, rhs: VarDiff<D, T>) -> Self::Output {
+        let grad = Rc::new(Gradient::ndarray_zeros(rhs.grad.shape()));
+        let op = WhereBackwardRight::new(mask.data.clone(), rhs.grad, grad.clone());
+        let var = Where::where_(self, mask, rhs.var);
+
+        VarDiff::node(var, grad.clone(), (Rc::new(op), grad), rhs.history)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Solve ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl<D, T> Solve<Var<D, T>> for Var<D, T>
+where
+    D: 'static + Dimension,
+    T: Float,
+{
+    type Output = Var<D, T>;
+
+    fn solve(mut self, rhs: Var<D, T>) -> Self::Output {
+        let shape = self.data.borrow().raw_dim();
+        let rhs_shape = rhs.data.borrow().raw_dim();
+        matrices_shape(&shape, true);
+        matrices_shape(&rhs_shape, false);
+        assert!(
+            shape.ndim() == rhs_shape.ndim()
+                && shape.slice()[..shape.ndim() - 1] == rhs_shape.slice()[..rhs_shape.ndim() - 1],
+            "error: cannot solve linear systems with coefficients of shape {:?} and right hand \
+             sides of shape {:?}.",
+            shape.slice(),
+            rhs_shape.slice()
+        );
+
+        self.history.merge(rhs.history);
+
+        let data = Rc::new(RefCell::new(Array::zeros(rhs_shape)));
+        let op = node::Solve::new(self.data, rhs.data, data.clone());
+
+        Var::node(data, Rc::new(op), self.history)
+    }
+}
+
+impl<D, T> Solve<VarDiff<D, T>> for Var<D, T>
+where
+    D: 'static + Dimension,
+    T: Float,
+{
+    type Output = VarDiff<D, T>;
+
+    fn solve(self, rhs: VarDiff<D, T>) -> Self::Output {
+        let grad = Rc::new(Gradient::ndarray_zeros(rhs.grad.shape()));
+        let op = SolveBackwardRight::new(self.data.clone(), rhs.grad, grad.clone());
+        let var = Solve::solve(self, rhs.var);
+
+        VarDiff::node(var, grad.clone(), (Rc::new(op), grad), rhs.history)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Attention ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl<T> Attention<Var<Ix4, T>, Var<Ix4, T>, AttentionMask<T>> for Var<Ix4, T>
+where
+    T: Float,
+{
+    type Output = Var<Ix4, T>;
+
+    fn scaled_dot_product_attention(
+        self,
+        key: Var<Ix4, T>,
+        value: Var<Ix4, T>,
+        mask: Option<AttentionMask<T>>,
+        dropout: f64,
+        status: Rc<Cell<bool>>,
+        causal: bool,
+    ) -> Self::Output {
+        let (_, weights_shape) = attention_shapes(
+            self.data.borrow().raw_dim(),
+            key.data.borrow().raw_dim(),
+            value.data.borrow().raw_dim(),
+        );
+        let state = (dropout > 0.).then(|| Rc::new(RefCell::new(None)));
+        let logsumexp = Rc::new(RefCell::new(Array::zeros(
+            weights_shape.remove_axis(Axis(3)),
+        )));
+
+        self.attention(
+            key,
+            value,
+            mask,
+            (dropout, status, state),
+            causal,
+            logsumexp,
+        )
+    }
+}
+
+impl<T> Attention<VarDiff<Ix4, T>, VarDiff<Ix4, T>, AttentionMask<T>> for Var<Ix4, T>
+where
+    T: Float,
+{
+    type Output = VarDiff<Ix4, T>;
+
+    fn scaled_dot_product_attention(
+        self,
+        mut key: VarDiff<Ix4, T>,
+        value: VarDiff<Ix4, T>,
+        mask: Option<AttentionMask<T>>,
+        dropout: f64,
+        status: Rc<Cell<bool>>,
+        causal: bool,
+    ) -> Self::Output {
+        key.history.merge(value.history);
+
+        VarDiff::attention(
+            (self, None),
+            (key.var, Some(key.grad)),
+            (value.var, Some(value.grad)),
+            mask,
+            (dropout, status),
+            causal,
+            key.history,
+        )
+    }
+}
+
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Convolution ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
-impl<D> Convolution<Var<D>, <D::Smaller as Dimension>::Smaller> for Var<D>
+impl<D, T> Convolution<Var<D, T>, <D::Smaller as Dimension>::Smaller> for Var<D, T>
 where
     D: 'static + Dimension + RemoveAxis,
+    T: Float,
 {
-    type Output = Var<D>;
+    type Output = Var<D, T>;
 
-    fn convolution<T>(self, input: Var<D>, stride: T, dilation: T, groups: usize) -> Self::Output
+    fn convolution<S>(
+        mut self,
+        input: Var<D, T>,
+        stride: S,
+        dilation: S,
+        groups: usize,
+    ) -> Self::Output
     where
-        T: IntoDimension<Dim = <D::Smaller as Dimension>::Smaller>,
+        S: IntoDimension<Dim = <D::Smaller as Dimension>::Smaller>,
     {
         let stride = stride.into_dimension();
         let dilation = dilation.into_dimension();
@@ -1321,6 +2828,8 @@ where
 
             conv_out_shape(input_shape, kernel_shape, stride_slice, dilation_slice)
         };
+        self.history.merge(input.history);
+
         let data = Rc::new(RefCell::new(Array::zeros(shape)));
         let op = node::Convolution::new(
             input.data,
@@ -1335,21 +2844,22 @@ where
     }
 }
 
-impl<D> Convolution<VarDiff<D>, <D::Smaller as Dimension>::Smaller> for Var<D>
+impl<D, T> Convolution<VarDiff<D, T>, <D::Smaller as Dimension>::Smaller> for Var<D, T>
 where
     D: 'static + Dimension + RemoveAxis,
+    T: Float,
 {
-    type Output = VarDiff<D>;
+    type Output = VarDiff<D, T>;
 
-    fn convolution<T>(
+    fn convolution<S>(
         self,
-        input: VarDiff<D>,
-        stride: T,
-        dilation: T,
+        input: VarDiff<D, T>,
+        stride: S,
+        dilation: S,
         groups: usize,
     ) -> Self::Output
     where
-        T: IntoDimension<Dim = <D::Smaller as Dimension>::Smaller> + Copy,
+        S: IntoDimension<Dim = <D::Smaller as Dimension>::Smaller> + Copy,
     {
         let kernel_data = self.data.clone();
         let var = self.convolution(input.var, stride, dilation, groups);
@@ -1372,9 +2882,10 @@ where
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Debug ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
-impl<D> Debug for Var<D>
+impl<D, T> Debug for Var<D, T>
 where
     D: 'static + Dimension,
+    T: Element,
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         Debug::fmt(&self.data.borrow(), f)
@@ -1383,9 +2894,10 @@ where
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Display ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
-impl<D> Display for Var<D>
+impl<D, T> Display for Var<D, T>
 where
     D: 'static + Dimension,
+    T: Element,
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.data.borrow())
@@ -1,25 +1,42 @@
 use std::{
-    cell::{Cell, Ref, RefCell, RefMut},
+    collections::{HashMap, HashSet},
     fmt::{Debug, Display},
-    ops::{Add, Div, Mul, Neg, Sub},
-    rc::Rc,
+    ops::{Add, Div, Mul, Neg, Range, Sub},
 };
 
 use ndarray::{
-    arr0, concatenate, stack, Array, Axis, DimMax, Dimension, IntoDimension, Ix0, Ix1, Ix2,
-    RemoveAxis, Zip,
+    arr0, Array, Axis, DimMax, Dimension, IntoDimension, Ix0, Ix1, Ix2, Ix4, IxDyn, RemoveAxis, Zip,
 };
 
 use crate::{
+    anomaly::{self, BackwardChecks},
     autograd::Backward,
-    gradient::{BufferedGradient, Gradient, NoGrad},
+    bf16,
+    cell::{Cell, MaybeSync, Rc, Ref, RefCell, RefMut},
+    f16, fusion,
+    gradient::{BufferedGradient, Gradient, NodeGradient},
+    graph::Graph,
     history::History,
+    hook::HookHandle,
+    memory::{assert_data_unplanned, assert_unplanned, read_by_backward, MemoryPlan, MemoryReport},
     node::*,
-    utils::{cobroadcasted_zeros, DotDim},
+    parameter::{Leaf, LeafVariable},
+    utils::{
+        attention_shapes, batch_shape, broadcast_shapes, cobroadcasted_zeros, concatenate_checked,
+        interleaved_shapes, matrices_shape, qr_shapes, stack_checked, svd_shapes, swapped_axes,
+        tiled_shapes, DotDim, Shared,
+    },
     var::Var,
-    Cat, Convolution, MatMatMul, MatMatMulT, MatVecMul, Reduction, Stack, VecMatMul, VecVecMul,
+    AsIndex, Attention, Cat, Convolution, Float, MatMatMul, MatMatMulT, MatVecMul, Parameter,
+    Reduction, Solve, Stack, VecMatMul, VecVecMul, Where,
 };
 
+/// The tape of a differentiable variable, holding the gradients of its nodes and leaves.
+pub(crate) type DiffHistory = History<(Rc<dyn Backward>, Rc<dyn NodeGradient>), Rc<dyn Leaf>>;
+
+/// An operand of the attention, together with its gradient if it is differentiable.
+type AttentionOperand<T> = (Var<Ix4, T>, Option<Rc<Gradient<Array<T, Ix4>, Ix4>>>);
+
 /// A differentiable variable.
 ///
 /// Differentiable variables can be created in the **two** following ways described hereafter:
@@ -32,43 +49,43 @@ use crate::{
 /// it, will also be differentiable. As an obvious consequence, the results of operations
 /// performed on two *VarDiff* will also be *VarDiff*.
 #[derive(Clone)]
-pub struct VarDiff<D>
+pub struct VarDiff<D, T = f32>
 where
     D: Dimension,
+    T: Float,
 {
-    pub(crate) var: Var<D>,
-    pub(crate) grad: Rc<Gradient<Array<f32, D>, D>>,
-    pub(crate) history: History<(Rc<dyn Backward>, Rc<dyn NoGrad>)>,
+    pub(crate) var: Var<D, T>,
+    pub(crate) grad: Rc<Gradient<Array<T, D>, D>>,
+    pub(crate) history: DiffHistory,
+    pub(crate) plan: Shared<Option<MemoryPlan>>,
 }
 
-impl<D> VarDiff<D>
+impl<D, T> VarDiff<D, T>
 where
     D: Dimension,
+    T: Float,
 {
-    pub(crate) fn leaf(var: Var<D>, array: Array<f32, D>) -> Self {
+    pub(crate) fn node(
+        var: Var<D, T>,
+        grad: Rc<Gradient<Array<T, D>, D>>,
+        op: (Rc<dyn Backward>, Rc<dyn NodeGradient>),
+        mut history: DiffHistory,
+    ) -> VarDiff<D, T> {
+        history.insert(Rc::as_ptr(&var.data) as usize, op);
+
         Self {
             var,
-            grad: Rc::new(Gradient::from_ndarray(array)),
-            history: History::default(),
+            grad,
+            history,
+            plan: Rc::default(),
         }
     }
 
-    pub(crate) fn node(
-        var: Var<D>,
-        grad: Rc<Gradient<Array<f32, D>, D>>,
-        op: (Rc<dyn Backward>, Rc<dyn NoGrad>),
-        mut history: History<(Rc<dyn Backward>, Rc<dyn NoGrad>)>,
-    ) -> VarDiff<D> {
-        history.insert(Rc::as_ptr(&op.0) as *const () as usize, op);
-
-        Self { var, grad, history }
-    }
-
     /// Returns an immutable reference to the data inside `self`.
     ///
     /// At the differentiable variable's creation the data is filled with zeros. You can populate it
     /// with a call to [`.forward()`](VarDiff::forward()).
-    pub fn data(&self) -> Ref<Array<f32, D>> {
+    pub fn data(&self) -> Ref<Array<T, D>> {
         self.var.data()
     }
 
@@ -76,7 +93,7 @@ where
     ///
     /// At the differentiable variable's creation the data is filled with zeros. You can populate it
     /// with a call to [`.forward()`](VarDiff::forward()).
-    pub fn data_mut(&self) -> RefMut<Array<f32, D>> {
+    pub fn data_mut(&self) -> RefMut<Array<T, D>> {
         self.var.data_mut()
     }
 
@@ -84,7 +101,7 @@ where
     ///
     /// At the differentiable variable's creation the gradient is filled with zeros. You can
     /// populate it with a call to [`.backward()`](VarDiff::backward()).
-    pub fn grad(&self) -> Ref<Array<f32, D>> {
+    pub fn grad(&self) -> Ref<Array<T, D>> {
         self.grad.borrow()
     }
 
@@ -92,19 +109,76 @@ where
     ///
     /// At the differentiable variable's creation the gradient is filled with zeros. You can
     /// populate it with a call to [`.backward()`](VarDiff::backward()).
-    pub fn grad_mut(&self) -> RefMut<Array<f32, D>> {
+    pub fn grad_mut(&self) -> RefMut<Array<T, D>> {
         self.grad.borrow_mut()
     }
 
     /// Sets the variable's gradient to zero.
     pub fn zero_grad(&self) {
-        Zip::from(&mut *self.grad_mut()).for_each(|grad_el| *grad_el = 0.0);
+        Zip::from(&mut *self.grad_mut()).for_each(|grad_el| *grad_el = T::zero());
+    }
+
+    /// Returns a non-differentiable variable that shares the data of `self`.
+    ///
+    /// The returned variable keeps track of the computations `self` depends on, so its data is
+    /// recomputed by [`.forward()`](Var::forward()), but no gradient flows through it during the
+    /// backward pass.
+    ///
+    /// **Do note** that the data is shared, thus, modifying it in place through the returned
+    /// variable also modifies the data of `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use neuronika_variable as neuronika;
+    /// let x = neuronika::ones(3).requires_grad();
+    /// let y = x.clone() * 2.;
+    ///
+    /// // Gradients flow only through the left operand.
+    /// let z = (y.clone() * y.detach()).sum();
+    /// z.forward();
+    /// z.backward(1.);
+    ///
+    /// assert_eq!(*x.grad(), ndarray::arr1(&[4., 4., 4.]));
+    /// ```
+    pub fn detach(&self) -> Var<D, T> {
+        self.var.clone()
+    }
+
+    /// Enables the fusion of the element-wise operations of the computational graph of `self`,
+    /// for both the forward and the backward pass.
+    ///
+    /// See [`Var::fuse()`] for more details. The hooks registered on the gradient of a node are
+    /// still run once the gradient is fully accumulated, while the passes of graphs whose memory
+    /// has been planned with [`.plan_memory()`](VarDiff::plan_memory()) are not fused.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use neuronika_variable as neuronika;
+    /// let x = neuronika::rand(1000).requires_grad();
+    /// let y = ((x.clone() * 3. + 1.).sigmoid() * 2. - 1.).sum();
+    ///
+    /// y.fuse();
+    /// y.forward();
+    /// y.backward(1.);
+    /// ```
+    pub fn fuse(&self) {
+        self.var.fuse();
     }
 
     /// Propagates the computations forwards and populates all the variables and differentiable
     /// variables from the leaves of the graph to `self`.   
     pub fn forward(&self) {
-        self.var.forward();
+        // The fused ops store the data read by the backward pass.
+        let read = match self.var.fused.get() {
+            true => read_by_backward(
+                self.var.history.iter().map(|(_, op)| op),
+                self.history.iter(),
+            ),
+            false => HashSet::new(),
+        };
+        self.var.run_forward(self.plan.borrow().as_ref(), &read);
 
         // Prepares the buffer for the backward pass.
         let mut buffer = self.history.buffer_mut();
@@ -122,22 +196,147 @@ where
     /// **Do note** that this method should be called after `.forward()`.
     ///
     /// The graph is differentiated through the [chain rule](https://en.wikipedia.org/wiki/Chain_rule).
-    pub fn backward(&self, seed: f32) {
+    pub fn backward(&self, seed: T) {
         assert_eq!(
             self.var.history.len(),
             self.var.history.buffer_len(),
             "Perhaps you forgot to call .forward()?"
         );
 
+        let buffer = self.history.buffer();
+        let plan = self.plan.borrow();
+
+        // Clear the gradients left over by previous passes.
+        match &*plan {
+            Some(plan) => plan.zero(&buffer),
+            None => {
+                assert_unplanned(&buffer);
+                buffer.iter().for_each(|(_, grad)| grad.zero())
+            }
+        }
+        self.history
+            .leaves()
+            .for_each(|leaf| leaf.begin_accumulation());
+
         // Seed the gradient.
         self.grad_mut().fill(seed);
 
-        // Compute gradients.
+        // Compute gradients. When a node is reached its gradient is fully accumulated.
+        let checks = anomaly::is_anomaly_detection_enabled()
+            .then(|| BackwardChecks::new(&self.var.history, &self.history));
+        match &*plan {
+            None if self.var.fused.get() && checks.is_none() => fusion::backward::<T>(&buffer),
+            plan => {
+                let mut pool = plan.as_ref().map(MemoryPlan::take_pool).unwrap_or_default();
+                buffer
+                    .iter()
+                    .enumerate()
+                    .rev()
+                    .for_each(|(position, (op, grad))| {
+                        if let Some(plan) = plan {
+                            plan.allocate(position, &buffer, &mut pool);
+                        }
+
+                        grad.run_hooks();
+                        op.backward();
+
+                        if let Some(checks) = &checks {
+                            checks.check(position);
+                        }
+
+                        if let Some(plan) = plan {
+                            plan.release(position, &buffer, &mut pool);
+                        }
+                    });
+            }
+        }
+
         self.history
-            .buffer()
+            .leaves()
+            .for_each(|leaf| leaf.end_accumulation());
+    }
+
+    /// Plans the memory of the forward and backward passes of `self` and returns the peak memory
+    /// used by its computational graph before and after the planning.
+    ///
+    /// Once planned, the gradients of the intermediate nodes of the graph are allocated only when
+    /// the backward pass reaches them and are de-allocated as soon as they have been
+    /// back-propagated. Likewise, the data of the intermediate nodes that no back-propagation
+    /// reads, such as the results of additions and subtractions, is allocated only when the
+    /// forward pass computes it and is de-allocated as soon as its consumers have been computed.
+    /// The buffers de-allocated are recycled by the data and the gradients allocated afterwards,
+    /// so that the ones whose lifetimes don't overlap share the same memory. The data and the
+    /// gradients of `self` and of the leaves, as well as the data read by the backward pass, are
+    /// left untouched. The results are unchanged.
+    ///
+    /// **Do note** that this method should be called after `.forward()` and that the memory of
+    /// the intermediate nodes belongs to the plan from then on: their gradients can no longer be
+    /// accessed, the data de-allocated is empty outside of the forward pass, and only `self` can
+    /// propagate through them.
+    ///
+    /// # Panics
+    ///
+    /// If the memory of a node of the graph has already been planned by another variable. The
+    /// forward and backward passes of a variable whose graph contains such nodes panic too.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use neuronika_variable as neuronika;
+    /// let w = neuronika::rand((10, 10)).requires_grad();
+    /// let x = neuronika::rand((4, 10));
+    /// let y = x.mm(w.clone()).relu().mm(w.clone()).relu().mm(w.clone()).sum();
+    ///
+    /// y.forward();
+    /// let report = y.plan_memory();
+    /// assert!(report.after() < report.before());
+    ///
+    /// y.backward(1.);
+    /// ```
+    pub fn plan_memory(&self) -> MemoryReport {
+        assert_eq!(
+            self.var.history.len(),
+            self.var.history.buffer_len(),
+            "Perhaps you forgot to call .forward()?"
+        );
+
+        let ids: Vec<usize> = self.history.iter().map(|(id, _)| id).collect();
+        let root = Rc::as_ptr(&self.var.data) as usize;
+
+        let forward = self.var.history.buffer();
+        let backward = self.history.buffer();
+        if self.plan.borrow().is_none() {
+            assert_data_unplanned(&forward);
+            assert_unplanned(&backward);
+        }
+
+        let graph = self.graph();
+        let leaves: usize = graph
+            .nodes()
             .iter()
-            .rev()
-            .for_each(|(op, _)| op.backward());
+            .filter(|node| node.is_leaf())
+            .map(|node| node.data_bytes())
+            .sum::<usize>()
+            + self
+                .history
+                .leaves()
+                .map(|leaf| leaf.bytes())
+                .sum::<usize>();
+
+        let data: usize = forward.iter().map(|(_, _, data)| data.bytes()).sum();
+        let gradients: usize = backward.iter().flat_map(|(_, grad)| grad.sizes()).sum();
+        let before = leaves + data + gradients;
+
+        let plan = match MemoryPlan::new(&forward, &ids, &backward, root) {
+            Some(plan) => plan,
+            None => return MemoryReport::new(before, before),
+        };
+
+        let after = leaves + plan.peak::<T>(&forward, &backward);
+        plan.hand_over(&forward, &backward);
+        *self.plan.borrow_mut() = Some(plan);
+
+        MemoryReport::new(before, after)
     }
 
     /// Disables gradient computation and de-allocates the gradient for `self` and all of its
@@ -163,16 +362,188 @@ where
 
         buffer.iter().for_each(|(_, grad)| grad.with_grad());
     }
+
+    /// Returns the computational graph of `self` in the [Graphviz](https://graphviz.org/) DOT
+    /// language.
+    ///
+    /// Each operation is labelled with its name and the shape of its result, leaves are drawn as
+    /// ellipses and the differentiable ones are filled. The edges along which the gradient flows
+    /// are highlighted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use neuronika_variable as neuronika;
+    /// let w = neuronika::ones((2, 3)).requires_grad();
+    /// let x = neuronika::ones((4, 3));
+    /// let y = x.mm_t(w).relu().sum();
+    ///
+    /// let dot = y.to_dot();
+    /// assert!(dot.contains("MatrixMatrixMulT"));
+    /// ```
+    pub fn to_dot(&self) -> String {
+        self.graph().to_dot()
+    }
+
+    /// Returns a read-only snapshot of the computational graph of `self`, including the memory
+    /// used by the gradients.
+    ///
+    /// See [`Graph`] for an example.
+    pub fn graph(&self) -> Graph {
+        let gradients = self
+            .history
+            .iter()
+            .map(|(id, (_, grad))| (id, grad.bytes()))
+            .chain(
+                self.history
+                    .iter_leaves()
+                    .map(|(id, grad)| (id, grad.bytes())),
+            )
+            .collect();
+
+        self.var.graph_with(&gradients)
+    }
+
+    /// Returns handles to the differentiable leaves `self` depends on, that is, its parameters,
+    /// in the same order as the nodes of its [`.graph()`](VarDiff::graph()).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use neuronika_variable as neuronika;
+    /// let w = neuronika::ones((2, 3)).requires_grad();
+    /// let b = neuronika::zeros(2).requires_grad();
+    /// let x = neuronika::ones((4, 3));
+    /// let y = (x.mm_t(w) + b).relu().sum();
+    ///
+    /// y.forward();
+    /// y.backward(1.);
+    ///
+    /// let parameters = y.parameters();
+    /// assert_eq!(parameters.len(), 2);
+    /// assert_eq!(parameters[0].shape(), &[2, 3]);
+    /// assert_eq!(parameters[0].grad(), ndarray::Array::from_elem((2, 3), 4.).into_dyn());
+    ///
+    /// parameters.iter().for_each(|parameter| parameter.zero_grad());
+    /// assert!(parameters[1].grad().iter().all(|&el| el == 0.));
+    /// ```
+    pub fn parameters(&self) -> Vec<Parameter<T>> {
+        let leaves: HashMap<usize, &Rc<dyn Leaf>> = self.history.iter_leaves().collect();
+        let root = Rc::as_ptr(&self.var.data) as usize;
+
+        let mut found = HashSet::new();
+        self.var
+            .history
+            .iter()
+            .flat_map(|(_, (op, _, _))| op.operands())
+            .map(|operand| operand.id)
+            .chain(std::iter::once(root))
+            .filter_map(|id| leaves.get(&id).filter(|_| found.insert(id)))
+            .map(|leaf| *leaf.parameter().downcast().unwrap())
+            .collect()
+    }
+}
+
+impl<D, T> VarDiff<D, T>
+where
+    D: 'static + Dimension,
+    T: Float,
+{
+    pub(crate) fn leaf(var: Var<D, T>, array: Array<T, D>) -> Self {
+        Self::from_leaf(var, Rc::new(Gradient::from_ndarray(array)))
+    }
+
+    /// Returns the differentiable leaf of `var` whose gradient is `grad`.
+    pub(crate) fn from_leaf(var: Var<D, T>, grad: Rc<Gradient<Array<T, D>, D>>) -> Self {
+        let mut history = History::default();
+        history.insert_leaf(
+            Rc::as_ptr(&var.data) as usize,
+            Rc::new(LeafVariable::new(var.clone(), grad.clone())) as Rc<dyn Leaf>,
+        );
+
+        Self {
+            var,
+            grad,
+            history,
+            plan: Rc::default(),
+        }
+    }
+
+    /// Registers a hook on the gradient of `self` and returns a handle that can be used to remove
+    /// it.
+    ///
+    /// During [`.backward()`](VarDiff::backward()) the hook is called with the gradient of `self`
+    /// once it is fully accumulated and before it is propagated to the ancestors, thus, any change
+    /// made by the hook affects all of them. Hooks run in the order they were registered.
+    ///
+    /// When `self` is a leaf the hook sees only the contribution of the current backward pass,
+    /// which is then accumulated into the gradient.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use neuronika_variable as neuronika;
+    /// let x = neuronika::ones(3).requires_grad();
+    /// let y = x.clone() * 2.;
+    ///
+    /// // Reverses the gradient flowing through `y`.
+    /// let handle = y.register_hook(|grad| grad.mapv_inplace(|el| -el));
+    ///
+    /// let z = y.sum();
+    /// z.forward();
+    /// z.backward(1.);
+    /// assert_eq!(*x.grad(), ndarray::arr1(&[-2., -2., -2.]));
+    ///
+    /// handle.remove();
+    /// ```
+    pub fn register_hook<F>(&self, hook: F) -> HookHandle
+    where
+        F: FnMut(&mut Array<T, D>) + MaybeSync + 'static,
+    {
+        let id = self.grad.register_hook(Box::new(hook));
+
+        HookHandle::new(id, Rc::downgrade(&self.grad) as _)
+    }
+
+    /// Registers a hook on the gradient of the leaf `self` and returns a handle that can be used
+    /// to remove it.
+    ///
+    /// The hook is called at the end of [`.backward()`](VarDiff::backward()) with the gradient of
+    /// `self`, after the current pass has been accumulated into it.
+    ///
+    /// # Panics
+    ///
+    /// If `self` is not a leaf.
+    pub fn register_post_accumulate_hook<F>(&self, hook: F) -> HookHandle
+    where
+        F: FnMut(&mut Array<T, D>) + MaybeSync + 'static,
+    {
+        assert_eq!(
+            self.history.len(),
+            0,
+            "Post-accumulate hooks can only be registered on leaves."
+        );
+
+        let id = self.grad.register_post_accumulate_hook(Box::new(hook));
+
+        HookHandle::new(id, Rc::downgrade(&self.grad) as _)
+    }
 }
 
-impl VarDiff<Ix0> {
+impl<T> VarDiff<Ix0, T>
+where
+    T: Float,
+{
     /// Returns the scalar contained in the variable.
-    pub fn item(&self) -> f32 {
+    pub fn item(&self) -> T {
         self.data()[()]
     }
 }
 
-impl VarDiff<Ix1> {
+impl<T> VarDiff<Ix1, T>
+where
+    T: Float,
+{
     /// Performs a vector-matrix multiplication between the vector variable `self` and the matrix
     /// variable `rhs`.
     ///
@@ -195,7 +566,10 @@ impl VarDiff<Ix1> {
     }
 }
 
-impl VarDiff<Ix2> {
+impl<T> VarDiff<Ix2, T>
+where
+    T: Float,
+{
     /// Performs a matrix multiplication between the matrix variables `self` and `rhs`. If `self`
     /// is *(n, m)* and `rhs` is *(m, o)* the output will be *(n, o)*.
     pub fn mm<Rhs>(self, rhs: Rhs) -> <Self as MatMatMul<Rhs>>::Output
@@ -230,13 +604,96 @@ impl VarDiff<Ix2> {
     }
 }
 
-impl<D> VarDiff<D>
+impl<T> VarDiff<Ix4, T>
+where
+    T: Float,
+{
+    /// Rearranges the elements of a differentiable variable of shape *(N, C × r², H, W)* into one
+    /// of shape *(N, C, H × r, W × r)*, where *r* is `upscale_factor`, and returns it.
+    ///
+    /// # Arguments
+    ///
+    /// * `upscale_factor` - factor to increase the spatial size by.
+    ///
+    /// # Panics
+    ///
+    /// If `upscale_factor` is zero or if the number of channels is not divisible by its square.
+    pub fn pixel_shuffle(self, upscale_factor: usize) -> VarDiff<Ix4, T> {
+        let var = self.var.pixel_shuffle(upscale_factor);
+        let grad = Rc::new(Gradient::ndarray_zeros(var.data().raw_dim()));
+        let op = PixelShuffleBackward::new(self.grad, grad.clone(), upscale_factor, false);
+
+        VarDiff::node(var, grad.clone(), (Rc::new(op), grad), self.history)
+    }
+
+    /// Rearranges the elements of a differentiable variable of shape *(N, C, H × r, W × r)* into
+    /// one of shape *(N, C × r², H, W)*, where *r* is `downscale_factor`, and returns it. This is
+    /// the inverse of [`.pixel_shuffle()`](VarDiff::pixel_shuffle()).
+    ///
+    /// # Arguments
+    ///
+    /// * `downscale_factor` - factor to decrease the spatial size by.
+    ///
+    /// # Panics
+    ///
+    /// If `downscale_factor` is zero or if the spatial size is not divisible by it.
+    pub fn pixel_unshuffle(self, downscale_factor: usize) -> VarDiff<Ix4, T> {
+        let var = self.var.pixel_unshuffle(downscale_factor);
+        let grad = Rc::new(Gradient::ndarray_zeros(var.data().raw_dim()));
+        let op = PixelShuffleBackward::new(self.grad, grad.clone(), downscale_factor, true);
+
+        VarDiff::node(var, grad.clone(), (Rc::new(op), grad), self.history)
+    }
+
+    /// Computes the scaled dot-product attention of `query` over `key` and `value`, whose
+    /// gradients are accumulated by the backward pass when present. The attention weights are
+    /// not stored: the backward pass recomputes them.
+    pub(crate) fn attention(
+        query: AttentionOperand<T>,
+        key: AttentionOperand<T>,
+        value: AttentionOperand<T>,
+        mask: Option<AttentionMask<T>>,
+        dropout: (f64, Rc<Cell<bool>>),
+        causal: bool,
+        history: DiffHistory,
+    ) -> VarDiff<Ix4, T> {
+        let ((query, query_grad), (key, key_grad), (value, value_grad)) = (query, key, value);
+        let (shape, weights_shape) = attention_shapes(
+            query.data.borrow().raw_dim(),
+            key.data.borrow().raw_dim(),
+            value.data.borrow().raw_dim(),
+        );
+        let (p, status) = dropout;
+        let state = (p > 0.).then(|| Rc::new(RefCell::new(None)));
+        let logsumexp = Rc::new(RefCell::new(Array::zeros(
+            weights_shape.remove_axis(Axis(3)),
+        )));
+
+        let grad = Rc::new(Gradient::ndarray_zeros(shape));
+        let op = ScaledDotProductAttentionBackward::new(
+            (query.data.clone(), query_grad),
+            (key.data.clone(), key_grad),
+            (value.data.clone(), value_grad),
+            mask.as_ref().map(AttentionMask::data),
+            causal,
+            state.clone().map(|state| (p, state)),
+            logsumexp.clone(),
+            grad.clone(),
+        );
+        let var = query.attention(key, value, mask, (p, status, state), causal, logsumexp);
+
+        VarDiff::node(var, grad.clone(), (Rc::new(op), grad), history)
+    }
+}
+
+impl<D, T> VarDiff<D, T>
 where
     D: 'static + Dimension,
+    T: Float,
 {
     /// Returns the sum of all elements in `self`.
-    pub fn sum(self) -> VarDiff<Ix0> {
-        let grad = Rc::new(Gradient::from_ndarray(arr0(0.)));
+    pub fn sum(self) -> VarDiff<Ix0, T> {
+        let grad = Rc::new(Gradient::from_ndarray(arr0(T::zero())));
         let op = SumBackward::new(self.grad.clone(), grad.clone());
         let var = self.var.sum();
 
@@ -244,8 +701,8 @@ where
     }
 
     /// Returns the mean of all elements in `self`.
-    pub fn mean(self) -> VarDiff<Ix0> {
-        let grad = Rc::new(Gradient::from_ndarray(arr0(0.)));
+    pub fn mean(self) -> VarDiff<Ix0, T> {
+        let grad = Rc::new(Gradient::from_ndarray(arr0(T::zero())));
         let op = MeanBackward::new(self.grad, grad.clone());
         let var = self.var.mean();
 
@@ -258,7 +715,7 @@ where
     /// # Arguments
     ///
     /// `exp` - exponent.
-    pub fn pow(self, exp: i32) -> VarDiff<D> {
+    pub fn pow(self, exp: i32) -> VarDiff<D, T> {
         let grad = Rc::new(Gradient::ndarray_zeros(self.grad.shape()));
         let op = PowerBackward::new(self.grad, self.var.data.clone(), grad.clone(), exp);
         let var = self.var.pow(exp);
@@ -267,7 +724,7 @@ where
     }
 
     /// Takes the square root element-wise and returns a differentiable variable with the result.
-    pub fn sqrt(self) -> VarDiff<D> {
+    pub fn sqrt(self) -> VarDiff<D, T> {
         let grad = Rc::new(Gradient::ndarray_zeros(self.grad.shape()));
         let var = self.var.sqrt();
         let op = SqrtBackward::new(self.grad, var.data.clone(), grad.clone());
@@ -279,7 +736,7 @@ where
     /// variable with the result.
     ///
     /// *ReLU(x) = max(0, x)*
-    pub fn relu(self) -> VarDiff<D> {
+    pub fn relu(self) -> VarDiff<D, T> {
         let grad = Rc::new(Gradient::ndarray_zeros(self.grad.shape()));
         let op = ReLUBackward::new(self.grad, self.var.data.clone(), grad.clone());
         let var = self.var.relu();
@@ -291,7 +748,7 @@ where
     /// variable with the result.
     ///
     /// *LeakyReLU(x) = max(0, x) + 0.01 * min(0, x)*
-    pub fn leaky_relu(self) -> VarDiff<D> {
+    pub fn leaky_relu(self) -> VarDiff<D, T> {
         let grad = Rc::new(Gradient::ndarray_zeros(self.grad.shape()));
         let op = LeakyReLUBackward::new(self.grad, self.var.data.clone(), grad.clone());
         let var = self.var.leaky_relu();
@@ -302,7 +759,7 @@ where
     /// Applies the *softplus* element-wise and returns a differentiable variable with the result.
     ///
     /// *Softplus(x) = log(1 + exp(x))*
-    pub fn softplus(self) -> VarDiff<D> {
+    pub fn softplus(self) -> VarDiff<D, T> {
         let grad = Rc::new(Gradient::ndarray_zeros(self.grad.shape()));
         let op = SoftPlusBackward::new(self.grad, self.var.data.clone(), grad.clone());
         let var = self.var.softplus();
@@ -311,7 +768,7 @@ where
     }
 
     /// Applies the *sigmoid* element-wise and returns a differentiable variable with the result.
-    pub fn sigmoid(self) -> VarDiff<D> {
+    pub fn sigmoid(self) -> VarDiff<D, T> {
         let grad = Rc::new(Gradient::ndarray_zeros(self.grad.shape()));
         let var = self.var.sigmoid();
         let op = SigmoidBackward::new(self.grad, var.data.clone(), grad.clone());
@@ -320,7 +777,7 @@ where
     }
 
     /// Applies the *tanh* element-wise and returns a differentiable variable with the result.
-    pub fn tanh(self) -> VarDiff<D> {
+    pub fn tanh(self) -> VarDiff<D, T> {
         let grad = Rc::new(Gradient::ndarray_zeros(self.grad.shape()));
         let var = self.var.tanh();
         let op = TanHBackward::new(self.grad, var.data.clone(), grad.clone());
@@ -330,7 +787,7 @@ where
 
     /// Applies the *natural logarithm* element-wise and returns a differentiable variable with the
     /// result.
-    pub fn ln(self) -> VarDiff<D> {
+    pub fn ln(self) -> VarDiff<D, T> {
         let grad = Rc::new(Gradient::ndarray_zeros(self.grad.shape()));
         let op = LognBackward::new(self.grad, self.var.data.clone(), grad.clone());
         let var = self.var.ln();
@@ -340,7 +797,7 @@ where
 
     /// Applies the *exponential* element-wise and returns a differentiable variable with the
     /// result.
-    pub fn exp(self) -> VarDiff<D> {
+    pub fn exp(self) -> VarDiff<D, T> {
         let grad = Rc::new(Gradient::ndarray_zeros(self.grad.shape()));
         let var = self.var.exp();
         let op = ExpBackward::new(self.grad, var.data.clone(), grad.clone());
@@ -356,7 +813,7 @@ where
     /// # Arguments
     ///
     /// `axis` - axis along which softmax will be computed.
-    pub fn softmax(self, axis: usize) -> VarDiff<D> {
+    pub fn softmax(self, axis: usize) -> VarDiff<D, T> {
         let grad = Rc::new(Gradient::ndarray_zeros(self.grad.shape()));
         let var = self.var.softmax(axis);
         let op = SoftmaxBackward::new(self.grad, var.data.clone(), grad.clone(), axis);
@@ -378,7 +835,7 @@ where
     /// # Arguments
     ///
     /// `axis` - axis along which log-softmax will be computed.
-    pub fn log_softmax(self, axis: usize) -> VarDiff<D> {
+    pub fn log_softmax(self, axis: usize) -> VarDiff<D, T> {
         let grad = Rc::new(Gradient::ndarray_zeros(self.grad.shape()));
         let var = self.var.log_softmax(axis);
         let op = LogSoftmaxBackward::new(self.grad, var.data.clone(), grad.clone(), axis);
@@ -386,8 +843,84 @@ where
         VarDiff::node(var, grad.clone(), (Rc::new(op), grad), self.history)
     }
 
+    /// Computes the cumulative sum of the elements of `self` along `axis`.
+    ///
+    /// # Arguments
+    ///
+    /// `axis` - axis along which the sum is accumulated.
+    ///
+    /// # Panics
+    ///
+    /// If `axis` is out of bounds.
+    pub fn cumsum(self, axis: usize) -> VarDiff<D, T> {
+        self.cumulative(axis, Scan::Sum)
+    }
+
+    /// Computes the cumulative product of the elements of `self` along `axis`.
+    ///
+    /// The gradient is exact also when some of the elements are zero.
+    ///
+    /// # Arguments
+    ///
+    /// `axis` - axis along which the product is accumulated.
+    ///
+    /// # Panics
+    ///
+    /// If `axis` is out of bounds.
+    pub fn cumprod(self, axis: usize) -> VarDiff<D, T> {
+        self.cumulative(axis, Scan::Prod)
+    }
+
+    /// Computes the cumulative maximum of the elements of `self` along `axis`.
+    ///
+    /// The gradient of each element of the result flows to the element of `self` it comes from,
+    /// which is the latest one among equal maxima.
+    ///
+    /// # Arguments
+    ///
+    /// `axis` - axis along which the maximum is accumulated.
+    ///
+    /// # Panics
+    ///
+    /// If `axis` is out of bounds.
+    pub fn cummax(self, axis: usize) -> VarDiff<D, T> {
+        self.cumulative(axis, Scan::Max)
+    }
+
+    /// Computes the logarithm of the cumulative sum of the exponentials of the elements of `self`
+    /// along `axis`.
+    ///
+    /// This is computed without overflowing even for large elements.
+    ///
+    /// # Arguments
+    ///
+    /// `axis` - axis along which the sum is accumulated.
+    ///
+    /// # Panics
+    ///
+    /// If `axis` is out of bounds.
+    pub fn logcumsumexp(self, axis: usize) -> VarDiff<D, T> {
+        self.cumulative(axis, Scan::LogSumExp)
+    }
+
+    fn cumulative(self, axis: usize, scan: Scan) -> VarDiff<D, T> {
+        let operand_data = self.var.data.clone();
+        let var = self.var.cumulative(axis, scan);
+        let grad = Rc::new(Gradient::ndarray_zeros(self.grad.shape()));
+        let op = CumulativeBackward::new(
+            operand_data,
+            self.grad,
+            var.data.clone(),
+            grad.clone(),
+            axis,
+            scan,
+        );
+
+        VarDiff::node(var, grad.clone(), (Rc::new(op), grad), self.history)
+    }
+
     /// Returns a differentiable variable equivalent to `self` with its dimensions reversed.
-    pub fn t(self) -> VarDiff<D> {
+    pub fn t(self) -> VarDiff<D, T> {
         let grad = Rc::new(Gradient::ndarray_zeros(self.grad.shape()));
         let op = TransposeBackward::new(self.grad, grad.clone());
         let var = self.var.t();
@@ -395,6 +928,84 @@ where
         VarDiff::node(var, grad.clone(), (Rc::new(op), grad), self.history)
     }
 
+    /// Returns a differentiable variable equivalent to `self` with its axes permuted.
+    ///
+    /// The *i*-th axis of the result is the `axes[i]`-th axis of `self`.
+    ///
+    /// # Arguments
+    ///
+    /// * `axes` - permutation of the axes of `self`.
+    ///
+    /// # Panics
+    ///
+    /// If `axes` is not a permutation of the axes of `self`.
+    pub fn permute<S>(self, axes: S) -> VarDiff<D, T>
+    where
+        S: IntoDimension<Dim = D>,
+    {
+        let axes = axes.into_dimension();
+        let var = self.var.permute(axes.clone());
+        let grad = Rc::new(Gradient::ndarray_zeros(var.data().raw_dim()));
+        let op = PermuteBackward::new(self.grad, grad.clone(), axes);
+
+        VarDiff::node(var, grad.clone(), (Rc::new(op), grad), self.history)
+    }
+
+    /// Returns a differentiable variable equivalent to `self` with axes `a` and `b` swapped.
+    ///
+    /// # Arguments
+    ///
+    /// * `a` - first axis.
+    ///
+    /// * `b` - second axis.
+    ///
+    /// # Panics
+    ///
+    /// If either `a` or `b` is out of bounds.
+    pub fn swap_axes(self, a: usize, b: usize) -> VarDiff<D, T> {
+        let axes: D = swapped_axes(self.var.data.borrow().ndim(), a, b);
+
+        self.permute(axes)
+    }
+
+    /// Reverses the order of the elements of `self` along `axes` and returns a differentiable
+    /// variable with the result.
+    ///
+    /// # Arguments
+    ///
+    /// * `axes` - axes to reverse.
+    ///
+    /// # Panics
+    ///
+    /// If any of `axes` is out of bounds.
+    pub fn flip(self, axes: &[usize]) -> VarDiff<D, T> {
+        let var = self.var.flip(axes);
+        let grad = Rc::new(Gradient::ndarray_zeros(var.data().raw_dim()));
+        let op = FlipBackward::new(self.grad, grad.clone(), axes);
+
+        VarDiff::node(var, grad.clone(), (Rc::new(op), grad), self.history)
+    }
+
+    /// Shifts the elements of `self` by `shift` positions along `axis`, wrapping around the ones
+    /// going past its end, and returns a differentiable variable with the result.
+    ///
+    /// # Arguments
+    ///
+    /// * `shift` - number of positions to shift by, negative values shift backwards.
+    ///
+    /// * `axis` - axis to shift along.
+    ///
+    /// # Panics
+    ///
+    /// If `axis` is out of bounds.
+    pub fn roll(self, shift: isize, axis: usize) -> VarDiff<D, T> {
+        let var = self.var.roll(shift, axis);
+        let grad = Rc::new(Gradient::ndarray_zeros(var.data().raw_dim()));
+        let op = RollBackward::new(self.grad, grad.clone(), shift, axis);
+
+        VarDiff::node(var, grad.clone(), (Rc::new(op), grad), self.history)
+    }
+
     /// Applies *dropout* to `self` and returns a differentiable variable with the result.
     ///
     /// During training, randomly zeroes some of the elements of `self` with probability *p* using
@@ -408,60 +1019,399 @@ where
     /// Furthermore, the outputs are scaled by a factor of 1/(1 - p) during training. This means
     /// that during evaluation the resulting variable simply computes an identity function.
     ///
-    /// [`nn::Dropout`]: crate::nn::Dropout        
+    /// [`nn::Dropout`]: crate::nn::Dropout        
+    ///
+    /// # Arguments
+    ///
+    /// * `p` - dropout factor.
+    ///
+    /// * `status` - dropout status.
+    pub fn dropout(self, p: f64, status: Rc<Cell<bool>>) -> VarDiff<D, T> {
+        let grad = Rc::new(Gradient::ndarray_zeros(self.grad.shape()));
+        let noise = Rc::new(RefCell::new(Array::zeros(self.grad.shape())));
+        let var = self
+            .var
+            .dropout_with_noise(self.grad.shape(), p, noise.clone(), status.clone());
+        let op = DropoutBackward::new(self.grad, grad.clone(), p, noise, status);
+
+        VarDiff::node(var, grad.clone(), (Rc::new(op), grad), self.history)
+    }
+
+    /// Splits `self` into a certain number of chunks of size `chunk_size` **skipping** the
+    /// remainder along each dimension that doesn’t fit evenly.
+    ///
+    /// # Arguments
+    ///
+    /// `chunk_size` - shape for each chunk.
+    pub fn chunks<E>(self, chunk_size: E) -> Vec<VarDiff<D, T>>
+    where
+        E: IntoDimension<Dim = D>,
+    {
+        let vars = self.var.chunks(chunk_size);
+        vars.into_iter()
+            .enumerate()
+            .map(|(i, var)| {
+                let grad = Rc::new(Gradient::ndarray_zeros(var.data.borrow().raw_dim()));
+                let op = ChunkBackward::new(self.grad.clone(), grad.clone(), i);
+
+                VarDiff::node(var, grad.clone(), (Rc::new(op), grad), self.history.clone())
+            })
+            .collect()
+    }
+
+    /// Returns a new differentiable variable with a dimension of size one inserted at the position
+    /// specified by `axis`.
+    ///
+    /// # Arguments
+    ///
+    /// `axis` - dimension to insert the new axis at.
+    ///
+    /// # Panics
+    ///
+    /// If `axis` is greater than the number of dimensions of the variable.
+    pub fn unsqueeze(self, axis: usize) -> VarDiff<D::Larger, T> {
+        let var = self.var.unsqueeze(axis);
+        let grad = Rc::new(Gradient::ndarray_zeros(var.data().raw_dim()));
+        let op = UnsqueezeBackward::new(self.grad, grad.clone());
+
+        VarDiff::node(var, grad.clone(), (Rc::new(op), grad), self.history)
+    }
+
+    /// Broadcasts `self` to `shape` and returns a differentiable variable with the result.
+    ///
+    /// The usual broadcasting rules apply: the shape of `self` is aligned to the trailing axes of
+    /// `shape` and its axes of length one are repeated as needed. The gradient is summed over
+    /// the repeated axes.
+    ///
+    /// # Arguments
+    ///
+    /// * `shape` - shape to broadcast to.
+    ///
+    /// # Panics
+    ///
+    /// If `self` cannot be broadcast to `shape`.
+    pub fn broadcast_to<S>(self, shape: S) -> VarDiff<S::Dim, T>
+    where
+        S: IntoDimension,
+        S::Dim: 'static,
+    {
+        let shape = broadcast_shapes(&self.var.data.borrow().raw_dim(), shape.into_dimension());
+
+        self.repeated(shape)
+    }
+
+    /// Repeats `self` `size` times along `axis`, which must have length one, and returns a
+    /// differentiable variable with the result.
+    ///
+    /// # Arguments
+    ///
+    /// * `axis` - axis to expand.
+    ///
+    /// * `size` - length of the expanded axis.
+    ///
+    /// # Panics
+    ///
+    /// If `axis` is out of bounds or if its length is not one.
+    pub fn expand(self, axis: usize, size: usize) -> VarDiff<D, T> {
+        let mut shape = self.var.data.borrow().raw_dim();
+        assert!(
+            axis < shape.ndim(),
+            "error: axis {} is out of bounds.",
+            axis
+        );
+        assert_eq!(
+            shape[axis], 1,
+            "error: axis {} of length {} cannot be expanded.",
+            axis, shape[axis]
+        );
+        shape[axis] = size;
+
+        self.broadcast_to(shape)
+    }
+
+    /// Tiles `self`, repeating it `reps[i]` times along the *i*-th axis, and returns a
+    /// differentiable variable with the result.
+    ///
+    /// # Arguments
+    ///
+    /// * `reps` - number of repetitions along each axis.
+    ///
+    /// # Panics
+    ///
+    /// If `reps` and `self` have a different number of dimensions.
+    pub fn repeat<S>(self, reps: S) -> VarDiff<D, T>
+    where
+        S: IntoDimension<Dim = D>,
+    {
+        let shape = tiled_shapes(&self.var.data.borrow().raw_dim(), &reps.into_dimension());
+
+        self.repeated(shape)
+    }
+
+    /// Repeats each element of `self` `repeats` times along `axis` and returns a differentiable
+    /// variable with the result.
+    ///
+    /// # Arguments
+    ///
+    /// * `repeats` - number of repetitions of each element.
+    ///
+    /// * `axis` - axis to repeat along.
+    ///
+    /// # Panics
+    ///
+    /// If `axis` is out of bounds.
+    pub fn repeat_interleave(self, repeats: usize, axis: usize) -> VarDiff<D, T> {
+        let shape = interleaved_shapes(&self.var.data.borrow().raw_dim(), repeats, axis);
+
+        self.repeated(shape)
+    }
+
+    fn repeated<E>(self, (shape, operand_shape, blocks): (E, IxDyn, IxDyn)) -> VarDiff<E, T>
+    where
+        E: 'static + Dimension,
+    {
+        let grad = Rc::new(Gradient::ndarray_zeros(shape.clone()));
+        let op = RepeatBackward::new(
+            self.grad,
+            grad.clone(),
+            operand_shape.clone(),
+            blocks.clone(),
+        );
+        let var = self.var.repeated((shape, operand_shape, blocks));
+
+        VarDiff::node(var, grad.clone(), (Rc::new(op), grad), self.history)
+    }
+
+    /// Converts the differentiable variable into one with a dynamic number of dimensions.
+    ///
+    /// The result is still connected to `self` in the computational graph.
+    pub fn into_dyn(self) -> VarDiff<IxDyn, T> {
+        self.into_dimensionality()
+    }
+
+    /// Converts the differentiable variable into one of dimensionality `E`.
+    ///
+    /// The result is still connected to `self` in the computational graph.
+    ///
+    /// # Panics
+    ///
+    /// If the number of dimensions of the variable doesn't match the one of `E`.
+    pub fn into_dimensionality<E>(self) -> VarDiff<E, T>
+    where
+        E: 'static + Dimension,
+    {
+        let var = self.var.into_dimensionality();
+        let grad = Rc::new(Gradient::ndarray_zeros(var.data().raw_dim()));
+        let op = IntoDimensionalityBackward::new(self.grad, grad.clone());
+
+        VarDiff::node(var, grad.clone(), (Rc::new(op), grad), self.history)
+    }
+
+    /// Lays out the elements of the differentiable variable, in logical order, into one of shape
+    /// `shape`.
+    ///
+    /// The result is still connected to `self` in the computational graph.
+    ///
+    /// # Arguments
+    ///
+    /// `shape` - shape of the result.
+    ///
+    /// # Panics
+    ///
+    /// If `shape` doesn't have the same number of elements as the variable.
+    pub fn reshape<S>(self, shape: S) -> VarDiff<S::Dim, T>
+    where
+        S: IntoDimension,
+        S::Dim: 'static,
+    {
+        let var = self.var.reshape(shape);
+        let grad = Rc::new(Gradient::ndarray_zeros(var.data().raw_dim()));
+        let op = ReshapeBackward::new(self.grad, grad.clone());
+
+        VarDiff::node(var, grad.clone(), (Rc::new(op), grad), self.history)
+    }
+
+    /// Fills the elements of `self` where `mask` is `true` with `value` and returns a
+    /// differentiable variable with the result.
+    ///
+    /// The filled elements receive no gradient.
+    ///
+    /// # Arguments
+    ///
+    /// * `mask` - boolean mask, must be broadcastable to the shape of `self`.
+    ///
+    /// * `value` - value to fill with.
+    ///
+    /// # Panics
+    ///
+    /// If `mask` cannot be broadcast to the shape of `self`.
+    pub fn masked_fill<M>(self, mask: Var<M, bool>, value: T) -> VarDiff<D, T>
+    where
+        M: 'static + Dimension,
+    {
+        let grad = Rc::new(Gradient::ndarray_zeros(self.grad.shape()));
+        let op = MaskedFillBackward::new(self.grad, mask.data.clone(), grad.clone());
+        let var = self.var.masked_fill(mask, value);
+
+        VarDiff::node(var, grad.clone(), (Rc::new(op), grad), self.history)
+    }
+
+    /// Gathers the values of `self` along `axis` at the positions specified by `index` and
+    /// returns a differentiable variable with the result.
+    ///
+    /// See [`Var::gather`] for more details.
+    ///
+    /// # Arguments
+    ///
+    /// * `axis` - axis to gather along.
+    ///
+    /// * `index` - indices of the elements to gather.
+    ///
+    /// # Panics
+    ///
+    /// If `axis` is out of bounds or if `index` is larger than `self` along any axis other than
+    /// `axis`. Computing the result panics if an index is out of bounds.
+    pub fn gather<L>(self, axis: usize, index: Var<D, L>) -> VarDiff<D, T>
+    where
+        L: AsIndex,
+    {
+        let grad = Rc::new(Gradient::ndarray_zeros(index.data.borrow().raw_dim()));
+        let op = GatherBackward::new(self.grad, index.data.clone(), grad.clone(), axis);
+        let var = self.var.gather(axis, index);
+
+        VarDiff::node(var, grad.clone(), (Rc::new(op), grad), self.history)
+    }
+
+    /// Sorts the elements of `self` along `axis`, returning a differentiable variable with the
+    /// sorted elements and a variable with their positions in `self`.
+    ///
+    /// The sort is stable, so equal elements keep their relative order. NaNs are considered
+    /// larger than any other element. The gradient flows back to the original positions of the
+    /// elements.
+    ///
+    /// # Arguments
+    ///
+    /// * `axis` - axis to sort along.
+    ///
+    /// * `descending` - whether to sort in descending order.
+    ///
+    /// # Panics
+    ///
+    /// If `axis` is out of bounds.
+    pub fn sort(self, axis: usize, descending: bool) -> (VarDiff<D, T>, Var<D, usize>) {
+        let index = self.var.clone().sort(axis, descending).1;
+
+        (self.gather(axis, index.clone()), index)
+    }
+
+    /// Selects the `k` largest or smallest elements of `self` along `axis`, returning a
+    /// differentiable variable with them, sorted, and a variable with their positions in `self`.
+    ///
+    /// Ties are broken in favour of the elements coming first along `axis`. The gradient flows
+    /// back to the positions of the selected elements.
+    ///
+    /// # Arguments
+    ///
+    /// * `k` - number of elements to select.
+    ///
+    /// * `axis` - axis to select along.
+    ///
+    /// * `largest` - whether to select the largest elements rather than the smallest.
+    ///
+    /// # Panics
+    ///
+    /// If `axis` is out of bounds or if `k` is larger than the length of `axis`.
+    pub fn topk(self, k: usize, axis: usize, largest: bool) -> (VarDiff<D, T>, Var<D, usize>) {
+        let index = self.var.clone().topk(k, axis, largest).1;
+
+        (self.gather(axis, index.clone()), index)
+    }
+
+    /// Selects the `k`-th smallest element of each lane of `self` along `axis`, returning a
+    /// differentiable variable with them and a variable with their positions in `self`.
+    ///
+    /// `axis` is kept in the results with length one. The gradient flows back to the positions
+    /// of the selected elements.
     ///
     /// # Arguments
     ///
-    /// * `p` - dropout factor.
+    /// * `k` - rank of the element to select, starting from 1.
     ///
-    /// * `status` - dropout status.
-    pub fn dropout(self, p: f64, status: Rc<Cell<bool>>) -> VarDiff<D> {
-        let grad = Rc::new(Gradient::ndarray_zeros(self.grad.shape()));
-        let noise = Rc::new(RefCell::new(Array::zeros(self.grad.shape())));
-        let var = self
-            .var
-            .dropout_with_noise(self.grad.shape(), p, noise.clone(), status.clone());
-        let op = DropoutBackward::new(self.grad, grad.clone(), p, noise, status);
+    /// * `axis` - axis to select along.
+    ///
+    /// # Panics
+    ///
+    /// If `axis` is out of bounds or if `k` is not between 1 and the length of `axis`.
+    pub fn kthvalue(self, k: usize, axis: usize) -> (VarDiff<D, T>, Var<D, usize>) {
+        let index = self.var.clone().kthvalue(k, axis).1;
 
-        VarDiff::node(var, grad.clone(), (Rc::new(op), grad), self.history)
+        (self.gather(axis, index.clone()), index)
     }
 
-    /// Splits `self` into a certain number of chunks of size `chunk_size` **skipping** the
-    /// remainder along each dimension that doesn’t fit evenly.
+    /// Computes *self > rhs* element-wise and returns a boolean mask with the result.
     ///
-    /// # Arguments
+    /// The operands are broadcast together.
+    pub fn gt<E>(self, rhs: Var<E, T>) -> Var<<D as DimMax<E>>::Output, bool>
+    where
+        D: DimMax<E>,
+        E: 'static + Dimension,
+    {
+        self.var.gt(rhs)
+    }
+
+    /// Computes *self >= rhs* element-wise and returns a boolean mask with the result.
     ///
-    /// `chunk_size` - shape for each chunk.
-    pub fn chunks<E>(self, chunk_size: E) -> Vec<VarDiff<D>>
+    /// The operands are broadcast together.
+    pub fn ge<E>(self, rhs: Var<E, T>) -> Var<<D as DimMax<E>>::Output, bool>
     where
-        E: IntoDimension<Dim = D>,
+        D: DimMax<E>,
+        E: 'static + Dimension,
     {
-        let vars = self.var.chunks(chunk_size);
-        vars.into_iter()
-            .enumerate()
-            .map(|(i, var)| {
-                let grad = Rc::new(Gradient::ndarray_zeros(var.data.borrow().raw_dim()));
-                let op = ChunkBackward::new(self.grad.clone(), grad.clone(), i);
+        self.var.ge(rhs)
+    }
 
-                VarDiff::node(var, grad.clone(), (Rc::new(op), grad), self.history.clone())
-            })
-            .collect()
+    /// Computes *self < rhs* element-wise and returns a boolean mask with the result.
+    ///
+    /// The operands are broadcast together.
+    pub fn lt<E>(self, rhs: Var<E, T>) -> Var<<D as DimMax<E>>::Output, bool>
+    where
+        D: DimMax<E>,
+        E: 'static + Dimension,
+    {
+        self.var.lt(rhs)
     }
 
-    /// Returns a new differentiable variable with a dimension of size one inserted at the position
-    /// specified by `axis`.
+    /// Computes *self <= rhs* element-wise and returns a boolean mask with the result.
     ///
-    /// # Arguments
+    /// The operands are broadcast together.
+    pub fn le<E>(self, rhs: Var<E, T>) -> Var<<D as DimMax<E>>::Output, bool>
+    where
+        D: DimMax<E>,
+        E: 'static + Dimension,
+    {
+        self.var.le(rhs)
+    }
+
+    /// Computes *self == rhs* element-wise and returns a boolean mask with the result.
     ///
-    /// `axis` - dimension to insert the new axis at.
-    pub fn unsqueeze(self, axis: usize) -> VarDiff<D::Larger> {
-        let grad = Rc::new(Gradient::ndarray_zeros(
-            self.grad.shape().insert_axis(Axis(axis)),
-        ));
-        let op = UnsqueezeBackward::new(self.grad, grad.clone());
-        let var = self.var.unsqueeze(axis);
+    /// The operands are broadcast together.
+    pub fn eq<E>(self, rhs: Var<E, T>) -> Var<<D as DimMax<E>>::Output, bool>
+    where
+        D: DimMax<E>,
+        E: 'static + Dimension,
+    {
+        self.var.eq(rhs)
+    }
 
-        VarDiff::node(var, grad.clone(), (Rc::new(op), grad), self.history)
+    /// Computes *self != rhs* element-wise and returns a boolean mask with the result.
+    ///
+    /// The operands are broadcast together.
+    pub fn ne<E>(self, rhs: Var<E, T>) -> Var<<D as DimMax<E>>::Output, bool>
+    where
+        D: DimMax<E>,
+        E: 'static + Dimension,
+    {
+        self.var.ne(rhs)
     }
 
     /// Computes the mean absolute error between the two variables.
@@ -471,7 +1421,7 @@ where
     /// * `target` - target variable.
     ///
     /// * `reduction` - reduction to apply to the criterion's output.
-    pub fn mae(self, target: Var<D>, reduction: Reduction) -> VarDiff<Ix0> {
+    pub fn mae(self, target: Var<D, T>, reduction: Reduction) -> VarDiff<Ix0, T> {
         let grad = Rc::new(Gradient::ndarray_zeros(().into_dimension()));
         let op = AbsoluteErrorBackward::new(
             self.var.data.clone(),
@@ -492,7 +1442,7 @@ where
     /// * `target` - target variable.
     ///
     /// * `reduction` - reduction to apply to the criterion's output.
-    pub fn mse(self, target: Var<D>, reduction: Reduction) -> VarDiff<Ix0> {
+    pub fn mse(self, target: Var<D, T>, reduction: Reduction) -> VarDiff<Ix0, T> {
         let grad = Rc::new(Gradient::ndarray_zeros(().into_dimension()));
         let op = SquaredErrorBackward::new(
             self.var.data.clone(),
@@ -522,7 +1472,7 @@ where
     /// * `target` - target variable.
     ///
     /// * `reduction` - reduction to apply to the criterion's output.
-    pub fn bce(self, target: Var<D>, reduction: Reduction) -> VarDiff<Ix0> {
+    pub fn bce(self, target: Var<D, T>, reduction: Reduction) -> VarDiff<Ix0, T> {
         let grad = Rc::new(Gradient::ndarray_zeros(().into_dimension()));
         let op = BinaryCrossEntropyBackward::new(
             self.var.data.clone(),
@@ -551,7 +1501,7 @@ where
     /// * `target` - target variable.
     ///
     /// * `reduction` - reduction to apply to the criterion's output.
-    pub fn bce_with_logits(self, target: Var<D>, reduction: Reduction) -> VarDiff<Ix0> {
+    pub fn bce_with_logits(self, target: Var<D, T>, reduction: Reduction) -> VarDiff<Ix0, T> {
         let grad = Rc::new(Gradient::ndarray_zeros(().into_dimension()));
         let op = BCEWithLogitsBackward::new(
             self.var.data.clone(),
@@ -580,7 +1530,7 @@ where
     /// * `target` - target variable.
     ///
     /// * `reduction` - reduction to apply to the criterion's output.
-    pub fn kldiv(self, target: Var<D>, reduction: Reduction) -> VarDiff<Ix0> {
+    pub fn kldiv(self, target: Var<D, T>, reduction: Reduction) -> VarDiff<Ix0, T> {
         let grad = Rc::new(Gradient::ndarray_zeros(().into_dimension()));
         let op = KLDivBackward::new(self.grad, target.data.clone(), grad.clone(), reduction);
         let var = self.var.kldiv(target, reduction);
@@ -589,9 +1539,10 @@ where
     }
 }
 
-impl<D> VarDiff<D>
+impl<D, T> VarDiff<D, T>
 where
     D: 'static + Dimension + RemoveAxis,
+    T: Float,
 {
     /// Concatenates the given sequence of differentiable variables `variables`, including
     /// `self`, along the given axis, and returns a differentiable variable with the results.
@@ -624,9 +1575,9 @@ where
     ///                                       [1., 1., 4., 4., 3., 3.],
     ///                                       [1., 1., 4., 4., 3., 3.]]);
     /// ```
-    pub fn cat(mut self, vars: &[Self], axis: usize) -> VarDiff<D> {
+    pub fn cat(mut self, vars: &[Self], axis: usize) -> VarDiff<D, T> {
         let var = {
-            let vars: Vec<Var<D>> = vars.iter().cloned().map(|x| x.var).collect();
+            let vars: Vec<Var<D, T>> = vars.iter().cloned().map(|x| x.var).collect();
             self.var.cat(&vars, axis)
         };
         let mut op_grads = Vec::with_capacity(vars.len());
@@ -678,9 +1629,9 @@ where
     ///                                       [[1., 1.],
     ///                                        [1., 1.]]]);
     /// ```
-    pub fn stack(mut self, vars: &[Self], axis: usize) -> VarDiff<D::Larger> {
+    pub fn stack(mut self, vars: &[Self], axis: usize) -> VarDiff<D::Larger, T> {
         let var = {
-            let vars: Vec<Var<D>> = vars.iter().cloned().map(|x| x.var).collect();
+            let vars: Vec<Var<D, T>> = vars.iter().cloned().map(|x| x.var).collect();
             self.var.stack(&vars, axis)
         };
         let mut op_grands = Vec::with_capacity(vars.len());
@@ -703,10 +1654,12 @@ where
     /// or (minibatch, C, d1, d2, ..., dk) with k >= 1 for the K-dimensional
     /// case.
     ///
-    /// The target variable should be a class index in the range [0, C) where C = number of classes.
+    /// The target variable should contain class indices in the range [0, C) where C = number of
+    /// classes. Its elements can be of any type implementing [`AsIndex`], such as `i64` or `usize`.
     ///
     /// When the given reduction is equal to [`Reduction::Mean`] the total negative likelihood is
-    /// divided by the batch size.
+    /// divided by the number of elements of the target, that is, the batch size in the
+    /// 1-dimensional case.
     ///
     /// As mentioned before, this criterion can also be used for higher dimensional inputs, such as 2D
     /// images, by providing an input of size (minibatch, C, d1, d2, ..., dk) with k >= 1 where
@@ -722,7 +1675,14 @@ where
     /// * `target` - target variable.
     ///
     /// * `reduction` - reduction to apply to the criterion's output.
-    pub fn nll(self, target: Var<D::Smaller>, reduction: Reduction) -> VarDiff<Ix0> {
+    ///
+    /// # Panics
+    ///
+    /// If the shape of `target` differs from the one of the input without the class axis.
+    pub fn nll<L>(self, target: Var<D::Smaller, L>, reduction: Reduction) -> VarDiff<Ix0, T>
+    where
+        L: AsIndex,
+    {
         let grad = Rc::new(Gradient::ndarray_zeros(().into_dimension()));
         let op = NegativeLogLikelihoodBackward::new(
             target.data.clone(),
@@ -736,138 +1696,373 @@ where
     }
 }
 
-impl<D> VarDiff<D>
+impl<D, T> VarDiff<D, T>
 where
     D: 'static + Dimension,
     D::Smaller: RemoveAxis,
-    <D::Smaller as Dimension>::Smaller: Copy,
+    T: Float,
 {
     /// Applies the specified padding over the spatial dimensions of the variable.
-    pub fn pad<T, E>(self, padding: E, mode: T) -> VarDiff<D>
+    pub fn pad<P, E>(self, padding: E, mode: P) -> VarDiff<D, T>
     where
-        T: 'static + PaddingMode<D>,
+        P: 'static + PaddingMode<D, T>,
         E: IntoDimension<Dim = <D::Smaller as Dimension>::Smaller>,
     {
         let padding = padding.into_dimension();
-        let grad = Rc::new(Gradient::ndarray_zeros(self.var.data().raw_dim()));
-        let var = self.var.pad(padding, mode);
+        let var = self.var.pad(padding.clone(), mode);
+        let grad = Rc::new(Gradient::ndarray_zeros(var.data().raw_dim()));
         let op = PadBackward::new(self.grad, grad.clone(), padding);
 
         VarDiff::node(var, grad.clone(), (Rc::new(op), grad), self.history)
     }
-}
 
-// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
-// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Arithmetic Operations Implementation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
-// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    /// Resizes the spatial dimensions of `self` and returns a differentiable variable with the
+    /// result.
+    ///
+    /// The variable must be of shape *(N, C, ...)*, with one, two or three spatial dimensions
+    /// depending on `mode`. When `align_corners` is `true` the centers of the corner elements of
+    /// `self` and of the result are aligned, otherwise their outer edges are. Nearest neighbour
+    /// interpolation ignores it. Scale factors are only used to compute the size of the result.
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - size of the spatial dimensions of the result or multiplier of the current one.
+    ///
+    /// * `mode` - interpolation algorithm.
+    ///
+    /// * `align_corners` - whether to align the corner elements.
+    ///
+    /// # Panics
+    ///
+    /// If `mode` doesn't support the number of spatial dimensions of `self`, if `size` has a
+    /// different number of spatial dimensions or if any of the resulting lengths is zero.
+    pub fn interpolate(
+        self,
+        size: Resize<<D::Smaller as Dimension>::Smaller>,
+        mode: Interpolation,
+        align_corners: bool,
+    ) -> VarDiff<D, T> {
+        let (shape, taps) = resampling(
+            &self.var.data.borrow().raw_dim(),
+            &size,
+            mode,
+            align_corners,
+        );
+        let grad = Rc::new(Gradient::ndarray_zeros(shape.clone()));
+        let op = InterpolateBackward::new(self.grad, grad.clone(), taps.clone());
+        let var = self.var.resampled(shape, taps);
 
-// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ VarDiff - f32 ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        VarDiff::node(var, grad.clone(), (Rc::new(op), grad), self.history)
+    }
+}
 
-impl<D> Add<f32> for VarDiff<D>
+impl<D, T> VarDiff<D, T>
 where
-    D: 'static + Dimension,
-    D: DimMax<Ix0>,
+    D: 'static + RemoveAxis,
+    D::Smaller: 'static + RemoveAxis,
+    <D::Smaller as Dimension>::Smaller: 'static,
+    T: Float,
 {
-    type Output = VarDiff<<D as DimMax<Ix0>>::Output>;
+    /// Computes the inverse of each matrix of `self`, which is a matrix or a batch of matrices
+    /// stacked along the leading axes.
+    ///
+    /// Singular matrices result in non-finite values.
+    ///
+    /// # Panics
+    ///
+    /// If the matrices are not square.
+    pub fn inv(self) -> VarDiff<D, T> {
+        let shape = self.var.data.borrow().raw_dim();
+        let (rows, columns) = matrices_shape(&shape, true);
+        let ranges = Decomposition::Inverse.ranges(rows, columns);
 
-    fn add(self, rhs: f32) -> Self::Output {
-        self + crate::full((), rhs)
+        self.linalg(Decomposition::Inverse)
+            .unpacked(shape, ranges[0].clone())
     }
-}
 
-impl<D> Sub<f32> for VarDiff<D>
-where
-    D: 'static + Dimension,
-    D: DimMax<Ix0>,
-{
-    type Output = VarDiff<<D as DimMax<Ix0>>::Output>;
+    /// Computes the determinant of each matrix of `self`.
+    ///
+    /// # Panics
+    ///
+    /// If the matrices are not square.
+    pub fn det(self) -> VarDiff<<D::Smaller as Dimension>::Smaller, T> {
+        let shape = batch_shape(self.var.data.borrow().raw_dim());
 
-    fn sub(self, rhs: f32) -> Self::Output {
-        self - crate::full((), rhs)
+        self.linalg(Decomposition::Determinant)
+            .unpacked(shape, 0..1)
     }
-}
 
-impl<D> Mul<f32> for VarDiff<D>
-where
-    D: 'static + Dimension,
-    D: DimMax<Ix0>,
-{
-    type Output = VarDiff<<D as DimMax<Ix0>>::Output>;
+    /// Computes the sign and the natural logarithm of the absolute value of the determinant of
+    /// each matrix of `self`.
+    ///
+    /// This is more accurate than [`.det()`](VarDiff::det()) for matrices whose determinant is
+    /// either very small or very large. The sign of singular matrices is zero and, being piecewise
+    /// constant, is not differentiable.
+    ///
+    /// # Panics
+    ///
+    /// If the matrices are not square.
+    #[allow(clippy::type_complexity)]
+    pub fn slogdet(
+        self,
+    ) -> (
+        Var<<D::Smaller as Dimension>::Smaller, T>,
+        VarDiff<<D::Smaller as Dimension>::Smaller, T>,
+    ) {
+        let shape = batch_shape(self.var.data.borrow().raw_dim());
+        let packed = self.linalg(Decomposition::Slogdet);
+
+        (
+            packed.var.unpacked(shape.clone(), 0..1),
+            packed.unpacked(shape, 1..2),
+        )
+    }
+
+    /// Computes the lower triangular Cholesky factor `L` of each matrix `A` of `self`, such that
+    /// `A = L · Lᵀ`.
+    ///
+    /// The matrices must be symmetric and positive-definite, only their symmetric part is
+    /// considered. Matrices that are not positive-definite result in non-finite values.
+    ///
+    /// # Panics
+    ///
+    /// If the matrices are not square.
+    pub fn cholesky(self) -> VarDiff<D, T> {
+        let shape = self.var.data.borrow().raw_dim();
+        let (rows, columns) = matrices_shape(&shape, true);
+        let ranges = Decomposition::Cholesky.ranges(rows, columns);
+
+        self.linalg(Decomposition::Cholesky)
+            .unpacked(shape, ranges[0].clone())
+    }
+
+    /// Computes the reduced QR decomposition of each matrix of `self`, returning the matrices
+    /// with orthonormal columns `Q` and the upper triangular ones `R`.
+    ///
+    /// Given matrices of *m* rows and *n* columns and *k = min(m, n)*, `Q` has *k* columns and
+    /// `R` has *k* rows. The diagonal elements of `R` are non-negative. The gradient is defined
+    /// for matrices whose first *k* columns are linearly independent.
+    ///
+    /// # Panics
+    ///
+    /// If `self` is not a matrix or a batch of matrices.
+    pub fn qr(self) -> (VarDiff<D, T>, VarDiff<D, T>) {
+        let shape = self.var.data.borrow().raw_dim();
+        let (q_shape, r_shape) = qr_shapes(&shape);
+        let (rows, columns) = matrices_shape(&shape, false);
+        let ranges = Decomposition::Qr.ranges(rows, columns);
+        let packed = self.linalg(Decomposition::Qr);
+
+        (
+            packed.unpacked(q_shape, ranges[0].clone()),
+            packed.unpacked(r_shape, ranges[1].clone()),
+        )
+    }
+
+    /// Computes the eigenvalues, in ascending order, and the eigenvectors, as columns, of each
+    /// matrix of `self`.
+    ///
+    /// The matrices must be symmetric, only their symmetric part is considered. The gradient with
+    /// respect to the eigenvectors is defined for distinct eigenvalues only.
+    ///
+    /// # Panics
+    ///
+    /// If the matrices are not square.
+    pub fn eigh(self) -> (VarDiff<D::Smaller, T>, VarDiff<D, T>) {
+        let shape = self.var.data.borrow().raw_dim();
+        let (rows, columns) = matrices_shape(&shape, true);
+        let ranges = Decomposition::Eigh.ranges(rows, columns);
+        let packed = self.linalg(Decomposition::Eigh);
+
+        (
+            packed.unpacked(shape.remove_axis(Axis(shape.ndim() - 1)), ranges[0].clone()),
+            packed.unpacked(shape, ranges[1].clone()),
+        )
+    }
+
+    /// Computes the reduced singular value decomposition `U · diag(S) · Vᵀ` of each matrix of
+    /// `self`, returning `U`, `S` and `Vᵀ`.
+    ///
+    /// Given matrices of *m* rows and *n* columns and *k = min(m, n)*, `U` has *k* columns, `S` has
+    /// *k* elements, sorted in descending order, and `Vᵀ` has *k* rows. The gradient with respect
+    /// to `U` and `Vᵀ` is defined for distinct and non-zero singular values only. The columns of
+    /// `U` and the rows of `Vᵀ` matching zero singular values complete orthonormal bases.
+    ///
+    /// # Panics
+    ///
+    /// If `self` is not a matrix or a batch of matrices.
+    #[allow(clippy::type_complexity)]
+    pub fn svd(self) -> (VarDiff<D, T>, VarDiff<D::Smaller, T>, VarDiff<D, T>) {
+        let shape = self.var.data.borrow().raw_dim();
+        let (u_shape, s_shape, vt_shape) = svd_shapes(&shape);
+        let (rows, columns) = matrices_shape(&shape, false);
+        let ranges = Decomposition::Svd.ranges(rows, columns);
+        let packed = self.linalg(Decomposition::Svd);
+
+        (
+            packed.unpacked(u_shape, ranges[0].clone()),
+            packed.unpacked(s_shape, ranges[1].clone()),
+            packed.unpacked(vt_shape, ranges[2].clone()),
+        )
+    }
+
+    /// Computes `decomposition` of each matrix of `self`, returning a differentiable variable with
+    /// one row per matrix packing all of its outputs.
+    fn linalg(&self, decomposition: Decomposition) -> VarDiff<Ix2, T> {
+        let var = self.var.linalg(decomposition);
+        let grad = Rc::new(Gradient::ndarray_zeros(var.data().raw_dim()));
+        let op = LinalgBackward::new(
+            self.var.data.clone(),
+            self.grad.clone(),
+            var.data.clone(),
+            grad.clone(),
+            decomposition,
+        );
 
-    fn mul(self, rhs: f32) -> Self::Output {
-        self * crate::full((), rhs)
+        VarDiff::node(var, grad.clone(), (Rc::new(op), grad), self.history.clone())
     }
 }
 
-impl<D> Div<f32> for VarDiff<D>
+impl<T> VarDiff<Ix2, T>
 where
-    D: 'static + Dimension,
-    D: DimMax<Ix0>,
+    T: Float,
 {
-    type Output = VarDiff<<D as DimMax<Ix0>>::Output>;
+    /// Copies the output at `range` out of the packed outputs `self` of a decomposition, returning
+    /// a differentiable variable of shape `shape` with the result.
+    fn unpacked<E>(&self, shape: E, range: Range<usize>) -> VarDiff<E, T>
+    where
+        E: 'static + Dimension,
+    {
+        let var = self.var.unpacked(shape, range.clone());
+        let grad = Rc::new(Gradient::ndarray_zeros(var.data().raw_dim()));
+        let op = UnpackBackward::new(self.grad.clone(), grad.clone(), range);
 
-    fn div(self, rhs: f32) -> Self::Output {
-        self / crate::full((), rhs)
+        VarDiff::node(var, grad.clone(), (Rc::new(op), grad), self.history.clone())
     }
 }
 
-// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ f32 - VarDiff ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Arithmetic Operations Implementation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ VarDiff - Scalar ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
-impl<D> Add<VarDiff<D>> for f32
+impl<D, T> Add<T> for VarDiff<D, T>
 where
     D: 'static + Dimension,
-    Ix0: DimMax<D>,
+    D: DimMax<Ix0>,
+    T: Float,
 {
-    type Output = VarDiff<<Ix0 as DimMax<D>>::Output>;
+    type Output = VarDiff<<D as DimMax<Ix0>>::Output, T>;
 
-    fn add(self, rhs: VarDiff<D>) -> Self::Output {
-        crate::full((), self) + rhs
+    fn add(self, rhs: T) -> Self::Output {
+        self + Var::leaf(arr0(rhs))
     }
 }
 
-impl<D> Sub<VarDiff<D>> for f32
+impl<D, T> Sub<T> for VarDiff<D, T>
 where
     D: 'static + Dimension,
-    Ix0: DimMax<D>,
+    D: DimMax<Ix0>,
+    T: Float,
 {
-    type Output = VarDiff<<Ix0 as DimMax<D>>::Output>;
+    type Output = VarDiff<<D as DimMax<Ix0>>::Output, T>;
 
-    fn sub(self, rhs: VarDiff<D>) -> Self::Output {
-        crate::full((), self) - rhs
+    fn sub(self, rhs: T) -> Self::Output {
+        self - Var::leaf(arr0(rhs))
     }
 }
 
-impl<D> Mul<VarDiff<D>> for f32
+impl<D, T> Mul<T> for VarDiff<D, T>
 where
     D: 'static + Dimension,
-    Ix0: DimMax<D>,
+    D: DimMax<Ix0>,
+    T: Float,
 {
-    type Output = VarDiff<<Ix0 as DimMax<D>>::Output>;
+    type Output = VarDiff<<D as DimMax<Ix0>>::Output, T>;
 
-    fn mul(self, rhs: VarDiff<D>) -> Self::Output {
-        crate::full((), self) * rhs
+    fn mul(self, rhs: T) -> Self::Output {
+        self * Var::leaf(arr0(rhs))
     }
 }
 
-impl<D> Div<VarDiff<D>> for f32
+impl<D, T> Div<T> for VarDiff<D, T>
 where
     D: 'static + Dimension,
-    Ix0: DimMax<D>,
+    D: DimMax<Ix0>,
+    T: Float,
 {
-    type Output = VarDiff<<Ix0 as DimMax<D>>::Output>;
+    type Output = VarDiff<<D as DimMax<Ix0>>::Output, T>;
 
-    fn div(self, rhs: VarDiff<D>) -> Self::Output {
-        crate::full((), self) / rhs
+    fn div(self, rhs: T) -> Self::Output {
+        self / Var::leaf(arr0(rhs))
     }
 }
 
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Scalar - VarDiff ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+macro_rules! impl_scalar_vardiff_ops {
+    ($($t:ty),*) => {$(
+        impl<D> Add<VarDiff<D, $t>> for $t
+        where
+            D: 'static + Dimension,
+            Ix0: DimMax<D>,
+        {
+            type Output = VarDiff<<Ix0 as DimMax<D>>::Output, $t>;
+
+            fn add(self, rhs: VarDiff<D, $t>) -> Self::Output {
+                Var::leaf(arr0(self)) + rhs
+            }
+        }
+
+        impl<D> Sub<VarDiff<D, $t>> for $t
+        where
+            D: 'static + Dimension,
+            Ix0: DimMax<D>,
+        {
+            type Output = VarDiff<<Ix0 as DimMax<D>>::Output, $t>;
+
+            fn sub(self, rhs: VarDiff<D, $t>) -> Self::Output {
+                Var::leaf(arr0(self)) - rhs
+            }
+        }
+
+        impl<D> Mul<VarDiff<D, $t>> for $t
+        where
+            D: 'static + Dimension,
+            Ix0: DimMax<D>,
+        {
+            type Output = VarDiff<<Ix0 as DimMax<D>>::Output, $t>;
+
+            fn mul(self, rhs: VarDiff<D, $t>) -> Self::Output {
+                Var::leaf(arr0(self)) * rhs
+            }
+        }
+
+        impl<D> Div<VarDiff<D, $t>> for $t
+        where
+            D: 'static + Dimension,
+            Ix0: DimMax<D>,
+        {
+            type Output = VarDiff<<Ix0 as DimMax<D>>::Output, $t>;
+
+            fn div(self, rhs: VarDiff<D, $t>) -> Self::Output {
+                Var::leaf(arr0(self)) / rhs
+            }
+        }
+    )*};
+}
+
+impl_scalar_vardiff_ops!(f32, f64, f16, bf16);
+
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Negation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
-impl<D> Neg for VarDiff<D>
+impl<D, T> Neg for VarDiff<D, T>
 where
     D: 'static + Dimension,
+    T: Float,
 {
-    type Output = VarDiff<D>;
+    type Output = VarDiff<D, T>;
 
     fn neg(self) -> Self::Output {
         let grad = Rc::new(Gradient::ndarray_zeros(self.grad.shape()));
@@ -880,33 +2075,35 @@ where
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Addition ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
-impl<D, E> Add<Var<E>> for VarDiff<D>
+impl<D, E, T> Add<Var<E, T>> for VarDiff<D, T>
 where
     D: 'static + Dimension + DimMax<E>,
     E: 'static + Dimension,
+    T: Float,
 {
-    type Output = VarDiff<<D as DimMax<E>>::Output>;
+    type Output = VarDiff<<D as DimMax<E>>::Output, T>;
 
-    fn add(self, rhs: Var<E>) -> Self::Output {
+    fn add(self, rhs: Var<E, T>) -> Self::Output {
         let grad = Rc::new(Gradient::from_ndarray(cobroadcasted_zeros(
             &self.var.data.borrow(),
             &rhs.data.borrow(),
         )));
-        let op = AdditionBackwardLeft::<D, E>::new(self.grad, grad.clone());
+        let op = AdditionBackwardLeft::<D, E, T>::new(self.grad, grad.clone());
         let var = self.var.add(rhs);
 
         VarDiff::node(var, grad.clone(), (Rc::new(op), grad), self.history)
     }
 }
 
-impl<D, E> Add<VarDiff<E>> for VarDiff<D>
+impl<D, E, T> Add<VarDiff<E, T>> for VarDiff<D, T>
 where
     D: 'static + Dimension + DimMax<E>,
     E: 'static + Dimension,
+    T: Float,
 {
-    type Output = VarDiff<<D as DimMax<E>>::Output>;
+    type Output = VarDiff<<D as DimMax<E>>::Output, T>;
 
-    fn add(mut self, rhs: VarDiff<E>) -> Self::Output {
+    fn add(mut self, rhs: VarDiff<E, T>) -> Self::Output {
         self.history.merge(rhs.history);
 
         let grad = Rc::new(Gradient::from_ndarray(cobroadcasted_zeros(
@@ -925,33 +2122,35 @@ where
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Subtraction ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
-impl<D, E> Sub<Var<E>> for VarDiff<D>
+impl<D, E, T> Sub<Var<E, T>> for VarDiff<D, T>
 where
     D: 'static + Dimension + DimMax<E>,
     E: 'static + Dimension,
+    T: Float,
 {
-    type Output = VarDiff<<D as DimMax<E>>::Output>;
+    type Output = VarDiff<<D as DimMax<E>>::Output, T>;
 
-    fn sub(self, rhs: Var<E>) -> Self::Output {
+    fn sub(self, rhs: Var<E, T>) -> Self::Output {
         let grad = Rc::new(Gradient::from_ndarray(cobroadcasted_zeros(
             &self.var.data.borrow(),
             &rhs.data.borrow(),
         )));
-        let op = SubtractionBackwardLeft::<D, E>::new(self.grad, grad.clone());
+        let op = SubtractionBackwardLeft::<D, E, T>::new(self.grad, grad.clone());
         let var = self.var.sub(rhs);
 
         VarDiff::node(var, grad.clone(), (Rc::new(op), grad), self.history)
     }
 }
 
-impl<D, E> Sub<VarDiff<E>> for VarDiff<D>
+impl<D, E, T> Sub<VarDiff<E, T>> for VarDiff<D, T>
 where
     D: 'static + Dimension + DimMax<E>,
     E: 'static + Dimension,
+    T: Float,
 {
-    type Output = VarDiff<<D as DimMax<E>>::Output>;
+    type Output = VarDiff<<D as DimMax<E>>::Output, T>;
 
-    fn sub(mut self, rhs: VarDiff<E>) -> Self::Output {
+    fn sub(mut self, rhs: VarDiff<E, T>) -> Self::Output {
         self.history.merge(rhs.history);
 
         let grad = Rc::new(Gradient::from_ndarray(cobroadcasted_zeros(
@@ -969,14 +2168,15 @@ where
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Multiplication ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
-impl<D, E> Mul<Var<E>> for VarDiff<D>
+impl<D, E, T> Mul<Var<E, T>> for VarDiff<D, T>
 where
     D: 'static + Dimension + DimMax<E>,
     E: 'static + Dimension,
+    T: Float,
 {
-    type Output = VarDiff<<D as DimMax<E>>::Output>;
+    type Output = VarDiff<<D as DimMax<E>>::Output, T>;
 
-    fn mul(self, rhs: Var<E>) -> Self::Output {
+    fn mul(self, rhs: Var<E, T>) -> Self::Output {
         let grad = Rc::new(Gradient::from_ndarray(cobroadcasted_zeros(
             &self.var.data.borrow(),
             &rhs.data.borrow(),
@@ -989,14 +2189,15 @@ where
     }
 }
 
-impl<D, E> Mul<VarDiff<E>> for VarDiff<D>
+impl<D, E, T> Mul<VarDiff<E, T>> for VarDiff<D, T>
 where
     D: 'static + Dimension + DimMax<E>,
     E: 'static + Dimension,
+    T: Float,
 {
-    type Output = VarDiff<<D as DimMax<E>>::Output>;
+    type Output = VarDiff<<D as DimMax<E>>::Output, T>;
 
-    fn mul(mut self, rhs: VarDiff<E>) -> Self::Output {
+    fn mul(mut self, rhs: VarDiff<E, T>) -> Self::Output {
         self.history.merge(rhs.history);
 
         let grad = Rc::new(Gradient::from_ndarray(cobroadcasted_zeros(
@@ -1015,14 +2216,15 @@ where
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Division ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
-impl<D, E> Div<Var<E>> for VarDiff<D>
+impl<D, E, T> Div<Var<E, T>> for VarDiff<D, T>
 where
     D: 'static + Dimension + DimMax<E>,
     E: 'static + Dimension,
+    T: Float,
 {
-    type Output = VarDiff<<D as DimMax<E>>::Output>;
+    type Output = VarDiff<<D as DimMax<E>>::Output, T>;
 
-    fn div(self, rhs: Var<E>) -> Self::Output {
+    fn div(self, rhs: Var<E, T>) -> Self::Output {
         let grad = Rc::new(Gradient::from_ndarray(cobroadcasted_zeros(
             &self.var.data.borrow(),
             &rhs.data.borrow(),
@@ -1035,14 +2237,15 @@ where
     }
 }
 
-impl<D, E> Div<VarDiff<E>> for VarDiff<D>
+impl<D, E, T> Div<VarDiff<E, T>> for VarDiff<D, T>
 where
     D: 'static + Dimension + DimMax<E>,
     E: 'static + Dimension,
+    T: Float,
 {
-    type Output = VarDiff<<D as DimMax<E>>::Output>;
+    type Output = VarDiff<<D as DimMax<E>>::Output, T>;
 
-    fn div(mut self, rhs: VarDiff<E>) -> Self::Output {
+    fn div(mut self, rhs: VarDiff<E, T>) -> Self::Output {
         self.history.merge(rhs.history);
 
         let grad = Rc::new(Gradient::from_ndarray(cobroadcasted_zeros(
@@ -1070,10 +2273,13 @@ where
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Matrix Multiplication ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
-impl MatMatMul<Var<Ix2>> for VarDiff<Ix2> {
-    type Output = VarDiff<Ix2>;
+impl<T> MatMatMul<Var<Ix2, T>> for VarDiff<Ix2, T>
+where
+    T: Float,
+{
+    type Output = VarDiff<Ix2, T>;
 
-    fn mm(self, rhs: Var<Ix2>) -> Self::Output {
+    fn mm(self, rhs: Var<Ix2, T>) -> Self::Output {
         let grad = Rc::new(Gradient::ndarray_zeros(DotDim::shape(
             self.var.data().raw_dim(),
             rhs.data().raw_dim(),
@@ -1085,10 +2291,13 @@ impl MatMatMul<Var<Ix2>> for VarDiff<Ix2> {
     }
 }
 
-impl MatMatMul<VarDiff<Ix2>> for VarDiff<Ix2> {
-    type Output = VarDiff<Ix2>;
+impl<T> MatMatMul<VarDiff<Ix2, T>> for VarDiff<Ix2, T>
+where
+    T: Float,
+{
+    type Output = VarDiff<Ix2, T>;
 
-    fn mm(mut self, rhs: VarDiff<Ix2>) -> Self::Output {
+    fn mm(mut self, rhs: VarDiff<Ix2, T>) -> Self::Output {
         self.history.merge(rhs.history);
 
         let grad = Rc::new(Gradient::ndarray_zeros(DotDim::shape(
@@ -1107,10 +2316,13 @@ impl MatMatMul<VarDiff<Ix2>> for VarDiff<Ix2> {
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~ Matrix Multiplication with Transposition  ~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
-impl MatMatMulT<Var<Ix2>> for VarDiff<Ix2> {
-    type Output = VarDiff<Ix2>;
+impl<T> MatMatMulT<Var<Ix2, T>> for VarDiff<Ix2, T>
+where
+    T: Float,
+{
+    type Output = VarDiff<Ix2, T>;
 
-    fn mm_t(self, rhs: Var<Ix2>) -> Self::Output {
+    fn mm_t(self, rhs: Var<Ix2, T>) -> Self::Output {
         let grad = Rc::new(Gradient::ndarray_zeros(DotDim::shape(
             self.var.data().raw_dim(),
             rhs.data.borrow().t().raw_dim(),
@@ -1122,10 +2334,13 @@ impl MatMatMulT<Var<Ix2>> for VarDiff<Ix2> {
     }
 }
 
-impl MatMatMulT<VarDiff<Ix2>> for VarDiff<Ix2> {
-    type Output = VarDiff<Ix2>;
+impl<T> MatMatMulT<VarDiff<Ix2, T>> for VarDiff<Ix2, T>
+where
+    T: Float,
+{
+    type Output = VarDiff<Ix2, T>;
 
-    fn mm_t(mut self, rhs: VarDiff<Ix2>) -> Self::Output {
+    fn mm_t(mut self, rhs: VarDiff<Ix2, T>) -> Self::Output {
         self.history.merge(rhs.history);
 
         let grad = Rc::new(Gradient::ndarray_zeros(DotDim::shape(
@@ -1144,10 +2359,13 @@ impl MatMatMulT<VarDiff<Ix2>> for VarDiff<Ix2> {
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ MatrixVectorMul ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
-impl MatVecMul<Var<Ix1>> for VarDiff<Ix2> {
-    type Output = VarDiff<Ix1>;
+impl<T> MatVecMul<Var<Ix1, T>> for VarDiff<Ix2, T>
+where
+    T: Float,
+{
+    type Output = VarDiff<Ix1, T>;
 
-    fn mv(self, rhs: Var<Ix1>) -> Self::Output {
+    fn mv(self, rhs: Var<Ix1, T>) -> Self::Output {
         let grad = Rc::new(Gradient::ndarray_zeros(DotDim::shape(
             self.var.data().raw_dim(),
             rhs.data().raw_dim(),
@@ -1159,10 +2377,13 @@ impl MatVecMul<Var<Ix1>> for VarDiff<Ix2> {
     }
 }
 
-impl MatVecMul<VarDiff<Ix1>> for VarDiff<Ix2> {
-    type Output = VarDiff<Ix1>;
+impl<T> MatVecMul<VarDiff<Ix1, T>> for VarDiff<Ix2, T>
+where
+    T: Float,
+{
+    type Output = VarDiff<Ix1, T>;
 
-    fn mv(mut self, rhs: VarDiff<Ix1>) -> Self::Output {
+    fn mv(mut self, rhs: VarDiff<Ix1, T>) -> Self::Output {
         self.history.merge(rhs.history);
 
         let grad = Rc::new(Gradient::ndarray_zeros(DotDim::shape(
@@ -1181,10 +2402,13 @@ impl MatVecMul<VarDiff<Ix1>> for VarDiff<Ix2> {
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ VectorMatrixMul ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
-impl VecMatMul<Var<Ix2>> for VarDiff<Ix1> {
-    type Output = VarDiff<Ix1>;
+impl<T> VecMatMul<Var<Ix2, T>> for VarDiff<Ix1, T>
+where
+    T: Float,
+{
+    type Output = VarDiff<Ix1, T>;
 
-    fn vm(self, rhs: Var<Ix2>) -> Self::Output {
+    fn vm(self, rhs: Var<Ix2, T>) -> Self::Output {
         let grad = Rc::new(Gradient::ndarray_zeros(DotDim::shape(
             self.var.data().raw_dim(),
             rhs.data().raw_dim(),
@@ -1196,10 +2420,13 @@ impl VecMatMul<Var<Ix2>> for VarDiff<Ix1> {
     }
 }
 
-impl VecMatMul<VarDiff<Ix2>> for VarDiff<Ix1> {
-    type Output = VarDiff<Ix1>;
+impl<T> VecMatMul<VarDiff<Ix2, T>> for VarDiff<Ix1, T>
+where
+    T: Float,
+{
+    type Output = VarDiff<Ix1, T>;
 
-    fn vm(mut self, rhs: VarDiff<Ix2>) -> Self::Output {
+    fn vm(mut self, rhs: VarDiff<Ix2, T>) -> Self::Output {
         self.history.merge(rhs.history);
 
         let grad = Rc::new(Gradient::ndarray_zeros(DotDim::shape(
@@ -1218,10 +2445,13 @@ impl VecMatMul<VarDiff<Ix2>> for VarDiff<Ix1> {
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ VectorVectorMul ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
-impl VecVecMul<Var<Ix1>> for VarDiff<Ix1> {
-    type Output = VarDiff<Ix0>;
+impl<T> VecVecMul<Var<Ix1, T>> for VarDiff<Ix1, T>
+where
+    T: Float,
+{
+    type Output = VarDiff<Ix0, T>;
 
-    fn vv(self, rhs: Var<Ix1>) -> Self::Output {
+    fn vv(self, rhs: Var<Ix1, T>) -> Self::Output {
         let grad = Rc::new(Gradient::from_ndarray(Array::zeros(())));
         let op = VectorVectorMulBackwardUnary::new(rhs.data.clone(), self.grad, grad.clone());
         let var = self.var.vv(rhs);
@@ -1230,10 +2460,13 @@ impl VecVecMul<Var<Ix1>> for VarDiff<Ix1> {
     }
 }
 
-impl VecVecMul<VarDiff<Ix1>> for VarDiff<Ix1> {
-    type Output = VarDiff<Ix0>;
+impl<T> VecVecMul<VarDiff<Ix1, T>> for VarDiff<Ix1, T>
+where
+    T: Float,
+{
+    type Output = VarDiff<Ix0, T>;
 
-    fn vv(mut self, rhs: VarDiff<Ix1>) -> Self::Output {
+    fn vv(mut self, rhs: VarDiff<Ix1, T>) -> Self::Output {
         self.history.merge(rhs.history);
 
         let grad = Rc::new(Gradient::from_ndarray(Array::zeros(())));
@@ -1257,18 +2490,18 @@ impl VecVecMul<VarDiff<Ix1>> for VarDiff<Ix1> {
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Concatenate ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
-impl<D> Cat<Var<D>> for VarDiff<D>
+impl<D, T> Cat<Var<D, T>> for VarDiff<D, T>
 where
     D: 'static + Dimension + RemoveAxis,
+    T: Float,
 {
-    type Output = VarDiff<D>;
+    type Output = VarDiff<D, T>;
 
-    fn cat(self, rhs: Var<D>, axis: usize) -> Self::Output {
-        let borrow = concatenate(
-            Axis(axis),
+    fn cat(self, rhs: Var<D, T>, axis: usize) -> Self::Output {
+        let borrow = concatenate_checked(
+            axis,
             &[self.var.data.borrow().view(), rhs.data.borrow().view()],
-        )
-        .unwrap();
+        );
         let grad = Rc::new(Gradient::from_ndarray(borrow));
         let op = ConcatenateBackwardLeft::new(self.grad, grad.clone(), axis);
         let var = Cat::cat(self.var, rhs, axis);
@@ -1277,20 +2510,20 @@ where
     }
 }
 
-impl<D> Cat<VarDiff<D>> for VarDiff<D>
+impl<D, T> Cat<VarDiff<D, T>> for VarDiff<D, T>
 where
     D: 'static + Dimension + RemoveAxis,
+    T: Float,
 {
-    type Output = VarDiff<D>;
+    type Output = VarDiff<D, T>;
 
-    fn cat(mut self, rhs: VarDiff<D>, axis: usize) -> Self::Output {
+    fn cat(mut self, rhs: VarDiff<D, T>, axis: usize) -> Self::Output {
         self.history.merge(rhs.history);
 
-        let borrow = concatenate(
-            Axis(axis),
+        let borrow = concatenate_checked(
+            axis,
             &[self.var.data.borrow().view(), rhs.var.data.borrow().view()],
-        )
-        .unwrap();
+        );
         let grad = Rc::new(Gradient::from_ndarray(borrow));
         let left = ConcatenateBackwardLeft::new(self.grad, grad.clone(), axis);
         let right = ConcatenateBackwardRight::new(
@@ -1308,18 +2541,18 @@ where
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Stack ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
-impl<D> Stack<Var<D>> for VarDiff<D>
+impl<D, T> Stack<Var<D, T>> for VarDiff<D, T>
 where
     D: 'static + Dimension + RemoveAxis,
+    T: Float,
 {
-    type Output = VarDiff<D::Larger>;
+    type Output = VarDiff<D::Larger, T>;
 
-    fn stack(self, rhs: Var<D>, axis: usize) -> Self::Output {
-        let borrow = stack(
-            Axis(axis),
+    fn stack(self, rhs: Var<D, T>, axis: usize) -> Self::Output {
+        let borrow = stack_checked(
+            axis,
             &[self.var.data.borrow().view(), rhs.data.borrow().view()],
-        )
-        .unwrap();
+        );
         let grad = Rc::new(Gradient::from_ndarray(borrow));
         let op = StackBackwardLeft::new(self.grad, grad.clone(), axis);
         let var = Stack::stack(self.var, rhs, axis);
@@ -1328,20 +2561,20 @@ where
     }
 }
 
-impl<D> Stack<VarDiff<D>> for VarDiff<D>
+impl<D, T> Stack<VarDiff<D, T>> for VarDiff<D, T>
 where
     D: 'static + Dimension + RemoveAxis,
+    T: Float,
 {
-    type Output = VarDiff<D::Larger>;
+    type Output = VarDiff<D::Larger, T>;
 
-    fn stack(mut self, rhs: VarDiff<D>, axis: usize) -> Self::Output {
+    fn stack(mut self, rhs: VarDiff<D, T>, axis: usize) -> Self::Output {
         self.history.merge(rhs.history);
 
-        let borrow = stack(
-            Axis(axis),
+        let borrow = stack_checked(
+            axis,
             &[self.var.data.borrow().view(), rhs.var.data.borrow().view()],
-        )
-        .unwrap();
+        );
         let grad = Rc::new(Gradient::from_ndarray(borrow));
         let left = StackBackwardLeft::new(self.grad, grad.clone(), axis);
         let right = StackBackwardRight::new(rhs.grad, grad.clone(), axis);
@@ -1352,17 +2585,162 @@ where
     }
 }
 
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Where ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl<D, M, T> Where<Var<M, bool>, Var<D, T>> for VarDiff<D, T>
+where
+    D: 'static + Dimension,
+    M: 'static + Dimension,
+    T: Float,
+{
+    type Output = VarDiff<D, T>;
+
+    fn where_(self, mask: Var<M, bool>, rhs: Var<D, T>) -> Self::Output {
+        let grad = Rc::new(Gradient::ndarray_zeros(self.grad.shape()));
+        let op = WhereBackwardLeft::new(mask.data.clone(), self.grad, grad.clone());
+        let var = Where::where_(self.var, mask, rhs);
+
+        VarDiff::node(var, grad.clone(), (Rc::new(op), grad), self.history)
+    }
+}
+
+impl<D, M, T> Where<Var<M, bool>, VarDiff<D, T>> for VarDiff<D, T>
+where
+    D: 'static + Dimension,
+    M: 'static + Dimension,
+    T: Float,
+{
+    type Output = VarDiff<D, T>;
+
+    fn where_(mut self, mask: Var<M, bool>, rhs: VarDiff<D, T>) -> Self::Output {
+        self.history.merge(rhs.history);
+
+        let grad = Rc::new(Gradient::ndarray_zeros(self.grad.shape()));
+        let op = WhereBackward::new(
+            WhereBackwardLeft::new(mask.data.clone(), self.grad, grad.clone()),
+            WhereBackwardRight::new(mask.data.clone(), rhs.grad, grad.clone()),
+        );
+        let var = Where::where_(self.var, mask, rhs.var);
+
+        VarDiff::node(var, grad.clone(), (Rc::new(op), grad), self.history)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Solve ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl<D, T> Solve<Var<D, T>> for VarDiff<D, T>
+where
+    D: 'static + Dimension,
+    T: Float,
+{
+    type Output = VarDiff<D, T>;
+
+    fn solve(self, rhs: Var<D, T>) -> Self::Output {
+        let var = Solve::solve(self.var.clone(), rhs);
+        let grad = Rc::new(Gradient::ndarray_zeros(var.data().raw_dim()));
+        let op = SolveBackwardLeft::new(self.var.data, var.data.clone(), self.grad, grad.clone());
+
+        VarDiff::node(var, grad.clone(), (Rc::new(op), grad), self.history)
+    }
+}
+
+impl<D, T> Solve<VarDiff<D, T>> for VarDiff<D, T>
+where
+    D: 'static + Dimension,
+    T: Float,
+{
+    type Output = VarDiff<D, T>;
+
+    fn solve(mut self, rhs: VarDiff<D, T>) -> Self::Output {
+        self.history.merge(rhs.history);
+
+        let var = Solve::solve(self.var.clone(), rhs.var);
+        let grad = Rc::new(Gradient::ndarray_zeros(var.data().raw_dim()));
+        let op = SolveBackward::new(
+            SolveBackwardLeft::new(
+                self.var.data.clone(),
+                var.data.clone(),
+                self.grad,
+                grad.clone(),
+            ),
+            SolveBackwardRight::new(self.var.data, rhs.grad, grad.clone()),
+        );
+
+        VarDiff::node(var, grad.clone(), (Rc::new(op), grad), self.history)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Attention ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl<T> Attention<Var<Ix4, T>, Var<Ix4, T>, AttentionMask<T>> for VarDiff<Ix4, T>
+where
+    T: Float,
+{
+    type Output = VarDiff<Ix4, T>;
+
+    fn scaled_dot_product_attention(
+        self,
+        key: Var<Ix4, T>,
+        value: Var<Ix4, T>,
+        mask: Option<AttentionMask<T>>,
+        dropout: f64,
+        status: Rc<Cell<bool>>,
+        causal: bool,
+    ) -> Self::Output {
+        VarDiff::attention(
+            (self.var, Some(self.grad)),
+            (key, None),
+            (value, None),
+            mask,
+            (dropout, status),
+            causal,
+            self.history,
+        )
+    }
+}
+
+impl<T> Attention<VarDiff<Ix4, T>, VarDiff<Ix4, T>, AttentionMask<T>> for VarDiff<Ix4, T>
+where
+    T: Float,
+{
+    type Output = VarDiff<Ix4, T>;
+
+    fn scaled_dot_product_attention(
+        mut self,
+        key: VarDiff<Ix4, T>,
+        value: VarDiff<Ix4, T>,
+        mask: Option<AttentionMask<T>>,
+        dropout: f64,
+        status: Rc<Cell<bool>>,
+        causal: bool,
+    ) -> Self::Output {
+        self.history.merge(key.history);
+        self.history.merge(value.history);
+
+        VarDiff::attention(
+            (self.var, Some(self.grad)),
+            (key.var, Some(key.grad)),
+            (value.var, Some(value.grad)),
+            mask,
+            (dropout, status),
+            causal,
+            self.history,
+        )
+    }
+}
+
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Convolution ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
-impl<D> Convolution<Var<D>, <D::Smaller as Dimension>::Smaller> for VarDiff<D>
+impl<D, T> Convolution<Var<D, T>, <D::Smaller as Dimension>::Smaller> for VarDiff<D, T>
 where
     D: 'static + Dimension + RemoveAxis,
+    T: Float,
 {
-    type Output = VarDiff<D>;
+    type Output = VarDiff<D, T>;
 
-    fn convolution<T>(self, input: Var<D>, stride: T, dilation: T, groups: usize) -> Self::Output
+    fn convolution<S>(self, input: Var<D, T>, stride: S, dilation: S, groups: usize) -> Self::Output
     where
-        T: IntoDimension<Dim = <D::Smaller as Dimension>::Smaller> + Copy,
+        S: IntoDimension<Dim = <D::Smaller as Dimension>::Smaller> + Copy,
     {
         let input_data = input.data.clone();
         let var = self.var.convolution(input, stride, dilation, groups);
@@ -1383,21 +2761,22 @@ where
     }
 }
 
-impl<D> Convolution<VarDiff<D>, <D::Smaller as Dimension>::Smaller> for VarDiff<D>
+impl<D, T> Convolution<VarDiff<D, T>, <D::Smaller as Dimension>::Smaller> for VarDiff<D, T>
 where
     D: 'static + Dimension + RemoveAxis,
+    T: Float,
 {
-    type Output = VarDiff<D>;
+    type Output = VarDiff<D, T>;
 
-    fn convolution<T>(
+    fn convolution<S>(
         mut self,
-        input: VarDiff<D>,
-        stride: T,
-        dilation: T,
+        input: VarDiff<D, T>,
+        stride: S,
+        dilation: S,
         groups: usize,
     ) -> Self::Output
     where
-        T: IntoDimension<Dim = <D::Smaller as Dimension>::Smaller> + Copy,
+        S: IntoDimension<Dim = <D::Smaller as Dimension>::Smaller> + Copy,
     {
         self.history.merge(input.history);
         let kernel_data = self.var.data.clone();
@@ -1432,9 +2811,10 @@ where
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Debug ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
-impl<D> Debug for VarDiff<D>
+impl<D, T> Debug for VarDiff<D, T>
 where
     D: 'static + Dimension,
+    T: Float,
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         Debug::fmt(&self.var, f)
@@ -1443,9 +2823,10 @@ where
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Display ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
-impl<D> Display for VarDiff<D>
+impl<D, T> Display for VarDiff<D, T>
 where
     D: 'static + Dimension,
+    T: Float,
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.var)